@@ -0,0 +1,14 @@
+//! Build script: places `memory.x` where `cortex-m-rt`'s `link.x` picks it
+//! up.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR not set"));
+    fs::copy("memory.x", out_dir.join("memory.x")).expect("failed to copy memory layout");
+
+    println!("cargo:rustc-link-search={}", out_dir.display());
+    println!("cargo:rerun-if-changed=memory.x");
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,11 @@
+/* STM32F407 bootloader: sectors 0-1 of flash, ahead of the boot state
+ * and the slots of boot::layout::STM32F407. Its RAM is SRAM2, which the
+ * firmware (memory/stm32f407*.x) leaves alone, so the panic and fault
+ * records the firmware keeps across resets survive the bootloader. */
+MEMORY
+{
+  FLASH (rx)   : ORIGIN = 0x08000000, LENGTH = 32K
+  RAM   (rwx)  : ORIGIN = 0x2001C000, LENGTH = 16K
+}
+
+_stack_start = ORIGIN(RAM) + LENGTH(RAM);
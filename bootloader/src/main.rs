@@ -0,0 +1,211 @@
+//! # Bootloader
+//!
+//! Sits in the first 32K of flash (`memory.x`), ahead of the boot state
+//! and the two application slots of `boot::layout::STM32F407`. At reset
+//! it:
+//!
+//! 1. listens for `imgtool upload` on USART2 (PA2 TX, PA3 RX, 115200 8N1)
+//!    for `WINDOW_MS`; once a host has spoken it takes requests until the
+//!    host asks to boot, writing new images to the slot not in use,
+//! 2. decides from the journal in the state sector which image to boot,
+//!    reverting a trial image that used up its `MAX_ATTEMPTS` boots
+//!    without confirming itself, or that no longer checks out, and
+//!    recording an attempt before booting a trial,
+//! 3. checks the image against its header (CRC-32 and SHA-256), points
+//!    VTOR at its vector table and jumps to it.
+//!
+//! With nothing to boot it stays in update mode. Applications are built
+//! with the `slot-a` or `slot-b` feature of the firmware, packed with
+//! `imgtool pack` and confirm themselves with `slot::confirm()`.
+//!
+//! The register map, the SCB and the flash driver are the firmware's own
+//! (`src/mcu`, `src/arch`, `src/driver/flash`), included by path.
+#![no_std]
+#![no_main]
+
+use boot::image::{self, Version};
+use boot::protocol::{MAX_RESPONSE, Updater};
+use boot::state::{Decision, MAX_ATTEMPTS};
+use core::panic::PanicInfo;
+use core::ptr;
+use cortex_m::peripheral::SYST;
+use cortex_m::peripheral::syst::SystClkSource;
+use cortex_m_rt::entry;
+use link::endpoint::{Config, Endpoint, Event};
+use link::packet::MAX_PAYLOAD;
+
+// Shared with the firmware, which uses more of them
+#[allow(dead_code)]
+#[path = "../../src/mcu/mod.rs"]
+mod mcu;
+
+#[allow(dead_code)]
+#[path = "../../src/arch"]
+mod arch {
+    pub mod cortex_m4 {
+        pub mod scb;
+    }
+}
+
+#[allow(dead_code)]
+#[path = "../../src/driver"]
+mod driver {
+    pub mod flash;
+}
+
+mod serial;
+
+use arch::cortex_m4::scb::SCB;
+use driver::flash::Flash;
+use driver::flash::boot::{Adapter, Journal, LAYOUT, Record, Slot, Storage};
+use driver::flash::v1::InternalFlash;
+use mcu::device::{self, PeripheralAccess};
+use serial::Serial;
+
+/// Version reported to `imgtool`
+const VERSION: Version = Version::new(0, 1, 0);
+
+const BAUDRATE: u32 = 115_200;
+
+/// Time after reset for a host to make itself known
+const WINDOW_MS: u64 = 500;
+
+/// Milliseconds since reset, counted by polling the SysTick wrap flag. It
+/// falls behind while flash is erased, which only makes the window and the
+/// link timeouts longer.
+struct Clock {
+    syst: SYST,
+    ms: u64,
+}
+
+impl Clock {
+    fn start(mut syst: SYST) -> Self {
+        syst.set_clock_source(SystClkSource::Core);
+        syst.set_reload(device::HSI_HZ / 1000 - 1);
+        syst.clear_current();
+        syst.enable_counter();
+        Self { syst, ms: 0 }
+    }
+
+    fn now_ms(&mut self) -> u64 {
+        if self.syst.has_wrapped() {
+            self.ms += 1;
+        }
+        self.ms
+    }
+
+    fn stop(&mut self) {
+        self.syst.disable_counter();
+        self.syst.clear_current();
+    }
+}
+
+/// Take requests from the host until it asks to boot, or until `deadline`
+/// if no request has come by then
+fn serve<S: Storage>(
+    link: &mut Endpoint<Serial>,
+    clock: &mut Clock,
+    storage: &mut S,
+    journal: &mut Journal,
+    deadline: Option<u64>,
+) {
+    let mut updater = Updater::new(LAYOUT, VERSION);
+    let mut request = [0; MAX_PAYLOAD];
+    let mut response = [0; MAX_RESPONSE];
+    // Length of a response waiting for the link to be free
+    let mut reply = None;
+    let mut spoken = false;
+    loop {
+        let now = clock.now_ms();
+        let mut len = 0;
+        if let Some(Event::Received(bytes)) = link.poll(now) {
+            len = bytes.len();
+            request[..len].copy_from_slice(bytes);
+        }
+        if len > 0 {
+            spoken = true;
+            reply = Some(updater.handle(storage, journal, &request[..len], &mut response));
+        }
+        if let Some(len) = reply
+            && !link.is_pending()
+        {
+            let _ = link.send(&response[..len], now);
+            reply = None;
+        }
+        // The answer to Boot goes out before the link is dropped
+        let idle = reply.is_none() && !link.is_pending();
+        if updater.boot_requested() && idle {
+            return;
+        }
+        if !spoken && deadline.is_some_and(|deadline| now >= deadline) {
+            return;
+        }
+    }
+}
+
+/// Slot of the image to boot, after recording the reverts and the attempt
+/// the decision calls for. None if there is nothing to boot, or the
+/// journal cannot be written and a trial image would boot uncounted.
+fn choose<S: Storage>(storage: &mut S, journal: &mut Journal) -> Option<Slot> {
+    loop {
+        let decision = journal.state().decide(MAX_ATTEMPTS, |slot| {
+            image::verify(storage, LAYOUT.slot(slot)).is_ok()
+        });
+        match decision {
+            Decision::Revert(slot) => journal.append(storage, Record::Revert(slot)).ok()?,
+            Decision::Trial(slot) => {
+                journal.append(storage, Record::Attempt(slot)).ok()?;
+                return Some(slot);
+            }
+            Decision::Boot(slot) => return Some(slot),
+            Decision::Wait => return None,
+        }
+    }
+}
+
+/// Hand the core over to the image of `slot` as it would be out of reset:
+/// peripherals the bootloader used turned off, VTOR on the vector table of
+/// the image, and the stack pointer and reset vector taken from it
+fn start(slot: Slot, link: &mut Endpoint<Serial>, clock: &mut Clock) -> ! {
+    let vector_table = LAYOUT.image_address(slot);
+    link.port().deinit();
+    clock.stop();
+    let _ = InternalFlash::new().uninitialize();
+    unsafe {
+        ptr::write_volatile(&mut (*SCB::ptr_mut()).vtor, vector_table);
+        cortex_m::asm::dsb();
+        cortex_m::asm::isb();
+        cortex_m::asm::bootload(vector_table as *const u32)
+    }
+}
+
+#[entry]
+fn main() -> ! {
+    let Some(peripherals) = cortex_m::Peripherals::take() else {
+        cortex_m::peripheral::SCB::sys_reset();
+    };
+    let mut clock = Clock::start(peripherals.SYST);
+    let mut flash = InternalFlash::new();
+    let mut storage = Adapter(&mut flash);
+    // Reading the internal flash does not fail
+    let Ok(mut journal) = Journal::open(&mut storage, LAYOUT.state) else {
+        cortex_m::peripheral::SCB::sys_reset();
+    };
+    let mut link = Endpoint::new(Serial::init(BAUDRATE), Config::default());
+
+    let mut deadline = Some(WINDOW_MS);
+    loop {
+        serve(&mut link, &mut clock, &mut storage, &mut journal, deadline);
+        if let Some(slot) = choose(&mut storage, &mut journal) {
+            start(slot, &mut link, &mut clock);
+        }
+        // Nothing to boot: wait for an image
+        deadline = None;
+    }
+}
+
+/// Start over from reset
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    cortex_m::peripheral::SCB::sys_reset()
+}
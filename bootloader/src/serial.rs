@@ -0,0 +1,113 @@
+//! Polled USART2 on PA2 (TX) and PA3 (RX), alternate function 7, 8N1 on
+//! the 16 MHz HSI: the port of the link to `imgtool upload`.
+
+use crate::mcu::device::{self, PeripheralAccess, gpio, rcc, usart};
+use core::ptr;
+use link::endpoint::Port;
+
+const TX_PIN: u32 = 2;
+const RX_PIN: u32 = 3;
+const AF_USART2: u32 = 7;
+
+fn rcc() -> *mut rcc::RegisterBlock {
+    device::RCC_BASEADDR as *mut rcc::RegisterBlock
+}
+
+fn regs() -> *mut usart::RegisterBlock {
+    usart::USART2::ptr_mut()
+}
+
+pub struct Serial;
+
+impl Serial {
+    pub fn init(baudrate: u32) -> Self {
+        let rcc = rcc();
+        let port = gpio::GPIOA::ptr_mut();
+        let usart = regs();
+        unsafe {
+            let v = ptr::read_volatile(&(*rcc).ahb1enr);
+            ptr::write_volatile(&mut (*rcc).ahb1enr, v | rcc::AHB1ENR_GPIOAEN_MASK);
+            let v = ptr::read_volatile(&(*rcc).apb1enr);
+            ptr::write_volatile(&mut (*rcc).apb1enr, v | rcc::APB1ENR_USART2EN_MASK);
+
+            // Alternate function mode, RX pulled up so an open line idles
+            let pins = (0b11 << (TX_PIN * 2)) | (0b11 << (RX_PIN * 2));
+            let v = ptr::read_volatile(&(*port).moder) & !pins;
+            ptr::write_volatile(
+                &mut (*port).moder,
+                v | (0b10 << (TX_PIN * 2)) | (0b10 << (RX_PIN * 2)),
+            );
+            let v = ptr::read_volatile(&(*port).pupdr) & !(0b11 << (RX_PIN * 2));
+            ptr::write_volatile(&mut (*port).pupdr, v | (0b01 << (RX_PIN * 2)));
+            let afs = (0xF << (TX_PIN * 4)) | (0xF << (RX_PIN * 4));
+            let v = ptr::read_volatile(&(*port).afrl) & !afs;
+            ptr::write_volatile(
+                &mut (*port).afrl,
+                v | (AF_USART2 << (TX_PIN * 4)) | (AF_USART2 << (RX_PIN * 4)),
+            );
+
+            // Oversampling by 16: BRR = usartdiv * 16, rounded
+            let brr = (device::HSI_HZ + baudrate / 2) / baudrate;
+            ptr::write_volatile(&mut (*usart).brr, brr);
+            ptr::write_volatile(
+                &mut (*usart).cr1,
+                usart::CR1_UE_MASK | usart::CR1_TE_MASK | usart::CR1_RE_MASK,
+            );
+        }
+        Self
+    }
+
+    /// Wait for the last byte to go out, then put the USART, the pins and
+    /// their clocks back as they are out of reset for the application
+    pub fn deinit(&mut self) {
+        let rcc = rcc();
+        let port = gpio::GPIOA::ptr_mut();
+        let usart = regs();
+        unsafe {
+            while ptr::read_volatile(&(*usart).sr) & usart::SR_TC_MASK == 0 {}
+            ptr::write_volatile(&mut (*usart).cr1, 0);
+            ptr::write_volatile(&mut (*usart).brr, 0);
+
+            let pins = (0b11 << (TX_PIN * 2)) | (0b11 << (RX_PIN * 2));
+            let v = ptr::read_volatile(&(*port).moder);
+            ptr::write_volatile(&mut (*port).moder, v & !pins);
+            let v = ptr::read_volatile(&(*port).pupdr);
+            ptr::write_volatile(&mut (*port).pupdr, v & !pins);
+            let afs = (0xF << (TX_PIN * 4)) | (0xF << (RX_PIN * 4));
+            let v = ptr::read_volatile(&(*port).afrl);
+            ptr::write_volatile(&mut (*port).afrl, v & !afs);
+
+            let v = ptr::read_volatile(&(*rcc).apb1enr);
+            ptr::write_volatile(&mut (*rcc).apb1enr, v & !rcc::APB1ENR_USART2EN_MASK);
+            let v = ptr::read_volatile(&(*rcc).ahb1enr);
+            ptr::write_volatile(&mut (*rcc).ahb1enr, v & !rcc::AHB1ENR_GPIOAEN_MASK);
+        }
+    }
+}
+
+impl Port for Serial {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        let usart = regs();
+        for &byte in bytes {
+            unsafe {
+                while ptr::read_volatile(&(*usart).sr) & usart::SR_TXE_MASK == 0 {}
+                ptr::write_volatile(&mut (*usart).dr, byte as u32);
+            }
+        }
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let usart = regs();
+        unsafe {
+            // Reading DR after SR also clears an overrun; the link
+            // retransmits what it lost
+            let sr = ptr::read_volatile(&(*usart).sr);
+            if sr & (usart::SR_RXNE_MASK | usart::SR_ORE_MASK) == 0 {
+                return None;
+            }
+            let byte = ptr::read_volatile(&(*usart).dr) as u8;
+            (sr & usart::SR_RXNE_MASK != 0).then_some(byte)
+        }
+    }
+}
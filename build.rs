@@ -0,0 +1,41 @@
+//! Build script: selects the linker memory layout of the MCU being built and
+//! places it where `cortex-m-rt`'s `link.x` picks it up as `memory.x`.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Memory layout file in `memory/` for the enabled MCU feature
+fn memory_layout() -> &'static str {
+    let has = |feature: &str| env::var_os(format!("CARGO_FEATURE_{feature}")).is_some();
+    if has("SLOT_A") {
+        "stm32f407-slot-a.x"
+    } else if has("SLOT_B") {
+        "stm32f407-slot-b.x"
+    } else if has("STM32F103XB") {
+        "stm32f103xb.x"
+    } else if has("STM32F103") {
+        "stm32f103x8.x"
+    } else if has("STM32F401") {
+        "stm32f401.x"
+    } else if has("STM32F411") {
+        "stm32f411.x"
+    } else if has("STM32G030") {
+        "stm32g030.x"
+    } else {
+        "stm32f407.x"
+    }
+}
+
+fn main() {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR not set"));
+    let layout = memory_layout();
+    fs::copy(
+        PathBuf::from("memory").join(layout),
+        out_dir.join("memory.x"),
+    )
+    .expect("failed to copy memory layout");
+
+    println!("cargo:rustc-link-search={}", out_dir.display());
+    println!("cargo:rerun-if-changed=memory");
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -1,8 +0,0 @@
-MEMORY
-{
-  FLASH (rx)   : ORIGIN = 0x08000000, LENGTH = 1024K
-  RAM   (rwx)  : ORIGIN = 0x20000000, LENGTH = 112K
-  CCMRAM(rwx)  : ORIGIN = 0x10000000, LENGTH = 64K
-}
-
-_stack_start = ORIGIN(RAM) + LENGTH(RAM);
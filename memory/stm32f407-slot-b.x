@@ -0,0 +1,20 @@
+/* STM32F407 firmware in slot B of the bootloader (bootloader/memory.x):
+ * linked after the 512-byte image header at the start of the slot */
+MEMORY
+{
+  FLASH (rx)   : ORIGIN = 0x08080200, LENGTH = 384K - 512
+  RAM   (rwx)  : ORIGIN = 0x20000000, LENGTH = 112K
+  CCMRAM(rwx)  : ORIGIN = 0x10000000, LENGTH = 64K
+}
+
+_stack_start = ORIGIN(RAM) + LENGTH(RAM);
+
+/* Memory map for the MPU setup, see src/arch/cortex_m4/mpu.rs: all of
+ * flash, an aligned power of two the region can cover */
+_flash_start = 0x08000000;
+_flash_size = 1024K;
+_ram_start = ORIGIN(RAM);
+_ram_size = LENGTH(RAM);
+
+/* Main stack reserved at the top of RAM, with the MPU guard region below */
+_stack_size = 4K;
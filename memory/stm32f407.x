@@ -0,0 +1,17 @@
+MEMORY
+{
+  FLASH (rx)   : ORIGIN = 0x08000000, LENGTH = 1024K
+  RAM   (rwx)  : ORIGIN = 0x20000000, LENGTH = 112K
+  CCMRAM(rwx)  : ORIGIN = 0x10000000, LENGTH = 64K
+}
+
+_stack_start = ORIGIN(RAM) + LENGTH(RAM);
+
+/* Memory map for the MPU setup, see src/arch/cortex_m4/mpu.rs */
+_flash_start = ORIGIN(FLASH);
+_flash_size = LENGTH(FLASH);
+_ram_start = ORIGIN(RAM);
+_ram_size = LENGTH(RAM);
+
+/* Main stack reserved at the top of RAM, with the MPU guard region below */
+_stack_size = 4K;
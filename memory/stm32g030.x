@@ -0,0 +1,7 @@
+MEMORY
+{
+  FLASH (rx)   : ORIGIN = 0x08000000, LENGTH = 64K
+  RAM   (rwx)  : ORIGIN = 0x20000000, LENGTH = 8K
+}
+
+_stack_start = ORIGIN(RAM) + LENGTH(RAM);
@@ -1,7 +1,7 @@
 #!/bin/bash
 
 # List of host-side packages
-host_packages=("data" "libiot")
+host_packages=("boot" "data" "executor" "fault" "i2cbus" "libiot" "link" "logfmt" "modbus" "mpu" "rtos" "sched" "shell" "time" "trace" "usb")
 
 # Test a specific host package: ./run.sh test-host data
 test_host() {
@@ -20,12 +20,61 @@ build_target() {
     cargo build --target thumbv7em-none-eabihf --release
 }
 
+# Build the bootloader (bootloader/) for the STM32F407: ./run.sh build-bootloader
+build_bootloader() {
+    cargo build -p bootloader --target thumbv7em-none-eabihf --release
+}
+
+# Build the firmware linked for a bootloader slot: ./run.sh build-target-slot a
+build_target_slot() {
+    slot="$1"
+    case "$slot" in
+        a|b)
+            cargo build --target thumbv7em-none-eabihf --release --features "slot-$slot"
+            ;;
+        *)
+            echo "Unsupported slot: $slot"
+            echo "Supported: a, b"
+            exit 1
+            ;;
+    esac
+}
+
+# Reference board of each MCU, used when building by MCU name
+mcu_board() {
+    case "$1" in
+        stm32f407) echo "f4-discovery" ;;
+        stm32f401) echo "nucleo-f401re" ;;
+        stm32f411) echo "nucleo-f411re" ;;
+        stm32f103) echo "blue-pill" ;;
+        stm32g030) echo "nucleo-g030" ;;
+    esac
+}
+
+# Rust target of each MCU
+mcu_target() {
+    case "$1" in
+        stm32f103) echo "thumbv7m-none-eabi" ;;
+        stm32g030) echo "thumbv6m-none-eabi" ;;
+        *) echo "thumbv7em-none-eabihf" ;;
+    esac
+}
+
+# Rust target of each board
+board_target() {
+    case "$1" in
+        blue-pill) mcu_target stm32f103 ;;
+        nucleo-g030) mcu_target stm32g030 ;;
+        *) mcu_target stm32f407 ;;
+    esac
+}
+
 # Build target for specific MCU: ./run.sh build-target-mcu stm32f407
 build_target_mcu() {
     mcu="$1"
     case "$mcu" in
         stm32f407|stm32f401|stm32f411|stm32f103|stm32g030)
-            cargo build --target thumbv7em-none-eabihf --release --features "$mcu" --no-default-features
+            cargo build --target "$(mcu_target "$mcu")" --release --features "board-$(mcu_board "$mcu")" --no-default-features
             ;;
         *)
             echo "Unsupported MCU: $mcu"
@@ -35,6 +84,21 @@ build_target_mcu() {
     esac
 }
 
+# Build target for specific board: ./run.sh build-target-board nucleo-f401re
+build_target_board() {
+    board="$1"
+    case "$board" in
+        f4-discovery|nucleo-f401re|nucleo-f411re|blue-pill|nucleo-g030)
+            cargo build --target "$(board_target "$board")" --release --features "log,board-$board" --no-default-features
+            ;;
+        *)
+            echo "Unsupported board: $board"
+            echo "Supported: f4-discovery, nucleo-f401re, nucleo-f411re, blue-pill, nucleo-g030"
+            exit 1
+            ;;
+    esac
+}
+
 # Run embedded test: ./run.sh test-target mod
 test_target() {
     mod="$1"
@@ -67,7 +131,7 @@ run_target_mcu() {
     mcu="$1"
     case "$mcu" in
         stm32f407|stm32f401|stm32f411|stm32f103|stm32g030)
-            cargo run --target thumbv7em-none-eabihf --release --features "$mcu" --no-default-features
+            cargo run --target "$(mcu_target "$mcu")" --release --features "board-$(mcu_board "$mcu")" --no-default-features
             ;;
         *)
             echo "Unsupported MCU: $mcu"
@@ -77,6 +141,21 @@ run_target_mcu() {
     esac
 }
 
+# Run target for specific board: ./run.sh run-target-board nucleo-f401re
+run_target_board() {
+    board="$1"
+    case "$board" in
+        f4-discovery|nucleo-f401re|nucleo-f411re|blue-pill|nucleo-g030)
+            cargo run --target "$(board_target "$board")" --release --features "log,board-$board" --no-default-features
+            ;;
+        *)
+            echo "Unsupported board: $board"
+            echo "Supported: f4-discovery, nucleo-f401re, nucleo-f411re, blue-pill, nucleo-g030"
+            exit 1
+            ;;
+    esac
+}
+
 # View the disassembly of the release binary
 objdump() {
     cargo objdump --release -- "$@"
@@ -93,11 +172,15 @@ usage() {
     echo "  $0 build-host <pkg>"
     echo "  $0 build-target"
     echo "  $0 build-target-mcu <mcu>     # Build for specific MCU (stm32f407, stm32f401, stm32f411, stm32f103, stm32g030)"
+    echo "  $0 build-target-board <board> # Build for specific board (f4-discovery, nucleo-f401re, nucleo-f411re, blue-pill, nucleo-g030)"
+    echo "  $0 build-bootloader"
+    echo "  $0 build-target-slot <a|b>    # Build the firmware for a bootloader slot, to pack with imgtool"
     echo "  $0 test-target <mod>"
     echo "  $0 build-all-host"
     echo "  $0 test-all-host"
     echo "  $0 run-target"
     echo "  $0 run-target-mcu <mcu>       # Run for specific MCU (stm32f407, stm32f401, stm32f411, stm32f103, stm32g030)"
+    echo "  $0 run-target-board <board>   # Run for specific board"
     echo "  $0 objdump [args...]"
     echo "  $0 nm [args...]"
     exit 1
@@ -127,6 +210,18 @@ case "$cmd" in
         [ $# -eq 1 ] || usage
         build_target_mcu "$1"
         ;;
+    build-target-board)
+        [ $# -eq 1 ] || usage
+        build_target_board "$1"
+        ;;
+    build-bootloader)
+        [ $# -eq 0 ] || usage
+        build_bootloader
+        ;;
+    build-target-slot)
+        [ $# -eq 1 ] || usage
+        build_target_slot "$1"
+        ;;
     test-target)
         [ $# -eq 1 ] || usage
         test_target "$1"
@@ -144,6 +239,10 @@ case "$cmd" in
         [ $# -eq 1 ] || usage
         run_target_mcu "$1"
         ;;
+    run-target-board)
+        [ $# -eq 1 ] || usage
+        run_target_board "$1"
+        ;;
     objdump)
         objdump "$@"
         ;;
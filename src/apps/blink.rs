@@ -1,7 +1,6 @@
-use crate::apps::App;
-use crate::arch::cortex_m4::systick::get_ticks;
-use crate::driver::gpio::stm32f407::{GpioDriver, pins};
-use crate::driver::gpio::{Direction, Gpio};
+use crate::apps::{App, Next};
+use crate::board::{BOARD, Led};
+use crate::driver::gpio::{GpioDriver, decode_pin};
 
 const BOUNCE: [u8; 6] = [0b0001, 0b0010, 0b0100, 0b1000, 0b0100, 0b0010];
 const IN_OUT: [u8; 6] = [0b1001, 0b0110, 0b1111, 0b0110, 0b1001, 0b0000];
@@ -18,27 +17,18 @@ const SMOKE_OFF_DELAY_MS: u32 = 200;
 #[derive(Clone, Copy)]
 enum State {
     Smoke(u8),
-    SmokeDelay(u8),
-    SmokeOffDelay,
     Bounce(u8, u8),
-    BounceDelay(u8, u8),
     InOut(u8, u8),
-    InOutDelay(u8, u8),
     AllOn,
-    AllOnDelay,
     AllOff,
-    AllOffDelay,
     Twinkle(u8, u8),
-    TwinkleDelay(u8, u8),
     Chase(u8),
-    ChaseDelay(u8),
 }
 
 pub struct BlinkApp {
     gpio_driver: Option<GpioDriver<'static>>,
     initialized: bool,
     state: State,
-    last_tick: u32,
 }
 
 impl BlinkApp {
@@ -47,7 +37,6 @@ impl BlinkApp {
             gpio_driver: None,
             initialized: false,
             state: State::Smoke(0),
-            last_tick: 0,
         }
     }
 
@@ -55,12 +44,14 @@ impl BlinkApp {
         if self.initialized {
             return Ok(());
         }
-        let mut gpio = GpioDriver::new_gpiod();
+        // All board LEDs are expected on one port, the first one selects it
+        let first = BOARD.leds.first().ok_or(-1)?;
+        let (port, _) = decode_pin(first.pin)?;
+        let mut gpio = GpioDriver::new(port);
 
-        // Configure PD12..PD15 as outputs and default OFF
-        for &p in &[pins::PD12, pins::PD13, pins::PD14, pins::PD15] {
-            gpio.set_direction(p, Direction::Output)?;
-            gpio.set_output(p, false);
+        // Configure the board LEDs as outputs and default OFF
+        for led in BOARD.leds {
+            led.init(&mut gpio)?;
         }
 
         self.gpio_driver = Some(gpio);
@@ -76,151 +67,98 @@ impl BlinkApp {
         }
     }
 
-    /// Write a 4-bit pattern to PD12..PD15 in one shot using BSRR.
-    /// bit0→PD12, bit1→PD13, bit2→PD14, bit3→PD15
+    /// Write a 4-bit pattern to the board LEDs.
+    /// bit0→LED0 .. bit3→LED3; boards with fewer LEDs fold the pattern, so
+    /// LED n is on if any bit i with i % len == n is set.
     fn write_mask_4(gpio: &mut GpioDriver, mask4: u8) {
-        // Change this depending on your board's LED polarity:
-        const ACTIVE_LOW: bool = false; // set true if LED turns ON when pin = 0
-
-        let pins = [pins::PD12, pins::PD13, pins::PD14, pins::PD15];
-        // Build set/clear masks (we still call per-pin BSRR writes; driver is fast)
-        for (i, &p) in pins.iter().enumerate() {
-            let want_on = ((mask4 >> i) & 1) != 0;
-            let level = if ACTIVE_LOW { !want_on } else { want_on };
-            gpio.set_output(p, level);
+        let leds: &[Led] = BOARD.leds;
+        for (n, led) in leds.iter().enumerate() {
+            let want_on = (0..4).any(|i| i % leds.len() == n && ((mask4 >> i) & 1) != 0);
+            led.set(gpio, want_on);
         }
     }
 
-    pub fn tick(&mut self) {
+    /// Show the current step of the pattern and move to the next one.
+    /// Returns how long the step stays on the LEDs.
+    pub fn tick(&mut self) -> Next {
         if !self.initialized {
-            return;
+            return Next::Done;
         }
-        if let Some(ref mut gpio) = self.gpio_driver {
-            match self.state {
-                State::Smoke(step) => {
-                    if step < 4 {
-                        for j in 0..4 {
-                            Self::write_mask_4(gpio, if j == step { 1 << j } else { 0 });
-                        }
-                        self.last_tick = get_ticks();
-                        self.state = State::SmokeDelay(step);
-                    } else {
-                        Self::write_mask_4(gpio, 0);
-                        self.last_tick = get_ticks();
-                        self.state = State::SmokeOffDelay;
-                    }
-                }
-                State::SmokeDelay(step) => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= SMOKE_DELAY_MS {
-                        self.state = State::Smoke(step + 1);
-                    }
-                }
-                State::SmokeOffDelay => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= SMOKE_OFF_DELAY_MS {
-                        self.state = State::Bounce(0, 0);
-                    }
-                }
-                State::Bounce(count, step) => {
-                    let m = BOUNCE[step as usize];
-                    Self::write_mask_4(gpio, m);
-                    self.last_tick = get_ticks();
-                    self.state = State::BounceDelay(count, step);
-                }
-                State::BounceDelay(count, step) => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= MED_MS {
-                        let next_step = step + 1;
-                        if next_step >= BOUNCE.len() as u8 {
-                            let next_count = count + 1;
-                            if next_count >= 4 {
-                                self.state = State::InOut(0, 0);
-                            } else {
-                                self.state = State::Bounce(next_count, 0);
-                            }
-                        } else {
-                            self.state = State::Bounce(count, next_step);
-                        }
-                    }
-                }
-                State::InOut(count, step) => {
-                    let m = IN_OUT[step as usize];
-                    Self::write_mask_4(gpio, m);
-                    self.last_tick = get_ticks();
-                    self.state = State::InOutDelay(count, step);
-                }
-                State::InOutDelay(count, step) => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= FAST_MS {
-                        let next_step = step + 1;
-                        if next_step >= IN_OUT.len() as u8 {
-                            let next_count = count + 1;
-                            if next_count >= 6 {
-                                self.state = State::AllOn;
-                            } else {
-                                self.state = State::InOut(next_count, 0);
-                            }
-                        } else {
-                            self.state = State::InOut(count, next_step);
-                        }
-                    }
-                }
-                State::AllOn => {
-                    Self::write_mask_4(gpio, 0b1111);
-                    self.last_tick = get_ticks();
-                    self.state = State::AllOnDelay;
-                }
-                State::AllOnDelay => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= SLOW_MS {
-                        self.state = State::AllOff;
-                    }
-                }
-                State::AllOff => {
-                    Self::write_mask_4(gpio, 0b0000);
-                    self.last_tick = get_ticks();
-                    self.state = State::AllOffDelay;
-                }
-                State::AllOffDelay => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= MED_MS {
-                        self.state = State::Twinkle(0, 0);
-                    }
-                }
-                State::Twinkle(count, step) => {
-                    let m = TWINKLE[step as usize];
-                    Self::write_mask_4(gpio, m);
-                    self.last_tick = get_ticks();
-                    self.state = State::TwinkleDelay(count, step);
-                }
-                State::TwinkleDelay(count, step) => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= FAST_MS {
-                        let next_step = step + 1;
-                        if next_step >= TWINKLE.len() as u8 {
-                            let next_count = count + 1;
-                            if next_count >= 6 {
-                                self.state = State::Chase(0);
-                            } else {
-                                self.state = State::Twinkle(next_count, 0);
-                            }
-                        } else {
-                            self.state = State::Twinkle(count, next_step);
-                        }
-                    }
-                }
-                State::Chase(step) => {
-                    let m = CHASE[step as usize];
-                    Self::write_mask_4(gpio, m);
-                    self.last_tick = get_ticks();
-                    self.state = State::ChaseDelay(step);
-                }
-                State::ChaseDelay(step) => {
-                    if get_ticks().wrapping_sub(self.last_tick) >= FAST_MS {
-                        let next_step = step + 1;
-                        if next_step >= CHASE.len() as u8 {
-                            self.state = State::Bounce(0, 0);
-                        } else {
-                            self.state = State::Chase(next_step);
-                        }
+        let Some(ref mut gpio) = self.gpio_driver else {
+            return Next::Done;
+        };
+        let delay_ms = match self.state {
+            State::Smoke(step) => {
+                if step < 4 {
+                    for j in 0..4 {
+                        Self::write_mask_4(gpio, if j == step { 1 << j } else { 0 });
                     }
+                    self.state = State::Smoke(step + 1);
+                    SMOKE_DELAY_MS
+                } else {
+                    Self::write_mask_4(gpio, 0);
+                    self.state = State::Bounce(0, 0);
+                    SMOKE_OFF_DELAY_MS
                 }
             }
-        }
+            State::Bounce(count, step) => {
+                Self::write_mask_4(gpio, BOUNCE[step as usize]);
+                let next_step = step + 1;
+                self.state = if next_step < BOUNCE.len() as u8 {
+                    State::Bounce(count, next_step)
+                } else if count + 1 < 4 {
+                    State::Bounce(count + 1, 0)
+                } else {
+                    State::InOut(0, 0)
+                };
+                MED_MS
+            }
+            State::InOut(count, step) => {
+                Self::write_mask_4(gpio, IN_OUT[step as usize]);
+                let next_step = step + 1;
+                self.state = if next_step < IN_OUT.len() as u8 {
+                    State::InOut(count, next_step)
+                } else if count + 1 < 6 {
+                    State::InOut(count + 1, 0)
+                } else {
+                    State::AllOn
+                };
+                FAST_MS
+            }
+            State::AllOn => {
+                Self::write_mask_4(gpio, 0b1111);
+                self.state = State::AllOff;
+                SLOW_MS
+            }
+            State::AllOff => {
+                Self::write_mask_4(gpio, 0b0000);
+                self.state = State::Twinkle(0, 0);
+                MED_MS
+            }
+            State::Twinkle(count, step) => {
+                Self::write_mask_4(gpio, TWINKLE[step as usize]);
+                let next_step = step + 1;
+                self.state = if next_step < TWINKLE.len() as u8 {
+                    State::Twinkle(count, next_step)
+                } else if count + 1 < 6 {
+                    State::Twinkle(count + 1, 0)
+                } else {
+                    State::Chase(0)
+                };
+                FAST_MS
+            }
+            State::Chase(step) => {
+                Self::write_mask_4(gpio, CHASE[step as usize]);
+                let next_step = step + 1;
+                self.state = if next_step < CHASE.len() as u8 {
+                    State::Chase(next_step)
+                } else {
+                    State::Bounce(0, 0)
+                };
+                FAST_MS
+            }
+        };
+        Next::After(delay_ms)
     }
 }
 
@@ -228,8 +166,8 @@ impl App for BlinkApp {
     fn init(&mut self) -> Result<(), i32> {
         self.init()
     }
-    fn loop_step(&mut self) {
-        self.tick();
+    fn loop_step(&mut self) -> Next {
+        self.tick()
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::apps::App;
+use crate::apps::{App, Next};
 
 pub struct EmptyApp {
     initialized: bool,
@@ -21,14 +21,16 @@ impl EmptyApp {
         Ok(())
     }
 
-    /// Non-blocking tick that does nothing useful (just idles).
-    pub fn tick(&mut self) {
+    /// Non-blocking tick that does nothing useful; the app never needs to
+    /// run again.
+    pub fn tick(&mut self) -> Next {
         // If not initialized yet, try to init.
         if !self.initialized {
             let _ = self.init();
         }
 
         // Do nothing
+        Next::Done
     }
 }
 
@@ -36,7 +38,7 @@ impl App for EmptyApp {
     fn init(&mut self) -> Result<(), i32> {
         self.init()
     }
-    fn loop_step(&mut self) {
+    fn loop_step(&mut self) -> Next {
         self.tick()
     }
 }
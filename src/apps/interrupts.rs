@@ -1,7 +1,11 @@
-use crate::arch::cortex_m4::systick;
+use crate::arch::cpu::systick;
 use cortex_m_rt::exception;
 
 #[exception]
 fn SysTick() {
     systick::increment_ticks();
+    crate::clock::on_tick();
+    crate::runtime::timer::on_tick();
+    #[cfg(feature = "rtos")]
+    crate::kernel::tick();
 }
@@ -4,53 +4,221 @@
 //! STM32 template. Applications implement the `App` trait and are registered
 //! at startup for coordinated initialization and execution.
 //!
-//! This module enables modular application development where each app can
-//! run independently while sharing system resources.
+//! The registry has a fixed capacity and needs no heap: apps live in static
+//! `AppSlot`s and are registered by name. A failing `init()` only marks that
+//! app as failed, and apps can be stopped and restarted at runtime.
+//!
+//! Registered apps are run by a cooperative scheduler: every step returns a
+//! `Next` telling it when the app wants to run again (after a delay, on its
+//! period, or when an event is signaled). Due apps run by priority and the
+//! core sleeps in the deepest low-power mode allowed by `power` while
+//! nothing is due.
 pub mod blink;
 pub mod empty;
+pub mod shell;
+#[cfg(feature = "usb-serial")]
+pub mod usb_serial;
 
-use alloc::boxed::Box;
-use alloc::vec::Vec;
+use crate::arch::cpu::systick;
+use crate::logging::{error, info, warn};
+use core::cell::{RefCell, UnsafeCell};
+use cortex_m::interrupt::{self, Mutex};
+use sched::registry::Registry;
+use sched::tick::TickSource;
 
-pub trait App {
-    fn init(&mut self) -> Result<(), i32>;
-    fn loop_step(&mut self);
-}
+pub use sched::app::App;
+pub use sched::registry::{AppId, AppState};
+pub use sched::scheduler::{Next, TaskStats};
+
+/// Maximum number of registered apps
+pub const MAX_APPS: usize = 8;
 
-static mut APPS: Option<Vec<Box<dyn App>>> = None;
+/// SysTick as the scheduler time base (1 tick = 1 ms)
+struct SysTickClock;
 
-pub fn init_app_registry() {
-    unsafe {
-        APPS = Some(Vec::new());
+impl TickSource for SysTickClock {
+    fn ticks(&self) -> u32 {
+        systick::get_ticks()
     }
 }
 
-pub fn register_app(app: Box<dyn App>) {
-    unsafe {
-        if let Some(ref mut apps) = APPS {
-            apps.push(app);
+/// Registry of the apps, only touched inside critical sections
+struct AppRegistry(Mutex<RefCell<Registry<'static, MAX_APPS>>>);
+
+// Apps hold driver handles that are not `Send`; they are only run from
+// thread mode and the registry is only accessed with interrupts disabled.
+unsafe impl Sync for AppRegistry {}
+
+static REGISTRY: AppRegistry = AppRegistry(Mutex::new(RefCell::new(Registry::new())));
+
+/// Events signaled from interrupts, handed to the registry on its next poll
+static PENDING_EVENTS: Mutex<RefCell<u32>> = Mutex::new(RefCell::new(0));
+
+fn with_registry<R>(f: impl FnOnce(&mut Registry<'static, MAX_APPS>) -> R) -> R {
+    interrupt::free(|cs| f(&mut REGISTRY.0.borrow(cs).borrow_mut()))
+}
+
+/// Static storage for one app, so apps can be registered without a heap.
+///
+/// ```ignore
+/// static BLINK: AppSlot<BlinkApp> = AppSlot::new();
+/// register_app("blink", BLINK.init(BlinkApp::new()).unwrap());
+/// ```
+pub struct AppSlot<T> {
+    app: UnsafeCell<Option<T>>,
+    used: Mutex<RefCell<bool>>,
+}
+
+// The app is only handed out once, see `init()`
+unsafe impl<T> Sync for AppSlot<T> {}
+
+impl<T> AppSlot<T> {
+    pub const fn new() -> Self {
+        Self {
+            app: UnsafeCell::new(None),
+            used: Mutex::new(RefCell::new(false)),
         }
     }
+
+    /// Move `app` into the slot. Returns `None` if the slot was already used.
+    pub fn init(&'static self, app: T) -> Option<&'static mut T> {
+        let used = interrupt::free(|cs| self.used.borrow(cs).replace(true));
+        if used {
+            return None;
+        }
+        // The flag above guarantees this is the only reference
+        let slot = unsafe { &mut *self.app.get() };
+        Some(slot.insert(app))
+    }
+}
+
+/// Register an app under `name`; it is scheduled once initialized by
+/// `init_all_apps`. Returns Err(-1) once `MAX_APPS` apps are registered.
+pub fn register_app(name: &'static str, app: &'static mut dyn App) -> Result<AppId, i32> {
+    with_registry(|registry| registry.register(name, app))
 }
 
-pub fn init_all_apps() -> Result<(), i32> {
-    unsafe {
-        if let Some(ref mut apps) = APPS {
-            for app in apps.iter_mut() {
-                app.init()?;
+/// Initialize every registered app. An app whose `init()` fails is left in
+/// `AppState::Failed` with its error code and the others still run; returns
+/// Err with the number of failed apps.
+pub fn init_all_apps() -> Result<(), usize> {
+    let mut failed = 0;
+    for id in 0..with_registry(|registry| registry.len()) {
+        if with_registry(|registry| registry.state(id)) != Some(AppState::Registered) {
+            continue;
+        }
+        let name = with_registry(|registry| registry.name(id)).unwrap_or("app");
+        match init_app(id) {
+            Ok(()) => info!("{}: started", name),
+            Err(e) => {
+                error!("{}: init failed, error {}", name, e);
+                failed += 1;
             }
         }
     }
+    if failed == 0 { Ok(()) } else { Err(failed) }
+}
+
+/// Run `init()` of an app and schedule it if that succeeds.
+fn init_app(id: AppId) -> Result<(), i32> {
+    let app = with_registry(|registry| registry.take_init(id))?;
+    // Init runs with interrupts enabled, so it can wait on SysTick; the app
+    // is out of the registry until it is handed back
+    let result = app.init();
+    with_registry(|registry| registry.complete_init(id, app, result, systick::get_ticks()))
+}
+
+/// Find a registered app by name
+pub fn find_app(name: &str) -> Option<AppId> {
+    with_registry(|registry| registry.find(name))
+}
+
+/// Name an app was registered under
+pub fn app_name(id: AppId) -> Option<&'static str> {
+    with_registry(|registry| registry.name(id))
+}
+
+/// Lifecycle state of an app
+pub fn app_state(id: AppId) -> Option<AppState> {
+    with_registry(|registry| registry.state(id))
+}
+
+/// Stop an app; it is no longer scheduled until restarted.
+/// Returns Err(-1) for an unknown app.
+pub fn stop_app(id: AppId) -> Result<(), i32> {
+    let name = with_registry(|registry| {
+        registry.stop(id)?;
+        Ok::<_, i32>(registry.name(id).unwrap_or("app"))
+    })?;
+    info!("{}: stopped", name);
     Ok(())
 }
 
-pub fn run_all_loop_steps() {
-    unsafe {
-        if let Some(ref mut apps) = APPS {
-            for app in apps.iter_mut() {
-                app.loop_step();
+/// Run `init()` of an app again and schedule it. Returns Err(-1) for an
+/// unknown app, Err(-2) when called from the app itself, or its init error.
+pub fn restart_app(id: AppId) -> Result<(), i32> {
+    let name = with_registry(|registry| registry.name(id)).unwrap_or("app");
+    let result = init_app(id);
+    match result {
+        Ok(()) => info!("{}: restarted", name),
+        Err(e) => error!("{}: restart failed, error {}", name, e),
+    }
+    result
+}
+
+/// Wake apps waiting on any of these event bits. Safe to call from
+/// interrupt handlers.
+pub fn signal_event(events: u32) {
+    interrupt::free(|cs| *PENDING_EVENTS.borrow(cs).borrow_mut() |= events);
+}
+
+/// Run-time statistics of an app
+pub fn app_stats(id: AppId) -> Option<TaskStats> {
+    with_registry(|registry| registry.stats(id))
+}
+
+/// Run at most one due app, or sleep until the next interrupt if none is due.
+pub fn run_scheduler_step() {
+    let start = systick::get_ticks();
+    let due = interrupt::free(|cs| {
+        let events = PENDING_EVENTS.borrow(cs).replace(0);
+        let mut registry = REGISTRY.0.borrow(cs).borrow_mut();
+        registry.signal(events);
+        let (id, app) = registry.take_due(start)?;
+        Some((id, registry.name(id).unwrap_or("app"), app))
+    });
+
+    match due {
+        // The step runs with interrupts enabled; the app is out of the
+        // registry until it is handed back
+        Some((id, name, app)) => {
+            let span = crate::profile::span(name);
+            let next = app.loop_step();
+            drop(span);
+            let end = systick::get_ticks();
+            // An app returning `Next::Periodic` without a period is stopped
+            if with_registry(|registry| registry.complete(id, app, start, end, next)).is_err() {
+                warn!("{}: stopped, periodic without a period", name);
             }
         }
+        None => {
+            // Interrupts stay masked between the check and the low-power
+            // mode so a new event is not slept through; a pending interrupt
+            // still wakes the core
+            interrupt::disable();
+            if interrupt::free(|cs| *PENDING_EVENTS.borrow(cs).borrow()) == 0 {
+                let idle = with_registry(|registry| registry.idle_ticks(systick::get_ticks()));
+                crate::power::idle(idle);
+            }
+            unsafe { interrupt::enable() };
+        }
+    }
+}
+
+/// Run the registered apps forever.
+pub fn run_scheduler() -> ! {
+    loop {
+        run_scheduler_step();
     }
 }
 
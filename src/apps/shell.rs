@@ -0,0 +1,380 @@
+//! # Shell App
+//!
+//! An interactive command shell on a USART, for poking at a board without
+//! reflashing it. Connect a terminal (115200 8N1 by default, VT100) and
+//! type `help`:
+//!
+//! | Command                  | Action                                    |
+//! | ------------------------ | ----------------------------------------- |
+//! | `peek <addr> [words]`    | read 32-bit words                         |
+//! | `poke <addr> <value>`    | write a 32-bit word                       |
+//! | `dump <addr> [len]`      | hex dump of memory                        |
+//! | `gpio <pin> [0\|1\|in]`  | read a pin, drive it, or make it an input |
+//! | `i2c scan`               | list and name the devices on the bus      |
+//! | `eeprom <addr> [len]`    | hex dump of the AT24 EEPROM               |
+//! | `flash <addr> [len]`     | hex dump of the SPI flash                 |
+//! | `time`                   | time since boot and RTC time              |
+//! | `apps`                   | registered apps, their state and stats    |
+//!
+//! Line editing, history and completion are in the host-tested `shell`
+//! component. Memory commands access any address they are given: an
+//! unmapped one faults, and the fault handler records it and resets.
+//!
+//! Input is polled every `POLL_MS`, which keeps up with typing; text
+//! pasted faster than that may lose bytes.
+//!
+//! ```ignore
+//! static SHELL: AppSlot<ShellApp<UsartDriver>> = AppSlot::new();
+//!
+//! let mut usart = UsartDriver::new_usart2(Config::default());
+//! usart.configure_pins(PA2, PA3)?;
+//! let devices = Devices { i2c: Some(i2c), ..Default::default() };
+//! register_app("shell", SHELL.init(ShellApp::new(usart, devices)).unwrap())?;
+//! ```
+
+use crate::apps::{self, App, AppState, MAX_APPS, Next};
+use crate::bsp::at24::{Eeprom, EepromError};
+use crate::bsp::spi_flash::SpiFlash;
+use crate::clock;
+use crate::driver::gpio::{Direction, Gpio, GpioDriver, Port, encode_pin};
+use crate::driver::i2c::{I2c, probe};
+use crate::driver::rtc;
+use crate::driver::spi::Spi;
+use crate::driver::usart::Usart;
+use ::shell::args::{Args, Error, parse_pin};
+use ::shell::command::Command;
+use ::shell::dump;
+use ::shell::session::Session;
+use core::fmt::{self, Write};
+use core::ptr;
+
+/// Longest command line
+pub const LINE_LEN: usize = 64;
+
+/// Lines kept in the history
+pub const HISTORY: usize = 8;
+
+/// Interval between checks for input
+pub const POLL_MS: u32 = 10;
+
+/// Most bytes one `dump`, `eeprom` or `flash` shows
+pub const MAX_DUMP: u32 = 256;
+
+/// Most words one `peek` reads
+pub const MAX_PEEK_WORDS: u32 = 64;
+
+const PROMPT: &str = "> ";
+
+/// An I2C bus the `i2c scan` command probes
+pub trait Probe {
+    /// Addresses that acknowledge, bit n for address n
+    fn scan(&mut self) -> Result<u128, Error>;
+
+    /// Name of the known device at `address`, if one answers there
+    fn identify(&mut self, address: u8) -> Option<&'static str>;
+}
+
+impl<T: I2c<'static>> Probe for T {
+    fn scan(&mut self) -> Result<u128, Error> {
+        probe::scan(self).map(|found| found.bits())
+    }
+
+    fn identify(&mut self, address: u8) -> Option<&'static str> {
+        probe::identify(self, address).map(|device| device.name)
+    }
+}
+
+/// A memory the `eeprom` and `flash` commands read
+pub trait Storage {
+    fn read(&mut self, address: u32, data: &mut [u8]) -> Result<(), Error>;
+}
+
+impl<I: I2c<'static>> Storage for Eeprom<'static, I> {
+    fn read(&mut self, address: u32, data: &mut [u8]) -> Result<(), Error> {
+        Eeprom::read(self, address, data).map_err(|e| match e {
+            EepromError::I2cError(code) => code,
+            EepromError::InvalidAddress => -1,
+            _ => -2,
+        })
+    }
+}
+
+impl<S: Spi<'static>> Storage for SpiFlash<'static, S> {
+    fn read(&mut self, address: u32, data: &mut [u8]) -> Result<(), Error> {
+        self.read_address(address, data)
+    }
+}
+
+/// The devices the commands work on; commands of a missing one return
+/// Err(-2)
+#[derive(Default)]
+pub struct Devices {
+    pub i2c: Option<&'static mut dyn Probe>,
+    pub eeprom: Option<&'static mut dyn Storage>,
+    pub flash: Option<&'static mut dyn Storage>,
+}
+
+static COMMANDS: &[Command<Devices>] = &[
+    Command {
+        name: "peek",
+        usage: "peek <addr> [words]",
+        help: "Read 32-bit words",
+        run: peek,
+    },
+    Command {
+        name: "poke",
+        usage: "poke <addr> <value>",
+        help: "Write a 32-bit word",
+        run: poke,
+    },
+    Command {
+        name: "dump",
+        usage: "dump <addr> [len]",
+        help: "Hex dump of memory",
+        run: dump_memory,
+    },
+    Command {
+        name: "gpio",
+        usage: "gpio <pin> [0|1|in]",
+        help: "Read, drive or release a pin, e.g. gpio PA5 1",
+        run: gpio,
+    },
+    Command {
+        name: "i2c",
+        usage: "i2c scan",
+        help: "List the devices on the I2C bus",
+        run: i2c,
+    },
+    Command {
+        name: "eeprom",
+        usage: "eeprom <addr> [len]",
+        help: "Hex dump of the EEPROM",
+        run: eeprom,
+    },
+    Command {
+        name: "flash",
+        usage: "flash <addr> [len]",
+        help: "Hex dump of the SPI flash",
+        run: flash,
+    },
+    Command {
+        name: "time",
+        usage: "time",
+        help: "Time since boot and RTC time",
+        run: time,
+    },
+    Command {
+        name: "apps",
+        usage: "apps",
+        help: "List the apps",
+        run: list_apps,
+    },
+];
+
+fn peek(_: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    let address = args.u32(1)?;
+    let words = args.u32_or(2, 1)?;
+    if !address.is_multiple_of(4) || words == 0 || words > MAX_PEEK_WORDS {
+        return Err(-1);
+    }
+    for n in 0..words {
+        let at = address.wrapping_add(n * 4);
+        if n % 4 == 0 {
+            let _ = write!(out, "{at:08x}:");
+        }
+        let value = unsafe { ptr::read_volatile(at as *const u32) };
+        let _ = write!(out, " {value:08x}");
+        if n % 4 == 3 || n == words - 1 {
+            let _ = write!(out, "\r\n");
+        }
+    }
+    Ok(())
+}
+
+fn poke(_: &mut Devices, args: &Args, _: &mut dyn Write) -> Result<(), Error> {
+    let address = args.u32(1)?;
+    let value = args.u32(2)?;
+    if !address.is_multiple_of(4) {
+        return Err(-1);
+    }
+    unsafe { ptr::write_volatile(address as *mut u32, value) };
+    Ok(())
+}
+
+/// Address and length of a dump command, the length 64 if not given
+fn range(args: &Args) -> Result<(u32, usize), Error> {
+    let address = args.u32(1)?;
+    let len = args.u32_or(2, 64)?;
+    if len == 0 || len > MAX_DUMP {
+        return Err(-1);
+    }
+    Ok((address, len as usize))
+}
+
+fn dump_memory(_: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    let (address, len) = range(args)?;
+    let mut buf = [0u8; MAX_DUMP as usize];
+    for (n, byte) in buf[..len].iter_mut().enumerate() {
+        *byte = unsafe { ptr::read_volatile(address.wrapping_add(n as u32) as *const u8) };
+    }
+    let _ = dump::hexdump(out, address, &buf[..len]);
+    Ok(())
+}
+
+fn gpio(_: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    let (port, number) = args.get(1).and_then(parse_pin).ok_or(-1)?;
+    let port = Port::from_index(port).ok_or(-1)?;
+    let pin = encode_pin(port, number);
+    let mut driver = GpioDriver::new(port);
+    match args.get(2) {
+        None => {
+            let level = driver.get_input(pin) as u8;
+            let _ = write!(out, "{level}\r\n");
+        }
+        Some("in") => driver.set_direction(pin, Direction::Input)?,
+        Some(level @ ("0" | "1")) => {
+            driver.set_direction(pin, Direction::Output)?;
+            driver.set_output(pin, level == "1");
+        }
+        Some(_) => return Err(-1),
+    }
+    Ok(())
+}
+
+fn i2c(devices: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    if args.get(1) != Some("scan") {
+        return Err(-1);
+    }
+    let bus = devices.i2c.as_mut().ok_or(-2)?;
+    let found = bus.scan()?;
+    let _ = dump::scan_grid(out, found);
+    for address in dump::SCAN_FIRST..=dump::SCAN_LAST {
+        if found & (1 << address) != 0
+            && let Some(name) = bus.identify(address)
+        {
+            let _ = write!(out, "0x{address:02x}: {name}\r\n");
+        }
+    }
+    Ok(())
+}
+
+fn read_storage(
+    storage: Option<&mut &'static mut dyn Storage>,
+    args: &Args,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    let (address, len) = range(args)?;
+    let storage = storage.ok_or(-2)?;
+    let mut buf = [0u8; MAX_DUMP as usize];
+    storage.read(address, &mut buf[..len])?;
+    let _ = dump::hexdump(out, address, &buf[..len]);
+    Ok(())
+}
+
+fn eeprom(devices: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    read_storage(devices.eeprom.as_mut(), args, out)
+}
+
+fn flash(devices: &mut Devices, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    read_storage(devices.flash.as_mut(), args, out)
+}
+
+fn time(_: &mut Devices, _: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    let micros = clock::now().as_micros();
+    let _ = write!(
+        out,
+        "uptime {}.{:06} s\r\n",
+        micros / 1_000_000,
+        micros % 1_000_000
+    );
+    write_rtc(out);
+    Ok(())
+}
+
+/// The STM32F1 RTC is a plain counter
+#[cfg(feature = "stm32f103")]
+fn write_rtc(out: &mut dyn Write) {
+    let _ = write!(out, "rtc count {}\r\n", rtc::timestamp());
+}
+
+/// The calendar RTC keeps the time of day
+#[cfg(not(feature = "stm32f103"))]
+fn write_rtc(out: &mut dyn Write) {
+    let ms = rtc::timestamp();
+    let secs = ms / 1000;
+    let _ = write!(
+        out,
+        "rtc {:02}:{:02}:{:02}.{:03}\r\n",
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60,
+        ms % 1000
+    );
+}
+
+fn list_apps(_: &mut Devices, _: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    let _ = write!(out, "id  name        state       runs  max ms\r\n");
+    for id in 0..MAX_APPS {
+        let (Some(name), Some(state)) = (apps::app_name(id), apps::app_state(id)) else {
+            continue;
+        };
+        let state = match state {
+            AppState::Registered => "registered",
+            AppState::Initialized => "running",
+            AppState::Failed(_) => "failed",
+            AppState::Stopped => "stopped",
+        };
+        let stats = apps::app_stats(id).unwrap_or_default();
+        let _ = write!(
+            out,
+            "{id:<3} {name:<11} {state:<10} {:>5} {:>7}\r\n",
+            stats.runs, stats.max_ticks
+        );
+    }
+    Ok(())
+}
+
+/// Terminal output on the USART; bytes are sent blocking
+struct Serial<'a, U>(&'a mut U);
+
+impl<U: Usart<'static>> Write for Serial<'_, U> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.send(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+pub struct ShellApp<U: Usart<'static> + 'static> {
+    usart: U,
+    devices: Devices,
+    session: Session<Devices, LINE_LEN, HISTORY>,
+}
+
+impl<U: Usart<'static> + 'static> ShellApp<U> {
+    /// A shell on `usart`, which must have its pins configured; `init()`
+    /// initializes it with its configuration
+    pub fn new(usart: U, devices: Devices) -> Self {
+        Self {
+            usart,
+            devices,
+            session: Session::new(PROMPT, COMMANDS),
+        }
+    }
+}
+
+impl<U: Usart<'static> + 'static> App for ShellApp<U> {
+    fn init(&mut self) -> Result<(), i32> {
+        self.usart.initialize(|_| {})?;
+        let mut out = Serial(&mut self.usart);
+        let _ = write!(out, "\r\nshell ready, type help\r\n");
+        self.session.start(&mut out);
+        Ok(())
+    }
+
+    fn loop_step(&mut self) -> Next {
+        while let Some(byte) = self.usart.read_byte() {
+            let mut out = Serial(&mut self.usart);
+            self.session.push(byte, &mut self.devices, &mut out);
+        }
+        Next::After(POLL_MS)
+    }
+}
@@ -0,0 +1,72 @@
+//! # USB Serial
+//!
+//! The board as a CDC-ACM serial port on the OTG_FS connector (micro-USB
+//! CN5 on the F4-Discovery; PA11/PA12 on the Nucleo morpho headers), echoing
+//! back what a terminal sends. A starting point for a console or a data link
+//! that needs no USB-UART bridge.
+//!
+//! The device is polled every millisecond, which keeps up with enumeration
+//! and with a bulk packet each way per poll.
+
+use crate::apps::{App, Next};
+use crate::driver::usb::device::UsbBus;
+use usb::cdc::{CdcAcm, FIFO_SIZE};
+use usb::descriptor::DeviceDescriptor;
+use usb::device::{Class, Device};
+
+/// pid.codes test VID/PID, to be replaced by one's own
+const VENDOR_ID: u16 = 0x1209;
+const PRODUCT_ID: u16 = 0x0001;
+
+const STRINGS: &[&str] = &["stm32-rust-template", "USB serial", "0001"];
+
+/// Draw from the bus, in mA
+const MAX_POWER_MA: u16 = 100;
+
+const POLL_MS: u32 = 1;
+
+pub struct UsbSerialApp {
+    bus: Option<UsbBus>,
+    device: Device<'static>,
+    port: CdcAcm,
+}
+
+impl UsbSerialApp {
+    pub fn new() -> Self {
+        let mut descriptor = DeviceDescriptor::composite(VENDOR_ID, PRODUCT_ID);
+        descriptor.manufacturer = 1;
+        descriptor.product = 2;
+        descriptor.serial_number = 3;
+        Self {
+            bus: None,
+            device: Device::new(descriptor, STRINGS),
+            port: CdcAcm::new(1, 2),
+        }
+    }
+}
+
+impl App for UsbSerialApp {
+    fn init(&mut self) -> Result<(), i32> {
+        let port = &mut self.port;
+        self.device
+            .configure(0, MAX_POWER_MA, |config| port.describe(config))?;
+        self.bus = Some(UsbBus::new()?);
+        Ok(())
+    }
+
+    fn loop_step(&mut self) -> Next {
+        let Some(bus) = self.bus.as_mut() else {
+            return Next::Done;
+        };
+        let classes: &mut [&mut dyn Class<UsbBus>] = &mut [&mut self.port];
+        while self.device.poll(bus, classes).is_some() {}
+
+        // Take no more than can be echoed
+        let mut buf = [0; 64];
+        let room = (FIFO_SIZE - self.port.pending()).min(buf.len());
+        let len = self.port.read(&mut buf[..room]);
+        self.port.write(&buf[..len]);
+        self.port.poll(bus);
+        Next::After(POLL_MS)
+    }
+}
@@ -0,0 +1,113 @@
+// Thread context switch for the preemptive kernel.
+//
+// PendSV runs at the lowest priority, so it only switches once no other
+// handler is active. It saves r4-r11 and EXC_RETURN below the hardware
+// frame of the outgoing thread, calls `rtos_switch_context` with that stack
+// pointer and restores the thread whose stack pointer it returns.
+//
+// ARMv6-M can only store r0-r7 with STM, so r8-r11 go through r4-r7; the
+// saved context has the same layout as on the Cortex-M4.
+
+use super::scb::*;
+use crate::mcu::device::PeripheralAccess;
+use core::ptr;
+
+/// Words of the initial thread context, see `init_stack()`
+const CONTEXT_WORDS: usize = 17;
+
+/// Smallest stack accepted for a thread, in words
+pub const MIN_STACK_WORDS: usize = 64;
+
+/// EXC_RETURN for thread mode on PSP without FPU context
+const EXC_RETURN_THREAD_PSP: u32 = 0xFFFF_FFFD;
+
+/// xPSR with only the Thumb bit set
+const XPSR_THUMB: u32 = 1 << 24;
+
+/// Build the initial context of a thread on `stack` so that the first
+/// switch to it calls `entry(arg)`, returning into `exit`. Returns the
+/// stack pointer to hand to the context switch, or Err(-1) if the stack is
+/// smaller than `MIN_STACK_WORDS`.
+pub fn init_stack(stack: &mut [u32], entry: usize, arg: usize, exit: usize) -> Result<u32, i32> {
+    if stack.len() < MIN_STACK_WORDS {
+        return Err(-1);
+    }
+    // The hardware frame, the top 8 words, must be 8-byte aligned
+    let base = stack.as_mut_ptr() as usize;
+    let top = (base + stack.len() * 4) & !7;
+    let sp = top - CONTEXT_WORDS * 4;
+    let frame = unsafe { core::slice::from_raw_parts_mut(sp as *mut u32, CONTEXT_WORDS) };
+    // r4-r11, then EXC_RETURN
+    frame[..8].fill(0);
+    frame[8] = EXC_RETURN_THREAD_PSP;
+    // Hardware frame: r0-r3, r12, lr, pc, xPSR
+    frame[9] = arg as u32;
+    frame[10..14].fill(0);
+    frame[14] = exit as u32;
+    frame[15] = entry as u32 & !1;
+    frame[16] = XPSR_THUMB;
+    Ok(sp as u32)
+}
+
+/// Give PendSV the lowest priority so switches never preempt a handler.
+pub fn init() {
+    unsafe {
+        let scb = SCB::ptr_mut();
+        // SHPR3 only supports word access
+        let shpr3 = ptr::read_volatile(&(*scb).shp[1]);
+        ptr::write_volatile(
+            &mut (*scb).shp[1],
+            (shpr3 & !SCB_SHPR3_PENDSV_MSK) | SCB_SHPR3_PENDSV_MSK,
+        );
+    }
+}
+
+/// Request a context switch, taken once no handler is active.
+pub fn pend_switch() {
+    unsafe {
+        let scb = SCB::ptr_mut();
+        ptr::write_volatile(&mut (*scb).icsr, SCB_ICSR_PENDSVSET_MSK);
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Set the process stack pointer used by threads.
+///
+/// # Safety
+/// Must not be called from a thread running on PSP.
+pub unsafe fn set_psp(sp: u32) {
+    unsafe { core::arch::asm!("msr psp, {}", in(reg) sp) };
+}
+
+core::arch::global_asm!(
+    ".section .text.PendSV,\"ax\",%progbits",
+    ".global PendSV",
+    ".type PendSV,%function",
+    ".thumb_func",
+    "PendSV:",
+    "    mrs r0, psp",
+    "    subs r0, #36",
+    "    stmia r0!, {{r4-r7}}",
+    "    mov r4, r8",
+    "    mov r5, r9",
+    "    mov r6, r10",
+    "    mov r7, r11",
+    "    stmia r0!, {{r4-r7}}",
+    "    mov r4, lr",
+    "    str r4, [r0]",
+    "    subs r0, #32",
+    "    bl rtos_switch_context",
+    "    adds r0, #16",
+    "    ldmia r0!, {{r4-r7}}",
+    "    mov r8, r4",
+    "    mov r9, r5",
+    "    mov r10, r6",
+    "    mov r11, r7",
+    "    ldmia r0!, {{r4}}",
+    "    mov lr, r4",
+    "    msr psp, r0",
+    "    subs r0, #36",
+    "    ldmia r0!, {{r4-r7}}",
+    "    bx lr",
+);
@@ -0,0 +1,58 @@
+// HardFault handler: capture the stacked frame, persist a report across
+// reset and hand it to the application on the next boot. ARMv6-M has no
+// configurable faults and no fault status or address registers, so the
+// report only carries the frame.
+
+use super::nvic::nvic_system_reset;
+use core::mem::MaybeUninit;
+use core::ptr;
+use cortex_m_rt::{ExceptionFrame as RtExceptionFrame, exception};
+use fault::record::FaultRecord;
+use fault::report::{ExceptionFrame, FaultKind, FaultReport};
+
+/// Survives a reset: placed in `.uninit`, which the runtime does not zero
+#[unsafe(link_section = ".uninit.FAULT_RECORD")]
+static mut FAULT_RECORD: MaybeUninit<FaultRecord> = MaybeUninit::uninit();
+
+/// Report recovered from the previous run by `init()`
+static mut LAST_FAULT: Option<FaultReport> = None;
+
+/// Recover the report of a fault from the previous run. Call once, early in
+/// `main`.
+pub fn init() {
+    unsafe {
+        let record = ptr::read_volatile(ptr::addr_of!(FAULT_RECORD)).assume_init();
+        LAST_FAULT = record.report();
+        ptr::write_volatile(
+            ptr::addr_of_mut!(FAULT_RECORD),
+            MaybeUninit::new(FaultRecord::empty()),
+        );
+    }
+}
+
+/// The fault that caused the last reset, if any
+pub fn last_fault() -> Option<FaultReport> {
+    unsafe { *ptr::addr_of!(LAST_FAULT) }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &RtExceptionFrame) -> ! {
+    let frame = ExceptionFrame {
+        r0: ef.r0(),
+        r1: ef.r1(),
+        r2: ef.r2(),
+        r3: ef.r3(),
+        r12: ef.r12(),
+        lr: ef.lr(),
+        pc: ef.pc(),
+        xpsr: ef.xpsr(),
+    };
+    let report = FaultReport::new(FaultKind::HardFault, frame, 0, 0, 0, 0);
+    unsafe {
+        ptr::write_volatile(
+            ptr::addr_of_mut!(FAULT_RECORD),
+            MaybeUninit::new(FaultRecord::new(&report)),
+        );
+    }
+    nvic_system_reset()
+}
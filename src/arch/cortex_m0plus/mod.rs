@@ -0,0 +1,14 @@
+//! # Cortex-M0+ Architecture Support
+//!
+//! Provides low-level system functionality and register access for the
+//! ARM Cortex-M0+ processor core used in the STM32G0 family.
+//!
+//! The M0+ (ARMv6-M) NVIC has 32 interrupts with 2 priority bits and only
+//! word access to the priority registers; there is no STIR and the SCB has
+//! no fault status registers.
+#[cfg(feature = "rtos")]
+pub mod context;
+pub mod fault;
+pub mod nvic;
+pub mod scb;
+pub mod systick;
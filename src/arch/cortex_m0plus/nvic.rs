@@ -0,0 +1,163 @@
+// NVIC (Nested Vectored Interrupt Controller) register definitions
+// Based on CMSIS Cortex-M0+ core_cm0plus.h
+
+use super::super::super::mcu::device::PeripheralAccess;
+
+// NVIC Base Address
+pub const NVIC_BASE: u32 = 0xE000E100;
+
+// NVIC Register Block
+#[repr(C)]
+pub struct Nvic {
+    pub iser: [u32; 1], // Interrupt Set Enable Register
+    _reserved0: [u32; 31],
+    pub icer: [u32; 1], // Interrupt Clear Enable Register
+    _reserved1: [u32; 31],
+    pub ispr: [u32; 1], // Interrupt Set Pending Register
+    _reserved2: [u32; 31],
+    pub icpr: [u32; 1], // Interrupt Clear Pending Register
+    _reserved3: [u32; 95],
+    pub ip: [u32; 8], // Interrupt Priority Register (word access only)
+}
+
+// NVIC Peripheral Instance
+pub struct NVIC;
+
+impl PeripheralAccess for NVIC {
+    const BASE_ADDRESS: u32 = NVIC_BASE;
+    type RegisterBlock = Nvic;
+}
+
+/// Number of external interrupts supported by the NVIC
+pub const NVIC_IRQ_COUNT: u32 = 32;
+
+/// Number of implemented priority bits (upper bits of each priority byte)
+pub const NVIC_PRIO_BITS: u32 = 2;
+
+// Helper functions for NVIC
+impl Nvic {
+    /// Enable Interrupt
+    pub fn enable_irq(&mut self, irqn: u32) {
+        if irqn < NVIC_IRQ_COUNT {
+            self.iser[0] = 1 << irqn;
+        }
+    }
+
+    /// Disable Interrupt
+    pub fn disable_irq(&mut self, irqn: u32) {
+        if irqn < NVIC_IRQ_COUNT {
+            self.icer[0] = 1 << irqn;
+        }
+    }
+
+    /// Get Interrupt Enable status
+    pub fn get_enable_irq(&self, irqn: u32) -> bool {
+        irqn < NVIC_IRQ_COUNT && (self.iser[0] & (1 << irqn)) != 0
+    }
+
+    /// Set Pending Interrupt
+    pub fn set_pending_irq(&mut self, irqn: u32) {
+        if irqn < NVIC_IRQ_COUNT {
+            self.ispr[0] = 1 << irqn;
+        }
+    }
+
+    /// Clear Pending Interrupt
+    pub fn clear_pending_irq(&mut self, irqn: u32) {
+        if irqn < NVIC_IRQ_COUNT {
+            self.icpr[0] = 1 << irqn;
+        }
+    }
+
+    /// Get Pending Interrupt
+    pub fn get_pending_irq(&self, irqn: u32) -> bool {
+        irqn < NVIC_IRQ_COUNT && (self.ispr[0] & (1 << irqn)) != 0
+    }
+
+    /// Set Interrupt Priority (raw byte, only the top 2 bits are implemented)
+    pub fn set_priority(&mut self, irqn: u32, priority: u8) {
+        if irqn < NVIC_IRQ_COUNT {
+            let index = (irqn >> 2) as usize;
+            let shift = (irqn & 0x3) * 8;
+            self.ip[index] = (self.ip[index] & !(0xFF << shift)) | ((priority as u32) << shift);
+        }
+    }
+
+    /// Get Interrupt Priority (raw byte)
+    pub fn get_priority(&self, irqn: u32) -> u8 {
+        if irqn < NVIC_IRQ_COUNT {
+            let index = (irqn >> 2) as usize;
+            let shift = (irqn & 0x3) * 8;
+            (self.ip[index] >> shift) as u8
+        } else {
+            0
+        }
+    }
+}
+
+// Global functions similar to CMSIS
+pub fn nvic_enable_irq(irqn: u32) {
+    unsafe {
+        let nvic = &mut *(NVIC_BASE as *mut Nvic);
+        nvic.enable_irq(irqn);
+    }
+}
+
+pub fn nvic_disable_irq(irqn: u32) {
+    unsafe {
+        let nvic = &mut *(NVIC_BASE as *mut Nvic);
+        nvic.disable_irq(irqn);
+    }
+}
+
+pub fn nvic_set_pending_irq(irqn: u32) {
+    unsafe {
+        let nvic = &mut *(NVIC_BASE as *mut Nvic);
+        nvic.set_pending_irq(irqn);
+    }
+}
+
+pub fn nvic_clear_pending_irq(irqn: u32) {
+    unsafe {
+        let nvic = &mut *(NVIC_BASE as *mut Nvic);
+        nvic.clear_pending_irq(irqn);
+    }
+}
+
+pub fn nvic_get_pending_irq(irqn: u32) -> bool {
+    unsafe {
+        let nvic = &*(NVIC_BASE as *const Nvic);
+        nvic.get_pending_irq(irqn)
+    }
+}
+
+pub fn nvic_set_priority(irqn: u32, priority: u32) {
+    unsafe {
+        let nvic = &mut *(NVIC_BASE as *mut Nvic);
+        nvic.set_priority(irqn, (priority as u8) << (8 - NVIC_PRIO_BITS));
+    }
+}
+
+pub fn nvic_get_priority(irqn: u32) -> u32 {
+    unsafe {
+        let nvic = &*(NVIC_BASE as *const Nvic);
+        (nvic.get_priority(irqn) >> (8 - NVIC_PRIO_BITS)) as u32
+    }
+}
+
+/// Request a system reset (CMSIS NVIC_SystemReset)
+pub fn nvic_system_reset() -> ! {
+    use super::scb::*;
+    unsafe {
+        let scb = &mut *(SCB_BASE as *mut Scb);
+        cortex_m::asm::dsb();
+        core::ptr::write_volatile(
+            &mut scb.aircr,
+            (0x5FA << SCB_AIRCR_VECTKEY_POS) | SCB_AIRCR_SYSRESETREQ_MSK,
+        );
+        cortex_m::asm::dsb();
+    }
+    loop {
+        cortex_m::asm::nop();
+    }
+}
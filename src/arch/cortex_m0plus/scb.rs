@@ -0,0 +1,121 @@
+// SCB (System Control Block) register definitions
+// Based on CMSIS Cortex-M0+ core_cm0plus.h
+
+use super::super::super::mcu::device::PeripheralAccess;
+
+// SCB Base Address
+pub const SCB_BASE: u32 = 0xE000ED00;
+
+// SCB Register Block
+#[repr(C)]
+pub struct Scb {
+    pub cpuid: u32, // CPUID Base Register
+    pub icsr: u32,  // Interrupt Control and State Register
+    pub vtor: u32,  // Vector Table Offset Register
+    pub aircr: u32, // Application Interrupt and Reset Control Register
+    pub scr: u32,   // System Control Register
+    pub ccr: u32,   // Configuration Control Register
+    _reserved0: u32,
+    pub shp: [u32; 2], // System Handlers Priority Registers (SHPR2, SHPR3, word access only)
+    pub shcsr: u32,    // System Handler Control and State Register
+}
+
+// SCB Peripheral Instance
+pub struct SCB;
+
+impl PeripheralAccess for SCB {
+    const BASE_ADDRESS: u32 = SCB_BASE;
+    type RegisterBlock = Scb;
+}
+
+// SCB CPUID Register Definitions
+pub const SCB_CPUID_IMPLEMENTER_POS: u32 = 24;
+pub const SCB_CPUID_IMPLEMENTER_MSK: u32 = 0xFF << SCB_CPUID_IMPLEMENTER_POS;
+
+pub const SCB_CPUID_VARIANT_POS: u32 = 20;
+pub const SCB_CPUID_VARIANT_MSK: u32 = 0xF << SCB_CPUID_VARIANT_POS;
+
+pub const SCB_CPUID_ARCHITECTURE_POS: u32 = 16;
+pub const SCB_CPUID_ARCHITECTURE_MSK: u32 = 0xF << SCB_CPUID_ARCHITECTURE_POS;
+
+pub const SCB_CPUID_PARTNO_POS: u32 = 4;
+pub const SCB_CPUID_PARTNO_MSK: u32 = 0xFFF << SCB_CPUID_PARTNO_POS;
+
+pub const SCB_CPUID_REVISION_POS: u32 = 0;
+pub const SCB_CPUID_REVISION_MSK: u32 = 0xF;
+
+// SCB Interrupt Control State Register Definitions
+pub const SCB_ICSR_NMIPENDSET_POS: u32 = 31;
+pub const SCB_ICSR_NMIPENDSET_MSK: u32 = 1 << SCB_ICSR_NMIPENDSET_POS;
+
+pub const SCB_ICSR_PENDSVSET_POS: u32 = 28;
+pub const SCB_ICSR_PENDSVSET_MSK: u32 = 1 << SCB_ICSR_PENDSVSET_POS;
+
+pub const SCB_ICSR_PENDSVCLR_POS: u32 = 27;
+pub const SCB_ICSR_PENDSVCLR_MSK: u32 = 1 << SCB_ICSR_PENDSVCLR_POS;
+
+pub const SCB_ICSR_PENDSTSET_POS: u32 = 26;
+pub const SCB_ICSR_PENDSTSET_MSK: u32 = 1 << SCB_ICSR_PENDSTSET_POS;
+
+pub const SCB_ICSR_PENDSTCLR_POS: u32 = 25;
+pub const SCB_ICSR_PENDSTCLR_MSK: u32 = 1 << SCB_ICSR_PENDSTCLR_POS;
+
+pub const SCB_ICSR_ISRPREEMPT_POS: u32 = 23;
+pub const SCB_ICSR_ISRPREEMPT_MSK: u32 = 1 << SCB_ICSR_ISRPREEMPT_POS;
+
+pub const SCB_ICSR_ISRPENDING_POS: u32 = 22;
+pub const SCB_ICSR_ISRPENDING_MSK: u32 = 1 << SCB_ICSR_ISRPENDING_POS;
+
+pub const SCB_ICSR_VECTPENDING_POS: u32 = 12;
+pub const SCB_ICSR_VECTPENDING_MSK: u32 = 0x1FF << SCB_ICSR_VECTPENDING_POS;
+
+pub const SCB_ICSR_VECTACTIVE_POS: u32 = 0;
+pub const SCB_ICSR_VECTACTIVE_MSK: u32 = 0x1FF;
+
+// SCB Vector Table Offset Register Definitions
+pub const SCB_VTOR_TBLOFF_POS: u32 = 8;
+pub const SCB_VTOR_TBLOFF_MSK: u32 = 0xFFFFFF << SCB_VTOR_TBLOFF_POS;
+
+// SCB Application Interrupt and Reset Control Register Definitions
+pub const SCB_AIRCR_VECTKEY_POS: u32 = 16;
+pub const SCB_AIRCR_VECTKEY_MSK: u32 = 0xFFFF << SCB_AIRCR_VECTKEY_POS;
+
+pub const SCB_AIRCR_ENDIANESS_POS: u32 = 15;
+pub const SCB_AIRCR_ENDIANESS_MSK: u32 = 1 << SCB_AIRCR_ENDIANESS_POS;
+
+pub const SCB_AIRCR_SYSRESETREQ_POS: u32 = 2;
+pub const SCB_AIRCR_SYSRESETREQ_MSK: u32 = 1 << SCB_AIRCR_SYSRESETREQ_POS;
+
+pub const SCB_AIRCR_VECTCLRACTIVE_POS: u32 = 1;
+pub const SCB_AIRCR_VECTCLRACTIVE_MSK: u32 = 1 << SCB_AIRCR_VECTCLRACTIVE_POS;
+
+// SCB System Control Register Definitions
+pub const SCB_SCR_SEVONPEND_POS: u32 = 4;
+pub const SCB_SCR_SEVONPEND_MSK: u32 = 1 << SCB_SCR_SEVONPEND_POS;
+
+pub const SCB_SCR_SLEEPDEEP_POS: u32 = 2;
+pub const SCB_SCR_SLEEPDEEP_MSK: u32 = 1 << SCB_SCR_SLEEPDEEP_POS;
+
+pub const SCB_SCR_SLEEPONEXIT_POS: u32 = 1;
+pub const SCB_SCR_SLEEPONEXIT_MSK: u32 = 1 << SCB_SCR_SLEEPONEXIT_POS;
+
+// SCB Configuration Control Register Definitions (read-only on the M0+)
+pub const SCB_CCR_STKALIGN_POS: u32 = 9;
+pub const SCB_CCR_STKALIGN_MSK: u32 = 1 << SCB_CCR_STKALIGN_POS;
+
+pub const SCB_CCR_UNALIGN_TRP_POS: u32 = 3;
+pub const SCB_CCR_UNALIGN_TRP_MSK: u32 = 1 << SCB_CCR_UNALIGN_TRP_POS;
+
+// SCB System Handler Priority Register Definitions
+pub const SCB_SHPR2_SVCALL_POS: u32 = 24;
+pub const SCB_SHPR2_SVCALL_MSK: u32 = 0xFF << SCB_SHPR2_SVCALL_POS;
+
+pub const SCB_SHPR3_SYSTICK_POS: u32 = 24;
+pub const SCB_SHPR3_SYSTICK_MSK: u32 = 0xFF << SCB_SHPR3_SYSTICK_POS;
+
+pub const SCB_SHPR3_PENDSV_POS: u32 = 16;
+pub const SCB_SHPR3_PENDSV_MSK: u32 = 0xFF << SCB_SHPR3_PENDSV_POS;
+
+// SCB System Handler Control and State Register Definitions
+pub const SCB_SHCSR_SVCALLPENDED_POS: u32 = 15;
+pub const SCB_SHCSR_SVCALLPENDED_MSK: u32 = 1 << SCB_SHCSR_SVCALLPENDED_POS;
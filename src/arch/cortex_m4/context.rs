@@ -0,0 +1,118 @@
+// Thread context switch for the preemptive kernel.
+//
+// PendSV runs at the lowest priority, so it only switches once no other
+// handler is active. It saves r4-r11 and EXC_RETURN below the hardware
+// frame of the outgoing thread, calls `rtos_switch_context` with that stack
+// pointer and restores the thread whose stack pointer it returns.
+//
+// With the FPU, a thread that used it enters PendSV with bit 4 of
+// EXC_RETURN clear and space for s0-s15/FPSCR reserved by lazy stacking;
+// s16-s31 are then saved as well, which also makes the hardware store the
+// lazily reserved registers. Threads that never used the FPU save none.
+
+use super::scb::*;
+use crate::mcu::device::PeripheralAccess;
+use core::ptr;
+
+/// Words of the initial thread context, see `init_stack()`
+const CONTEXT_WORDS: usize = 17;
+
+/// Smallest stack accepted for a thread, in words
+pub const MIN_STACK_WORDS: usize = 64;
+
+/// EXC_RETURN for thread mode on PSP without FPU context
+const EXC_RETURN_THREAD_PSP: u32 = 0xFFFF_FFFD;
+
+/// xPSR with only the Thumb bit set
+const XPSR_THUMB: u32 = 1 << 24;
+
+/// Build the initial context of a thread on `stack` so that the first
+/// switch to it calls `entry(arg)`, returning into `exit`. Returns the
+/// stack pointer to hand to the context switch, or Err(-1) if the stack is
+/// smaller than `MIN_STACK_WORDS`.
+pub fn init_stack(stack: &mut [u32], entry: usize, arg: usize, exit: usize) -> Result<u32, i32> {
+    if stack.len() < MIN_STACK_WORDS {
+        return Err(-1);
+    }
+    // The hardware frame, the top 8 words, must be 8-byte aligned
+    let base = stack.as_mut_ptr() as usize;
+    let top = (base + stack.len() * 4) & !7;
+    let sp = top - CONTEXT_WORDS * 4;
+    let frame = unsafe { core::slice::from_raw_parts_mut(sp as *mut u32, CONTEXT_WORDS) };
+    // r4-r11, then EXC_RETURN
+    frame[..8].fill(0);
+    frame[8] = EXC_RETURN_THREAD_PSP;
+    // Hardware frame: r0-r3, r12, lr, pc, xPSR
+    frame[9] = arg as u32;
+    frame[10..14].fill(0);
+    frame[14] = exit as u32;
+    frame[15] = entry as u32 & !1;
+    frame[16] = XPSR_THUMB;
+    Ok(sp as u32)
+}
+
+/// Give PendSV the lowest priority so switches never preempt a handler.
+pub fn init() {
+    unsafe {
+        let scb = SCB::ptr_mut();
+        // PendSV is exception 14, SHPR index 14 - 4
+        ptr::write_volatile(&mut (*scb).shp[10], 0xFF);
+    }
+}
+
+/// Request a context switch, taken once no handler is active.
+pub fn pend_switch() {
+    unsafe {
+        let scb = SCB::ptr_mut();
+        ptr::write_volatile(&mut (*scb).icsr, SCB_ICSR_PENDSVSET_MSK);
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Set the process stack pointer used by threads.
+///
+/// # Safety
+/// Must not be called from a thread running on PSP.
+pub unsafe fn set_psp(sp: u32) {
+    unsafe { core::arch::asm!("msr psp, {}", in(reg) sp) };
+}
+
+// The assembler only takes the VFP registers once told of the FPU
+#[cfg(not(feature = "stm32f103"))]
+core::arch::global_asm!(
+    ".fpu fpv4-sp-d16",
+    ".section .text.PendSV,\"ax\",%progbits",
+    ".global PendSV",
+    ".type PendSV,%function",
+    ".thumb_func",
+    "PendSV:",
+    "    mrs r0, psp",
+    "    tst lr, #0x10",
+    "    it eq",
+    "    vstmdbeq r0!, {{s16-s31}}",
+    "    stmdb r0!, {{r4-r11, lr}}",
+    "    bl rtos_switch_context",
+    "    ldmia r0!, {{r4-r11, lr}}",
+    "    tst lr, #0x10",
+    "    it eq",
+    "    vldmiaeq r0!, {{s16-s31}}",
+    "    msr psp, r0",
+    "    bx lr",
+);
+
+// The Cortex-M3 of the STM32F1 has no FPU
+#[cfg(feature = "stm32f103")]
+core::arch::global_asm!(
+    ".section .text.PendSV,\"ax\",%progbits",
+    ".global PendSV",
+    ".type PendSV,%function",
+    ".thumb_func",
+    "PendSV:",
+    "    mrs r0, psp",
+    "    stmdb r0!, {{r4-r11, lr}}",
+    "    bl rtos_switch_context",
+    "    ldmia r0!, {{r4-r11, lr}}",
+    "    msr psp, r0",
+    "    bx lr",
+);
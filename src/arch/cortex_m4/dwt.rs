@@ -0,0 +1,185 @@
+// DWT (Data Watchpoint and Trace) register definitions
+// Based on CMSIS Cortex-M4 core_cm4.h
+
+use super::super::super::mcu::device::PeripheralAccess;
+use core::ptr;
+
+// DWT Base Address
+pub const DWT_BASE: u32 = 0xE0001000;
+
+// Debug Exception and Monitor Control Register of CoreDebug, whose TRCENA
+// bit powers the DWT and ITM
+pub const DEMCR_ADDR: u32 = 0xE000EDFC;
+
+pub const DEMCR_TRCENA_POS: u32 = 24;
+pub const DEMCR_TRCENA_MSK: u32 = 1 << DEMCR_TRCENA_POS;
+
+// One comparator: address, ignored low address bits, and what it does
+#[repr(C)]
+pub struct Comparator {
+    pub comp: u32,     // Comparator Register
+    pub mask: u32,     // Mask Register
+    pub function: u32, // Function Register
+    _reserved: u32,
+}
+
+// DWT Register Block
+#[repr(C)]
+pub struct Dwt {
+    pub ctrl: u32,     // Control Register
+    pub cyccnt: u32,   // Cycle Count Register
+    pub cpicnt: u32,   // CPI Count Register
+    pub exccnt: u32,   // Exception Overhead Count Register
+    pub sleepcnt: u32, // Sleep Count Register
+    pub lsucnt: u32,   // LSU Count Register
+    pub foldcnt: u32,  // Folded-instruction Count Register
+    pub pcsr: u32,     // Program Counter Sample Register
+    pub comparators: [Comparator; 4],
+}
+
+// DWT Peripheral Instance
+pub struct DWT;
+
+impl PeripheralAccess for DWT {
+    const BASE_ADDRESS: u32 = DWT_BASE;
+    type RegisterBlock = Dwt;
+}
+
+// DWT Control Register Definitions
+pub const DWT_CTRL_NUMCOMP_POS: u32 = 28;
+pub const DWT_CTRL_NUMCOMP_MSK: u32 = 0xF << DWT_CTRL_NUMCOMP_POS;
+
+pub const DWT_CTRL_NOCYCCNT_POS: u32 = 25;
+pub const DWT_CTRL_NOCYCCNT_MSK: u32 = 1 << DWT_CTRL_NOCYCCNT_POS;
+
+pub const DWT_CTRL_CYCCNTENA_POS: u32 = 0;
+pub const DWT_CTRL_CYCCNTENA_MSK: u32 = 1;
+
+// DWT Mask Register Definitions
+pub const DWT_MASK_MASK_POS: u32 = 0;
+pub const DWT_MASK_MASK_MSK: u32 = 0x1F;
+
+// DWT Function Register Definitions
+pub const DWT_FUNCTION_MATCHED_POS: u32 = 24;
+pub const DWT_FUNCTION_MATCHED_MSK: u32 = 1 << DWT_FUNCTION_MATCHED_POS;
+
+pub const DWT_FUNCTION_DATAVMATCH_POS: u32 = 8;
+pub const DWT_FUNCTION_DATAVMATCH_MSK: u32 = 1 << DWT_FUNCTION_DATAVMATCH_POS;
+
+pub const DWT_FUNCTION_CYCMATCH_POS: u32 = 7;
+pub const DWT_FUNCTION_CYCMATCH_MSK: u32 = 1 << DWT_FUNCTION_CYCMATCH_POS;
+
+pub const DWT_FUNCTION_FUNCTION_POS: u32 = 0;
+pub const DWT_FUNCTION_FUNCTION_MSK: u32 = 0xF;
+
+// FUNCTION values for a data address comparator
+pub const DWT_FUNCTION_DISABLED: u32 = 0b0000;
+pub const DWT_FUNCTION_WATCH_PC: u32 = 0b0100;
+pub const DWT_FUNCTION_WATCH_READ: u32 = 0b0101;
+pub const DWT_FUNCTION_WATCH_WRITE: u32 = 0b0110;
+pub const DWT_FUNCTION_WATCH_READ_WRITE: u32 = 0b0111;
+
+/// Accesses a watchpoint halts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+    /// Instruction fetch, a hardware breakpoint on a code address
+    Execute,
+}
+
+/// Power up the DWT and ITM, needed before using either
+pub fn enable_trace() {
+    unsafe {
+        let demcr = DEMCR_ADDR as *mut u32;
+        ptr::write_volatile(demcr, ptr::read_volatile(demcr) | DEMCR_TRCENA_MSK);
+    }
+}
+
+/// Start the cycle counter from 0. Returns Err(-2) if the core has none.
+pub fn enable_cycle_counter() -> Result<(), i32> {
+    enable_trace();
+    unsafe {
+        let dwt = DWT::ptr_mut();
+        let ctrl = ptr::read_volatile(&(*dwt).ctrl);
+        if ctrl & DWT_CTRL_NOCYCCNT_MSK != 0 {
+            return Err(-2);
+        }
+        ptr::write_volatile(&mut (*dwt).cyccnt, 0);
+        ptr::write_volatile(&mut (*dwt).ctrl, ctrl | DWT_CTRL_CYCCNTENA_MSK);
+    }
+    Ok(())
+}
+
+/// Stop the cycle counter
+pub fn disable_cycle_counter() {
+    unsafe {
+        let dwt = DWT::ptr_mut();
+        let ctrl = ptr::read_volatile(&(*dwt).ctrl);
+        ptr::write_volatile(&mut (*dwt).ctrl, ctrl & !DWT_CTRL_CYCCNTENA_MSK);
+    }
+}
+
+/// Core clock cycles counted so far, wrapping at 2^32
+pub fn cycles() -> u32 {
+    unsafe { ptr::read_volatile(&(*DWT::ptr()).cyccnt) }
+}
+
+/// Number of comparators available for watchpoints
+pub fn num_comparators() -> usize {
+    let ctrl = unsafe { ptr::read_volatile(&(*DWT::ptr()).ctrl) };
+    (((ctrl & DWT_CTRL_NUMCOMP_MSK) >> DWT_CTRL_NUMCOMP_POS) as usize).min(4)
+}
+
+/// Halt the core in the debugger on `access` to the `size` bytes at
+/// `address`, using comparator `index`. `size` must be a power of two and
+/// `address` aligned to it. Returns Err(-1) for an invalid comparator,
+/// size or alignment.
+pub fn set_watchpoint(index: usize, address: u32, size: u32, access: Access) -> Result<(), i32> {
+    if index >= num_comparators() || !size.is_power_of_two() || !address.is_multiple_of(size) {
+        return Err(-1);
+    }
+    let ignored_bits = size.trailing_zeros();
+    if ignored_bits > DWT_MASK_MASK_MSK {
+        return Err(-1);
+    }
+    let function = match access {
+        Access::Read => DWT_FUNCTION_WATCH_READ,
+        Access::Write => DWT_FUNCTION_WATCH_WRITE,
+        Access::ReadWrite => DWT_FUNCTION_WATCH_READ_WRITE,
+        Access::Execute => DWT_FUNCTION_WATCH_PC,
+    };
+    enable_trace();
+    unsafe {
+        let comparator = &mut (*DWT::ptr_mut()).comparators[index];
+        ptr::write_volatile(&mut comparator.function, DWT_FUNCTION_DISABLED);
+        ptr::write_volatile(&mut comparator.comp, address);
+        ptr::write_volatile(&mut comparator.mask, ignored_bits);
+        ptr::write_volatile(&mut comparator.function, function);
+    }
+    Ok(())
+}
+
+/// Disable the watchpoint of comparator `index`. Returns Err(-1) for an
+/// invalid comparator.
+pub fn clear_watchpoint(index: usize) -> Result<(), i32> {
+    if index >= num_comparators() {
+        return Err(-1);
+    }
+    unsafe {
+        let comparator = &mut (*DWT::ptr_mut()).comparators[index];
+        ptr::write_volatile(&mut comparator.function, DWT_FUNCTION_DISABLED);
+    }
+    Ok(())
+}
+
+/// Whether the watchpoint of comparator `index` matched since last asked
+/// (reading clears the flag)
+pub fn watchpoint_matched(index: usize) -> bool {
+    if index >= num_comparators() {
+        return false;
+    }
+    let function = unsafe { ptr::read_volatile(&(*DWT::ptr()).comparators[index].function) };
+    function & DWT_FUNCTION_MATCHED_MSK != 0
+}
@@ -0,0 +1,126 @@
+// Fault handlers: capture the stacked frame and fault registers, persist a
+// report across reset and hand it to the application on the next boot.
+
+use super::nvic::nvic_system_reset;
+use super::scb::*;
+use crate::mcu::device::PeripheralAccess;
+use core::mem::MaybeUninit;
+use core::ptr;
+use cortex_m_rt::{ExceptionFrame as RtExceptionFrame, exception};
+use fault::record::FaultRecord;
+use fault::report::{ExceptionFrame, FaultKind, FaultReport};
+
+/// Survives a reset: placed in `.uninit`, which the runtime does not zero
+#[unsafe(link_section = ".uninit.FAULT_RECORD")]
+static mut FAULT_RECORD: MaybeUninit<FaultRecord> = MaybeUninit::uninit();
+
+/// Report recovered from the previous run by `init()`
+static mut LAST_FAULT: Option<FaultReport> = None;
+
+/// Enable the MemManage, BusFault and UsageFault handlers (otherwise these
+/// escalate to HardFault) and recover the report of a fault from the
+/// previous run. Call once, early in `main`.
+pub fn init() {
+    unsafe {
+        let record = ptr::read_volatile(ptr::addr_of!(FAULT_RECORD)).assume_init();
+        LAST_FAULT = record.report();
+        ptr::write_volatile(
+            ptr::addr_of_mut!(FAULT_RECORD),
+            MaybeUninit::new(FaultRecord::empty()),
+        );
+
+        let scb = SCB::ptr_mut();
+        let shcsr = ptr::read_volatile(&(*scb).shcsr);
+        ptr::write_volatile(
+            &mut (*scb).shcsr,
+            shcsr
+                | SCB_SHCSR_MEMFAULTENA_MSK
+                | SCB_SHCSR_BUSFAULTENA_MSK
+                | SCB_SHCSR_USGFAULTENA_MSK,
+        );
+    }
+}
+
+/// The fault that caused the last reset, if any
+pub fn last_fault() -> Option<FaultReport> {
+    unsafe { *ptr::addr_of!(LAST_FAULT) }
+}
+
+/// Capture the fault registers, persist the report and reset.
+fn record_and_reset(kind: FaultKind, frame: ExceptionFrame) -> ! {
+    let scb = SCB::ptr();
+    let report = unsafe {
+        FaultReport::new(
+            kind,
+            frame,
+            ptr::read_volatile(&(*scb).cfsr),
+            ptr::read_volatile(&(*scb).hfsr),
+            ptr::read_volatile(&(*scb).mmfar),
+            ptr::read_volatile(&(*scb).bfar),
+        )
+    };
+    unsafe {
+        ptr::write_volatile(
+            ptr::addr_of_mut!(FAULT_RECORD),
+            MaybeUninit::new(FaultRecord::new(&report)),
+        );
+    }
+    nvic_system_reset()
+}
+
+#[exception]
+unsafe fn HardFault(ef: &RtExceptionFrame) -> ! {
+    let frame = ExceptionFrame {
+        r0: ef.r0(),
+        r1: ef.r1(),
+        r2: ef.r2(),
+        r3: ef.r3(),
+        r12: ef.r12(),
+        lr: ef.lr(),
+        pc: ef.pc(),
+        xpsr: ef.xpsr(),
+    };
+    record_and_reset(FaultKind::HardFault, frame)
+}
+
+/// Entry from the trampolines below with the active stack pointer in r0 and
+/// the fault kind in r1.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn configurable_fault_handler(frame: *const ExceptionFrame, kind: u32) -> ! {
+    let kind = FaultKind::from_u32(kind).unwrap_or(FaultKind::HardFault);
+    record_and_reset(kind, unsafe { ptr::read(frame) })
+}
+
+// cortex-m-rt passes no frame to these handlers, so they are defined here
+// and find the frame on MSP or PSP from bit 2 of EXC_RETURN.
+core::arch::global_asm!(
+    ".section .text.MemoryManagement,\"ax\",%progbits",
+    ".global MemoryManagement",
+    ".type MemoryManagement,%function",
+    ".thumb_func",
+    "MemoryManagement:",
+    "    movs r1, #1",
+    "    b configurable_fault_entry",
+    ".section .text.BusFault,\"ax\",%progbits",
+    ".global BusFault",
+    ".type BusFault,%function",
+    ".thumb_func",
+    "BusFault:",
+    "    movs r1, #2",
+    "    b configurable_fault_entry",
+    ".section .text.UsageFault,\"ax\",%progbits",
+    ".global UsageFault",
+    ".type UsageFault,%function",
+    ".thumb_func",
+    "UsageFault:",
+    "    movs r1, #3",
+    "    b configurable_fault_entry",
+    ".section .text.configurable_fault_entry,\"ax\",%progbits",
+    ".thumb_func",
+    "configurable_fault_entry:",
+    "    tst lr, #4",
+    "    ite eq",
+    "    mrseq r0, msp",
+    "    mrsne r0, psp",
+    "    b configurable_fault_handler",
+);
@@ -0,0 +1,189 @@
+// ITM (Instrumentation Trace Macrocell) and TPIU register definitions
+// Based on CMSIS Cortex-M4 core_cm4.h
+
+use super::super::super::mcu::device::PeripheralAccess;
+use super::dwt;
+use core::ptr;
+
+// ITM Base Address
+pub const ITM_BASE: u32 = 0xE0000000;
+
+// TPIU Base Address
+pub const TPIU_BASE: u32 = 0xE0040000;
+
+// Number of stimulus ports
+pub const ITM_PORTS: usize = 32;
+
+// ITM Register Block
+#[repr(C)]
+pub struct Itm {
+    pub stim: [u32; 32], // Stimulus Port Registers
+    _reserved0: [u32; 864],
+    pub ter: u32, // Trace Enable Register
+    _reserved1: [u32; 15],
+    pub tpr: u32, // Trace Privilege Register
+    _reserved2: [u32; 15],
+    pub tcr: u32, // Trace Control Register
+    _reserved3: [u32; 75],
+    pub lar: u32, // Lock Access Register
+    pub lsr: u32, // Lock Status Register
+}
+
+// ITM Peripheral Instance
+pub struct ITM;
+
+impl PeripheralAccess for ITM {
+    const BASE_ADDRESS: u32 = ITM_BASE;
+    type RegisterBlock = Itm;
+}
+
+// TPIU Register Block
+#[repr(C)]
+pub struct Tpiu {
+    pub sspsr: u32, // Supported Parallel Port Size Register
+    pub cspsr: u32, // Current Parallel Port Size Register
+    _reserved0: [u32; 2],
+    pub acpr: u32, // Asynchronous Clock Prescaler Register
+    _reserved1: [u32; 55],
+    pub sppr: u32, // Selected Pin Protocol Register
+    _reserved2: [u32; 131],
+    pub ffsr: u32, // Formatter and Flush Status Register
+    pub ffcr: u32, // Formatter and Flush Control Register
+}
+
+// TPIU Peripheral Instance
+pub struct TPIU;
+
+impl PeripheralAccess for TPIU {
+    const BASE_ADDRESS: u32 = TPIU_BASE;
+    type RegisterBlock = Tpiu;
+}
+
+// Key unlocking the ITM registers for writing
+pub const ITM_LAR_KEY: u32 = 0xC5ACCE55;
+
+// ITM Trace Control Register Definitions
+pub const ITM_TCR_BUSY_POS: u32 = 23;
+pub const ITM_TCR_BUSY_MSK: u32 = 1 << ITM_TCR_BUSY_POS;
+
+pub const ITM_TCR_TRACEBUSID_POS: u32 = 16;
+pub const ITM_TCR_TRACEBUSID_MSK: u32 = 0x7F << ITM_TCR_TRACEBUSID_POS;
+
+pub const ITM_TCR_SWOENA_POS: u32 = 4;
+pub const ITM_TCR_SWOENA_MSK: u32 = 1 << ITM_TCR_SWOENA_POS;
+
+pub const ITM_TCR_DWTENA_POS: u32 = 3;
+pub const ITM_TCR_DWTENA_MSK: u32 = 1 << ITM_TCR_DWTENA_POS;
+
+pub const ITM_TCR_SYNCENA_POS: u32 = 2;
+pub const ITM_TCR_SYNCENA_MSK: u32 = 1 << ITM_TCR_SYNCENA_POS;
+
+pub const ITM_TCR_TSENA_POS: u32 = 1;
+pub const ITM_TCR_TSENA_MSK: u32 = 1 << ITM_TCR_TSENA_POS;
+
+pub const ITM_TCR_ITMENA_POS: u32 = 0;
+pub const ITM_TCR_ITMENA_MSK: u32 = 1;
+
+// ITM Stimulus Port Definitions: reads 1 while the port can take a write
+pub const ITM_STIM_FIFOREADY_POS: u32 = 0;
+pub const ITM_STIM_FIFOREADY_MSK: u32 = 1;
+
+// TPIU Selected Pin Protocol Register Definitions
+pub const TPIU_SPPR_TXMODE_MANCHESTER: u32 = 1;
+pub const TPIU_SPPR_TXMODE_NRZ: u32 = 2;
+
+// TPIU Formatter and Flush Control Register Definitions: the formatter is
+// bypassed for SWO, leaving TrigIn set
+pub const TPIU_FFCR_TRIGIN_POS: u32 = 8;
+pub const TPIU_FFCR_TRIGIN_MSK: u32 = 1 << TPIU_FFCR_TRIGIN_POS;
+
+pub const TPIU_FFCR_ENFCONT_POS: u32 = 1;
+pub const TPIU_FFCR_ENFCONT_MSK: u32 = 1 << TPIU_FFCR_ENFCONT_POS;
+
+/// Route the ITM to the SWO pin as NRZ (UART) at `swo_hz` from a core
+/// clock of `cpu_hz`, and enable the stimulus ports in `ports` (bit n for
+/// port n). The MCU must also route its trace pin, see `DBGMCU_CR`.
+/// Returns Err(-1) for a baud rate the prescaler cannot reach.
+pub fn init(cpu_hz: u32, swo_hz: u32, ports: u32) -> Result<(), i32> {
+    if swo_hz == 0 || swo_hz > cpu_hz {
+        return Err(-1);
+    }
+    let prescaler = cpu_hz / swo_hz - 1;
+    if prescaler > 0x1FFF {
+        return Err(-1);
+    }
+    dwt::enable_trace();
+    unsafe {
+        let tpiu = TPIU::ptr_mut();
+        ptr::write_volatile(&mut (*tpiu).sppr, TPIU_SPPR_TXMODE_NRZ);
+        ptr::write_volatile(&mut (*tpiu).acpr, prescaler);
+        ptr::write_volatile(&mut (*tpiu).ffcr, TPIU_FFCR_TRIGIN_MSK);
+
+        let itm = ITM::ptr_mut();
+        ptr::write_volatile(&mut (*itm).lar, ITM_LAR_KEY);
+        ptr::write_volatile(&mut (*itm).tcr, 0);
+        while ptr::read_volatile(&(*itm).tcr) & ITM_TCR_BUSY_MSK != 0 {}
+        ptr::write_volatile(
+            &mut (*itm).tcr,
+            (1 << ITM_TCR_TRACEBUSID_POS) | ITM_TCR_SYNCENA_MSK | ITM_TCR_ITMENA_MSK,
+        );
+        ptr::write_volatile(&mut (*itm).ter, ports);
+    }
+    Ok(())
+}
+
+/// Whether the ITM and stimulus port `port` are enabled, by `init` or by
+/// the debugger
+pub fn is_enabled(port: usize) -> bool {
+    if port >= ITM_PORTS {
+        return false;
+    }
+    unsafe {
+        let itm = ITM::ptr();
+        ptr::read_volatile(&(*itm).tcr) & ITM_TCR_ITMENA_MSK != 0
+            && ptr::read_volatile(&(*itm).ter) & (1 << port) != 0
+    }
+}
+
+fn wait_ready(port: usize) {
+    unsafe {
+        let stim = &(*ITM::ptr()).stim[port];
+        while ptr::read_volatile(stim) & ITM_STIM_FIFOREADY_MSK == 0 {}
+    }
+}
+
+/// Write one byte to stimulus port `port`, waiting for room in the FIFO
+pub fn write_u8(port: usize, value: u8) {
+    wait_ready(port);
+    unsafe {
+        let stim = &mut (*ITM::ptr_mut()).stim[port] as *mut u32 as *mut u8;
+        ptr::write_volatile(stim, value);
+    }
+}
+
+/// Write a word to stimulus port `port`, sent as a 4-byte packet
+pub fn write_u32(port: usize, value: u32) {
+    wait_ready(port);
+    unsafe {
+        ptr::write_volatile(&mut (*ITM::ptr_mut()).stim[port], value);
+    }
+}
+
+/// Write `bytes` to stimulus port `port`, whole words where possible. Does
+/// nothing if the port is not enabled, so the core does not wait on a
+/// FIFO nobody drains.
+pub fn write_bytes(port: usize, bytes: &[u8]) {
+    if !is_enabled(port) {
+        return;
+    }
+    let mut chunks = bytes.chunks_exact(4);
+    for word in &mut chunks {
+        write_u32(
+            port,
+            u32::from_le_bytes([word[0], word[1], word[2], word[3]]),
+        );
+    }
+    for &byte in chunks.remainder() {
+        write_u8(port, byte);
+    }
+}
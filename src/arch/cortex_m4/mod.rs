@@ -4,7 +4,17 @@
 //! ARM Cortex-M4 processor core used in STM32 microcontrollers.
 //!
 //! This module includes implementations for the Nested Vectored Interrupt
-//! Controller (NVIC), System Control Block (SCB), and SysTick timer.
+//! Controller (NVIC), System Control Block (SCB), and SysTick timer, the
+//! DWT cycle counter and watchpoints, the ITM for SWO tracing and the MPU
+//! with a stack guard (not fitted on the STM32F1), plus
+//! fault handlers that keep a crash report across reset and, with the
+//! `rtos` feature, the PendSV context switch of the preemptive kernel.
+#[cfg(feature = "rtos")]
+pub mod context;
+pub mod dwt;
+pub mod fault;
+pub mod itm;
+pub mod mpu;
 pub mod nvic;
 pub mod scb;
 pub mod systick;
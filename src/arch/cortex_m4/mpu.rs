@@ -0,0 +1,198 @@
+// MPU (Memory Protection Unit) register definitions
+// Based on CMSIS Cortex-M4 core_cm4.h
+//
+// The region encoding is in the host-tested `mpu` component; this module
+// writes the registers and applies the default protection of `mpu::layout`.
+
+use super::super::super::mcu::device::PeripheralAccess;
+use core::ptr;
+use mpu::layout::{DEFAULT_GUARD_SIZE, Layout};
+use mpu::region::Region;
+
+// MPU Base Address
+pub const MPU_BASE: u32 = 0xE000ED90;
+
+// MPU Register Block
+#[repr(C)]
+pub struct Mpu {
+    pub type_: u32,   // Type Register
+    pub ctrl: u32,    // Control Register
+    pub rnr: u32,     // Region Number Register
+    pub rbar: u32,    // Region Base Address Register
+    pub rasr: u32,    // Region Attribute and Size Register
+    pub rbar_a1: u32, // Alias 1 of RBAR
+    pub rasr_a1: u32, // Alias 1 of RASR
+    pub rbar_a2: u32, // Alias 2 of RBAR
+    pub rasr_a2: u32, // Alias 2 of RASR
+    pub rbar_a3: u32, // Alias 3 of RBAR
+    pub rasr_a3: u32, // Alias 3 of RASR
+}
+
+// MPU Peripheral Instance
+pub struct MPU;
+
+impl PeripheralAccess for MPU {
+    const BASE_ADDRESS: u32 = MPU_BASE;
+    type RegisterBlock = Mpu;
+}
+
+// MPU Type Register Definitions
+pub const MPU_TYPE_IREGION_POS: u32 = 16;
+pub const MPU_TYPE_IREGION_MSK: u32 = 0xFF << MPU_TYPE_IREGION_POS;
+
+pub const MPU_TYPE_DREGION_POS: u32 = 8;
+pub const MPU_TYPE_DREGION_MSK: u32 = 0xFF << MPU_TYPE_DREGION_POS;
+
+pub const MPU_TYPE_SEPARATE_POS: u32 = 0;
+pub const MPU_TYPE_SEPARATE_MSK: u32 = 1;
+
+// MPU Control Register Definitions
+pub const MPU_CTRL_PRIVDEFENA_POS: u32 = 2;
+pub const MPU_CTRL_PRIVDEFENA_MSK: u32 = 1 << MPU_CTRL_PRIVDEFENA_POS;
+
+pub const MPU_CTRL_HFNMIENA_POS: u32 = 1;
+pub const MPU_CTRL_HFNMIENA_MSK: u32 = 1 << MPU_CTRL_HFNMIENA_POS;
+
+pub const MPU_CTRL_ENABLE_POS: u32 = 0;
+pub const MPU_CTRL_ENABLE_MSK: u32 = 1;
+
+/// Number of regions, 0 on cores without an MPU (STM32F1)
+pub fn num_regions() -> u8 {
+    let type_ = unsafe { ptr::read_volatile(&(*MPU::ptr()).type_) };
+    ((type_ & MPU_TYPE_DREGION_MSK) >> MPU_TYPE_DREGION_POS) as u8
+}
+
+/// Turn the MPU off, all memory then has its default attributes
+pub fn disable() {
+    cortex_m::asm::dmb();
+    unsafe { ptr::write_volatile(&mut (*MPU::ptr_mut()).ctrl, 0) };
+}
+
+/// Turn the MPU on. With `default_map`, privileged code may still access
+/// memory no region covers with the default attributes (peripherals, system
+/// memory); otherwise that faults. HardFault and NMI always run with the
+/// MPU off (HFNMIENA clear), so the fault handlers can record a fault hit
+/// inside a region.
+pub fn enable(default_map: bool) {
+    let mut ctrl = MPU_CTRL_ENABLE_MSK;
+    if default_map {
+        ctrl |= MPU_CTRL_PRIVDEFENA_MSK;
+    }
+    unsafe { ptr::write_volatile(&mut (*MPU::ptr_mut()).ctrl, ctrl) };
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+}
+
+/// Program region `number`. Returns Err(-2) without an MPU, Err(-1) for an
+/// invalid region or number.
+pub fn set_region(number: u8, region: &Region) -> Result<(), i32> {
+    let regions = num_regions();
+    if regions == 0 {
+        return Err(-2);
+    }
+    if number >= regions {
+        return Err(-1);
+    }
+    let rbar = region.rbar(number)?;
+    let rasr = region.rasr()?;
+    unsafe {
+        let mpu = MPU::ptr_mut();
+        ptr::write_volatile(&mut (*mpu).rbar, rbar);
+        ptr::write_volatile(&mut (*mpu).rasr, rasr);
+    }
+    Ok(())
+}
+
+/// Disable region `number`. Returns Err(-2) without an MPU, Err(-1) for an
+/// invalid number.
+pub fn clear_region(number: u8) -> Result<(), i32> {
+    let regions = num_regions();
+    if regions == 0 {
+        return Err(-2);
+    }
+    if number >= regions {
+        return Err(-1);
+    }
+    unsafe {
+        let mpu = MPU::ptr_mut();
+        ptr::write_volatile(&mut (*mpu).rnr, number as u32);
+        ptr::write_volatile(&mut (*mpu).rasr, 0);
+    }
+    Ok(())
+}
+
+/// Run `write` with region `number` disabled and put the region back as it
+/// was after, for code that programs flash under `FLASH_REGION`. Without
+/// an MPU, or with `number` invalid, just runs `write`.
+pub fn with_region_lifted<T>(number: u8, write: impl FnOnce() -> T) -> T {
+    if number >= num_regions() {
+        return write();
+    }
+    let mpu = MPU::ptr_mut();
+    let (rbar, rasr) = unsafe {
+        ptr::write_volatile(&mut (*mpu).rnr, number as u32);
+        (
+            ptr::read_volatile(&(*mpu).rbar),
+            ptr::read_volatile(&(*mpu).rasr),
+        )
+    };
+    let _ = clear_region(number);
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+    let result = write();
+    unsafe {
+        ptr::write_volatile(&mut (*mpu).rnr, number as u32);
+        ptr::write_volatile(&mut (*mpu).rbar, rbar);
+        ptr::write_volatile(&mut (*mpu).rasr, rasr);
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::isb();
+    result
+}
+
+// Memory map from the linker script (memory/*.x); the addresses of these
+// symbols are the values
+unsafe extern "C" {
+    static _flash_start: u8;
+    static _flash_size: u8;
+    static _ram_start: u8;
+    static _ram_size: u8;
+    static _stack_start: u8;
+    static _stack_size: u8;
+}
+
+/// The memory map of the firmware, with statics and heap ending at
+/// `data_end`
+pub fn layout(data_end: u32) -> Layout {
+    Layout {
+        flash_start: &raw const _flash_start as u32,
+        flash_size: &raw const _flash_size as u32,
+        ram_start: &raw const _ram_start as u32,
+        ram_size: &raw const _ram_size as u32,
+        stack_top: &raw const _stack_start as u32,
+        stack_size: &raw const _stack_size as u32,
+        data_end,
+        guard_size: DEFAULT_GUARD_SIZE,
+    }
+}
+
+/// Apply the default protection (see `mpu::layout`): flash read-only, RAM
+/// execute-never and a no-access guard below the `_stack_size` bytes of
+/// main stack, above the statics and heap ending at `data_end`. Memory
+/// outside these regions keeps the default map. Code that programs flash
+/// must lift `FLASH_REGION` first.
+///
+/// Returns Err(-2) without an MPU, Err(-1) if the stack and guard do not
+/// fit above `data_end`.
+pub fn init_default(data_end: u32) -> Result<(), i32> {
+    if num_regions() == 0 {
+        return Err(-2);
+    }
+    let regions = layout(data_end).regions()?;
+    disable();
+    for (number, region) in regions.iter() {
+        set_region(*number, region)?;
+    }
+    enable(true);
+    Ok(())
+}
@@ -1,7 +1,7 @@
 // NVIC (Nested Vectored Interrupt Controller) register definitions
 // Based on CMSIS Cortex-M4 core_cm4.h
 
-use super::super::super::mcu::stm32f407::PeripheralAccess;
+use super::super::super::mcu::device::PeripheralAccess;
 
 // NVIC Base Address
 pub const NVIC_BASE: u32 = 0xE000E100;
@@ -187,3 +187,23 @@ pub fn nvic_get_priority(irqn: u32) -> u32 {
         (nvic.get_priority(irqn) >> 4) as u32
     }
 }
+
+/// Request a system reset (CMSIS NVIC_SystemReset), keeping the priority grouping
+pub fn nvic_system_reset() -> ! {
+    use super::scb::*;
+    unsafe {
+        let scb = &mut *(SCB_BASE as *mut Scb);
+        cortex_m::asm::dsb();
+        let aircr = core::ptr::read_volatile(&scb.aircr);
+        core::ptr::write_volatile(
+            &mut scb.aircr,
+            (0x5FA << SCB_AIRCR_VECTKEY_POS)
+                | (aircr & SCB_AIRCR_PRIGROUP_MSK)
+                | SCB_AIRCR_SYSRESETREQ_MSK,
+        );
+        cortex_m::asm::dsb();
+    }
+    loop {
+        cortex_m::asm::nop();
+    }
+}
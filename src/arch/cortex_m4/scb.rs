@@ -1,7 +1,7 @@
 // SCB (System Control Block) register definitions
 // Based on CMSIS Cortex-M4 core_cm4.h
 
-use super::super::super::mcu::stm32f407::PeripheralAccess;
+use super::super::super::mcu::device::PeripheralAccess;
 
 // SCB Base Address
 pub const SCB_BASE: u32 = 0xE000ED00;
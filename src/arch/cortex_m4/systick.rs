@@ -1,7 +1,9 @@
 // SysTick (System Timer) register definitions
 // Based on CMSIS Cortex-M4 core_cm4.h
 
-use super::super::super::mcu::stm32f407::PeripheralAccess;
+use super::super::super::mcu::device::PeripheralAccess;
+use super::scb::{SCB, SCB_ICSR_PENDSTSET_MSK};
+use core::ptr;
 use core::sync::atomic::{AtomicU32, Ordering};
 
 // SysTick Base Address
@@ -24,17 +26,60 @@ impl PeripheralAccess for SYSTICK {
     type RegisterBlock = SysTick;
 }
 
-// Global tick counter
+// Global tick counter, the low half of the 64-bit tick count
 static TICK_COUNT: AtomicU32 = AtomicU32::new(0);
 
+// High half of the 64-bit tick count, there are no 64-bit atomics
+static TICK_COUNT_HIGH: AtomicU32 = AtomicU32::new(0);
+
 // Get current tick count
 pub fn get_ticks() -> u32 {
     TICK_COUNT.load(Ordering::Relaxed)
 }
 
+/// Get the current tick count as 64 bits, which does not wrap
+pub fn get_ticks64() -> u64 {
+    time::systick::read_split(
+        || TICK_COUNT_HIGH.load(Ordering::Relaxed),
+        || TICK_COUNT.load(Ordering::Relaxed),
+    )
+}
+
+/// Sample the tick count and the current value for sub-tick time.
+/// Interrupts must be masked.
+pub fn sample() -> time::systick::Sample {
+    unsafe {
+        let systick = SYSTICK::ptr_mut();
+        let scb = SCB::ptr_mut();
+        let current_before = ptr::read_volatile(&(*systick).val) & SYSTICK_VAL_CURRENT_MSK;
+        let pending = ptr::read_volatile(&(*scb).icsr) & SCB_ICSR_PENDSTSET_MSK != 0;
+        let current_after = ptr::read_volatile(&(*systick).val) & SYSTICK_VAL_CURRENT_MSK;
+        time::systick::Sample {
+            ticks: get_ticks64(),
+            reload: ptr::read_volatile(&(*systick).load) & SYSTICK_LOAD_RELOAD_MSK,
+            current_before,
+            pending,
+            current_after,
+        }
+    }
+}
+
 // Increment tick count (called from interrupt handler)
 pub fn increment_ticks() {
-    TICK_COUNT.fetch_add(1, Ordering::Relaxed);
+    if TICK_COUNT.fetch_add(1, Ordering::Relaxed) == u32::MAX {
+        TICK_COUNT_HIGH.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Add `ticks` that passed while SysTick was stopped, in Stop mode.
+/// Interrupts must be masked.
+pub fn advance_ticks(ticks: u32) {
+    let (low, carry) = TICK_COUNT.load(Ordering::Relaxed).overflowing_add(ticks);
+    TICK_COUNT.store(low, Ordering::Relaxed);
+    if carry {
+        let high = TICK_COUNT_HIGH.load(Ordering::Relaxed);
+        TICK_COUNT_HIGH.store(high.wrapping_add(1), Ordering::Relaxed);
+    }
 }
 
 // SysTick Control / Status Register Definitions
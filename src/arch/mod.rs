@@ -1,8 +1,21 @@
 //! # Architecture-Specific Code
 //!
 //! Contains architecture-specific implementations and abstractions for the
-//! Cortex-M4 processor core used in STM32 microcontrollers.
+//! Cortex-M processor cores used in STM32 microcontrollers.
 //!
 //! This module provides low-level system functionality including interrupt
-//! handling, system control, and timing services.
+//! handling, system control, and timing services. The Cortex-M3 of the
+//! STM32F1 uses the Cortex-M4 module, whose NVIC, SCB and SysTick are a
+//! superset of the M3 registers.
+#[cfg(not(feature = "stm32g030"))]
 pub mod cortex_m4;
+
+#[cfg(feature = "stm32g030")]
+pub mod cortex_m0plus;
+
+// Core of the selected MCU, for code shared between parts
+#[cfg(not(feature = "stm32g030"))]
+pub use cortex_m4 as cpu;
+
+#[cfg(feature = "stm32g030")]
+pub use cortex_m0plus as cpu;
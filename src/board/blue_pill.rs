@@ -0,0 +1,73 @@
+//! # Blue Pill (STM32F103C8)
+//!
+//! Generic STM32F103C8 breakout. The only user LED is on PC13 and is active
+//! low; there is no user button. USART1 on PA9/PA10 is the conventional debug
+//! and bootloader UART.
+use super::{Board, DebugUart, Header, Led, Polarity, hp};
+use crate::driver::gpio::{PC, Port, encode_pin};
+
+/// Green LED on PC13
+pub type LedGreen = PC<13>;
+
+pub const BOARD: Board = Board {
+    name: "Blue Pill",
+    mcu: "STM32F103C8T6",
+    hse_hz: Some(8_000_000),
+    leds: &[Led {
+        name: "green",
+        pin: encode_pin(Port::C, 13),
+        polarity: Polarity::ActiveLow,
+    }],
+    buttons: &[],
+    debug_uart: Some(DebugUart {
+        instance: 1,
+        tx: encode_pin(Port::A, 9),
+        rx: encode_pin(Port::A, 10),
+        baudrate: 115_200,
+    }),
+    headers: &[
+        // Row along the USB connector side, from the USB end
+        Header {
+            name: "left",
+            pins: &[
+                hp("B12", encode_pin(Port::B, 12)),
+                hp("B13", encode_pin(Port::B, 13)),
+                hp("B14", encode_pin(Port::B, 14)),
+                hp("B15", encode_pin(Port::B, 15)),
+                hp("A8", encode_pin(Port::A, 8)),
+                hp("A9", encode_pin(Port::A, 9)),
+                hp("A10", encode_pin(Port::A, 10)),
+                hp("A11", encode_pin(Port::A, 11)),
+                hp("A12", encode_pin(Port::A, 12)),
+                hp("A15", encode_pin(Port::A, 15)),
+                hp("B3", encode_pin(Port::B, 3)),
+                hp("B4", encode_pin(Port::B, 4)),
+                hp("B5", encode_pin(Port::B, 5)),
+                hp("B6", encode_pin(Port::B, 6)),
+                hp("B7", encode_pin(Port::B, 7)),
+                hp("B8", encode_pin(Port::B, 8)),
+                hp("B9", encode_pin(Port::B, 9)),
+            ],
+        },
+        Header {
+            name: "right",
+            pins: &[
+                hp("C13", encode_pin(Port::C, 13)),
+                hp("C14", encode_pin(Port::C, 14)),
+                hp("C15", encode_pin(Port::C, 15)),
+                hp("A0", encode_pin(Port::A, 0)),
+                hp("A1", encode_pin(Port::A, 1)),
+                hp("A2", encode_pin(Port::A, 2)),
+                hp("A3", encode_pin(Port::A, 3)),
+                hp("A4", encode_pin(Port::A, 4)),
+                hp("A5", encode_pin(Port::A, 5)),
+                hp("A6", encode_pin(Port::A, 6)),
+                hp("A7", encode_pin(Port::A, 7)),
+                hp("B0", encode_pin(Port::B, 0)),
+                hp("B1", encode_pin(Port::B, 1)),
+                hp("B10", encode_pin(Port::B, 10)),
+                hp("B11", encode_pin(Port::B, 11)),
+            ],
+        },
+    ],
+};
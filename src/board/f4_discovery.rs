@@ -0,0 +1,109 @@
+//! # STM32F4-Discovery (STM32F407VG)
+//!
+//! Four user LEDs on PD12..PD15 (active high), the blue user button on PA0
+//! (active high, external pull-down). The on-board ST-LINK/V2 has no virtual
+//! COM port, so USART2 on PA2/PA3 is used for debug output through an
+//! external adapter.
+use super::{Board, Button, DebugUart, Header, Led, Polarity, hp};
+use crate::driver::gpio::{PA, PD, Pin, Port, PullResistor, encode_pin};
+use crate::driver::usb::UsbPin;
+use crate::driver::usb::host::PortPower;
+
+/// Green LED LD4
+pub type LedGreen = PD<12>;
+/// Orange LED LD3
+pub type LedOrange = PD<13>;
+/// Red LED LD5
+pub type LedRed = PD<14>;
+/// Blue LED LD6
+pub type LedBlue = PD<15>;
+/// Blue user button B1
+pub type UserButton = PA<0>;
+
+/// LIS3DHS/LIS302DL accelerometer chip select (SPI1)
+pub const ACCEL_CS: Pin = encode_pin(Port::E, 3);
+/// CS43L22 audio DAC reset (control interface on I2C1 PB6/PB9)
+pub const AUDIO_RESET: Pin = encode_pin(Port::D, 4);
+/// STMPS2141 power switch of the micro-AB connector CN5: enable on PC0,
+/// over-current flag on PD5, both active low
+pub const USB_POWER: PortPower = PortPower {
+    pins: UsbPin::VBUS.union(UsbPin::OC),
+    vbus: encode_pin(Port::C, 0),
+    vbus_polarity: Polarity::ActiveLow,
+    over_current: encode_pin(Port::D, 5),
+    over_current_polarity: Polarity::ActiveLow,
+};
+
+pub const BOARD: Board = Board {
+    name: "STM32F4-Discovery",
+    mcu: "STM32F407VGT6",
+    hse_hz: Some(8_000_000),
+    leds: &[
+        Led {
+            name: "green",
+            pin: encode_pin(Port::D, 12),
+            polarity: Polarity::ActiveHigh,
+        },
+        Led {
+            name: "orange",
+            pin: encode_pin(Port::D, 13),
+            polarity: Polarity::ActiveHigh,
+        },
+        Led {
+            name: "red",
+            pin: encode_pin(Port::D, 14),
+            polarity: Polarity::ActiveHigh,
+        },
+        Led {
+            name: "blue",
+            pin: encode_pin(Port::D, 15),
+            polarity: Polarity::ActiveHigh,
+        },
+    ],
+    buttons: &[Button {
+        name: "user",
+        pin: encode_pin(Port::A, 0),
+        polarity: Polarity::ActiveHigh,
+        pull: PullResistor::None,
+    }],
+    debug_uart: Some(DebugUart {
+        instance: 2,
+        tx: encode_pin(Port::A, 2),
+        rx: encode_pin(Port::A, 3),
+        baudrate: 115_200,
+    }),
+    headers: &[
+        Header {
+            name: "SPI1",
+            pins: &[
+                hp("SCK", encode_pin(Port::A, 5)),
+                hp("MISO", encode_pin(Port::A, 6)),
+                hp("MOSI", encode_pin(Port::A, 7)),
+                hp("CS", encode_pin(Port::E, 3)),
+            ],
+        },
+        Header {
+            name: "I2C1",
+            pins: &[
+                hp("SCL", encode_pin(Port::B, 6)),
+                hp("SDA", encode_pin(Port::B, 9)),
+            ],
+        },
+        Header {
+            name: "SPI2",
+            pins: &[
+                hp("SCK", encode_pin(Port::B, 13)),
+                hp("MISO", encode_pin(Port::B, 14)),
+                hp("MOSI", encode_pin(Port::B, 15)),
+                hp("NSS", encode_pin(Port::B, 12)),
+            ],
+        },
+        Header {
+            name: "USART2",
+            pins: &[
+                hp("TX", encode_pin(Port::A, 2)),
+                hp("RX", encode_pin(Port::A, 3)),
+            ],
+        },
+    ],
+};
@@ -0,0 +1,226 @@
+//! # Board Definitions
+//!
+//! Describes the boards the template runs on: which pins the on-board LEDs,
+//! buttons and debug UART are wired to, their polarity, and the pins broken
+//! out on expansion headers.
+//!
+//! Exactly one board is selected with a `board-*` cargo feature, which also
+//! enables the matching MCU feature. The selected description is re-exported
+//! as [`BOARD`] so applications can stay board-agnostic.
+#![allow(dead_code)]
+
+use crate::driver::gpio::{Gpio, Pin, PullResistor, Result};
+
+#[cfg(any(
+    all(feature = "board-f4-discovery", feature = "board-nucleo-f401re"),
+    all(feature = "board-f4-discovery", feature = "board-nucleo-f411re"),
+    all(feature = "board-f4-discovery", feature = "board-blue-pill"),
+    all(feature = "board-f4-discovery", feature = "board-nucleo-g030"),
+    all(feature = "board-nucleo-f401re", feature = "board-nucleo-f411re"),
+    all(feature = "board-nucleo-f401re", feature = "board-blue-pill"),
+    all(feature = "board-nucleo-f401re", feature = "board-nucleo-g030"),
+    all(feature = "board-nucleo-f411re", feature = "board-blue-pill"),
+    all(feature = "board-nucleo-f411re", feature = "board-nucleo-g030"),
+    all(feature = "board-blue-pill", feature = "board-nucleo-g030")
+))]
+compile_error!("more than one `board-*` feature is enabled, select exactly one");
+
+#[cfg(all(
+    feature = "usb-serial",
+    not(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))
+))]
+compile_error!("`usb-serial` needs the OTG_FS core, only found on the STM32F4 boards");
+
+#[cfg(feature = "board-f4-discovery")]
+pub mod f4_discovery;
+
+#[cfg(feature = "board-nucleo-f401re")]
+pub mod nucleo_f401re;
+
+#[cfg(feature = "board-nucleo-f411re")]
+pub mod nucleo_f411re;
+
+#[cfg(feature = "board-blue-pill")]
+pub mod blue_pill;
+
+#[cfg(feature = "board-nucleo-g030")]
+pub mod nucleo_g030;
+
+#[cfg(feature = "board-f4-discovery")]
+pub use f4_discovery::BOARD;
+
+#[cfg(feature = "board-nucleo-f401re")]
+pub use nucleo_f401re::BOARD;
+
+#[cfg(feature = "board-nucleo-f411re")]
+pub use nucleo_f411re::BOARD;
+
+#[cfg(feature = "board-blue-pill")]
+pub use blue_pill::BOARD;
+
+#[cfg(feature = "board-nucleo-g030")]
+pub use nucleo_g030::BOARD;
+
+/// Electrical polarity of an on-board LED or button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// Logic high turns the LED on / reads high when the button is pressed
+    ActiveHigh,
+    /// Logic low turns the LED on / reads low when the button is pressed
+    ActiveLow,
+}
+
+impl Polarity {
+    /// Converts a logical state (on/pressed) to a pin level.
+    pub const fn level(self, active: bool) -> bool {
+        match self {
+            Polarity::ActiveHigh => active,
+            Polarity::ActiveLow => !active,
+        }
+    }
+}
+
+/// An on-board LED.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Led {
+    pub name: &'static str,
+    pub pin: Pin,
+    pub polarity: Polarity,
+}
+
+impl Led {
+    /// Configures the LED pin as an output and turns the LED off.
+    pub fn init<'a>(&self, gpio: &mut impl Gpio<'a>) -> Result<()> {
+        gpio.set_direction(self.pin, crate::driver::gpio::Direction::Output)?;
+        self.set(gpio, false);
+        Ok(())
+    }
+
+    /// Turns the LED on or off, taking its polarity into account.
+    pub fn set<'a>(&self, gpio: &mut impl Gpio<'a>, on: bool) {
+        gpio.set_output(self.pin, self.polarity.level(on));
+    }
+}
+
+/// An on-board push button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Button {
+    pub name: &'static str,
+    pub pin: Pin,
+    pub polarity: Polarity,
+    /// Internal pull resistor needed when the board has no external one
+    pub pull: PullResistor,
+}
+
+impl Button {
+    /// Configures the button pin as an input with the required pull resistor.
+    pub fn init<'a>(&self, gpio: &mut impl Gpio<'a>) -> Result<()> {
+        gpio.set_direction(self.pin, crate::driver::gpio::Direction::Input)?;
+        gpio.set_pull_resistor(self.pin, self.pull)
+    }
+
+    /// Returns true while the button is pressed.
+    pub fn is_pressed<'a>(&self, gpio: &impl Gpio<'a>) -> bool {
+        gpio.get_input(self.pin) == self.polarity.level(true)
+    }
+}
+
+/// The UART used for debug output (e.g. the ST-LINK virtual COM port).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugUart {
+    /// USART instance number (1 = USART1, 2 = USART2, ...)
+    pub instance: u8,
+    pub tx: Pin,
+    pub rx: Pin,
+    pub baudrate: u32,
+}
+
+/// A named pin on an expansion header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderPin {
+    pub label: &'static str,
+    pub pin: Pin,
+}
+
+/// An expansion header (Arduino, Morpho, breadboard rows, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub name: &'static str,
+    pub pins: &'static [HeaderPin],
+}
+
+impl Header {
+    /// Looks up a header pin by its silkscreen label.
+    pub fn pin(&self, label: &str) -> Option<Pin> {
+        self.pins.iter().find(|p| p.label == label).map(|p| p.pin)
+    }
+}
+
+/// Complete description of a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    pub name: &'static str,
+    /// Part number of the MCU fitted on the board
+    pub mcu: &'static str,
+    /// Frequency of the external crystal/clock, if one is fitted
+    pub hse_hz: Option<u32>,
+    pub leds: &'static [Led],
+    pub buttons: &'static [Button],
+    pub debug_uart: Option<DebugUart>,
+    pub headers: &'static [Header],
+}
+
+impl Board {
+    /// Looks up an LED by name.
+    pub fn led(&self, name: &str) -> Option<&'static Led> {
+        self.leds.iter().find(|l| l.name == name)
+    }
+
+    /// Looks up a button by name.
+    pub fn button(&self, name: &str) -> Option<&'static Button> {
+        self.buttons.iter().find(|b| b.name == name)
+    }
+
+    /// Looks up a header by name.
+    pub fn header(&self, name: &str) -> Option<&'static Header> {
+        self.headers.iter().find(|h| h.name == name)
+    }
+}
+
+/// Shorthand used by the board tables.
+pub(crate) const fn hp(label: &'static str, pin: Pin) -> HeaderPin {
+    HeaderPin { label, pin }
+}
+
+/// Arduino Uno R3 connector of the STM32F4 Nucleo-64 boards (UM1724).
+#[cfg(any(feature = "board-nucleo-f401re", feature = "board-nucleo-f411re"))]
+pub(crate) const ARDUINO_UNO_NUCLEO64: Header = {
+    use crate::driver::gpio::{Port, encode_pin};
+    Header {
+        name: "arduino",
+        pins: &[
+            hp("A0", encode_pin(Port::A, 0)),
+            hp("A1", encode_pin(Port::A, 1)),
+            hp("A2", encode_pin(Port::A, 4)),
+            hp("A3", encode_pin(Port::B, 0)),
+            hp("A4", encode_pin(Port::C, 1)),
+            hp("A5", encode_pin(Port::C, 0)),
+            hp("D0", encode_pin(Port::A, 3)),
+            hp("D1", encode_pin(Port::A, 2)),
+            hp("D2", encode_pin(Port::A, 10)),
+            hp("D3", encode_pin(Port::B, 3)),
+            hp("D4", encode_pin(Port::B, 5)),
+            hp("D5", encode_pin(Port::B, 4)),
+            hp("D6", encode_pin(Port::B, 10)),
+            hp("D7", encode_pin(Port::A, 8)),
+            hp("D8", encode_pin(Port::A, 9)),
+            hp("D9", encode_pin(Port::C, 7)),
+            hp("D10", encode_pin(Port::B, 6)),
+            hp("D11", encode_pin(Port::A, 7)),
+            hp("D12", encode_pin(Port::A, 6)),
+            hp("D13", encode_pin(Port::A, 5)),
+            hp("D14", encode_pin(Port::B, 9)),
+            hp("D15", encode_pin(Port::B, 8)),
+        ],
+    }
+};
@@ -0,0 +1,37 @@
+//! # NUCLEO-F401RE (STM32F401RE)
+//!
+//! Green user LED LD2 on PA5 (active high, shared with Arduino D13), blue
+//! user button B1 on PC13 (active low, external pull-up). USART2 on PA2/PA3
+//! is routed to the ST-LINK/V2-1 virtual COM port.
+use super::{Board, Button, DebugUart, Led, Polarity};
+use crate::driver::gpio::{PA, PC, Port, PullResistor, encode_pin};
+
+/// Green LED LD2
+pub type LedGreen = PA<5>;
+/// Blue user button B1
+pub type UserButton = PC<13>;
+
+pub const BOARD: Board = Board {
+    name: "NUCLEO-F401RE",
+    mcu: "STM32F401RET6",
+    // HSE is fed from the ST-LINK MCO, no crystal fitted by default
+    hse_hz: None,
+    leds: &[Led {
+        name: "green",
+        pin: encode_pin(Port::A, 5),
+        polarity: Polarity::ActiveHigh,
+    }],
+    buttons: &[Button {
+        name: "user",
+        pin: encode_pin(Port::C, 13),
+        polarity: Polarity::ActiveLow,
+        pull: PullResistor::None,
+    }],
+    debug_uart: Some(DebugUart {
+        instance: 2,
+        tx: encode_pin(Port::A, 2),
+        rx: encode_pin(Port::A, 3),
+        baudrate: 115_200,
+    }),
+    headers: &[super::ARDUINO_UNO_NUCLEO64],
+};
@@ -0,0 +1,51 @@
+//! # Nucleo-G030 (STM32G030C8)
+//!
+//! Nucleo-64 style board built around the STM32G030C8, following the
+//! NUCLEO-G070RB conventions: user LED on PA5 (active high), user button on
+//! PC13 (active low, external pull-up) and USART2 on PA2/PA3 routed to the
+//! ST-LINK virtual COM port.
+use super::{Board, Button, DebugUart, Header, Led, Polarity, hp};
+use crate::driver::gpio::{PA, PC, Port, PullResistor, encode_pin};
+
+/// Green user LED
+pub type LedGreen = PA<5>;
+/// Blue user button
+pub type UserButton = PC<13>;
+
+pub const BOARD: Board = Board {
+    name: "Nucleo-G030",
+    mcu: "STM32G030C8T6",
+    hse_hz: None,
+    leds: &[Led {
+        name: "green",
+        pin: encode_pin(Port::A, 5),
+        polarity: Polarity::ActiveHigh,
+    }],
+    buttons: &[Button {
+        name: "user",
+        pin: encode_pin(Port::C, 13),
+        polarity: Polarity::ActiveLow,
+        pull: PullResistor::None,
+    }],
+    debug_uart: Some(DebugUart {
+        instance: 2,
+        tx: encode_pin(Port::A, 2),
+        rx: encode_pin(Port::A, 3),
+        baudrate: 115_200,
+    }),
+    headers: &[Header {
+        name: "arduino",
+        pins: &[
+            hp("A0", encode_pin(Port::A, 0)),
+            hp("A1", encode_pin(Port::A, 1)),
+            hp("A2", encode_pin(Port::A, 4)),
+            hp("D0", encode_pin(Port::A, 3)),
+            hp("D1", encode_pin(Port::A, 2)),
+            hp("D11", encode_pin(Port::A, 7)),
+            hp("D12", encode_pin(Port::A, 6)),
+            hp("D13", encode_pin(Port::A, 5)),
+            hp("D14", encode_pin(Port::B, 9)),
+            hp("D15", encode_pin(Port::B, 8)),
+        ],
+    }],
+};
@@ -10,7 +10,8 @@
 //! - 24C16 (16Kbit)
 //! - 24C32 and above (32Kbit+)
 
-use crate::driver::i2c::{Event, I2c, Result};
+use crate::driver::i2c::probe::{self, Response};
+use crate::driver::i2c::{Event, I2c, Operation, Result};
 use core::marker::PhantomData;
 
 /// Default I2C address for 24 series EEPROM (0xA0 >> 1 = 0x50)
@@ -154,14 +155,14 @@ where
         Ok(eeprom)
     }
 
-    /// Check if the EEPROM device is ready
+    /// Check if the EEPROM device is ready: it acknowledges its address
+    /// unless it is absent or busy with an internal write cycle
     pub fn is_device_ready(&mut self) -> EepromResult<()> {
-        // Simple way to check device readiness - try to read status
-        let status = self.i2c.get_status();
-        if status.bus_error {
-            return Err(EepromError::DeviceNotReady);
+        match probe::probe(&mut self.i2c, self.config.address as u8) {
+            Ok(Response::Ack) => Ok(()),
+            Ok(Response::Nack) => Err(EepromError::DeviceNotReady),
+            Err(e) => Err(EepromError::from(e)),
         }
-        Ok(())
     }
 
     /// Read data from EEPROM
@@ -265,88 +266,43 @@ where
         }
     }
 
-    /// Read with 8-bit addressing
-    fn read_with_8bit_address(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
-        // For 8-bit addressing, send address as first byte, then read
-        let addr_bytes = [address as u8];
-
-        // Send address
-        self.i2c
-            .master_transmit(self.config.address, &addr_bytes, true)
-            .map_err(EepromError::from)?;
-
-        // Read data
+    /// Write the memory address, then read `data` after a repeated START
+    fn read_at(&mut self, device_addr: u32, mem_addr: &[u8], data: &mut [u8]) -> EepromResult<()> {
         self.i2c
-            .master_receive(self.config.address, data, false)
-            .map_err(EepromError::from)?;
+            .transaction(
+                device_addr,
+                &mut [Operation::Write(mem_addr), Operation::Read(data)],
+            )
+            .map_err(EepromError::from)
+    }
 
-        Ok(())
+    /// Read with 8-bit addressing
+    fn read_with_8bit_address(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
+        self.read_at(self.config.address, &[address as u8], data)
     }
 
     /// Read with 16-bit addressing
     fn read_with_16bit_address(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
-        // For 16-bit addressing, send address as two bytes, then read
         let addr_bytes = [(address >> 8) as u8, address as u8];
-
-        // Send address
-        self.i2c
-            .master_transmit(self.config.address, &addr_bytes, true)
-            .map_err(EepromError::from)?;
-
-        // Read data
-        self.i2c
-            .master_receive(self.config.address, data, false)
-            .map_err(EepromError::from)?;
-
-        Ok(())
+        self.read_at(self.config.address, &addr_bytes, data)
     }
 
     /// Read from 4Kbit EEPROM (uses device address bits)
     fn read_4kbit(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
         let device_addr = self.config.address | ((address & 0x0100) >> 7);
-        let mem_addr = [address as u8];
-
-        self.i2c
-            .master_transmit(device_addr, &mem_addr, true)
-            .map_err(EepromError::from)?;
-
-        self.i2c
-            .master_receive(device_addr, data, false)
-            .map_err(EepromError::from)?;
-
-        Ok(())
+        self.read_at(device_addr, &[address as u8], data)
     }
 
     /// Read from 8Kbit EEPROM (uses device address bits)
     fn read_8kbit(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
         let device_addr = self.config.address | ((address & 0x0300) >> 7);
-        let mem_addr = [address as u8];
-
-        self.i2c
-            .master_transmit(device_addr, &mem_addr, true)
-            .map_err(EepromError::from)?;
-
-        self.i2c
-            .master_receive(device_addr, data, false)
-            .map_err(EepromError::from)?;
-
-        Ok(())
+        self.read_at(device_addr, &[address as u8], data)
     }
 
     /// Read from 16Kbit EEPROM (uses device address bits)
     fn read_16kbit(&mut self, address: u32, data: &mut [u8]) -> EepromResult<()> {
         let device_addr = self.config.address | ((address & 0x0700) >> 7);
-        let mem_addr = [address as u8];
-
-        self.i2c
-            .master_transmit(device_addr, &mem_addr, true)
-            .map_err(EepromError::from)?;
-
-        self.i2c
-            .master_receive(device_addr, data, false)
-            .map_err(EepromError::from)?;
-
-        Ok(())
+        self.read_at(device_addr, &[address as u8], data)
     }
 
     /// Write with 8-bit addressing
@@ -1,4 +1,4 @@
-use crate::driver::i2c::{I2c, Result};
+use crate::driver::i2c::{I2c, Operation, Result};
 
 /// DS1307 RTC chip I2C address
 const DS1307_I2C_ADDRESS: u32 = 0x68;
@@ -191,14 +191,11 @@ where
 
     /// Read a value from a DS1307 register
     fn read_register(&mut self, reg_addr: u8) -> Result<u8> {
-        // Send register address
-        self.i2c
-            .master_transmit(DS1307_I2C_ADDRESS, &[reg_addr], true)?;
-
-        // Read the register value
         let mut data = [0u8; 1];
-        self.i2c
-            .master_receive(DS1307_I2C_ADDRESS, &mut data, false)?;
+        self.i2c.transaction(
+            DS1307_I2C_ADDRESS,
+            &mut [Operation::Write(&[reg_addr]), Operation::Read(&mut data)],
+        )?;
 
         Ok(data[0])
     }
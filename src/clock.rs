@@ -0,0 +1,122 @@
+//! # Clock
+//!
+//! Monotonic time since boot with microsecond resolution, blocking delays,
+//! and software timers with callbacks.
+//!
+//! Time comes from the 1 ms SysTick: the 64-bit tick count plus the
+//! current value of the counter within the tick, so SysTick must be running
+//! (`systick_init_1ms`). Timer callbacks run in the SysTick interrupt and
+//! should be short; they may start and stop timers.
+//!
+//! ```ignore
+//! fn toggle(_: clock::TimerId) {
+//!     led.toggle();
+//! }
+//!
+//! let id = clock::start_periodic(Duration::from_millis(500), toggle)?;
+//! clock::delay_us(10);
+//! let elapsed = clock::now() - start;
+//! ```
+
+use crate::arch::cpu::systick;
+use core::cell::RefCell;
+use cortex_m::interrupt::{self, Mutex};
+use time::wheel::{Callback, TimerWheel};
+
+pub use time::instant::{Duration, Instant};
+pub use time::wheel::TimerId;
+
+/// Microseconds per SysTick tick
+const TICK_US: u32 = 1_000;
+
+/// Maximum number of running software timers
+pub const MAX_TIMERS: usize = 16;
+
+/// Buckets of the timer wheel, in ticks
+const WHEEL_SLOTS: usize = 32;
+
+static TIMERS: Mutex<RefCell<TimerWheel<MAX_TIMERS, WHEEL_SLOTS>>> =
+    Mutex::new(RefCell::new(TimerWheel::new()));
+
+/// Time since boot
+pub fn now() -> Instant {
+    let sample = interrupt::free(|_| systick::sample());
+    Instant::from_micros(time::systick::micros(sample, TICK_US))
+}
+
+/// Busy-wait for `us` microseconds.
+pub fn delay_us(us: u32) {
+    let end = now() + Duration::from_micros(us as u64);
+    while now() < end {}
+}
+
+/// Busy-wait for `ms` milliseconds.
+pub fn delay_ms(ms: u32) {
+    let end = now() + Duration::from_millis(ms as u64);
+    while now() < end {}
+}
+
+fn start(delay: Duration, period: Duration, callback: Callback) -> Result<TimerId, i32> {
+    interrupt::free(|cs| {
+        TIMERS.borrow(cs).borrow_mut().start(
+            delay.as_millis_ceil(),
+            period.as_millis_ceil(),
+            callback,
+        )
+    })
+}
+
+/// Call `callback` once after `delay`, rounded up to whole ticks.
+/// Returns Err(-1) if all timers are in use.
+pub fn start_one_shot(delay: Duration, callback: Callback) -> Result<TimerId, i32> {
+    start(delay, Duration::ZERO, callback)
+}
+
+/// Call `callback` every `period`, rounded up to whole ticks, starting one
+/// period from now. Returns Err(-1) if all timers are in use or the period
+/// is zero.
+pub fn start_periodic(period: Duration, callback: Callback) -> Result<TimerId, i32> {
+    if period == Duration::ZERO {
+        return Err(-1);
+    }
+    start(period, period, callback)
+}
+
+/// Stop a timer. Returns Err(-1) if it is not running.
+pub fn stop_timer(id: TimerId) -> Result<(), i32> {
+    interrupt::free(|cs| TIMERS.borrow(cs).borrow_mut().stop(id))
+}
+
+/// Returns true if the timer has not expired or been stopped
+pub fn is_timer_active(id: TimerId) -> bool {
+    interrupt::free(|cs| TIMERS.borrow(cs).borrow().is_active(id))
+}
+
+/// Ticks until the next software timer expires, `None` if none is running
+pub fn idle_ticks() -> Option<u32> {
+    let next = interrupt::free(|cs| TIMERS.borrow(cs).borrow().next_expiry())?;
+    let ticks = next.saturating_sub(systick::get_ticks64());
+    Some(ticks.min(u32::MAX as u64) as u32)
+}
+
+/// Advance the software timers to the current tick and run the callbacks
+/// of those that expired. Called from the SysTick handler.
+pub fn on_tick() {
+    let mut expired: [Option<(TimerId, Callback)>; MAX_TIMERS] = [None; MAX_TIMERS];
+    let mut count = 0;
+    interrupt::free(|cs| {
+        TIMERS
+            .borrow(cs)
+            .borrow_mut()
+            .advance(systick::get_ticks64(), |id, callback| {
+                if count < MAX_TIMERS {
+                    expired[count] = Some((id, callback));
+                    count += 1;
+                }
+            })
+    });
+    // Outside the critical section, so callbacks can use the timers
+    for (id, callback) in expired.into_iter().flatten() {
+        callback(id);
+    }
+}
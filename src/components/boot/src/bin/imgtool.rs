@@ -0,0 +1,268 @@
+//! Host tool for application images: packs a raw binary with its header,
+//! shows the header of an image file, and uploads one to the bootloader.
+//!
+//! ```text
+//! imgtool pack --version 1.2.0 --slot a app.bin app.img
+//! imgtool show app.img
+//! imgtool upload [--baud 115200] /dev/ttyUSB0 app.img
+//! ```
+//!
+//! `pack` takes the output of `objcopy -O binary` for an application
+//! built with the feature of the slot (`slot-a` or `slot-b`), so that it
+//! is linked after the header in that slot of the STM32F407 layout.
+//! `upload` keeps asking until the bootloader answers, so it can be
+//! started before the board is reset into the bootloader.
+
+use std::process::ExitCode;
+
+use boot::image::{self, HEADER_SIZE, Header, Version};
+use boot::layout::{STM32F407, Slot};
+use boot::protocol::{Info, MAX_DATA, Request, Response, Status};
+use link::endpoint::{Config, Endpoint, Event};
+use link::host::{Clock, SerialPort};
+use link::packet::MAX_PAYLOAD;
+
+/// How long `upload` waits for the bootloader to answer at all
+const CONNECT_MS: u64 = 30_000;
+
+/// How long a request may take, erasing a slot being the slowest
+const REQUEST_MS: u64 = 10_000;
+
+fn usage() -> ExitCode {
+    eprintln!("usage: imgtool pack --version MAJOR.MINOR.PATCH --slot a|b IN.bin OUT.img");
+    eprintln!("       imgtool show FILE.img");
+    eprintln!("       imgtool upload [--baud BAUD] PORT FILE.img");
+    ExitCode::FAILURE
+}
+
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|err| format!("{path}: {err}"))
+}
+
+fn pack(args: &[String]) -> Result<(), String> {
+    let mut version = None;
+    let mut slot = None;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--version" => version = args.next().and_then(|text| Version::parse(text)),
+            "--slot" => {
+                slot = match args.next().map(String::as_str) {
+                    Some("a" | "A") => Some(Slot::A),
+                    Some("b" | "B") => Some(Slot::B),
+                    _ => None,
+                }
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    let (Some(version), Some(slot), [input, output]) = (version, slot, paths.as_slice()) else {
+        return Err("pack needs --version, --slot, an input and an output".into());
+    };
+    let body = read(input)?;
+    let region = STM32F407.slot(slot);
+    if (HEADER_SIZE + body.len()) as u32 > region.size {
+        return Err(format!(
+            "{input}: {} bytes do not fit in slot {}",
+            body.len(),
+            slot.name()
+        ));
+    }
+    let file = image::pack(version, STM32F407.image_address(slot), &body);
+    let header = Header::parse(&file).map_err(|_| "header".to_string())?;
+    header.check(&body).map_err(|_| {
+        format!(
+            "{input}: the reset vector is outside the image, is it linked for slot {}?",
+            slot.name()
+        )
+    })?;
+    std::fs::write(output, &file).map_err(|err| format!("{output}: {err}"))?;
+    println!(
+        "{output}: version {version}, {} bytes for slot {}",
+        body.len(),
+        slot.name()
+    );
+    Ok(())
+}
+
+/// The header of an image file, checked against the image
+fn load(path: &str) -> Result<(Vec<u8>, Header), String> {
+    let file = read(path)?;
+    let header = Header::parse(&file).map_err(|_| format!("{path}: not an image file"))?;
+    let body = file.get(HEADER_SIZE..).unwrap_or(&[]);
+    header
+        .check(body)
+        .map_err(|_| format!("{path}: the image does not match its header"))?;
+    Ok((file, header))
+}
+
+fn show(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err("show needs a file".into());
+    };
+    let (_, header) = load(path)?;
+    let slot = STM32F407
+        .slot_at(header.load_address)
+        .map_or("none", Slot::name);
+    println!("version       {}", header.version);
+    println!("length        {} bytes", header.length);
+    println!("load address  {:#010x} (slot {slot})", header.load_address);
+    println!("crc-32        {:#010x}", header.crc);
+    let hash: String = header.sha256.iter().map(|b| format!("{b:02x}")).collect();
+    println!("sha-256       {hash}");
+    Ok(())
+}
+
+/// A link to the bootloader
+struct Session {
+    link: Endpoint<SerialPort>,
+    clock: Clock,
+}
+
+impl Session {
+    /// Send `request` and wait up to `timeout_ms` for its response.
+    /// Returns Ok(None) if the bootloader did not answer in time.
+    fn request(
+        &mut self,
+        request: &Request<'_>,
+        timeout_ms: u64,
+    ) -> Result<Option<Vec<u8>>, String> {
+        let mut bytes = [0; MAX_PAYLOAD];
+        let len = request.encode(&mut bytes).map_err(|_| "request too long")?;
+        let deadline = self.clock.now_ms() + timeout_ms;
+        // The acknowledgement of the last request may come after its
+        // response
+        while self.link.is_pending() && self.clock.now_ms() < deadline {
+            let _ = self.link.poll(self.clock.now_ms());
+        }
+        self.link
+            .send(&bytes[..len], self.clock.now_ms())
+            .map_err(|err| format!("serial port error {err}"))?;
+        while self.clock.now_ms() < deadline {
+            let answer = match self.link.poll(self.clock.now_ms()) {
+                Some(Event::Received(payload)) => Some(payload.to_vec()),
+                Some(Event::Failed(_)) => return Ok(None),
+                _ => None,
+            };
+            let Some(answer) = answer else {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+                continue;
+            };
+            let response = Response::parse(&answer).map_err(|_| "not a response")?;
+            if response.request != bytes[0] {
+                continue;
+            }
+            if response.status != Status::Ok {
+                return Err(format!("the bootloader answered {:?}", response.status));
+            }
+            return Ok(Some(response.data.to_vec()));
+        }
+        Ok(None)
+    }
+
+    /// `request` answered in time
+    fn expect(&mut self, request: &Request<'_>) -> Result<Vec<u8>, String> {
+        self.request(request, REQUEST_MS)?
+            .ok_or_else(|| "the bootloader stopped answering".to_string())
+    }
+
+    /// Ask for `Info` until the bootloader answers
+    fn connect(&mut self) -> Result<Info, String> {
+        let deadline = self.clock.now_ms() + CONNECT_MS;
+        while self.clock.now_ms() < deadline {
+            // A bootloader that dropped the request as a repeat of the
+            // last packet of an earlier session answers the next one
+            if let Some(data) = self.request(&Request::Info, 500)? {
+                return Info::parse(&data).map_err(|_| "malformed Info".to_string());
+            }
+        }
+        Err("no answer from the bootloader, reset the board".into())
+    }
+}
+
+fn upload(args: &[String]) -> Result<(), String> {
+    let mut baud = 115_200;
+    let mut paths = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--baud" => {
+                baud = args
+                    .next()
+                    .and_then(|text| text.parse().ok())
+                    .ok_or("--baud needs a number")?
+            }
+            _ => paths.push(arg.as_str()),
+        }
+    }
+    let [port, path] = paths.as_slice() else {
+        return Err("upload needs a port and a file".into());
+    };
+    let (file, header) = load(path)?;
+    let port = SerialPort::open(port, baud).map_err(|err| format!("{port}: {err}"))?;
+    let mut session = Session {
+        link: Endpoint::new(port, Config::default()),
+        clock: Clock::new(),
+    };
+
+    let info = session.connect()?;
+    println!(
+        "bootloader {}, running {}, writing slot {}",
+        info.bootloader,
+        info.running
+            .map_or("nothing".to_string(), |version| version.to_string()),
+        info.update_slot.name()
+    );
+    if header.load_address != info.update_address {
+        return Err(format!(
+            "{path} is linked at {:#010x}, build and pack it for slot {}",
+            header.load_address,
+            info.update_slot.name()
+        ));
+    }
+    if file.len() as u32 > info.capacity {
+        return Err(format!("{path} does not fit in the slot"));
+    }
+
+    session.expect(&Request::Begin {
+        length: file.len() as u32,
+    })?;
+    for (index, chunk) in file.chunks(MAX_DATA).enumerate() {
+        let offset = (index * MAX_DATA) as u32;
+        session.expect(&Request::Data {
+            offset,
+            bytes: chunk,
+        })?;
+        print!("\r{} / {} bytes", offset as usize + chunk.len(), file.len());
+    }
+    println!();
+    let data = session.expect(&Request::End)?;
+    let installed = data
+        .get(..4)
+        .map(|bytes| Version(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])))
+        .ok_or("malformed answer to End")?;
+    println!("installed version {installed}, booting it");
+    session.expect(&Request::Boot)?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some((command, rest)) = args.split_first() else {
+        return usage();
+    };
+    let result = match command.as_str() {
+        "pack" => pack(rest),
+        "show" => show(rest),
+        "upload" => upload(rest),
+        _ => return usage(),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("imgtool: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
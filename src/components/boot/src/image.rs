@@ -0,0 +1,222 @@
+//! The header in front of an application image, and the checks the
+//! bootloader makes before running one.
+//!
+//! ```text
+//! offset  size
+//!      0     4  magic, "BIMG"
+//!      4     2  header format, 1
+//!      6     2  reserved, 0
+//!      8     4  image version, major << 24 | minor << 16 | patch
+//!     12     4  length of the image after the header
+//!     16     4  load address, where the vector table of the image is
+//!     20     4  CRC-32 of the image
+//!     24    32  SHA-256 of the image
+//!     56     4  CRC-32 of the 56 bytes above
+//! ```
+//!
+//! Fields are little-endian. The header is padded with 0xFF to
+//! `HEADER_SIZE`, which keeps the vector table that follows aligned for
+//! VTOR on every part with up to 112 interrupts.
+
+use core::fmt;
+
+use fault::crc::{crc32, crc32_update};
+
+use crate::sha256::{DIGEST_SIZE, Sha256};
+use crate::storage::{Region, Storage};
+
+pub const MAGIC: u32 = u32::from_le_bytes(*b"BIMG");
+
+/// Format of the header this module reads and writes
+pub const FORMAT: u16 = 1;
+
+/// Bytes of the header holding fields
+pub const HEADER_LEN: usize = 60;
+
+/// Bytes from the start of a slot to the vector table of its image
+pub const HEADER_SIZE: usize = 0x200;
+
+/// Bytes read from flash at a time while checking an image
+const CHUNK: usize = 256;
+
+/// Version of an image, ordered as the numbers read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32);
+
+impl Version {
+    pub const fn new(major: u8, minor: u8, patch: u16) -> Self {
+        Self((major as u32) << 24 | (minor as u32) << 16 | patch as u32)
+    }
+
+    pub const fn major(&self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+
+    pub const fn minor(&self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    pub const fn patch(&self) -> u16 {
+        self.0 as u16
+    }
+
+    /// Parse "major.minor.patch"
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major(), self.minor(), self.patch())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub version: Version,
+    /// Bytes of the image after the header
+    pub length: u32,
+    /// Address the image is linked at, that of its vector table
+    pub load_address: u32,
+    pub crc: u32,
+    pub sha256: [u8; DIGEST_SIZE],
+}
+
+fn word(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+impl Header {
+    /// The header of `image`, linked at `load_address`
+    pub fn new(version: Version, load_address: u32, image: &[u8]) -> Self {
+        Self {
+            version,
+            length: image.len() as u32,
+            load_address,
+            crc: crc32(image),
+            sha256: crate::sha256::digest(image),
+        }
+    }
+
+    pub fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut out = [0; HEADER_LEN];
+        out[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        out[4..6].copy_from_slice(&FORMAT.to_le_bytes());
+        out[8..12].copy_from_slice(&self.version.0.to_le_bytes());
+        out[12..16].copy_from_slice(&self.length.to_le_bytes());
+        out[16..20].copy_from_slice(&self.load_address.to_le_bytes());
+        out[20..24].copy_from_slice(&self.crc.to_le_bytes());
+        out[24..56].copy_from_slice(&self.sha256);
+        let check = crc32(&out[..56]);
+        out[56..60].copy_from_slice(&check.to_le_bytes());
+        out
+    }
+
+    /// Returns Err(-1) if `bytes` does not start with a header of this
+    /// format, Err(-4) if its check does not match.
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        if bytes.len() < HEADER_LEN
+            || word(bytes, 0) != MAGIC
+            || u16::from_le_bytes([bytes[4], bytes[5]]) != FORMAT
+        {
+            return Err(-1);
+        }
+        if word(bytes, 56) != crc32(&bytes[..56]) {
+            return Err(-4);
+        }
+        let mut sha256 = [0; DIGEST_SIZE];
+        sha256.copy_from_slice(&bytes[24..56]);
+        Ok(Self {
+            version: Version(word(bytes, 8)),
+            length: word(bytes, 12),
+            load_address: word(bytes, 16),
+            crc: word(bytes, 20),
+            sha256,
+        })
+    }
+
+    /// Check that `image` is the one described. Returns Err(-1) if its
+    /// length differs or its reset vector points outside it, Err(-4) if
+    /// the CRC or the hash does not match.
+    pub fn check(&self, image: &[u8]) -> Result<(), i32> {
+        if image.len() != self.length as usize {
+            return Err(-1);
+        }
+        self.check_reset_vector(image)?;
+        if crc32(image) != self.crc || crate::sha256::digest(image) != self.sha256 {
+            return Err(-4);
+        }
+        Ok(())
+    }
+
+    /// Returns Err(-1) unless the reset vector, the second word of the
+    /// vector table at the start of `image`, is a Thumb address inside
+    /// the image
+    fn check_reset_vector(&self, image: &[u8]) -> Result<(), i32> {
+        if image.len() < 8 {
+            return Err(-1);
+        }
+        let reset = word(image, 4);
+        let inside = reset >= self.load_address && reset - self.load_address < self.length;
+        if reset & 1 == 0 || !inside {
+            return Err(-1);
+        }
+        Ok(())
+    }
+}
+
+/// The whole image file for `image`: header, padding and image
+#[cfg(feature = "std")]
+pub fn pack(version: Version, load_address: u32, image: &[u8]) -> Vec<u8> {
+    let mut out = vec![0xFF; HEADER_SIZE];
+    out[..HEADER_LEN].copy_from_slice(&Header::new(version, load_address, image).encode());
+    out.extend_from_slice(image);
+    out
+}
+
+/// Read the header of the image written to `region` and check the image
+/// against it. Returns Err(-1) if there is no header, the image is not
+/// linked for the region or does not fit in it, Err(-4) if it does not
+/// match its header, and the error of `storage` if reading fails.
+pub fn verify<S: Storage>(storage: &mut S, region: Region) -> Result<Header, i32> {
+    let mut bytes = [0; HEADER_LEN];
+    storage.read(region.start, &mut bytes)?;
+    let header = Header::parse(&bytes)?;
+    let start = region.start + HEADER_SIZE as u32;
+    if header.load_address != start || !region.contains(start, header.length) {
+        return Err(-1);
+    }
+
+    let mut vectors = [0; 8];
+    storage.read(start, &mut vectors)?;
+    header.check_reset_vector(&vectors)?;
+
+    let mut crc = 0xFFFF_FFFF;
+    let mut hash = Sha256::new();
+    let mut chunk = [0; CHUNK];
+    let mut offset = 0;
+    while offset < header.length {
+        let len = CHUNK.min((header.length - offset) as usize);
+        storage.read(start + offset, &mut chunk[..len])?;
+        crc = crc32_update(crc, &chunk[..len]);
+        hash.update(&chunk[..len]);
+        offset += len as u32;
+    }
+    if !crc != header.crc || hash.finalize() != header.sha256 {
+        return Err(-4);
+    }
+    Ok(header)
+}
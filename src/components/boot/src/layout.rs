@@ -0,0 +1,102 @@
+//! Where the bootloader, its state and the two image slots sit in flash.
+//!
+//! Images run in place, so each is linked for the slot it is written to:
+//! its vector table follows the header at the start of the slot.
+
+use crate::image::HEADER_SIZE;
+use crate::storage::Region;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    A = 0,
+    B = 1,
+}
+
+impl Slot {
+    pub const ALL: [Slot; 2] = [Slot::A, Slot::B];
+
+    pub const fn other(self) -> Slot {
+        match self {
+            Slot::A => Slot::B,
+            Slot::B => Slot::A,
+        }
+    }
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn from_index(index: u8) -> Option<Slot> {
+        match index {
+            0 => Some(Slot::A),
+            1 => Some(Slot::B),
+            _ => None,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Slot::A => "A",
+            Slot::B => "B",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub bootloader: Region,
+    /// Journal of installs, boot attempts and confirmations
+    pub state: Region,
+    pub slots: [Region; 2],
+}
+
+impl Layout {
+    pub const fn slot(&self, slot: Slot) -> Region {
+        self.slots[slot.index()]
+    }
+
+    /// Address the image of `slot` is linked at, that of its vector table
+    pub const fn image_address(&self, slot: Slot) -> u32 {
+        self.slot(slot).start + HEADER_SIZE as u32
+    }
+
+    /// The slot whose image is linked at `address`
+    pub fn slot_at(&self, address: u32) -> Option<Slot> {
+        Slot::ALL
+            .into_iter()
+            .find(|&slot| self.image_address(slot) == address)
+    }
+
+    /// Returns Err(-1) if regions overlap, or a slot cannot hold a header
+    /// and a vector table
+    pub fn validate(&self) -> Result<(), i32> {
+        let regions = [self.bootloader, self.state, self.slots[0], self.slots[1]];
+        for (i, a) in regions.iter().enumerate() {
+            for b in &regions[i + 1..] {
+                if a.start < b.end() && b.start < a.end() {
+                    return Err(-1);
+                }
+            }
+        }
+        if self
+            .slots
+            .iter()
+            .any(|slot| slot.size <= HEADER_SIZE as u32)
+        {
+            return Err(-1);
+        }
+        Ok(())
+    }
+}
+
+/// STM32F407 with 1 MB of flash: the bootloader in sectors 0-1, its
+/// state in sector 2, slot A in sectors 5-7 and slot B in sectors 8-10.
+/// Sectors 3, 4 and 11 are left to the application.
+pub const STM32F407: Layout = Layout {
+    bootloader: Region::new(0x0800_0000, 32 * 1024),
+    state: Region::new(0x0800_8000, 16 * 1024),
+    slots: [
+        Region::new(0x0802_0000, 384 * 1024),
+        Region::new(0x0808_0000, 384 * 1024),
+    ],
+};
@@ -0,0 +1,9 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod image;
+pub mod layout;
+pub mod mock;
+pub mod protocol;
+pub mod sha256;
+pub mod state;
+pub mod storage;
@@ -0,0 +1,108 @@
+//! Flash in RAM for host tests, with uniform sectors and power that can
+//! be cut in the middle of programming.
+
+use crate::storage::{Region, Storage};
+
+pub struct Flash<const N: usize> {
+    base: u32,
+    sector_size: u32,
+    bytes: [u8; N],
+    /// Bytes programmed before the power is cut, if it is to be
+    budget: Option<usize>,
+    pub erases: u32,
+}
+
+impl<const N: usize> Flash<N> {
+    /// Erased flash of `N` bytes at `base`, in sectors of `sector_size`
+    pub fn new(base: u32, sector_size: u32) -> Self {
+        Self {
+            base,
+            sector_size,
+            bytes: [0xFF; N],
+            budget: None,
+            erases: 0,
+        }
+    }
+
+    /// Program only `bytes` more bytes, then fail every program with
+    /// Err(-3) as if the power had gone
+    pub fn cut_power_after(&mut self, bytes: usize) {
+        self.budget = Some(bytes);
+    }
+
+    pub fn restore_power(&mut self) {
+        self.budget = None;
+    }
+
+    /// The bytes at `address`
+    pub fn bytes(&self, address: u32, len: usize) -> &[u8] {
+        let offset = (address - self.base) as usize;
+        &self.bytes[offset..offset + len]
+    }
+
+    /// Write `data` at `address` as it is, erased or not
+    pub fn poke(&mut self, address: u32, data: &[u8]) {
+        let offset = (address - self.base) as usize;
+        self.bytes[offset..offset + data.len()].copy_from_slice(data);
+    }
+
+    fn range(&self, address: u32, len: usize) -> Result<core::ops::Range<usize>, i32> {
+        let offset = address.checked_sub(self.base).ok_or(-1)? as usize;
+        if offset + len > N {
+            return Err(-1);
+        }
+        Ok(offset..offset + len)
+    }
+}
+
+impl<const N: usize> Storage for Flash<N> {
+    /// Returns Err(-1) outside the flash
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), i32> {
+        let range = self.range(address, buf.len())?;
+        buf.copy_from_slice(&self.bytes[range]);
+        Ok(())
+    }
+
+    /// Returns Err(-1) outside the flash or over bytes not erased, and
+    /// Err(-3) once the power is cut
+    fn program(&mut self, address: u32, data: &[u8]) -> Result<(), i32> {
+        let range = self.range(address, data.len())?;
+        if self.bytes[range.clone()].iter().any(|&byte| byte != 0xFF) {
+            return Err(-1);
+        }
+        let len = self
+            .budget
+            .map_or(data.len(), |budget| budget.min(data.len()));
+        self.bytes[range.start..range.start + len].copy_from_slice(&data[..len]);
+        if let Some(budget) = &mut self.budget {
+            *budget -= len;
+            if len < data.len() {
+                return Err(-3);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns Err(-1) if the region does not start and end on sector
+    /// bounds inside the flash
+    fn erase(&mut self, region: Region) -> Result<(), i32> {
+        let range = self.range(region.start, region.size as usize)?;
+        let sector = self.sector_size as usize;
+        if range.start % sector != 0 || range.end % sector != 0 {
+            return Err(-1);
+        }
+        self.bytes[range].fill(0xFF);
+        self.erases += 1;
+        Ok(())
+    }
+}
+
+/// An image file for a body of `len` bytes linked at `load_address`,
+/// starting with a vector table whose reset vector points into it
+#[cfg(feature = "std")]
+pub fn image_file(version: crate::image::Version, load_address: u32, len: usize) -> Vec<u8> {
+    let mut body: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+    body[..4].copy_from_slice(&0x2001_C000u32.to_le_bytes());
+    body[4..8].copy_from_slice(&(load_address + 0x101).to_le_bytes());
+    crate::image::pack(version, load_address, &body)
+}
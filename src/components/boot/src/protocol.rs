@@ -0,0 +1,390 @@
+//! The update protocol: requests from the host and the responses of the
+//! bootloader, each the payload of one `link` packet, which frames,
+//! checks and acknowledges it.
+//!
+//! ```text
+//! request  code  after the code
+//! Info     0x01
+//! Begin    0x02  length of the image file, u32
+//! Data     0x03  offset in the file, u32, then up to MAX_DATA bytes
+//! End      0x04
+//! Boot     0x05
+//!
+//! response: code | 0x80, status, data
+//! ```
+//!
+//! Numbers are little-endian. The image file, header included, is written
+//! to the update slot from `Begin` to `End`, in order; `End` checks the
+//! image and installs it as the trial, and `Boot` leaves the bootloader.
+//! Only `Info` and `End` answer with data, `Info` and the image version.
+
+use link::packet::MAX_PAYLOAD;
+
+use crate::image::{self, HEADER_SIZE, Version};
+use crate::layout::{Layout, Slot};
+use crate::state::{Journal, Record};
+use crate::storage::Storage;
+
+pub mod code {
+    pub const INFO: u8 = 0x01;
+    pub const BEGIN: u8 = 0x02;
+    pub const DATA: u8 = 0x03;
+    pub const END: u8 = 0x04;
+    pub const BOOT: u8 = 0x05;
+    /// Set in the code of a response
+    pub const RESPONSE: u8 = 0x80;
+}
+
+/// Longest data of a `Data` request
+pub const MAX_DATA: usize = MAX_PAYLOAD - 5;
+
+/// Longest response
+pub const MAX_RESPONSE: usize = 2 + INFO_LEN;
+
+const INFO_LEN: usize = 20;
+
+/// No slot, in `Info`
+const NO_SLOT: u8 = 0xFF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Request<'a> {
+    Info,
+    /// Erase the update slot for an image file of `length` bytes
+    Begin {
+        length: u32,
+    },
+    Data {
+        offset: u32,
+        bytes: &'a [u8],
+    },
+    /// Check the image written and install it
+    End,
+    /// Leave the bootloader, and boot what the boot state says
+    Boot,
+}
+
+impl<'a> Request<'a> {
+    /// Write the request into `out` and return its length. Returns Err(-1)
+    /// if `out` is too short or the data longer than `MAX_DATA`.
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, i32> {
+        let len = match self {
+            Request::Begin { .. } => 5,
+            Request::Data { bytes, .. } if bytes.len() <= MAX_DATA => 5 + bytes.len(),
+            Request::Data { .. } => return Err(-1),
+            _ => 1,
+        };
+        if out.len() < len {
+            return Err(-1);
+        }
+        match *self {
+            Request::Info => out[0] = code::INFO,
+            Request::Begin { length } => {
+                out[0] = code::BEGIN;
+                out[1..5].copy_from_slice(&length.to_le_bytes());
+            }
+            Request::Data { offset, bytes } => {
+                out[0] = code::DATA;
+                out[1..5].copy_from_slice(&offset.to_le_bytes());
+                out[5..len].copy_from_slice(bytes);
+            }
+            Request::End => out[0] = code::END,
+            Request::Boot => out[0] = code::BOOT,
+        }
+        Ok(len)
+    }
+
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, Status> {
+        let number = |bytes: &[u8]| u32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]);
+        match bytes {
+            [code::INFO] => Ok(Request::Info),
+            [code::BEGIN, _, _, _, _] => Ok(Request::Begin {
+                length: number(bytes),
+            }),
+            [code::DATA, _, _, _, _, ..] if bytes.len() - 5 <= MAX_DATA => Ok(Request::Data {
+                offset: number(bytes),
+                bytes: &bytes[5..],
+            }),
+            [code::END] => Ok(Request::End),
+            [code::BOOT] => Ok(Request::Boot),
+            _ => Err(Status::Invalid),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    /// Not a request
+    Invalid = 1,
+    /// Data or End without Begin, or data not following the last
+    Sequence = 2,
+    /// The image does not fit in the slot
+    TooLarge = 3,
+    /// Erasing or programming failed
+    Flash = 4,
+    /// The image written does not check out
+    Image = 5,
+}
+
+impl Status {
+    pub fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Status::Ok,
+            1 => Status::Invalid,
+            2 => Status::Sequence,
+            3 => Status::TooLarge,
+            4 => Status::Flash,
+            5 => Status::Image,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Response<'a> {
+    /// Code of the request answered
+    pub request: u8,
+    pub status: Status,
+    pub data: &'a [u8],
+}
+
+impl<'a> Response<'a> {
+    /// Returns Err(-1) if `bytes` is not a response
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, i32> {
+        match bytes {
+            [code, status, data @ ..] if code & code::RESPONSE != 0 => Ok(Self {
+                request: code & !code::RESPONSE,
+                status: Status::from_code(*status).ok_or(-1)?,
+                data,
+            }),
+            _ => Err(-1),
+        }
+    }
+}
+
+/// Answer to `Info`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Info {
+    pub bootloader: Version,
+    /// Slot the next image is written to
+    pub update_slot: Slot,
+    /// Address the image for the update slot must be linked at
+    pub update_address: u32,
+    /// Longest image file the update slot holds
+    pub capacity: u32,
+    pub active: Option<Slot>,
+    pub trial: Option<Slot>,
+    /// Version of the image the bootloader boots, if any
+    pub running: Option<Version>,
+}
+
+impl Info {
+    pub fn encode(&self) -> [u8; INFO_LEN] {
+        let slot = |slot: Option<Slot>| slot.map_or(NO_SLOT, |slot| slot as u8);
+        let mut out = [0; INFO_LEN];
+        out[0..4].copy_from_slice(&self.bootloader.0.to_le_bytes());
+        out[4] = self.update_slot as u8;
+        out[5..9].copy_from_slice(&self.update_address.to_le_bytes());
+        out[9..13].copy_from_slice(&self.capacity.to_le_bytes());
+        out[13] = slot(self.active);
+        out[14] = slot(self.trial);
+        out[15] = self.running.is_some() as u8;
+        let running = self.running.map_or(0, |version| version.0);
+        out[16..20].copy_from_slice(&running.to_le_bytes());
+        out
+    }
+
+    /// Returns Err(-1) if `bytes` is too short or names an unknown slot
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        if bytes.len() < INFO_LEN {
+            return Err(-1);
+        }
+        let number = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        let slot = |at: usize| match bytes[at] {
+            NO_SLOT => Ok(None),
+            index => Slot::from_index(index).map(Some).ok_or(-1),
+        };
+        Ok(Self {
+            bootloader: Version(number(0)),
+            update_slot: Slot::from_index(bytes[4]).ok_or(-1)?,
+            update_address: number(5),
+            capacity: number(9),
+            active: slot(13)?,
+            trial: slot(14)?,
+            running: (bytes[15] != 0).then(|| Version(number(16))),
+        })
+    }
+}
+
+/// An image file being written
+#[derive(Clone, Copy)]
+struct Transfer {
+    slot: Slot,
+    length: u32,
+    written: u32,
+}
+
+/// The bootloader side: carries out requests on the flash and the boot
+/// state
+pub struct Updater {
+    layout: Layout,
+    version: Version,
+    transfer: Option<Transfer>,
+    boot: bool,
+}
+
+impl Updater {
+    /// An updater for the slots of `layout`, in a bootloader of `version`
+    pub fn new(layout: Layout, version: Version) -> Self {
+        Self {
+            layout,
+            version,
+            transfer: None,
+            boot: false,
+        }
+    }
+
+    /// Whether the host asked to leave the bootloader
+    pub fn boot_requested(&self) -> bool {
+        self.boot
+    }
+
+    /// Slot the next image is written to: never that of the confirmed
+    /// image, nor without one, that of an image that checks out
+    pub fn update_slot<S: Storage>(&self, storage: &mut S, journal: &Journal) -> Slot {
+        if let Some(active) = journal.state().active {
+            return active.other();
+        }
+        Slot::ALL
+            .into_iter()
+            .find(|&slot| image::verify(storage, self.layout.slot(slot)).is_ok())
+            .map_or(Slot::A, Slot::other)
+    }
+
+    /// Carry out the request `bytes` and write the response into `out`,
+    /// returning its length
+    pub fn handle<S: Storage>(
+        &mut self,
+        storage: &mut S,
+        journal: &mut Journal,
+        bytes: &[u8],
+        out: &mut [u8; MAX_RESPONSE],
+    ) -> usize {
+        let request = bytes.first().copied().unwrap_or(0);
+        let mut data = [0; INFO_LEN];
+        let (status, len) = match Request::parse(bytes) {
+            Ok(request) => self.serve(storage, journal, request, &mut data),
+            Err(status) => (status, 0),
+        };
+        out[0] = request | code::RESPONSE;
+        out[1] = status as u8;
+        out[2..2 + len].copy_from_slice(&data[..len]);
+        2 + len
+    }
+
+    fn serve<S: Storage>(
+        &mut self,
+        storage: &mut S,
+        journal: &mut Journal,
+        request: Request<'_>,
+        data: &mut [u8; INFO_LEN],
+    ) -> (Status, usize) {
+        match request {
+            Request::Info => {
+                *data = self.info(storage, journal).encode();
+                (Status::Ok, INFO_LEN)
+            }
+            Request::Begin { length } => (self.begin(storage, journal, length), 0),
+            Request::Data { offset, bytes } => (self.data(storage, offset, bytes), 0),
+            Request::End => match self.end(storage, journal) {
+                Ok(version) => {
+                    data[..4].copy_from_slice(&version.0.to_le_bytes());
+                    (Status::Ok, 4)
+                }
+                Err(status) => (status, 0),
+            },
+            Request::Boot => {
+                self.boot = true;
+                (Status::Ok, 0)
+            }
+        }
+    }
+
+    fn info<S: Storage>(&self, storage: &mut S, journal: &Journal) -> Info {
+        let state = journal.state();
+        let slot = self.update_slot(storage, journal);
+        let running = state
+            .trial
+            .or(state.active)
+            .and_then(|slot| image::verify(storage, self.layout.slot(slot)).ok())
+            .map(|header| header.version);
+        Info {
+            bootloader: self.version,
+            update_slot: slot,
+            update_address: self.layout.image_address(slot),
+            capacity: self.layout.slot(slot).size,
+            active: state.active,
+            trial: state.trial,
+            running,
+        }
+    }
+
+    fn begin<S: Storage>(&mut self, storage: &mut S, journal: &Journal, length: u32) -> Status {
+        self.transfer = None;
+        let slot = self.update_slot(storage, journal);
+        let region = self.layout.slot(slot);
+        if length <= HEADER_SIZE as u32 {
+            return Status::Invalid;
+        }
+        if length > region.size {
+            return Status::TooLarge;
+        }
+        if storage.erase(region).is_err() {
+            return Status::Flash;
+        }
+        self.transfer = Some(Transfer {
+            slot,
+            length,
+            written: 0,
+        });
+        Status::Ok
+    }
+
+    fn data<S: Storage>(&mut self, storage: &mut S, offset: u32, bytes: &[u8]) -> Status {
+        let Some(transfer) = &mut self.transfer else {
+            return Status::Sequence;
+        };
+        if offset != transfer.written {
+            return Status::Sequence;
+        }
+        if bytes.len() as u32 > transfer.length - offset {
+            return Status::TooLarge;
+        }
+        let address = self.layout.slot(transfer.slot).start + offset;
+        if storage.program(address, bytes).is_err() {
+            self.transfer = None;
+            return Status::Flash;
+        }
+        transfer.written += bytes.len() as u32;
+        Status::Ok
+    }
+
+    fn end<S: Storage>(
+        &mut self,
+        storage: &mut S,
+        journal: &mut Journal,
+    ) -> Result<Version, Status> {
+        let transfer = self.transfer.take().ok_or(Status::Sequence)?;
+        if transfer.written != transfer.length {
+            return Err(Status::Sequence);
+        }
+        let header =
+            image::verify(storage, self.layout.slot(transfer.slot)).map_err(|_| Status::Image)?;
+        journal
+            .append(storage, Record::Install(transfer.slot))
+            .map_err(|_| Status::Flash)?;
+        Ok(header.version)
+    }
+}
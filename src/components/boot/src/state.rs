@@ -0,0 +1,259 @@
+//! Which slot to boot, kept as a journal of records appended to a flash
+//! region, so that nothing is erased until the region fills up.
+//!
+//! A new image is installed in a slot as a trial. The bootloader records
+//! an attempt each time it boots the trial, and the image records a
+//! confirmation once it runs well. A trial that used up its attempts
+//! without being confirmed, or that no longer checks out, is reverted,
+//! and the confirmed image boots again.
+//!
+//! ```text
+//! | kind | slot | !kind | !slot | CRC-32 of the 4 bytes before |
+//! ```
+//!
+//! A record that is erased ends the journal. One whose check does not
+//! match, written when power failed, is skipped.
+
+use fault::crc::crc32;
+
+use crate::layout::Slot;
+use crate::storage::{Region, Storage};
+
+/// Bytes of a record
+pub const RECORD_SIZE: usize = 8;
+
+/// Boots a trial image gets from the bootloader before it is reverted
+pub const MAX_ATTEMPTS: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Record {
+    /// A new image was written to the slot, to be tried
+    Install(Slot),
+    /// The bootloader started the trial image of the slot
+    Attempt(Slot),
+    /// The image of the slot runs well
+    Confirm(Slot),
+    /// The trial image of the slot failed and is given up
+    Revert(Slot),
+}
+
+impl Record {
+    const fn kind(&self) -> u8 {
+        match self {
+            Record::Install(_) => 1,
+            Record::Attempt(_) => 2,
+            Record::Confirm(_) => 3,
+            Record::Revert(_) => 4,
+        }
+    }
+
+    pub const fn slot(&self) -> Slot {
+        match *self {
+            Record::Install(slot)
+            | Record::Attempt(slot)
+            | Record::Confirm(slot)
+            | Record::Revert(slot) => slot,
+        }
+    }
+
+    pub fn encode(&self) -> [u8; RECORD_SIZE] {
+        let kind = self.kind();
+        let slot = self.slot() as u8;
+        let mut out = [kind, slot, !kind, !slot, 0, 0, 0, 0];
+        let check = crc32(&out[..4]);
+        out[4..].copy_from_slice(&check.to_le_bytes());
+        out
+    }
+
+    /// Returns Err(-4) if the check does not match, Err(-1) for a record
+    /// of an unknown kind
+    pub fn parse(bytes: &[u8; RECORD_SIZE]) -> Result<Self, i32> {
+        let [kind, slot, not_kind, not_slot, ..] = *bytes;
+        let check = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        if kind != !not_kind || slot != !not_slot || check != crc32(&bytes[..4]) {
+            return Err(-4);
+        }
+        let slot = Slot::from_index(slot).ok_or(-1)?;
+        match kind {
+            1 => Ok(Record::Install(slot)),
+            2 => Ok(Record::Attempt(slot)),
+            3 => Ok(Record::Confirm(slot)),
+            4 => Ok(Record::Revert(slot)),
+            _ => Err(-1),
+        }
+    }
+}
+
+/// What to do at reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Record an attempt and boot the trial image of the slot
+    Trial(Slot),
+    /// Record that the trial of the slot is reverted, and decide again
+    Revert(Slot),
+    /// Boot the image of the slot
+    Boot(Slot),
+    /// Nothing to boot, wait for an image
+    Wait,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct State {
+    /// Slot of the image last confirmed
+    pub active: Option<Slot>,
+    /// Slot of an image installed and not yet confirmed or reverted
+    pub trial: Option<Slot>,
+    /// Times the trial image was booted
+    pub attempts: u8,
+    /// Slot of the image last reverted, not booted unless installed again
+    pub rejected: Option<Slot>,
+}
+
+impl State {
+    pub fn apply(&mut self, record: Record) {
+        match record {
+            Record::Install(slot) => {
+                self.trial = Some(slot);
+                self.attempts = 0;
+                // The previous image of the slot was erased
+                if self.active == Some(slot) {
+                    self.active = None;
+                }
+                if self.rejected == Some(slot) {
+                    self.rejected = None;
+                }
+            }
+            Record::Attempt(slot) => {
+                if self.trial == Some(slot) {
+                    self.attempts = self.attempts.saturating_add(1);
+                }
+            }
+            Record::Confirm(slot) => {
+                self.active = Some(slot);
+                if self.trial == Some(slot) {
+                    self.trial = None;
+                    self.attempts = 0;
+                }
+                if self.rejected == Some(slot) {
+                    self.rejected = None;
+                }
+            }
+            Record::Revert(slot) => {
+                if self.trial == Some(slot) {
+                    self.trial = None;
+                    self.attempts = 0;
+                    self.rejected = Some(slot);
+                }
+            }
+        }
+    }
+
+    /// Decide what to boot, with `max_attempts` boots for a trial image
+    /// and `valid` telling whether the image in a slot checks out.
+    ///
+    /// Without a confirmed image, as after programming one with a probe,
+    /// the first valid slot that was not rejected boots, and is recorded
+    /// as confirmed once the image confirms itself.
+    pub fn decide(&self, max_attempts: u8, mut valid: impl FnMut(Slot) -> bool) -> Decision {
+        if let Some(trial) = self.trial {
+            if self.attempts >= max_attempts || !valid(trial) {
+                return Decision::Revert(trial);
+            }
+            return Decision::Trial(trial);
+        }
+        if let Some(active) = self.active
+            && valid(active)
+        {
+            return Decision::Boot(active);
+        }
+        Slot::ALL
+            .into_iter()
+            .filter(|&slot| Some(slot) != self.active && Some(slot) != self.rejected)
+            .find(|&slot| valid(slot))
+            .map_or(Decision::Wait, Decision::Boot)
+    }
+
+    /// The records that rebuild this state in an empty journal
+    fn records(&self) -> impl Iterator<Item = Record> {
+        let active = self.active.map(Record::Confirm);
+        let rejected = self
+            .rejected
+            .map(|slot| [Record::Install(slot), Record::Revert(slot)]);
+        let trial = self.trial.map(|slot| {
+            core::iter::once(Record::Install(slot)).chain(core::iter::repeat_n(
+                Record::Attempt(slot),
+                self.attempts as usize,
+            ))
+        });
+        rejected
+            .into_iter()
+            .flatten()
+            .chain(active)
+            .chain(trial.into_iter().flatten())
+    }
+}
+
+/// The journal in a flash region
+pub struct Journal {
+    region: Region,
+    /// Address of the next record
+    next: u32,
+    state: State,
+}
+
+impl Journal {
+    /// Replay the journal in `region`. An erased region is an empty
+    /// journal.
+    pub fn open<S: Storage>(storage: &mut S, region: Region) -> Result<Self, i32> {
+        let mut state = State::default();
+        let mut next = region.start;
+        let mut bytes = [0; RECORD_SIZE];
+        while region.contains(next, RECORD_SIZE as u32) {
+            storage.read(next, &mut bytes)?;
+            if bytes.iter().all(|&byte| byte == 0xFF) {
+                break;
+            }
+            if let Ok(record) = Record::parse(&bytes) {
+                state.apply(record);
+            }
+            next += RECORD_SIZE as u32;
+        }
+        Ok(Self {
+            region,
+            next,
+            state,
+        })
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Records that still fit before the region fills up
+    pub fn free(&self) -> usize {
+        ((self.region.end() - self.next) as usize) / RECORD_SIZE
+    }
+
+    /// Append `record`. A full region is erased and the state written
+    /// back first; power lost in between loses the state, and the next
+    /// boot falls back to the first valid image.
+    pub fn append<S: Storage>(&mut self, storage: &mut S, record: Record) -> Result<(), i32> {
+        if self.free() == 0 {
+            self.compact(storage)?;
+        }
+        storage.program(self.next, &record.encode())?;
+        self.next += RECORD_SIZE as u32;
+        self.state.apply(record);
+        Ok(())
+    }
+
+    fn compact<S: Storage>(&mut self, storage: &mut S) -> Result<(), i32> {
+        storage.erase(self.region)?;
+        self.next = self.region.start;
+        for record in self.state.records() {
+            storage.program(self.next, &record.encode())?;
+            self.next += RECORD_SIZE as u32;
+        }
+        Ok(())
+    }
+}
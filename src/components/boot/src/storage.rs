@@ -0,0 +1,49 @@
+//! The flash the slots and the boot state live in.
+
+/// A range of flash, starting and ending on sector bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub start: u32,
+    pub size: u32,
+}
+
+impl Region {
+    pub const fn new(start: u32, size: u32) -> Self {
+        Self { start, size }
+    }
+
+    /// First address past the region
+    pub const fn end(&self) -> u32 {
+        self.start + self.size
+    }
+
+    pub const fn contains(&self, address: u32, len: u32) -> bool {
+        address >= self.start && len <= self.size && address - self.start <= self.size - len
+    }
+}
+
+/// Flash as the bootloader sees it: erased to 0xFF a sector at a time,
+/// and programmed once between erases. Addresses are absolute.
+pub trait Storage {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), i32>;
+
+    /// Program `data` at `address`, which must have been erased
+    fn program(&mut self, address: u32, data: &[u8]) -> Result<(), i32>;
+
+    /// Erase the sectors making up `region`
+    fn erase(&mut self, region: Region) -> Result<(), i32>;
+}
+
+impl<S: Storage> Storage for &mut S {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), i32> {
+        (**self).read(address, buf)
+    }
+
+    fn program(&mut self, address: u32, data: &[u8]) -> Result<(), i32> {
+        (**self).program(address, data)
+    }
+
+    fn erase(&mut self, region: Region) -> Result<(), i32> {
+        (**self).erase(region)
+    }
+}
@@ -0,0 +1,129 @@
+use boot::image::*;
+use boot::mock::{self, Flash};
+use boot::storage::{Region, Storage};
+
+const SLOT: Region = Region::new(0x0800_2000, 0x2000);
+const LOAD: u32 = 0x0800_2000 + HEADER_SIZE as u32;
+
+fn flash_with(file: &[u8]) -> Flash<0x4000> {
+    let mut flash = Flash::new(0x0800_0000, 0x400);
+    flash.program(SLOT.start, file).unwrap();
+    flash
+}
+
+#[test]
+fn test_version() {
+    let version = Version::new(1, 2, 300);
+    assert_eq!(version.0, 0x0102_012C);
+    assert_eq!(version.to_string(), "1.2.300");
+    assert_eq!(Version::parse("1.2.300"), Some(version));
+    assert_eq!(Version::parse("1.2"), None);
+    assert_eq!(Version::parse("1.2.3.4"), None);
+    assert_eq!(Version::parse("256.0.0"), None);
+    assert!(Version::new(1, 10, 0) > Version::new(1, 9, 99));
+}
+
+#[test]
+fn test_header_round_trip() {
+    let file = mock::image_file(Version::new(2, 0, 1), LOAD, 1000);
+    let header = Header::parse(&file).unwrap();
+    assert_eq!(header.version, Version::new(2, 0, 1));
+    assert_eq!(header.length, 1000);
+    assert_eq!(header.load_address, LOAD);
+    assert_eq!(header.crc, fault::crc::crc32(&file[HEADER_SIZE..]));
+    assert_eq!(header.encode()[..], file[..HEADER_LEN]);
+    assert!(file[HEADER_LEN..HEADER_SIZE].iter().all(|&b| b == 0xFF));
+    assert_eq!(header.check(&file[HEADER_SIZE..]), Ok(()));
+}
+
+#[test]
+fn test_header_rejected() {
+    let file = mock::image_file(Version::new(1, 0, 0), LOAD, 1000);
+    assert_eq!(Header::parse(&file[..HEADER_LEN - 1]), Err(-1));
+
+    let mut bad = file.clone();
+    bad[0] ^= 1;
+    assert_eq!(Header::parse(&bad), Err(-1));
+
+    let mut bad = file.clone();
+    bad[4] = 2;
+    assert_eq!(Header::parse(&bad), Err(-1));
+
+    // A flipped bit in a field is caught by the check of the header
+    let mut bad = file.clone();
+    bad[12] ^= 0x10;
+    assert_eq!(Header::parse(&bad), Err(-4));
+
+    // Erased flash
+    assert_eq!(Header::parse(&[0xFF; HEADER_LEN]), Err(-1));
+}
+
+#[test]
+fn test_check_image() {
+    let file = mock::image_file(Version::new(1, 0, 0), LOAD, 1000);
+    let header = Header::parse(&file).unwrap();
+    let body = &file[HEADER_SIZE..];
+
+    let mut corrupt = body.to_vec();
+    corrupt[500] ^= 0x80;
+    assert_eq!(header.check(&corrupt), Err(-4));
+    assert_eq!(header.check(&body[..999]), Err(-1));
+
+    // Bytes swapped keep the length but not the hash
+    let mut swapped = body.to_vec();
+    swapped.swap(100, 101);
+    assert_eq!(header.check(&swapped), Err(-4));
+}
+
+#[test]
+fn test_reset_vector() {
+    let mut body = vec![0u8; 1000];
+    // Even: not a Thumb address
+    body[4..8].copy_from_slice(&(LOAD + 0x100).to_le_bytes());
+    assert_eq!(Header::new(Version(1), LOAD, &body).check(&body), Err(-1));
+    // Past the end
+    body[4..8].copy_from_slice(&(LOAD + 1001).to_le_bytes());
+    assert_eq!(Header::new(Version(1), LOAD, &body).check(&body), Err(-1));
+    // Linked for another address
+    body[4..8].copy_from_slice(&(LOAD + 0x101).to_le_bytes());
+    let header = Header::new(Version(1), LOAD + 0x6000, &body);
+    assert_eq!(header.check(&body), Err(-1));
+    assert_eq!(Header::new(Version(1), LOAD, &body).check(&body), Ok(()));
+}
+
+#[test]
+fn test_verify() {
+    let file = mock::image_file(Version::new(3, 1, 4), LOAD, 3000);
+    let mut flash = flash_with(&file);
+    let header = verify(&mut flash, SLOT).unwrap();
+    assert_eq!(header.version, Version::new(3, 1, 4));
+    assert_eq!(header.length, 3000);
+}
+
+#[test]
+fn test_verify_rejects() {
+    // Nothing written
+    let mut flash: Flash<0x4000> = Flash::new(0x0800_0000, 0x400);
+    assert_eq!(verify(&mut flash, SLOT), Err(-1));
+
+    // A byte of the image changed in flash
+    let file = mock::image_file(Version::new(1, 0, 0), LOAD, 3000);
+    let mut flash = flash_with(&file);
+    flash.poke(LOAD + 2000, &[file[HEADER_SIZE + 2000] ^ 1]);
+    assert_eq!(verify(&mut flash, SLOT), Err(-4));
+
+    // Written halfway
+    let mut flash = flash_with(&file[..2000]);
+    assert_eq!(verify(&mut flash, SLOT), Err(-4));
+
+    // Linked for another slot
+    let other = mock::image_file(Version::new(1, 0, 0), LOAD + 0x2000, 3000);
+    let mut flash = flash_with(&other);
+    assert_eq!(verify(&mut flash, SLOT), Err(-1));
+
+    // Longer than the slot
+    let long = mock::image_file(Version::new(1, 0, 0), LOAD, 0x2000);
+    let mut flash: Flash<0x8000> = Flash::new(0x0800_0000, 0x400);
+    flash.program(SLOT.start, &long).unwrap();
+    assert_eq!(verify(&mut flash, SLOT), Err(-1));
+}
@@ -0,0 +1,51 @@
+use boot::image::HEADER_SIZE;
+use boot::layout::*;
+use boot::storage::Region;
+
+#[test]
+fn test_slots() {
+    assert_eq!(Slot::A.other(), Slot::B);
+    assert_eq!(Slot::B.other(), Slot::A);
+    assert_eq!(Slot::from_index(1), Some(Slot::B));
+    assert_eq!(Slot::from_index(2), None);
+    assert_eq!(Slot::B.name(), "B");
+}
+
+#[test]
+fn test_stm32f407() {
+    assert_eq!(STM32F407.validate(), Ok(()));
+    assert_eq!(STM32F407.image_address(Slot::A), 0x0802_0200);
+    assert_eq!(STM32F407.image_address(Slot::B), 0x0808_0200);
+    assert_eq!(STM32F407.slot_at(0x0808_0200), Some(Slot::B));
+    assert_eq!(STM32F407.slot_at(0x0808_0000), None);
+    // Slots end inside the 1 MB of flash
+    assert!(STM32F407.slots.iter().all(|slot| slot.end() <= 0x0810_0000));
+    // The vector table is aligned for VTOR
+    assert_eq!(STM32F407.image_address(Slot::A) % 512, 0);
+}
+
+#[test]
+fn test_validate() {
+    let mut layout = STM32F407;
+    layout.state = Region::new(0x0800_4000, 0x4000);
+    assert_eq!(layout.validate(), Err(-1));
+
+    let mut layout = STM32F407;
+    layout.slots[1] = Region::new(0x0807_0000, 0x20000);
+    assert_eq!(layout.validate(), Err(-1));
+
+    let mut layout = STM32F407;
+    layout.slots[0].size = HEADER_SIZE as u32;
+    assert_eq!(layout.validate(), Err(-1));
+}
+
+#[test]
+fn test_region() {
+    let region = Region::new(0x1000, 0x100);
+    assert_eq!(region.end(), 0x1100);
+    assert!(region.contains(0x1000, 0x100));
+    assert!(region.contains(0x10F8, 8));
+    assert!(!region.contains(0x10F9, 8));
+    assert!(!region.contains(0x0FFF, 1));
+    assert!(!region.contains(0x1000, 0x101));
+}
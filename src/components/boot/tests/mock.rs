@@ -0,0 +1,50 @@
+use boot::mock::Flash;
+use boot::storage::{Region, Storage};
+
+fn flash() -> Flash<0x1000> {
+    Flash::new(0x0800_0000, 0x400)
+}
+
+#[test]
+fn test_program_and_read() {
+    let mut flash = flash();
+    flash.program(0x0800_0010, &[1, 2, 3]).unwrap();
+    let mut buf = [0; 5];
+    flash.read(0x0800_000F, &mut buf).unwrap();
+    assert_eq!(buf, [0xFF, 1, 2, 3, 0xFF]);
+}
+
+#[test]
+fn test_program_needs_erased_flash() {
+    let mut flash = flash();
+    flash.program(0x0800_0010, &[1]).unwrap();
+    assert_eq!(flash.program(0x0800_000F, &[0, 0]), Err(-1));
+    flash.erase(Region::new(0x0800_0000, 0x400)).unwrap();
+    flash.program(0x0800_0010, &[2]).unwrap();
+    assert_eq!(flash.bytes(0x0800_0010, 1), [2]);
+}
+
+#[test]
+fn test_bounds() {
+    let mut flash = flash();
+    let mut buf = [0; 2];
+    assert_eq!(flash.read(0x0800_0FFF, &mut buf), Err(-1));
+    assert_eq!(flash.read(0x07FF_FFFF, &mut buf), Err(-1));
+    assert_eq!(flash.program(0x0800_1000, &[0]), Err(-1));
+    // Not on sector bounds
+    assert_eq!(flash.erase(Region::new(0x0800_0200, 0x400)), Err(-1));
+    assert_eq!(flash.erase(Region::new(0x0800_0000, 0x200)), Err(-1));
+    assert_eq!(flash.erases, 0);
+}
+
+#[test]
+fn test_power_cut() {
+    let mut flash = flash();
+    flash.cut_power_after(3);
+    assert_eq!(flash.program(0x0800_0000, &[1, 2]), Ok(()));
+    assert_eq!(flash.program(0x0800_0002, &[3, 4]), Err(-3));
+    assert_eq!(flash.bytes(0x0800_0000, 4), [1, 2, 3, 0xFF]);
+    assert_eq!(flash.program(0x0800_0004, &[5]), Err(-3));
+    flash.restore_power();
+    assert_eq!(flash.program(0x0800_0004, &[5]), Ok(()));
+}
@@ -0,0 +1,6 @@
+pub mod image;
+pub mod layout;
+pub mod mock;
+pub mod protocol;
+pub mod sha256;
+pub mod state;
@@ -0,0 +1,385 @@
+use boot::image::{HEADER_SIZE, Version};
+use boot::layout::{Layout, Slot};
+use boot::mock::{self, Flash};
+use boot::protocol::*;
+use boot::state::{Decision, Journal, Record};
+use boot::storage::{Region, Storage};
+
+const LAYOUT: Layout = Layout {
+    bootloader: Region::new(0x0800_0000, 0x1000),
+    state: Region::new(0x0800_1000, 0x400),
+    slots: [
+        Region::new(0x0800_2000, 0x2000),
+        Region::new(0x0800_4000, 0x2000),
+    ],
+};
+
+const BOOTLOADER: Version = Version::new(1, 0, 0);
+
+struct Device {
+    flash: Flash<0x6000>,
+    journal: Journal,
+    updater: Updater,
+}
+
+impl Device {
+    fn new() -> Self {
+        let mut flash = Flash::new(0x0800_0000, 0x400);
+        let journal = Journal::open(&mut flash, LAYOUT.state).unwrap();
+        Self {
+            flash,
+            journal,
+            updater: Updater::new(LAYOUT, BOOTLOADER),
+        }
+    }
+
+    /// An image of `version` programmed in `slot`, as with a probe
+    fn with_image(slot: Slot, version: Version) -> Self {
+        let mut device = Self::new();
+        let file = image(slot, version);
+        device
+            .flash
+            .program(LAYOUT.slot(slot).start, &file)
+            .unwrap();
+        device
+    }
+
+    /// Send `request` and return the status and data of the response
+    fn send(&mut self, request: &Request<'_>) -> (Status, Vec<u8>) {
+        let mut bytes = [0; 256];
+        let len = request.encode(&mut bytes).unwrap();
+        let mut out = [0; MAX_RESPONSE];
+        let len = self
+            .updater
+            .handle(&mut self.flash, &mut self.journal, &bytes[..len], &mut out);
+        let response = Response::parse(&out[..len]).unwrap();
+        assert_eq!(response.request, bytes[0]);
+        (response.status, response.data.to_vec())
+    }
+
+    fn info(&mut self) -> Info {
+        let (status, data) = self.send(&Request::Info);
+        assert_eq!(status, Status::Ok);
+        Info::parse(&data).unwrap()
+    }
+
+    /// Write `file` and install it, returning the status of the first
+    /// request that failed
+    fn upload(&mut self, file: &[u8]) -> Result<Version, Status> {
+        let length = file.len() as u32;
+        match self.send(&Request::Begin { length }).0 {
+            Status::Ok => {}
+            status => return Err(status),
+        }
+        for (index, chunk) in file.chunks(MAX_DATA).enumerate() {
+            let offset = (index * MAX_DATA) as u32;
+            match self
+                .send(&Request::Data {
+                    offset,
+                    bytes: chunk,
+                })
+                .0
+            {
+                Status::Ok => {}
+                status => return Err(status),
+            }
+        }
+        match self.send(&Request::End) {
+            (Status::Ok, data) => Ok(Version(u32::from_le_bytes(data[..4].try_into().unwrap()))),
+            (status, _) => Err(status),
+        }
+    }
+
+    fn decide(&mut self) -> Decision {
+        let flash = &mut self.flash;
+        self.journal.state().decide(3, |slot| {
+            boot::image::verify(flash, LAYOUT.slot(slot)).is_ok()
+        })
+    }
+}
+
+fn image(slot: Slot, version: Version) -> Vec<u8> {
+    mock::image_file(version, LAYOUT.image_address(slot), 5000)
+}
+
+#[test]
+fn test_request_round_trip() {
+    let data = [7u8; MAX_DATA];
+    for request in [
+        Request::Info,
+        Request::Begin { length: 0x12345 },
+        Request::Data {
+            offset: 0x200,
+            bytes: &data,
+        },
+        Request::Data {
+            offset: 0,
+            bytes: &[],
+        },
+        Request::End,
+        Request::Boot,
+    ] {
+        let mut bytes = [0; 256];
+        let len = request.encode(&mut bytes).unwrap();
+        assert_eq!(Request::parse(&bytes[..len]), Ok(request));
+    }
+}
+
+#[test]
+fn test_request_rejected() {
+    let data = [0u8; MAX_DATA + 1];
+    let mut bytes = [0; 512];
+    let too_long = Request::Data {
+        offset: 0,
+        bytes: &data,
+    };
+    assert_eq!(too_long.encode(&mut bytes), Err(-1));
+    assert_eq!(
+        Request::Begin { length: 1 }.encode(&mut bytes[..4]),
+        Err(-1)
+    );
+
+    assert_eq!(Request::parse(&[]), Err(Status::Invalid));
+    assert_eq!(Request::parse(&[0x42]), Err(Status::Invalid));
+    assert_eq!(Request::parse(&[code::INFO, 0]), Err(Status::Invalid));
+    assert_eq!(Request::parse(&[code::BEGIN, 1, 2]), Err(Status::Invalid));
+    assert_eq!(Request::parse(&[code::DATA, 1, 2, 3]), Err(Status::Invalid));
+}
+
+#[test]
+fn test_response_parse() {
+    let response = Response::parse(&[0x84, 0, 1, 2]).unwrap();
+    assert_eq!(response.request, code::END);
+    assert_eq!(response.status, Status::Ok);
+    assert_eq!(response.data, [1, 2]);
+    assert_eq!(Response::parse(&[0x04, 0]), Err(-1));
+    assert_eq!(Response::parse(&[0x84, 9]), Err(-1));
+    assert_eq!(Response::parse(&[0x84]), Err(-1));
+}
+
+#[test]
+fn test_info_round_trip() {
+    let info = Info {
+        bootloader: Version::new(1, 2, 3),
+        update_slot: Slot::B,
+        update_address: 0x0808_0200,
+        capacity: 384 * 1024,
+        active: Some(Slot::A),
+        trial: None,
+        running: Some(Version::new(4, 5, 6)),
+    };
+    assert_eq!(Info::parse(&info.encode()), Ok(info));
+    let none = Info {
+        running: None,
+        active: None,
+        ..info
+    };
+    assert_eq!(Info::parse(&none.encode()), Ok(none));
+    assert_eq!(Info::parse(&info.encode()[..10]), Err(-1));
+}
+
+#[test]
+fn test_malformed_request_is_answered() {
+    let mut device = Device::new();
+    let mut out = [0; MAX_RESPONSE];
+    let len = device
+        .updater
+        .handle(&mut device.flash, &mut device.journal, &[0x42, 1], &mut out);
+    assert_eq!(out[..len], [0xC2, Status::Invalid as u8]);
+}
+
+#[test]
+fn test_first_update_keeps_the_probe_image() {
+    let mut device = Device::with_image(Slot::A, Version::new(1, 0, 0));
+    let info = device.info();
+    assert_eq!(info.bootloader, BOOTLOADER);
+    assert_eq!(info.update_slot, Slot::B);
+    assert_eq!(info.update_address, 0x0800_4000 + HEADER_SIZE as u32);
+    assert_eq!(info.capacity, 0x2000);
+    assert_eq!(info.active, None);
+    assert_eq!(info.running, None);
+    assert_eq!(device.decide(), Decision::Boot(Slot::A));
+
+    let version = Version::new(1, 1, 0);
+    assert_eq!(device.upload(&image(Slot::B, version)), Ok(version));
+    assert_eq!(device.journal.state().trial, Some(Slot::B));
+    assert_eq!(device.info().running, Some(version));
+    assert_eq!(device.decide(), Decision::Trial(Slot::B));
+
+    assert!(!device.updater.boot_requested());
+    assert_eq!(device.send(&Request::Boot).0, Status::Ok);
+    assert!(device.updater.boot_requested());
+}
+
+#[test]
+fn test_updates_alternate_slots() {
+    let mut device = Device::with_image(Slot::A, Version::new(1, 0, 0));
+    device
+        .journal
+        .append(&mut device.flash, Record::Confirm(Slot::A))
+        .unwrap();
+    assert_eq!(device.info().update_slot, Slot::B);
+    device
+        .upload(&image(Slot::B, Version::new(2, 0, 0)))
+        .unwrap();
+    // Booted and confirmed by the image
+    device
+        .journal
+        .append(&mut device.flash, Record::Attempt(Slot::B))
+        .unwrap();
+    device
+        .journal
+        .append(&mut device.flash, Record::Confirm(Slot::B))
+        .unwrap();
+    let info = device.info();
+    assert_eq!(info.active, Some(Slot::B));
+    assert_eq!(info.update_slot, Slot::A);
+    assert_eq!(info.running, Some(Version::new(2, 0, 0)));
+    device
+        .upload(&image(Slot::A, Version::new(3, 0, 0)))
+        .unwrap();
+    assert_eq!(device.decide(), Decision::Trial(Slot::A));
+}
+
+#[test]
+fn test_failed_trial_rolls_back() {
+    let mut device = Device::with_image(Slot::A, Version::new(1, 0, 0));
+    device
+        .journal
+        .append(&mut device.flash, Record::Confirm(Slot::A))
+        .unwrap();
+    device
+        .upload(&image(Slot::B, Version::new(2, 0, 0)))
+        .unwrap();
+    // The trial never confirms
+    loop {
+        match device.decide() {
+            Decision::Trial(slot) => device
+                .journal
+                .append(&mut device.flash, Record::Attempt(slot))
+                .unwrap(),
+            Decision::Revert(slot) => device
+                .journal
+                .append(&mut device.flash, Record::Revert(slot))
+                .unwrap(),
+            decision => {
+                assert_eq!(decision, Decision::Boot(Slot::A));
+                break;
+            }
+        }
+    }
+    assert_eq!(device.journal.state().rejected, Some(Slot::B));
+    assert_eq!(device.info().update_slot, Slot::B);
+}
+
+#[test]
+fn test_image_for_the_other_slot() {
+    let mut device = Device::with_image(Slot::A, Version::new(1, 0, 0));
+    assert_eq!(
+        device.upload(&image(Slot::A, Version::new(2, 0, 0))),
+        Err(Status::Image)
+    );
+    assert_eq!(device.journal.state().trial, None);
+    assert_eq!(device.decide(), Decision::Boot(Slot::A));
+}
+
+#[test]
+fn test_corrupted_upload() {
+    let mut device = Device::new();
+    let mut file = image(Slot::A, Version::new(1, 0, 0));
+    file[3000] ^= 0x01;
+    assert_eq!(device.upload(&file), Err(Status::Image));
+    assert_eq!(device.journal.state().trial, None);
+    assert_eq!(device.decide(), Decision::Wait);
+}
+
+#[test]
+fn test_out_of_sequence() {
+    let mut device = Device::new();
+    let chunk = [0u8; 16];
+    let data = |offset| Request::Data {
+        offset,
+        bytes: &chunk,
+    };
+    assert_eq!(device.send(&data(0)).0, Status::Sequence);
+    assert_eq!(device.send(&Request::End).0, Status::Sequence);
+
+    assert_eq!(device.send(&Request::Begin { length: 1024 }).0, Status::Ok);
+    assert_eq!(device.send(&data(16)).0, Status::Sequence);
+    assert_eq!(device.send(&data(0)).0, Status::Ok);
+    // Sent again after a lost response
+    assert_eq!(device.send(&data(0)).0, Status::Sequence);
+    assert_eq!(device.send(&data(16)).0, Status::Ok);
+    // Ended early
+    assert_eq!(device.send(&Request::End).0, Status::Sequence);
+    assert_eq!(device.send(&data(32)).0, Status::Sequence);
+}
+
+#[test]
+fn test_too_large() {
+    let mut device = Device::new();
+    assert_eq!(
+        device.send(&Request::Begin { length: 0x2001 }).0,
+        Status::TooLarge
+    );
+    assert_eq!(
+        device
+            .send(&Request::Begin {
+                length: HEADER_SIZE as u32
+            })
+            .0,
+        Status::Invalid
+    );
+    assert_eq!(device.send(&Request::Begin { length: 0x220 }).0, Status::Ok);
+    let chunk = [0u8; 0xC0];
+    let data = |offset| Request::Data {
+        offset,
+        bytes: &chunk,
+    };
+    assert_eq!(device.send(&data(0)).0, Status::Ok);
+    assert_eq!(device.send(&data(0xC0)).0, Status::Ok);
+    assert_eq!(device.send(&data(0x180)).0, Status::TooLarge);
+}
+
+#[test]
+fn test_begin_erases_the_slot() {
+    let mut device = Device::with_image(Slot::B, Version::new(1, 0, 0));
+    device
+        .journal
+        .append(&mut device.flash, Record::Confirm(Slot::A))
+        .unwrap();
+    assert_eq!(device.send(&Request::Begin { length: 1024 }).0, Status::Ok);
+    let slot = LAYOUT.slot(Slot::B);
+    assert!(
+        device
+            .flash
+            .bytes(slot.start, slot.size as usize)
+            .iter()
+            .all(|&b| b == 0xFF)
+    );
+}
+
+#[test]
+fn test_power_lost_during_upload() {
+    let mut device = Device::with_image(Slot::A, Version::new(1, 0, 0));
+    device
+        .journal
+        .append(&mut device.flash, Record::Confirm(Slot::A))
+        .unwrap();
+    device.flash.cut_power_after(3000);
+    assert_eq!(
+        device.upload(&image(Slot::B, Version::new(2, 0, 0))),
+        Err(Status::Flash)
+    );
+    device.flash.restore_power();
+
+    // After the reset
+    let mut device = Device {
+        journal: Journal::open(&mut device.flash, LAYOUT.state).unwrap(),
+        updater: Updater::new(LAYOUT, BOOTLOADER),
+        flash: device.flash,
+    };
+    assert_eq!(device.decide(), Decision::Boot(Slot::A));
+    let version = Version::new(2, 0, 0);
+    assert_eq!(device.upload(&image(Slot::B, version)), Ok(version));
+}
@@ -0,0 +1,60 @@
+use boot::sha256::*;
+
+fn hex(digest: [u8; DIGEST_SIZE]) -> String {
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(
+        hex(digest(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+}
+
+#[test]
+fn test_abc() {
+    assert_eq!(
+        hex(digest(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_two_blocks() {
+    // 56 bytes: the length no longer fits in the first block
+    assert_eq!(
+        hex(digest(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+}
+
+#[test]
+fn test_million_a() {
+    let mut hash = Sha256::new();
+    let chunk = [b'a'; 1000];
+    for _ in 0..1000 {
+        hash.update(&chunk);
+    }
+    assert_eq!(
+        hex(hash.finalize()),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+}
+
+#[test]
+fn test_pieces_match_whole() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 % 251) as u8).collect();
+    for len in [0, 1, 55, 56, 63, 64, 65, 119, 120, 128, 1000] {
+        let whole = digest(&data[..len]);
+        for piece in [1, 3, 17, 64, 100] {
+            let mut hash = Sha256::new();
+            data[..len]
+                .chunks(piece)
+                .for_each(|chunk| hash.update(chunk));
+            assert_eq!(hash.finalize(), whole, "len {len} in pieces of {piece}");
+        }
+    }
+}
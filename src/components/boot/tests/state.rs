@@ -0,0 +1,262 @@
+use boot::layout::Slot;
+use boot::mock::Flash;
+use boot::state::*;
+use boot::storage::Region;
+
+const REGION: Region = Region::new(0x0800_0400, 0x400);
+
+fn flash() -> Flash<0x1000> {
+    Flash::new(0x0800_0000, 0x400)
+}
+
+fn state(records: &[Record]) -> State {
+    let mut state = State::default();
+    records.iter().for_each(|&record| state.apply(record));
+    state
+}
+
+#[test]
+fn test_record_round_trip() {
+    for slot in Slot::ALL {
+        for record in [
+            Record::Install(slot),
+            Record::Attempt(slot),
+            Record::Confirm(slot),
+            Record::Revert(slot),
+        ] {
+            assert_eq!(Record::parse(&record.encode()), Ok(record));
+        }
+    }
+}
+
+#[test]
+fn test_record_rejected() {
+    let mut bytes = Record::Confirm(Slot::B).encode();
+    bytes[6] ^= 1;
+    assert_eq!(Record::parse(&bytes), Err(-4));
+
+    let mut bytes = Record::Confirm(Slot::B).encode();
+    bytes[0] = 3;
+    bytes[2] = !3;
+    bytes[1] = 5;
+    bytes[3] = !5;
+    let check = fault::crc::crc32(&bytes[..4]).to_le_bytes();
+    bytes[4..].copy_from_slice(&check);
+    assert_eq!(Record::parse(&bytes), Err(-1));
+    assert_eq!(Record::parse(&[0xFF; RECORD_SIZE]), Err(-4));
+}
+
+#[test]
+fn test_nothing_to_boot() {
+    let state = State::default();
+    assert_eq!(state.decide(3, |_| false), Decision::Wait);
+}
+
+#[test]
+fn test_image_from_a_probe_boots() {
+    let state = State::default();
+    assert_eq!(
+        state.decide(3, |slot| slot == Slot::B),
+        Decision::Boot(Slot::B)
+    );
+    let state = self::state(&[Record::Confirm(Slot::B)]);
+    assert_eq!(state.active, Some(Slot::B));
+    assert_eq!(state.decide(3, |_| true), Decision::Boot(Slot::B));
+}
+
+#[test]
+fn test_trial_then_confirm() {
+    let mut state = state(&[Record::Confirm(Slot::A), Record::Install(Slot::B)]);
+    assert_eq!(state.decide(3, |_| true), Decision::Trial(Slot::B));
+    state.apply(Record::Attempt(Slot::B));
+    state.apply(Record::Confirm(Slot::B));
+    assert_eq!(state.active, Some(Slot::B));
+    assert_eq!(state.trial, None);
+    assert_eq!(state.decide(3, |_| true), Decision::Boot(Slot::B));
+}
+
+#[test]
+fn test_trial_rolls_back() {
+    let mut state = state(&[Record::Confirm(Slot::A), Record::Install(Slot::B)]);
+    for _ in 0..3 {
+        assert_eq!(state.decide(3, |_| true), Decision::Trial(Slot::B));
+        state.apply(Record::Attempt(Slot::B));
+    }
+    assert_eq!(state.attempts, 3);
+    assert_eq!(state.decide(3, |_| true), Decision::Revert(Slot::B));
+    state.apply(Record::Revert(Slot::B));
+    assert_eq!(state.rejected, Some(Slot::B));
+    assert_eq!(state.decide(3, |_| true), Decision::Boot(Slot::A));
+}
+
+#[test]
+fn test_invalid_trial_is_reverted() {
+    let state = state(&[Record::Confirm(Slot::A), Record::Install(Slot::B)]);
+    assert_eq!(
+        state.decide(3, |slot| slot == Slot::A),
+        Decision::Revert(Slot::B)
+    );
+}
+
+#[test]
+fn test_rejected_image_is_not_booted() {
+    let mut state = state(&[Record::Install(Slot::A)]);
+    (0..2).for_each(|_| state.apply(Record::Attempt(Slot::A)));
+    assert_eq!(state.decide(2, |_| true), Decision::Revert(Slot::A));
+    state.apply(Record::Revert(Slot::A));
+    assert_eq!(state.decide(2, |slot| slot == Slot::A), Decision::Wait);
+    assert_eq!(state.decide(2, |_| true), Decision::Boot(Slot::B));
+
+    // Until an image is installed there again
+    state.apply(Record::Install(Slot::A));
+    assert_eq!(state.rejected, None);
+    assert_eq!(state.decide(2, |_| true), Decision::Trial(Slot::A));
+}
+
+#[test]
+fn test_install_over_the_active_image() {
+    let state = state(&[Record::Confirm(Slot::A), Record::Install(Slot::A)]);
+    assert_eq!(state.active, None);
+    assert_eq!(state.trial, Some(Slot::A));
+}
+
+#[test]
+fn test_stray_records_are_ignored() {
+    let state = state(&[
+        Record::Confirm(Slot::A),
+        Record::Attempt(Slot::B),
+        Record::Revert(Slot::B),
+    ]);
+    assert_eq!(state.active, Some(Slot::A));
+    assert_eq!(state.attempts, 0);
+    assert_eq!(state.rejected, None);
+}
+
+#[test]
+fn test_journal_replays() {
+    let mut flash = flash();
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(*journal.state(), State::default());
+    assert_eq!(journal.free(), 128);
+
+    journal
+        .append(&mut flash, Record::Confirm(Slot::A))
+        .unwrap();
+    journal
+        .append(&mut flash, Record::Install(Slot::B))
+        .unwrap();
+    journal
+        .append(&mut flash, Record::Attempt(Slot::B))
+        .unwrap();
+    assert_eq!(journal.free(), 125);
+
+    let reopened = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(reopened.state(), journal.state());
+    assert_eq!(reopened.state().attempts, 1);
+    assert_eq!(reopened.free(), 125);
+}
+
+#[test]
+fn test_torn_record_is_skipped() {
+    let mut flash = flash();
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    journal
+        .append(&mut flash, Record::Confirm(Slot::A))
+        .unwrap();
+    journal
+        .append(&mut flash, Record::Install(Slot::B))
+        .unwrap();
+    flash.cut_power_after(5);
+    assert_eq!(
+        journal.append(&mut flash, Record::Attempt(Slot::B)),
+        Err(-3)
+    );
+    flash.restore_power();
+
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(journal.state().attempts, 0);
+    assert_eq!(journal.free(), 125);
+    journal
+        .append(&mut flash, Record::Attempt(Slot::B))
+        .unwrap();
+    let journal = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(journal.state().trial, Some(Slot::B));
+    assert_eq!(journal.state().attempts, 1);
+}
+
+#[test]
+fn test_full_journal_is_compacted() {
+    let mut flash = flash();
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    journal
+        .append(&mut flash, Record::Install(Slot::A))
+        .unwrap();
+    journal
+        .append(&mut flash, Record::Attempt(Slot::A))
+        .unwrap();
+    journal.append(&mut flash, Record::Revert(Slot::A)).unwrap();
+    journal
+        .append(&mut flash, Record::Confirm(Slot::B))
+        .unwrap();
+    for _ in 0..61 {
+        journal
+            .append(&mut flash, Record::Install(Slot::A))
+            .unwrap();
+        journal
+            .append(&mut flash, Record::Confirm(Slot::A))
+            .unwrap();
+    }
+    journal
+        .append(&mut flash, Record::Install(Slot::B))
+        .unwrap();
+    journal
+        .append(&mut flash, Record::Attempt(Slot::B))
+        .unwrap();
+    assert_eq!(journal.free(), 0);
+    let before = *journal.state();
+    assert_eq!(flash.erases, 0);
+
+    journal
+        .append(&mut flash, Record::Attempt(Slot::B))
+        .unwrap();
+    assert_eq!(flash.erases, 1);
+    let reopened = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(reopened.state().active, before.active);
+    assert_eq!(reopened.state().trial, Some(Slot::B));
+    assert_eq!(reopened.state().attempts, 2);
+    assert!(reopened.free() > 120);
+
+    // A rejected slot survives compaction
+    let mut flash = flash_with_rejected();
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    while journal.free() > 0 {
+        journal
+            .append(&mut flash, Record::Confirm(Slot::A))
+            .unwrap();
+    }
+    journal
+        .append(&mut flash, Record::Confirm(Slot::A))
+        .unwrap();
+    let reopened = Journal::open(&mut flash, REGION).unwrap();
+    assert_eq!(reopened.state().rejected, Some(Slot::B));
+    assert_eq!(reopened.state().active, Some(Slot::A));
+}
+
+fn flash_with_rejected() -> Flash<0x1000> {
+    let mut flash = flash();
+    let mut journal = Journal::open(&mut flash, REGION).unwrap();
+    for record in [
+        Record::Confirm(Slot::A),
+        Record::Install(Slot::B),
+        Record::Revert(Slot::B),
+    ] {
+        journal.append(&mut flash, record).unwrap();
+    }
+    flash
+}
+
+#[test]
+fn test_journal_outside_flash() {
+    let mut flash = flash();
+    assert!(Journal::open(&mut flash, Region::new(0x0800_1000, 0x400)).is_err());
+}
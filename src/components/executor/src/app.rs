@@ -0,0 +1,44 @@
+//! Runs an executor as one app of the cooperative scheduler, so async tasks
+//! and `App`s share the core.
+
+use crate::executor::Executor;
+use sched::app::App;
+use sched::scheduler::{Next, TaskConfig};
+
+/// App polling the tasks of an executor. While no task is ready it waits
+/// for `event`, which the executor's notify function must signal.
+pub struct ExecutorApp<const N: usize> {
+    executor: &'static Executor<N>,
+    event: u32,
+    config: TaskConfig,
+}
+
+impl<const N: usize> ExecutorApp<N> {
+    pub fn new(executor: &'static Executor<N>, event: u32, config: TaskConfig) -> Self {
+        Self {
+            executor,
+            event,
+            config,
+        }
+    }
+}
+
+impl<const N: usize> App for ExecutorApp<N> {
+    fn init(&mut self) -> Result<(), i32> {
+        Ok(())
+    }
+
+    fn loop_step(&mut self) -> Next {
+        self.executor.poll();
+        // Tasks may still be spawned later, so the app never finishes
+        if self.executor.has_ready() {
+            Next::Yield
+        } else {
+            Next::Event(self.event)
+        }
+    }
+
+    fn config(&self) -> TaskConfig {
+        self.config
+    }
+}
@@ -0,0 +1,63 @@
+//! Static storage for task futures.
+//!
+//! The future of an `async fn` has no type name, so it cannot be put in a
+//! typed `static`. The arena moves it into a static byte buffer instead.
+//! Space is never given back; it suits tasks spawned once at start-up.
+
+use core::cell::{Cell, UnsafeCell};
+use core::mem::{MaybeUninit, align_of, size_of};
+
+#[repr(C, align(8))]
+struct Buffer<const N: usize>([MaybeUninit<u8>; N]);
+
+/// Bump allocator over `N` bytes of static storage
+pub struct Arena<const N: usize> {
+    buffer: UnsafeCell<Buffer<N>>,
+    used: Cell<usize>,
+}
+
+// Allocation must happen from a single context (thread mode on target);
+// handed-out values are not touched again by the arena
+unsafe impl<const N: usize> Sync for Arena<N> {}
+
+impl<const N: usize> Arena<N> {
+    pub const fn new() -> Self {
+        Self {
+            buffer: UnsafeCell::new(Buffer([MaybeUninit::uninit(); N])),
+            used: Cell::new(0),
+        }
+    }
+
+    /// Move `value` into the arena. Returns it back if there is no room.
+    #[allow(clippy::mut_from_ref)] // each value is handed out exactly once
+    pub fn alloc<T>(&'static self, value: T) -> Result<&'static mut T, T> {
+        let base = self.buffer.get() as usize;
+        let start = (base + self.used.get()).next_multiple_of(align_of::<T>()) - base;
+        let end = match start.checked_add(size_of::<T>()) {
+            Some(end) if end <= N => end,
+            _ => return Err(value),
+        };
+        self.used.set(end);
+        let slot = (base + start) as *mut T;
+        // The range is in the buffer, aligned for T and handed out only once
+        unsafe {
+            slot.write(value);
+            Ok(&mut *slot)
+        }
+    }
+
+    /// Bytes handed out so far, including alignment padding
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for Arena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
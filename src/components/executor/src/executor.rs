@@ -0,0 +1,137 @@
+//! A small executor for `async` tasks that needs no heap.
+//!
+//! Tasks are futures kept in static storage by the caller. Each task has a
+//! wake flag its wakers point to; waking only stores to the flag, so wakers
+//! can be used from interrupts on any core, and the executor polls a task
+//! only after it was woken.
+
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, RawWaker, RawWakerVTable, Waker};
+
+pub type TaskId = usize;
+
+/// A spawned task: a future in static storage
+pub type Task = Pin<&'static mut dyn Future<Output = ()>>;
+
+/// Wake flag of a task, what its wakers point to
+struct Flag {
+    ready: AtomicBool,
+    /// Called after every wake, e.g. to wake whatever runs the executor
+    notify: fn(),
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, drop);
+
+unsafe fn clone(flag: *const ()) -> RawWaker {
+    RawWaker::new(flag, &VTABLE)
+}
+
+unsafe fn wake(flag: *const ()) {
+    let flag = unsafe { &*(flag as *const Flag) };
+    flag.ready.store(true, Ordering::Release);
+    (flag.notify)();
+}
+
+unsafe fn drop(_: *const ()) {}
+
+/// Executor for up to `N` tasks.
+///
+/// Spawn and poll from one context only (thread mode on target); wakers may
+/// be used from anywhere.
+pub struct Executor<const N: usize> {
+    tasks: [Cell<Option<Task>>; N],
+    flags: [Flag; N],
+}
+
+// Tasks are only touched by `spawn()` and `poll()`, which must be called
+// from a single context; wakers only touch the atomic flags.
+unsafe impl<const N: usize> Sync for Executor<N> {}
+
+impl<const N: usize> Executor<N> {
+    /// Create an executor calling `notify` whenever a task is woken.
+    pub const fn new(notify: fn()) -> Self {
+        let mut flags = [const {
+            Flag {
+                ready: AtomicBool::new(false),
+                notify: no_notify,
+            }
+        }; N];
+        let mut i = 0;
+        while i < N {
+            flags[i].notify = notify;
+            i += 1;
+        }
+        Self {
+            tasks: [const { Cell::new(None) }; N],
+            flags,
+        }
+    }
+
+    /// Add a task, polled on the next `poll()`. Returns Err(-1) if all
+    /// task slots are in use.
+    pub fn spawn(&'static self, task: Task) -> Result<TaskId, i32> {
+        let id = (0..N).find(|&id| self.is_free(id)).ok_or(-1)?;
+        self.tasks[id].set(Some(task));
+        let waker = self.waker(id);
+        waker.wake();
+        Ok(id)
+    }
+
+    fn is_free(&self, id: TaskId) -> bool {
+        let task = self.tasks[id].take();
+        let free = task.is_none();
+        self.tasks[id].set(task);
+        free
+    }
+
+    fn waker(&'static self, id: TaskId) -> Waker {
+        let flag = &self.flags[id] as *const Flag as *const ();
+        // The flag is static and the vtable functions only touch the flag
+        unsafe { Waker::from_raw(RawWaker::new(flag, &VTABLE)) }
+    }
+
+    /// Poll every task woken since its last poll once. Returns the number
+    /// of tasks polled.
+    pub fn poll(&'static self) -> usize {
+        let mut polled = 0;
+        for id in 0..N {
+            if !self.flags[id].ready.load(Ordering::Acquire) {
+                continue;
+            }
+            // Out of its slot while polled, so a task spawning another
+            // cannot be handed its own slot
+            let Some(mut task) = self.tasks[id].take() else {
+                continue;
+            };
+            // Cleared before polling, so a wake during the poll is kept
+            self.flags[id].ready.store(false, Ordering::Release);
+            let waker = self.waker(id);
+            let mut cx = Context::from_waker(&waker);
+            polled += 1;
+            if task.as_mut().poll(&mut cx).is_pending() {
+                self.tasks[id].set(Some(task));
+            }
+        }
+        polled
+    }
+
+    /// Returns true if a task was woken and waits to be polled
+    pub fn has_ready(&self) -> bool {
+        (0..N).any(|id| self.flags[id].ready.load(Ordering::Acquire) && !self.is_free(id))
+    }
+
+    /// Number of unfinished tasks
+    pub fn len(&self) -> usize {
+        (0..N).filter(|&id| !self.is_free(id)).count()
+    }
+
+    /// Returns true if no task is left
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn no_notify() {}
@@ -0,0 +1,7 @@
+#![no_std]
+
+pub mod app;
+pub mod arena;
+pub mod executor;
+pub mod timer;
+pub mod waker;
@@ -0,0 +1,73 @@
+//! Deadlines of sleeping tasks, expired from the tick interrupt.
+
+use core::task::Waker;
+use sched::tick::reached;
+
+/// Queue of up to `N` wakers waiting for a tick deadline.
+pub struct TimerQueue<const N: usize> {
+    entries: [Option<(u32, Waker)>; N],
+}
+
+impl<const N: usize> TimerQueue<N> {
+    pub const fn new() -> Self {
+        Self {
+            entries: [const { None }; N],
+        }
+    }
+
+    /// Wake `waker` once tick `deadline` is reached. Registering the same
+    /// waker for the same deadline again is a no-op. Returns Err(-1) if
+    /// the queue is full.
+    pub fn schedule(&mut self, deadline: u32, waker: &Waker) -> Result<(), i32> {
+        let registered = self
+            .entries
+            .iter()
+            .flatten()
+            .any(|(d, w)| *d == deadline && w.will_wake(waker));
+        if registered {
+            return Ok(());
+        }
+        let slot = self.entries.iter_mut().find(|e| e.is_none()).ok_or(-1)?;
+        *slot = Some((deadline, waker.clone()));
+        Ok(())
+    }
+
+    /// Wake the entries whose deadline is reached at `now`. Returns the
+    /// number of wakers woken.
+    pub fn expire(&mut self, now: u32) -> usize {
+        let mut woken = 0;
+        for entry in self.entries.iter_mut() {
+            if entry.as_ref().is_some_and(|(d, _)| reached(now, *d))
+                && let Some((_, waker)) = entry.take()
+            {
+                waker.wake();
+                woken += 1;
+            }
+        }
+        woken
+    }
+
+    /// The earliest deadline in the queue, relative to `now`
+    pub fn next_deadline(&self, now: u32) -> Option<u32> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|(d, _)| *d)
+            .min_by_key(|d| d.wrapping_sub(now) as i32)
+    }
+
+    /// Number of waiting entries
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const N: usize> Default for TimerQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,90 @@
+//! Wakers of tasks waiting on interrupts.
+
+use core::task::Waker;
+
+/// The waker of the task waiting on one event
+pub struct WakerSlot {
+    waker: Option<Waker>,
+}
+
+impl WakerSlot {
+    pub const fn new() -> Self {
+        Self { waker: None }
+    }
+
+    /// Register the waker to wake on the event, replacing the previous one
+    /// unless it wakes the same task.
+    pub fn register(&mut self, waker: &Waker) {
+        match &self.waker {
+            Some(w) if w.will_wake(waker) => {}
+            _ => self.waker = Some(waker.clone()),
+        }
+    }
+
+    /// Wake and forget the registered waker. Returns true if there was one.
+    pub fn wake(&mut self) -> bool {
+        match self.waker.take() {
+            Some(waker) => {
+                waker.wake();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_registered(&self) -> bool {
+        self.waker.is_some()
+    }
+}
+
+impl Default for WakerSlot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Waker slots for up to `N` interrupt lines, keyed by IRQ number.
+pub struct WakerTable<const N: usize> {
+    slots: [(u32, WakerSlot); N],
+}
+
+impl<const N: usize> WakerTable<N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { (0, WakerSlot::new()) }; N],
+        }
+    }
+
+    /// Register the waker to wake on interrupt `irqn`. Returns Err(-1) if
+    /// all slots wait on other interrupts.
+    pub fn register(&mut self, irqn: u32, waker: &Waker) -> Result<(), i32> {
+        let index = self
+            .slots
+            .iter()
+            .position(|(n, slot)| *n == irqn && slot.is_registered())
+            .or_else(|| {
+                self.slots
+                    .iter()
+                    .position(|(_, slot)| !slot.is_registered())
+            })
+            .ok_or(-1)?;
+        let (n, slot) = &mut self.slots[index];
+        *n = irqn;
+        slot.register(waker);
+        Ok(())
+    }
+
+    /// Wake the task waiting on interrupt `irqn`. Returns true if there was one.
+    pub fn wake(&mut self, irqn: u32) -> bool {
+        self.slots
+            .iter_mut()
+            .find(|(n, slot)| *n == irqn && slot.is_registered())
+            .is_some_and(|(_, slot)| slot.wake())
+    }
+}
+
+impl<const N: usize> Default for WakerTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,64 @@
+use core::future::{Future, poll_fn};
+use core::pin::Pin;
+use core::task::{Poll, Waker};
+use executor::app::*;
+use executor::executor::*;
+use sched::app::App;
+use sched::scheduler::{Next, TaskConfig};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const EVENT: u32 = 1 << 31;
+
+fn leak<F: Future<Output = ()> + 'static>(future: F) -> Task {
+    let future: &'static mut dyn Future<Output = ()> = Box::leak(Box::new(future));
+    unsafe { Pin::new_unchecked(future) }
+}
+
+#[test]
+fn test_app_waits_for_event_once_tasks_are_pending() {
+    let executor: &'static Executor<2> = Box::leak(Box::new(Executor::new(|| {})));
+    let waker: Rc<RefCell<Option<Waker>>> = Rc::new(RefCell::new(None));
+    let slot = waker.clone();
+    executor
+        .spawn(leak(poll_fn(move |cx| {
+            *slot.borrow_mut() = Some(cx.waker().clone());
+            Poll::<()>::Pending
+        })))
+        .unwrap();
+
+    let mut app = ExecutorApp::new(executor, EVENT, TaskConfig::default());
+    assert_eq!(app.init(), Ok(()));
+    assert_eq!(app.loop_step(), Next::Event(EVENT));
+
+    waker.borrow().as_ref().unwrap().wake_by_ref();
+    assert_eq!(app.loop_step(), Next::Event(EVENT));
+}
+
+#[test]
+fn test_app_yields_while_tasks_stay_ready() {
+    let executor: &'static Executor<1> = Box::leak(Box::new(Executor::new(|| {})));
+    let polls = Rc::new(RefCell::new(0));
+    let count = polls.clone();
+    executor
+        .spawn(leak(poll_fn(move |cx| {
+            *count.borrow_mut() += 1;
+            if *count.borrow() < 2 {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })))
+        .unwrap();
+
+    let config = TaskConfig {
+        priority: 3,
+        ..TaskConfig::default()
+    };
+    let mut app = ExecutorApp::new(executor, EVENT, config);
+    assert_eq!(app.config().priority, 3);
+    assert_eq!(app.loop_step(), Next::Yield);
+    assert_eq!(app.loop_step(), Next::Event(EVENT));
+    assert!(executor.is_empty());
+}
@@ -0,0 +1,36 @@
+use executor::arena::*;
+
+fn arena<const N: usize>() -> &'static Arena<N> {
+    Box::leak(Box::new(Arena::new()))
+}
+
+#[test]
+fn test_alloc_moves_values_in() {
+    let arena = arena::<16>();
+    let a = arena.alloc(1u8).unwrap();
+    let b = arena.alloc(0x1234_5678u32).unwrap();
+    assert_eq!(*a, 1);
+    assert_eq!(*b, 0x1234_5678);
+
+    *a = 2;
+    assert_eq!(*a, 2);
+    assert_eq!(*b, 0x1234_5678);
+}
+
+#[test]
+fn test_alloc_aligns_values() {
+    let arena = arena::<32>();
+    arena.alloc(1u8).unwrap();
+    let wide = arena.alloc(7u64).unwrap();
+    assert_eq!(wide as *mut u64 as usize % 8, 0);
+    assert_eq!(arena.used(), 16);
+}
+
+#[test]
+fn test_alloc_returns_value_when_full() {
+    let arena = arena::<8>();
+    arena.alloc([0u8; 6]).unwrap();
+    assert_eq!(arena.alloc(0xAABBu16), Ok(&mut 0xAABB));
+    assert_eq!(arena.alloc(1u8), Err(1));
+    assert_eq!(arena.used(), arena.capacity());
+}
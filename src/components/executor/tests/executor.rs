@@ -0,0 +1,117 @@
+use core::future::{Future, poll_fn};
+use core::pin::Pin;
+use core::task::{Poll, Waker};
+use executor::executor::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn leak<F: Future<Output = ()> + 'static>(future: F) -> Task {
+    let future: &'static mut dyn Future<Output = ()> = Box::leak(Box::new(future));
+    unsafe { Pin::new_unchecked(future) }
+}
+
+fn executor<const N: usize>(notify: fn()) -> &'static Executor<N> {
+    Box::leak(Box::new(Executor::new(notify)))
+}
+
+/// Future pending until its shared flag is set, keeping the last waker
+fn gate(open: Rc<RefCell<(bool, Option<Waker>)>>) -> impl Future<Output = ()> {
+    poll_fn(move |cx| {
+        let mut gate = open.borrow_mut();
+        if gate.0 {
+            Poll::Ready(())
+        } else {
+            gate.1 = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    })
+}
+
+#[test]
+fn test_spawned_task_runs_to_completion() {
+    let executor = executor::<2>(|| {});
+    let done = Rc::new(RefCell::new(false));
+    let flag = done.clone();
+    executor
+        .spawn(leak(async move { *flag.borrow_mut() = true }))
+        .unwrap();
+
+    assert_eq!(executor.len(), 1);
+    assert!(executor.has_ready());
+    assert_eq!(executor.poll(), 1);
+    assert!(*done.borrow());
+    assert!(executor.is_empty());
+    assert!(!executor.has_ready());
+}
+
+#[test]
+fn test_pending_task_is_polled_only_after_wake() {
+    let executor = executor::<2>(|| {});
+    let gate_state = Rc::new(RefCell::new((false, None)));
+    executor.spawn(leak(gate(gate_state.clone()))).unwrap();
+
+    assert_eq!(executor.poll(), 1);
+    assert_eq!(executor.poll(), 0);
+    assert!(!executor.has_ready());
+
+    gate_state.borrow_mut().0 = true;
+    let waker = gate_state.borrow_mut().1.take().unwrap();
+    waker.wake();
+    assert!(executor.has_ready());
+    assert_eq!(executor.poll(), 1);
+    assert!(executor.is_empty());
+}
+
+#[test]
+fn test_wake_calls_notify() {
+    static NOTIFIED: AtomicU32 = AtomicU32::new(0);
+    let executor = executor::<1>(|| {
+        NOTIFIED.fetch_add(1, Ordering::Relaxed);
+    });
+    let gate_state = Rc::new(RefCell::new((false, None)));
+    executor.spawn(leak(gate(gate_state.clone()))).unwrap();
+    assert_eq!(NOTIFIED.load(Ordering::Relaxed), 1);
+
+    executor.poll();
+    let waker = gate_state.borrow().1.clone().unwrap();
+    waker.wake_by_ref();
+    assert_eq!(NOTIFIED.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_wake_during_poll_is_kept() {
+    let executor = executor::<1>(|| {});
+    let polls = Rc::new(RefCell::new(0));
+    let count = polls.clone();
+    let task = poll_fn(move |cx| {
+        *count.borrow_mut() += 1;
+        if *count.borrow() < 3 {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    });
+    executor.spawn(leak(task)).unwrap();
+
+    assert_eq!(executor.poll(), 1);
+    assert!(executor.has_ready());
+    executor.poll();
+    executor.poll();
+    assert_eq!(*polls.borrow(), 3);
+    assert!(executor.is_empty());
+}
+
+#[test]
+fn test_spawn_fails_when_full_and_reuses_finished_slots() {
+    let executor = executor::<2>(|| {});
+    let gate_state = Rc::new(RefCell::new((false, None)));
+    executor.spawn(leak(gate(gate_state.clone()))).unwrap();
+    let second = executor.spawn(leak(async {})).unwrap();
+    assert_eq!(executor.spawn(leak(async {})), Err(-1));
+
+    executor.poll();
+    assert_eq!(executor.len(), 1);
+    assert_eq!(executor.spawn(leak(async {})), Ok(second));
+}
@@ -0,0 +1,5 @@
+pub mod app;
+pub mod arena;
+pub mod executor;
+pub mod timer;
+pub mod waker;
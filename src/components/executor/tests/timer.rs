@@ -0,0 +1,74 @@
+use core::task::Waker;
+use executor::timer::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::Wake;
+
+struct CountingWaker(AtomicU32);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker(AtomicU32::new(0)));
+    (counter.clone(), Waker::from(counter))
+}
+
+#[test]
+fn test_expire_wakes_reached_deadlines_only() {
+    let mut queue = TimerQueue::<4>::new();
+    let (early, early_waker) = counting_waker();
+    let (late, late_waker) = counting_waker();
+    queue.schedule(10, &early_waker).unwrap();
+    queue.schedule(20, &late_waker).unwrap();
+
+    assert_eq!(queue.expire(9), 0);
+    assert_eq!(queue.expire(10), 1);
+    assert_eq!(early.0.load(Ordering::Relaxed), 1);
+    assert_eq!(late.0.load(Ordering::Relaxed), 0);
+    assert_eq!(queue.len(), 1);
+
+    assert_eq!(queue.expire(25), 1);
+    assert_eq!(late.0.load(Ordering::Relaxed), 1);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_same_waker_and_deadline_is_scheduled_once() {
+    let mut queue = TimerQueue::<2>::new();
+    let (counter, waker) = counting_waker();
+    queue.schedule(5, &waker).unwrap();
+    queue.schedule(5, &waker).unwrap();
+    assert_eq!(queue.len(), 1);
+
+    queue.schedule(6, &waker).unwrap();
+    assert_eq!(queue.len(), 2);
+    queue.expire(6);
+    assert_eq!(counter.0.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_schedule_fails_when_full() {
+    let mut queue = TimerQueue::<1>::new();
+    let (_, first) = counting_waker();
+    let (_, second) = counting_waker();
+    queue.schedule(1, &first).unwrap();
+    assert_eq!(queue.schedule(2, &second), Err(-1));
+}
+
+#[test]
+fn test_next_deadline_across_wraparound() {
+    let mut queue = TimerQueue::<4>::new();
+    assert_eq!(queue.next_deadline(0), None);
+
+    let (_, waker) = counting_waker();
+    queue.schedule(5, &waker).unwrap();
+    queue.schedule(u32::MAX - 2, &waker).unwrap();
+    assert_eq!(queue.next_deadline(u32::MAX - 10), Some(u32::MAX - 2));
+
+    assert_eq!(queue.expire(3), 1);
+    assert_eq!(queue.next_deadline(3), Some(5));
+}
@@ -0,0 +1,71 @@
+use core::task::Waker;
+use executor::waker::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::Wake;
+
+struct CountingWaker(AtomicU32);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+    let counter = Arc::new(CountingWaker(AtomicU32::new(0)));
+    (counter.clone(), Waker::from(counter))
+}
+
+#[test]
+fn test_slot_wakes_once() {
+    let mut slot = WakerSlot::new();
+    assert!(!slot.wake());
+
+    let (counter, waker) = counting_waker();
+    slot.register(&waker);
+    assert!(slot.is_registered());
+    assert!(slot.wake());
+    assert!(!slot.wake());
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_slot_replaces_waker_of_other_task() {
+    let mut slot = WakerSlot::new();
+    let (first, first_waker) = counting_waker();
+    let (second, second_waker) = counting_waker();
+    slot.register(&first_waker);
+    slot.register(&second_waker);
+    slot.wake();
+    assert_eq!(first.0.load(Ordering::Relaxed), 0);
+    assert_eq!(second.0.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_table_wakes_by_irq() {
+    let mut table = WakerTable::<2>::new();
+    let (uart, uart_waker) = counting_waker();
+    let (spi, spi_waker) = counting_waker();
+    table.register(37, &uart_waker).unwrap();
+    table.register(35, &spi_waker).unwrap();
+
+    assert!(table.wake(37));
+    assert!(!table.wake(37));
+    assert!(!table.wake(52));
+    assert_eq!(uart.0.load(Ordering::Relaxed), 1);
+    assert_eq!(spi.0.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_table_reuses_slots_after_wake() {
+    let mut table = WakerTable::<1>::new();
+    let (_, waker) = counting_waker();
+    table.register(37, &waker).unwrap();
+    table.register(37, &waker).unwrap();
+    assert_eq!(table.register(38, &waker), Err(-1));
+
+    table.wake(37);
+    table.register(38, &waker).unwrap();
+    assert!(table.wake(38));
+}
@@ -0,0 +1,21 @@
+//! CRC-32 (IEEE 802.3, reflected, polynomial 0xEDB88320) used to validate
+//! records kept in RAM across reset.
+
+const POLY: u32 = 0xEDB8_8320;
+
+/// CRC-32 of `data`, as computed by zlib and Ethernet
+pub fn crc32(data: &[u8]) -> u32 {
+    !crc32_update(0xFFFF_FFFF, data)
+}
+
+/// Feed `data` into a running (non-inverted) CRC-32 state
+pub fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (POLY & mask);
+        }
+    }
+    crc
+}
@@ -0,0 +1,7 @@
+#![no_std]
+
+pub mod crc;
+pub mod panic;
+pub mod record;
+pub mod report;
+pub mod reset;
@@ -0,0 +1,159 @@
+//! A panic message, location and tick count packed into fixed buffers so
+//! that it can live in RAM that is not initialized at startup.
+
+use crate::crc::crc32_update;
+use core::fmt;
+
+/// Marks a record written by the panic handler ("PNC!")
+pub const PANIC_MAGIC: u32 = 0x504E_4321;
+
+/// Bytes kept of the source file path
+pub const PANIC_FILE_LEN: usize = 64;
+
+/// Bytes kept of the formatted panic message
+pub const PANIC_MESSAGE_LEN: usize = 128;
+
+/// Raw storage for one panic.
+///
+/// Lengths are validated before use and every field accepts any bit
+/// pattern, so a record left in RAM after power-up is rejected by
+/// `report()` rather than misread.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct PanicRecord {
+    magic: u32,
+    ticks: u32,
+    line: u32,
+    column: u32,
+    file_len: u32,
+    message_len: u32,
+    file: [u8; PANIC_FILE_LEN],
+    message: [u8; PANIC_MESSAGE_LEN],
+    crc: u32,
+}
+
+/// A validated view of a panic record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanicReport<'a> {
+    pub message: &'a str,
+    pub file: &'a str,
+    pub line: u32,
+    pub column: u32,
+    /// SysTick count when the panic happened
+    pub ticks: u32,
+}
+
+impl PanicRecord {
+    /// A record that holds no panic.
+    pub const fn empty() -> Self {
+        Self {
+            magic: 0,
+            ticks: 0,
+            line: 0,
+            column: 0,
+            file_len: 0,
+            message_len: 0,
+            file: [0; PANIC_FILE_LEN],
+            message: [0; PANIC_MESSAGE_LEN],
+            crc: 0,
+        }
+    }
+
+    /// Start a record for a panic at `file:line:column`. Write the message
+    /// through `fmt::Write`, then call `seal()`.
+    pub fn new(file: &str, line: u32, column: u32, ticks: u32) -> Self {
+        let mut record = Self::empty();
+        record.ticks = ticks;
+        record.line = line;
+        record.column = column;
+        // Keep the end of long paths, the file name is the useful part
+        let file = tail_on_char_boundary(file, PANIC_FILE_LEN);
+        record.file[..file.len()].copy_from_slice(file.as_bytes());
+        record.file_len = file.len() as u32;
+        record
+    }
+
+    /// Mark the record valid and compute its CRC
+    pub fn seal(&mut self) {
+        self.magic = PANIC_MAGIC;
+        self.crc = self.compute_crc();
+    }
+
+    /// The stored panic, or `None` if the record is empty or corrupted.
+    pub fn report(&self) -> Option<PanicReport<'_>> {
+        if self.magic != PANIC_MAGIC || self.crc != self.compute_crc() {
+            return None;
+        }
+        let file_len = self.file_len as usize;
+        let message_len = self.message_len as usize;
+        if file_len > PANIC_FILE_LEN || message_len > PANIC_MESSAGE_LEN {
+            return None;
+        }
+        Some(PanicReport {
+            message: core::str::from_utf8(&self.message[..message_len]).ok()?,
+            file: core::str::from_utf8(&self.file[..file_len]).ok()?,
+            line: self.line,
+            column: self.column,
+            ticks: self.ticks,
+        })
+    }
+
+    fn compute_crc(&self) -> u32 {
+        let header = [
+            self.magic,
+            self.ticks,
+            self.line,
+            self.column,
+            self.file_len,
+            self.message_len,
+        ];
+        let mut crc = 0xFFFF_FFFF;
+        for word in header {
+            crc = crc32_update(crc, &word.to_le_bytes());
+        }
+        crc = crc32_update(crc, &self.file);
+        !crc32_update(crc, &self.message)
+    }
+}
+
+impl Default for PanicRecord {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Write for PanicRecord {
+    /// Append to the message, silently truncating at the buffer size so a
+    /// long message never causes a second panic.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let used = self.message_len as usize;
+        let s = head_on_char_boundary(s, PANIC_MESSAGE_LEN - used);
+        self.message[used..used + s.len()].copy_from_slice(s.as_bytes());
+        self.message_len += s.len() as u32;
+        Ok(())
+    }
+}
+
+/// The longest prefix of `s` that fits in `max` bytes
+fn head_on_char_boundary(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// The longest suffix of `s` that fits in `max` bytes
+fn tail_on_char_boundary(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut start = s.len() - max;
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
@@ -0,0 +1,96 @@
+//! A fault report packed into plain words so that it can live in RAM that
+//! is not initialized at startup and be validated after a reset.
+
+use crate::report::{ExceptionFrame, FaultKind, FaultReport};
+
+/// Marks a record written by the fault handler ("FLT!")
+pub const RECORD_MAGIC: u32 = 0x464C_5421;
+
+/// Raw, checksummed storage for one fault report.
+///
+/// Every field is a `u32` so any bit pattern left in RAM after a power-up
+/// is a valid value; `report()` rejects it unless magic and checksum match.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultRecord {
+    magic: u32,
+    kind: u32,
+    frame: [u32; 8],
+    cfsr: u32,
+    hfsr: u32,
+    mmfar: u32,
+    bfar: u32,
+    checksum: u32,
+}
+
+impl FaultRecord {
+    /// A record that holds no report.
+    pub const fn empty() -> Self {
+        Self {
+            magic: 0,
+            kind: 0,
+            frame: [0; 8],
+            cfsr: 0,
+            hfsr: 0,
+            mmfar: 0,
+            bfar: 0,
+            checksum: 0,
+        }
+    }
+
+    /// Pack a report into a record with a valid magic and checksum.
+    pub fn new(report: &FaultReport) -> Self {
+        let f = &report.frame;
+        let mut record = Self {
+            magic: RECORD_MAGIC,
+            kind: report.kind as u32,
+            frame: [f.r0, f.r1, f.r2, f.r3, f.r12, f.lr, f.pc, f.xpsr],
+            cfsr: report.cfsr,
+            hfsr: report.hfsr,
+            mmfar: report.mmfar,
+            bfar: report.bfar,
+            checksum: 0,
+        };
+        record.checksum = record.compute_checksum();
+        record
+    }
+
+    /// Unpack the report, or `None` if the record is empty or corrupted.
+    pub fn report(&self) -> Option<FaultReport> {
+        if self.magic != RECORD_MAGIC || self.checksum != self.compute_checksum() {
+            return None;
+        }
+        let kind = FaultKind::from_u32(self.kind)?;
+        let [r0, r1, r2, r3, r12, lr, pc, xpsr] = self.frame;
+        let frame = ExceptionFrame {
+            r0,
+            r1,
+            r2,
+            r3,
+            r12,
+            lr,
+            pc,
+            xpsr,
+        };
+        Some(FaultReport::new(
+            kind, frame, self.cfsr, self.hfsr, self.mmfar, self.bfar,
+        ))
+    }
+
+    /// FNV-1a over every word preceding the checksum
+    fn compute_checksum(&self) -> u32 {
+        let words = [self.magic, self.kind]
+            .into_iter()
+            .chain(self.frame)
+            .chain([self.cfsr, self.hfsr, self.mmfar, self.bfar]);
+        words.fold(0x811C_9DC5, |hash, word| {
+            (hash ^ word).wrapping_mul(0x0100_0193)
+        })
+    }
+}
+
+impl Default for FaultRecord {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
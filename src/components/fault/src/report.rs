@@ -0,0 +1,219 @@
+//! Decoding of Cortex-M fault status registers into a typed report.
+//!
+//! The bit positions are the architectural ones of ARMv7-M (CFSR, HFSR), so
+//! the decoder runs unchanged on the host.
+
+/// Registers stacked by the core on exception entry, in stack order.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExceptionFrame {
+    pub r0: u32,
+    pub r1: u32,
+    pub r2: u32,
+    pub r3: u32,
+    pub r12: u32,
+    pub lr: u32,
+    pub pc: u32,
+    pub xpsr: u32,
+}
+
+/// The fault exception that was taken.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+    HardFault = 0,
+    MemManage = 1,
+    BusFault = 2,
+    UsageFault = 3,
+}
+
+impl FaultKind {
+    /// Convert a raw value back into a kind, as stored in a fault record.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(FaultKind::HardFault),
+            1 => Some(FaultKind::MemManage),
+            2 => Some(FaultKind::BusFault),
+            3 => Some(FaultKind::UsageFault),
+            _ => None,
+        }
+    }
+
+    /// Short name for logs
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FaultKind::HardFault => "HardFault",
+            FaultKind::MemManage => "MemManage",
+            FaultKind::BusFault => "BusFault",
+            FaultKind::UsageFault => "UsageFault",
+        }
+    }
+}
+
+/// A single cause flagged in CFSR or HFSR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultCause {
+    /// MemManage: instruction fetch from a no-execute or protected region
+    InstructionAccessViolation,
+    /// MemManage: load or store to a protected region
+    DataAccessViolation,
+    /// MemManage: fault while unstacking on exception return
+    MemManageUnstacking,
+    /// MemManage: fault while stacking on exception entry
+    MemManageStacking,
+    /// MemManage: fault during lazy floating-point state preservation
+    MemManageLazyFpStacking,
+    /// BusFault: instruction fetch bus error
+    InstructionBusError,
+    /// BusFault: data bus error, BFAR holds the address when valid
+    PreciseDataBusError,
+    /// BusFault: data bus error reported after the faulting instruction
+    ImpreciseDataBusError,
+    /// BusFault: bus error while unstacking on exception return
+    BusFaultUnstacking,
+    /// BusFault: bus error while stacking on exception entry
+    BusFaultStacking,
+    /// BusFault: bus error during lazy floating-point state preservation
+    BusFaultLazyFpStacking,
+    /// UsageFault: undefined instruction
+    UndefinedInstruction,
+    /// UsageFault: invalid EPSR state, e.g. a branch to an even address
+    InvalidState,
+    /// UsageFault: invalid EXC_RETURN value loaded into PC
+    InvalidPc,
+    /// UsageFault: coprocessor access while it is disabled or absent
+    NoCoprocessor,
+    /// UsageFault: unaligned access with UNALIGN_TRP set
+    UnalignedAccess,
+    /// UsageFault: division by zero with DIV_0_TRP set
+    DivideByZero,
+    /// HardFault: bus error on a vector table read
+    VectorTableRead,
+    /// HardFault: a configurable fault escalated because it was disabled or
+    /// could not be taken
+    Forced,
+    /// HardFault: debug event while the debugger is not attached
+    DebugEvent,
+}
+
+// CFSR fields
+pub const CFSR_IACCVIOL: u32 = 1 << 0;
+pub const CFSR_DACCVIOL: u32 = 1 << 1;
+pub const CFSR_MUNSTKERR: u32 = 1 << 3;
+pub const CFSR_MSTKERR: u32 = 1 << 4;
+pub const CFSR_MLSPERR: u32 = 1 << 5;
+pub const CFSR_MMARVALID: u32 = 1 << 7;
+pub const CFSR_IBUSERR: u32 = 1 << 8;
+pub const CFSR_PRECISERR: u32 = 1 << 9;
+pub const CFSR_IMPRECISERR: u32 = 1 << 10;
+pub const CFSR_UNSTKERR: u32 = 1 << 11;
+pub const CFSR_STKERR: u32 = 1 << 12;
+pub const CFSR_LSPERR: u32 = 1 << 13;
+pub const CFSR_BFARVALID: u32 = 1 << 15;
+pub const CFSR_UNDEFINSTR: u32 = 1 << 16;
+pub const CFSR_INVSTATE: u32 = 1 << 17;
+pub const CFSR_INVPC: u32 = 1 << 18;
+pub const CFSR_NOCP: u32 = 1 << 19;
+pub const CFSR_UNALIGNED: u32 = 1 << 24;
+pub const CFSR_DIVBYZERO: u32 = 1 << 25;
+
+// HFSR fields
+pub const HFSR_VECTTBL: u32 = 1 << 1;
+pub const HFSR_FORCED: u32 = 1 << 30;
+pub const HFSR_DEBUGEVT: u32 = 1 << 31;
+
+const CFSR_CAUSES: [(u32, FaultCause); 17] = [
+    (CFSR_IACCVIOL, FaultCause::InstructionAccessViolation),
+    (CFSR_DACCVIOL, FaultCause::DataAccessViolation),
+    (CFSR_MUNSTKERR, FaultCause::MemManageUnstacking),
+    (CFSR_MSTKERR, FaultCause::MemManageStacking),
+    (CFSR_MLSPERR, FaultCause::MemManageLazyFpStacking),
+    (CFSR_IBUSERR, FaultCause::InstructionBusError),
+    (CFSR_PRECISERR, FaultCause::PreciseDataBusError),
+    (CFSR_IMPRECISERR, FaultCause::ImpreciseDataBusError),
+    (CFSR_UNSTKERR, FaultCause::BusFaultUnstacking),
+    (CFSR_STKERR, FaultCause::BusFaultStacking),
+    (CFSR_LSPERR, FaultCause::BusFaultLazyFpStacking),
+    (CFSR_UNDEFINSTR, FaultCause::UndefinedInstruction),
+    (CFSR_INVSTATE, FaultCause::InvalidState),
+    (CFSR_INVPC, FaultCause::InvalidPc),
+    (CFSR_NOCP, FaultCause::NoCoprocessor),
+    (CFSR_UNALIGNED, FaultCause::UnalignedAccess),
+    (CFSR_DIVBYZERO, FaultCause::DivideByZero),
+];
+
+const HFSR_CAUSES: [(u32, FaultCause); 3] = [
+    (HFSR_VECTTBL, FaultCause::VectorTableRead),
+    (HFSR_FORCED, FaultCause::Forced),
+    (HFSR_DEBUGEVT, FaultCause::DebugEvent),
+];
+
+/// Everything captured when a fault was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultReport {
+    pub kind: FaultKind,
+    pub frame: ExceptionFrame,
+    /// Configurable Fault Status Register
+    pub cfsr: u32,
+    /// HardFault Status Register
+    pub hfsr: u32,
+    /// MemManage Fault Address Register, only meaningful with MMARVALID
+    pub mmfar: u32,
+    /// BusFault Address Register, only meaningful with BFARVALID
+    pub bfar: u32,
+}
+
+impl FaultReport {
+    /// Build a report from the stacked frame and the raw fault registers.
+    pub fn new(
+        kind: FaultKind,
+        frame: ExceptionFrame,
+        cfsr: u32,
+        hfsr: u32,
+        mmfar: u32,
+        bfar: u32,
+    ) -> Self {
+        Self {
+            kind,
+            frame,
+            cfsr,
+            hfsr,
+            mmfar,
+            bfar,
+        }
+    }
+
+    /// Address of the instruction that faulted (or the next one for
+    /// imprecise bus errors).
+    pub fn pc(&self) -> u32 {
+        self.frame.pc
+    }
+
+    /// Data address of a MemManage fault, if MMFAR holds a valid one
+    pub fn mem_manage_address(&self) -> Option<u32> {
+        (self.cfsr & CFSR_MMARVALID != 0).then_some(self.mmfar)
+    }
+
+    /// Data address of a BusFault, if BFAR holds a valid one
+    pub fn bus_fault_address(&self) -> Option<u32> {
+        (self.cfsr & CFSR_BFARVALID != 0).then_some(self.bfar)
+    }
+
+    /// The faulting data address, from MMFAR or BFAR
+    pub fn fault_address(&self) -> Option<u32> {
+        self.mem_manage_address().or(self.bus_fault_address())
+    }
+
+    /// Iterate over every cause flagged in CFSR and HFSR
+    pub fn causes(&self) -> impl Iterator<Item = FaultCause> + '_ {
+        let cfsr = CFSR_CAUSES.iter().filter(|(mask, _)| self.cfsr & mask != 0);
+        let hfsr = HFSR_CAUSES.iter().filter(|(mask, _)| self.hfsr & mask != 0);
+        cfsr.chain(hfsr).map(|&(_, cause)| cause)
+    }
+
+    /// Whether the fault was raised while the core was in Handler mode,
+    /// based on the exception number in the stacked xPSR.
+    pub fn in_handler(&self) -> bool {
+        self.frame.xpsr & 0x1FF != 0
+    }
+}
@@ -0,0 +1,96 @@
+//! Why the MCU last came out of reset, from the RCC reset flags and the
+//! crash records left by the panic and fault handlers.
+
+/// Reset flags latched by the RCC (CSR on STM32F1/F4/G0).
+///
+/// Several flags are usually set together: every reset source also raises
+/// the pin flag, and a power-on reset also raises the brown-out flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResetFlags {
+    /// Illegal Stop/Standby entry (LPWRRSTF)
+    pub low_power: bool,
+    /// Window watchdog (WWDGRSTF)
+    pub window_watchdog: bool,
+    /// Independent watchdog (IWDGRSTF)
+    pub independent_watchdog: bool,
+    /// SYSRESETREQ from software (SFTRSTF)
+    pub software: bool,
+    /// Power-on/power-down reset (PORRSTF, PWRRSTF on the G0)
+    pub power_on: bool,
+    /// NRST pin (PINRSTF)
+    pub pin: bool,
+    /// Brown-out reset (BORRSTF, STM32F4 only)
+    pub brown_out: bool,
+    /// Option byte loader reset (OBLRSTF)
+    pub option_byte_load: bool,
+}
+
+/// The most specific reason for the last reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetReason {
+    /// The panic handler reset the MCU, a panic record is available
+    Panic,
+    /// A fault handler reset the MCU, a fault report is available
+    Fault,
+    IndependentWatchdog,
+    WindowWatchdog,
+    /// Illegal entry into a low-power mode
+    LowPower,
+    /// SYSRESETREQ without a crash record
+    Software,
+    OptionByteLoad,
+    PowerOn,
+    BrownOut,
+    /// NRST pin only, e.g. the reset button or a debugger
+    Pin,
+    /// No flag set, e.g. the flags were already cleared this boot
+    Unknown,
+}
+
+impl ResetReason {
+    /// Combine the RCC flags with whether a panic or fault record survived
+    /// the reset. Crash records only count for a software reset, which is
+    /// how both handlers restart the MCU.
+    pub fn from_flags(flags: ResetFlags, panicked: bool, faulted: bool) -> Self {
+        if flags.software && panicked {
+            ResetReason::Panic
+        } else if flags.software && faulted {
+            ResetReason::Fault
+        } else if flags.independent_watchdog {
+            ResetReason::IndependentWatchdog
+        } else if flags.window_watchdog {
+            ResetReason::WindowWatchdog
+        } else if flags.low_power {
+            ResetReason::LowPower
+        } else if flags.software {
+            ResetReason::Software
+        } else if flags.option_byte_load {
+            ResetReason::OptionByteLoad
+        } else if flags.power_on {
+            ResetReason::PowerOn
+        } else if flags.brown_out {
+            ResetReason::BrownOut
+        } else if flags.pin {
+            ResetReason::Pin
+        } else {
+            ResetReason::Unknown
+        }
+    }
+
+    /// Short name for logs
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResetReason::Panic => "panic",
+            ResetReason::Fault => "fault",
+            ResetReason::IndependentWatchdog => "independent watchdog",
+            ResetReason::WindowWatchdog => "window watchdog",
+            ResetReason::LowPower => "low-power entry",
+            ResetReason::Software => "software",
+            ResetReason::OptionByteLoad => "option byte load",
+            ResetReason::PowerOn => "power-on",
+            ResetReason::BrownOut => "brown-out",
+            ResetReason::Pin => "pin",
+            ResetReason::Unknown => "unknown",
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use fault::crc::{crc32, crc32_update};
+
+#[test]
+fn test_crc32_check_value() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b""), 0);
+}
+
+#[test]
+fn test_crc32_incremental() {
+    let state = crc32_update(0xFFFF_FFFF, b"1234");
+    assert_eq!(!crc32_update(state, b"56789"), crc32(b"123456789"));
+}
@@ -0,0 +1,5 @@
+pub mod crc;
+pub mod panic;
+pub mod record;
+pub mod report;
+pub mod reset;
@@ -0,0 +1,62 @@
+use core::fmt::Write;
+use fault::panic::*;
+
+#[test]
+fn test_panic_record_round_trip() {
+    let mut record = PanicRecord::new("src/apps/blink.rs", 42, 9, 1234);
+    write!(record, "index out of bounds: {} >= {}", 5, 4).unwrap();
+    record.seal();
+
+    let report = record.report().unwrap();
+    assert_eq!(report.message, "index out of bounds: 5 >= 4");
+    assert_eq!(report.file, "src/apps/blink.rs");
+    assert_eq!(report.line, 42);
+    assert_eq!(report.column, 9);
+    assert_eq!(report.ticks, 1234);
+}
+
+#[test]
+fn test_unsealed_and_empty_records_are_rejected() {
+    assert!(PanicRecord::empty().report().is_none());
+    let record = PanicRecord::new("main.rs", 1, 1, 0);
+    assert!(record.report().is_none());
+}
+
+#[test]
+fn test_corrupted_record_is_rejected() {
+    let mut record = PanicRecord::new("main.rs", 7, 3, 99);
+    write!(record, "boom").unwrap();
+    record.seal();
+
+    let size = size_of::<PanicRecord>();
+    for i in (0..size).step_by(7) {
+        let mut corrupted = record;
+        let raw = &mut corrupted as *mut PanicRecord as *mut u8;
+        unsafe { *raw.add(i) ^= 0x10 };
+        assert!(corrupted.report().is_none(), "byte {i}");
+    }
+}
+
+#[test]
+fn test_long_message_is_truncated_on_char_boundary() {
+    let mut record = PanicRecord::new("main.rs", 1, 1, 0);
+    for _ in 0..PANIC_MESSAGE_LEN {
+        write!(record, "é").unwrap();
+    }
+    record.seal();
+
+    let report = record.report().unwrap();
+    assert_eq!(report.message.len(), PANIC_MESSAGE_LEN);
+    assert!(report.message.chars().all(|c| c == 'é'));
+}
+
+#[test]
+fn test_long_file_keeps_the_end_of_the_path() {
+    let path = "a/very/long/path/".repeat(8) + "src/main.rs";
+    let mut record = PanicRecord::new(&path, 1, 1, 0);
+    record.seal();
+
+    let report = record.report().unwrap();
+    assert_eq!(report.file.len(), PANIC_FILE_LEN);
+    assert!(report.file.ends_with("src/main.rs"));
+}
@@ -0,0 +1,56 @@
+use fault::record::{FaultRecord, RECORD_MAGIC};
+use fault::report::*;
+
+fn report() -> FaultReport {
+    let frame = ExceptionFrame {
+        r0: 0x10,
+        r1: 0x11,
+        r2: 0x12,
+        r3: 0x13,
+        r12: 0x1C,
+        lr: 0xFFFF_FFF9,
+        pc: 0x0800_1234,
+        xpsr: 0x0100_0003,
+    };
+    FaultReport::new(
+        FaultKind::HardFault,
+        frame,
+        CFSR_PRECISERR | CFSR_BFARVALID,
+        HFSR_FORCED,
+        0,
+        0x4000_0000,
+    )
+}
+
+#[test]
+fn test_record_round_trip() {
+    let record = FaultRecord::new(&report());
+    assert_eq!(record.report(), Some(report()));
+}
+
+#[test]
+fn test_empty_record_has_no_report() {
+    assert_eq!(FaultRecord::empty().report(), None);
+    assert_eq!(FaultRecord::default().report(), None);
+}
+
+#[test]
+fn test_corrupted_record_is_rejected() {
+    let record = FaultRecord::new(&report());
+
+    // Flip one bit in every word in turn, each must invalidate the record
+    let words = size_of::<FaultRecord>() / 4;
+    for i in 0..words {
+        let mut corrupted = record;
+        let raw = &mut corrupted as *mut FaultRecord as *mut u32;
+        unsafe { *raw.add(i) ^= 1 << 5 };
+        assert_eq!(corrupted.report(), None, "word {i}");
+    }
+}
+
+#[test]
+fn test_record_layout_starts_with_magic() {
+    let record = FaultRecord::new(&report());
+    let first = unsafe { *(&record as *const FaultRecord as *const u32) };
+    assert_eq!(first, RECORD_MAGIC);
+}
@@ -0,0 +1,114 @@
+use fault::report::*;
+
+fn frame() -> ExceptionFrame {
+    ExceptionFrame {
+        r0: 1,
+        r1: 2,
+        r2: 3,
+        r3: 4,
+        r12: 12,
+        lr: 0x0800_0101,
+        pc: 0x0800_0200,
+        xpsr: 0x0100_0000,
+    }
+}
+
+#[test]
+fn test_precise_bus_fault_reports_bfar() {
+    let report = FaultReport::new(
+        FaultKind::BusFault,
+        frame(),
+        CFSR_PRECISERR | CFSR_BFARVALID,
+        0,
+        0,
+        0x2003_0000,
+    );
+    assert_eq!(report.bus_fault_address(), Some(0x2003_0000));
+    assert_eq!(report.mem_manage_address(), None);
+    assert_eq!(report.fault_address(), Some(0x2003_0000));
+    let mut causes = report.causes();
+    assert_eq!(causes.next(), Some(FaultCause::PreciseDataBusError));
+    assert_eq!(causes.next(), None);
+}
+
+#[test]
+fn test_fault_address_ignored_without_valid_bit() {
+    let report = FaultReport::new(
+        FaultKind::BusFault,
+        frame(),
+        CFSR_IMPRECISERR,
+        0,
+        0xDEAD_BEEF,
+        0xDEAD_BEEF,
+    );
+    assert_eq!(report.fault_address(), None);
+    assert_eq!(
+        report.causes().next(),
+        Some(FaultCause::ImpreciseDataBusError)
+    );
+}
+
+#[test]
+fn test_mem_manage_data_access_violation() {
+    let report = FaultReport::new(
+        FaultKind::MemManage,
+        frame(),
+        CFSR_DACCVIOL | CFSR_MMARVALID,
+        0,
+        0x0000_0004,
+        0,
+    );
+    assert_eq!(report.mem_manage_address(), Some(0x0000_0004));
+    assert_eq!(report.fault_address(), Some(0x0000_0004));
+    assert_eq!(
+        report.causes().next(),
+        Some(FaultCause::DataAccessViolation)
+    );
+}
+
+#[test]
+fn test_forced_hard_fault_lists_escalated_cause() {
+    let report = FaultReport::new(
+        FaultKind::HardFault,
+        frame(),
+        CFSR_UNDEFINSTR,
+        HFSR_FORCED,
+        0,
+        0,
+    );
+    let mut causes = report.causes();
+    assert_eq!(causes.next(), Some(FaultCause::UndefinedInstruction));
+    assert_eq!(causes.next(), Some(FaultCause::Forced));
+    assert_eq!(causes.next(), None);
+}
+
+#[test]
+fn test_usage_fault_causes() {
+    let report = FaultReport::new(
+        FaultKind::UsageFault,
+        frame(),
+        CFSR_INVSTATE | CFSR_DIVBYZERO,
+        0,
+        0,
+        0,
+    );
+    let mut causes = report.causes();
+    assert_eq!(causes.next(), Some(FaultCause::InvalidState));
+    assert_eq!(causes.next(), Some(FaultCause::DivideByZero));
+    assert_eq!(causes.next(), None);
+    assert_eq!(report.pc(), 0x0800_0200);
+    assert!(!report.in_handler());
+}
+
+#[test]
+fn test_fault_kind_round_trip() {
+    for kind in [
+        FaultKind::HardFault,
+        FaultKind::MemManage,
+        FaultKind::BusFault,
+        FaultKind::UsageFault,
+    ] {
+        assert_eq!(FaultKind::from_u32(kind as u32), Some(kind));
+    }
+    assert_eq!(FaultKind::from_u32(4), None);
+}
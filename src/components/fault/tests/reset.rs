@@ -0,0 +1,112 @@
+use fault::reset::{ResetFlags, ResetReason};
+
+#[test]
+fn test_power_on_sets_pin_and_brown_out_too() {
+    let flags = ResetFlags {
+        power_on: true,
+        brown_out: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(flags, false, false),
+        ResetReason::PowerOn
+    );
+}
+
+#[test]
+fn test_brown_out_without_power_on() {
+    let flags = ResetFlags {
+        brown_out: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(flags, false, false),
+        ResetReason::BrownOut
+    );
+}
+
+#[test]
+fn test_pin_only() {
+    let flags = ResetFlags {
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(flags, false, false),
+        ResetReason::Pin
+    );
+}
+
+#[test]
+fn test_software_reset_with_crash_records() {
+    let flags = ResetFlags {
+        software: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(flags, true, false),
+        ResetReason::Panic
+    );
+    assert_eq!(
+        ResetReason::from_flags(flags, false, true),
+        ResetReason::Fault
+    );
+    assert_eq!(
+        ResetReason::from_flags(flags, false, false),
+        ResetReason::Software
+    );
+}
+
+#[test]
+fn test_crash_record_without_software_reset_is_ignored() {
+    let flags = ResetFlags {
+        independent_watchdog: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(flags, true, true),
+        ResetReason::IndependentWatchdog
+    );
+}
+
+#[test]
+fn test_watchdogs_and_low_power() {
+    let wwdg = ResetFlags {
+        window_watchdog: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(wwdg, false, false),
+        ResetReason::WindowWatchdog
+    );
+
+    let lpwr = ResetFlags {
+        low_power: true,
+        pin: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        ResetReason::from_flags(lpwr, false, false),
+        ResetReason::LowPower
+    );
+
+    assert_eq!(
+        ResetReason::from_flags(ResetFlags::default(), false, false),
+        ResetReason::Unknown
+    );
+}
+
+#[test]
+fn test_reason_names() {
+    assert_eq!(ResetReason::PowerOn.as_str(), "power-on");
+    assert_eq!(
+        ResetReason::IndependentWatchdog.as_str(),
+        "independent watchdog"
+    );
+    assert_eq!(ResetReason::Unknown.as_str(), "unknown");
+}
@@ -0,0 +1,52 @@
+//! The bus as the protocol helpers see it: transactions of writes and
+//! reads addressed to one target.
+//!
+//! Addresses are 7-bit unless `ADDRESS_10BIT` is set, the same encoding
+//! as the `I2c` driver addresses.
+
+/// Flag of a 10-bit address
+pub const ADDRESS_10BIT: u16 = 0x0400;
+
+/// One part of a transaction
+#[derive(Debug, PartialEq, Eq)]
+pub enum Operation<'a> {
+    Write(&'a [u8]),
+    Read(&'a mut [u8]),
+}
+
+/// A bus the SMBus and PMBus helpers run on
+pub trait Bus {
+    /// Run `operations` on the target at `address`: START, a repeated
+    /// START between operations and STOP after the last. Returns Err(-1)
+    /// if the target does not acknowledge, Err(-3) on a bus fault.
+    fn transaction(&mut self, address: u16, operations: &mut [Operation<'_>]) -> Result<(), i32>;
+}
+
+pub fn is_ten_bit(address: u16) -> bool {
+    address & ADDRESS_10BIT != 0
+}
+
+/// Whether `address` is a 7-bit address, or a 10-bit one with the flag
+pub fn is_valid(address: u16) -> bool {
+    if is_ten_bit(address) {
+        address & !(ADDRESS_10BIT | 0x3FF) == 0
+    } else {
+        address < 0x80
+    }
+}
+
+/// First byte of a 10-bit address on the wire: 11110, address bits 9:8
+/// and the R/W bit
+pub fn header(address: u16, read: bool) -> u8 {
+    0xF0 | ((address >> 7) & 0x06) as u8 | read as u8
+}
+
+/// Second byte of a 10-bit address on the wire: address bits 7:0
+pub fn low_byte(address: u16) -> u8 {
+    address as u8
+}
+
+/// The byte a 7-bit address is sent as, with the R/W bit
+pub fn address_byte(address: u8, read: bool) -> u8 {
+    address << 1 | read as u8
+}
@@ -0,0 +1,108 @@
+//! Known devices and how to tell them apart.
+//!
+//! Several devices share addresses (an MPU-6050 and a DS1307 both sit at
+//! 0x68), so a device is recognised by reading a register whose value it
+//! fixes: an ID register where it has one, otherwise bits its datasheet
+//! says always read 0. A device without any, like the AT24, is recognised
+//! by its address alone, which is only a best guess.
+
+/// How a device at a matching address is recognised
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// By the address alone
+    Address,
+    /// By `register` reading `value` in the bits of `mask`
+    Register { register: u8, mask: u8, value: u8 },
+}
+
+/// A device the scan can name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Device {
+    pub name: &'static str,
+    /// Lowest and highest address the device can be strapped to
+    pub first: u8,
+    pub last: u8,
+    pub check: Check,
+}
+
+impl Device {
+    pub fn answers_at(&self, address: u8) -> bool {
+        (self.first..=self.last).contains(&address)
+    }
+}
+
+/// Known devices, tried in this order; devices recognised by the address
+/// alone come after those sharing it that have a register check.
+pub const DEVICES: &[Device] = &[
+    Device {
+        name: "MPU-6050",
+        first: 0x68,
+        last: 0x69,
+        // WHO_AM_I holds the upper six bits of the address
+        check: Check::Register {
+            register: 0x75,
+            mask: 0x7E,
+            value: 0x68,
+        },
+    },
+    Device {
+        name: "DS1307",
+        first: 0x68,
+        last: 0x68,
+        // Control register bits 2, 3, 5 and 6 always read 0
+        check: Check::Register {
+            register: 0x07,
+            mask: 0x6C,
+            value: 0x00,
+        },
+    },
+    Device {
+        name: "BMP280",
+        first: 0x76,
+        last: 0x77,
+        check: Check::Register {
+            register: 0xD0,
+            mask: 0xFF,
+            value: 0x58,
+        },
+    },
+    Device {
+        name: "BME280",
+        first: 0x76,
+        last: 0x77,
+        check: Check::Register {
+            register: 0xD0,
+            mask: 0xFF,
+            value: 0x60,
+        },
+    },
+    Device {
+        name: "AT24",
+        first: 0x50,
+        last: 0x57,
+        check: Check::Address,
+    },
+];
+
+/// Known devices that can answer at `address`, in the order they are tried
+pub fn candidates(address: u8) -> impl Iterator<Item = &'static Device> {
+    DEVICES
+        .iter()
+        .filter(move |device| device.answers_at(address))
+}
+
+/// Name the device at `address`, reading registers with `read`. A failed
+/// read rules a candidate out. Returns None if no known device matches.
+pub fn identify(
+    address: u8,
+    mut read: impl FnMut(u8) -> Result<u8, i32>,
+) -> Option<&'static Device> {
+    candidates(address).find(|device| match device.check {
+        Check::Address => true,
+        Check::Register {
+            register,
+            mask,
+            value,
+        } => read(register).is_ok_and(|read| read & mask == value),
+    })
+}
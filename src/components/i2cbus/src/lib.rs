@@ -0,0 +1,9 @@
+#![no_std]
+
+pub mod bus;
+pub mod device;
+pub mod mock;
+pub mod pec;
+pub mod pmbus;
+pub mod scan;
+pub mod smbus;
@@ -0,0 +1,197 @@
+//! A mock I2C target for host tests of code written against `Bus`.
+//!
+//! It behaves like most sensors and PMBus devices: the first byte written
+//! in a transaction selects a register, further bytes are written from
+//! there and reads continue from there, wrapping at 256. Commands set up
+//! with `set_block` hold an SMBus block instead, read back prefixed with
+//! its length.
+//!
+//! With PEC enabled, the last write of a transaction must end with the
+//! PEC of the message, or the target rejects it with a NACK, and the last
+//! read ends with the PEC after the register or block bytes.
+
+use crate::bus::{Bus, Operation, address_byte};
+use crate::pec;
+use crate::smbus::MAX_BLOCK;
+
+/// Most commands holding a block
+pub const MAX_BLOCKS: usize = 4;
+
+/// What the target sends past the end of a block
+const PADDING: u8 = 0xFF;
+
+#[derive(Clone, Copy)]
+struct Block {
+    command: u8,
+    len: usize,
+    data: [u8; MAX_BLOCK],
+}
+
+pub struct Target {
+    address: u16,
+    pub registers: [u8; 256],
+    pointer: u8,
+    blocks: [Option<Block>; MAX_BLOCKS],
+    pec: bool,
+    fault: Option<i32>,
+    /// Transactions addressed to the target
+    pub transactions: usize,
+    /// Writes rejected for a wrong PEC
+    pub pec_errors: usize,
+}
+
+impl Target {
+    pub fn new(address: u16) -> Self {
+        Self {
+            address,
+            registers: [0; 256],
+            pointer: 0,
+            blocks: [None; MAX_BLOCKS],
+            pec: false,
+            fault: None,
+            transactions: 0,
+            pec_errors: 0,
+        }
+    }
+
+    pub fn with_pec(mut self, pec: bool) -> Self {
+        self.pec = pec;
+        self
+    }
+
+    /// Make `command` hold the block `data`. Returns Err(-1) if `data` is
+    /// longer than `MAX_BLOCK` or `MAX_BLOCKS` commands hold one already.
+    pub fn set_block(&mut self, command: u8, data: &[u8]) -> Result<(), i32> {
+        if data.len() > MAX_BLOCK {
+            return Err(-1);
+        }
+        let slot = match self.block_index(command) {
+            Some(index) => index,
+            None => self.blocks.iter().position(Option::is_none).ok_or(-1)?,
+        };
+        let mut block = Block {
+            command,
+            len: data.len(),
+            data: [0; MAX_BLOCK],
+        };
+        block.data[..data.len()].copy_from_slice(data);
+        self.blocks[slot] = Some(block);
+        Ok(())
+    }
+
+    /// The block `command` holds
+    pub fn block(&self, command: u8) -> Option<&[u8]> {
+        let block = self.blocks[self.block_index(command)?].as_ref()?;
+        Some(&block.data[..block.len])
+    }
+
+    /// Register the next read starts at
+    pub fn pointer(&self) -> u8 {
+        self.pointer
+    }
+
+    /// Fail the following transactions with `error`, such as Err(-3) for a
+    /// bus fault, until called with None
+    pub fn fail_with(&mut self, error: Option<i32>) {
+        self.fault = error;
+    }
+
+    fn block_index(&self, command: u8) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|block| block.is_some_and(|block| block.command == command))
+    }
+
+    fn write(&mut self, bytes: &[u8], last: bool, crc: &mut u8) -> Result<Option<u8>, i32> {
+        *crc = pec::update(*crc, &[address_byte(self.address as u8, false)]);
+        let payload = if self.pec && last {
+            let (payload, sent) = bytes.split_at(bytes.len().saturating_sub(1));
+            if sent.first() != Some(&pec::update(*crc, payload)) {
+                self.pec_errors += 1;
+                return Err(-1);
+            }
+            payload
+        } else {
+            bytes
+        };
+        *crc = pec::update(*crc, payload);
+        let Some((&command, data)) = payload.split_first() else {
+            return Ok(None);
+        };
+        self.pointer = command;
+        match self.block_index(command) {
+            Some(_) if !data.is_empty() => {
+                let len = (data[0] as usize).min(data.len() - 1);
+                self.set_block(command, &data[1..1 + len])?;
+            }
+            Some(_) => {}
+            None => {
+                for &byte in data {
+                    self.registers[self.pointer as usize] = byte;
+                    self.pointer = self.pointer.wrapping_add(1);
+                }
+                self.pointer = command;
+            }
+        }
+        Ok(Some(command))
+    }
+
+    fn read(&mut self, buffer: &mut [u8], command: Option<u8>, last: bool, crc: &mut u8) {
+        *crc = pec::update(*crc, &[address_byte(self.address as u8, true)]);
+        // A block is sent prefixed with its length
+        let mut message = [PADDING; 1 + MAX_BLOCK];
+        let block = command
+            .and_then(|command| self.block(command))
+            .map(|block| {
+                message[0] = block.len() as u8;
+                message[1..1 + block.len()].copy_from_slice(block);
+                1 + block.len()
+            });
+        // Bytes of the message proper, the PEC follows them
+        let len = match block {
+            Some(block) => block.min(buffer.len()),
+            None if self.pec && last => buffer.len().saturating_sub(1),
+            None => buffer.len(),
+        };
+        if block.is_some() {
+            buffer[..len].copy_from_slice(&message[..len]);
+        } else {
+            for byte in &mut buffer[..len] {
+                *byte = self.registers[self.pointer as usize];
+                self.pointer = self.pointer.wrapping_add(1);
+            }
+        }
+        *crc = pec::update(*crc, &buffer[..len]);
+        let mut rest = buffer[len..].iter_mut();
+        if self.pec
+            && last
+            && let Some(byte) = rest.next()
+        {
+            *byte = *crc;
+        }
+        rest.for_each(|byte| *byte = PADDING);
+    }
+}
+
+impl Bus for Target {
+    fn transaction(&mut self, address: u16, operations: &mut [Operation<'_>]) -> Result<(), i32> {
+        if address != self.address {
+            return Err(-1);
+        }
+        self.transactions += 1;
+        if let Some(error) = self.fault {
+            return Err(error);
+        }
+        let mut crc = 0;
+        let mut command = None;
+        let count = operations.len();
+        for (i, operation) in operations.iter_mut().enumerate() {
+            let last = i + 1 == count;
+            match operation {
+                Operation::Write(bytes) => command = self.write(bytes, last, &mut crc)?,
+                Operation::Read(buffer) => self.read(buffer, command, last, &mut crc),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//! SMBus packet error code: a CRC-8 with polynomial x^8 + x^2 + x + 1
+//! and initial value 0, over every byte of a message including the
+//! address bytes.
+
+const POLYNOMIAL: u8 = 0x07;
+
+/// Continue the PEC `crc` over `bytes`
+pub fn update(mut crc: u8, bytes: &[u8]) -> u8 {
+    for &byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                crc << 1 ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// The PEC of `bytes`
+pub fn pec(bytes: &[u8]) -> u8 {
+    update(0, bytes)
+}
@@ -0,0 +1,120 @@
+//! PMBus over SMBus: command codes, the LINEAR11 and LINEAR16 data
+//! formats, and reads of the usual telemetry.
+//!
+//! LINEAR11 packs a 5-bit exponent above an 11-bit mantissa, both two's
+//! complement, for value = mantissa * 2^exponent. LINEAR16, used for
+//! output voltages, is an unsigned 16-bit mantissa whose exponent comes
+//! from `VOUT_MODE`.
+
+use crate::bus::Bus;
+use crate::smbus::Smbus;
+
+/// Command codes of the PMBus specification, part II
+pub mod command {
+    pub const PAGE: u8 = 0x00;
+    pub const OPERATION: u8 = 0x01;
+    pub const CLEAR_FAULTS: u8 = 0x03;
+    pub const VOUT_MODE: u8 = 0x20;
+    pub const VOUT_COMMAND: u8 = 0x21;
+    pub const STATUS_BYTE: u8 = 0x78;
+    pub const STATUS_WORD: u8 = 0x79;
+    pub const READ_VIN: u8 = 0x88;
+    pub const READ_IIN: u8 = 0x89;
+    pub const READ_VOUT: u8 = 0x8B;
+    pub const READ_IOUT: u8 = 0x8C;
+    pub const READ_TEMPERATURE_1: u8 = 0x8D;
+    pub const READ_POUT: u8 = 0x96;
+    pub const PMBUS_REVISION: u8 = 0x98;
+    pub const MFR_ID: u8 = 0x99;
+    pub const MFR_MODEL: u8 = 0x9A;
+}
+
+/// `value` * 2^`exponent`, without the float functions of std
+fn scale(mut value: f32, exponent: i32) -> f32 {
+    for _ in 0..exponent.max(0) {
+        value *= 2.0;
+    }
+    for _ in exponent.min(0)..0 {
+        value *= 0.5;
+    }
+    value
+}
+
+fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+pub fn linear11_to_f32(raw: u16) -> f32 {
+    let exponent = (raw as i16) >> 11;
+    let mantissa = ((raw << 5) as i16) >> 5;
+    scale(mantissa as f32, exponent as i32)
+}
+
+/// Encode `value` as LINEAR11 with the smallest exponent the mantissa
+/// fits with, which keeps the most precision. Values beyond the range
+/// saturate.
+pub fn f32_to_linear11(value: f32) -> u16 {
+    let mut exponent = -16;
+    let mut mantissa = round(scale(value, 16));
+    while !(-1024..=1023).contains(&mantissa) && exponent < 15 {
+        exponent += 1;
+        mantissa = round(scale(value, -exponent));
+    }
+    let mantissa = mantissa.clamp(-1024, 1023);
+    ((exponent as u16 & 0x1F) << 11) | (mantissa as u16 & 0x7FF)
+}
+
+/// Exponent of LINEAR16 output voltages from `VOUT_MODE`, or None if the
+/// device uses another mode (VID, direct)
+pub fn vout_exponent(vout_mode: u8) -> Option<i8> {
+    if vout_mode >> 5 == 0 {
+        Some(((vout_mode << 3) as i8) >> 3)
+    } else {
+        None
+    }
+}
+
+pub fn linear16_to_f32(raw: u16, exponent: i8) -> f32 {
+    scale(raw as f32, exponent as i32)
+}
+
+/// Encode `value` as LINEAR16, saturating to the 16-bit range
+pub fn f32_to_linear16(value: f32, exponent: i8) -> u16 {
+    round(scale(value, -(exponent as i32))).clamp(0, u16::MAX as i32) as u16
+}
+
+/// Select the page (output rail) later commands apply to
+pub fn set_page<B: Bus>(device: &mut Smbus<'_, B>, page: u8) -> Result<(), i32> {
+    device.write_byte_data(command::PAGE, page)
+}
+
+pub fn clear_faults<B: Bus>(device: &mut Smbus<'_, B>) -> Result<(), i32> {
+    device.send_byte(command::CLEAR_FAULTS)
+}
+
+pub fn status_word<B: Bus>(device: &mut Smbus<'_, B>) -> Result<u16, i32> {
+    device.read_word_data(command::STATUS_WORD)
+}
+
+/// Read a LINEAR11 value, such as `READ_VIN`, `READ_IOUT` or
+/// `READ_TEMPERATURE_1`
+pub fn read_linear11<B: Bus>(device: &mut Smbus<'_, B>, command: u8) -> Result<f32, i32> {
+    device.read_word_data(command).map(linear11_to_f32)
+}
+
+/// Read the output voltage. Returns Err(-2) unless `VOUT_MODE` is linear.
+pub fn read_vout<B: Bus>(device: &mut Smbus<'_, B>) -> Result<f32, i32> {
+    let exponent = vout_exponent(device.read_byte_data(command::VOUT_MODE)?).ok_or(-2)?;
+    let raw = device.read_word_data(command::READ_VOUT)?;
+    Ok(linear16_to_f32(raw, exponent))
+}
+
+/// Set the output voltage. Returns Err(-2) unless `VOUT_MODE` is linear.
+pub fn set_vout<B: Bus>(device: &mut Smbus<'_, B>, volts: f32) -> Result<(), i32> {
+    let exponent = vout_exponent(device.read_byte_data(command::VOUT_MODE)?).ok_or(-2)?;
+    device.write_word_data(command::VOUT_COMMAND, f32_to_linear16(volts, exponent))
+}
@@ -0,0 +1,89 @@
+//! Bus scan: which 7-bit addresses a target acknowledges.
+//!
+//! A target is probed with an address-only write (START, address + W,
+//! STOP), which no device acts on. Drivers report the outcome as
+//! `Ok(())` for an ACK, Err(-1) for a NACK and another error for a bus
+//! fault (arbitration lost, bus error); only a fault stops a scan.
+
+/// First address outside the reserved block at the start of the range
+/// (general call, CBUS, high-speed master codes)
+pub const FIRST: u8 = 0x08;
+
+/// Last address before the reserved block at the end (10-bit addressing
+/// prefixes, device ID)
+pub const LAST: u8 = 0x77;
+
+/// Whether `address` is reserved by the I2C specification rather than
+/// assignable to a target, or not a 7-bit address at all
+pub fn is_reserved(address: u8) -> bool {
+    !(FIRST..=LAST).contains(&address)
+}
+
+/// How a target answered its address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Ack,
+    Nack,
+}
+
+/// Classify the result of an address-only transfer. A NACK is an answer;
+/// any other error is a bus fault and returned.
+pub fn classify(result: Result<(), i32>) -> Result<Response, i32> {
+    match result {
+        Ok(()) => Ok(Response::Ack),
+        Err(-1) => Ok(Response::Nack),
+        Err(e) => Err(e),
+    }
+}
+
+/// A set of 7-bit addresses, bit n for address n
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddressMap(u128);
+
+impl AddressMap {
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    pub const fn from_bits(bits: u128) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(&self) -> u128 {
+        self.0
+    }
+
+    /// Add `address`; the bit above 7 is ignored
+    pub fn insert(&mut self, address: u8) {
+        self.0 |= 1 << (address & 0x7F);
+    }
+
+    pub fn contains(&self, address: u8) -> bool {
+        address < 0x80 && self.0 & (1 << address) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Addresses in the set, lowest first
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..0x80u8).filter(|&address| self.contains(address))
+    }
+}
+
+/// Probe every non-reserved address with `probe`, an address-only write
+/// returning the driver result. Stops at the first bus fault.
+pub fn scan(mut probe: impl FnMut(u8) -> Result<(), i32>) -> Result<AddressMap, i32> {
+    let mut found = AddressMap::new();
+    for address in FIRST..=LAST {
+        if classify(probe(address))? == Response::Ack {
+            found.insert(address);
+        }
+    }
+    Ok(found)
+}
@@ -0,0 +1,171 @@
+//! SMBus protocols over a `Bus`: byte, word, process call and block
+//! transfers to one device, with an optional packet error code.
+//!
+//! Words are little-endian. Errors are those of the bus, plus Err(-4) if
+//! a PEC does not match and Err(-5) if a block read reports a length
+//! beyond `MAX_BLOCK` or the buffer.
+
+use crate::bus::{Bus, Operation, address_byte};
+use crate::pec;
+
+/// Longest block of a block transfer
+pub const MAX_BLOCK: usize = 32;
+
+/// Longest message: command, count, block and PEC
+const MAX_MESSAGE: usize = 2 + MAX_BLOCK + 1;
+
+/// An SMBus device on `bus`, at a 7-bit address
+pub struct Smbus<'b, B: Bus> {
+    bus: &'b mut B,
+    address: u8,
+    pec: bool,
+}
+
+impl<'b, B: Bus> Smbus<'b, B> {
+    pub fn new(bus: &'b mut B, address: u8) -> Self {
+        Self {
+            bus,
+            address,
+            pec: false,
+        }
+    }
+
+    /// Append a PEC to writes and check the one ending reads
+    pub fn with_pec(mut self, pec: bool) -> Self {
+        self.pec = pec;
+        self
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// PEC of a message writing `written`, then reading `read` after a
+    /// repeated START if there is anything to read
+    fn message_pec(&self, written: &[u8], read: &[u8]) -> u8 {
+        let mut crc = 0;
+        if !written.is_empty() {
+            crc = pec::update(crc, &[address_byte(self.address, false)]);
+            crc = pec::update(crc, written);
+        }
+        if !read.is_empty() {
+            crc = pec::update(crc, &[address_byte(self.address, true)]);
+            crc = pec::update(crc, read);
+        }
+        crc
+    }
+
+    /// Write `bytes`, followed by the PEC if enabled
+    fn write(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        let mut message = [0; MAX_MESSAGE];
+        let mut len = bytes.len();
+        message[..len].copy_from_slice(bytes);
+        if self.pec {
+            message[len] = self.message_pec(bytes, &[]);
+            len += 1;
+        }
+        let address = self.address as u16;
+        self.bus
+            .transaction(address, &mut [Operation::Write(&message[..len])])
+    }
+
+    /// Write `bytes` if any, then read `data` after a repeated START,
+    /// followed by the PEC if enabled
+    fn write_read(&mut self, bytes: &[u8], data: &mut [u8]) -> Result<(), i32> {
+        let mut message = [0; MAX_MESSAGE];
+        let len = data.len() + self.pec as usize;
+        let address = self.address as u16;
+        if bytes.is_empty() {
+            self.bus
+                .transaction(address, &mut [Operation::Read(&mut message[..len])])?;
+        } else {
+            self.bus.transaction(
+                address,
+                &mut [
+                    Operation::Write(bytes),
+                    Operation::Read(&mut message[..len]),
+                ],
+            )?;
+        }
+        let (read, rest) = message.split_at(data.len());
+        if self.pec && rest[0] != self.message_pec(bytes, read) {
+            return Err(-4);
+        }
+        data.copy_from_slice(read);
+        Ok(())
+    }
+
+    pub fn send_byte(&mut self, value: u8) -> Result<(), i32> {
+        self.write(&[value])
+    }
+
+    pub fn receive_byte(&mut self) -> Result<u8, i32> {
+        let mut value = [0];
+        self.write_read(&[], &mut value)?;
+        Ok(value[0])
+    }
+
+    pub fn write_byte_data(&mut self, command: u8, value: u8) -> Result<(), i32> {
+        self.write(&[command, value])
+    }
+
+    pub fn read_byte_data(&mut self, command: u8) -> Result<u8, i32> {
+        let mut value = [0];
+        self.write_read(&[command], &mut value)?;
+        Ok(value[0])
+    }
+
+    pub fn write_word_data(&mut self, command: u8, value: u16) -> Result<(), i32> {
+        let [low, high] = value.to_le_bytes();
+        self.write(&[command, low, high])
+    }
+
+    pub fn read_word_data(&mut self, command: u8) -> Result<u16, i32> {
+        let mut value = [0; 2];
+        self.write_read(&[command], &mut value)?;
+        Ok(u16::from_le_bytes(value))
+    }
+
+    /// Write a word and read the device's answer in one transaction
+    pub fn process_call(&mut self, command: u8, value: u16) -> Result<u16, i32> {
+        let [low, high] = value.to_le_bytes();
+        let mut answer = [0; 2];
+        self.write_read(&[command, low, high], &mut answer)?;
+        Ok(u16::from_le_bytes(answer))
+    }
+
+    /// Write `data`, prefixed with its length. Returns Err(-1) if it is
+    /// longer than `MAX_BLOCK`.
+    pub fn block_write(&mut self, command: u8, data: &[u8]) -> Result<(), i32> {
+        if data.len() > MAX_BLOCK {
+            return Err(-1);
+        }
+        let mut message = [0; 2 + MAX_BLOCK];
+        message[0] = command;
+        message[1] = data.len() as u8;
+        message[2..2 + data.len()].copy_from_slice(data);
+        self.write(&message[..2 + data.len()])
+    }
+
+    /// Read a block into `data` and return its length.
+    ///
+    /// The length comes first in the block, so a whole `MAX_BLOCK` is read
+    /// and the bytes the device sends past its block are dropped.
+    pub fn block_read(&mut self, command: u8, data: &mut [u8]) -> Result<usize, i32> {
+        let mut message = [0; 1 + MAX_BLOCK + 1];
+        let address = self.address as u16;
+        self.bus.transaction(
+            address,
+            &mut [Operation::Write(&[command]), Operation::Read(&mut message)],
+        )?;
+        let len = message[0] as usize;
+        if len > MAX_BLOCK || len > data.len() {
+            return Err(-5);
+        }
+        if self.pec && message[1 + len] != self.message_pec(&[command], &message[..1 + len]) {
+            return Err(-4);
+        }
+        data[..len].copy_from_slice(&message[1..1 + len]);
+        Ok(len)
+    }
+}
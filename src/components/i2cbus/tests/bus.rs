@@ -0,0 +1,28 @@
+use i2cbus::bus::*;
+
+#[test]
+fn test_valid_addresses() {
+    assert!(is_valid(0x00));
+    assert!(is_valid(0x7F));
+    assert!(!is_valid(0x80));
+    assert!(is_valid(ADDRESS_10BIT | 0x3FF));
+    assert!(!is_valid(ADDRESS_10BIT | 0x800));
+    assert!(!is_ten_bit(0x50));
+    assert!(is_ten_bit(ADDRESS_10BIT | 0x50));
+}
+
+#[test]
+fn test_ten_bit_encoding() {
+    let address = ADDRESS_10BIT | 0x2A5;
+    assert_eq!(header(address, false), 0b1111_0100);
+    assert_eq!(header(address, true), 0b1111_0101);
+    assert_eq!(low_byte(address), 0xA5);
+    assert_eq!(header(ADDRESS_10BIT | 0x3FF, false), 0b1111_0110);
+    assert_eq!(header(ADDRESS_10BIT, true), 0b1111_0001);
+}
+
+#[test]
+fn test_address_byte() {
+    assert_eq!(address_byte(0x50, false), 0xA0);
+    assert_eq!(address_byte(0x50, true), 0xA1);
+}
@@ -0,0 +1,56 @@
+use i2cbus::device::*;
+
+/// Register reads of a target holding `registers`, failing elsewhere
+fn target(registers: &[(u8, u8)]) -> impl FnMut(u8) -> Result<u8, i32> + '_ {
+    move |register| {
+        registers
+            .iter()
+            .find(|(r, _)| *r == register)
+            .map(|(_, value)| *value)
+            .ok_or(-1)
+    }
+}
+
+#[test]
+fn test_candidates() {
+    let names = |address| candidates(address).map(|d| d.name).collect::<Vec<_>>();
+    assert_eq!(names(0x68), ["MPU-6050", "DS1307"]);
+    assert_eq!(names(0x69), ["MPU-6050"]);
+    assert_eq!(names(0x53), ["AT24"]);
+    assert!(names(0x20).is_empty());
+}
+
+#[test]
+fn test_identify_by_register() {
+    let mpu = identify(0x68, target(&[(0x75, 0x68), (0x07, 0x00)]));
+    assert_eq!(mpu.map(|d| d.name), Some("MPU-6050"));
+    // The rest of the control register is the DS1307's to choose
+    let rtc = identify(0x68, target(&[(0x75, 0x00), (0x07, 0x93)]));
+    assert_eq!(rtc.map(|d| d.name), Some("DS1307"));
+    let bme = identify(0x77, target(&[(0xD0, 0x60)]));
+    assert_eq!(bme.map(|d| d.name), Some("BME280"));
+}
+
+#[test]
+fn test_identify_by_address() {
+    let eeprom = identify(0x57, target(&[]));
+    assert_eq!(eeprom.map(|d| d.name), Some("AT24"));
+}
+
+#[test]
+fn test_identify_unknown() {
+    assert_eq!(identify(0x68, target(&[(0x75, 0x12), (0x07, 0x04)])), None);
+    assert_eq!(identify(0x76, target(&[])), None);
+    assert_eq!(identify(0x20, target(&[])), None);
+}
+
+#[test]
+fn test_checks_read_only_candidates() {
+    let mut reads = Vec::new();
+    let device = identify(0x76, |register| {
+        reads.push(register);
+        Ok(0x58)
+    });
+    assert_eq!(device.map(|d| d.name), Some("BMP280"));
+    assert_eq!(reads, [0xD0]);
+}
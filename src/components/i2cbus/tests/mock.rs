@@ -0,0 +1,90 @@
+use i2cbus::bus::{ADDRESS_10BIT, Bus, Operation};
+use i2cbus::mock::*;
+
+#[test]
+fn test_register_pointer() {
+    let mut target = Target::new(0x48);
+    target
+        .transaction(0x48, &mut [Operation::Write(&[0x10, 1, 2, 3])])
+        .unwrap();
+    assert_eq!(&target.registers[0x10..0x13], [1, 2, 3]);
+    assert_eq!(target.pointer(), 0x10);
+
+    let mut data = [0; 2];
+    target
+        .transaction(
+            0x48,
+            &mut [Operation::Write(&[0x11]), Operation::Read(&mut data)],
+        )
+        .unwrap();
+    assert_eq!(data, [2, 3]);
+    // A read on its own continues where the last one stopped
+    let mut next = [0];
+    target
+        .transaction(0x48, &mut [Operation::Read(&mut next)])
+        .unwrap();
+    assert_eq!(next, [0]);
+    assert_eq!(target.pointer(), 0x14);
+    assert_eq!(target.transactions, 3);
+}
+
+#[test]
+fn test_pointer_wraps() {
+    let mut target = Target::new(0x48);
+    target.registers[0] = 0xAA;
+    target.registers[0xFF] = 0x55;
+    let mut data = [0; 2];
+    target
+        .transaction(
+            0x48,
+            &mut [Operation::Write(&[0xFF]), Operation::Read(&mut data)],
+        )
+        .unwrap();
+    assert_eq!(data, [0x55, 0xAA]);
+}
+
+#[test]
+fn test_other_address_nacks() {
+    let mut target = Target::new(ADDRESS_10BIT | 0x148);
+    let result = target.transaction(0x48, &mut [Operation::Write(&[0])]);
+    assert_eq!(result, Err(-1));
+    assert_eq!(target.transactions, 0);
+    let result = target.transaction(ADDRESS_10BIT | 0x148, &mut [Operation::Write(&[0])]);
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn test_blocks() {
+    let mut target = Target::new(0x0B);
+    target.set_block(0x21, b"battery").unwrap();
+    let mut data = [0; 10];
+    target
+        .transaction(
+            0x0B,
+            &mut [Operation::Write(&[0x21]), Operation::Read(&mut data)],
+        )
+        .unwrap();
+    assert_eq!(&data[..8], b"\x07battery");
+    assert_eq!(&data[8..], [0xFF, 0xFF]);
+
+    target
+        .transaction(0x0B, &mut [Operation::Write(&[0x21, 3, b'a', b'b', b'c'])])
+        .unwrap();
+    assert_eq!(target.block(0x21), Some(&b"abc"[..]));
+    assert_eq!(target.set_block(0x22, &[0; 33]), Err(-1));
+}
+
+#[test]
+fn test_fault() {
+    let mut target = Target::new(0x48);
+    target.fail_with(Some(-3));
+    assert_eq!(
+        target.transaction(0x48, &mut [Operation::Write(&[0])]),
+        Err(-3)
+    );
+    target.fail_with(None);
+    assert_eq!(
+        target.transaction(0x48, &mut [Operation::Write(&[0])]),
+        Ok(())
+    );
+}
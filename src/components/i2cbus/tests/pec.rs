@@ -0,0 +1,15 @@
+use i2cbus::pec::*;
+
+#[test]
+fn test_check_value() {
+    // CRC-8/SMBUS check value
+    assert_eq!(pec(b"123456789"), 0xF4);
+    assert_eq!(pec(&[]), 0);
+}
+
+#[test]
+fn test_update_in_parts() {
+    let whole = pec(&[0xB6, 0x8B, 0xB7, 0x12, 0x34]);
+    let parts = update(update(0, &[0xB6, 0x8B]), &[0xB7, 0x12, 0x34]);
+    assert_eq!(whole, parts);
+}
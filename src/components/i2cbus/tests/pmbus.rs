@@ -0,0 +1,79 @@
+use i2cbus::mock::Target;
+use i2cbus::pmbus::*;
+use i2cbus::smbus::Smbus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn test_linear11_decode() {
+    // 0xD3C0: exponent -6, mantissa 0x3C0 = 960, 15 V
+    assert_eq!(linear11_to_f32(0xD3C0), 15.0);
+    // exponent 1, mantissa -1
+    assert_eq!(linear11_to_f32(0x0FFF), -2.0);
+    assert_eq!(linear11_to_f32(0x0000), 0.0);
+    // exponent -2, mantissa 1
+    assert_eq!(linear11_to_f32(0xF001), 0.25);
+}
+
+#[test]
+fn test_linear11_encode() {
+    for value in [15.0, -2.0, 0.25, 1.5, 230.0, -40.0, 0.0] {
+        assert_eq!(linear11_to_f32(f32_to_linear11(value)), value);
+    }
+    // 12.34 with the smallest exponent that fits: -6, 12.34 * 64 = 790
+    assert_eq!(f32_to_linear11(12.34), 0xD000 | 790);
+    // Beyond 1023 * 2^15 saturates
+    assert_eq!(linear11_to_f32(f32_to_linear11(1.0e9)), 1023.0 * 32768.0);
+}
+
+#[test]
+fn test_linear16() {
+    assert_eq!(vout_exponent(0x17), Some(-9));
+    assert_eq!(vout_exponent(0x14), Some(-12));
+    assert_eq!(vout_exponent(0x05), Some(5));
+    // VID mode
+    assert_eq!(vout_exponent(0x20 | 0x17), None);
+    assert_eq!(linear16_to_f32(0x0600, -9), 3.0);
+    assert_eq!(f32_to_linear16(3.3, -9), 1690);
+    assert_eq!(f32_to_linear16(-1.0, -9), 0);
+    assert_eq!(f32_to_linear16(1000.0, -9), u16::MAX);
+}
+
+#[test]
+fn test_telemetry() {
+    let mut target = Target::new(ADDRESS as u16);
+    target.registers[command::VOUT_MODE as usize] = 0x17;
+    target.registers[command::READ_VOUT as usize..][..2].copy_from_slice(&0x0A00u16.to_le_bytes());
+    target.registers[command::READ_VIN as usize..][..2].copy_from_slice(&0xD3C0u16.to_le_bytes());
+    target.registers[command::STATUS_WORD as usize..][..2]
+        .copy_from_slice(&0x0840u16.to_le_bytes());
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    assert_eq!(read_vout(&mut device), Ok(5.0));
+    assert_eq!(read_linear11(&mut device, command::READ_VIN), Ok(15.0));
+    assert_eq!(status_word(&mut device), Ok(0x0840));
+}
+
+#[test]
+fn test_commands() {
+    let mut target = Target::new(ADDRESS as u16);
+    target.registers[command::VOUT_MODE as usize] = 0x17;
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    set_page(&mut device, 1).unwrap();
+    set_vout(&mut device, 1.8).unwrap();
+    clear_faults(&mut device).unwrap();
+    assert_eq!(target.registers[command::PAGE as usize], 1);
+    let vout = u16::from_le_bytes([
+        target.registers[command::VOUT_COMMAND as usize],
+        target.registers[command::VOUT_COMMAND as usize + 1],
+    ]);
+    assert_eq!(vout, 922);
+    assert_eq!(target.pointer(), command::CLEAR_FAULTS);
+}
+
+#[test]
+fn test_vout_mode_not_linear() {
+    let mut target = Target::new(ADDRESS as u16);
+    target.registers[command::VOUT_MODE as usize] = 0x40;
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    assert_eq!(read_vout(&mut device), Err(-2));
+}
@@ -0,0 +1,62 @@
+use i2cbus::scan::*;
+
+#[test]
+fn test_reserved_addresses() {
+    assert!(is_reserved(0x00));
+    assert!(is_reserved(0x07));
+    assert!(!is_reserved(FIRST));
+    assert!(!is_reserved(0x50));
+    assert!(!is_reserved(LAST));
+    assert!(is_reserved(0x78));
+    assert!(is_reserved(0x80));
+}
+
+#[test]
+fn test_classify() {
+    assert_eq!(classify(Ok(())), Ok(Response::Ack));
+    assert_eq!(classify(Err(-1)), Ok(Response::Nack));
+    assert_eq!(classify(Err(-3)), Err(-3));
+}
+
+#[test]
+fn test_address_map() {
+    let mut map = AddressMap::new();
+    assert!(map.is_empty());
+    map.insert(0x50);
+    map.insert(0x68);
+    map.insert(0x50);
+    assert_eq!(map.len(), 2);
+    assert!(map.contains(0x68));
+    assert!(!map.contains(0x51));
+    assert!(!map.contains(0xD0));
+    assert_eq!(map.iter().collect::<Vec<_>>(), [0x50, 0x68]);
+    assert_eq!(map.bits(), 1 << 0x50 | 1 << 0x68);
+    assert_eq!(AddressMap::from_bits(map.bits()), map);
+}
+
+#[test]
+fn test_scan_probes_unreserved_addresses() {
+    let mut probed = Vec::new();
+    let found = scan(|address| {
+        probed.push(address);
+        if address == 0x50 || address == 0x68 {
+            Ok(())
+        } else {
+            Err(-1)
+        }
+    })
+    .unwrap();
+    assert_eq!(probed, (FIRST..=LAST).collect::<Vec<_>>());
+    assert_eq!(found.iter().collect::<Vec<_>>(), [0x50, 0x68]);
+}
+
+#[test]
+fn test_scan_stops_on_bus_fault() {
+    let mut probed = 0;
+    let result = scan(|address| {
+        probed += 1;
+        if address == 0x20 { Err(-3) } else { Err(-1) }
+    });
+    assert_eq!(result, Err(-3));
+    assert_eq!(probed, 0x20 - FIRST as usize + 1);
+}
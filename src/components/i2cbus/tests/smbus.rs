@@ -0,0 +1,103 @@
+use i2cbus::bus::{Bus, Operation};
+use i2cbus::mock::Target;
+use i2cbus::pec::pec;
+use i2cbus::smbus::*;
+
+const ADDRESS: u8 = 0x0B;
+
+#[test]
+fn test_byte_and_word_data() {
+    let mut target = Target::new(ADDRESS as u16);
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    device.write_byte_data(0x01, 0x5A).unwrap();
+    assert_eq!(device.read_byte_data(0x01), Ok(0x5A));
+    device.write_word_data(0x08, 0x1234).unwrap();
+    assert_eq!(device.read_word_data(0x08), Ok(0x1234));
+    assert_eq!(&target.registers[0x08..0x0A], [0x34, 0x12]);
+}
+
+#[test]
+fn test_send_and_receive_byte() {
+    let mut target = Target::new(ADDRESS as u16);
+    target.registers[0x40] = 0x99;
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    device.send_byte(0x40).unwrap();
+    assert_eq!(device.receive_byte(), Ok(0x99));
+}
+
+#[test]
+fn test_process_call() {
+    let mut target = Target::new(ADDRESS as u16);
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    // The mock answers with the word it was given
+    assert_eq!(device.process_call(0x30, 0xBEEF), Ok(0xBEEF));
+}
+
+#[test]
+fn test_block_transfers() {
+    let mut target = Target::new(ADDRESS as u16);
+    target.set_block(0x20, b"").unwrap();
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    device.block_write(0x20, b"ACME").unwrap();
+    let mut data = [0; MAX_BLOCK];
+    assert_eq!(device.block_read(0x20, &mut data), Ok(4));
+    assert_eq!(&data[..4], b"ACME");
+
+    let mut short = [0; 2];
+    assert_eq!(device.block_read(0x20, &mut short), Err(-5));
+    assert_eq!(device.block_write(0x20, &[0; MAX_BLOCK + 1]), Err(-1));
+}
+
+#[test]
+fn test_pec() {
+    let mut target = Target::new(ADDRESS as u16).with_pec(true);
+    target.set_block(0x20, b"pec").unwrap();
+    let mut device = Smbus::new(&mut target, ADDRESS).with_pec(true);
+    device.write_word_data(0x08, 0x0102).unwrap();
+    assert_eq!(device.read_word_data(0x08), Ok(0x0102));
+    assert_eq!(device.read_byte_data(0x09), Ok(0x01));
+    let mut data = [0; MAX_BLOCK];
+    assert_eq!(device.block_read(0x20, &mut data), Ok(3));
+    device.send_byte(0x08).unwrap();
+    assert_eq!(device.receive_byte(), Ok(0x02));
+    assert_eq!(target.pec_errors, 0);
+}
+
+#[test]
+fn test_pec_mismatch() {
+    // A device without PEC sends no PEC byte, whatever follows is wrong
+    let mut target = Target::new(ADDRESS as u16);
+    target.registers[0x08] = 0x12;
+    target.registers[0x09] = 0xFF;
+    let mut device = Smbus::new(&mut target, ADDRESS).with_pec(true);
+    assert_eq!(device.read_byte_data(0x08), Err(-4));
+
+    // A write with a PEC the device does not expect is rejected
+    let mut target = Target::new(ADDRESS as u16).with_pec(true);
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    assert_eq!(device.write_byte_data(0x08, 0x12), Err(-1));
+    assert_eq!(target.pec_errors, 1);
+}
+
+#[test]
+fn test_write_pec_covers_address() {
+    let mut target = Target::new(ADDRESS as u16).with_pec(true);
+    let payload = [0x08, 0x12];
+    let without_address = [0x08, 0x12, pec(&payload)];
+    let result = target.transaction(ADDRESS as u16, &mut [Operation::Write(&without_address)]);
+    assert_eq!(result, Err(-1));
+    let with_address = [0x08, 0x12, pec(&[ADDRESS << 1, 0x08, 0x12])];
+    let result = target.transaction(ADDRESS as u16, &mut [Operation::Write(&with_address)]);
+    assert_eq!(result, Ok(()));
+    assert_eq!(target.registers[0x08], 0x12);
+}
+
+#[test]
+fn test_nack_and_fault() {
+    let mut target = Target::new(0x0C);
+    let mut device = Smbus::new(&mut target, ADDRESS);
+    assert_eq!(device.read_byte_data(0x00), Err(-1));
+    target.fail_with(Some(-3));
+    let mut device = Smbus::new(&mut target, 0x0C);
+    assert_eq!(device.write_byte_data(0x00, 0), Err(-3));
+}
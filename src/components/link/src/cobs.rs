@@ -0,0 +1,123 @@
+//! Consistent Overhead Byte Stuffing: removes every zero from a frame at
+//! the cost of one byte in 254, so that a zero can delimit frames.
+
+/// Longest encoding of `len` bytes, without the delimiter
+pub const fn max_encoded_len(len: usize) -> usize {
+    len + len / 254 + 1
+}
+
+/// Encode `data` into `out` and return the length, without the
+/// delimiter. Returns Err(-1) if `out` is shorter than
+/// `max_encoded_len`.
+pub fn encode(data: &[u8], out: &mut [u8]) -> Result<usize, i32> {
+    if out.len() < max_encoded_len(data.len()) {
+        return Err(-1);
+    }
+    // Index of the code byte of the current block
+    let mut code_index = 0;
+    let mut code = 1u8;
+    let mut len = 1;
+    for &byte in data {
+        if byte != 0 {
+            out[len] = byte;
+            len += 1;
+            code += 1;
+        }
+        if byte == 0 || code == 0xFF {
+            out[code_index] = code;
+            code_index = len;
+            len += 1;
+            code = 1;
+        }
+    }
+    out[code_index] = code;
+    Ok(len)
+}
+
+/// Decode the frame in `buf`, without its delimiter, in place and return
+/// its length. Returns Err(-1) if it is not a valid encoding.
+pub fn decode_in_place(buf: &mut [u8]) -> Result<usize, i32> {
+    let mut read = 0;
+    let mut len = 0;
+    while read < buf.len() {
+        let code = buf[read] as usize;
+        if code == 0 || read + code > buf.len() {
+            return Err(-1);
+        }
+        read += 1;
+        for _ in 1..code {
+            if buf[read] == 0 {
+                return Err(-1);
+            }
+            buf[len] = buf[read];
+            len += 1;
+            read += 1;
+        }
+        // A block shorter than the longest ends with a zero, except the last
+        if code != 0xFF && read < buf.len() {
+            buf[len] = 0;
+            len += 1;
+        }
+    }
+    Ok(len)
+}
+
+/// Decode `data` into `out` and return the length. Returns Err(-1) if it
+/// is not a valid encoding or `out` is shorter than `data`.
+pub fn decode(data: &[u8], out: &mut [u8]) -> Result<usize, i32> {
+    let buf = out.get_mut(..data.len()).ok_or(-1)?;
+    buf.copy_from_slice(data);
+    decode_in_place(buf)
+}
+
+/// Decodes frames from a byte stream, delimited by zeros
+pub struct Decoder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    overflow: bool,
+}
+
+impl<const N: usize> Decoder<N> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    /// Add a received byte. At a delimiter, returns the decoded frame, or
+    /// Err(-1) if it was invalid or longer than `N` encoded. Empty frames
+    /// are skipped.
+    pub fn push(&mut self, byte: u8) -> Option<Result<&[u8], i32>> {
+        if byte != 0 {
+            if self.len < N {
+                self.buf[self.len] = byte;
+                self.len += 1;
+            } else {
+                self.overflow = true;
+            }
+            return None;
+        }
+        let len = core::mem::take(&mut self.len);
+        if core::mem::take(&mut self.overflow) {
+            return Some(Err(-1));
+        }
+        if len == 0 {
+            return None;
+        }
+        Some(decode_in_place(&mut self.buf[..len]).map(|len| &self.buf[..len]))
+    }
+
+    /// Drop the frame being received
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.overflow = false;
+    }
+}
+
+impl<const N: usize> Default for Decoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
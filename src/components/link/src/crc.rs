@@ -0,0 +1,27 @@
+//! Checks of link packets: CRC-16/CCITT-FALSE (polynomial 0x1021, initial
+//! value 0xFFFF, not reflected) for short packets, and the CRC-32 of the
+//! `fault` component for longer ones.
+
+pub use fault::crc::{crc32, crc32_update};
+
+const POLYNOMIAL: u16 = 0x1021;
+
+/// Continue the CRC-16 `crc` over `bytes`
+pub fn crc16_update(mut crc: u16, bytes: &[u8]) -> u16 {
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                crc << 1 ^ POLYNOMIAL
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// The CRC-16 of `bytes`
+pub fn crc16(bytes: &[u8]) -> u16 {
+    crc16_update(0xFFFF, bytes)
+}
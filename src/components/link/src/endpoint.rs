@@ -0,0 +1,238 @@
+//! One end of a reliable link: data packets carry a sequence number, the
+//! receiver acknowledges each one, and the sender retransmits a packet not
+//! acknowledged in time (stop-and-wait).
+//!
+//! A retransmitted packet whose first copy arrived is acknowledged again
+//! but not delivered twice. Both ends start at sequence number 0, so an
+//! end that restarts should be reset on the other side too.
+//!
+//! ```ignore
+//! let mut link = Endpoint::new(port, Config::default());
+//! link.send(b"status", now_ms)?;
+//! loop {
+//!     match link.poll(now_ms) {
+//!         Some(Event::Received(payload)) => handle(payload),
+//!         Some(Event::Delivered(_)) => break,
+//!         Some(Event::Failed(_)) => return Err(-3),
+//!         None => idle(),
+//!     }
+//! }
+//! ```
+
+use crate::frame::{self, Decoder, Encoding};
+use crate::packet::{Check, HEADER, Kind, MAX_PACKET, MAX_PAYLOAD, Packet};
+
+/// Longest frame on the line
+pub const MAX_FRAME: usize = frame::max_encoded_len(Encoding::Slip, MAX_PACKET);
+
+/// Longest frame of an acknowledgement
+const ACK_FRAME: usize = frame::max_encoded_len(Encoding::Slip, HEADER + 4);
+
+/// A byte stream to another end
+pub trait Port {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), i32>;
+
+    /// The next byte received, if any
+    fn read_byte(&mut self) -> Option<u8>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    pub encoding: Encoding,
+    pub check: Check,
+    /// Time to wait for an acknowledgement before retransmitting
+    pub retry_ms: u32,
+    /// Retransmissions before a packet is given up
+    pub retries: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            encoding: Encoding::Cobs,
+            check: Check::Crc16,
+            retry_ms: 100,
+            retries: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// A payload from the other end
+    Received(&'a [u8]),
+    /// The other end acknowledged the packet of this sequence number
+    Delivered(u8),
+    /// The packet of this sequence number was given up after the retries
+    Failed(u8),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Data packets sent, not counting retransmissions
+    pub sent: u32,
+    pub retransmits: u32,
+    /// Data packets delivered
+    pub received: u32,
+    /// Data packets received again after their acknowledgement was lost
+    pub duplicates: u32,
+    /// Frames dropped as invalid or with a wrong check
+    pub errors: u32,
+}
+
+/// Encode `packet` in a frame and write it to `port`
+fn write_packet<P: Port>(
+    port: &mut P,
+    config: &Config,
+    packet: &Packet<'_>,
+    out: &mut [u8],
+) -> Result<usize, i32> {
+    let mut bytes = [0; MAX_PACKET];
+    let len = packet.encode(config.check, &mut bytes)?;
+    let len = frame::encode(config.encoding, &bytes[..len], out)?;
+    port.write(&out[..len])?;
+    Ok(len)
+}
+
+pub struct Endpoint<P: Port> {
+    port: P,
+    config: Config,
+    decoder: Decoder<MAX_FRAME>,
+    /// Sequence number of the next data packet
+    next_seq: u8,
+    /// Sequence number of the last data packet delivered
+    last_received: Option<u8>,
+    /// Sequence number of the packet awaiting its acknowledgement
+    pending: Option<u8>,
+    /// Its frame, kept for retransmissions
+    frame: [u8; MAX_FRAME],
+    frame_len: usize,
+    sent_ms: u64,
+    attempts: u8,
+    payload: [u8; MAX_PAYLOAD],
+    stats: Stats,
+}
+
+impl<P: Port> Endpoint<P> {
+    pub fn new(port: P, config: Config) -> Self {
+        Self {
+            port,
+            config,
+            decoder: Decoder::new(config.encoding),
+            next_seq: 0,
+            last_received: None,
+            pending: None,
+            frame: [0; MAX_FRAME],
+            frame_len: 0,
+            sent_ms: 0,
+            attempts: 0,
+            payload: [0; MAX_PAYLOAD],
+            stats: Stats::default(),
+        }
+    }
+
+    pub fn port(&mut self) -> &mut P {
+        &mut self.port
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Whether a packet awaits its acknowledgement
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Forget the packet in flight and start both directions again at
+    /// sequence number 0
+    pub fn reset(&mut self) {
+        self.decoder.clear();
+        self.next_seq = 0;
+        self.last_received = None;
+        self.pending = None;
+    }
+
+    /// Send `payload` and return its sequence number. Returns Err(-1) if
+    /// it is longer than `MAX_PAYLOAD`, Err(-5) while the previous packet
+    /// awaits its acknowledgement, and the error of the port if writing
+    /// fails.
+    pub fn send(&mut self, payload: &[u8], now_ms: u64) -> Result<u8, i32> {
+        if self.pending.is_some() {
+            return Err(-5);
+        }
+        let seq = self.next_seq;
+        let packet = Packet::data(seq, payload);
+        self.frame_len = write_packet(&mut self.port, &self.config, &packet, &mut self.frame)?;
+        self.next_seq = seq.wrapping_add(1);
+        self.pending = Some(seq);
+        self.sent_ms = now_ms;
+        self.attempts = 1;
+        self.stats.sent += 1;
+        Ok(seq)
+    }
+
+    /// Receive what has arrived, acknowledge data packets, and retransmit
+    /// a packet not acknowledged in time. Returns the first event, the
+    /// rest wait for the next call.
+    pub fn poll(&mut self, now_ms: u64) -> Option<Event<'_>> {
+        while let Some(byte) = self.port.read_byte() {
+            let Some(frame) = self.decoder.push(byte) else {
+                continue;
+            };
+            let packet = match frame.and_then(|bytes| Packet::parse(bytes, self.config.check)) {
+                Ok(packet) => packet,
+                Err(_) => {
+                    self.stats.errors += 1;
+                    continue;
+                }
+            };
+            match packet.kind {
+                Kind::Ack => {
+                    if self.pending == Some(packet.seq) {
+                        self.pending = None;
+                        return Some(Event::Delivered(packet.seq));
+                    }
+                }
+                Kind::Data => {
+                    // A lost acknowledgement is made up by the next
+                    // retransmission
+                    let mut ack = [0; ACK_FRAME];
+                    let _ = write_packet(
+                        &mut self.port,
+                        &self.config,
+                        &Packet::ack(packet.seq),
+                        &mut ack,
+                    );
+                    if self.last_received == Some(packet.seq) {
+                        self.stats.duplicates += 1;
+                        continue;
+                    }
+                    self.last_received = Some(packet.seq);
+                    self.stats.received += 1;
+                    let len = packet.payload.len();
+                    self.payload[..len].copy_from_slice(packet.payload);
+                    return Some(Event::Received(&self.payload[..len]));
+                }
+            }
+        }
+        let seq = self.pending?;
+        if now_ms.saturating_sub(self.sent_ms) < self.config.retry_ms as u64 {
+            return None;
+        }
+        if self.attempts > self.config.retries {
+            self.pending = None;
+            return Some(Event::Failed(seq));
+        }
+        // A failed write counts as an attempt
+        let _ = self.port.write(&self.frame[..self.frame_len]);
+        self.attempts += 1;
+        self.sent_ms = now_ms;
+        self.stats.retransmits += 1;
+        None
+    }
+}
@@ -0,0 +1,64 @@
+//! Frames on the byte stream, in either encoding, with their delimiters.
+
+use crate::{cobs, slip};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// COBS, each frame followed by a zero
+    Cobs,
+    /// SLIP, each frame between END bytes
+    Slip,
+}
+
+/// Longest encoding of `len` bytes, with the delimiters
+pub const fn max_encoded_len(encoding: Encoding, len: usize) -> usize {
+    match encoding {
+        Encoding::Cobs => cobs::max_encoded_len(len) + 1,
+        Encoding::Slip => slip::max_encoded_len(len),
+    }
+}
+
+/// Encode `data` into `out`, delimited, and return the length. Returns
+/// Err(-1) if it does not fit.
+pub fn encode(encoding: Encoding, data: &[u8], out: &mut [u8]) -> Result<usize, i32> {
+    match encoding {
+        Encoding::Cobs => {
+            let len = cobs::encode(data, out)?;
+            *out.get_mut(len).ok_or(-1)? = 0;
+            Ok(len + 1)
+        }
+        Encoding::Slip => slip::encode(data, out),
+    }
+}
+
+/// Decodes frames in either encoding, `N` bytes long at most encoded
+/// (COBS) or decoded (SLIP)
+pub enum Decoder<const N: usize> {
+    Cobs(cobs::Decoder<N>),
+    Slip(slip::Decoder<N>),
+}
+
+impl<const N: usize> Decoder<N> {
+    pub const fn new(encoding: Encoding) -> Self {
+        match encoding {
+            Encoding::Cobs => Decoder::Cobs(cobs::Decoder::new()),
+            Encoding::Slip => Decoder::Slip(slip::Decoder::new()),
+        }
+    }
+
+    /// Add a received byte, and return the frame it ends, if any, or
+    /// Err(-1) if that frame was invalid
+    pub fn push(&mut self, byte: u8) -> Option<Result<&[u8], i32>> {
+        match self {
+            Decoder::Cobs(decoder) => decoder.push(byte),
+            Decoder::Slip(decoder) => decoder.push(byte),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        match self {
+            Decoder::Cobs(decoder) => decoder.clear(),
+            Decoder::Slip(decoder) => decoder.clear(),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! The host side of a link on Linux: serial ports and pseudo-terminals
+//! as the `Port` of an `Endpoint`, and a millisecond clock to poll it
+//! with.
+//!
+//! ```ignore
+//! let port = SerialPort::open("/dev/ttyUSB0", 115_200)?;
+//! let mut link = Endpoint::new(port, Config::default());
+//! let clock = Clock::new();
+//! link.send(b"ping", clock.now_ms())?;
+//! while let Some(event) = link.poll(clock.now_ms()) { ... }
+//! ```
+//!
+//! The termios calls go straight to the C library std links against, so
+//! the layout below is that of glibc and musl on Linux.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Write};
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::endpoint::Port;
+
+const O_NOCTTY: i32 = 0o400;
+const O_NONBLOCK: i32 = 0o4000;
+const TCSANOW: i32 = 0;
+
+#[repr(C)]
+struct Termios {
+    iflag: u32,
+    oflag: u32,
+    cflag: u32,
+    lflag: u32,
+    line: u8,
+    cc: [u8; 32],
+    ispeed: u32,
+    ospeed: u32,
+}
+
+unsafe extern "C" {
+    fn tcgetattr(fd: i32, termios: *mut Termios) -> i32;
+    fn tcsetattr(fd: i32, action: i32, termios: *const Termios) -> i32;
+    fn cfmakeraw(termios: *mut Termios);
+    fn cfsetspeed(termios: *mut Termios, speed: u32) -> i32;
+    fn grantpt(fd: i32) -> i32;
+    fn unlockpt(fd: i32) -> i32;
+    fn ptsname_r(fd: i32, buf: *mut u8, len: usize) -> i32;
+}
+
+/// The termios speed constant of `baud`
+fn speed(baud: u32) -> Option<u32> {
+    Some(match baud {
+        9600 => 0o15,
+        19_200 => 0o16,
+        38_400 => 0o17,
+        57_600 => 0o10001,
+        115_200 => 0o10002,
+        230_400 => 0o10003,
+        460_800 => 0o10004,
+        921_600 => 0o10007,
+        _ => return None,
+    })
+}
+
+fn check(result: i32) -> io::Result<()> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn open_nonblocking(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(O_NOCTTY | O_NONBLOCK)
+        .open(path)
+}
+
+/// A serial device, or either side of a pseudo-terminal, read without
+/// blocking
+pub struct SerialPort {
+    file: File,
+    buf: [u8; 256],
+    start: usize,
+    end: usize,
+}
+
+impl SerialPort {
+    /// Open a serial device or the terminal side of a pseudo-terminal, in
+    /// raw mode at `baud`
+    pub fn open(path: impl AsRef<Path>, baud: u32) -> io::Result<Self> {
+        let speed = speed(baud)
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidInput, "unsupported baud rate"))?;
+        let file = open_nonblocking(path.as_ref())?;
+        let fd = file.as_raw_fd();
+        let mut termios = Termios {
+            iflag: 0,
+            oflag: 0,
+            cflag: 0,
+            lflag: 0,
+            line: 0,
+            cc: [0; 32],
+            ispeed: 0,
+            ospeed: 0,
+        };
+        // SAFETY: `termios` has the C layout and outlives the calls
+        unsafe {
+            check(tcgetattr(fd, &mut termios))?;
+            cfmakeraw(&mut termios);
+            check(cfsetspeed(&mut termios, speed))?;
+            check(tcsetattr(fd, TCSANOW, &termios))?;
+        }
+        Ok(Self::from_file(file))
+    }
+
+    /// Use a file opened non-blocking, such as the controller side of a
+    /// pseudo-terminal
+    pub fn from_file(file: File) -> Self {
+        Self {
+            file,
+            buf: [0; 256],
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+impl Port for SerialPort {
+    /// Returns Err(-3) if the write fails
+    fn write(&mut self, mut bytes: &[u8]) -> Result<(), i32> {
+        while !bytes.is_empty() {
+            match self.file.write(bytes) {
+                Ok(written) => bytes = &bytes[written..],
+                Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(_) => return Err(-3),
+            }
+        }
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        if self.start == self.end {
+            self.start = 0;
+            self.end = self.file.read(&mut self.buf).unwrap_or(0);
+        }
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(self.buf[self.start - 1])
+    }
+}
+
+/// Open a pseudo-terminal and return its controller side and the path of
+/// its terminal side, which stands in for the device's serial port
+pub fn pty() -> io::Result<(SerialPort, PathBuf)> {
+    let file = open_nonblocking(Path::new("/dev/ptmx"))?;
+    let fd = file.as_raw_fd();
+    let mut name = [0u8; 64];
+    // SAFETY: `name` outlives the call and its length is passed along
+    unsafe {
+        check(grantpt(fd))?;
+        check(unlockpt(fd))?;
+        let error = ptsname_r(fd, name.as_mut_ptr(), name.len());
+        if error != 0 {
+            return Err(io::Error::from_raw_os_error(error));
+        }
+    }
+    let len = name
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(name.len());
+    let path = std::str::from_utf8(&name[..len])
+        .map_err(|_| io::Error::new(ErrorKind::InvalidData, "pty name"))?;
+    Ok((SerialPort::from_file(file), PathBuf::from(path)))
+}
+
+/// Milliseconds since the clock was made
+pub struct Clock(Instant);
+
+impl Clock {
+    pub fn new() -> Self {
+        Self(Instant::now())
+    }
+
+    pub fn now_ms(&self) -> u64 {
+        self.0.elapsed().as_millis() as u64
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
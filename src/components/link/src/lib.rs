@@ -0,0 +1,10 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod cobs;
+pub mod crc;
+pub mod endpoint;
+pub mod frame;
+#[cfg(all(feature = "std", target_os = "linux"))]
+pub mod host;
+pub mod packet;
+pub mod slip;
@@ -0,0 +1,113 @@
+//! Packets inside frames: kind, sequence number, payload and check.
+//!
+//! ```text
+//! | kind | seq | payload ... | CRC-16 or CRC-32, little-endian |
+//! ```
+//!
+//! The check covers the kind, sequence number and payload.
+
+use crate::crc::{crc16, crc32};
+
+/// Longest payload of a packet
+pub const MAX_PAYLOAD: usize = 256;
+
+/// Kind and sequence number
+pub const HEADER: usize = 2;
+
+/// Longest packet, with the longest check
+pub const MAX_PACKET: usize = HEADER + MAX_PAYLOAD + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Payload to deliver, acknowledged by the receiver
+    Data = 0x01,
+    /// Acknowledges the data packet of the same sequence number
+    Ack = 0x02,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Crc16,
+    Crc32,
+}
+
+impl Check {
+    /// Bytes the check adds to a packet
+    pub const fn size(&self) -> usize {
+        match self {
+            Check::Crc16 => 2,
+            Check::Crc32 => 4,
+        }
+    }
+
+    fn write(&self, bytes: &[u8], out: &mut [u8]) {
+        match self {
+            Check::Crc16 => out.copy_from_slice(&crc16(bytes).to_le_bytes()),
+            Check::Crc32 => out.copy_from_slice(&crc32(bytes).to_le_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet<'a> {
+    pub kind: Kind,
+    pub seq: u8,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Packet<'a> {
+    pub fn data(seq: u8, payload: &'a [u8]) -> Self {
+        Self {
+            kind: Kind::Data,
+            seq,
+            payload,
+        }
+    }
+
+    pub fn ack(seq: u8) -> Packet<'static> {
+        Packet {
+            kind: Kind::Ack,
+            seq,
+            payload: &[],
+        }
+    }
+
+    /// Write the packet into `out` and return its length. Returns Err(-1)
+    /// if the payload is longer than `MAX_PAYLOAD` or does not fit.
+    pub fn encode(&self, check: Check, out: &mut [u8]) -> Result<usize, i32> {
+        let len = HEADER + self.payload.len();
+        if self.payload.len() > MAX_PAYLOAD || out.len() < len + check.size() {
+            return Err(-1);
+        }
+        out[0] = self.kind as u8;
+        out[1] = self.seq;
+        out[HEADER..len].copy_from_slice(self.payload);
+        let (bytes, rest) = out.split_at_mut(len);
+        check.write(bytes, &mut rest[..check.size()]);
+        Ok(len + check.size())
+    }
+
+    /// Parse a received packet. Returns Err(-1) if it is too short or of
+    /// an unknown kind, and Err(-4) if the check does not match.
+    pub fn parse(bytes: &'a [u8], check: Check) -> Result<Self, i32> {
+        if bytes.len() < HEADER + check.size() || bytes.len() > MAX_PACKET {
+            return Err(-1);
+        }
+        let (body, received) = bytes.split_at(bytes.len() - check.size());
+        let mut expected = [0; 4];
+        check.write(body, &mut expected[..check.size()]);
+        if expected[..check.size()] != *received {
+            return Err(-4);
+        }
+        let kind = match body[0] {
+            0x01 => Kind::Data,
+            0x02 => Kind::Ack,
+            _ => return Err(-1),
+        };
+        Ok(Self {
+            kind,
+            seq: body[1],
+            payload: &body[HEADER..],
+        })
+    }
+}
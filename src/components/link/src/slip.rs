@@ -0,0 +1,111 @@
+//! SLIP (RFC 1055): frames end with END, and END and ESC inside a frame
+//! are sent as ESC followed by ESC_END or ESC_ESC. Frames also start with
+//! END, which flushes any noise received before them.
+
+pub const END: u8 = 0xC0;
+pub const ESC: u8 = 0xDB;
+pub const ESC_END: u8 = 0xDC;
+pub const ESC_ESC: u8 = 0xDD;
+
+/// Longest encoding of `len` bytes, with both END bytes
+pub const fn max_encoded_len(len: usize) -> usize {
+    2 * len + 2
+}
+
+/// Encode `data` into `out`, between END bytes, and return the length.
+/// Returns Err(-1) if it does not fit.
+pub fn encode(data: &[u8], out: &mut [u8]) -> Result<usize, i32> {
+    let mut len = 0;
+    let mut put = |byte: u8| -> Result<(), i32> {
+        *out.get_mut(len).ok_or(-1)? = byte;
+        len += 1;
+        Ok(())
+    };
+    put(END)?;
+    for &byte in data {
+        match byte {
+            END => {
+                put(ESC)?;
+                put(ESC_END)?;
+            }
+            ESC => {
+                put(ESC)?;
+                put(ESC_ESC)?;
+            }
+            byte => put(byte)?,
+        }
+    }
+    put(END)?;
+    Ok(len)
+}
+
+/// Decodes frames from a byte stream, delimited by END
+pub struct Decoder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    escape: bool,
+    /// A bad escape or an overflow spoiled the frame
+    error: bool,
+}
+
+impl<const N: usize> Decoder<N> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+            escape: false,
+            error: false,
+        }
+    }
+
+    /// Add a received byte. At END, returns the decoded frame, or Err(-1)
+    /// if it had a bad escape or was longer than `N`. Empty frames are
+    /// skipped.
+    pub fn push(&mut self, byte: u8) -> Option<Result<&[u8], i32>> {
+        let escape = core::mem::take(&mut self.escape);
+        let byte = match (escape, byte) {
+            // END ends the frame even after ESC, which spoils it
+            (_, END) => {
+                let len = core::mem::take(&mut self.len);
+                if core::mem::take(&mut self.error) || escape {
+                    return Some(Err(-1));
+                }
+                if len == 0 {
+                    return None;
+                }
+                return Some(Ok(&self.buf[..len]));
+            }
+            (false, ESC) => {
+                self.escape = true;
+                return None;
+            }
+            (false, byte) => byte,
+            (true, ESC_END) => END,
+            (true, ESC_ESC) => ESC,
+            (true, _) => {
+                self.error = true;
+                return None;
+            }
+        };
+        if self.len < N {
+            self.buf[self.len] = byte;
+            self.len += 1;
+        } else {
+            self.error = true;
+        }
+        None
+    }
+
+    /// Drop the frame being received
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.escape = false;
+        self.error = false;
+    }
+}
+
+impl<const N: usize> Default for Decoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,72 @@
+use link::cobs::*;
+
+fn round_trip(data: &[u8], expected: &[u8]) {
+    let mut out = [0; 600];
+    let len = encode(data, &mut out).unwrap();
+    assert_eq!(&out[..len], expected);
+    assert!(len <= max_encoded_len(data.len()));
+    let mut decoded = [0; 600];
+    let len = decode(expected, &mut decoded).unwrap();
+    assert_eq!(&decoded[..len], data);
+}
+
+#[test]
+fn test_examples() {
+    round_trip(&[], &[0x01]);
+    round_trip(&[0x00], &[0x01, 0x01]);
+    round_trip(&[0x00, 0x00], &[0x01, 0x01, 0x01]);
+    round_trip(&[0x11, 0x22, 0x00, 0x33], &[0x03, 0x11, 0x22, 0x02, 0x33]);
+    round_trip(&[0x11, 0x00, 0x00, 0x00], &[0x02, 0x11, 0x01, 0x01, 0x01]);
+}
+
+#[test]
+fn test_long_blocks() {
+    let data: Vec<u8> = (1..=254).collect();
+    let mut expected = vec![0xFF];
+    expected.extend(&data);
+    expected.push(0x01);
+    round_trip(&data, &expected);
+
+    let data: Vec<u8> = (0..600).map(|i| (i % 7) as u8).collect();
+    let mut out = [0; 700];
+    let len = encode(&data, &mut out).unwrap();
+    assert!(!out[..len].contains(&0));
+    let mut decoded = [0; 700];
+    assert_eq!(decode(&out[..len], &mut decoded), Ok(data.len()));
+    assert_eq!(&decoded[..data.len()], data);
+}
+
+#[test]
+fn test_errors() {
+    let mut out = [0; 8];
+    assert_eq!(encode(&[1, 2, 3], &mut out[..3]), Err(-1));
+    assert_eq!(decode(&[0x03, 0x11, 0x00], &mut out), Err(-1));
+    assert_eq!(decode(&[0x05, 0x11], &mut out), Err(-1));
+    assert_eq!(decode(&[0x00], &mut out), Err(-1));
+}
+
+#[test]
+fn test_decoder() {
+    let mut decoder = Decoder::<16>::new();
+    let mut frames = Vec::new();
+    for &byte in &[
+        0x00, 0x03, 0x11, 0x22, 0x02, 0x33, 0x00, 0x00, 0x02, 0x44, 0x00,
+    ] {
+        if let Some(frame) = decoder.push(byte) {
+            frames.push(frame.map(|frame| frame.to_vec()));
+        }
+    }
+    assert_eq!(frames, [Ok(vec![0x11, 0x22, 0x00, 0x33]), Ok(vec![0x44])]);
+}
+
+#[test]
+fn test_decoder_overflow() {
+    let mut decoder = Decoder::<4>::new();
+    for byte in 1..=5 {
+        assert_eq!(decoder.push(byte), None);
+    }
+    assert_eq!(decoder.push(0), Some(Err(-1)));
+    assert_eq!(decoder.push(0x02), None);
+    assert_eq!(decoder.push(0x7F), None);
+    assert_eq!(decoder.push(0), Some(Ok(&[0x7F][..])));
+}
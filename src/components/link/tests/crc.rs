@@ -0,0 +1,13 @@
+use link::crc::*;
+
+#[test]
+fn test_crc16() {
+    assert_eq!(crc16(b"123456789"), 0x29B1);
+    assert_eq!(crc16(&[]), 0xFFFF);
+    assert_eq!(crc16_update(crc16(b"1234"), b"56789"), 0x29B1);
+}
+
+#[test]
+fn test_crc32() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
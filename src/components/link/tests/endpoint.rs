@@ -0,0 +1,221 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use link::endpoint::*;
+use link::frame::Encoding;
+use link::packet::{Check, MAX_PAYLOAD};
+
+/// One direction of an in-memory line, which can lose or corrupt the
+/// next frames written to it
+#[derive(Default)]
+struct Wire {
+    bytes: VecDeque<u8>,
+    drop: usize,
+    corrupt: usize,
+    frames: usize,
+}
+
+#[derive(Clone)]
+struct WirePort {
+    tx: Rc<RefCell<Wire>>,
+    rx: Rc<RefCell<Wire>>,
+}
+
+impl Port for WirePort {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        let mut wire = self.tx.borrow_mut();
+        wire.frames += 1;
+        if wire.drop > 0 {
+            wire.drop -= 1;
+            return Ok(());
+        }
+        let start = wire.bytes.len();
+        wire.bytes.extend(bytes);
+        if wire.corrupt > 0 {
+            wire.corrupt -= 1;
+            wire.bytes[start + 2] ^= 0x01;
+        }
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        self.rx.borrow_mut().bytes.pop_front()
+    }
+}
+
+fn pair() -> (WirePort, WirePort, Rc<RefCell<Wire>>, Rc<RefCell<Wire>>) {
+    let a_to_b = Rc::new(RefCell::new(Wire::default()));
+    let b_to_a = Rc::new(RefCell::new(Wire::default()));
+    let a = WirePort {
+        tx: a_to_b.clone(),
+        rx: b_to_a.clone(),
+    };
+    let b = WirePort {
+        tx: b_to_a.clone(),
+        rx: a_to_b.clone(),
+    };
+    (a, b, a_to_b, b_to_a)
+}
+
+/// What happened at each end
+#[derive(Debug, Default, PartialEq)]
+struct Log {
+    received: Vec<Vec<u8>>,
+    delivered: Vec<u8>,
+    failed: Vec<u8>,
+}
+
+impl Log {
+    fn record(&mut self, event: Event<'_>) {
+        match event {
+            Event::Received(payload) => self.received.push(payload.to_vec()),
+            Event::Delivered(seq) => self.delivered.push(seq),
+            Event::Failed(seq) => self.failed.push(seq),
+        }
+    }
+}
+
+/// Poll both ends every 10 ms for `ms`
+fn run(
+    a: &mut Endpoint<WirePort>,
+    b: &mut Endpoint<WirePort>,
+    now: &mut u64,
+    ms: u64,
+) -> (Log, Log) {
+    let (mut log_a, mut log_b) = (Log::default(), Log::default());
+    let end = *now + ms;
+    while *now < end {
+        while let Some(event) = a.poll(*now) {
+            log_a.record(event);
+        }
+        while let Some(event) = b.poll(*now) {
+            log_b.record(event);
+        }
+        *now += 10;
+    }
+    (log_a, log_b)
+}
+
+#[test]
+fn test_exchange() {
+    for (encoding, check) in [
+        (Encoding::Cobs, Check::Crc16),
+        (Encoding::Slip, Check::Crc32),
+    ] {
+        let config = Config {
+            encoding,
+            check,
+            ..Config::default()
+        };
+        let (port_a, port_b, _, _) = pair();
+        let mut a = Endpoint::new(port_a, config);
+        let mut b = Endpoint::new(port_b, config);
+        let mut now = 0;
+
+        assert_eq!(a.send(b"telemetry\0\xC0", now), Ok(0));
+        assert_eq!(b.send(b"command", now), Ok(0));
+        let (log_a, log_b) = run(&mut a, &mut b, &mut now, 50);
+        assert_eq!(log_a.received, [b"command".to_vec()]);
+        assert_eq!(log_a.delivered, [0]);
+        assert_eq!(log_b.received, [b"telemetry\0\xC0".to_vec()]);
+        assert_eq!(log_b.delivered, [0]);
+
+        assert_eq!(a.send(&[0; MAX_PAYLOAD], now), Ok(1));
+        let (log_a, log_b) = run(&mut a, &mut b, &mut now, 50);
+        assert_eq!(log_a.delivered, [1]);
+        assert_eq!(log_b.received, [vec![0; MAX_PAYLOAD]]);
+        assert_eq!(a.stats().retransmits, 0);
+    }
+}
+
+#[test]
+fn test_send_errors() {
+    let (port_a, _port_b, _, _) = pair();
+    let mut a = Endpoint::new(port_a, Config::default());
+    assert_eq!(a.send(&[0; MAX_PAYLOAD + 1], 0), Err(-1));
+    assert_eq!(a.send(b"one", 0), Ok(0));
+    assert!(a.is_pending());
+    assert_eq!(a.send(b"two", 0), Err(-5));
+}
+
+#[test]
+fn test_retransmit_lost_data() {
+    let (port_a, port_b, a_to_b, _) = pair();
+    let mut a = Endpoint::new(port_a, Config::default());
+    let mut b = Endpoint::new(port_b, Config::default());
+    let mut now = 0;
+    a_to_b.borrow_mut().drop = 2;
+    a.send(b"x", now).unwrap();
+    let (log_a, log_b) = run(&mut a, &mut b, &mut now, 500);
+    assert_eq!(log_a.delivered, [0]);
+    assert_eq!(log_b.received, [b"x".to_vec()]);
+    assert_eq!(a.stats().retransmits, 2);
+    assert_eq!(a_to_b.borrow().frames, 3);
+}
+
+#[test]
+fn test_lost_ack_is_not_delivered_twice() {
+    let (port_a, port_b, _, b_to_a) = pair();
+    let mut a = Endpoint::new(port_a, Config::default());
+    let mut b = Endpoint::new(port_b, Config::default());
+    let mut now = 0;
+    b_to_a.borrow_mut().drop = 1;
+    a.send(b"once", now).unwrap();
+    let (log_a, log_b) = run(&mut a, &mut b, &mut now, 500);
+    assert_eq!(log_a.delivered, [0]);
+    assert_eq!(log_b.received, [b"once".to_vec()]);
+    assert_eq!(b.stats().duplicates, 1);
+    assert_eq!(b.stats().received, 1);
+}
+
+#[test]
+fn test_corrupt_frame_is_dropped() {
+    let (port_a, port_b, a_to_b, _) = pair();
+    let mut a = Endpoint::new(port_a, Config::default());
+    let mut b = Endpoint::new(port_b, Config::default());
+    let mut now = 0;
+    a_to_b.borrow_mut().corrupt = 1;
+    a.send(b"payload", now).unwrap();
+    let (log_a, log_b) = run(&mut a, &mut b, &mut now, 500);
+    assert_eq!(log_a.delivered, [0]);
+    assert_eq!(log_b.received, [b"payload".to_vec()]);
+    assert_eq!(b.stats().errors, 1);
+    assert_eq!(a.stats().retransmits, 1);
+}
+
+#[test]
+fn test_gives_up() {
+    let (port_a, port_b, a_to_b, _) = pair();
+    let config = Config {
+        retry_ms: 50,
+        retries: 2,
+        ..Config::default()
+    };
+    let mut a = Endpoint::new(port_a, config);
+    let mut b = Endpoint::new(port_b, config);
+    let mut now = 0;
+    a_to_b.borrow_mut().drop = usize::MAX;
+    a.send(b"lost", now).unwrap();
+    let (log_a, log_b) = run(&mut a, &mut b, &mut now, 1000);
+    assert_eq!(log_a.failed, [0]);
+    assert!(log_b.received.is_empty());
+    assert_eq!(a_to_b.borrow().frames, 3);
+    assert!(!a.is_pending());
+    // The next packet goes out with the next sequence number
+    assert_eq!(a.send(b"next", now), Ok(1));
+}
+
+#[test]
+fn test_sequence_wraps() {
+    let (port_a, port_b, _, _) = pair();
+    let mut a = Endpoint::new(port_a, Config::default());
+    let mut b = Endpoint::new(port_b, Config::default());
+    let mut now = 0;
+    for i in 0..300u32 {
+        assert_eq!(a.send(&i.to_le_bytes(), now), Ok(i as u8));
+        let (log_a, log_b) = run(&mut a, &mut b, &mut now, 20);
+        assert_eq!(log_a.delivered, [i as u8]);
+        assert_eq!(log_b.received, [i.to_le_bytes().to_vec()]);
+    }
+}
@@ -0,0 +1,25 @@
+use link::frame::*;
+
+#[test]
+fn test_round_trip() {
+    let data = [0x00, 0xC0, 0xDB, 0x01, 0x00];
+    for encoding in [Encoding::Cobs, Encoding::Slip] {
+        let mut out = [0; 32];
+        let len = encode(encoding, &data, &mut out).unwrap();
+        assert!(len <= max_encoded_len(encoding, data.len()));
+        let mut decoder = Decoder::<32>::new(encoding);
+        let frames: Vec<_> = out[..len]
+            .iter()
+            .filter_map(|&byte| decoder.push(byte).map(|frame| frame.unwrap().to_vec()))
+            .collect();
+        assert_eq!(frames, [data.to_vec()]);
+    }
+}
+
+#[test]
+fn test_cobs_delimiter() {
+    let mut out = [0; 8];
+    assert_eq!(encode(Encoding::Cobs, &[0x11], &mut out), Ok(3));
+    assert_eq!(out[..3], [0x02, 0x11, 0x00]);
+    assert_eq!(encode(Encoding::Cobs, &[0x11], &mut out[..2]), Err(-1));
+}
@@ -0,0 +1,64 @@
+//! Loopback over a pseudo-terminal: one end on its controller side, as
+//! the host, the other on its terminal side, as the device.
+#![cfg(all(feature = "std", target_os = "linux"))]
+
+use link::endpoint::*;
+use link::frame::Encoding;
+use link::host::{Clock, SerialPort, pty};
+use link::packet::Check;
+
+#[test]
+fn test_serial_port_bytes() {
+    let (mut host, path) = pty().unwrap();
+    let mut device = SerialPort::open(&path, 115_200).unwrap();
+    host.write(&[0x00, 0x0A, 0x0D, 0x03, 0xFF]).unwrap();
+    let clock = Clock::new();
+    let mut received = Vec::new();
+    while received.len() < 5 && clock.now_ms() < 1000 {
+        received.extend(device.read_byte());
+    }
+    // Raw mode: no translation of newlines or control characters
+    assert_eq!(received, [0x00, 0x0A, 0x0D, 0x03, 0xFF]);
+    assert_eq!(device.read_byte(), None);
+    assert!(SerialPort::open(&path, 12_345).is_err());
+}
+
+#[test]
+fn test_link_over_pty() {
+    for encoding in [Encoding::Cobs, Encoding::Slip] {
+        let config = Config {
+            encoding,
+            check: Check::Crc32,
+            ..Config::default()
+        };
+        let (controller, path) = pty().unwrap();
+        let device = SerialPort::open(&path, 115_200).unwrap();
+        let mut host = Endpoint::new(controller, config);
+        let mut device = Endpoint::new(device, config);
+        let clock = Clock::new();
+
+        let mut received = Vec::new();
+        let mut delivered = 0;
+        let mut next = 0u8;
+        while delivered < 20 && clock.now_ms() < 5000 {
+            if !host.is_pending() {
+                host.send(&[next; 40], clock.now_ms()).unwrap();
+                next += 1;
+            }
+            while let Some(event) = host.poll(clock.now_ms()) {
+                if let Event::Delivered(_) = event {
+                    delivered += 1;
+                }
+            }
+            while let Some(event) = device.poll(clock.now_ms()) {
+                if let Event::Received(payload) = event {
+                    received.push(payload.to_vec());
+                }
+            }
+        }
+        assert_eq!(delivered, 20);
+        let expected: Vec<Vec<u8>> = (0..20).map(|i| vec![i; 40]).collect();
+        assert_eq!(received, expected);
+        assert_eq!(host.stats().errors + device.stats().errors, 0);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod cobs;
+pub mod crc;
+pub mod endpoint;
+pub mod frame;
+pub mod host;
+pub mod packet;
+pub mod slip;
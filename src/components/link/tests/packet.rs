@@ -0,0 +1,57 @@
+use link::packet::*;
+
+#[test]
+fn test_round_trip() {
+    for check in [Check::Crc16, Check::Crc32] {
+        let packet = Packet::data(7, b"hello");
+        let mut out = [0; MAX_PACKET];
+        let len = packet.encode(check, &mut out).unwrap();
+        assert_eq!(len, HEADER + 5 + check.size());
+        assert_eq!(out[..2], [Kind::Data as u8, 7]);
+        assert_eq!(Packet::parse(&out[..len], check), Ok(packet));
+
+        let len = Packet::ack(7).encode(check, &mut out).unwrap();
+        assert_eq!(Packet::parse(&out[..len], check), Ok(Packet::ack(7)));
+    }
+}
+
+#[test]
+fn test_check() {
+    let mut out = [0; MAX_PACKET];
+    let len = Packet::data(1, &[1, 2, 3])
+        .encode(Check::Crc16, &mut out)
+        .unwrap();
+    out[3] ^= 0x10;
+    assert_eq!(Packet::parse(&out[..len], Check::Crc16), Err(-4));
+    out[3] ^= 0x10;
+    // Parsed with the other check
+    assert_eq!(Packet::parse(&out[..len], Check::Crc32), Err(-4));
+    assert_eq!(Packet::parse(&out[..3], Check::Crc16), Err(-1));
+}
+
+#[test]
+fn test_limits() {
+    let mut out = [0; MAX_PACKET];
+    let payload = [0; MAX_PAYLOAD + 1];
+    assert_eq!(
+        Packet::data(0, &payload).encode(Check::Crc16, &mut out),
+        Err(-1)
+    );
+    let len = Packet::data(0, &payload[..MAX_PAYLOAD])
+        .encode(Check::Crc32, &mut out)
+        .unwrap();
+    assert_eq!(len, MAX_PACKET);
+    assert_eq!(
+        Packet::data(0, b"ab").encode(Check::Crc16, &mut out[..5]),
+        Err(-1)
+    );
+}
+
+#[test]
+fn test_unknown_kind() {
+    use link::crc::crc16;
+    let body = [0x09, 0x00];
+    let mut bytes = body.to_vec();
+    bytes.extend(crc16(&body).to_le_bytes());
+    assert_eq!(Packet::parse(&bytes, Check::Crc16), Err(-1));
+}
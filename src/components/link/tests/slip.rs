@@ -0,0 +1,48 @@
+use link::slip::*;
+
+#[test]
+fn test_encode() {
+    let mut out = [0; 16];
+    let len = encode(&[0x01, END, 0x02, ESC, 0x03], &mut out).unwrap();
+    assert_eq!(
+        &out[..len],
+        [END, 0x01, ESC, ESC_END, 0x02, ESC, ESC_ESC, 0x03, END]
+    );
+    assert_eq!(encode(&[END; 7], &mut out[..15]), Err(-1));
+    assert_eq!(encode(&[END; 7], &mut [0; 16]), Ok(max_encoded_len(7)));
+}
+
+#[test]
+fn test_decoder() {
+    let mut decoder = Decoder::<8>::new();
+    let mut frames = Vec::new();
+    let stream = [
+        0x55, END, 0x01, ESC, ESC_END, ESC, ESC_ESC, END, END, 0x02, END,
+    ];
+    for byte in stream {
+        if let Some(frame) = decoder.push(byte) {
+            frames.push(frame.map(|frame| frame.to_vec()));
+        }
+    }
+    // Noise before the first END comes out as a frame of its own
+    assert_eq!(
+        frames,
+        [Ok(vec![0x55]), Ok(vec![0x01, END, ESC]), Ok(vec![0x02])]
+    );
+}
+
+#[test]
+fn test_decoder_errors() {
+    let mut decoder = Decoder::<2>::new();
+    assert_eq!(decoder.push(ESC), None);
+    assert_eq!(decoder.push(0x10), None);
+    assert_eq!(decoder.push(END), Some(Err(-1)));
+    assert_eq!(decoder.push(ESC), None);
+    assert_eq!(decoder.push(END), Some(Err(-1)));
+    for byte in [1, 2, 3] {
+        assert_eq!(decoder.push(byte), None);
+    }
+    assert_eq!(decoder.push(END), Some(Err(-1)));
+    assert_eq!(decoder.push(4), None);
+    assert_eq!(decoder.push(END), Some(Ok(&[4][..])));
+}
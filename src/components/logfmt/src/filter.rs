@@ -0,0 +1,110 @@
+//! Log levels and per-module level filtering.
+//!
+//! A rule applies to a module and its submodules: the rule for
+//! `driver::i2c` matches `driver::i2c` and `driver::i2c::v1`, not
+//! `driver::i2cx`. The longest matching rule wins, records of modules
+//! without one are checked against the default level.
+
+/// Errors are i32 codes as in the firmware
+pub type Error = i32;
+
+/// Severity of a record, and as a filter the most verbose level let
+/// through; `Off` lets nothing through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Fixed-width name for log lines
+    pub fn label(self) -> &'static str {
+        match self {
+            Level::Off => "OFF  ",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN ",
+            Level::Info => "INFO ",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+/// Whether `module` is `prefix` or one of its submodules
+fn matches(module: &str, prefix: &str) -> bool {
+    match module.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::") || prefix.is_empty(),
+        None => false,
+    }
+}
+
+/// Default level plus up to `N` module rules
+#[derive(Debug, Clone)]
+pub struct Filter<const N: usize> {
+    default: Level,
+    rules: [Option<(&'static str, Level)>; N],
+}
+
+impl<const N: usize> Filter<N> {
+    pub const fn new(default: Level) -> Self {
+        Self {
+            default,
+            rules: [None; N],
+        }
+    }
+
+    pub fn set_default(&mut self, level: Level) {
+        self.default = level;
+    }
+
+    /// Set the level of `module` and its submodules. Returns Err(-1) once
+    /// `N` modules have rules.
+    pub fn set(&mut self, module: &'static str, level: Level) -> Result<(), Error> {
+        let slot = self
+            .rules
+            .iter()
+            .position(|r| r.is_some_and(|(m, _)| m == module))
+            .or_else(|| self.rules.iter().position(Option::is_none))
+            .ok_or(-1)?;
+        self.rules[slot] = Some((module, level));
+        Ok(())
+    }
+
+    /// Remove the rule of `module`, it then follows the default again
+    pub fn clear(&mut self, module: &str) {
+        for rule in self.rules.iter_mut() {
+            if rule.is_some_and(|(m, _)| m == module) {
+                *rule = None;
+            }
+        }
+    }
+
+    /// The level that applies to `module`
+    pub fn level(&self, module: &str) -> Level {
+        self.rules
+            .iter()
+            .flatten()
+            .filter(|(prefix, _)| matches(module, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    /// Whether a record of `level` from `module` is let through
+    pub fn enabled(&self, module: &str, level: Level) -> bool {
+        level != Level::Off && level <= self.level(module)
+    }
+
+    /// The most verbose level any module lets through, to skip formatting
+    /// early
+    pub fn max_level(&self) -> Level {
+        self.rules
+            .iter()
+            .flatten()
+            .map(|&(_, level)| level)
+            .fold(self.default, Level::max)
+    }
+}
@@ -0,0 +1,34 @@
+//! Text form of a log record:
+//!
+//! ```text
+//! [    12.345678] INFO  apps: blink: started
+//! ```
+
+use crate::filter::Level;
+use core::fmt::{self, Write};
+
+/// `target` without the leading `krate::` segment, so rules and lines use
+/// module paths relative to the crate
+pub fn module<'a>(target: &'a str, krate: &str) -> &'a str {
+    match target.strip_prefix(krate) {
+        Some("") => "",
+        Some(rest) => rest.strip_prefix("::").unwrap_or(target),
+        None => target,
+    }
+}
+
+/// Write the line prefix: the time since boot in seconds with microsecond
+/// resolution, the level and the module
+pub fn write_prefix(out: &mut impl Write, micros: u64, level: Level, module: &str) -> fmt::Result {
+    write!(
+        out,
+        "[{:>6}.{:06}] {} ",
+        micros / 1_000_000,
+        micros % 1_000_000,
+        level.label()
+    )?;
+    if !module.is_empty() {
+        write!(out, "{module}: ")?;
+    }
+    Ok(())
+}
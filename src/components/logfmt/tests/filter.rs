@@ -0,0 +1,55 @@
+use logfmt::filter::*;
+
+#[test]
+fn test_default_level() {
+    let filter = Filter::<4>::new(Level::Info);
+    assert!(filter.enabled("apps", Level::Error));
+    assert!(filter.enabled("apps", Level::Info));
+    assert!(!filter.enabled("apps", Level::Debug));
+    assert!(!filter.enabled("apps", Level::Off));
+    assert_eq!(filter.max_level(), Level::Info);
+}
+
+#[test]
+fn test_module_rules() {
+    let mut filter = Filter::<4>::new(Level::Warn);
+    filter.set("driver", Level::Info).unwrap();
+    filter.set("driver::i2c", Level::Trace).unwrap();
+    assert_eq!(filter.level("driver::usart::v1"), Level::Info);
+    assert_eq!(filter.level("driver::i2c"), Level::Trace);
+    assert_eq!(filter.level("driver::i2c::v2"), Level::Trace);
+    // Only whole path segments match
+    assert_eq!(filter.level("driver::i2cx"), Level::Info);
+    assert_eq!(filter.level("drivers"), Level::Warn);
+    assert_eq!(filter.level("apps"), Level::Warn);
+    assert_eq!(filter.max_level(), Level::Trace);
+}
+
+#[test]
+fn test_silence_module() {
+    let mut filter = Filter::<2>::new(Level::Debug);
+    filter.set("power", Level::Off).unwrap();
+    assert!(!filter.enabled("power", Level::Error));
+    assert!(filter.enabled("apps", Level::Debug));
+}
+
+#[test]
+fn test_replace_and_clear() {
+    let mut filter = Filter::<1>::new(Level::Info);
+    filter.set("apps", Level::Debug).unwrap();
+    filter.set("apps", Level::Error).unwrap();
+    assert_eq!(filter.level("apps"), Level::Error);
+    assert_eq!(filter.set("power", Level::Debug), Err(-1));
+    filter.clear("apps");
+    assert_eq!(filter.level("apps"), Level::Info);
+    filter.set("power", Level::Debug).unwrap();
+    filter.set_default(Level::Off);
+    assert_eq!(filter.level("apps"), Level::Off);
+}
+
+#[test]
+fn test_levels_ordered() {
+    assert!(Level::Error < Level::Warn);
+    assert!(Level::Debug < Level::Trace);
+    assert_eq!(Level::Warn.label().len(), Level::Error.label().len());
+}
@@ -0,0 +1,36 @@
+use logfmt::filter::Level;
+use logfmt::line::*;
+
+fn prefix(micros: u64, level: Level, module: &str) -> String {
+    let mut out = String::new();
+    write_prefix(&mut out, micros, level, module).unwrap();
+    out
+}
+
+#[test]
+fn test_prefix() {
+    assert_eq!(
+        prefix(12_345_678, Level::Info, "apps"),
+        "[    12.345678] INFO  apps: "
+    );
+    assert_eq!(
+        prefix(0, Level::Error, "driver::rtc"),
+        "[     0.000000] ERROR driver::rtc: "
+    );
+    assert_eq!(
+        prefix(1_000_000_000_001, Level::Warn, ""),
+        "[1000000.000001] WARN  "
+    );
+}
+
+#[test]
+fn test_module_relative_to_crate() {
+    assert_eq!(
+        module("firmware::driver::i2c::v1", "firmware"),
+        "driver::i2c::v1"
+    );
+    assert_eq!(module("firmware", "firmware"), "");
+    assert_eq!(module("sched::registry", "firmware"), "sched::registry");
+    // Another crate sharing the prefix
+    assert_eq!(module("firmware_extra::x", "firmware"), "firmware_extra::x");
+}
@@ -0,0 +1,24 @@
+//! CRC-16 of Modbus RTU frames: polynomial 0x8005 reflected (0xA001),
+//! initial value 0xFFFF. The CRC ends the frame low byte first.
+
+const POLYNOMIAL: u16 = 0xA001;
+
+/// Continue the CRC `crc` over `bytes`
+pub fn update(mut crc: u16, bytes: &[u8]) -> u16 {
+    for &byte in bytes {
+        crc ^= byte as u16;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                crc >> 1 ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+/// The CRC of `bytes`
+pub fn crc16(bytes: &[u8]) -> u16 {
+    update(0xFFFF, bytes)
+}
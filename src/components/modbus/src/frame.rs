@@ -0,0 +1,108 @@
+//! RTU frames: slave address, PDU and CRC, delimited by silence on the
+//! line.
+
+use crate::crc::crc16;
+use crate::timing::Timing;
+
+/// Longest frame: address, 253-byte PDU and CRC
+pub const MAX_ADU: usize = 256;
+
+/// Longest PDU: function code and data
+pub const MAX_PDU: usize = MAX_ADU - 3;
+
+/// Shortest frame: address, function code and CRC
+const MIN_ADU: usize = 4;
+
+/// Write the frame of `pdu` to `slave` into `out` and return its length.
+/// Returns Err(-1) if `pdu` is empty or does not fit.
+pub fn encode(slave: u8, pdu: &[u8], out: &mut [u8]) -> Result<usize, i32> {
+    let len = pdu.len() + 3;
+    if pdu.is_empty() || pdu.len() > MAX_PDU || out.len() < len {
+        return Err(-1);
+    }
+    out[0] = slave;
+    out[1..1 + pdu.len()].copy_from_slice(pdu);
+    let crc = crc16(&out[..len - 2]);
+    out[len - 2..len].copy_from_slice(&crc.to_le_bytes());
+    Ok(len)
+}
+
+/// Split a received frame into slave address and PDU. Returns Err(-1) if
+/// it is too short or its CRC does not match.
+pub fn decode(frame: &[u8]) -> Result<(u8, &[u8]), i32> {
+    if frame.len() < MIN_ADU || frame.len() > MAX_ADU {
+        return Err(-1);
+    }
+    let (body, crc) = frame.split_at(frame.len() - 2);
+    if crc16(body).to_le_bytes() != crc {
+        return Err(-1);
+    }
+    Ok((body[0], &body[1..]))
+}
+
+/// Assembles frames from received bytes and the times they arrived
+pub struct Receiver {
+    timing: Timing,
+    buf: [u8; MAX_ADU],
+    len: usize,
+    /// Arrival of the last byte
+    last_us: u64,
+    /// A gap or an overflow spoiled the frame
+    spoiled: bool,
+}
+
+impl Receiver {
+    pub const fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            buf: [0; MAX_ADU],
+            len: 0,
+            last_us: 0,
+            spoiled: false,
+        }
+    }
+
+    /// Add a byte received at `now_us`. After 3.5 character times of
+    /// silence it starts a new frame, dropping a frame not taken.
+    pub fn push(&mut self, byte: u8, now_us: u64) {
+        let gap = now_us.saturating_sub(self.last_us);
+        if self.len > 0 && gap >= self.timing.t3_5_us as u64 {
+            self.len = 0;
+            self.spoiled = false;
+        } else if self.len > 0 && gap > self.timing.t1_5_us as u64 {
+            self.spoiled = true;
+        }
+        if self.len < MAX_ADU {
+            self.buf[self.len] = byte;
+            self.len += 1;
+        } else {
+            self.spoiled = true;
+        }
+        self.last_us = now_us;
+    }
+
+    /// The frame received, once 3.5 character times of silence at
+    /// `now_us` have ended it. The frame is taken; a spoiled one is
+    /// dropped instead.
+    pub fn poll(&mut self, now_us: u64) -> Option<&[u8]> {
+        if self.len == 0 || now_us.saturating_sub(self.last_us) < self.timing.t3_5_us as u64 {
+            return None;
+        }
+        let len = core::mem::take(&mut self.len);
+        if core::mem::take(&mut self.spoiled) {
+            return None;
+        }
+        Some(&self.buf[..len])
+    }
+
+    /// Whether bytes of a frame not yet ended have been received
+    pub fn is_receiving(&self) -> bool {
+        self.len > 0
+    }
+
+    /// Drop the frame being received
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spoiled = false;
+    }
+}
@@ -0,0 +1,10 @@
+#![no_std]
+
+pub mod crc;
+pub mod frame;
+pub mod master;
+pub mod mock;
+pub mod pdu;
+pub mod serial;
+pub mod slave;
+pub mod timing;
@@ -0,0 +1,279 @@
+//! The master (client): sends a request to one slave, or to all with
+//! address 0, and assembles the response.
+//!
+//! It does not block: `send` transmits the request and `poll`, called
+//! more often than one character time, collects the response, so the
+//! caller decides how to wait.
+//!
+//! ```ignore
+//! master.send(17, &Request::ReadHoldingRegisters { address: 0x6B, count: 3 })?;
+//! let mut registers = [0; 3];
+//! loop {
+//!     match master.poll() {
+//!         Some(Ok(Response::Registers(words))) => {
+//!             registers.iter_mut().zip(words.iter()).for_each(|(r, w)| *r = w);
+//!             break;
+//!         }
+//!         Some(Ok(_)) => return Err(Error::Malformed),
+//!         Some(Err(error)) => return Err(error),
+//!         None => idle(),
+//!     }
+//! }
+//! ```
+
+use crate::frame::{self, MAX_ADU, MAX_PDU, Receiver};
+use crate::pdu::{Bits, Exception, Request, Words, function};
+use crate::serial::Serial;
+use crate::timing::Timing;
+
+/// Address of a request to all slaves, which do not answer
+pub const BROADCAST: u8 = 0;
+
+/// Highest slave address
+pub const MAX_SLAVE: u8 = 247;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// No valid response in time
+    Timeout,
+    /// A response that does not answer the request
+    Malformed,
+    /// The slave rejected the request
+    Exception(Exception),
+    /// A request out of the limits of its function, or not a write
+    /// broadcast
+    Invalid,
+    /// A request is still outstanding
+    Busy,
+    /// The serial port failed with this error
+    Serial(i32),
+}
+
+/// Response of a slave to a request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Response<'a> {
+    /// Coils or discrete inputs read
+    Bits(Bits<'a>),
+    /// Registers read
+    Registers(Words<'a>),
+    /// A single coil or register written; a coil turned on reads 0xFF00
+    Written { address: u16, value: u16 },
+    /// Coils or registers written
+    WrittenMultiple { address: u16, count: u16 },
+    /// A broadcast has been sent, and the turnaround delay for the slaves
+    /// to handle it has passed
+    Broadcast,
+}
+
+impl<'a> Response<'a> {
+    /// Parse the response PDU of a slave to `request`, whose PDU it is
+    /// checked against as well
+    pub fn parse(request: &Request<'_>, request_pdu: &[u8], pdu: &'a [u8]) -> Result<Self, Error> {
+        let expected = request.function();
+        match pdu {
+            [code, exception] if *code == expected | function::EXCEPTION => {
+                return Err(Error::Exception(Exception::from_code(*exception)));
+            }
+            [code, ..] if *code == expected => {}
+            _ => return Err(Error::Malformed),
+        }
+        match *request {
+            Request::ReadCoils { count, .. } | Request::ReadDiscreteInputs { count, .. } => {
+                let bytes = &pdu[1..];
+                if bytes.len() < 2
+                    || bytes[0] as usize != (count as usize).div_ceil(8)
+                    || bytes.len() != 1 + bytes[0] as usize
+                {
+                    return Err(Error::Malformed);
+                }
+                Ok(Response::Bits(Bits::Packed {
+                    bytes: &bytes[1..],
+                    count,
+                }))
+            }
+            Request::ReadHoldingRegisters { count, .. }
+            | Request::ReadInputRegisters { count, .. }
+            | Request::ReadWriteMultipleRegisters {
+                read_count: count, ..
+            } => {
+                let bytes = &pdu[1..];
+                if bytes.len() < 2
+                    || bytes[0] as usize != 2 * count as usize
+                    || bytes.len() != 1 + bytes[0] as usize
+                {
+                    return Err(Error::Malformed);
+                }
+                Ok(Response::Registers(Words::Bytes(&bytes[1..])))
+            }
+            // Echoes of the request
+            Request::WriteSingleCoil { .. } | Request::WriteSingleRegister { .. } => {
+                if pdu != request_pdu {
+                    return Err(Error::Malformed);
+                }
+                Ok(Response::Written {
+                    address: u16::from_be_bytes([pdu[1], pdu[2]]),
+                    value: u16::from_be_bytes([pdu[3], pdu[4]]),
+                })
+            }
+            Request::WriteMultipleCoils { .. } | Request::WriteMultipleRegisters { .. } => {
+                if pdu.len() != 5 || pdu[..5] != request_pdu[..5] {
+                    return Err(Error::Malformed);
+                }
+                Ok(Response::WrittenMultiple {
+                    address: u16::from_be_bytes([pdu[1], pdu[2]]),
+                    count: u16::from_be_bytes([pdu[3], pdu[4]]),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Idle,
+    /// Waiting for the response of `slave` until `deadline_us`
+    Waiting {
+        slave: u8,
+        deadline_us: u64,
+    },
+    /// Waiting for the slaves to handle a broadcast until `until_us`
+    Broadcast {
+        until_us: u64,
+    },
+}
+
+pub struct Master<S: Serial> {
+    serial: S,
+    receiver: Receiver,
+    state: State,
+    timeout_us: u64,
+    turnaround_us: u64,
+    /// Frame of the request outstanding
+    request: [u8; MAX_ADU],
+    request_len: usize,
+    /// PDU of its response
+    response: [u8; MAX_PDU],
+    response_len: usize,
+}
+
+impl<S: Serial> Master<S> {
+    pub fn new(serial: S, timing: Timing) -> Self {
+        Self {
+            serial,
+            receiver: Receiver::new(timing),
+            state: State::Idle,
+            timeout_us: 1_000_000,
+            turnaround_us: 100_000,
+            request: [0; MAX_ADU],
+            request_len: 0,
+            response: [0; MAX_PDU],
+            response_len: 0,
+        }
+    }
+
+    /// How long to wait for a response after sending, 1 s by default
+    pub fn with_timeout_ms(mut self, ms: u32) -> Self {
+        self.timeout_us = ms as u64 * 1000;
+        self
+    }
+
+    /// How long slaves take to handle a broadcast, 100 ms by default
+    pub fn with_turnaround_ms(mut self, ms: u32) -> Self {
+        self.turnaround_us = ms as u64 * 1000;
+        self
+    }
+
+    pub fn serial(&mut self) -> &mut S {
+        &mut self.serial
+    }
+
+    /// Whether a request is outstanding
+    pub fn is_busy(&self) -> bool {
+        self.state != State::Idle
+    }
+
+    /// Send `request` to `slave`, or to all slaves if it is `BROADCAST`
+    pub fn send(&mut self, slave: u8, request: &Request<'_>) -> Result<(), Error> {
+        if self.is_busy() {
+            return Err(Error::Busy);
+        }
+        if slave > MAX_SLAVE || (slave == BROADCAST && !request.is_write()) {
+            return Err(Error::Invalid);
+        }
+        let mut pdu = [0; MAX_PDU];
+        let len = request.encode(&mut pdu).map_err(|_| Error::Invalid)?;
+        self.request_len =
+            frame::encode(slave, &pdu[..len], &mut self.request).map_err(|_| Error::Invalid)?;
+
+        // Drop what arrived since the last transaction
+        while self.serial.read_byte().is_some() {}
+        self.receiver.clear();
+
+        self.serial
+            .send(&self.request[..self.request_len])
+            .map_err(Error::Serial)?;
+        let now = self.serial.now_us();
+        self.state = if slave == BROADCAST {
+            State::Broadcast {
+                until_us: now + self.turnaround_us,
+            }
+        } else {
+            State::Waiting {
+                slave,
+                deadline_us: now + self.timeout_us,
+            }
+        };
+        Ok(())
+    }
+
+    /// Receive what has arrived, and return the outcome of the request
+    /// once there is one. Frames with a wrong CRC or from another slave
+    /// are ignored, and end in a timeout if no valid one follows.
+    pub fn poll(&mut self) -> Option<Result<Response<'_>, Error>> {
+        while let Some(byte) = self.serial.read_byte() {
+            let now = self.serial.now_us();
+            self.receiver.push(byte, now);
+        }
+        let now = self.serial.now_us();
+        match self.state {
+            State::Idle => None,
+            State::Broadcast { until_us } => {
+                self.receiver.clear();
+                if now < until_us {
+                    return None;
+                }
+                self.state = State::Idle;
+                Some(Ok(Response::Broadcast))
+            }
+            State::Waiting { slave, deadline_us } => {
+                let answered = match self.receiver.poll(now).map(frame::decode) {
+                    Some(Ok((address, pdu))) if address == slave => {
+                        self.response[..pdu.len()].copy_from_slice(pdu);
+                        self.response_len = pdu.len();
+                        true
+                    }
+                    _ => false,
+                };
+                if answered {
+                    self.state = State::Idle;
+                    let request_pdu = &self.request[1..self.request_len - 2];
+                    let response = Request::parse(request_pdu)
+                        .map_err(|_| Error::Invalid)
+                        .and_then(|request| {
+                            Response::parse(
+                                &request,
+                                request_pdu,
+                                &self.response[..self.response_len],
+                            )
+                        });
+                    return Some(response);
+                }
+                if now >= deadline_us && !self.receiver.is_receiving() {
+                    self.state = State::Idle;
+                    return Some(Err(Error::Timeout));
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! A slave register map for host tests: each table holds `SIZE` items
+//! from address 0 on, and further addresses are illegal.
+
+use crate::pdu::Exception;
+use crate::slave::RegisterMap;
+
+/// Items of each table
+pub const SIZE: usize = 64;
+
+pub struct Registers {
+    pub coils: [bool; SIZE],
+    pub discrete_inputs: [bool; SIZE],
+    pub holding: [u16; SIZE],
+    pub input: [u16; SIZE],
+    /// Writes carried out, one per item
+    pub writes: usize,
+}
+
+impl Registers {
+    pub const fn new() -> Self {
+        Self {
+            coils: [false; SIZE],
+            discrete_inputs: [false; SIZE],
+            holding: [0; SIZE],
+            input: [0; SIZE],
+            writes: 0,
+        }
+    }
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn index(address: u16) -> Result<usize, Exception> {
+    if (address as usize) < SIZE {
+        Ok(address as usize)
+    } else {
+        Err(Exception::IllegalDataAddress)
+    }
+}
+
+impl RegisterMap for Registers {
+    fn read_coil(&self, address: u16) -> Result<bool, Exception> {
+        Ok(self.coils[index(address)?])
+    }
+
+    fn read_discrete_input(&self, address: u16) -> Result<bool, Exception> {
+        Ok(self.discrete_inputs[index(address)?])
+    }
+
+    fn read_holding_register(&self, address: u16) -> Result<u16, Exception> {
+        Ok(self.holding[index(address)?])
+    }
+
+    fn read_input_register(&self, address: u16) -> Result<u16, Exception> {
+        Ok(self.input[index(address)?])
+    }
+
+    fn write_coil(&mut self, address: u16, value: bool) -> Result<(), Exception> {
+        self.coils[index(address)?] = value;
+        self.writes += 1;
+        Ok(())
+    }
+
+    fn write_register(&mut self, address: u16, value: u16) -> Result<(), Exception> {
+        self.holding[index(address)?] = value;
+        self.writes += 1;
+        Ok(())
+    }
+}
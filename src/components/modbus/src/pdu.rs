@@ -0,0 +1,436 @@
+//! Protocol data units: the function code and data of a request, in both
+//! directions, the master encoding and the slave parsing it.
+//!
+//! Registers are big-endian on the wire; coils and discrete inputs are
+//! packed eight to a byte, the first in bit 0.
+
+/// Function codes
+pub mod function {
+    pub const READ_COILS: u8 = 0x01;
+    pub const READ_DISCRETE_INPUTS: u8 = 0x02;
+    pub const READ_HOLDING_REGISTERS: u8 = 0x03;
+    pub const READ_INPUT_REGISTERS: u8 = 0x04;
+    pub const WRITE_SINGLE_COIL: u8 = 0x05;
+    pub const WRITE_SINGLE_REGISTER: u8 = 0x06;
+    pub const WRITE_MULTIPLE_COILS: u8 = 0x0F;
+    pub const WRITE_MULTIPLE_REGISTERS: u8 = 0x10;
+    pub const READ_WRITE_MULTIPLE_REGISTERS: u8 = 0x17;
+
+    /// Set in the function code of an exception response
+    pub const EXCEPTION: u8 = 0x80;
+}
+
+/// Most coils or inputs one read returns
+pub const MAX_READ_BITS: u16 = 2000;
+/// Most registers one read returns
+pub const MAX_READ_REGISTERS: u16 = 125;
+/// Most coils one write sets
+pub const MAX_WRITE_BITS: u16 = 1968;
+/// Most registers one write sets
+pub const MAX_WRITE_REGISTERS: u16 = 123;
+/// Most registers the write of a read/write sets
+pub const MAX_READ_WRITE_REGISTERS: u16 = 121;
+
+/// Value of a coil turned on in a write single coil request
+const COIL_ON: u16 = 0xFF00;
+
+/// Exception codes a slave answers a request it cannot serve with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exception {
+    IllegalFunction,
+    IllegalDataAddress,
+    IllegalDataValue,
+    ServerDeviceFailure,
+    Acknowledge,
+    ServerDeviceBusy,
+    /// A code this stack does not name
+    Other(u8),
+}
+
+impl Exception {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0x01 => Self::IllegalFunction,
+            0x02 => Self::IllegalDataAddress,
+            0x03 => Self::IllegalDataValue,
+            0x04 => Self::ServerDeviceFailure,
+            0x05 => Self::Acknowledge,
+            0x06 => Self::ServerDeviceBusy,
+            code => Self::Other(code),
+        }
+    }
+
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::IllegalFunction => 0x01,
+            Self::IllegalDataAddress => 0x02,
+            Self::IllegalDataValue => 0x03,
+            Self::ServerDeviceFailure => 0x04,
+            Self::Acknowledge => 0x05,
+            Self::ServerDeviceBusy => 0x06,
+            Self::Other(code) => *code,
+        }
+    }
+}
+
+/// Coil or discrete input values
+#[derive(Debug, Clone, Copy)]
+pub enum Bits<'a> {
+    /// As on the wire
+    Packed {
+        bytes: &'a [u8],
+        count: u16,
+    },
+    Values(&'a [bool]),
+}
+
+impl Bits<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            Bits::Packed { count, .. } => *count as usize,
+            Bits::Values(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<bool> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(match self {
+            Bits::Packed { bytes, .. } => bytes[index / 8] & (1 << (index % 8)) != 0,
+            Bits::Values(values) => values[index],
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Bytes the values take packed
+    pub fn packed_len(&self) -> usize {
+        self.len().div_ceil(8)
+    }
+
+    /// Pack the values into `out`, which holds at least `packed_len()`
+    fn pack(&self, out: &mut [u8]) {
+        out[..self.packed_len()].fill(0);
+        for (index, value) in self.iter().enumerate() {
+            out[index / 8] |= (value as u8) << (index % 8);
+        }
+    }
+}
+
+impl PartialEq for Bits<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+/// Register values
+#[derive(Debug, Clone, Copy)]
+pub enum Words<'a> {
+    /// As on the wire, big-endian
+    Bytes(&'a [u8]),
+    Values(&'a [u16]),
+}
+
+impl Words<'_> {
+    pub fn len(&self) -> usize {
+        match self {
+            Words::Bytes(bytes) => bytes.len() / 2,
+            Words::Values(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u16> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(match self {
+            Words::Bytes(bytes) => u16::from_be_bytes([bytes[2 * index], bytes[2 * index + 1]]),
+            Words::Values(values) => values[index],
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..self.len()).filter_map(|index| self.get(index))
+    }
+
+    /// Write the values big-endian into `out`, which holds at least
+    /// twice `len()`
+    fn write(&self, out: &mut [u8]) {
+        for (index, value) in self.iter().enumerate() {
+            out[2 * index..2 * index + 2].copy_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+impl PartialEq for Words<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+/// A request of one of the supported functions
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Request<'a> {
+    ReadCoils {
+        address: u16,
+        count: u16,
+    },
+    ReadDiscreteInputs {
+        address: u16,
+        count: u16,
+    },
+    ReadHoldingRegisters {
+        address: u16,
+        count: u16,
+    },
+    ReadInputRegisters {
+        address: u16,
+        count: u16,
+    },
+    WriteSingleCoil {
+        address: u16,
+        value: bool,
+    },
+    WriteSingleRegister {
+        address: u16,
+        value: u16,
+    },
+    WriteMultipleCoils {
+        address: u16,
+        values: Bits<'a>,
+    },
+    WriteMultipleRegisters {
+        address: u16,
+        values: Words<'a>,
+    },
+    /// Write, then read, in one request
+    ReadWriteMultipleRegisters {
+        read_address: u16,
+        read_count: u16,
+        write_address: u16,
+        values: Words<'a>,
+    },
+}
+
+fn u16_at(bytes: &[u8], index: usize) -> u16 {
+    u16::from_be_bytes([bytes[index], bytes[index + 1]])
+}
+
+/// Whether `count` items from `address` are a valid range of 1..=`max`
+/// items within the 16-bit address space
+fn check_range(address: u16, count: u16, max: u16) -> Result<(), Exception> {
+    if count == 0 || count > max {
+        return Err(Exception::IllegalDataValue);
+    }
+    if address as u32 + count as u32 > 0x1_0000 {
+        return Err(Exception::IllegalDataAddress);
+    }
+    Ok(())
+}
+
+impl<'a> Request<'a> {
+    pub fn function(&self) -> u8 {
+        match self {
+            Request::ReadCoils { .. } => function::READ_COILS,
+            Request::ReadDiscreteInputs { .. } => function::READ_DISCRETE_INPUTS,
+            Request::ReadHoldingRegisters { .. } => function::READ_HOLDING_REGISTERS,
+            Request::ReadInputRegisters { .. } => function::READ_INPUT_REGISTERS,
+            Request::WriteSingleCoil { .. } => function::WRITE_SINGLE_COIL,
+            Request::WriteSingleRegister { .. } => function::WRITE_SINGLE_REGISTER,
+            Request::WriteMultipleCoils { .. } => function::WRITE_MULTIPLE_COILS,
+            Request::WriteMultipleRegisters { .. } => function::WRITE_MULTIPLE_REGISTERS,
+            Request::ReadWriteMultipleRegisters { .. } => function::READ_WRITE_MULTIPLE_REGISTERS,
+        }
+    }
+
+    /// Whether the request only writes, the only kind that can be
+    /// broadcast
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            Request::WriteSingleCoil { .. }
+                | Request::WriteSingleRegister { .. }
+                | Request::WriteMultipleCoils { .. }
+                | Request::WriteMultipleRegisters { .. }
+        )
+    }
+
+    /// Check the quantities and addresses against the limits of the
+    /// function
+    pub fn validate(&self) -> Result<(), Exception> {
+        match *self {
+            Request::ReadCoils { address, count }
+            | Request::ReadDiscreteInputs { address, count } => {
+                check_range(address, count, MAX_READ_BITS)
+            }
+            Request::ReadHoldingRegisters { address, count }
+            | Request::ReadInputRegisters { address, count } => {
+                check_range(address, count, MAX_READ_REGISTERS)
+            }
+            Request::WriteSingleCoil { .. } | Request::WriteSingleRegister { .. } => Ok(()),
+            Request::WriteMultipleCoils { address, values } => {
+                check_range(address, values.len() as u16, MAX_WRITE_BITS)
+            }
+            Request::WriteMultipleRegisters { address, values } => {
+                check_range(address, values.len() as u16, MAX_WRITE_REGISTERS)
+            }
+            Request::ReadWriteMultipleRegisters {
+                read_address,
+                read_count,
+                write_address,
+                values,
+            } => {
+                check_range(read_address, read_count, MAX_READ_REGISTERS)?;
+                check_range(write_address, values.len() as u16, MAX_READ_WRITE_REGISTERS)
+            }
+        }
+    }
+
+    /// Write the PDU into `out` and return its length. Returns Err(-1) if
+    /// the request is out of the limits of its function or does not fit.
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, i32> {
+        self.validate().map_err(|_| -1)?;
+        let len = match self {
+            Request::WriteMultipleCoils { values, .. } => 6 + values.packed_len(),
+            Request::WriteMultipleRegisters { values, .. } => 6 + 2 * values.len(),
+            Request::ReadWriteMultipleRegisters { values, .. } => 10 + 2 * values.len(),
+            _ => 5,
+        };
+        if out.len() < len {
+            return Err(-1);
+        }
+        out[0] = self.function();
+        match *self {
+            Request::ReadCoils { address, count }
+            | Request::ReadDiscreteInputs { address, count }
+            | Request::ReadHoldingRegisters { address, count }
+            | Request::ReadInputRegisters { address, count } => {
+                out[1..3].copy_from_slice(&address.to_be_bytes());
+                out[3..5].copy_from_slice(&count.to_be_bytes());
+            }
+            Request::WriteSingleCoil { address, value } => {
+                let value = if value { COIL_ON } else { 0 };
+                out[1..3].copy_from_slice(&address.to_be_bytes());
+                out[3..5].copy_from_slice(&value.to_be_bytes());
+            }
+            Request::WriteSingleRegister { address, value } => {
+                out[1..3].copy_from_slice(&address.to_be_bytes());
+                out[3..5].copy_from_slice(&value.to_be_bytes());
+            }
+            Request::WriteMultipleCoils { address, values } => {
+                out[1..3].copy_from_slice(&address.to_be_bytes());
+                out[3..5].copy_from_slice(&(values.len() as u16).to_be_bytes());
+                out[5] = values.packed_len() as u8;
+                values.pack(&mut out[6..]);
+            }
+            Request::WriteMultipleRegisters { address, values } => {
+                out[1..3].copy_from_slice(&address.to_be_bytes());
+                out[3..5].copy_from_slice(&(values.len() as u16).to_be_bytes());
+                out[5] = 2 * values.len() as u8;
+                values.write(&mut out[6..]);
+            }
+            Request::ReadWriteMultipleRegisters {
+                read_address,
+                read_count,
+                write_address,
+                values,
+            } => {
+                out[1..3].copy_from_slice(&read_address.to_be_bytes());
+                out[3..5].copy_from_slice(&read_count.to_be_bytes());
+                out[5..7].copy_from_slice(&write_address.to_be_bytes());
+                out[7..9].copy_from_slice(&(values.len() as u16).to_be_bytes());
+                out[9] = 2 * values.len() as u8;
+                values.write(&mut out[10..]);
+            }
+        }
+        Ok(len)
+    }
+
+    /// Parse a request PDU, with the exception a slave answers one it
+    /// cannot serve with
+    pub fn parse(pdu: &'a [u8]) -> Result<Self, Exception> {
+        let Some(&function) = pdu.first() else {
+            return Err(Exception::IllegalFunction);
+        };
+        // Length of the fixed part, and of the data counted in it
+        let (fixed, data) = match function {
+            function::READ_COILS..=function::WRITE_SINGLE_REGISTER => (5, 0),
+            function::WRITE_MULTIPLE_COILS | function::WRITE_MULTIPLE_REGISTERS => {
+                (6, *pdu.get(5).unwrap_or(&0) as usize)
+            }
+            function::READ_WRITE_MULTIPLE_REGISTERS => (10, *pdu.get(9).unwrap_or(&0) as usize),
+            _ => return Err(Exception::IllegalFunction),
+        };
+        if pdu.len() != fixed + data {
+            return Err(Exception::IllegalDataValue);
+        }
+        let address = u16_at(pdu, 1);
+        let count = u16_at(pdu, 3);
+        let request = match function {
+            function::READ_COILS => Request::ReadCoils { address, count },
+            function::READ_DISCRETE_INPUTS => Request::ReadDiscreteInputs { address, count },
+            function::READ_HOLDING_REGISTERS => Request::ReadHoldingRegisters { address, count },
+            function::READ_INPUT_REGISTERS => Request::ReadInputRegisters { address, count },
+            function::WRITE_SINGLE_COIL => match count {
+                COIL_ON => Request::WriteSingleCoil {
+                    address,
+                    value: true,
+                },
+                0 => Request::WriteSingleCoil {
+                    address,
+                    value: false,
+                },
+                _ => return Err(Exception::IllegalDataValue),
+            },
+            function::WRITE_SINGLE_REGISTER => Request::WriteSingleRegister {
+                address,
+                value: count,
+            },
+            function::WRITE_MULTIPLE_COILS => {
+                if data != (count as usize).div_ceil(8) {
+                    return Err(Exception::IllegalDataValue);
+                }
+                Request::WriteMultipleCoils {
+                    address,
+                    values: Bits::Packed {
+                        bytes: &pdu[6..],
+                        count,
+                    },
+                }
+            }
+            function::WRITE_MULTIPLE_REGISTERS => {
+                if data != 2 * count as usize {
+                    return Err(Exception::IllegalDataValue);
+                }
+                Request::WriteMultipleRegisters {
+                    address,
+                    values: Words::Bytes(&pdu[6..]),
+                }
+            }
+            _ => {
+                if data != 2 * u16_at(pdu, 7) as usize {
+                    return Err(Exception::IllegalDataValue);
+                }
+                Request::ReadWriteMultipleRegisters {
+                    read_address: address,
+                    read_count: count,
+                    write_address: u16_at(pdu, 5),
+                    values: Words::Bytes(&pdu[10..]),
+                }
+            }
+        };
+        request.validate()?;
+        Ok(request)
+    }
+}
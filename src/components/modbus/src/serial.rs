@@ -0,0 +1,135 @@
+//! The serial line a master or slave talks over, and an in-memory pair of
+//! ports for host tests.
+
+use core::cell::{Cell, RefCell};
+
+use crate::timing::Timing;
+
+/// A half-duplex serial port with a microsecond clock
+pub trait Serial {
+    /// Transmit `bytes`, returning once the last has left the line, so
+    /// that an RS-485 transceiver can be turned back to receive
+    fn send(&mut self, bytes: &[u8]) -> Result<(), i32>;
+
+    /// The next byte received, if any
+    fn read_byte(&mut self) -> Option<u8>;
+
+    fn now_us(&self) -> u64;
+}
+
+/// Bytes a port of a `Line` holds unread
+pub const QUEUE_SIZE: usize = 512;
+
+struct Queue {
+    buf: [u8; QUEUE_SIZE],
+    head: usize,
+    len: usize,
+}
+
+impl Queue {
+    const fn new() -> Self {
+        Self {
+            buf: [0; QUEUE_SIZE],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns false if the queue is full and `byte` is lost
+    fn push(&mut self, byte: u8) -> bool {
+        if self.len == QUEUE_SIZE {
+            return false;
+        }
+        self.buf[(self.head + self.len) % QUEUE_SIZE] = byte;
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.buf[self.head];
+        self.head = (self.head + 1) % QUEUE_SIZE;
+        self.len -= 1;
+        Some(byte)
+    }
+}
+
+/// Two ports joined by a line, sharing a simulated clock.
+///
+/// Sending takes one character time per byte on the clock, and the bytes
+/// are all readable by the other port once it returns. Between sends the
+/// clock stands still until `advance` moves it.
+///
+/// A master or slave times bytes as it reads them, so it must poll
+/// between a send and the `advance` past the silence ending the frame,
+/// as it would be polled during the frame on a real line.
+pub struct Line {
+    timing: Timing,
+    queues: [RefCell<Queue>; 2],
+    now_us: Cell<u64>,
+}
+
+impl Line {
+    pub const fn new(baud: u32) -> Self {
+        Self {
+            timing: Timing::new(baud),
+            queues: [RefCell::new(Queue::new()), RefCell::new(Queue::new())],
+            now_us: Cell::new(0),
+        }
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    /// The ports at both ends
+    pub fn ports(&self) -> (Port<'_>, Port<'_>) {
+        (self.port(0), self.port(1))
+    }
+
+    /// The port at end `side`, 0 or 1
+    pub fn port(&self, side: usize) -> Port<'_> {
+        Port { line: self, side }
+    }
+
+    pub fn now_us(&self) -> u64 {
+        self.now_us.get()
+    }
+
+    /// Let `us` pass
+    pub fn advance(&self, us: u64) {
+        self.now_us.set(self.now_us.get() + us);
+    }
+}
+
+/// One end of a `Line`
+pub struct Port<'a> {
+    line: &'a Line,
+    side: usize,
+}
+
+impl Serial for Port<'_> {
+    /// Returns Err(-1) if the other port's queue overflows
+    fn send(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        let mut queue = self.line.queues[1 - self.side].borrow_mut();
+        let mut result = Ok(());
+        for &byte in bytes {
+            if !queue.push(byte) {
+                result = Err(-1);
+            }
+        }
+        self.line
+            .advance(bytes.len() as u64 * self.line.timing.char_us as u64);
+        result
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        self.line.queues[self.side].borrow_mut().pop()
+    }
+
+    fn now_us(&self) -> u64 {
+        self.line.now_us()
+    }
+}
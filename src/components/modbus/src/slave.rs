@@ -0,0 +1,232 @@
+//! The slave (server): answers the requests addressed to it from a
+//! `RegisterMap`, and carries out broadcast writes without answering.
+
+use crate::frame::{self, MAX_ADU, MAX_PDU, Receiver};
+use crate::master::BROADCAST;
+use crate::pdu::{Bits, Exception, Request, Words, function};
+use crate::serial::Serial;
+use crate::timing::Timing;
+
+/// The data model of a slave: coils, discrete inputs, holding and input
+/// registers, each in its own 16-bit address space.
+///
+/// Every method defaults to rejecting the request with
+/// `IllegalFunction`, so a map implements only the tables it has, and
+/// returns `IllegalDataAddress` for addresses outside them.
+pub trait RegisterMap {
+    fn read_coil(&self, _address: u16) -> Result<bool, Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    fn read_discrete_input(&self, _address: u16) -> Result<bool, Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    fn read_holding_register(&self, _address: u16) -> Result<u16, Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    fn read_input_register(&self, _address: u16) -> Result<u16, Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    fn write_coil(&mut self, _address: u16, _value: bool) -> Result<(), Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    fn write_register(&mut self, _address: u16, _value: u16) -> Result<(), Exception> {
+        Err(Exception::IllegalFunction)
+    }
+
+    /// Write coils from `address` on. The default writes them one at a
+    /// time and stops at the first error; a map that must not be left
+    /// half written checks the whole range first.
+    fn write_coils(&mut self, address: u16, values: &Bits<'_>) -> Result<(), Exception> {
+        for (offset, value) in values.iter().enumerate() {
+            self.write_coil(address + offset as u16, value)?;
+        }
+        Ok(())
+    }
+
+    /// Write registers from `address` on, like `write_coils`
+    fn write_registers(&mut self, address: u16, values: &Words<'_>) -> Result<(), Exception> {
+        for (offset, value) in values.iter().enumerate() {
+            self.write_register(address + offset as u16, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read `count` bits from `address` on into `out`, prefixed with their
+/// byte count, and return the length
+fn read_bits(
+    out: &mut [u8],
+    address: u16,
+    count: u16,
+    read: impl Fn(u16) -> Result<bool, Exception>,
+) -> Result<usize, Exception> {
+    let bytes = (count as usize).div_ceil(8);
+    out[0] = bytes as u8;
+    out[1..1 + bytes].fill(0);
+    for offset in 0..count {
+        let index = offset as usize;
+        out[1 + index / 8] |= (read(address + offset)? as u8) << (index % 8);
+    }
+    Ok(1 + bytes)
+}
+
+/// Read `count` registers from `address` on into `out`, prefixed with
+/// their byte count, and return the length
+fn read_registers(
+    out: &mut [u8],
+    address: u16,
+    count: u16,
+    read: impl Fn(u16) -> Result<u16, Exception>,
+) -> Result<usize, Exception> {
+    out[0] = 2 * count as u8;
+    for offset in 0..count {
+        let index = 1 + 2 * offset as usize;
+        out[index..index + 2].copy_from_slice(&read(address + offset)?.to_be_bytes());
+    }
+    Ok(1 + 2 * count as usize)
+}
+
+/// Serve `request` from `map`, writing the data of the response into
+/// `out`, and return its length
+fn serve<M: RegisterMap>(
+    map: &mut M,
+    request: &Request<'_>,
+    request_pdu: &[u8],
+    out: &mut [u8],
+) -> Result<usize, Exception> {
+    match *request {
+        Request::ReadCoils { address, count } => {
+            read_bits(out, address, count, |address| map.read_coil(address))
+        }
+        Request::ReadDiscreteInputs { address, count } => {
+            read_bits(out, address, count, |address| {
+                map.read_discrete_input(address)
+            })
+        }
+        Request::ReadHoldingRegisters { address, count } => {
+            read_registers(out, address, count, |address| {
+                map.read_holding_register(address)
+            })
+        }
+        Request::ReadInputRegisters { address, count } => {
+            read_registers(out, address, count, |address| {
+                map.read_input_register(address)
+            })
+        }
+        Request::WriteSingleCoil { address, value } => {
+            map.write_coil(address, value)?;
+            out[..4].copy_from_slice(&request_pdu[1..5]);
+            Ok(4)
+        }
+        Request::WriteSingleRegister { address, value } => {
+            map.write_register(address, value)?;
+            out[..4].copy_from_slice(&request_pdu[1..5]);
+            Ok(4)
+        }
+        Request::WriteMultipleCoils { address, values } => {
+            map.write_coils(address, &values)?;
+            out[..4].copy_from_slice(&request_pdu[1..5]);
+            Ok(4)
+        }
+        Request::WriteMultipleRegisters { address, values } => {
+            map.write_registers(address, &values)?;
+            out[..4].copy_from_slice(&request_pdu[1..5]);
+            Ok(4)
+        }
+        Request::ReadWriteMultipleRegisters {
+            read_address,
+            read_count,
+            write_address,
+            values,
+        } => {
+            map.write_registers(write_address, &values)?;
+            read_registers(out, read_address, read_count, |address| {
+                map.read_holding_register(address)
+            })
+        }
+    }
+}
+
+/// Handle the request PDU `pdu` with `map`, write the response PDU into
+/// `out`, which holds `MAX_PDU` bytes, and return its length. Requests
+/// that cannot be served get an exception response.
+pub fn handle<M: RegisterMap>(map: &mut M, pdu: &[u8], out: &mut [u8]) -> usize {
+    let code = pdu.first().copied().unwrap_or(0);
+    let result = Request::parse(pdu).and_then(|request| serve(map, &request, pdu, &mut out[1..]));
+    match result {
+        Ok(len) => {
+            out[0] = code;
+            1 + len
+        }
+        Err(exception) => {
+            out[0] = code | function::EXCEPTION;
+            out[1] = exception.code();
+            2
+        }
+    }
+}
+
+pub struct Slave<S: Serial, M: RegisterMap> {
+    serial: S,
+    address: u8,
+    map: M,
+    receiver: Receiver,
+}
+
+impl<S: Serial, M: RegisterMap> Slave<S, M> {
+    pub fn new(serial: S, address: u8, map: M, timing: Timing) -> Self {
+        Self {
+            serial,
+            address,
+            map,
+            receiver: Receiver::new(timing),
+        }
+    }
+
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+
+    pub fn map_mut(&mut self) -> &mut M {
+        &mut self.map
+    }
+
+    /// Receive what has arrived and handle a complete request. Call it
+    /// more often than one character time. Returns whether a request
+    /// addressed to the slave or broadcast was handled; frames with a
+    /// wrong CRC are dropped silently, as the specification asks.
+    pub fn poll(&mut self) -> Result<bool, i32> {
+        while let Some(byte) = self.serial.read_byte() {
+            let now = self.serial.now_us();
+            self.receiver.push(byte, now);
+        }
+        let now = self.serial.now_us();
+        let mut response = [0; MAX_PDU];
+        let (address, len) = match self.receiver.poll(now).map(frame::decode) {
+            Some(Ok((address, pdu))) if address == self.address => {
+                (address, handle(&mut self.map, pdu, &mut response))
+            }
+            Some(Ok((BROADCAST, pdu))) => {
+                if Request::parse(pdu).is_ok_and(|request| request.is_write()) {
+                    handle(&mut self.map, pdu, &mut response);
+                    return Ok(true);
+                }
+                return Ok(false);
+            }
+            _ => return Ok(false),
+        };
+        let mut frame = [0; MAX_ADU];
+        let len = frame::encode(address, &response[..len], &mut frame)?;
+        self.serial.send(&frame[..len])?;
+        Ok(true)
+    }
+}
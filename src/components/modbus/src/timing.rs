@@ -0,0 +1,40 @@
+//! Frame timing of Modbus RTU. A frame ends with 3.5 character times of
+//! silence, and a gap of more than 1.5 character times inside a frame
+//! makes it invalid. Above 19200 baud the specification fixes the times
+//! at 750 us and 1750 us instead, so that fast lines do not need
+//! sub-millisecond timers.
+
+/// Bits of a character: start, 8 data, parity or a second stop, stop
+pub const BITS_PER_CHAR: u32 = 11;
+
+/// Above this baud rate the gaps are fixed
+const FIXED_ABOVE_BAUD: u32 = 19_200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    /// Time one character takes on the line
+    pub char_us: u32,
+    /// Longest gap within a frame
+    pub t1_5_us: u32,
+    /// Silence that ends a frame
+    pub t3_5_us: u32,
+}
+
+impl Timing {
+    pub const fn new(baud: u32) -> Self {
+        let char_us = (BITS_PER_CHAR * 1_000_000).div_ceil(baud);
+        if baud > FIXED_ABOVE_BAUD {
+            Self {
+                char_us,
+                t1_5_us: 750,
+                t3_5_us: 1750,
+            }
+        } else {
+            Self {
+                char_us,
+                t1_5_us: char_us * 3 / 2,
+                t3_5_us: char_us * 7 / 2,
+            }
+        }
+    }
+}
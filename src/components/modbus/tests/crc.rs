@@ -0,0 +1,17 @@
+use modbus::crc::*;
+
+#[test]
+fn test_crc16() {
+    // Read 10 holding registers of slave 1, and of the specification's
+    // example slave 17
+    assert_eq!(crc16(&[0x01, 0x03, 0x00, 0x00, 0x00, 0x0A]), 0xCDC5);
+    assert_eq!(crc16(&[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03]), 0x8776);
+    assert_eq!(crc16(&[]), 0xFFFF);
+}
+
+#[test]
+fn test_update() {
+    let bytes = [0x11, 0x03, 0x00, 0x6B, 0x00, 0x03];
+    let (first, rest) = bytes.split_at(2);
+    assert_eq!(update(crc16(first), rest), crc16(&bytes));
+}
@@ -0,0 +1,83 @@
+use modbus::frame::*;
+use modbus::timing::Timing;
+
+const REQUEST: [u8; 8] = [0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87];
+
+#[test]
+fn test_encode_decode() {
+    let mut out = [0; MAX_ADU];
+    let len = encode(0x11, &REQUEST[1..6], &mut out).unwrap();
+    assert_eq!(&out[..len], REQUEST);
+    assert_eq!(decode(&REQUEST), Ok((0x11, &REQUEST[1..6])));
+
+    let mut corrupt = REQUEST;
+    corrupt[3] ^= 0x01;
+    assert_eq!(decode(&corrupt), Err(-1));
+    assert_eq!(decode(&REQUEST[..3]), Err(-1));
+}
+
+#[test]
+fn test_encode_limits() {
+    let mut out = [0; MAX_ADU];
+    assert_eq!(encode(1, &[], &mut out), Err(-1));
+    assert_eq!(encode(1, &[0; MAX_PDU], &mut out), Ok(MAX_ADU));
+    assert_eq!(encode(1, &[0; MAX_PDU + 1], &mut out), Err(-1));
+    assert_eq!(encode(1, &[0x03], &mut out[..3]), Err(-1));
+}
+
+fn receive(receiver: &mut Receiver, bytes: &[u8], start_us: u64, char_us: u64) -> u64 {
+    let mut now = start_us;
+    for &byte in bytes {
+        now += char_us;
+        receiver.push(byte, now);
+    }
+    now
+}
+
+#[test]
+fn test_receiver_ends_frame_after_silence() {
+    let timing = Timing::new(9600);
+    let mut receiver = Receiver::new(timing);
+    let end = receive(&mut receiver, &REQUEST, 0, timing.char_us as u64);
+    assert!(receiver.is_receiving());
+    assert_eq!(receiver.poll(end + timing.t3_5_us as u64 - 1), None);
+    assert_eq!(
+        receiver.poll(end + timing.t3_5_us as u64),
+        Some(&REQUEST[..])
+    );
+    assert_eq!(receiver.poll(end + 10 * timing.t3_5_us as u64), None);
+    assert!(!receiver.is_receiving());
+}
+
+#[test]
+fn test_receiver_splits_frames_on_silence() {
+    let timing = Timing::new(9600);
+    let mut receiver = Receiver::new(timing);
+    let end = receive(&mut receiver, &[0xAA, 0xBB], 0, timing.char_us as u64);
+    // The first frame, not taken, is dropped by the next
+    let end = receive(&mut receiver, &REQUEST, end + timing.t3_5_us as u64, 0);
+    assert_eq!(
+        receiver.poll(end + timing.t3_5_us as u64),
+        Some(&REQUEST[..])
+    );
+}
+
+#[test]
+fn test_receiver_drops_frame_with_gap() {
+    let timing = Timing::new(9600);
+    let mut receiver = Receiver::new(timing);
+    let char_us = timing.char_us as u64;
+    let end = receive(&mut receiver, &REQUEST[..4], 0, char_us);
+    // More than 1.5 but less than 3.5 characters of silence
+    let end = receive(&mut receiver, &REQUEST[4..], end + 2 * char_us, char_us);
+    assert_eq!(receiver.poll(end + timing.t3_5_us as u64), None);
+    assert!(!receiver.is_receiving());
+}
+
+#[test]
+fn test_receiver_drops_overflow() {
+    let timing = Timing::new(115_200);
+    let mut receiver = Receiver::new(timing);
+    let end = receive(&mut receiver, &[0; MAX_ADU + 1], 0, timing.char_us as u64);
+    assert_eq!(receiver.poll(end + timing.t3_5_us as u64), None);
+}
@@ -0,0 +1,328 @@
+use modbus::frame::{self, MAX_ADU};
+use modbus::master::*;
+use modbus::mock::Registers;
+use modbus::pdu::{Bits, Exception, Request, Words};
+use modbus::serial::{Line, Port, QUEUE_SIZE, Serial};
+use modbus::slave::Slave;
+
+const SLAVE: u8 = 17;
+
+/// Step time until the master has an outcome, letting the slave answer
+/// in between
+fn run<T>(
+    line: &Line,
+    master: &mut Master<Port<'_>>,
+    slave: &mut Slave<Port<'_>, Registers>,
+    check: impl FnOnce(Result<Response<'_>, Error>) -> T,
+) -> T {
+    for _ in 0..100_000 {
+        line.advance(100);
+        slave.poll().unwrap();
+        if let Some(outcome) = master.poll() {
+            return check(outcome);
+        }
+    }
+    panic!("no outcome");
+}
+
+fn setup(line: &Line) -> (Master<Port<'_>>, Slave<Port<'_>, Registers>) {
+    let (a, b) = line.ports();
+    (
+        Master::new(a, line.timing()),
+        Slave::new(b, SLAVE, Registers::new(), line.timing()),
+    )
+}
+
+#[test]
+fn test_read_registers() {
+    let line = Line::new(9600);
+    let (mut master, mut slave) = setup(&line);
+    slave.map_mut().holding[0x10..0x13].copy_from_slice(&[0x022B, 0x0000, 0x0064]);
+    slave.map_mut().input[2] = 0x7FFF;
+
+    let request = Request::ReadHoldingRegisters {
+        address: 0x10,
+        count: 3,
+    };
+    master.send(SLAVE, &request).unwrap();
+    assert!(master.is_busy());
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::Registers(Words::Values(&[
+                0x022B, 0x0000, 0x0064
+            ])))
+        )
+    });
+    assert!(!master.is_busy());
+
+    let request = Request::ReadInputRegisters {
+        address: 2,
+        count: 1,
+    };
+    master.send(SLAVE, &request).unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(outcome, Ok(Response::Registers(Words::Values(&[0x7FFF]))))
+    });
+}
+
+#[test]
+fn test_read_bits() {
+    let line = Line::new(9600);
+    let (mut master, mut slave) = setup(&line);
+    slave.map_mut().coils[1] = true;
+    slave.map_mut().discrete_inputs[9] = true;
+
+    master
+        .send(
+            SLAVE,
+            &Request::ReadCoils {
+                address: 0,
+                count: 3,
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::Bits(Bits::Values(&[false, true, false])))
+        )
+    });
+
+    master
+        .send(
+            SLAVE,
+            &Request::ReadDiscreteInputs {
+                address: 8,
+                count: 2,
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(outcome, Ok(Response::Bits(Bits::Values(&[false, true]))))
+    });
+}
+
+#[test]
+fn test_writes() {
+    let line = Line::new(115_200);
+    let (mut master, mut slave) = setup(&line);
+
+    master
+        .send(
+            SLAVE,
+            &Request::WriteSingleCoil {
+                address: 4,
+                value: true,
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::Written {
+                address: 4,
+                value: 0xFF00
+            })
+        )
+    });
+    master
+        .send(
+            SLAVE,
+            &Request::WriteSingleRegister {
+                address: 5,
+                value: 0xBEEF,
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::Written {
+                address: 5,
+                value: 0xBEEF
+            })
+        )
+    });
+    master
+        .send(
+            SLAVE,
+            &Request::WriteMultipleCoils {
+                address: 10,
+                values: Bits::Values(&[true, true, false, true]),
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::WrittenMultiple {
+                address: 10,
+                count: 4
+            })
+        )
+    });
+    master
+        .send(
+            SLAVE,
+            &Request::WriteMultipleRegisters {
+                address: 20,
+                values: Words::Values(&[1, 2, 3]),
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Ok(Response::WrittenMultiple {
+                address: 20,
+                count: 3
+            })
+        )
+    });
+    master
+        .send(
+            SLAVE,
+            &Request::ReadWriteMultipleRegisters {
+                read_address: 19,
+                read_count: 3,
+                write_address: 19,
+                values: Words::Values(&[9]),
+            },
+        )
+        .unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(outcome, Ok(Response::Registers(Words::Values(&[9, 1, 2]))))
+    });
+
+    let map = slave.map();
+    assert!(map.coils[4]);
+    assert_eq!(&map.coils[10..14], [true, true, false, true]);
+    assert_eq!(map.holding[5], 0xBEEF);
+    assert_eq!(&map.holding[19..23], [9, 1, 2, 3]);
+}
+
+#[test]
+fn test_exception() {
+    let line = Line::new(9600);
+    let (mut master, mut slave) = setup(&line);
+    let request = Request::ReadHoldingRegisters {
+        address: 100,
+        count: 1,
+    };
+    master.send(SLAVE, &request).unwrap();
+    run(&line, &mut master, &mut slave, |outcome| {
+        assert_eq!(
+            outcome,
+            Err(Error::Exception(Exception::IllegalDataAddress))
+        )
+    });
+}
+
+#[test]
+fn test_timeout() {
+    let line = Line::new(9600);
+    let (a, _b) = line.ports();
+    let mut master = Master::new(a, line.timing()).with_timeout_ms(50);
+    let request = Request::ReadCoils {
+        address: 0,
+        count: 1,
+    };
+    master.send(5, &request).unwrap();
+    assert_eq!(master.send(5, &request), Err(Error::Busy));
+    let start = line.now_us();
+    while master.poll().is_none() {
+        line.advance(100);
+    }
+    assert!(line.now_us() - start >= 50_000);
+    assert!(!master.is_busy());
+}
+
+#[test]
+fn test_wrong_responses() {
+    let line = Line::new(9600);
+    let (a, mut b) = line.ports();
+    let mut master = Master::new(a, line.timing()).with_timeout_ms(50);
+    let request = Request::ReadHoldingRegisters {
+        address: 0,
+        count: 2,
+    };
+    let mut answer = |master: &mut Master<Port<'_>>, pdu: &[u8], slave: u8, corrupt: bool| {
+        let mut frame = [0; MAX_ADU];
+        let len = frame::encode(slave, pdu, &mut frame).unwrap();
+        if corrupt {
+            frame[1] ^= 0x40;
+        }
+        b.send(&frame[..len]).unwrap();
+        // The master times bytes as it reads them
+        assert_eq!(master.poll(), None);
+        line.advance(line.timing().t3_5_us as u64);
+        master.poll().map(|outcome| outcome.map(|_| ()))
+    };
+
+    // One register where two were asked for
+    master.send(SLAVE, &request).unwrap();
+    let outcome = answer(&mut master, &[0x03, 0x02, 0x00, 0x01], SLAVE, false);
+    assert_eq!(outcome, Some(Err(Error::Malformed)));
+
+    // A bad CRC, or another slave, ends in a timeout
+    master.send(SLAVE, &request).unwrap();
+    assert_eq!(
+        answer(&mut master, &[0x03, 0x04, 0, 1, 0, 2], SLAVE, true),
+        None
+    );
+    assert_eq!(
+        answer(&mut master, &[0x03, 0x04, 0, 1, 0, 2], SLAVE + 1, false),
+        None
+    );
+    line.advance(50_000);
+    assert_eq!(master.poll(), Some(Err(Error::Timeout)));
+}
+
+#[test]
+fn test_broadcast() {
+    let line = Line::new(19_200);
+    let (a, b) = line.ports();
+    let mut master = Master::new(a, line.timing()).with_turnaround_ms(20);
+    let mut slaves = Slave::new(b, SLAVE, Registers::new(), line.timing());
+
+    let read = Request::ReadCoils {
+        address: 0,
+        count: 1,
+    };
+    assert_eq!(master.send(BROADCAST, &read), Err(Error::Invalid));
+    assert_eq!(master.send(MAX_SLAVE + 1, &read), Err(Error::Invalid));
+    let invalid = Request::ReadCoils {
+        address: 0,
+        count: 0,
+    };
+    assert_eq!(master.send(SLAVE, &invalid), Err(Error::Invalid));
+
+    let write = Request::WriteSingleRegister {
+        address: 3,
+        value: 42,
+    };
+    master.send(BROADCAST, &write).unwrap();
+    let start = line.now_us();
+    run(&line, &mut master, &mut slaves, |outcome| {
+        assert_eq!(outcome, Ok(Response::Broadcast))
+    });
+    assert!(line.now_us() - start >= 20_000);
+    assert_eq!(slaves.map().holding[3], 42);
+}
+
+#[test]
+fn test_serial_error() {
+    let line = Line::new(9600);
+    let (a, mut b) = line.ports();
+    let mut master = Master::new(a, line.timing());
+    // Fill the slave's end of the line
+    line.port(0).send(&[0; QUEUE_SIZE]).unwrap();
+    let request = Request::ReadCoils {
+        address: 0,
+        count: 1,
+    };
+    assert_eq!(master.send(SLAVE, &request), Err(Error::Serial(-1)));
+    assert!(!master.is_busy());
+    assert_eq!(b.read_byte(), Some(0));
+}
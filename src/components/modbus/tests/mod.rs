@@ -0,0 +1,7 @@
+pub mod crc;
+pub mod frame;
+pub mod master;
+pub mod pdu;
+pub mod serial;
+pub mod slave;
+pub mod timing;
@@ -0,0 +1,183 @@
+use modbus::frame::MAX_PDU;
+use modbus::pdu::*;
+
+fn round_trip(request: Request<'_>, expected: &[u8]) {
+    let mut out = [0; MAX_PDU];
+    let len = request.encode(&mut out).unwrap();
+    assert_eq!(&out[..len], expected);
+    assert_eq!(Request::parse(expected), Ok(request));
+}
+
+#[test]
+fn test_reads() {
+    round_trip(
+        Request::ReadCoils {
+            address: 0x13,
+            count: 0x25,
+        },
+        &[0x01, 0x00, 0x13, 0x00, 0x25],
+    );
+    round_trip(
+        Request::ReadDiscreteInputs {
+            address: 0xC4,
+            count: 0x16,
+        },
+        &[0x02, 0x00, 0xC4, 0x00, 0x16],
+    );
+    round_trip(
+        Request::ReadHoldingRegisters {
+            address: 0x6B,
+            count: 3,
+        },
+        &[0x03, 0x00, 0x6B, 0x00, 0x03],
+    );
+    round_trip(
+        Request::ReadInputRegisters {
+            address: 0x08,
+            count: 1,
+        },
+        &[0x04, 0x00, 0x08, 0x00, 0x01],
+    );
+}
+
+#[test]
+fn test_single_writes() {
+    round_trip(
+        Request::WriteSingleCoil {
+            address: 0xAC,
+            value: true,
+        },
+        &[0x05, 0x00, 0xAC, 0xFF, 0x00],
+    );
+    round_trip(
+        Request::WriteSingleCoil {
+            address: 0xAC,
+            value: false,
+        },
+        &[0x05, 0x00, 0xAC, 0x00, 0x00],
+    );
+    round_trip(
+        Request::WriteSingleRegister {
+            address: 0x01,
+            value: 0x0003,
+        },
+        &[0x06, 0x00, 0x01, 0x00, 0x03],
+    );
+    assert_eq!(
+        Request::parse(&[0x05, 0x00, 0xAC, 0x12, 0x34]),
+        Err(Exception::IllegalDataValue)
+    );
+}
+
+#[test]
+fn test_multiple_writes() {
+    let coils = [
+        true, false, true, true, false, false, true, true, true, false,
+    ];
+    round_trip(
+        Request::WriteMultipleCoils {
+            address: 0x13,
+            values: Bits::Values(&coils),
+        },
+        &[0x0F, 0x00, 0x13, 0x00, 0x0A, 0x02, 0xCD, 0x01],
+    );
+    round_trip(
+        Request::WriteMultipleRegisters {
+            address: 0x01,
+            values: Words::Values(&[0x000A, 0x0102]),
+        },
+        &[0x10, 0x00, 0x01, 0x00, 0x02, 0x04, 0x00, 0x0A, 0x01, 0x02],
+    );
+    round_trip(
+        Request::ReadWriteMultipleRegisters {
+            read_address: 0x03,
+            read_count: 6,
+            write_address: 0x0E,
+            values: Words::Values(&[0x00FF, 0x00FF, 0x00FF]),
+        },
+        &[
+            0x17, 0x00, 0x03, 0x00, 0x06, 0x00, 0x0E, 0x00, 0x03, 0x06, 0x00, 0xFF, 0x00, 0xFF,
+            0x00, 0xFF,
+        ],
+    );
+}
+
+#[test]
+fn test_bits_and_words() {
+    let bits = Bits::Packed {
+        bytes: &[0xCD, 0x01],
+        count: 10,
+    };
+    assert_eq!(bits.len(), 10);
+    assert_eq!(bits.get(0), Some(true));
+    assert_eq!(bits.get(1), Some(false));
+    assert_eq!(bits.get(8), Some(true));
+    assert_eq!(bits.get(10), None);
+    assert_eq!(bits.packed_len(), 2);
+
+    let words = Words::Bytes(&[0x12, 0x34, 0xAB, 0xCD]);
+    assert_eq!(words.len(), 2);
+    assert_eq!(words.iter().collect::<Vec<_>>(), [0x1234, 0xABCD]);
+    assert_eq!(words, Words::Values(&[0x1234, 0xABCD]));
+}
+
+#[test]
+fn test_limits() {
+    let mut out = [0; MAX_PDU];
+    let read = |count| Request::ReadHoldingRegisters { address: 0, count };
+    assert!(read(MAX_READ_REGISTERS).encode(&mut out).is_ok());
+    assert_eq!(read(MAX_READ_REGISTERS + 1).encode(&mut out), Err(-1));
+    assert_eq!(read(0).encode(&mut out), Err(-1));
+    assert_eq!(
+        read(MAX_READ_REGISTERS + 1).validate(),
+        Err(Exception::IllegalDataValue)
+    );
+    assert_eq!(
+        Request::ReadCoils {
+            address: 0xFFFF,
+            count: 2
+        }
+        .validate(),
+        Err(Exception::IllegalDataAddress)
+    );
+    let values = [0; MAX_READ_WRITE_REGISTERS as usize + 1];
+    let read_write = Request::ReadWriteMultipleRegisters {
+        read_address: 0,
+        read_count: 1,
+        write_address: 0,
+        values: Words::Values(&values),
+    };
+    assert_eq!(read_write.encode(&mut out), Err(-1));
+    assert_eq!(read(1).encode(&mut out[..4]), Err(-1));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(Request::parse(&[]), Err(Exception::IllegalFunction));
+    assert_eq!(
+        Request::parse(&[0x2B, 0x0E]),
+        Err(Exception::IllegalFunction)
+    );
+    assert_eq!(
+        Request::parse(&[0x03, 0x00, 0x00, 0x00]),
+        Err(Exception::IllegalDataValue)
+    );
+    // Byte count not matching the quantity
+    assert_eq!(
+        Request::parse(&[0x10, 0x00, 0x01, 0x00, 0x02, 0x02, 0x00, 0x0A]),
+        Err(Exception::IllegalDataValue)
+    );
+    assert_eq!(
+        Request::parse(&[0x0F, 0x00, 0x13, 0x00, 0x0A, 0x01, 0xCD]),
+        Err(Exception::IllegalDataValue)
+    );
+}
+
+#[test]
+fn test_exception_codes() {
+    for code in 1..=6 {
+        assert_eq!(Exception::from_code(code).code(), code);
+    }
+    assert_eq!(Exception::from_code(0x0B), Exception::Other(0x0B));
+    assert_eq!(Exception::from_code(0x02), Exception::IllegalDataAddress);
+}
@@ -0,0 +1,31 @@
+use modbus::serial::*;
+
+#[test]
+fn test_line() {
+    let line = Line::new(9600);
+    let (mut a, mut b) = line.ports();
+    a.send(&[1, 2, 3]).unwrap();
+    assert_eq!(line.now_us(), 3 * line.timing().char_us as u64);
+    assert_eq!(a.read_byte(), None);
+    assert_eq!(b.read_byte(), Some(1));
+    b.send(&[9]).unwrap();
+    assert_eq!(a.read_byte(), Some(9));
+    assert_eq!(b.read_byte(), Some(2));
+    assert_eq!(b.read_byte(), Some(3));
+    assert_eq!(b.read_byte(), None);
+
+    line.advance(500);
+    assert_eq!(a.now_us(), 4 * line.timing().char_us as u64 + 500);
+}
+
+#[test]
+fn test_line_overflow() {
+    let line = Line::new(9600);
+    let (mut a, mut b) = line.ports();
+    assert_eq!(a.send(&[0; QUEUE_SIZE]), Ok(()));
+    assert_eq!(a.send(&[1]), Err(-1));
+    assert_eq!(
+        (0..QUEUE_SIZE).filter_map(|_| b.read_byte()).count(),
+        QUEUE_SIZE
+    );
+}
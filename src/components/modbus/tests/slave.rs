@@ -0,0 +1,179 @@
+use modbus::frame::{self, MAX_ADU, MAX_PDU};
+use modbus::mock::Registers;
+use modbus::pdu::Exception;
+use modbus::serial::{Line, Port, Serial};
+use modbus::slave::*;
+
+fn serve(map: &mut Registers, request: &[u8]) -> Vec<u8> {
+    let mut out = [0; MAX_PDU];
+    let len = handle(map, request, &mut out);
+    out[..len].to_vec()
+}
+
+#[test]
+fn test_reads() {
+    let mut map = Registers::new();
+    map.coils[..10].copy_from_slice(&[
+        true, false, true, true, false, false, true, true, true, false,
+    ]);
+    map.holding[0x10..0x13].copy_from_slice(&[0x022B, 0x0000, 0x0064]);
+    map.input[8] = 0x000A;
+    map.discrete_inputs[3] = true;
+    assert_eq!(
+        serve(&mut map, &[0x01, 0x00, 0x00, 0x00, 0x0A]),
+        [0x01, 0x02, 0xCD, 0x01]
+    );
+    assert_eq!(
+        serve(&mut map, &[0x02, 0x00, 0x00, 0x00, 0x04]),
+        [0x02, 0x01, 0x08]
+    );
+    assert_eq!(
+        serve(&mut map, &[0x03, 0x00, 0x10, 0x00, 0x03]),
+        [0x03, 0x06, 0x02, 0x2B, 0x00, 0x00, 0x00, 0x64]
+    );
+    assert_eq!(
+        serve(&mut map, &[0x04, 0x00, 0x08, 0x00, 0x01]),
+        [0x04, 0x02, 0x00, 0x0A]
+    );
+}
+
+#[test]
+fn test_writes() {
+    let mut map = Registers::new();
+    let request = [0x05, 0x00, 0x02, 0xFF, 0x00];
+    assert_eq!(serve(&mut map, &request), request);
+    assert!(map.coils[2]);
+    let request = [0x06, 0x00, 0x01, 0x00, 0x03];
+    assert_eq!(serve(&mut map, &request), request);
+    assert_eq!(map.holding[1], 3);
+    assert_eq!(
+        serve(&mut map, &[0x0F, 0x00, 0x08, 0x00, 0x0A, 0x02, 0xCD, 0x01]),
+        [0x0F, 0x00, 0x08, 0x00, 0x0A]
+    );
+    assert_eq!(&map.coils[8..12], [true, false, true, true]);
+    assert!(map.coils[16]);
+    assert_eq!(
+        serve(
+            &mut map,
+            &[0x10, 0x00, 0x20, 0x00, 0x02, 0x04, 0x00, 0x0A, 0x01, 0x02]
+        ),
+        [0x10, 0x00, 0x20, 0x00, 0x02]
+    );
+    assert_eq!(&map.holding[0x20..0x22], [0x000A, 0x0102]);
+}
+
+#[test]
+fn test_read_write_writes_first() {
+    let mut map = Registers::new();
+    // Write 2 registers at 4 and read 3 from 3
+    assert_eq!(
+        serve(
+            &mut map,
+            &[
+                0x17, 0x00, 0x03, 0x00, 0x03, 0x00, 0x04, 0x00, 0x02, 0x04, 0x12, 0x34, 0x56, 0x78
+            ]
+        ),
+        [0x17, 0x06, 0x00, 0x00, 0x12, 0x34, 0x56, 0x78]
+    );
+}
+
+#[test]
+fn test_exceptions() {
+    let mut map = Registers::new();
+    assert_eq!(serve(&mut map, &[0x2B, 0x0E, 0x01, 0x00]), [0xAB, 0x01]);
+    assert_eq!(
+        serve(&mut map, &[0x03, 0x00, 0x3F, 0x00, 0x02]),
+        [0x83, 0x02]
+    );
+    assert_eq!(
+        serve(&mut map, &[0x03, 0x00, 0x00, 0x00, 0x7E]),
+        [0x83, 0x03]
+    );
+    assert_eq!(
+        serve(&mut map, &[0x05, 0x00, 0x00, 0x12, 0x34]),
+        [0x85, 0x03]
+    );
+}
+
+struct Inputs;
+
+impl RegisterMap for Inputs {
+    fn read_input_register(&self, address: u16) -> Result<u16, Exception> {
+        Ok(address)
+    }
+}
+
+#[test]
+fn test_default_methods() {
+    let mut map = Inputs;
+    let mut out = [0; MAX_PDU];
+    let len = handle(&mut map, &[0x04, 0x00, 0x07, 0x00, 0x01], &mut out);
+    assert_eq!(&out[..len], [0x04, 0x02, 0x00, 0x07]);
+    let len = handle(&mut map, &[0x06, 0x00, 0x07, 0x00, 0x01], &mut out);
+    assert_eq!(&out[..len], [0x86, 0x01]);
+}
+
+/// Send a request frame to `slave` and let it handle it. It times bytes
+/// as it reads them, so it reads the frame before the silence ending it.
+fn request(
+    line: &Line,
+    master: &mut Port<'_>,
+    slave: &mut Slave<Port<'_>, Registers>,
+    frame: &[u8],
+) -> Result<bool, i32> {
+    master.send(frame).unwrap();
+    assert_eq!(slave.poll(), Ok(false));
+    line.advance(line.timing().t3_5_us as u64);
+    slave.poll()
+}
+
+fn encode(address: u8, pdu: &[u8]) -> Vec<u8> {
+    let mut frame = [0; MAX_ADU];
+    let len = frame::encode(address, pdu, &mut frame).unwrap();
+    frame[..len].to_vec()
+}
+
+#[test]
+fn test_slave_over_line() {
+    let line = Line::new(19_200);
+    let (mut master, port) = line.ports();
+    let mut slave = Slave::new(port, 17, Registers::new(), line.timing());
+    slave.map_mut().holding[0x2B] = 0xAE41;
+
+    assert_eq!(slave.poll(), Ok(false));
+    let frame = encode(17, &[0x03, 0x00, 0x2B, 0x00, 0x01]);
+    assert_eq!(request(&line, &mut master, &mut slave, &frame), Ok(true));
+    let response: Vec<u8> = core::iter::from_fn(|| master.read_byte()).collect();
+    assert_eq!(
+        frame::decode(&response),
+        Ok((17, &[0x03, 0x02, 0xAE, 0x41][..]))
+    );
+
+    // Another slave's request goes unanswered
+    let frame = encode(18, &[0x03, 0x00, 0x2B, 0x00, 0x01]);
+    assert_eq!(request(&line, &mut master, &mut slave, &frame), Ok(false));
+    assert_eq!(master.read_byte(), None);
+}
+
+#[test]
+fn test_slave_broadcast_and_bad_crc() {
+    let line = Line::new(19_200);
+    let (mut master, port) = line.ports();
+    let mut slave = Slave::new(port, 17, Registers::new(), line.timing());
+
+    // Broadcast writes are carried out but not answered
+    let frame = encode(0, &[0x06, 0x00, 0x01, 0x12, 0x34]);
+    assert_eq!(request(&line, &mut master, &mut slave, &frame), Ok(true));
+    assert_eq!(slave.map().holding[1], 0x1234);
+    assert_eq!(master.read_byte(), None);
+
+    // Broadcast reads are ignored
+    let frame = encode(0, &[0x03, 0x00, 0x01, 0x00, 0x01]);
+    assert_eq!(request(&line, &mut master, &mut slave, &frame), Ok(false));
+
+    let mut frame = encode(17, &[0x06, 0x00, 0x01, 0x00, 0x00]);
+    *frame.last_mut().unwrap() ^= 0xFF;
+    assert_eq!(request(&line, &mut master, &mut slave, &frame), Ok(false));
+    assert_eq!(slave.map().holding[1], 0x1234);
+    assert_eq!(master.read_byte(), None);
+}
@@ -0,0 +1,18 @@
+use modbus::timing::*;
+
+#[test]
+fn test_timing() {
+    let timing = Timing::new(9600);
+    assert_eq!(timing.char_us, 1146);
+    assert_eq!(timing.t1_5_us, 1719);
+    assert_eq!(timing.t3_5_us, 4011);
+}
+
+#[test]
+fn test_fixed_above_19200() {
+    let timing = Timing::new(115_200);
+    assert_eq!(timing.char_us, 96);
+    assert_eq!(timing.t1_5_us, 750);
+    assert_eq!(timing.t3_5_us, 1750);
+    assert_eq!(Timing::new(19_200).t3_5_us, 573 * 7 / 2);
+}
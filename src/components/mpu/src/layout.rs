@@ -0,0 +1,92 @@
+//! The default protection of the firmware memory: flash read-only, RAM
+//! execute-never, and a no-access guard region right below the main stack
+//! so an overflow faults instead of overwriting the heap and statics.
+//!
+//! Regions with higher numbers take precedence where they overlap, so the
+//! guard carved out of RAM has the highest number.
+
+use crate::region::{Access, Attributes, Error, Memory, Region};
+
+/// Region numbers of the default protection
+pub const FLASH_REGION: u8 = 0;
+pub const RAM_REGION: u8 = 1;
+pub const STACK_GUARD_REGION: u8 = 2;
+
+/// Size of the stack guard. Once the stack hits the guard, the fault
+/// handlers run with the MPU off (HardFault) on the stack below it, so the
+/// guard also has to hold their frames.
+pub const DEFAULT_GUARD_SIZE: u32 = 512;
+
+/// The memory map, from the linker script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub flash_start: u32,
+    pub flash_size: u32,
+    pub ram_start: u32,
+    pub ram_size: u32,
+    /// Initial stack pointer, the stack grows down from here
+    pub stack_top: u32,
+    /// Bytes reserved for the main stack
+    pub stack_size: u32,
+    /// End of the statics and the heap, which the guard must stay above
+    pub data_end: u32,
+    pub guard_size: u32,
+}
+
+const FLASH: Attributes = Attributes {
+    access: Access::ReadOnly,
+    execute_never: false,
+    memory: Memory::WriteThrough,
+    shareable: false,
+};
+
+const RAM: Attributes = Attributes {
+    access: Access::ReadWrite,
+    execute_never: true,
+    memory: Memory::WriteBack,
+    shareable: false,
+};
+
+const GUARD: Attributes = Attributes {
+    access: Access::NoAccess,
+    execute_never: true,
+    memory: Memory::WriteBack,
+    shareable: false,
+};
+
+impl Layout {
+    /// The guard region: `guard_size` bytes ending at or below the bottom of
+    /// the stack, aligned to their size. Returns Err(-1) if the stack and
+    /// the guard do not fit between `data_end` and `stack_top`.
+    pub fn stack_guard(&self) -> Result<Region, Error> {
+        if !self.guard_size.is_power_of_two() {
+            return Err(-1);
+        }
+        let stack_bottom = self.stack_top.checked_sub(self.stack_size).ok_or(-1)?;
+        let guard_top = stack_bottom & !(self.guard_size - 1);
+        let base = guard_top.checked_sub(self.guard_size).ok_or(-1)?;
+        if base < self.data_end || base < self.ram_start {
+            return Err(-1);
+        }
+        let region = Region::new(base, self.guard_size, GUARD);
+        region.validate()?;
+        Ok(region)
+    }
+
+    /// The regions of the default protection with their numbers. Returns
+    /// Err(-1) if flash or RAM cannot be covered by one region each (a base
+    /// not aligned to the region size) or the guard does not fit.
+    pub fn regions(&self) -> Result<[(u8, Region); 3], Error> {
+        Ok([
+            (
+                FLASH_REGION,
+                Region::covering(self.flash_start, self.flash_size, FLASH)?,
+            ),
+            (
+                RAM_REGION,
+                Region::covering(self.ram_start, self.ram_size, RAM)?,
+            ),
+            (STACK_GUARD_REGION, self.stack_guard()?),
+        ])
+    }
+}
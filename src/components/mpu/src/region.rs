@@ -0,0 +1,198 @@
+//! Encoding of ARMv7-M MPU regions into RBAR/RASR register values.
+//!
+//! A region is a power-of-two size from 32 bytes, aligned to its size.
+//! Regions of 256 bytes and more are split into 8 equal subregions that
+//! can be disabled one by one, which lets one region cover a size that is
+//! not a power of two.
+
+/// Errors are i32 codes as in the firmware
+pub type Error = i32;
+
+/// Smallest region size
+pub const MIN_REGION_SIZE: u32 = 32;
+
+/// Smallest region size with subregions
+pub const MIN_SUBREGION_REGION_SIZE: u32 = 256;
+
+/// Subregions of a region
+pub const SUBREGIONS: u32 = 8;
+
+// RBAR: region base address, VALID selects the region number given in the
+// low bits instead of RNR
+pub const RBAR_ADDR_MASK: u32 = 0xFFFF_FFE0;
+pub const RBAR_VALID_POS: u32 = 4;
+pub const RBAR_VALID_MASK: u32 = 1 << RBAR_VALID_POS;
+pub const RBAR_REGION_POS: u32 = 0;
+pub const RBAR_REGION_WIDTH: u32 = 4;
+pub const RBAR_REGION_MASK: u32 = 0xF << RBAR_REGION_POS;
+
+// RASR: region attributes and size
+pub const RASR_XN_POS: u32 = 28;
+pub const RASR_XN_MASK: u32 = 1 << RASR_XN_POS;
+pub const RASR_AP_POS: u32 = 24;
+pub const RASR_AP_MASK: u32 = 0x7 << RASR_AP_POS;
+pub const RASR_TEX_POS: u32 = 19;
+pub const RASR_TEX_MASK: u32 = 0x7 << RASR_TEX_POS;
+pub const RASR_S_POS: u32 = 18;
+pub const RASR_S_MASK: u32 = 1 << RASR_S_POS;
+pub const RASR_C_POS: u32 = 17;
+pub const RASR_C_MASK: u32 = 1 << RASR_C_POS;
+pub const RASR_B_POS: u32 = 16;
+pub const RASR_B_MASK: u32 = 1 << RASR_B_POS;
+pub const RASR_SRD_POS: u32 = 8;
+pub const RASR_SRD_MASK: u32 = 0xFF << RASR_SRD_POS;
+pub const RASR_SIZE_POS: u32 = 1;
+pub const RASR_SIZE_MASK: u32 = 0x1F << RASR_SIZE_POS;
+pub const RASR_ENABLE_POS: u32 = 0;
+pub const RASR_ENABLE_MASK: u32 = 1;
+
+/// Access permissions, the AP field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Any access faults
+    NoAccess = 0b000,
+    /// Privileged read-write, unprivileged no access
+    PrivilegedReadWrite = 0b001,
+    /// Privileged read-write, unprivileged read-only
+    UnprivilegedReadOnly = 0b010,
+    /// Read-write for all
+    ReadWrite = 0b011,
+    /// Privileged read-only, unprivileged no access
+    PrivilegedReadOnly = 0b101,
+    /// Read-only for all
+    ReadOnly = 0b110,
+}
+
+/// Memory type, the TEX, C and B fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Memory {
+    StronglyOrdered,
+    Device,
+    /// Normal memory, write-through, no write allocate (flash)
+    WriteThrough,
+    /// Normal memory, write-back, no write allocate (SRAM)
+    WriteBack,
+}
+
+impl Memory {
+    /// (TEX, C, B)
+    fn bits(self) -> (u32, u32, u32) {
+        match self {
+            Memory::StronglyOrdered => (0, 0, 0),
+            Memory::Device => (0, 0, 1),
+            Memory::WriteThrough => (0, 1, 0),
+            Memory::WriteBack => (0, 1, 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attributes {
+    pub access: Access,
+    pub execute_never: bool,
+    pub memory: Memory,
+    pub shareable: bool,
+}
+
+/// One MPU region
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub base: u32,
+    pub size: u32,
+    pub attributes: Attributes,
+    /// Bit n disables subregion n, the n-th eighth of the region
+    pub disabled_subregions: u8,
+}
+
+impl Region {
+    /// A region of `size` bytes at `base`, all subregions enabled
+    pub fn new(base: u32, size: u32, attributes: Attributes) -> Self {
+        Self {
+            base,
+            size,
+            attributes,
+            disabled_subregions: 0,
+        }
+    }
+
+    /// The smallest region at `base` covering `len` bytes, with the
+    /// subregions past the end disabled; the end is rounded up to a whole
+    /// subregion. Returns Err(-1) if `base` is not aligned to that size or
+    /// `len` is 0 or above 2 GB.
+    pub fn covering(base: u32, len: u32, attributes: Attributes) -> Result<Self, Error> {
+        if len == 0 {
+            return Err(-1);
+        }
+        let size = len
+            .checked_next_power_of_two()
+            .ok_or(-1)?
+            .max(MIN_REGION_SIZE);
+        let mut region = Self::new(base, size, attributes);
+        if size >= MIN_SUBREGION_REGION_SIZE {
+            let subregion = size / SUBREGIONS;
+            let used = len.div_ceil(subregion);
+            region.disabled_subregions = !((1u32 << used) - 1) as u8;
+        }
+        region.validate()?;
+        Ok(region)
+    }
+
+    /// Returns Err(-1) unless the size is a power of two from 32 bytes,
+    /// the base is aligned to it, and subregions are only disabled from
+    /// 256 bytes
+    pub fn validate(&self) -> Result<(), Error> {
+        if !self.size.is_power_of_two() || self.size < MIN_REGION_SIZE {
+            return Err(-1);
+        }
+        if !self.base.is_multiple_of(self.size) {
+            return Err(-1);
+        }
+        if self.disabled_subregions != 0 && self.size < MIN_SUBREGION_REGION_SIZE {
+            return Err(-1);
+        }
+        Ok(())
+    }
+
+    /// RBAR value selecting region `number`. Returns Err(-1) for an
+    /// invalid region or number.
+    pub fn rbar(&self, number: u8) -> Result<u32, Error> {
+        self.validate()?;
+        if number as u32 >= 1 << RBAR_REGION_WIDTH {
+            return Err(-1);
+        }
+        Ok((self.base & RBAR_ADDR_MASK) | RBAR_VALID_MASK | number as u32)
+    }
+
+    /// RASR value enabling the region. Returns Err(-1) for an invalid
+    /// region.
+    pub fn rasr(&self) -> Result<u32, Error> {
+        self.validate()?;
+        let attributes = &self.attributes;
+        let (tex, c, b) = attributes.memory.bits();
+        let size_field = self.size.trailing_zeros() - 1;
+        Ok(((attributes.execute_never as u32) << RASR_XN_POS)
+            | ((attributes.access as u32) << RASR_AP_POS)
+            | (tex << RASR_TEX_POS)
+            | ((attributes.shareable as u32) << RASR_S_POS)
+            | (c << RASR_C_POS)
+            | (b << RASR_B_POS)
+            | ((self.disabled_subregions as u32) << RASR_SRD_POS)
+            | (size_field << RASR_SIZE_POS)
+            | RASR_ENABLE_MASK)
+    }
+
+    /// Whether `address` falls in an enabled part of the region
+    pub fn contains(&self, address: u32) -> bool {
+        let Some(offset) = address.checked_sub(self.base) else {
+            return false;
+        };
+        if offset >= self.size {
+            return false;
+        }
+        if self.size < MIN_SUBREGION_REGION_SIZE {
+            return true;
+        }
+        let subregion = offset / (self.size / SUBREGIONS);
+        self.disabled_subregions & (1 << subregion) == 0
+    }
+}
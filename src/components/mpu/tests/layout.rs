@@ -0,0 +1,77 @@
+use mpu::layout::*;
+use mpu::region::Access;
+
+/// STM32F407: 1 MB flash, 112 KB RAM, 4 KB stack
+fn f407() -> Layout {
+    Layout {
+        flash_start: 0x0800_0000,
+        flash_size: 1024 * 1024,
+        ram_start: 0x2000_0000,
+        ram_size: 112 * 1024,
+        stack_top: 0x2001_C000,
+        stack_size: 4 * 1024,
+        data_end: 0x2000_2000,
+        guard_size: DEFAULT_GUARD_SIZE,
+    }
+}
+
+#[test]
+fn test_default_regions() {
+    let regions = f407().regions().unwrap();
+    let numbers: Vec<_> = regions.iter().map(|(n, _)| *n).collect();
+    assert_eq!(numbers, [FLASH_REGION, RAM_REGION, STACK_GUARD_REGION]);
+
+    let flash = regions[0].1;
+    assert_eq!(flash.attributes.access, Access::ReadOnly);
+    assert!(!flash.attributes.execute_never);
+    let ram = regions[1].1;
+    assert_eq!(ram.attributes.access, Access::ReadWrite);
+    assert!(ram.attributes.execute_never);
+    for (_, region) in regions {
+        region.rasr().unwrap();
+    }
+}
+
+#[test]
+fn test_guard_below_stack() {
+    let guard = f407().stack_guard().unwrap();
+    assert_eq!(guard.base, 0x2001_B000 - DEFAULT_GUARD_SIZE);
+    assert_eq!(guard.size, DEFAULT_GUARD_SIZE);
+    assert_eq!(guard.attributes.access, Access::NoAccess);
+    // The guard lies within RAM and wins as it has the higher number
+    let ram = f407().regions().unwrap()[1].1;
+    assert!(ram.contains(guard.base));
+    const { assert!(STACK_GUARD_REGION > RAM_REGION) };
+}
+
+#[test]
+fn test_guard_aligned_down() {
+    let layout = Layout {
+        stack_size: 4 * 1024 + 100,
+        ..f407()
+    };
+    let guard = layout.stack_guard().unwrap();
+    assert_eq!(guard.base % DEFAULT_GUARD_SIZE, 0);
+    assert!(guard.base + guard.size <= layout.stack_top - layout.stack_size);
+}
+
+#[test]
+fn test_guard_must_fit_above_data() {
+    let layout = Layout {
+        data_end: 0x2001_AF00,
+        ..f407()
+    };
+    assert_eq!(layout.stack_guard(), Err(-1));
+    assert_eq!(layout.regions().map(|_| ()), Err(-1));
+
+    let layout = Layout {
+        stack_size: 0x3000_0000,
+        ..f407()
+    };
+    assert_eq!(layout.stack_guard(), Err(-1));
+    let layout = Layout {
+        guard_size: 300,
+        ..f407()
+    };
+    assert_eq!(layout.stack_guard(), Err(-1));
+}
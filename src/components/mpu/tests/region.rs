@@ -0,0 +1,98 @@
+use mpu::region::*;
+
+const RW: Attributes = Attributes {
+    access: Access::ReadWrite,
+    execute_never: true,
+    memory: Memory::WriteBack,
+    shareable: false,
+};
+
+#[test]
+fn test_rbar() {
+    let region = Region::new(0x2000_0000, 0x2_0000, RW);
+    assert_eq!(region.rbar(1), Ok(0x2000_0000 | RBAR_VALID_MASK | 1));
+    assert_eq!(region.rbar(16), Err(-1));
+}
+
+#[test]
+fn test_rasr_fields() {
+    // 128 KB: SIZE = log2(size) - 1 = 16
+    let rasr = Region::new(0x2000_0000, 0x2_0000, RW).rasr().unwrap();
+    assert_eq!((rasr & RASR_SIZE_MASK) >> RASR_SIZE_POS, 16);
+    assert_eq!((rasr & RASR_AP_MASK) >> RASR_AP_POS, 0b011);
+    assert_ne!(rasr & RASR_XN_MASK, 0);
+    assert_ne!(rasr & RASR_C_MASK, 0);
+    assert_ne!(rasr & RASR_B_MASK, 0);
+    assert_eq!(rasr & (RASR_TEX_MASK | RASR_S_MASK | RASR_SRD_MASK), 0);
+    assert_ne!(rasr & RASR_ENABLE_MASK, 0);
+
+    let flash = Attributes {
+        access: Access::ReadOnly,
+        execute_never: false,
+        memory: Memory::WriteThrough,
+        shareable: false,
+    };
+    // 1 MB read-only, executable, write-through
+    let rasr = Region::new(0x0800_0000, 0x10_0000, flash).rasr().unwrap();
+    assert_eq!(
+        rasr,
+        (0b110 << RASR_AP_POS) | RASR_C_MASK | (19 << RASR_SIZE_POS) | 1
+    );
+}
+
+#[test]
+fn test_smallest_region() {
+    let guard = Attributes {
+        access: Access::NoAccess,
+        ..RW
+    };
+    let rasr = Region::new(0x2000_1000, 32, guard).rasr().unwrap();
+    assert_eq!(
+        rasr,
+        RASR_XN_MASK | RASR_C_MASK | RASR_B_MASK | (4 << RASR_SIZE_POS) | 1
+    );
+}
+
+#[test]
+fn test_invalid_regions() {
+    // Not a power of two, too small, misaligned
+    assert_eq!(Region::new(0x2000_0000, 0x1_C000, RW).rasr(), Err(-1));
+    assert_eq!(Region::new(0x2000_0000, 16, RW).rasr(), Err(-1));
+    assert_eq!(Region::new(0x2000_0100, 0x400, RW).rasr(), Err(-1));
+    // Subregions need 256 bytes
+    let mut region = Region::new(0x2000_0000, 128, RW);
+    region.disabled_subregions = 0x80;
+    assert_eq!(region.validate(), Err(-1));
+}
+
+#[test]
+fn test_covering_with_subregions() {
+    // 112 KB of SRAM: a 128 KB region without its last 16 KB
+    let region = Region::covering(0x2000_0000, 112 * 1024, RW).unwrap();
+    assert_eq!(region.size, 128 * 1024);
+    assert_eq!(region.disabled_subregions, 0x80);
+    let rasr = region.rasr().unwrap();
+    assert_eq!((rasr & RASR_SRD_MASK) >> RASR_SRD_POS, 0x80);
+    assert!(region.contains(0x2001_BFFF));
+    assert!(!region.contains(0x2001_C000));
+    assert!(!region.contains(0x1FFF_FFFF));
+
+    // 96 KB, the end rounded up to whole 16 KB subregions
+    let region = Region::covering(0x2000_0000, 90 * 1024, RW).unwrap();
+    assert_eq!(region.disabled_subregions, 0xC0);
+
+    // Power of two sizes need no subregions
+    let region = Region::covering(0x2000_0000, 20 * 1024, RW).unwrap();
+    assert_eq!((region.size, region.disabled_subregions), (32 * 1024, 0xE0));
+    let region = Region::covering(0x0800_0000, 512 * 1024, RW).unwrap();
+    assert_eq!(region.disabled_subregions, 0);
+}
+
+#[test]
+fn test_covering_small_and_invalid() {
+    let region = Region::covering(0x2000_0000, 20, RW).unwrap();
+    assert_eq!((region.size, region.disabled_subregions), (32, 0));
+    assert_eq!(Region::covering(0x2000_0000, 0, RW), Err(-1));
+    assert_eq!(Region::covering(0x2000_4000, 112 * 1024, RW), Err(-1));
+    assert_eq!(Region::covering(0, u32::MAX, RW), Err(-1));
+}
@@ -0,0 +1,327 @@
+//! Scheduling policy of the preemptive kernel.
+//!
+//! The kernel only decides which thread runs: the highest priority ready
+//! thread, round-robin between threads of equal priority on every tick.
+//! Saving and restoring thread contexts is left to the architecture layer,
+//! which calls `schedule()` from its context switch.
+//!
+//! Threads waiting on a mutex record its owner, so the owner inherits the
+//! priority of its highest priority waiter, through chains of mutexes.
+
+pub type ThreadId = usize;
+
+/// Identity of the object a thread waits on, the address of the object
+pub type WaitObject = usize;
+
+pub type Error = i32;
+
+/// Returned when a wait ended by timeout
+pub const ERR_TIMEOUT: Error = -3;
+
+/// Priority of the idle thread, the lowest
+pub const IDLE_PRIORITY: u8 = 0;
+
+/// Returns true if `deadline` is reached at `now`, with tick wrap-around.
+pub fn reached(now: u32, deadline: u32) -> bool {
+    now.wrapping_sub(deadline) as i32 >= 0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadState {
+    /// Ready to run, or running if it is the current thread
+    Ready,
+    /// Sleeping until its deadline
+    Sleeping,
+    /// Waiting on an object, until signaled or its deadline
+    Blocked(WaitObject),
+    /// Returned from its entry function
+    Finished,
+}
+
+/// Why a thread last stopped waiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeReason {
+    Signaled,
+    Timeout,
+}
+
+#[derive(Clone, Copy)]
+struct Thread {
+    base_priority: u8,
+    /// Base priority raised by priority inheritance
+    priority: u8,
+    state: ThreadState,
+    deadline: Option<u32>,
+    /// Owner of the mutex this thread is blocked on
+    owner: Option<ThreadId>,
+    /// Position among threads of equal priority, lower runs first
+    order: u32,
+    wake: WakeReason,
+}
+
+impl Thread {
+    /// Returns true if this thread goes before one of `priority` and `order`
+    fn before(&self, priority: u8, order: u32) -> bool {
+        self.priority > priority
+            || (self.priority == priority && (self.order.wrapping_sub(order) as i32) < 0)
+    }
+}
+
+/// Thread table for up to `N` threads.
+pub struct Kernel<const N: usize> {
+    threads: [Option<Thread>; N],
+    current: Option<ThreadId>,
+    order: u32,
+}
+
+impl<const N: usize> Kernel<N> {
+    /// Create a kernel without threads.
+    pub const fn new() -> Self {
+        Self {
+            threads: [None; N],
+            current: None,
+            order: 0,
+        }
+    }
+
+    fn next_order(&mut self) -> u32 {
+        self.order = self.order.wrapping_add(1);
+        self.order
+    }
+
+    fn thread(&self, id: ThreadId) -> Option<&Thread> {
+        self.threads.get(id).and_then(|t| t.as_ref())
+    }
+
+    fn thread_mut(&mut self, id: ThreadId) -> Option<&mut Thread> {
+        self.threads.get_mut(id).and_then(|t| t.as_mut())
+    }
+
+    /// Add a ready thread. Slots of finished threads are reused.
+    /// Returns Err(-1) if the thread table is full.
+    pub fn spawn(&mut self, priority: u8) -> Result<ThreadId, Error> {
+        let current = self.current;
+        let id = (0..N)
+            .find(|&id| match &self.threads[id] {
+                None => true,
+                Some(t) => t.state == ThreadState::Finished && current != Some(id),
+            })
+            .ok_or(-1)?;
+        let order = self.next_order();
+        self.threads[id] = Some(Thread {
+            base_priority: priority,
+            priority,
+            state: ThreadState::Ready,
+            deadline: None,
+            owner: None,
+            order,
+            wake: WakeReason::Signaled,
+        });
+        Ok(id)
+    }
+
+    /// The running thread, `None` before the first `schedule()`
+    pub fn current(&self) -> Option<ThreadId> {
+        self.current
+    }
+
+    pub fn state(&self, id: ThreadId) -> Option<ThreadState> {
+        self.thread(id).map(|t| t.state)
+    }
+
+    /// Effective priority, including inherited priority
+    pub fn priority(&self, id: ThreadId) -> Option<u8> {
+        self.thread(id).map(|t| t.priority)
+    }
+
+    /// Why the thread last stopped waiting
+    pub fn wake_reason(&self, id: ThreadId) -> Option<WakeReason> {
+        self.thread(id).map(|t| t.wake)
+    }
+
+    /// Ready thread that should run: highest priority, then longest waiting
+    fn best_ready(&self) -> Option<ThreadId> {
+        self.first_in(ThreadState::Ready)
+    }
+
+    /// Thread in `state` that goes first by priority and order
+    fn first_in(&self, state: ThreadState) -> Option<ThreadId> {
+        let mut best: Option<(ThreadId, &Thread)> = None;
+        for (id, t) in self.threads.iter().enumerate() {
+            let Some(t) = t else { continue };
+            if t.state == state && best.is_none_or(|(_, b)| t.before(b.priority, b.order)) {
+                best = Some((id, t));
+            }
+        }
+        best.map(|(id, _)| id)
+    }
+
+    /// Returns true if `schedule()` would pick another thread.
+    pub fn needs_switch(&self) -> bool {
+        self.best_ready() != self.current
+    }
+
+    /// Pick the thread to run and make it current. Returns `None` if no
+    /// thread is ready.
+    pub fn schedule(&mut self) -> Option<ThreadId> {
+        let next = self.best_ready();
+        if next.is_some() {
+            self.current = next;
+        }
+        next
+    }
+
+    /// Move the current thread behind the ready threads of its priority.
+    pub fn yield_now(&mut self) {
+        let order = self.next_order();
+        if let Some(t) = self.current.and_then(|id| self.thread_mut(id)) {
+            t.order = order;
+        }
+    }
+
+    /// Handle a tick at `now`: wake threads whose deadline is reached and
+    /// rotate the current thread among ready threads of its priority.
+    /// Returns true if another thread should run.
+    pub fn tick(&mut self, now: u32) -> bool {
+        for id in 0..N {
+            let expired = self.thread(id).is_some_and(|t| {
+                matches!(t.state, ThreadState::Sleeping | ThreadState::Blocked(_))
+                    && t.deadline.is_some_and(|d| reached(now, d))
+            });
+            if expired {
+                let order = self.next_order();
+                let Some(t) = self.thread_mut(id) else {
+                    continue;
+                };
+                let owner = t.owner.take();
+                t.state = ThreadState::Ready;
+                t.deadline = None;
+                t.wake = WakeReason::Timeout;
+                t.order = order;
+                // The owner no longer inherits from this thread
+                if let Some(owner) = owner {
+                    self.update_priority(owner);
+                }
+            }
+        }
+
+        if let Some(current) = self.current
+            && let Some(t) = self.thread(current)
+            && t.state == ThreadState::Ready
+        {
+            let priority = t.priority;
+            let peer = self.threads.iter().enumerate().any(|(id, o)| {
+                id != current
+                    && o.is_some_and(|o| o.state == ThreadState::Ready && o.priority == priority)
+            });
+            if peer {
+                self.yield_now();
+            }
+        }
+        self.needs_switch()
+    }
+
+    /// Put the current thread to sleep until `deadline`.
+    /// Returns Err(-1) without a current thread.
+    pub fn sleep_until(&mut self, deadline: u32) -> Result<(), Error> {
+        let t = self.current.and_then(|id| self.thread_mut(id)).ok_or(-1)?;
+        t.state = ThreadState::Sleeping;
+        t.deadline = Some(deadline);
+        Ok(())
+    }
+
+    /// Block the current thread on `object` until woken or until
+    /// `deadline`, forever if `None`. Returns Err(-1) without a current thread.
+    pub fn block(&mut self, object: WaitObject, deadline: Option<u32>) -> Result<(), Error> {
+        let t = self.current.and_then(|id| self.thread_mut(id)).ok_or(-1)?;
+        t.state = ThreadState::Blocked(object);
+        t.deadline = deadline;
+        t.owner = None;
+        Ok(())
+    }
+
+    /// Block the current thread on a mutex held by `owner`, which inherits
+    /// the priority of the current thread.
+    pub fn block_on_mutex(
+        &mut self,
+        object: WaitObject,
+        owner: ThreadId,
+        deadline: Option<u32>,
+    ) -> Result<(), Error> {
+        self.block(object, deadline)?;
+        if let Some(t) = self.current.and_then(|id| self.thread_mut(id)) {
+            t.owner = Some(owner);
+        }
+        self.update_priority(owner);
+        Ok(())
+    }
+
+    /// Wake the highest priority thread blocked on `object`, the one
+    /// waiting longest among equals.
+    pub fn wake_one(&mut self, object: WaitObject) -> Option<ThreadId> {
+        let id = self.first_in(ThreadState::Blocked(object))?;
+        let order = self.next_order();
+        let t = self.thread_mut(id)?;
+        t.state = ThreadState::Ready;
+        t.deadline = None;
+        t.wake = WakeReason::Signaled;
+        t.order = order;
+        let owner = t.owner.take();
+        if let Some(owner) = owner {
+            self.update_priority(owner);
+        }
+        Some(id)
+    }
+
+    /// Hand the mutex `object` to `owner`: the remaining waiters now wait
+    /// for it, and the previous owner drops the priority it inherited.
+    pub fn set_mutex_owner(&mut self, object: WaitObject, previous: ThreadId, owner: ThreadId) {
+        for t in self.threads.iter_mut().flatten() {
+            if t.state == ThreadState::Blocked(object) {
+                t.owner = Some(owner);
+            }
+        }
+        self.update_priority(previous);
+        self.update_priority(owner);
+    }
+
+    /// Recompute the inherited priority of a thread and, if it changed, of
+    /// the owner of the mutex it waits on.
+    pub fn update_priority(&mut self, id: ThreadId) {
+        let mut id = id;
+        // A deadlock cycle would loop forever, a chain is at most N long
+        for _ in 0..N {
+            let inherited = self
+                .threads
+                .iter()
+                .flatten()
+                .filter(|t| t.owner == Some(id) && matches!(t.state, ThreadState::Blocked(_)))
+                .map(|t| t.priority)
+                .max()
+                .unwrap_or(IDLE_PRIORITY);
+            let Some(t) = self.thread_mut(id) else { return };
+            let priority = t.base_priority.max(inherited);
+            if priority == t.priority {
+                return;
+            }
+            t.priority = priority;
+            match (t.state, t.owner) {
+                (ThreadState::Blocked(_), Some(owner)) => id = owner,
+                _ => return,
+            }
+        }
+    }
+
+    /// Mark the current thread finished; it never runs again.
+    pub fn exit(&mut self) {
+        if let Some(t) = self.current.and_then(|id| self.thread_mut(id)) {
+            t.state = ThreadState::Finished;
+        }
+    }
+}
+
+impl<const N: usize> Default for Kernel<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
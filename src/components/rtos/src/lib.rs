@@ -0,0 +1,4 @@
+#![no_std]
+
+pub mod kernel;
+pub mod sync;
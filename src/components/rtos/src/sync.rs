@@ -0,0 +1,217 @@
+//! Mutexes, semaphores and message queues on top of the kernel.
+//!
+//! None of these block by themselves: when a thread has to wait they put
+//! it in the kernel's blocked state and return `Wait::Blocked`, and the
+//! caller switches context. Mutexes and semaphores are handed over to the
+//! thread woken by `unlock()` or `release()`, so a thread woken with
+//! `WakeReason::Signaled` owns them. Queue waiters retry when woken.
+
+use crate::kernel::{Error, Kernel, WaitObject};
+use data::queue::Queue;
+
+/// Outcome of an operation that may have to wait
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wait<T> {
+    Done(T),
+    /// The current thread is blocked and must switch out
+    Blocked,
+}
+
+/// Mutex with priority inheritance. Not recursive.
+pub struct Mutex {
+    owner: Option<usize>,
+}
+
+impl Mutex {
+    pub const fn new() -> Self {
+        Self { owner: None }
+    }
+
+    fn object(&self) -> WaitObject {
+        self as *const Self as WaitObject
+    }
+
+    /// Thread holding the mutex
+    pub fn owner(&self) -> Option<usize> {
+        self.owner
+    }
+
+    /// Take the mutex for the current thread if it is free.
+    /// Returns Err(-1) without a current thread or if it already holds it.
+    pub fn try_lock<const N: usize>(&mut self, kernel: &Kernel<N>) -> Result<bool, Error> {
+        let current = kernel.current().ok_or(-1)?;
+        match self.owner {
+            None => {
+                self.owner = Some(current);
+                Ok(true)
+            }
+            Some(owner) if owner == current => Err(-1),
+            Some(_) => Ok(false),
+        }
+    }
+
+    /// Take the mutex, or block the current thread until it is handed over
+    /// or `deadline` passes. The owner inherits the waiter's priority.
+    pub fn lock<const N: usize>(
+        &mut self,
+        kernel: &mut Kernel<N>,
+        deadline: Option<u32>,
+    ) -> Result<Wait<()>, Error> {
+        if self.try_lock(kernel)? {
+            return Ok(Wait::Done(()));
+        }
+        let owner = self.owner.ok_or(-1)?;
+        kernel.block_on_mutex(self.object(), owner, deadline)?;
+        Ok(Wait::Blocked)
+    }
+
+    /// Release the mutex, handing it to the highest priority waiter.
+    /// Returns Err(-1) if the current thread does not hold it.
+    pub fn unlock<const N: usize>(&mut self, kernel: &mut Kernel<N>) -> Result<(), Error> {
+        let current = kernel.current().ok_or(-1)?;
+        if self.owner != Some(current) {
+            return Err(-1);
+        }
+        self.owner = kernel.wake_one(self.object());
+        match self.owner {
+            Some(owner) => kernel.set_mutex_owner(self.object(), current, owner),
+            None => kernel.update_priority(current),
+        }
+        Ok(())
+    }
+}
+
+impl Default for Mutex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counting semaphore
+pub struct Semaphore {
+    count: u32,
+    max: u32,
+}
+
+impl Semaphore {
+    /// Create a semaphore holding `count` of at most `max` permits.
+    pub const fn new(count: u32, max: u32) -> Self {
+        Self { count, max }
+    }
+
+    fn object(&self) -> WaitObject {
+        self as *const Self as WaitObject
+    }
+
+    /// Permits currently available
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Take a permit if one is available.
+    pub fn try_acquire(&mut self) -> bool {
+        if self.count == 0 {
+            return false;
+        }
+        self.count -= 1;
+        true
+    }
+
+    /// Take a permit, or block the current thread until one is handed over
+    /// or `deadline` passes.
+    pub fn acquire<const N: usize>(
+        &mut self,
+        kernel: &mut Kernel<N>,
+        deadline: Option<u32>,
+    ) -> Result<Wait<()>, Error> {
+        if self.try_acquire() {
+            return Ok(Wait::Done(()));
+        }
+        kernel.block(self.object(), deadline)?;
+        Ok(Wait::Blocked)
+    }
+
+    /// Give a permit back, directly to a waiting thread if there is one.
+    /// Safe from interrupts. Returns Err(-1) if the semaphore is full.
+    pub fn release<const N: usize>(&mut self, kernel: &mut Kernel<N>) -> Result<(), Error> {
+        if kernel.wake_one(self.object()).is_some() {
+            return Ok(());
+        }
+        if self.count >= self.max {
+            return Err(-1);
+        }
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Fixed-size message queue of up to `Q` items
+pub struct MessageQueue<T, const Q: usize> {
+    queue: Queue<T, Q>,
+}
+
+impl<T, const Q: usize> MessageQueue<T, Q> {
+    pub const fn new() -> Self {
+        Self {
+            queue: Queue::new(),
+        }
+    }
+
+    /// Receivers wait on the queue address, senders on the next byte
+    fn receivers(&self) -> WaitObject {
+        self as *const Self as WaitObject
+    }
+
+    fn senders(&self) -> WaitObject {
+        self.receivers() + 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Append an item and wake a waiting receiver. Safe from interrupts.
+    /// Returns the item back if the queue is full.
+    pub fn try_send<const N: usize>(&mut self, kernel: &mut Kernel<N>, item: T) -> Result<(), T> {
+        self.queue.enqueue(item)?;
+        kernel.wake_one(self.receivers());
+        Ok(())
+    }
+
+    /// Take the oldest item and wake a waiting sender. Safe from interrupts.
+    pub fn try_receive<const N: usize>(&mut self, kernel: &mut Kernel<N>) -> Option<T> {
+        let item = self.queue.dequeue()?;
+        kernel.wake_one(self.senders());
+        Some(item)
+    }
+
+    /// Block the current thread until there may be room to send, or
+    /// `deadline` passes. Retry `try_send()` when woken.
+    pub fn wait_send<const N: usize>(
+        &self,
+        kernel: &mut Kernel<N>,
+        deadline: Option<u32>,
+    ) -> Result<(), Error> {
+        kernel.block(self.senders(), deadline)
+    }
+
+    /// Block the current thread until there may be an item, or `deadline`
+    /// passes. Retry `try_receive()` when woken.
+    pub fn wait_receive<const N: usize>(
+        &self,
+        kernel: &mut Kernel<N>,
+        deadline: Option<u32>,
+    ) -> Result<(), Error> {
+        kernel.block(self.receivers(), deadline)
+    }
+}
+
+impl<T, const Q: usize> Default for MessageQueue<T, Q> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,128 @@
+use rtos::kernel::*;
+
+#[test]
+fn test_highest_priority_runs_first() {
+    let mut kernel: Kernel<4> = Kernel::new();
+    let low = kernel.spawn(1).unwrap();
+    let high = kernel.spawn(5).unwrap();
+    assert_eq!(kernel.current(), None);
+    assert!(kernel.needs_switch());
+    assert_eq!(kernel.schedule(), Some(high));
+    assert_eq!(kernel.current(), Some(high));
+
+    // Sleeping hands the CPU to the lower priority thread
+    kernel.sleep_until(10).unwrap();
+    assert_eq!(kernel.schedule(), Some(low));
+}
+
+#[test]
+fn test_spawn_full_and_reuse_finished_slot() {
+    let mut kernel: Kernel<2> = Kernel::new();
+    let a = kernel.spawn(1).unwrap();
+    kernel.spawn(1).unwrap();
+    assert_eq!(kernel.spawn(1), Err(-1));
+
+    kernel.schedule();
+    assert_eq!(kernel.current(), Some(a));
+    kernel.exit();
+    // The running thread's slot is not reused until it switched out
+    assert_eq!(kernel.spawn(1), Err(-1));
+    kernel.schedule();
+    assert_eq!(kernel.spawn(3), Ok(a));
+}
+
+#[test]
+fn test_round_robin_on_tick() {
+    let mut kernel: Kernel<3> = Kernel::new();
+    let a = kernel.spawn(2).unwrap();
+    let b = kernel.spawn(2).unwrap();
+    kernel.spawn(1).unwrap();
+
+    assert_eq!(kernel.schedule(), Some(a));
+    assert!(kernel.tick(1));
+    assert_eq!(kernel.schedule(), Some(b));
+    assert!(kernel.tick(2));
+    assert_eq!(kernel.schedule(), Some(a));
+}
+
+#[test]
+fn test_alone_at_priority_is_not_rotated() {
+    let mut kernel: Kernel<2> = Kernel::new();
+    let a = kernel.spawn(2).unwrap();
+    kernel.spawn(1).unwrap();
+    assert_eq!(kernel.schedule(), Some(a));
+    assert!(!kernel.tick(1));
+}
+
+#[test]
+fn test_yield_moves_behind_peers() {
+    let mut kernel: Kernel<2> = Kernel::new();
+    let a = kernel.spawn(1).unwrap();
+    let b = kernel.spawn(1).unwrap();
+    assert_eq!(kernel.schedule(), Some(a));
+    kernel.yield_now();
+    assert_eq!(kernel.schedule(), Some(b));
+}
+
+#[test]
+fn test_sleep_wakes_at_deadline_and_preempts() {
+    let mut kernel: Kernel<2> = Kernel::new();
+    let high = kernel.spawn(5).unwrap();
+    let low = kernel.spawn(1).unwrap();
+    kernel.schedule();
+    kernel.sleep_until(10).unwrap();
+    assert_eq!(kernel.schedule(), Some(low));
+
+    assert!(!kernel.tick(9));
+    assert!(kernel.tick(10));
+    assert_eq!(kernel.wake_reason(high), Some(WakeReason::Timeout));
+    assert_eq!(kernel.schedule(), Some(high));
+}
+
+#[test]
+fn test_deadline_across_tick_wrap() {
+    assert!(reached(5, u32::MAX - 2));
+    assert!(!reached(u32::MAX - 2, 5));
+    assert!(reached(7, 7));
+}
+
+#[test]
+fn test_wake_one_picks_highest_priority_waiter() {
+    let mut kernel: Kernel<4> = Kernel::new();
+    let a = kernel.spawn(2).unwrap();
+    let b = kernel.spawn(3).unwrap();
+    let c = kernel.spawn(3).unwrap();
+    kernel.spawn(0).unwrap();
+
+    for _ in 0..3 {
+        kernel.schedule();
+        kernel.block(0x100, None).unwrap();
+    }
+    assert_eq!(kernel.state(a), Some(ThreadState::Blocked(0x100)));
+    assert_eq!(kernel.wake_one(0x100), Some(b));
+    assert_eq!(kernel.wake_one(0x100), Some(c));
+    assert_eq!(kernel.wake_one(0x100), Some(a));
+    assert_eq!(kernel.wake_one(0x100), None);
+    assert_eq!(kernel.wake_reason(a), Some(WakeReason::Signaled));
+}
+
+#[test]
+fn test_block_timeout() {
+    let mut kernel: Kernel<2> = Kernel::new();
+    let a = kernel.spawn(2).unwrap();
+    kernel.spawn(0).unwrap();
+    kernel.schedule();
+    kernel.block(0x100, Some(5)).unwrap();
+    kernel.tick(5);
+    assert_eq!(kernel.state(a), Some(ThreadState::Ready));
+    assert_eq!(kernel.wake_reason(a), Some(WakeReason::Timeout));
+    assert_eq!(kernel.wake_one(0x100), None);
+}
+
+#[test]
+fn test_no_current_thread_cannot_block() {
+    let mut kernel: Kernel<1> = Kernel::new();
+    assert_eq!(kernel.sleep_until(1), Err(-1));
+    assert_eq!(kernel.block(0x100, None), Err(-1));
+    assert_eq!(kernel.schedule(), None);
+}
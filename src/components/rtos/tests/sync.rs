@@ -0,0 +1,172 @@
+use rtos::kernel::*;
+use rtos::sync::*;
+
+/// Kernel with threads of the given priorities plus an idle thread,
+/// running the first one
+fn kernel_with(priorities: &[u8]) -> Kernel<8> {
+    let mut kernel = Kernel::new();
+    for &p in priorities {
+        kernel.spawn(p).unwrap();
+    }
+    kernel.spawn(IDLE_PRIORITY).unwrap();
+    kernel
+}
+
+/// Switch to thread `id`, as if the threads before it were blocked
+fn run(kernel: &mut Kernel<8>, id: ThreadId) {
+    while kernel.schedule() != Some(id) {
+        kernel.sleep_until(1000).unwrap();
+    }
+}
+
+#[test]
+fn test_mutex_lock_unlock() {
+    let mut kernel = kernel_with(&[1]);
+    let mut mutex = Mutex::new();
+    run(&mut kernel, 0);
+
+    assert_eq!(mutex.lock(&mut kernel, None), Ok(Wait::Done(())));
+    assert_eq!(mutex.owner(), Some(0));
+    // Not recursive
+    assert_eq!(mutex.lock(&mut kernel, None), Err(-1));
+    assert_eq!(mutex.unlock(&mut kernel), Ok(()));
+    assert_eq!(mutex.owner(), None);
+    assert_eq!(mutex.unlock(&mut kernel), Err(-1));
+}
+
+#[test]
+fn test_mutex_priority_inheritance() {
+    // 0: low, 1: medium, 2: high
+    let mut kernel = kernel_with(&[1, 2, 3]);
+    let mut mutex = Mutex::new();
+
+    kernel.schedule();
+    kernel.sleep_until(1000).unwrap();
+    kernel.schedule();
+    kernel.sleep_until(1000).unwrap();
+    assert_eq!(kernel.schedule(), Some(0));
+    assert_eq!(mutex.lock(&mut kernel, None), Ok(Wait::Done(())));
+
+    kernel.tick(1000);
+    assert_eq!(kernel.schedule(), Some(2));
+    assert_eq!(mutex.lock(&mut kernel, None), Ok(Wait::Blocked));
+
+    // The owner runs at the priority of the waiter, above the medium thread
+    assert_eq!(kernel.priority(0), Some(3));
+    assert_eq!(kernel.schedule(), Some(0));
+
+    // Unlocking hands the mutex over and drops the inherited priority
+    mutex.unlock(&mut kernel).unwrap();
+    assert_eq!(mutex.owner(), Some(2));
+    assert_eq!(kernel.priority(0), Some(1));
+    assert_eq!(kernel.wake_reason(2), Some(WakeReason::Signaled));
+    assert_eq!(kernel.schedule(), Some(2));
+}
+
+#[test]
+fn test_mutex_inheritance_through_chain() {
+    // 0 holds A, 1 holds B and waits on A, 2 waits on B
+    let mut kernel = kernel_with(&[1, 2, 5]);
+    let mut a = Mutex::new();
+    let mut b = Mutex::new();
+
+    run(&mut kernel, 0);
+    a.lock(&mut kernel, None).unwrap();
+    kernel.tick(1000);
+    run(&mut kernel, 1);
+    b.lock(&mut kernel, None).unwrap();
+    assert_eq!(a.lock(&mut kernel, None), Ok(Wait::Blocked));
+    assert_eq!(kernel.priority(0), Some(2));
+
+    kernel.tick(2000);
+    run(&mut kernel, 2);
+    assert_eq!(b.lock(&mut kernel, None), Ok(Wait::Blocked));
+    assert_eq!(kernel.priority(1), Some(5));
+    assert_eq!(kernel.priority(0), Some(5));
+}
+
+#[test]
+fn test_mutex_wait_timeout_drops_inheritance() {
+    let mut kernel = kernel_with(&[1, 4]);
+    let mut mutex = Mutex::new();
+    run(&mut kernel, 0);
+    mutex.lock(&mut kernel, None).unwrap();
+    kernel.tick(1000);
+    run(&mut kernel, 1);
+    mutex.lock(&mut kernel, Some(1010)).unwrap();
+    assert_eq!(kernel.priority(0), Some(4));
+
+    kernel.tick(1010);
+    assert_eq!(kernel.wake_reason(1), Some(WakeReason::Timeout));
+    assert_eq!(kernel.priority(0), Some(1));
+    assert_eq!(mutex.owner(), Some(0));
+}
+
+#[test]
+fn test_mutex_remaining_waiters_follow_new_owner() {
+    let mut kernel = kernel_with(&[1, 3, 2]);
+    let mut mutex = Mutex::new();
+    // Let the low priority thread 0 take the mutex first
+    run(&mut kernel, 0);
+    mutex.lock(&mut kernel, None).unwrap();
+    kernel.tick(1000);
+
+    assert_eq!(kernel.schedule(), Some(1));
+    assert_eq!(mutex.lock(&mut kernel, None), Ok(Wait::Blocked));
+    assert_eq!(kernel.schedule(), Some(0));
+    kernel.sleep_until(1010).unwrap();
+    assert_eq!(kernel.schedule(), Some(2));
+    assert_eq!(mutex.lock(&mut kernel, None), Ok(Wait::Blocked));
+    kernel.tick(1010);
+    assert_eq!(kernel.schedule(), Some(0));
+
+    mutex.unlock(&mut kernel).unwrap();
+    assert_eq!(mutex.owner(), Some(1));
+    assert_eq!(kernel.priority(0), Some(1));
+    assert_eq!(kernel.priority(1), Some(3));
+
+    // Thread 2 now waits for thread 1
+    assert_eq!(kernel.schedule(), Some(1));
+    mutex.unlock(&mut kernel).unwrap();
+    assert_eq!(mutex.owner(), Some(2));
+}
+
+#[test]
+fn test_semaphore_hand_over() {
+    let mut kernel = kernel_with(&[1]);
+    let mut sem = Semaphore::new(1, 1);
+    run(&mut kernel, 0);
+
+    assert_eq!(sem.acquire(&mut kernel, None), Ok(Wait::Done(())));
+    assert_eq!(sem.count(), 0);
+    assert_eq!(sem.acquire(&mut kernel, None), Ok(Wait::Blocked));
+
+    // Released from an interrupt: the permit goes to the waiter
+    sem.release(&mut kernel).unwrap();
+    assert_eq!(sem.count(), 0);
+    assert_eq!(kernel.state(0), Some(ThreadState::Ready));
+    sem.release(&mut kernel).unwrap();
+    assert_eq!(sem.count(), 1);
+    assert_eq!(sem.release(&mut kernel), Err(-1));
+}
+
+#[test]
+fn test_queue_wakes_receiver_and_sender() {
+    let mut kernel = kernel_with(&[2, 1]);
+    let mut queue: MessageQueue<u8, 1> = MessageQueue::new();
+    run(&mut kernel, 0);
+
+    assert_eq!(queue.try_receive(&mut kernel), None);
+    queue.wait_receive(&mut kernel, None).unwrap();
+    assert_eq!(kernel.schedule(), Some(1));
+
+    queue.try_send(&mut kernel, 7).unwrap();
+    assert_eq!(kernel.state(0), Some(ThreadState::Ready));
+    assert_eq!(queue.try_send(&mut kernel, 8), Err(8));
+    queue.wait_send(&mut kernel, None).unwrap();
+
+    assert_eq!(kernel.schedule(), Some(0));
+    assert_eq!(queue.try_receive(&mut kernel), Some(7));
+    assert_eq!(kernel.state(1), Some(ThreadState::Ready));
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,15 @@
+//! The interface implemented by every application run by the scheduler.
+
+use crate::scheduler::{Next, TaskConfig};
+
+pub trait App {
+    fn init(&mut self) -> Result<(), i32>;
+
+    /// Run one step and return when the app wants to run again.
+    fn loop_step(&mut self) -> Next;
+
+    /// Priority, period and deadline of the app
+    fn config(&self) -> TaskConfig {
+        TaskConfig::default()
+    }
+}
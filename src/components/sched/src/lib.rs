@@ -0,0 +1,7 @@
+#![no_std]
+
+pub mod app;
+pub mod power;
+pub mod registry;
+pub mod scheduler;
+pub mod tick;
@@ -0,0 +1,125 @@
+//! Choice of the low-power mode the core enters while no app is due.
+//!
+//! Deeper modes save more but cost more to leave: Stop halts the clocks of
+//! the peripherals and needs them restored, Standby loses RAM and wakes
+//! through a reset. Apps pick the deepest mode they accept and lock out
+//! deeper modes while they need peripherals running.
+
+use crate::scheduler::Error;
+
+/// Low-power modes, from lightest to deepest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PowerMode {
+    /// Core clock stopped, peripherals running; any interrupt wakes
+    Sleep,
+    /// All clocks stopped, RAM kept; EXTI lines and the RTC wake
+    Stop,
+    /// Powered down, RAM lost; the WKUP pin or the RTC wake with a reset
+    Standby,
+}
+
+/// Default shortest idle time worth entering Stop for, in ticks
+pub const DEFAULT_MIN_STOP_TICKS: u32 = 10;
+
+/// Picks the mode to enter from the time until the next scheduled work
+#[derive(Debug, Clone)]
+pub struct PowerPolicy {
+    deepest: PowerMode,
+    stop_locks: u16,
+    standby_locks: u16,
+    min_stop_ticks: u32,
+    timed_wakeup: bool,
+}
+
+impl PowerPolicy {
+    /// Sleep only, until a deeper mode is allowed
+    pub const fn new() -> Self {
+        Self {
+            deepest: PowerMode::Sleep,
+            stop_locks: 0,
+            standby_locks: 0,
+            min_stop_ticks: DEFAULT_MIN_STOP_TICKS,
+            timed_wakeup: false,
+        }
+    }
+
+    /// Allow modes down to `mode`
+    pub fn set_deepest(&mut self, mode: PowerMode) {
+        self.deepest = mode;
+    }
+
+    pub fn deepest(&self) -> PowerMode {
+        self.deepest
+    }
+
+    /// Keep the core out of `mode` and deeper modes until `unlock(mode)`.
+    /// Locks nest. Returns Err(-1) for Sleep, which is always allowed.
+    pub fn lock(&mut self, mode: PowerMode) -> Result<(), Error> {
+        let count = self.locks_mut(mode).ok_or(-1)?;
+        *count = count.checked_add(1).ok_or(-1)?;
+        Ok(())
+    }
+
+    /// Release a `lock(mode)`. Returns Err(-1) if `mode` is not locked.
+    pub fn unlock(&mut self, mode: PowerMode) -> Result<(), Error> {
+        let count = self.locks_mut(mode).ok_or(-1)?;
+        *count = count.checked_sub(1).ok_or(-1)?;
+        Ok(())
+    }
+
+    fn locks_mut(&mut self, mode: PowerMode) -> Option<&mut u16> {
+        match mode {
+            PowerMode::Sleep => None,
+            PowerMode::Stop => Some(&mut self.stop_locks),
+            PowerMode::Standby => Some(&mut self.standby_locks),
+        }
+    }
+
+    /// Shortest idle time, in ticks, worth the wakeup latency of Stop
+    pub fn set_min_stop_ticks(&mut self, ticks: u32) {
+        self.min_stop_ticks = ticks;
+    }
+
+    /// Whether a timer (the RTC) can wake the core from Stop in time for
+    /// scheduled work. Without it Stop is only entered with nothing
+    /// scheduled.
+    pub fn set_timed_wakeup(&mut self, available: bool) {
+        self.timed_wakeup = available;
+    }
+
+    pub fn timed_wakeup(&self) -> bool {
+        self.timed_wakeup
+    }
+
+    /// The deepest mode the locks allow
+    pub fn allowed(&self) -> PowerMode {
+        let mut mode = self.deepest;
+        if self.standby_locks > 0 {
+            mode = mode.min(PowerMode::Stop);
+        }
+        if self.stop_locks > 0 {
+            mode = mode.min(PowerMode::Sleep);
+        }
+        mode
+    }
+
+    /// The mode to enter for `idle` ticks until the next scheduled work,
+    /// `None` if nothing is scheduled. Standby is only chosen with nothing
+    /// scheduled, as leaving it restarts the firmware.
+    pub fn choose(&self, idle: Option<u32>) -> PowerMode {
+        let allowed = self.allowed();
+        match idle {
+            None => allowed,
+            Some(ticks) if self.timed_wakeup && ticks >= self.min_stop_ticks.max(1) => {
+                allowed.min(PowerMode::Stop)
+            }
+            Some(_) => PowerMode::Sleep,
+        }
+    }
+}
+
+impl Default for PowerPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,230 @@
+//! A fixed-capacity registry of apps with lifecycle states, run by the
+//! cooperative scheduler.
+//!
+//! The registry only borrows the apps, so it needs no heap: on target they
+//! live in statics. While an app runs it is taken out of the registry, so
+//! the registry can be locked only around the bookkeeping and not for the
+//! whole step.
+
+use crate::app::App;
+use crate::scheduler::{Error, Next, Poll, Scheduler, TaskId, TaskStats};
+use crate::tick::TickSource;
+
+/// Index of an app in the registry, also its scheduler task id
+pub type AppId = TaskId;
+
+/// Lifecycle of a registered app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppState {
+    /// Registered, `init()` has not run yet
+    Registered,
+    /// Initialized and scheduled
+    Initialized,
+    /// `init()` returned the error code, the app is not scheduled
+    Failed(i32),
+    /// Stopped at runtime, the app is not scheduled until restarted
+    Stopped,
+}
+
+struct Entry<'a> {
+    name: &'static str,
+    /// `None` while the app is running
+    app: Option<&'a mut dyn App>,
+    state: AppState,
+}
+
+/// Registry for up to `N` apps.
+pub struct Registry<'a, const N: usize> {
+    entries: [Option<Entry<'a>>; N],
+    len: usize,
+    scheduler: Scheduler<N>,
+}
+
+impl<'a, const N: usize> Registry<'a, N> {
+    /// Create an empty registry.
+    pub const fn new() -> Self {
+        Self {
+            entries: [const { None }; N],
+            len: 0,
+            scheduler: Scheduler::new(),
+        }
+    }
+
+    /// Number of registered apps
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no app is registered
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Register an app under `name`. It is not scheduled until initialized.
+    /// Returns Err(-1) if the registry is full.
+    pub fn register(&mut self, name: &'static str, app: &'a mut dyn App) -> Result<AppId, Error> {
+        let id = self.scheduler.add(app.config(), 0)?;
+        self.scheduler.suspend(id)?;
+        self.entries[id] = Some(Entry {
+            name,
+            app: Some(app),
+            state: AppState::Registered,
+        });
+        self.len += 1;
+        Ok(id)
+    }
+
+    fn entry(&self, id: AppId) -> Option<&Entry<'a>> {
+        self.entries.get(id).and_then(|e| e.as_ref())
+    }
+
+    fn entry_mut(&mut self, id: AppId) -> Option<&mut Entry<'a>> {
+        self.entries.get_mut(id).and_then(|e| e.as_mut())
+    }
+
+    /// Name an app was registered with
+    pub fn name(&self, id: AppId) -> Option<&'static str> {
+        self.entry(id).map(|e| e.name)
+    }
+
+    /// Find an app by name
+    pub fn find(&self, name: &str) -> Option<AppId> {
+        (0..self.len).find(|&id| self.name(id) == Some(name))
+    }
+
+    /// Lifecycle state of an app
+    pub fn state(&self, id: AppId) -> Option<AppState> {
+        self.entry(id).map(|e| e.state)
+    }
+
+    /// Run-time statistics of an app
+    pub fn stats(&self, id: AppId) -> Option<TaskStats> {
+        self.scheduler.stats(id)
+    }
+
+    /// Take an app out of the registry to run its `init()`, so the caller
+    /// can run it without holding the registry locked. It must be handed
+    /// back with `complete_init()`. Returns Err(-1) for an unknown app,
+    /// Err(-2) if the app is already out of the registry.
+    pub fn take_init(&mut self, id: AppId) -> Result<&'a mut dyn App, Error> {
+        let entry = self.entry_mut(id).ok_or(-1)?;
+        entry.app.take().ok_or(-2)
+    }
+
+    /// Hand back an app taken with `take_init()` with the `result` of its
+    /// `init()`, and schedule it at `now` if that succeeded. Returns the
+    /// error of its `init()`.
+    pub fn complete_init(
+        &mut self,
+        id: AppId,
+        app: &'a mut dyn App,
+        result: Result<(), i32>,
+        now: u32,
+    ) -> Result<(), Error> {
+        let entry = self.entry_mut(id).ok_or(-1)?;
+        entry.app = Some(app);
+        match result {
+            Ok(()) => {
+                entry.state = AppState::Initialized;
+                self.scheduler.resume(id, now)
+            }
+            Err(code) => {
+                entry.state = AppState::Failed(code);
+                self.scheduler.suspend(id)?;
+                Err(code)
+            }
+        }
+    }
+
+    /// Initialize one app and schedule it at `now` if that succeeds.
+    fn init_one(&mut self, id: AppId, now: u32) -> Result<(), Error> {
+        let app = self.take_init(id)?;
+        let result = app.init();
+        self.complete_init(id, app, result, now)
+    }
+
+    /// Initialize every registered app. An app that fails is marked
+    /// `Failed` and the others are still initialized; returns Err with the
+    /// number of failed apps.
+    pub fn init_all(&mut self, now: u32) -> Result<(), usize> {
+        let mut failed = 0;
+        for id in 0..self.len {
+            if self.state(id) == Some(AppState::Registered) && self.init_one(id, now).is_err() {
+                failed += 1;
+            }
+        }
+        if failed == 0 { Ok(()) } else { Err(failed) }
+    }
+
+    /// Stop an app; it keeps its state but is no longer scheduled.
+    /// Returns Err(-1) for an unknown app.
+    pub fn stop(&mut self, id: AppId) -> Result<(), Error> {
+        let entry = self.entry_mut(id).ok_or(-1)?;
+        entry.state = AppState::Stopped;
+        self.scheduler.suspend(id)
+    }
+
+    /// Initialize a stopped or failed app again and schedule it at `now`.
+    /// Returns Err(-1) for an unknown app, Err(-2) if the app is running,
+    /// or the error of its `init()`.
+    pub fn restart(&mut self, id: AppId, now: u32) -> Result<(), Error> {
+        self.init_one(id, now)
+    }
+
+    /// Signal events to apps waiting with `Next::Event`
+    pub fn signal(&mut self, events: u32) {
+        self.scheduler.signal(events);
+    }
+
+    /// Take the next due app out of the registry to run it. It must be
+    /// handed back with `complete()`.
+    pub fn take_due(&mut self, now: u32) -> Option<(AppId, &'a mut dyn App)> {
+        let id = self.scheduler.next_ready(now)?;
+        let app = self.entry_mut(id)?.app.take()?;
+        Some((id, app))
+    }
+
+    /// Hand back an app taken with `take_due()` after a step that ran from
+    /// `start` to `end`. An app stopped during its step stays stopped.
+    pub fn complete(
+        &mut self,
+        id: AppId,
+        app: &'a mut dyn App,
+        start: u32,
+        end: u32,
+        next: Next,
+    ) -> Result<(), Error> {
+        let entry = self.entry_mut(id).ok_or(-1)?;
+        entry.app = Some(app);
+        let stopped = entry.state == AppState::Stopped;
+        let result = self.scheduler.complete(id, start, end, next);
+        if stopped {
+            self.scheduler.suspend(id)?;
+        }
+        result
+    }
+
+    /// Ticks until the next app is due, `None` if none is scheduled in time
+    pub fn idle_ticks(&self, now: u32) -> Option<u32> {
+        self.scheduler.idle_ticks(now)
+    }
+
+    /// Run at most one due app.
+    pub fn poll(&mut self, clock: &impl TickSource) -> Result<Poll, Error> {
+        let start = clock.ticks();
+        match self.take_due(start) {
+            Some((id, app)) => {
+                let next = app.loop_step();
+                self.complete(id, app, start, clock.ticks(), next)?;
+                Ok(Poll::Ran(id))
+            }
+            None => Ok(Poll::Idle(self.idle_ticks(start))),
+        }
+    }
+}
+
+impl<const N: usize> Default for Registry<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
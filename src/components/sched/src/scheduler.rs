@@ -0,0 +1,300 @@
+//! A cooperative, fixed-capacity scheduler.
+//!
+//! Tasks are identified by their index and run to completion; each run
+//! returns a [`Next`] telling the scheduler when the task wants to run
+//! again. Among due tasks the highest priority runs first, ties go to the
+//! task that has been due the longest.
+
+use crate::tick::{TickSource, reached, ticks_until};
+
+/// Index of a task in the scheduler
+pub type TaskId = usize;
+
+/// When a task wants to run again, returned from every run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Next {
+    /// One period after the previous release (requires a period)
+    Periodic,
+    /// After the given number of ticks from now
+    After(u32),
+    /// At an absolute tick
+    At(u32),
+    /// When any event in the mask is signaled
+    Event(u32),
+    /// As soon as possible, after other due tasks of the same priority
+    Yield,
+    /// Never, the task is finished
+    Done,
+}
+
+/// Static scheduling parameters of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskConfig {
+    /// Higher runs first
+    pub priority: u8,
+    /// Release period in ticks for `Next::Periodic`, 0 if not periodic
+    pub period: u32,
+    /// Ticks after release by which a run must have finished, 0 for none.
+    /// Defaults to the period for periodic tasks.
+    pub deadline: u32,
+}
+
+impl TaskConfig {
+    /// A periodic task with its deadline at the end of the period
+    pub const fn periodic(priority: u8, period: u32) -> Self {
+        Self {
+            priority,
+            period,
+            deadline: period,
+        }
+    }
+}
+
+/// Run-time statistics of one task, in ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskStats {
+    /// Number of completed runs
+    pub runs: u32,
+    /// Sum of all run times
+    pub total_ticks: u32,
+    /// Longest single run
+    pub max_ticks: u32,
+    /// Runs that finished after their deadline
+    pub overruns: u32,
+    /// Periodic releases dropped because the task fell a full period behind
+    pub skipped: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wait {
+    /// Due at the release tick
+    Until(u32),
+    /// Waiting for any event of the mask
+    Event(u32),
+    /// Never runs again
+    Done,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Task {
+    config: TaskConfig,
+    wait: Wait,
+    /// Tick the current (or last) run was released at
+    release: u32,
+    stats: TaskStats,
+}
+
+/// Outcome of one scheduler poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Poll {
+    /// A task ran
+    Ran(TaskId),
+    /// Nothing was due; the next task is due in the given number of ticks,
+    /// or `None` if every task waits for an event or is done
+    Idle(Option<u32>),
+}
+
+/// Errors reported by the scheduler, as i32 codes like the drivers
+pub type Error = i32;
+
+/// Cooperative scheduler for up to `N` tasks.
+pub struct Scheduler<const N: usize> {
+    tasks: [Option<Task>; N],
+    len: usize,
+    /// Events signaled but not yet consumed by a waiting task
+    pending: u32,
+}
+
+impl<const N: usize> Scheduler<N> {
+    /// Create a scheduler without tasks.
+    pub const fn new() -> Self {
+        Self {
+            tasks: [None; N],
+            len: 0,
+            pending: 0,
+        }
+    }
+
+    /// Number of tasks added
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no task was added
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add a task that is due immediately at `now`.
+    /// Returns Err(-1) if the scheduler is full.
+    pub fn add(&mut self, config: TaskConfig, now: u32) -> Result<TaskId, Error> {
+        if self.len == N {
+            return Err(-1);
+        }
+        let id = self.len;
+        self.tasks[id] = Some(Task {
+            config,
+            wait: Wait::Until(now),
+            release: now,
+            stats: TaskStats::default(),
+        });
+        self.len += 1;
+        Ok(id)
+    }
+
+    /// Signal events; tasks waiting on any of these bits become due.
+    pub fn signal(&mut self, events: u32) {
+        self.pending |= events;
+    }
+
+    /// Statistics of a task
+    pub fn stats(&self, id: TaskId) -> Option<TaskStats> {
+        self.task(id).map(|t| t.stats)
+    }
+
+    /// Configuration of a task
+    pub fn config(&self, id: TaskId) -> Option<TaskConfig> {
+        self.task(id).map(|t| t.config)
+    }
+
+    fn task(&self, id: TaskId) -> Option<&Task> {
+        self.tasks.get(id).and_then(|t| t.as_ref())
+    }
+
+    /// Park a task until `resume()`, whatever it was waiting for.
+    /// Returns Err(-1) for an unknown task.
+    pub fn suspend(&mut self, id: TaskId) -> Result<(), Error> {
+        let task = self.tasks.get_mut(id).and_then(|t| t.as_mut()).ok_or(-1)?;
+        task.wait = Wait::Done;
+        Ok(())
+    }
+
+    /// Make a task due at `now`. Returns Err(-1) for an unknown task.
+    pub fn resume(&mut self, id: TaskId, now: u32) -> Result<(), Error> {
+        let task = self.tasks.get_mut(id).and_then(|t| t.as_mut()).ok_or(-1)?;
+        task.wait = Wait::Until(now);
+        task.release = now;
+        Ok(())
+    }
+
+    /// Wake tasks waiting on pending events, consuming the bits they match.
+    fn dispatch_events(&mut self, now: u32) {
+        if self.pending == 0 {
+            return;
+        }
+        let mut consumed = 0;
+        for task in self.tasks.iter_mut().flatten() {
+            if let Wait::Event(mask) = task.wait
+                && mask & self.pending != 0
+            {
+                consumed |= mask & self.pending;
+                task.wait = Wait::Until(now);
+                task.release = now;
+            }
+        }
+        self.pending &= !consumed;
+    }
+
+    /// The task to run at `now`: highest priority, then longest overdue.
+    pub fn next_ready(&mut self, now: u32) -> Option<TaskId> {
+        self.dispatch_events(now);
+        let mut best: Option<(TaskId, u8, u32)> = None;
+        for (id, task) in self.tasks.iter().enumerate() {
+            let Some(task) = task else { continue };
+            let Wait::Until(due) = task.wait else {
+                continue;
+            };
+            if !reached(now, due) {
+                continue;
+            }
+            let overdue = now.wrapping_sub(due);
+            let better = match best {
+                None => true,
+                Some((_, prio, late)) => {
+                    task.config.priority > prio || (task.config.priority == prio && overdue > late)
+                }
+            };
+            if better {
+                best = Some((id, task.config.priority, overdue));
+            }
+        }
+        best.map(|(id, _, _)| id)
+    }
+
+    /// Ticks until the next task is due, `None` if nothing is scheduled in
+    /// time (every task waits for an event or is done).
+    pub fn idle_ticks(&self, now: u32) -> Option<u32> {
+        self.tasks
+            .iter()
+            .flatten()
+            .filter_map(|t| match t.wait {
+                Wait::Until(due) => Some(ticks_until(now, due)),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Record a run of `id` from `start` to `end` and schedule its next
+    /// release. Returns Err(-1) for an unknown task and Err(-2) for
+    /// `Next::Periodic` on a task without a period (it is then finished).
+    pub fn complete(&mut self, id: TaskId, start: u32, end: u32, next: Next) -> Result<(), Error> {
+        let task = self.tasks.get_mut(id).and_then(|t| t.as_mut()).ok_or(-1)?;
+
+        let run = end.wrapping_sub(start);
+        task.stats.runs = task.stats.runs.wrapping_add(1);
+        task.stats.total_ticks = task.stats.total_ticks.wrapping_add(run);
+        task.stats.max_ticks = task.stats.max_ticks.max(run);
+        let deadline = task.config.deadline;
+        if deadline != 0 && end.wrapping_sub(task.release) > deadline {
+            task.stats.overruns += 1;
+        }
+
+        let (wait, result) = match next {
+            Next::Periodic if task.config.period == 0 => (Wait::Done, Err(-2)),
+            Next::Periodic => {
+                let period = task.config.period;
+                let mut release = task.release.wrapping_add(period);
+                // Drop releases that are already a full period late
+                while reached(end, release.wrapping_add(period)) {
+                    release = release.wrapping_add(period);
+                    task.stats.skipped += 1;
+                }
+                (Wait::Until(release), Ok(()))
+            }
+            Next::After(ticks) => (Wait::Until(end.wrapping_add(ticks)), Ok(())),
+            Next::At(tick) => (Wait::Until(tick), Ok(())),
+            Next::Event(mask) => (Wait::Event(mask), Ok(())),
+            Next::Yield => (Wait::Until(end), Ok(())),
+            Next::Done => (Wait::Done, Ok(())),
+        };
+        task.wait = wait;
+        if let Wait::Until(release) = wait {
+            task.release = release;
+        }
+        result
+    }
+
+    /// Run at most one due task. `run` is called with the task id and
+    /// returns when it wants to run again.
+    pub fn poll(
+        &mut self,
+        clock: &impl TickSource,
+        mut run: impl FnMut(TaskId) -> Next,
+    ) -> Result<Poll, Error> {
+        let start = clock.ticks();
+        match self.next_ready(start) {
+            Some(id) => {
+                let next = run(id);
+                self.complete(id, start, clock.ticks(), next)?;
+                Ok(Poll::Ran(id))
+            }
+            None => Ok(Poll::Idle(self.idle_ticks(start))),
+        }
+    }
+}
+
+impl<const N: usize> Default for Scheduler<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,21 @@
+//! Tick arithmetic on a free-running, wrapping `u32` millisecond counter.
+
+/// A source of the current tick count (SysTick on target, a mock on host).
+pub trait TickSource {
+    fn ticks(&self) -> u32;
+}
+
+/// Returns true if tick `a` is at or after tick `b`, valid while the two
+/// are less than 2^31 ticks apart.
+pub fn reached(a: u32, b: u32) -> bool {
+    a.wrapping_sub(b) as i32 >= 0
+}
+
+/// Ticks from `now` until `due`, 0 if `due` has already been reached
+pub fn ticks_until(now: u32, due: u32) -> u32 {
+    if reached(now, due) {
+        0
+    } else {
+        due.wrapping_sub(now)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod power;
+pub mod registry;
+pub mod scheduler;
+pub mod tick;
@@ -0,0 +1,80 @@
+use sched::power::*;
+
+fn policy(deepest: PowerMode) -> PowerPolicy {
+    let mut policy = PowerPolicy::new();
+    policy.set_deepest(deepest);
+    policy.set_timed_wakeup(true);
+    policy
+}
+
+#[test]
+fn test_sleep_by_default() {
+    let policy = PowerPolicy::new();
+    assert_eq!(policy.deepest(), PowerMode::Sleep);
+    assert_eq!(policy.choose(None), PowerMode::Sleep);
+    assert_eq!(policy.choose(Some(1000)), PowerMode::Sleep);
+}
+
+#[test]
+fn test_standby_only_with_nothing_scheduled() {
+    let policy = policy(PowerMode::Standby);
+    assert_eq!(policy.choose(None), PowerMode::Standby);
+    assert_eq!(policy.choose(Some(60_000)), PowerMode::Stop);
+}
+
+#[test]
+fn test_short_idle_sleeps() {
+    let mut policy = policy(PowerMode::Stop);
+    assert_eq!(policy.choose(Some(0)), PowerMode::Sleep);
+    assert_eq!(
+        policy.choose(Some(DEFAULT_MIN_STOP_TICKS - 1)),
+        PowerMode::Sleep
+    );
+    assert_eq!(policy.choose(Some(DEFAULT_MIN_STOP_TICKS)), PowerMode::Stop);
+
+    policy.set_min_stop_ticks(100);
+    assert_eq!(policy.choose(Some(50)), PowerMode::Sleep);
+    assert_eq!(policy.choose(Some(100)), PowerMode::Stop);
+}
+
+#[test]
+fn test_stop_needs_timed_wakeup_when_scheduled() {
+    let mut policy = policy(PowerMode::Stop);
+    policy.set_timed_wakeup(false);
+    assert_eq!(policy.choose(Some(1000)), PowerMode::Sleep);
+    assert_eq!(policy.choose(None), PowerMode::Stop);
+}
+
+#[test]
+fn test_locks_cap_the_mode() {
+    let mut policy = policy(PowerMode::Standby);
+    policy.lock(PowerMode::Standby).unwrap();
+    assert_eq!(policy.allowed(), PowerMode::Stop);
+    assert_eq!(policy.choose(None), PowerMode::Stop);
+
+    policy.lock(PowerMode::Stop).unwrap();
+    assert_eq!(policy.choose(None), PowerMode::Sleep);
+
+    policy.unlock(PowerMode::Stop).unwrap();
+    policy.unlock(PowerMode::Standby).unwrap();
+    assert_eq!(policy.choose(None), PowerMode::Standby);
+}
+
+#[test]
+fn test_locks_nest() {
+    let mut policy = policy(PowerMode::Stop);
+    policy.lock(PowerMode::Stop).unwrap();
+    policy.lock(PowerMode::Stop).unwrap();
+    policy.unlock(PowerMode::Stop).unwrap();
+    assert_eq!(policy.choose(None), PowerMode::Sleep);
+    policy.unlock(PowerMode::Stop).unwrap();
+    assert_eq!(policy.choose(None), PowerMode::Stop);
+}
+
+#[test]
+fn test_lock_errors() {
+    let mut policy = PowerPolicy::new();
+    assert_eq!(policy.lock(PowerMode::Sleep), Err(-1));
+    assert_eq!(policy.unlock(PowerMode::Sleep), Err(-1));
+    assert_eq!(policy.unlock(PowerMode::Stop), Err(-1));
+}
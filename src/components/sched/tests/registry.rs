@@ -0,0 +1,213 @@
+use core::cell::Cell;
+use sched::app::App;
+use sched::registry::*;
+use sched::scheduler::{Next, Poll, TaskConfig};
+use sched::tick::TickSource;
+
+struct MockClock {
+    now: Cell<u32>,
+}
+
+impl TickSource for MockClock {
+    fn ticks(&self) -> u32 {
+        self.now.get()
+    }
+}
+
+/// App counting its calls; fails `init()` with `init_error` if set
+struct CountingApp {
+    init_error: Option<i32>,
+    inits: u32,
+    steps: u32,
+    next: Next,
+    priority: u8,
+}
+
+impl CountingApp {
+    fn new(next: Next) -> Self {
+        Self {
+            init_error: None,
+            inits: 0,
+            steps: 0,
+            next,
+            priority: 0,
+        }
+    }
+}
+
+impl App for CountingApp {
+    fn init(&mut self) -> Result<(), i32> {
+        self.inits += 1;
+        match self.init_error {
+            Some(code) => Err(code),
+            None => Ok(()),
+        }
+    }
+
+    fn loop_step(&mut self) -> Next {
+        self.steps += 1;
+        self.next
+    }
+
+    fn config(&self) -> TaskConfig {
+        TaskConfig {
+            priority: self.priority,
+            ..Default::default()
+        }
+    }
+}
+
+fn clock() -> MockClock {
+    MockClock { now: Cell::new(0) }
+}
+
+#[test]
+fn test_register_and_find_by_name() {
+    let mut a = CountingApp::new(Next::Done);
+    let mut b = CountingApp::new(Next::Done);
+    let mut registry: Registry<2> = Registry::new();
+
+    let ida = registry.register("a", &mut a).unwrap();
+    let idb = registry.register("b", &mut b).unwrap();
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.find("b"), Some(idb));
+    assert_eq!(registry.name(ida), Some("a"));
+    assert_eq!(registry.find("c"), None);
+    assert_eq!(registry.state(ida), Some(AppState::Registered));
+
+    let mut c = CountingApp::new(Next::Done);
+    assert_eq!(registry.register("c", &mut c), Err(-1));
+}
+
+#[test]
+fn test_apps_do_not_run_before_init() {
+    let clock = clock();
+    let mut app = CountingApp::new(Next::Yield);
+    let mut registry: Registry<1> = Registry::new();
+    registry.register("app", &mut app).unwrap();
+
+    assert_eq!(registry.poll(&clock), Ok(Poll::Idle(None)));
+    registry.init_all(0).unwrap();
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(0)));
+}
+
+#[test]
+fn test_failed_init_does_not_stop_other_apps() {
+    let clock = clock();
+    let mut bad = CountingApp::new(Next::Yield);
+    bad.init_error = Some(-5);
+    let mut good = CountingApp::new(Next::Done);
+    let mut registry: Registry<2> = Registry::new();
+    let bad_id = registry.register("bad", &mut bad).unwrap();
+    let good_id = registry.register("good", &mut good).unwrap();
+
+    assert_eq!(registry.init_all(0), Err(1));
+    assert_eq!(registry.state(bad_id), Some(AppState::Failed(-5)));
+    assert_eq!(registry.state(good_id), Some(AppState::Initialized));
+
+    // Only the good app is scheduled
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(good_id)));
+    assert_eq!(registry.poll(&clock), Ok(Poll::Idle(None)));
+}
+
+#[test]
+fn test_stop_and_restart() {
+    let clock = clock();
+    let mut app = CountingApp::new(Next::After(10));
+    {
+        let mut registry: Registry<1> = Registry::new();
+        let id = registry.register("app", &mut app).unwrap();
+        registry.init_all(0).unwrap();
+        registry.poll(&clock).unwrap();
+
+        registry.stop(id).unwrap();
+        assert_eq!(registry.state(id), Some(AppState::Stopped));
+        clock.now.set(10);
+        assert_eq!(registry.poll(&clock), Ok(Poll::Idle(None)));
+
+        registry.restart(id, 10).unwrap();
+        assert_eq!(registry.state(id), Some(AppState::Initialized));
+        assert_eq!(registry.poll(&clock), Ok(Poll::Ran(id)));
+    }
+    assert_eq!(app.inits, 2);
+    assert_eq!(app.steps, 2);
+}
+
+#[test]
+fn test_restart_failed_app_reports_error_again() {
+    let mut app = CountingApp::new(Next::Done);
+    app.init_error = Some(-3);
+    let mut registry: Registry<1> = Registry::new();
+    let id = registry.register("app", &mut app).unwrap();
+
+    assert_eq!(registry.init_all(0), Err(1));
+    assert_eq!(registry.restart(id, 0), Err(-3));
+    assert_eq!(registry.restart(7, 0), Err(-1));
+}
+
+#[test]
+fn test_taken_app_cannot_restart_and_stays_stopped() {
+    let mut app = CountingApp::new(Next::Yield);
+    let mut registry: Registry<1> = Registry::new();
+    let id = registry.register("app", &mut app).unwrap();
+    registry.init_all(0).unwrap();
+
+    // Stop the app while its step is running
+    let (taken_id, taken) = registry.take_due(0).unwrap();
+    assert_eq!(taken_id, id);
+    assert_eq!(registry.restart(id, 0), Err(-2));
+    registry.stop(id).unwrap();
+    let next = taken.loop_step();
+    registry.complete(id, taken, 0, 0, next).unwrap();
+
+    assert_eq!(registry.state(id), Some(AppState::Stopped));
+    assert_eq!(registry.take_due(0).map(|(id, _)| id), None);
+}
+
+#[test]
+fn test_init_outside_the_registry() {
+    let clock = clock();
+    let mut app = CountingApp::new(Next::Yield);
+    let mut registry: Registry<1> = Registry::new();
+    let id = registry.register("app", &mut app).unwrap();
+
+    // While `init()` runs the app is out of the registry
+    let taken = registry.take_init(id).unwrap();
+    assert_eq!(registry.take_init(id).map(|_| ()), Err(-2));
+    assert_eq!(registry.restart(id, 0), Err(-2));
+    let result = taken.init();
+    registry.complete_init(id, taken, result, 0).unwrap();
+
+    assert_eq!(registry.state(id), Some(AppState::Initialized));
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(id)));
+    assert_eq!(registry.take_init(3).map(|_| ()), Err(-1));
+}
+
+#[test]
+fn test_priority_from_app_config() {
+    let clock = clock();
+    let mut low = CountingApp::new(Next::Done);
+    let mut high = CountingApp::new(Next::Done);
+    high.priority = 3;
+    let mut registry: Registry<2> = Registry::new();
+    registry.register("low", &mut low).unwrap();
+    let high_id = registry.register("high", &mut high).unwrap();
+    registry.init_all(0).unwrap();
+
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(high_id)));
+}
+
+#[test]
+fn test_event_wakes_app() {
+    let clock = clock();
+    let mut app = CountingApp::new(Next::Event(0x4));
+    let mut registry: Registry<1> = Registry::new();
+    let id = registry.register("app", &mut app).unwrap();
+    registry.init_all(0).unwrap();
+
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(id)));
+    assert_eq!(registry.poll(&clock), Ok(Poll::Idle(None)));
+    registry.signal(0x4);
+    assert_eq!(registry.poll(&clock), Ok(Poll::Ran(id)));
+    assert_eq!(registry.stats(id).unwrap().runs, 2);
+}
@@ -0,0 +1,226 @@
+use core::cell::Cell;
+use sched::scheduler::*;
+use sched::tick::TickSource;
+
+/// Tick source driven by the test
+struct MockClock {
+    now: Cell<u32>,
+}
+
+impl MockClock {
+    fn new(start: u32) -> Self {
+        Self {
+            now: Cell::new(start),
+        }
+    }
+
+    fn advance(&self, ticks: u32) {
+        self.now.set(self.now.get().wrapping_add(ticks));
+    }
+
+    fn set(&self, tick: u32) {
+        self.now.set(tick);
+    }
+}
+
+impl TickSource for MockClock {
+    fn ticks(&self) -> u32 {
+        self.now.get()
+    }
+}
+
+#[test]
+fn test_periodic_task_runs_once_per_period() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<2> = Scheduler::new();
+    let id = sched
+        .add(TaskConfig::periodic(0, 10), clock.ticks())
+        .unwrap();
+
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Ran(id)));
+    assert_eq!(
+        sched.poll(&clock, |_| Next::Periodic),
+        Ok(Poll::Idle(Some(10)))
+    );
+
+    clock.advance(9);
+    assert_eq!(
+        sched.poll(&clock, |_| Next::Periodic),
+        Ok(Poll::Idle(Some(1)))
+    );
+
+    clock.advance(1);
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Ran(id)));
+    assert_eq!(sched.stats(id).unwrap().runs, 2);
+}
+
+#[test]
+fn test_periodic_release_does_not_drift() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<1> = Scheduler::new();
+    let id = sched.add(TaskConfig::periodic(0, 10), 0).unwrap();
+
+    // Run late by 3 ticks, the next release stays on the 10-tick grid
+    clock.set(3);
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Ran(id)));
+    assert_eq!(sched.idle_ticks(clock.ticks()), Some(7));
+}
+
+#[test]
+fn test_higher_priority_runs_first() {
+    let clock = MockClock::new(100);
+    let mut sched: Scheduler<3> = Scheduler::new();
+    let low = sched.add(TaskConfig::default(), 100).unwrap();
+    let high = sched
+        .add(
+            TaskConfig {
+                priority: 5,
+                ..Default::default()
+            },
+            100,
+        )
+        .unwrap();
+
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(high)));
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(low)));
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Idle(None)));
+}
+
+#[test]
+fn test_equal_priority_round_robin_with_yield() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<2> = Scheduler::new();
+    let a = sched.add(TaskConfig::default(), 0).unwrap();
+    let b = sched.add(TaskConfig::default(), 0).unwrap();
+
+    let mut order = Vec::new();
+    for _ in 0..4 {
+        sched
+            .poll(&clock, |id| {
+                order.push(id);
+                clock.advance(1);
+                Next::Yield
+            })
+            .unwrap();
+    }
+    assert_eq!(order, vec![a, b, a, b]);
+}
+
+#[test]
+fn test_event_wakes_waiting_task() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<2> = Scheduler::new();
+    let id = sched.add(TaskConfig::default(), 0).unwrap();
+
+    assert_eq!(sched.poll(&clock, |_| Next::Event(0b10)), Ok(Poll::Ran(id)));
+    assert_eq!(
+        sched.poll(&clock, |_| Next::Event(0b10)),
+        Ok(Poll::Idle(None))
+    );
+
+    // An unrelated event does not wake it
+    sched.signal(0b01);
+    assert_eq!(
+        sched.poll(&clock, |_| Next::Event(0b10)),
+        Ok(Poll::Idle(None))
+    );
+
+    sched.signal(0b10);
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(id)));
+}
+
+#[test]
+fn test_event_signaled_before_wait_is_kept() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<1> = Scheduler::new();
+    let id = sched.add(TaskConfig::default(), 0).unwrap();
+
+    sched.signal(0b1);
+    assert_eq!(sched.poll(&clock, |_| Next::Event(0b1)), Ok(Poll::Ran(id)));
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(id)));
+}
+
+#[test]
+fn test_after_and_at() {
+    let clock = MockClock::new(50);
+    let mut sched: Scheduler<2> = Scheduler::new();
+    let a = sched.add(TaskConfig::default(), 50).unwrap();
+    let b = sched.add(TaskConfig::default(), 50).unwrap();
+
+    sched.poll(&clock, |_| Next::After(20)).unwrap();
+    sched.poll(&clock, |_| Next::At(60)).unwrap();
+    assert_eq!(sched.idle_ticks(clock.ticks()), Some(10));
+
+    clock.set(60);
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(b)));
+    clock.set(70);
+    assert_eq!(sched.poll(&clock, |_| Next::Done), Ok(Poll::Ran(a)));
+}
+
+#[test]
+fn test_run_time_and_overrun_statistics() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<1> = Scheduler::new();
+    let id = sched.add(TaskConfig::periodic(0, 10), 0).unwrap();
+
+    // 4 ticks, within the deadline
+    sched
+        .poll(&clock, |_| {
+            clock.advance(4);
+            Next::Periodic
+        })
+        .unwrap();
+    // Released at 10, finishes at 32: over the 10-tick deadline
+    clock.set(10);
+    sched
+        .poll(&clock, |_| {
+            clock.advance(22);
+            Next::Periodic
+        })
+        .unwrap();
+
+    let stats = sched.stats(id).unwrap();
+    assert_eq!(stats.runs, 2);
+    assert_eq!(stats.total_ticks, 26);
+    assert_eq!(stats.max_ticks, 22);
+    assert_eq!(stats.overruns, 1);
+    // The release at 20 is a full period late at 32 and is dropped, the
+    // one at 30 is due right away
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(sched.idle_ticks(clock.ticks()), Some(0));
+}
+
+#[test]
+fn test_periodic_without_period_is_an_error() {
+    let clock = MockClock::new(0);
+    let mut sched: Scheduler<1> = Scheduler::new();
+    sched.add(TaskConfig::default(), 0).unwrap();
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Err(-2));
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Idle(None)));
+}
+
+#[test]
+fn test_full_scheduler_rejects_task() {
+    let mut sched: Scheduler<1> = Scheduler::new();
+    assert_eq!(sched.add(TaskConfig::default(), 0), Ok(0));
+    assert_eq!(sched.add(TaskConfig::default(), 0), Err(-1));
+    assert_eq!(sched.len(), 1);
+}
+
+#[test]
+fn test_schedule_across_tick_wrap() {
+    let clock = MockClock::new(u32::MAX - 4);
+    let mut sched: Scheduler<1> = Scheduler::new();
+    let id = sched
+        .add(TaskConfig::periodic(0, 10), clock.ticks())
+        .unwrap();
+
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Ran(id)));
+    clock.advance(9);
+    assert_eq!(
+        sched.poll(&clock, |_| Next::Periodic),
+        Ok(Poll::Idle(Some(1)))
+    );
+    clock.advance(1);
+    assert_eq!(sched.poll(&clock, |_| Next::Periodic), Ok(Poll::Ran(id)));
+}
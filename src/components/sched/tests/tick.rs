@@ -0,0 +1,17 @@
+use sched::tick::{reached, ticks_until};
+
+#[test]
+fn test_reached_across_wrap() {
+    assert!(reached(10, 10));
+    assert!(reached(11, 10));
+    assert!(!reached(9, 10));
+    assert!(reached(5, u32::MAX - 5));
+    assert!(!reached(u32::MAX - 5, 5));
+}
+
+#[test]
+fn test_ticks_until() {
+    assert_eq!(ticks_until(100, 150), 50);
+    assert_eq!(ticks_until(150, 100), 0);
+    assert_eq!(ticks_until(u32::MAX - 1, 3), 5);
+}
@@ -0,0 +1,120 @@
+//! Splitting a command line into arguments, and parsing numbers and pin
+//! names out of them.
+//!
+//! Arguments are separated by spaces; double quotes group an argument with
+//! spaces in it (`echo "a b"`). There are no escapes.
+
+/// Errors are i32 codes as in the firmware
+pub type Error = i32;
+
+/// Maximum number of arguments, the command name included
+pub const MAX_ARGS: usize = 8;
+
+/// The arguments of one command line, `get(0)` is the command name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Args<'a> {
+    argv: [&'a str; MAX_ARGS],
+    len: usize,
+}
+
+impl<'a> Args<'a> {
+    /// Split `line`. Returns Err(-1) for more than `MAX_ARGS` arguments or
+    /// an unterminated quote.
+    pub fn parse(line: &'a str) -> Result<Self, Error> {
+        let mut args = Self {
+            argv: [""; MAX_ARGS],
+            len: 0,
+        };
+        let mut rest = line.trim_start();
+        while !rest.is_empty() {
+            let (arg, tail) = match rest.strip_prefix('"') {
+                Some(quoted) => {
+                    let end = quoted.find('"').ok_or(-1)?;
+                    (&quoted[..end], &quoted[end + 1..])
+                }
+                None => {
+                    let end = rest.find(' ').unwrap_or(rest.len());
+                    (&rest[..end], &rest[end..])
+                }
+            };
+            if args.len == MAX_ARGS {
+                return Err(-1);
+            }
+            args.argv[args.len] = arg;
+            args.len += 1;
+            rest = tail.trim_start();
+        }
+        Ok(args)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a str> {
+        self.argv[..self.len].get(index).copied()
+    }
+
+    /// The command name, empty for a blank line
+    pub fn command(&self) -> &'a str {
+        self.get(0).unwrap_or("")
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.argv[..self.len].iter().copied()
+    }
+
+    /// Argument `index` as a number (see `parse_u32`). Returns Err(-1) if
+    /// it is missing or not a number.
+    pub fn u32(&self, index: usize) -> Result<u32, Error> {
+        self.get(index).and_then(parse_u32).ok_or(-1)
+    }
+
+    /// Argument `index` as a number, `default` if it is missing. Returns
+    /// Err(-1) if it is not a number.
+    pub fn u32_or(&self, index: usize, default: u32) -> Result<u32, Error> {
+        match self.get(index) {
+            Some(arg) => parse_u32(arg).ok_or(-1),
+            None => Ok(default),
+        }
+    }
+}
+
+/// A decimal, `0x` hexadecimal or `0b` binary number; `_` may separate
+/// digits
+pub fn parse_u32(s: &str) -> Option<u32> {
+    let (digits, radix) = if let Some(hex) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+        (hex, 16)
+    } else if let Some(bin) = s.strip_prefix("0b").or(s.strip_prefix("0B")) {
+        (bin, 2)
+    } else {
+        (s, 10)
+    };
+    let mut value: u32 = 0;
+    let mut any = false;
+    for c in digits.chars().filter(|&c| c != '_') {
+        let digit = c.to_digit(radix)?;
+        value = value.checked_mul(radix)?.checked_add(digit)?;
+        any = true;
+    }
+    any.then_some(value)
+}
+
+/// A pin name like `PA5` or `pc13` as (port index, pin number), port A
+/// being 0
+pub fn parse_pin(s: &str) -> Option<(u8, u8)> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 3 || !bytes[0].eq_ignore_ascii_case(&b'p') {
+        return None;
+    }
+    let port = bytes[1].to_ascii_uppercase().checked_sub(b'A')?;
+    if port > b'K' - b'A' {
+        return None;
+    }
+    let number: u8 = s[2..].parse().ok()?;
+    (number < 16).then_some((port, number))
+}
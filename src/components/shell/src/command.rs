@@ -0,0 +1,81 @@
+//! The command table: a static slice of `Command`s, looked up by name and
+//! completed by prefix.
+//!
+//! ```ignore
+//! static COMMANDS: &[Command<Board>] = &[
+//!     Command { name: "peek", usage: "peek <addr> [words]", help: "Read memory", run: peek },
+//! ];
+//! ```
+
+use crate::args::{Args, Error};
+use core::fmt::Write;
+
+/// Runs a command on the context `C`, writing its output to the terminal.
+/// Returning Err(-1) prints the usage of the command, other errors their
+/// code.
+pub type Handler<C> = fn(&mut C, &Args, &mut dyn Write) -> Result<(), Error>;
+
+pub struct Command<C> {
+    pub name: &'static str,
+    /// Arguments, shown by `help` and when the handler returns Err(-1)
+    pub usage: &'static str,
+    /// One line describing the command
+    pub help: &'static str,
+    pub run: Handler<C>,
+}
+
+/// The command called `name`
+pub fn find<'a, C>(commands: &'a [Command<C>], name: &str) -> Option<&'a Command<C>> {
+    commands.iter().find(|command| command.name == name)
+}
+
+/// Names of the commands starting with `prefix`
+pub fn matches<'a, C>(
+    commands: &'a [Command<C>],
+    prefix: &'a str,
+) -> impl Iterator<Item = &'static str> + 'a {
+    commands
+        .iter()
+        .map(|command| command.name)
+        .filter(move |name| name.starts_with(prefix))
+}
+
+/// What completing a prefix gives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Completion {
+    /// No command starts with the prefix
+    None,
+    /// Exactly one does; the name
+    Unique(&'static str),
+    /// Several do; the longest prefix they share, at least the one given
+    Common(&'static str),
+}
+
+/// Complete `prefix` to a command name
+pub fn complete<C>(commands: &[Command<C>], prefix: &str) -> Completion {
+    complete_names(commands.iter().map(|command| command.name), prefix)
+}
+
+/// Complete `prefix` to one of `names`
+pub fn complete_names(names: impl Iterator<Item = &'static str>, prefix: &str) -> Completion {
+    let mut names = names.filter(|name| name.starts_with(prefix));
+    let Some(first) = names.next() else {
+        return Completion::None;
+    };
+    let mut common = first.len();
+    let mut several = false;
+    for name in names {
+        several = true;
+        common = first
+            .bytes()
+            .zip(name.bytes())
+            .take(common)
+            .take_while(|(a, b)| a == b)
+            .count();
+    }
+    if several {
+        Completion::Common(&first[..common])
+    } else {
+        Completion::Unique(first)
+    }
+}
@@ -0,0 +1,76 @@
+//! Formatting memory and bus contents for the terminal.
+
+use core::fmt::{self, Write};
+
+/// Bytes per line of a hex dump
+pub const DUMP_WIDTH: usize = 16;
+
+/// `bytes` as hex and ASCII, 16 per line, each line starting with its
+/// address counted from `address`:
+///
+/// ```text
+/// 08000000  00 50 00 20 c1 01 00 08  45 02 00 08 4d 02 00 08  .P. ....E...M...
+/// ```
+pub fn hexdump(out: &mut dyn Write, address: u32, bytes: &[u8]) -> fmt::Result {
+    for (n, line) in bytes.chunks(DUMP_WIDTH).enumerate() {
+        write!(
+            out,
+            "{:08x} ",
+            address.wrapping_add((n * DUMP_WIDTH) as u32)
+        )?;
+        for i in 0..DUMP_WIDTH {
+            if i == DUMP_WIDTH / 2 {
+                out.write_char(' ')?;
+            }
+            match line.get(i) {
+                Some(byte) => write!(out, " {byte:02x}")?,
+                None => out.write_str("   ")?,
+            }
+        }
+        out.write_str("  ")?;
+        for &byte in line {
+            let c = if byte == b' ' || byte.is_ascii_graphic() {
+                byte as char
+            } else {
+                '.'
+            };
+            out.write_char(c)?;
+        }
+        out.write_str("\r\n")?;
+    }
+    Ok(())
+}
+
+/// First and last address of an I2C scan; the others are reserved
+pub const SCAN_FIRST: u8 = 0x08;
+pub const SCAN_LAST: u8 = 0x77;
+
+/// A 7-bit I2C bus scan as a grid of 16 addresses per row, `found` holding
+/// bit n for a device answering at address n:
+///
+/// ```text
+///      0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f
+/// 00:                         -- -- -- -- -- -- -- --
+/// 50: 50 -- -- -- -- -- -- -- -- -- -- -- -- -- -- --
+/// ```
+pub fn scan_grid(out: &mut dyn Write, found: u128) -> fmt::Result {
+    out.write_str("    ")?;
+    for column in 0..16 {
+        write!(out, "  {column:x}")?;
+    }
+    out.write_str("\r\n")?;
+    for row in (0..128u8).step_by(16) {
+        write!(out, "{row:02x}:")?;
+        for address in row..row + 16 {
+            if !(SCAN_FIRST..=SCAN_LAST).contains(&address) {
+                out.write_str("   ")?;
+            } else if found & (1 << address) != 0 {
+                write!(out, " {address:02x}")?;
+            } else {
+                out.write_str(" --")?;
+            }
+        }
+        out.write_str("\r\n")?;
+    }
+    Ok(())
+}
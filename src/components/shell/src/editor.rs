@@ -0,0 +1,270 @@
+//! Line editing on a VT100 terminal: the bytes typed come in one at a
+//! time and the editor echoes them, keeping the terminal in sync with the
+//! line being edited.
+//!
+//! Keys understood:
+//!
+//! | Key                     | Action                            |
+//! | ----------------------- | --------------------------------- |
+//! | Left, Right, Ctrl-B/F   | move the cursor                   |
+//! | Home, End, Ctrl-A/E     | start, end of the line            |
+//! | Backspace, Delete       | delete before, at the cursor      |
+//! | Ctrl-U, Ctrl-K          | delete to the start, to the end   |
+//! | Up, Down, Ctrl-P/N      | older, newer line of the history  |
+//! | Tab                     | `Input::Complete`                 |
+//! | Ctrl-C                  | drop the line, `Input::Cancel`    |
+//! | Enter (CR, LF or CR LF) | `Input::Line`                     |
+//!
+//! Only ASCII is accepted, other bytes are ignored.
+
+use crate::history::History;
+use core::fmt::Write;
+
+/// What the caller has to act on after a byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// Nothing, the byte was an edit
+    Pending,
+    /// Enter was pressed; the line is in `line()` until `reset()`
+    Line,
+    /// Tab was pressed
+    Complete,
+    /// Ctrl-C dropped the line
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// After ESC
+    Start,
+    /// After ESC [ or ESC O, with the number so far
+    Sequence(u8),
+}
+
+const ESC: u8 = 0x1B;
+const BACKSPACE: u8 = 0x08;
+const DEL: u8 = 0x7F;
+
+const fn ctrl(key: u8) -> u8 {
+    key & 0x1F
+}
+
+/// A line of up to `LEN` bytes and a history of `HIST` lines
+pub struct Editor<const LEN: usize, const HIST: usize> {
+    prompt: &'static str,
+    buf: [u8; LEN],
+    len: usize,
+    cursor: usize,
+    history: History<HIST, LEN>,
+    /// History line shown, `None` while editing a new line
+    browsing: Option<usize>,
+    escape: Escape,
+    /// The last byte was CR, an LF right after it is part of the same Enter
+    after_cr: bool,
+}
+
+impl<const LEN: usize, const HIST: usize> Editor<LEN, HIST> {
+    pub const fn new(prompt: &'static str) -> Self {
+        Self {
+            prompt,
+            buf: [0; LEN],
+            len: 0,
+            cursor: 0,
+            history: History::new(),
+            browsing: None,
+            escape: Escape::None,
+            after_cr: false,
+        }
+    }
+
+    /// The line being edited
+    pub fn line(&self) -> &str {
+        // Only ASCII is ever stored
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn history(&self) -> &History<HIST, LEN> {
+        &self.history
+    }
+
+    /// Start a new, empty line. Does not print the prompt.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.cursor = 0;
+        self.browsing = None;
+    }
+
+    /// Print the prompt and the line, with the cursor in place
+    pub fn redraw(&self, out: &mut dyn Write) {
+        let _ = write!(out, "\r{}{}\x1b[K", self.prompt, self.line());
+        let back = self.len - self.cursor;
+        if back > 0 {
+            let _ = write!(out, "\x1b[{back}D");
+        }
+    }
+
+    /// Insert `text` at the cursor, as far as it fits. Returns false if it
+    /// did not fit entirely or held non-ASCII text.
+    pub fn insert(&mut self, text: &str, out: &mut dyn Write) -> bool {
+        let mut all = true;
+        for &byte in text.as_bytes() {
+            if !(byte == b' ' || byte.is_ascii_graphic()) || self.len == LEN {
+                all = false;
+                continue;
+            }
+            self.buf.copy_within(self.cursor..self.len, self.cursor + 1);
+            self.buf[self.cursor] = byte;
+            self.len += 1;
+            self.cursor += 1;
+        }
+        self.redraw(out);
+        all
+    }
+
+    /// Feed one received byte
+    pub fn push(&mut self, byte: u8, out: &mut dyn Write) -> Input {
+        let after_cr = core::mem::replace(&mut self.after_cr, byte == b'\r');
+        match self.escape {
+            Escape::Start => {
+                self.escape = match byte {
+                    b'[' | b'O' => Escape::Sequence(0),
+                    _ => Escape::None,
+                };
+                return Input::Pending;
+            }
+            Escape::Sequence(number) => {
+                if byte.is_ascii_digit() {
+                    self.escape = Escape::Sequence(number.saturating_mul(10) + (byte - b'0'));
+                } else {
+                    self.escape = Escape::None;
+                    self.sequence(number, byte, out);
+                }
+                return Input::Pending;
+            }
+            Escape::None => {}
+        }
+        match byte {
+            b'\r' => return self.enter(out),
+            b'\n' if after_cr => {}
+            b'\n' => return self.enter(out),
+            b'\t' => return Input::Complete,
+            ESC => self.escape = Escape::Start,
+            BACKSPACE | DEL if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.delete(out);
+            }
+            b if b == ctrl(b'C') => {
+                let _ = write!(out, "^C\r\n");
+                self.reset();
+                return Input::Cancel;
+            }
+            b if b == ctrl(b'A') => self.move_to(0, out),
+            b if b == ctrl(b'E') => self.move_to(self.len, out),
+            b if b == ctrl(b'B') => self.move_to(self.cursor.saturating_sub(1), out),
+            b if b == ctrl(b'F') => self.move_to(self.cursor + 1, out),
+            b if b == ctrl(b'P') => self.older(out),
+            b if b == ctrl(b'N') => self.newer(out),
+            b if b == ctrl(b'U') => {
+                self.buf.copy_within(self.cursor..self.len, 0);
+                self.len -= self.cursor;
+                self.cursor = 0;
+                self.redraw(out);
+            }
+            b if b == ctrl(b'K') => {
+                self.len = self.cursor;
+                let _ = write!(out, "\x1b[K");
+            }
+            b if (b == b' ' || b.is_ascii_graphic()) && self.len < LEN => {
+                let at_end = self.cursor == self.len;
+                self.buf.copy_within(self.cursor..self.len, self.cursor + 1);
+                self.buf[self.cursor] = b;
+                self.len += 1;
+                self.cursor += 1;
+                if at_end {
+                    let _ = out.write_char(b as char);
+                } else {
+                    self.redraw(out);
+                }
+            }
+            _ => {}
+        }
+        Input::Pending
+    }
+
+    fn enter(&mut self, out: &mut dyn Write) -> Input {
+        let _ = write!(out, "\r\n");
+        if let Ok(line) = core::str::from_utf8(&self.buf[..self.len]) {
+            self.history.push(line);
+        }
+        self.browsing = None;
+        Input::Line
+    }
+
+    /// The final byte `key` of an escape sequence with parameter `number`
+    fn sequence(&mut self, number: u8, key: u8, out: &mut dyn Write) {
+        match (key, number) {
+            (b'A', _) => self.older(out),
+            (b'B', _) => self.newer(out),
+            (b'C', _) => self.move_to(self.cursor + 1, out),
+            (b'D', _) => self.move_to(self.cursor.saturating_sub(1), out),
+            (b'H', _) | (b'~', 1) | (b'~', 7) => self.move_to(0, out),
+            (b'F', _) | (b'~', 4) | (b'~', 8) => self.move_to(self.len, out),
+            (b'~', 3) if self.cursor < self.len => self.delete(out),
+            _ => {}
+        }
+    }
+
+    /// Remove the byte at the cursor
+    fn delete(&mut self, out: &mut dyn Write) {
+        self.buf.copy_within(self.cursor + 1..self.len, self.cursor);
+        self.len -= 1;
+        self.redraw(out);
+    }
+
+    fn move_to(&mut self, cursor: usize, out: &mut dyn Write) {
+        let cursor = cursor.min(self.len);
+        if cursor < self.cursor {
+            let _ = write!(out, "\x1b[{}D", self.cursor - cursor);
+        } else if cursor > self.cursor {
+            let _ = write!(out, "\x1b[{}C", cursor - self.cursor);
+        }
+        self.cursor = cursor;
+    }
+
+    fn older(&mut self, out: &mut dyn Write) {
+        let age = self.browsing.map_or(0, |age| age + 1);
+        if age < self.history.len() {
+            self.show_history(Some(age), out);
+        }
+    }
+
+    fn newer(&mut self, out: &mut dyn Write) {
+        match self.browsing {
+            Some(0) => {
+                self.browsing = None;
+                self.len = 0;
+                self.cursor = 0;
+                self.redraw(out);
+            }
+            Some(age) => self.show_history(Some(age - 1), out),
+            None => {}
+        }
+    }
+
+    fn show_history(&mut self, age: Option<usize>, out: &mut dyn Write) {
+        let Some(line) = age.and_then(|age| self.history.get(age)) else {
+            return;
+        };
+        let len = line.len();
+        self.buf[..len].copy_from_slice(line.as_bytes());
+        self.len = len;
+        self.cursor = len;
+        self.browsing = age;
+        self.redraw(out);
+    }
+}
@@ -0,0 +1,67 @@
+//! The last entered command lines, newest first.
+
+/// Up to `N` lines of up to `LEN` bytes
+#[derive(Debug, Clone)]
+pub struct History<const N: usize, const LEN: usize> {
+    lines: [[u8; LEN]; N],
+    lens: [usize; N],
+    /// Slot of the next line
+    head: usize,
+    count: usize,
+}
+
+impl<const N: usize, const LEN: usize> History<N, LEN> {
+    pub const fn new() -> Self {
+        Self {
+            lines: [[0; LEN]; N],
+            lens: [0; N],
+            head: 0,
+            count: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Remember `line`, dropping the oldest when full. Blank lines and
+    /// repeats of the newest line are not stored; long lines are cut at
+    /// `LEN` bytes.
+    pub fn push(&mut self, line: &str) {
+        if N == 0 || line.trim().is_empty() || self.get(0) == Some(line) {
+            return;
+        }
+        let mut len = line.len().min(LEN);
+        while !line.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.lines[self.head][..len].copy_from_slice(&line.as_bytes()[..len]);
+        self.lens[self.head] = len;
+        self.head = (self.head + 1) % N;
+        self.count = (self.count + 1).min(N);
+    }
+
+    /// The line entered `age` lines ago, 0 being the newest
+    pub fn get(&self, age: usize) -> Option<&str> {
+        if age >= self.count {
+            return None;
+        }
+        let slot = (self.head + N - 1 - age) % N;
+        core::str::from_utf8(&self.lines[slot][..self.lens[slot]]).ok()
+    }
+
+    /// Lines from the oldest to the newest
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.count).rev().filter_map(|age| self.get(age))
+    }
+}
+
+impl<const N: usize, const LEN: usize> Default for History<N, LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,8 @@
+#![no_std]
+
+pub mod args;
+pub mod command;
+pub mod dump;
+pub mod editor;
+pub mod history;
+pub mod session;
@@ -0,0 +1,140 @@
+//! A shell session: the line editor in front of a command table.
+//!
+//! Every received byte goes to `push()` with the context the commands work
+//! on (drivers, board state) and the terminal output. Enter runs the line;
+//! Tab completes the command name. Two commands are built in: `help` lists
+//! the commands and `history` the lines entered.
+
+use crate::args::Args;
+use crate::command::{self, Command, Completion};
+use crate::editor::{Editor, Input};
+use core::fmt::Write;
+
+/// Commands of every session
+pub const BUILTINS: [&str; 2] = ["help", "history"];
+
+/// A session over `commands`, with lines of up to `LEN` bytes and a
+/// history of `HIST` lines
+pub struct Session<C: 'static, const LEN: usize, const HIST: usize> {
+    editor: Editor<LEN, HIST>,
+    commands: &'static [Command<C>],
+}
+
+impl<C: 'static, const LEN: usize, const HIST: usize> Session<C, LEN, HIST> {
+    pub const fn new(prompt: &'static str, commands: &'static [Command<C>]) -> Self {
+        Self {
+            editor: Editor::new(prompt),
+            commands,
+        }
+    }
+
+    pub fn editor(&self) -> &Editor<LEN, HIST> {
+        &self.editor
+    }
+
+    /// Print the prompt of a new line
+    pub fn start(&mut self, out: &mut dyn Write) {
+        self.editor.reset();
+        self.editor.redraw(out);
+    }
+
+    /// Feed one received byte
+    pub fn push(&mut self, byte: u8, context: &mut C, out: &mut dyn Write) {
+        match self.editor.push(byte, out) {
+            Input::Pending => {}
+            Input::Cancel => self.editor.redraw(out),
+            Input::Complete => self.complete(out),
+            Input::Line => {
+                let args = Args::parse(self.editor.line());
+                match args {
+                    Ok(args) => self.run(&args, context, out),
+                    Err(_) => {
+                        let _ = write!(out, "too many arguments or unterminated quote\r\n");
+                    }
+                }
+                self.start(out);
+            }
+        }
+    }
+
+    /// Run a parsed command line
+    pub fn run(&self, args: &Args, context: &mut C, out: &mut dyn Write) {
+        let name = args.command();
+        match name {
+            "" => {}
+            "help" => self.help(out),
+            "history" => {
+                for (n, line) in self.editor.history().iter().enumerate() {
+                    let _ = write!(out, "{:4}  {}\r\n", n + 1, line);
+                }
+            }
+            _ => match command::find(self.commands, name) {
+                Some(command) => match (command.run)(context, args, out) {
+                    Ok(()) => {}
+                    Err(-1) => {
+                        let _ = write!(out, "usage: {}\r\n", command.usage);
+                    }
+                    Err(e) => {
+                        let _ = write!(out, "{name}: error {e}\r\n");
+                    }
+                },
+                None => {
+                    let _ = write!(out, "{name}: unknown command, try help\r\n");
+                }
+            },
+        }
+    }
+
+    fn help(&self, out: &mut dyn Write) {
+        let width = self
+            .commands
+            .iter()
+            .map(|command| command.usage.len())
+            .chain(BUILTINS.iter().map(|name| name.len()))
+            .max()
+            .unwrap_or(0);
+        for command in self.commands {
+            let _ = write!(out, "{:width$}  {}\r\n", command.usage, command.help);
+        }
+        let _ = write!(out, "{:width$}  List the commands\r\n", "help");
+        let _ = write!(out, "{:width$}  List the lines entered\r\n", "history");
+    }
+
+    fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.commands
+            .iter()
+            .map(|command| command.name)
+            .chain(BUILTINS)
+    }
+
+    /// Complete the command name under the cursor; arguments are not
+    /// completed
+    fn complete(&mut self, out: &mut dyn Write) {
+        let line = self.editor.line();
+        if line.contains(' ') || self.editor.cursor() != line.len() {
+            return;
+        }
+        let completion = command::complete_names(self.names(), line);
+        let done = line.len();
+        match completion {
+            Completion::None => {
+                let _ = out.write_char('\x07');
+            }
+            Completion::Unique(name) => {
+                self.editor.insert(&name[done..], out);
+                self.editor.insert(" ", out);
+            }
+            Completion::Common(prefix) if prefix.len() > done => {
+                self.editor.insert(&prefix[done..], out);
+            }
+            Completion::Common(prefix) => {
+                let _ = write!(out, "\r\n");
+                for name in self.names().filter(|name| name.starts_with(prefix)) {
+                    let _ = write!(out, "{name}  ");
+                }
+                let _ = write!(out, "\r\n");
+                self.editor.redraw(out);
+            }
+        }
+    }
+}
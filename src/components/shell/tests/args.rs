@@ -0,0 +1,64 @@
+use shell::args::*;
+
+#[test]
+fn test_split() {
+    let args = Args::parse("  peek  0x20000000 4 ").unwrap();
+    assert_eq!(args.len(), 3);
+    assert_eq!(args.command(), "peek");
+    assert_eq!(args.get(1), Some("0x20000000"));
+    assert_eq!(args.get(3), None);
+    assert_eq!(args.iter().last(), Some("4"));
+}
+
+#[test]
+fn test_blank_line() {
+    let args = Args::parse("   ").unwrap();
+    assert!(args.is_empty());
+    assert_eq!(args.command(), "");
+}
+
+#[test]
+fn test_quotes() {
+    let args = Args::parse("echo \"a b\" c").unwrap();
+    assert_eq!(args.get(1), Some("a b"));
+    assert_eq!(args.get(2), Some("c"));
+    assert_eq!(Args::parse("echo \"a b"), Err(-1));
+    assert_eq!(Args::parse("echo \"\"").unwrap().get(1), Some(""));
+}
+
+#[test]
+fn test_too_many() {
+    assert!(Args::parse("a b c d e f g h").is_ok());
+    assert_eq!(Args::parse("a b c d e f g h i"), Err(-1));
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(parse_u32("42"), Some(42));
+    assert_eq!(parse_u32("0x2000_0000"), Some(0x2000_0000));
+    assert_eq!(parse_u32("0XfF"), Some(255));
+    assert_eq!(parse_u32("0b101"), Some(5));
+    assert_eq!(parse_u32("0xFFFFFFFF"), Some(u32::MAX));
+    assert_eq!(parse_u32("0x100000000"), None);
+    assert_eq!(parse_u32("0x"), None);
+    assert_eq!(parse_u32("12a"), None);
+    assert_eq!(parse_u32(""), None);
+
+    let args = Args::parse("peek 0x10 x").unwrap();
+    assert_eq!(args.u32(1), Ok(16));
+    assert_eq!(args.u32(2), Err(-1));
+    assert_eq!(args.u32(3), Err(-1));
+    assert_eq!(args.u32_or(3, 1), Ok(1));
+    assert_eq!(args.u32_or(2, 1), Err(-1));
+}
+
+#[test]
+fn test_pins() {
+    assert_eq!(parse_pin("PA5"), Some((0, 5)));
+    assert_eq!(parse_pin("pc13"), Some((2, 13)));
+    assert_eq!(parse_pin("PK15"), Some((10, 15)));
+    assert_eq!(parse_pin("PA16"), None);
+    assert_eq!(parse_pin("PL0"), None);
+    assert_eq!(parse_pin("A5"), None);
+    assert_eq!(parse_pin("P5"), None);
+}
@@ -0,0 +1,61 @@
+use core::fmt::Write;
+use shell::args::{Args, Error};
+use shell::command::*;
+
+fn nop(_: &mut (), _: &Args, _: &mut dyn Write) -> Result<(), Error> {
+    Ok(())
+}
+
+static COMMANDS: &[Command<()>] = &[
+    Command {
+        name: "peek",
+        usage: "peek <addr>",
+        help: "",
+        run: nop,
+    },
+    Command {
+        name: "poke",
+        usage: "poke <addr> <value>",
+        help: "",
+        run: nop,
+    },
+    Command {
+        name: "gpio",
+        usage: "gpio <pin>",
+        help: "",
+        run: nop,
+    },
+];
+
+#[test]
+fn test_find() {
+    assert_eq!(
+        find(COMMANDS, "poke").map(|c| c.usage),
+        Some("poke <addr> <value>")
+    );
+    assert!(find(COMMANDS, "pok").is_none());
+}
+
+#[test]
+fn test_complete() {
+    assert_eq!(complete(COMMANDS, "g"), Completion::Unique("gpio"));
+    assert_eq!(complete(COMMANDS, "gpio"), Completion::Unique("gpio"));
+    assert_eq!(complete(COMMANDS, "p"), Completion::Common("p"));
+    assert_eq!(complete(COMMANDS, ""), Completion::Common(""));
+    assert_eq!(complete(COMMANDS, "pe"), Completion::Unique("peek"));
+    assert_eq!(complete(COMMANDS, "x"), Completion::None);
+    assert_eq!(matches(COMMANDS, "p").collect::<Vec<_>>(), ["peek", "poke"]);
+}
+
+#[test]
+fn test_common_prefix() {
+    let names = ["flash", "flashinfo", "flashread"];
+    assert_eq!(
+        complete_names(names.into_iter(), "f"),
+        Completion::Common("flash")
+    );
+    assert_eq!(
+        complete_names(names.into_iter(), "flashr"),
+        Completion::Unique("flashread")
+    );
+}
@@ -0,0 +1,49 @@
+use shell::dump::*;
+
+#[test]
+fn test_hexdump() {
+    let mut out = String::new();
+    let bytes: Vec<u8> = (0x41..0x41 + 20).collect();
+    hexdump(&mut out, 0x0800_0000, &bytes).unwrap();
+    let lines: Vec<&str> = out.split("\r\n").collect();
+    assert_eq!(
+        lines[0],
+        "08000000  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  ABCDEFGHIJKLMNOP"
+    );
+    assert_eq!(
+        lines[1],
+        "08000010  51 52 53 54                                       QRST"
+    );
+    assert_eq!(lines[2], "");
+}
+
+#[test]
+fn test_hexdump_unprintable() {
+    let mut out = String::new();
+    hexdump(&mut out, 0xFFFF_FFF0, &[0, b' ', 0x7F, 0xFF]).unwrap();
+    assert!(out.starts_with("fffffff0  00 20 7f ff"));
+    assert!(out.ends_with("  . ..\r\n"));
+}
+
+#[test]
+fn test_scan_grid() {
+    let mut out = String::new();
+    scan_grid(&mut out, (1 << 0x50) | (1 << 0x68) | 1).unwrap();
+    let lines: Vec<&str> = out.split("\r\n").collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(
+        lines[0],
+        "      0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f"
+    );
+    // Reserved addresses stay blank even when set
+    assert_eq!(
+        lines[1],
+        "00:                         -- -- -- -- -- -- -- --"
+    );
+    assert!(lines[6].starts_with("50: 50 -- --"));
+    assert!(lines[7].starts_with("60: -- -- -- -- -- -- -- -- 68 --"));
+    assert_eq!(
+        lines[8],
+        format!("70:{}{}", " --".repeat(8), "   ".repeat(8))
+    );
+}
@@ -0,0 +1,119 @@
+use shell::editor::{Editor, Input};
+
+type Ed = Editor<16, 4>;
+
+fn feed(editor: &mut Ed, bytes: &[u8]) -> (Vec<Input>, String) {
+    let mut out = String::new();
+    let inputs = bytes.iter().map(|&b| editor.push(b, &mut out)).collect();
+    (inputs, out)
+}
+
+#[test]
+fn test_type_and_enter() {
+    let mut editor = Ed::new("> ");
+    let (inputs, out) = feed(&mut editor, b"ls\r");
+    assert_eq!(inputs, [Input::Pending, Input::Pending, Input::Line]);
+    assert_eq!(out, "ls\r\n");
+    assert_eq!(editor.line(), "ls");
+    assert_eq!(editor.history().get(0), Some("ls"));
+    editor.reset();
+    assert_eq!(editor.line(), "");
+}
+
+#[test]
+fn test_crlf_is_one_enter() {
+    let mut editor = Ed::new("> ");
+    let (inputs, _) = feed(&mut editor, b"a\r\n");
+    assert_eq!(inputs[1..], [Input::Line, Input::Pending]);
+    editor.reset();
+    let (inputs, _) = feed(&mut editor, b"\n");
+    assert_eq!(inputs, [Input::Line]);
+}
+
+#[test]
+fn test_backspace_and_cursor() {
+    let mut editor = Ed::new("> ");
+    // "abd", left, insert c, end, backspace
+    feed(&mut editor, b"abd\x1b[Dc\x1b[F\x7f");
+    assert_eq!(editor.line(), "abc");
+    assert_eq!(editor.cursor(), 3);
+    // Home and delete
+    let (_, out) = feed(&mut editor, b"\x01\x1b[3~");
+    assert_eq!(editor.line(), "bc");
+    assert_eq!(editor.cursor(), 0);
+    assert!(out.ends_with("\r> bc\x1b[K\x1b[2D"));
+}
+
+#[test]
+fn test_insert_redraws_tail() {
+    let mut editor = Ed::new("$ ");
+    feed(&mut editor, b"ac\x02");
+    let (_, out) = feed(&mut editor, b"b");
+    assert_eq!(out, "\r$ abc\x1b[K\x1b[1D");
+}
+
+#[test]
+fn test_kill() {
+    let mut editor = Ed::new("> ");
+    feed(
+        &mut editor,
+        b"hello world\x1b[D\x1b[D\x1b[D\x1b[D\x1b[D\x0b",
+    );
+    assert_eq!(editor.line(), "hello ");
+    feed(&mut editor, b"\x02\x15");
+    assert_eq!(editor.line(), " ");
+    assert_eq!(editor.cursor(), 0);
+}
+
+#[test]
+fn test_line_full() {
+    let mut editor = Ed::new("> ");
+    feed(&mut editor, b"0123456789abcdefXYZ");
+    assert_eq!(editor.line(), "0123456789abcdef");
+}
+
+#[test]
+fn test_ignores_control_and_non_ascii() {
+    let mut editor = Ed::new("> ");
+    feed(&mut editor, b"a\x00\xc3\xa9\x07b");
+    assert_eq!(editor.line(), "ab");
+}
+
+#[test]
+fn test_history_browsing() {
+    let mut editor = Ed::new("> ");
+    for line in [&b"one\r"[..], b"two\r"] {
+        feed(&mut editor, line);
+        editor.reset();
+    }
+    feed(&mut editor, b"\x1b[A");
+    assert_eq!(editor.line(), "two");
+    feed(&mut editor, b"\x1b[A\x1b[A");
+    assert_eq!(editor.line(), "one");
+    feed(&mut editor, b"\x0e");
+    assert_eq!(editor.line(), "two");
+    feed(&mut editor, b"\x1b[B");
+    assert_eq!(editor.line(), "");
+    // Entering a recalled line stores it once
+    feed(&mut editor, b"\x10\r");
+    assert_eq!(editor.history().len(), 2);
+}
+
+#[test]
+fn test_tab_and_ctrl_c() {
+    let mut editor = Ed::new("> ");
+    let (inputs, out) = feed(&mut editor, b"pe\t\x03");
+    assert_eq!(inputs[2..], [Input::Complete, Input::Cancel]);
+    assert!(out.ends_with("^C\r\n"));
+    assert_eq!(editor.line(), "");
+}
+
+#[test]
+fn test_insert_text() {
+    let mut editor = Ed::new("> ");
+    let mut out = String::new();
+    assert!(editor.insert("peek ", &mut out));
+    assert_eq!(out, "\r> peek \x1b[K");
+    assert!(!editor.insert("0x2000_0000_0", &mut out));
+    assert_eq!(editor.line(), "peek 0x2000_0000");
+}
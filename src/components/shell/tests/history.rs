@@ -0,0 +1,42 @@
+use shell::history::History;
+
+#[test]
+fn test_newest_first() {
+    let mut history = History::<3, 16>::new();
+    assert!(history.is_empty());
+    history.push("one");
+    history.push("two");
+    assert_eq!(history.get(0), Some("two"));
+    assert_eq!(history.get(1), Some("one"));
+    assert_eq!(history.get(2), None);
+}
+
+#[test]
+fn test_drops_oldest() {
+    let mut history = History::<2, 16>::new();
+    history.push("one");
+    history.push("two");
+    history.push("three");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.iter().collect::<Vec<_>>(), ["two", "three"]);
+}
+
+#[test]
+fn test_skips_blank_and_repeats() {
+    let mut history = History::<4, 16>::new();
+    history.push("apps");
+    history.push("apps");
+    history.push("  ");
+    history.push("");
+    assert_eq!(history.len(), 1);
+    history.push("time");
+    history.push("apps");
+    assert_eq!(history.len(), 3);
+}
+
+#[test]
+fn test_long_lines_cut() {
+    let mut history = History::<2, 4>::new();
+    history.push("abcdef");
+    assert_eq!(history.get(0), Some("abcd"));
+}
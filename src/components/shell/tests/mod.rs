@@ -0,0 +1,6 @@
+pub mod args;
+pub mod command;
+pub mod dump;
+pub mod editor;
+pub mod history;
+pub mod session;
@@ -0,0 +1,111 @@
+use core::fmt::Write;
+use shell::args::{Args, Error};
+use shell::command::Command;
+use shell::session::Session;
+
+#[derive(Default)]
+struct Board {
+    led: bool,
+}
+
+fn led(board: &mut Board, args: &Args, out: &mut dyn Write) -> Result<(), Error> {
+    match args.get(1) {
+        Some("on") => board.led = true,
+        Some("off") => board.led = false,
+        None => {
+            let _ = write!(out, "{}\r\n", if board.led { "on" } else { "off" });
+        }
+        Some(_) => return Err(-1),
+    }
+    Ok(())
+}
+
+fn fail(_: &mut Board, _: &Args, _: &mut dyn Write) -> Result<(), Error> {
+    Err(-2)
+}
+
+static COMMANDS: &[Command<Board>] = &[
+    Command {
+        name: "led",
+        usage: "led [on|off]",
+        help: "Show or set the LED",
+        run: led,
+    },
+    Command {
+        name: "lamp",
+        usage: "lamp",
+        help: "Fail",
+        run: fail,
+    },
+];
+
+fn feed(session: &mut Session<Board, 32, 4>, board: &mut Board, bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for &b in bytes {
+        session.push(b, board, &mut out);
+    }
+    out
+}
+
+#[test]
+fn test_run_command() {
+    let mut session = Session::new("> ", COMMANDS);
+    let mut board = Board::default();
+    let mut out = String::new();
+    session.start(&mut out);
+    assert_eq!(out, "\r> \x1b[K");
+    feed(&mut session, &mut board, b"led on\r");
+    assert!(board.led);
+    let out = feed(&mut session, &mut board, b"led\r");
+    assert_eq!(out, "led\r\non\r\n\r> \x1b[K");
+}
+
+#[test]
+fn test_errors() {
+    let mut session = Session::new("> ", COMMANDS);
+    let mut board = Board::default();
+    let out = feed(&mut session, &mut board, b"led blink\r");
+    assert!(out.contains("usage: led [on|off]\r\n"));
+    let out = feed(&mut session, &mut board, b"lamp\r");
+    assert!(out.contains("lamp: error -2\r\n"));
+    let out = feed(&mut session, &mut board, b"reboot\r");
+    assert!(out.contains("reboot: unknown command, try help\r\n"));
+    let out = feed(&mut session, &mut board, b"led \"on\r");
+    assert!(out.contains("unterminated quote"));
+    let out = feed(&mut session, &mut board, b"\r");
+    assert_eq!(out, "\r\n\r> \x1b[K");
+}
+
+#[test]
+fn test_help_and_history() {
+    let mut session = Session::new("> ", COMMANDS);
+    let mut board = Board::default();
+    let out = feed(&mut session, &mut board, b"help\r");
+    assert!(out.contains("led [on|off]  Show or set the LED\r\n"));
+    assert!(out.contains("history       List the lines entered\r\n"));
+    let out = feed(&mut session, &mut board, b"history\r");
+    assert!(out.contains("   1  help\r\n   2  history\r\n"));
+}
+
+#[test]
+fn test_completion() {
+    let mut session = Session::new("> ", COMMANDS);
+    let mut board = Board::default();
+    feed(&mut session, &mut board, b"le\t");
+    assert_eq!(session.editor().line(), "led ");
+    feed(&mut session, &mut board, b"\x03");
+
+    // Shared prefix, then the choices
+    feed(&mut session, &mut board, b"l\t");
+    assert_eq!(session.editor().line(), "l");
+    let out = feed(&mut session, &mut board, b"a\t");
+    assert_eq!(session.editor().line(), "lamp ");
+    assert!(out.ends_with("mp\x1b[K\r> lamp \x1b[K"));
+    feed(&mut session, &mut board, b"\x03");
+
+    let out = feed(&mut session, &mut board, b"h\t");
+    assert_eq!(session.editor().line(), "h");
+    assert!(out.contains("\r\nhelp  history  \r\n\r> h\x1b[K"));
+    let out = feed(&mut session, &mut board, b"x\t");
+    assert!(out.ends_with("\x07"));
+}
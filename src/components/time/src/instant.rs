@@ -0,0 +1,199 @@
+//! Monotonic time in microseconds since boot.
+//!
+//! 64 bits of microseconds do not wrap for over 500,000 years, so instants
+//! compare and subtract directly, unlike the wrapping 32-bit tick counter.
+
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// A span of time with microsecond resolution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Duration {
+    micros: u64,
+}
+
+impl Duration {
+    pub const ZERO: Duration = Duration { micros: 0 };
+    pub const MAX: Duration = Duration { micros: u64::MAX };
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self { micros }
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self {
+            micros: millis.saturating_mul(1_000),
+        }
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self {
+            micros: secs.saturating_mul(1_000_000),
+        }
+    }
+
+    pub const fn as_micros(&self) -> u64 {
+        self.micros
+    }
+
+    /// Whole milliseconds, rounded down
+    pub const fn as_millis(&self) -> u64 {
+        self.micros / 1_000
+    }
+
+    /// Milliseconds rounded up, for waits that must not end early
+    pub const fn as_millis_ceil(&self) -> u64 {
+        self.micros.div_ceil(1_000)
+    }
+
+    /// Whole seconds, rounded down
+    pub const fn as_secs(&self) -> u64 {
+        self.micros / 1_000_000
+    }
+
+    pub const fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_add(rhs.micros) {
+            Some(micros) => Some(Duration { micros }),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_sub(rhs.micros) {
+            Some(micros) => Some(Duration { micros }),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_sub(self, rhs: Duration) -> Duration {
+        Duration {
+            micros: self.micros.saturating_sub(rhs.micros),
+        }
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        Duration {
+            micros: self.micros.saturating_add(rhs.micros),
+        }
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    /// Saturates at zero
+    fn sub(self, rhs: Duration) -> Duration {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+/// A point in time, in microseconds since boot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Instant {
+    micros: u64,
+}
+
+impl Instant {
+    /// Boot
+    pub const ZERO: Instant = Instant { micros: 0 };
+
+    pub const fn from_micros(micros: u64) -> Self {
+        Self { micros }
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Self {
+            micros: millis.saturating_mul(1_000),
+        }
+    }
+
+    /// Microseconds since boot
+    pub const fn as_micros(&self) -> u64 {
+        self.micros
+    }
+
+    /// Whole milliseconds since boot
+    pub const fn as_millis(&self) -> u64 {
+        self.micros / 1_000
+    }
+
+    /// Time from `earlier` to this instant, zero if `earlier` is later
+    pub const fn duration_since(&self, earlier: Instant) -> Duration {
+        Duration {
+            micros: self.micros.saturating_sub(earlier.micros),
+        }
+    }
+
+    /// Time from `earlier` to this instant, None if `earlier` is later
+    pub const fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+        match self.micros.checked_sub(earlier.micros) {
+            Some(micros) => Some(Duration { micros }),
+            None => None,
+        }
+    }
+
+    pub const fn checked_add(&self, duration: Duration) -> Option<Instant> {
+        match self.micros.checked_add(duration.micros) {
+            Some(micros) => Some(Instant { micros }),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+        match self.micros.checked_sub(duration.micros) {
+            Some(micros) => Some(Instant { micros }),
+            None => None,
+        }
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.saturating_add(rhs.micros),
+        }
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Instant;
+
+    /// Saturates at boot
+    fn sub(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.saturating_sub(rhs.micros),
+        }
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+
+    /// Saturates at zero, see `duration_since`
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
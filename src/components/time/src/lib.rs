@@ -0,0 +1,6 @@
+#![no_std]
+
+pub mod instant;
+pub mod rtc;
+pub mod systick;
+pub mod wheel;
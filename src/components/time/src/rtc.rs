@@ -0,0 +1,102 @@
+//! Arithmetic for the RTC used as time base through low-power modes.
+//!
+//! SysTick stops in Stop mode, so the time spent there is measured on the
+//! RTC: the calendar (BCD time register plus subsecond counter) of the
+//! STM32F4/G0, or the plain counter of the STM32F1. The RTC wakeup timer
+//! is programmed from the same numbers.
+
+/// Milliseconds in a day, the period of the calendar time of day
+pub const MS_PER_DAY: u32 = 86_400_000;
+
+/// Asynchronous prescaler division, the largest for the lowest power
+pub const PREDIV_A: u32 = 128;
+
+/// Prescaler register values (PREDIV_A, PREDIV_S) dividing an RTC clock of
+/// `rtc_hz` down to the 1 Hz calendar clock
+pub fn prescalers(rtc_hz: u32) -> (u32, u32) {
+    (PREDIV_A - 1, (rtc_hz / PREDIV_A).max(1) - 1)
+}
+
+fn bcd(value: u32) -> u32 {
+    (value >> 4) * 10 + (value & 0xF)
+}
+
+/// Milliseconds since midnight of a time register value (TR, BCD hours,
+/// minutes and seconds in 24-hour format) and the subsecond counter (SSR),
+/// which counts down from `prediv_s` within each second.
+pub fn ms_of_day(tr: u32, ssr: u32, prediv_s: u32) -> u32 {
+    let hours = bcd((tr >> 16) & 0x3F);
+    let minutes = bcd((tr >> 8) & 0x7F);
+    let seconds = bcd(tr & 0x7F);
+    let sub = prediv_s.saturating_sub(ssr.min(prediv_s));
+    let ms = (sub as u64 * 1000 / (prediv_s as u64 + 1)) as u32;
+    (hours * 3600 + minutes * 60 + seconds) * 1000 + ms
+}
+
+/// Milliseconds from time of day `from` to `to`, across midnight
+pub fn elapsed_ms_of_day(from: u32, to: u32) -> u32 {
+    if to >= from {
+        to - from
+    } else {
+        MS_PER_DAY - from + to
+    }
+}
+
+/// Milliseconds for `counts` of a counter running at `counter_hz`
+pub fn counts_to_ms(counts: u32, counter_hz: u32) -> u32 {
+    (counts as u64 * 1000 / counter_hz.max(1) as u64) as u32
+}
+
+/// Counts of a counter running at `counter_hz` in `ms` milliseconds,
+/// rounded down so a wakeup is never late
+pub fn ms_to_counts(ms: u32, counter_hz: u32) -> u32 {
+    (ms as u64 * counter_hz as u64 / 1000).min(u32::MAX as u64) as u32
+}
+
+/// Clock of the wakeup timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WakeupClock {
+    /// RTC clock divided by 16, fine steps up to a few tens of seconds
+    RtcDiv16,
+    /// The 1 Hz calendar clock, up to 18 hours
+    Spre,
+}
+
+/// Wakeup timer setting: the clock and the WUTR reload value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WakeupConfig {
+    pub clock: WakeupClock,
+    pub reload: u16,
+}
+
+impl WakeupConfig {
+    /// Milliseconds until the timer fires with an RTC clock of `rtc_hz`
+    pub fn period_ms(&self, rtc_hz: u32) -> u32 {
+        let counts = self.reload as u32 + 1;
+        match self.clock {
+            WakeupClock::RtcDiv16 => counts_to_ms(counts, rtc_hz / 16),
+            WakeupClock::Spre => counts * 1000,
+        }
+    }
+}
+
+/// Wakeup timer setting firing at most `ms` milliseconds from now with an
+/// RTC clock of `rtc_hz`, as close as the timer allows. `None` for a
+/// period shorter than one timer step.
+pub fn wakeup_config(ms: u32, rtc_hz: u32) -> Option<WakeupConfig> {
+    let counts = ms_to_counts(ms, rtc_hz / 16);
+    if counts == 0 {
+        return None;
+    }
+    if counts <= 1 << 16 {
+        return Some(WakeupConfig {
+            clock: WakeupClock::RtcDiv16,
+            reload: (counts - 1) as u16,
+        });
+    }
+    let seconds = (ms / 1000).min(1 << 16);
+    Some(WakeupConfig {
+        clock: WakeupClock::Spre,
+        reload: (seconds - 1) as u16,
+    })
+}
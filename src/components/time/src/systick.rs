@@ -0,0 +1,70 @@
+//! Time from the SysTick tick counter and current value.
+//!
+//! SysTick counts down from its reload value and interrupts on reaching
+//! zero, where the tick counter is incremented. Between interrupts the
+//! current value gives the time within the tick.
+
+/// Combine the halves of a tick count
+pub const fn join(high: u32, low: u32) -> u64 {
+    ((high as u64) << 32) | low as u64
+}
+
+/// Read a 64-bit tick count kept as two 32-bit halves, for cores without
+/// 64-bit atomics, while the tick interrupt may carry into `high`: `high` is
+/// read again after `low` until it did not change.
+pub fn read_split(mut high: impl FnMut() -> u32, mut low: impl FnMut() -> u32) -> u64 {
+    loop {
+        let before = high();
+        let low = low();
+        if high() == before {
+            return join(before, low);
+        }
+    }
+}
+
+/// One sample of SysTick, taken with interrupts masked: the current value
+/// is read, then the pending flag of the tick interrupt, then the current
+/// value again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// Ticks counted so far
+    pub ticks: u64,
+    /// SYST_RVR, the counter counts from here down to 0
+    pub reload: u32,
+    /// SYST_CVR before reading the pending flag
+    pub current_before: u32,
+    /// The tick interrupt is pending, so `ticks` is one behind
+    pub pending: bool,
+    /// SYST_CVR after reading the pending flag
+    pub current_after: u32,
+}
+
+/// Microseconds since the tick counter started, for ticks of `tick_us`
+/// microseconds each.
+///
+/// If the tick interrupt is pending the counter wrapped before the second
+/// read of the current value, which then belongs to the next tick. If it is
+/// not, the first read came before any wrap.
+pub fn micros(sample: Sample, tick_us: u32) -> u64 {
+    let (ticks, elapsed) = position(sample);
+    let period = sample.reload as u64 + 1;
+    ticks * tick_us as u64 + elapsed * tick_us as u64 / period
+}
+
+/// SysTick clock cycles since the tick counter started, the core clock
+/// cycles when SysTick runs on the processor clock. For cores without a
+/// cycle counter.
+pub fn cycles(sample: Sample) -> u64 {
+    let (ticks, elapsed) = position(sample);
+    ticks * (sample.reload as u64 + 1) + elapsed
+}
+
+/// Whole ticks and counts into the current tick of a sample
+fn position(sample: Sample) -> (u64, u64) {
+    let (ticks, current) = if sample.pending {
+        (sample.ticks + 1, sample.current_after)
+    } else {
+        (sample.ticks, sample.current_before)
+    };
+    (ticks, sample.reload.saturating_sub(current) as u64)
+}
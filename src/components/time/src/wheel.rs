@@ -0,0 +1,172 @@
+//! Software timers on a hashed timing wheel.
+//!
+//! Timers are hashed into `SLOTS` buckets by deadline tick, so advancing the
+//! wheel by one tick only looks at the timers of one bucket. Timers and
+//! bucket lists live in fixed arrays, no heap is used.
+
+pub type TimerId = usize;
+
+/// Called when a timer expires, with the id of the timer
+pub type Callback = fn(TimerId);
+
+#[derive(Clone, Copy)]
+struct Timer {
+    deadline: u64,
+    /// Ticks between expiries, 0 for a one-shot timer
+    period: u64,
+    callback: Callback,
+    /// Next timer in the same bucket
+    next: Option<TimerId>,
+}
+
+/// Up to `N` timers in `SLOTS` buckets, counting in ticks
+pub struct TimerWheel<const N: usize, const SLOTS: usize> {
+    timers: [Option<Timer>; N],
+    buckets: [Option<TimerId>; SLOTS],
+    /// Last tick processed
+    now: u64,
+}
+
+impl<const N: usize, const SLOTS: usize> TimerWheel<N, SLOTS> {
+    pub const fn new() -> Self {
+        Self {
+            timers: [None; N],
+            buckets: [None; SLOTS],
+            now: 0,
+        }
+    }
+
+    /// The tick the wheel was last advanced to
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Start a timer expiring `delay` ticks from now (at least one), then
+    /// every `period` ticks unless `period` is 0. Returns Err(-1) if all
+    /// timers are in use.
+    pub fn start(&mut self, delay: u64, period: u64, callback: Callback) -> Result<TimerId, i32> {
+        let id = self.timers.iter().position(Option::is_none).ok_or(-1)?;
+        let deadline = self.now.saturating_add(delay.max(1));
+        self.timers[id] = Some(Timer {
+            deadline,
+            period,
+            callback,
+            next: None,
+        });
+        self.link(id);
+        Ok(id)
+    }
+
+    /// Stop a timer. Returns Err(-1) if it is not running.
+    pub fn stop(&mut self, id: TimerId) -> Result<(), i32> {
+        if !self.is_active(id) {
+            return Err(-1);
+        }
+        self.unlink(id);
+        self.timers[id] = None;
+        Ok(())
+    }
+
+    pub fn is_active(&self, id: TimerId) -> bool {
+        self.timers.get(id).is_some_and(Option::is_some)
+    }
+
+    /// Ticks until the timer expires next
+    pub fn remaining(&self, id: TimerId) -> Option<u64> {
+        let timer = self.timers.get(id)?.as_ref()?;
+        Some(timer.deadline.saturating_sub(self.now))
+    }
+
+    /// Tick of the earliest expiry, `None` if no timer is running
+    pub fn next_expiry(&self) -> Option<u64> {
+        self.timers.iter().flatten().map(|t| t.deadline).min()
+    }
+
+    /// Number of running timers
+    pub fn len(&self) -> usize {
+        self.timers.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Advance to tick `now`, calling `fire` for every expiry on the way in
+    /// tick order. One-shot timers are stopped before they fire; periodic
+    /// timers are re-armed, skipping periods missed by a large step.
+    pub fn advance(&mut self, now: u64, mut fire: impl FnMut(TimerId, Callback)) {
+        if now <= self.now {
+            return;
+        }
+        // Each bucket holds the deadlines of one residue, so one pass over
+        // the last SLOTS ticks finds everything that expired in a longer step
+        let steps = (now - self.now).min(SLOTS as u64);
+        for tick in now - steps + 1..=now {
+            self.expire_bucket(tick, now, &mut fire);
+        }
+        self.now = now;
+    }
+
+    fn bucket(deadline: u64) -> usize {
+        (deadline % SLOTS as u64) as usize
+    }
+
+    fn link(&mut self, id: TimerId) {
+        let Some(timer) = self.timers[id].as_mut() else {
+            return;
+        };
+        let bucket = Self::bucket(timer.deadline);
+        timer.next = self.buckets[bucket];
+        self.buckets[bucket] = Some(id);
+    }
+
+    fn unlink(&mut self, id: TimerId) {
+        let Some(timer) = self.timers[id] else {
+            return;
+        };
+        let bucket = Self::bucket(timer.deadline);
+        if self.buckets[bucket] == Some(id) {
+            self.buckets[bucket] = timer.next;
+            return;
+        }
+        let mut cursor = self.buckets[bucket];
+        while let Some(prev) = cursor {
+            let prev_timer = self.timers[prev].as_mut().expect("linked timer");
+            if prev_timer.next == Some(id) {
+                prev_timer.next = timer.next;
+                return;
+            }
+            cursor = prev_timer.next;
+        }
+    }
+
+    fn expire_bucket(&mut self, tick: u64, now: u64, fire: &mut impl FnMut(TimerId, Callback)) {
+        // Detach the bucket and put back what has not expired yet, so timers
+        // re-armed into this bucket are not seen twice
+        let mut cursor = self.buckets[Self::bucket(tick)].take();
+        while let Some(id) = cursor {
+            let mut timer = self.timers[id].expect("linked timer");
+            cursor = timer.next;
+            if timer.deadline > tick {
+                self.link(id);
+                continue;
+            }
+            // A period of 0 is a one-shot timer
+            match (now - timer.deadline).checked_div(timer.period) {
+                None => self.timers[id] = None,
+                Some(missed) => {
+                    timer.deadline += (missed + 1) * timer.period;
+                    self.timers[id] = Some(timer);
+                    self.link(id);
+                }
+            }
+            fire(id, timer.callback);
+        }
+    }
+}
+
+impl<const N: usize, const SLOTS: usize> Default for TimerWheel<N, SLOTS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,56 @@
+use time::instant::*;
+
+#[test]
+fn test_duration_units() {
+    assert_eq!(Duration::from_millis(3).as_micros(), 3_000);
+    assert_eq!(Duration::from_secs(2).as_millis(), 2_000);
+    assert_eq!(Duration::from_micros(1_500).as_millis(), 1);
+    assert_eq!(Duration::from_micros(1_500).as_millis_ceil(), 2);
+    assert_eq!(Duration::from_micros(2_000).as_millis_ceil(), 2);
+    assert_eq!(Duration::from_micros(2_500_000).as_secs(), 2);
+}
+
+#[test]
+fn test_duration_arithmetic_saturates() {
+    let a = Duration::from_millis(5);
+    let b = Duration::from_millis(3);
+    assert_eq!(a + b, Duration::from_millis(8));
+    assert_eq!(a - b, Duration::from_millis(2));
+    assert_eq!(b - a, Duration::ZERO);
+    assert_eq!(b.checked_sub(a), None);
+    assert_eq!(Duration::MAX + a, Duration::MAX);
+    assert_eq!(Duration::MAX.checked_add(a), None);
+    assert_eq!(Duration::from_secs(u64::MAX), Duration::MAX);
+}
+
+#[test]
+fn test_instant_ordering_and_difference() {
+    let start = Instant::from_millis(10);
+    let later = start + Duration::from_micros(250);
+    assert!(later > start);
+    assert_eq!(later - start, Duration::from_micros(250));
+    assert_eq!(later.duration_since(start).as_micros(), 250);
+    assert_eq!(start.duration_since(later), Duration::ZERO);
+    assert_eq!(start.checked_duration_since(later), None);
+    assert_eq!(later - Duration::from_micros(250), start);
+    assert_eq!(later.as_millis(), 10);
+}
+
+#[test]
+fn test_instant_past_32_bit_tick_wrap() {
+    // 2^32 ms is where the 32-bit tick counter wraps, about 49.7 days
+    let before = Instant::from_millis(u32::MAX as u64);
+    let after = before + Duration::from_millis(2);
+    assert!(after > before);
+    assert_eq!(after.as_millis(), u32::MAX as u64 + 2);
+    assert_eq!(after - before, Duration::from_millis(2));
+}
+
+#[test]
+fn test_instant_saturates() {
+    assert_eq!(Instant::ZERO - Duration::from_secs(1), Instant::ZERO);
+    assert_eq!(Instant::ZERO.checked_sub(Duration::from_micros(1)), None);
+    let end = Instant::from_micros(u64::MAX);
+    assert_eq!(end + Duration::from_micros(1), end);
+    assert_eq!(end.checked_add(Duration::from_micros(1)), None);
+}
@@ -0,0 +1,4 @@
+pub mod instant;
+pub mod rtc;
+pub mod systick;
+pub mod wheel;
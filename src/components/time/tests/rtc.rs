@@ -0,0 +1,68 @@
+use time::rtc::*;
+
+#[test]
+fn test_prescalers_give_one_hertz() {
+    assert_eq!(prescalers(32_768), (127, 255));
+    assert_eq!(prescalers(32_000), (127, 249));
+    let (a, s) = prescalers(40_000);
+    assert_eq!(40_000 / ((a + 1) * (s + 1)), 1);
+}
+
+#[test]
+fn test_ms_of_day_from_bcd() {
+    // 13:45:59, no subseconds elapsed
+    assert_eq!(
+        ms_of_day(0x13_45_59, 255, 255),
+        (13 * 3600 + 45 * 60 + 59) * 1000
+    );
+    // Half a second in
+    assert_eq!(ms_of_day(0x00_00_01, 127, 255), 1500);
+    assert_eq!(ms_of_day(0x23_59_59, 0, 255), MS_PER_DAY - 4);
+}
+
+#[test]
+fn test_ms_of_day_ignores_flags_above_the_fields() {
+    // PM bit (22) is not part of the hours in 24-hour format
+    assert_eq!(ms_of_day(0x00_00_00, 255, 255), 0);
+    assert_eq!(ms_of_day(0x8000_0000 | 0x00_01_00, 255, 255), 60_000);
+}
+
+#[test]
+fn test_elapsed_across_midnight() {
+    assert_eq!(elapsed_ms_of_day(1000, 4000), 3000);
+    assert_eq!(elapsed_ms_of_day(MS_PER_DAY - 500, 250), 750);
+    assert_eq!(elapsed_ms_of_day(7, 7), 0);
+}
+
+#[test]
+fn test_counter_conversions() {
+    assert_eq!(counts_to_ms(1024, 1024), 1000);
+    assert_eq!(ms_to_counts(1000, 1024), 1024);
+    assert_eq!(ms_to_counts(1, 1024), 1);
+    assert_eq!(counts_to_ms(5, 0), 5000);
+}
+
+#[test]
+fn test_wakeup_config_short_uses_rtc_div16() {
+    // 2048 Hz counter: 100 ms is 204.8 counts, rounded down
+    let config = wakeup_config(100, 32_768).unwrap();
+    assert_eq!(config.clock, WakeupClock::RtcDiv16);
+    assert_eq!(config.reload, 203);
+    assert!(config.period_ms(32_768) <= 100);
+}
+
+#[test]
+fn test_wakeup_config_long_uses_spre() {
+    let config = wakeup_config(60_500, 32_768).unwrap();
+    assert_eq!(config.clock, WakeupClock::Spre);
+    assert_eq!(config.reload, 59);
+    assert_eq!(config.period_ms(32_768), 60_000);
+}
+
+#[test]
+fn test_wakeup_config_limits() {
+    assert_eq!(wakeup_config(0, 32_768), None);
+    let longest = wakeup_config(u32::MAX, 32_768).unwrap();
+    assert_eq!(longest.clock, WakeupClock::Spre);
+    assert_eq!(longest.reload, u16::MAX);
+}
@@ -0,0 +1,85 @@
+use std::cell::Cell;
+use time::systick::*;
+
+/// 1 ms ticks at 16 MHz
+const RELOAD: u32 = 15_999;
+
+fn sample(ticks: u64, before: u32, pending: bool, after: u32) -> Sample {
+    Sample {
+        ticks,
+        reload: RELOAD,
+        current_before: before,
+        pending,
+        current_after: after,
+    }
+}
+
+#[test]
+fn test_join_halves() {
+    assert_eq!(join(0, 7), 7);
+    assert_eq!(join(1, 0), 1 << 32);
+    assert_eq!(join(0, u32::MAX) + 1, join(1, 0));
+}
+
+#[test]
+fn test_read_split_stable() {
+    let high = Cell::new(2u32);
+    let low = Cell::new(5u32);
+    assert_eq!(read_split(|| high.get(), || low.get()), join(2, 5));
+}
+
+#[test]
+fn test_read_split_retries_when_high_changes() {
+    let reads = Cell::new(0);
+    let high = || {
+        reads.set(reads.get() + 1);
+        // The carry lands between the first and second read of `high`
+        if reads.get() == 1 { 0 } else { 1 }
+    };
+    let low = Cell::new(u32::MAX);
+    let value = read_split(high, || {
+        let v = low.get();
+        low.set(0);
+        v
+    });
+    assert_eq!(value, join(1, 0));
+}
+
+#[test]
+fn test_micros_within_tick() {
+    assert_eq!(micros(sample(0, RELOAD, false, RELOAD), 1_000), 0);
+    assert_eq!(micros(sample(5, 8_000, false, 7_990), 1_000), 5_499);
+    assert_eq!(micros(sample(5, 0, false, 0), 1_000), 5_999);
+}
+
+#[test]
+fn test_micros_uses_second_read_when_tick_pending() {
+    // Wrapped between the reads: the first value is from the old tick,
+    // the second from the new one the counter does not show yet
+    let value = micros(sample(5, 3, true, RELOAD - 16), 1_000);
+    assert_eq!(value, 6_001);
+}
+
+#[test]
+fn test_micros_monotonic_across_tick() {
+    let mut last = 0;
+    for ticks in 0..3u64 {
+        for current in (0..=RELOAD).rev().step_by(997) {
+            let now = micros(sample(ticks, current, false, current), 1_000);
+            assert!(now >= last);
+            last = now;
+        }
+        // Interrupt pending, not served yet
+        let now = micros(sample(ticks, 0, true, RELOAD), 1_000);
+        assert!(now >= last);
+        last = now;
+    }
+}
+
+#[test]
+fn test_cycles() {
+    assert_eq!(cycles(sample(0, RELOAD, false, RELOAD)), 0);
+    assert_eq!(cycles(sample(2, RELOAD - 100, false, RELOAD - 110)), 32_100);
+    // Wrapped between the reads: the second read is in the next tick
+    assert_eq!(cycles(sample(2, 3, true, RELOAD - 16)), 48_016);
+}
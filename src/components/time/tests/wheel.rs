@@ -0,0 +1,121 @@
+use std::cell::RefCell;
+use time::wheel::*;
+
+fn noop(_: TimerId) {}
+
+/// Advance tick by tick up to `to`, collecting (tick, id) of every expiry
+fn run<const N: usize, const S: usize>(
+    wheel: &mut TimerWheel<N, S>,
+    to: u64,
+) -> Vec<(u64, TimerId)> {
+    let fired = RefCell::new(Vec::new());
+    for tick in wheel.now() + 1..=to {
+        wheel.advance(tick, |id, _| fired.borrow_mut().push((tick, id)));
+    }
+    fired.into_inner()
+}
+
+#[test]
+fn test_one_shot_fires_once() {
+    let mut wheel = TimerWheel::<4, 8>::new();
+    let id = wheel.start(3, 0, noop).unwrap();
+    assert_eq!(wheel.remaining(id), Some(3));
+
+    assert_eq!(run(&mut wheel, 10), vec![(3, id)]);
+    assert!(!wheel.is_active(id));
+    assert!(wheel.is_empty());
+}
+
+#[test]
+fn test_zero_delay_fires_on_next_tick() {
+    let mut wheel = TimerWheel::<2, 8>::new();
+    let id = wheel.start(0, 0, noop).unwrap();
+    assert_eq!(run(&mut wheel, 1), vec![(1, id)]);
+}
+
+#[test]
+fn test_periodic_rearms() {
+    let mut wheel = TimerWheel::<4, 8>::new();
+    let id = wheel.start(2, 5, noop).unwrap();
+    let fired: Vec<u64> = run(&mut wheel, 20).into_iter().map(|(t, _)| t).collect();
+    assert_eq!(fired, vec![2, 7, 12, 17]);
+    assert!(wheel.is_active(id));
+    assert_eq!(wheel.remaining(id), Some(2));
+}
+
+#[test]
+fn test_deadlines_beyond_one_revolution() {
+    let mut wheel = TimerWheel::<4, 8>::new();
+    let near = wheel.start(3, 0, noop).unwrap();
+    let far = wheel.start(19, 0, noop).unwrap();
+    assert_eq!(run(&mut wheel, 30), vec![(3, near), (19, far)]);
+}
+
+#[test]
+fn test_stop_unlinks_from_shared_bucket() {
+    let mut wheel = TimerWheel::<4, 4>::new();
+    let a = wheel.start(1, 0, noop).unwrap();
+    let b = wheel.start(5, 0, noop).unwrap();
+    let c = wheel.start(9, 0, noop).unwrap();
+    wheel.stop(b).unwrap();
+    assert_eq!(wheel.stop(b), Err(-1));
+    assert_eq!(wheel.len(), 2);
+    assert_eq!(run(&mut wheel, 10), vec![(1, a), (9, c)]);
+}
+
+#[test]
+fn test_start_fails_when_full() {
+    let mut wheel = TimerWheel::<2, 4>::new();
+    wheel.start(1, 0, noop).unwrap();
+    wheel.start(1, 0, noop).unwrap();
+    assert_eq!(wheel.start(1, 0, noop), Err(-1));
+    run(&mut wheel, 1);
+    assert!(wheel.start(1, 0, noop).is_ok());
+}
+
+#[test]
+fn test_large_step_fires_each_timer_once() {
+    let mut wheel = TimerWheel::<4, 8>::new();
+    let one_shot = wheel.start(3, 0, noop).unwrap();
+    let periodic = wheel.start(2, 4, noop).unwrap();
+    let later = wheel.start(150, 0, noop).unwrap();
+
+    let mut fired = Vec::new();
+    wheel.advance(100, |id, _| fired.push(id));
+    fired.sort();
+    assert_eq!(fired, vec![one_shot, periodic]);
+    assert_eq!(wheel.now(), 100);
+    // Missed periods are skipped, the next expiry stays on the period grid
+    assert_eq!(wheel.remaining(periodic), Some(2));
+    assert_eq!(wheel.remaining(later), Some(50));
+}
+
+#[test]
+fn test_fire_passes_callback() {
+    fn mark(id: TimerId) {
+        MARKED.with(|m| m.borrow_mut().push(id));
+    }
+    thread_local! {
+        static MARKED: RefCell<Vec<TimerId>> = const { RefCell::new(Vec::new()) };
+    }
+
+    let mut wheel = TimerWheel::<2, 4>::new();
+    let id = wheel.start(1, 0, mark).unwrap();
+    wheel.advance(1, |id, callback| callback(id));
+    MARKED.with(|m| assert_eq!(*m.borrow(), vec![id]));
+}
+
+#[test]
+fn test_next_expiry() {
+    let mut wheel = TimerWheel::<4, 8>::new();
+    assert_eq!(wheel.next_expiry(), None);
+    let a = wheel.start(20, 0, noop).unwrap();
+    wheel.start(5, 10, noop).unwrap();
+    assert_eq!(wheel.next_expiry(), Some(5));
+    run(&mut wheel, 5);
+    assert_eq!(wheel.next_expiry(), Some(15));
+    wheel.advance(16, |_, _| {});
+    assert_eq!(wheel.next_expiry(), Some(20));
+    wheel.stop(a).unwrap();
+    assert_eq!(wheel.next_expiry(), Some(25));
+}
@@ -0,0 +1,136 @@
+//! Host-side decoder of the profiling records emitted by the firmware.
+//!
+//! ```text
+//! trace-decode [--itm PORT] [--quiet] [FILE]
+//! ```
+//!
+//! Reads FILE, or stdin, as the raw bytes of the RTT "Profile" channel, or
+//! with `--itm` as an SWO capture from which stimulus port PORT is kept.
+//! Prints every span as it completes, then statistics per span name.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::process::ExitCode;
+use trace::itm::{Demux, PORTS};
+use trace::record::{Record, Records};
+use trace::stats::Collector;
+
+struct Options {
+    itm_port: Option<u8>,
+    quiet: bool,
+    path: Option<String>,
+}
+
+fn usage() -> ExitCode {
+    eprintln!("usage: trace-decode [--itm PORT] [--quiet] [FILE]");
+    ExitCode::FAILURE
+}
+
+fn parse_args() -> Option<Options> {
+    let mut options = Options {
+        itm_port: None,
+        quiet: false,
+        path: None,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--itm" => {
+                let port = args.next()?.parse().ok().filter(|&p| p < PORTS)?;
+                options.itm_port = Some(port);
+            }
+            "--quiet" => options.quiet = true,
+            _ if arg.starts_with("--") || options.path.is_some() => return None,
+            _ => options.path = Some(arg),
+        }
+    }
+    Some(options)
+}
+
+fn read_input(path: Option<&str>) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match path {
+        Some(path) => bytes = std::fs::read(path)?,
+        None => {
+            io::stdin().read_to_end(&mut bytes)?;
+        }
+    }
+    Ok(bytes)
+}
+
+fn format_cycles(collector: &Collector, cycles: u64) -> String {
+    match collector.micros(cycles) {
+        Some(us) => format!("{cycles} cycles ({us} us)"),
+        None => format!("{cycles} cycles"),
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(options) = parse_args() else {
+        return usage();
+    };
+    let input = match read_input(options.path.as_deref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("trace-decode: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let bytes = match options.itm_port {
+        Some(port) => {
+            let mut demux = Demux::new(port);
+            input.iter().filter_map(|&b| demux.push(b)).collect()
+        }
+        None => input,
+    };
+
+    let mut names = BTreeMap::new();
+    let mut collector = Collector::new();
+    let mut records = Records::new(&bytes);
+    for record in records.by_ref() {
+        if let Record::Name { id, name } = record {
+            names.insert(id, name.to_string());
+        }
+        if let Some(span) = collector.push(&record)
+            && !options.quiet
+        {
+            let name = names.get(&span.id).map_or("?", String::as_str);
+            println!(
+                "{:>10} {name}: {}",
+                span.start,
+                format_cycles(&collector, span.cycles as u64)
+            );
+        }
+    }
+
+    println!(
+        "{:<32} {:>8} {:>12} {:>12} {:>12}",
+        "span", "count", "min", "mean", "max"
+    );
+    for (&id, name) in &names {
+        let stats = collector.stats(id);
+        if stats.count == 0 {
+            continue;
+        }
+        let value = |cycles: u32| match collector.micros(cycles as u64) {
+            Some(us) => format!("{us} us"),
+            None => format!("{cycles} cyc"),
+        };
+        println!(
+            "{name:<32} {:>8} {:>12} {:>12} {:>12}",
+            stats.count,
+            value(stats.min),
+            value(stats.mean()),
+            value(stats.max)
+        );
+    }
+
+    let skipped = records.skipped();
+    let incomplete = bytes.len() - records.consumed();
+    if skipped > 0 || incomplete > 0 {
+        eprintln!(
+            "trace-decode: {skipped} bytes skipped, {incomplete} bytes of an incomplete record"
+        );
+    }
+    ExitCode::SUCCESS
+}
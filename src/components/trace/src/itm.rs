@@ -0,0 +1,100 @@
+//! Demultiplexing of the ITM packet stream captured from SWO.
+//!
+//! Every write to a stimulus port goes out as a source packet: a header
+//! byte with the port number and payload size, then 1, 2 or 4 payload
+//! bytes. The stream also carries synchronization, overflow, timestamp and
+//! hardware (DWT) packets, which are skipped.
+
+/// Number of stimulus ports
+pub const PORTS: u8 = 32;
+
+/// Zero bytes before the 0x80 that ends a synchronization packet
+const SYNC_ZEROS: u8 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Header,
+    /// `remaining` payload bytes, kept if they are from the selected port
+    Payload {
+        remaining: u8,
+        keep: bool,
+    },
+    /// Continuation bytes of a protocol packet until one has bit 7 clear
+    Continuation,
+}
+
+/// Extracts the payload written to one stimulus port
+#[derive(Debug, Clone)]
+pub struct Demux {
+    port: u8,
+    state: State,
+    zeros: u8,
+}
+
+impl Demux {
+    /// Keep the payload of stimulus port `port`
+    pub fn new(port: u8) -> Self {
+        Self {
+            port,
+            state: State::Header,
+            zeros: 0,
+        }
+    }
+
+    /// Feed one byte of the stream. Returns it if it is payload of the
+    /// selected port.
+    pub fn push(&mut self, byte: u8) -> Option<u8> {
+        match self.state {
+            State::Payload { remaining, keep } => {
+                self.state = if remaining > 1 {
+                    State::Payload {
+                        remaining: remaining - 1,
+                        keep,
+                    }
+                } else {
+                    State::Header
+                };
+                keep.then_some(byte)
+            }
+            State::Continuation => {
+                if byte & 0x80 == 0 {
+                    self.state = State::Header;
+                }
+                None
+            }
+            State::Header => {
+                self.header(byte);
+                None
+            }
+        }
+    }
+
+    fn header(&mut self, byte: u8) {
+        let zeros = self.zeros;
+        self.zeros = if byte == 0 {
+            zeros.saturating_add(1)
+        } else {
+            0
+        };
+        match byte {
+            // Synchronization: zeros, then 0x80 ends it
+            0x00 => {}
+            0x80 if zeros >= SYNC_ZEROS => {}
+            // Overflow
+            0x70 => {}
+            _ if byte & 0x03 != 0 => {
+                let remaining = match byte & 0x03 {
+                    1 => 1,
+                    2 => 2,
+                    _ => 4,
+                };
+                // Bit 2 clear: software source, a stimulus port
+                let keep = byte & 0x04 == 0 && byte >> 3 == self.port;
+                self.state = State::Payload { remaining, keep };
+            }
+            // Timestamp and extension packets, continued while bit 7 is set
+            _ if byte & 0x80 != 0 => self.state = State::Continuation,
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,6 @@
+#![no_std]
+
+pub mod itm;
+pub mod names;
+pub mod record;
+pub mod stats;
@@ -0,0 +1,49 @@
+//! Ids of the span names, assigned on first use so the names themselves
+//! are sent only once.
+
+/// Names by id, up to `N` of them
+#[derive(Debug, Clone)]
+pub struct Names<const N: usize> {
+    names: [Option<&'static str>; N],
+}
+
+impl<const N: usize> Names<N> {
+    pub const fn new() -> Self {
+        Self { names: [None; N] }
+    }
+
+    /// The id of `name`, assigned if it is new, and whether it is new.
+    /// `None` once `N` names are known.
+    pub fn id(&mut self, name: &'static str) -> Option<(u8, bool)> {
+        for (id, slot) in self.names.iter_mut().enumerate().take(u8::MAX as usize + 1) {
+            match slot {
+                Some(known) if *known == name => return Some((id as u8, false)),
+                Some(_) => {}
+                None => {
+                    *slot = Some(name);
+                    return Some((id as u8, true));
+                }
+            }
+        }
+        None
+    }
+
+    /// The name of `id`
+    pub fn name(&self, id: u8) -> Option<&'static str> {
+        self.names.get(id as usize).copied().flatten()
+    }
+
+    /// Known names with their ids, to send them again to a late reader
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &'static str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .filter_map(|(id, name)| name.map(|name| (id as u8, name)))
+    }
+}
+
+impl<const N: usize> Default for Names<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,169 @@
+//! Profiling records as emitted on the trace channel, an ITM stimulus port
+//! or an RTT up channel.
+//!
+//! Every record starts with a tag byte, multi-byte values are little
+//! endian. A span name goes out once, the first time it is used, and spans
+//! then refer to it by id. Timestamps are the low 32 bits of the cycle
+//! counter, so a span is measured correctly as long as it is shorter than
+//! one wrap of the counter (about 25 s at 168 MHz).
+
+/// Errors are i32 codes as in the firmware
+pub type Error = i32;
+
+/// `[tag, hz: u32]`, the frequency of the cycle counter
+pub const TAG_CLOCK: u8 = 0x01;
+/// `[tag, id: u8, len: u8, name: [u8; len]]`, a span name and its id
+pub const TAG_NAME: u8 = 0x02;
+/// `[tag, id: u8, cycles: u32]`, a span was entered
+pub const TAG_ENTER: u8 = 0x03;
+/// `[tag, id: u8, cycles: u32]`, a span was left
+pub const TAG_EXIT: u8 = 0x04;
+
+/// Longest span name, longer names are cut
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Longest encoded record, a name record
+pub const MAX_RECORD_LEN: usize = 3 + MAX_NAME_LEN;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Record<'a> {
+    Clock { hz: u32 },
+    Name { id: u8, name: &'a str },
+    Enter { id: u8, cycles: u32 },
+    Exit { id: u8, cycles: u32 },
+}
+
+/// `name` cut to at most `MAX_NAME_LEN` bytes on a character boundary
+fn cut(name: &str) -> &str {
+    let mut len = name.len().min(MAX_NAME_LEN);
+    while !name.is_char_boundary(len) {
+        len -= 1;
+    }
+    &name[..len]
+}
+
+impl Record<'_> {
+    /// Encode into `buf`, returning the bytes of the record
+    pub fn encode<'b>(&self, buf: &'b mut [u8; MAX_RECORD_LEN]) -> &'b [u8] {
+        let len = match *self {
+            Record::Clock { hz } => {
+                buf[0] = TAG_CLOCK;
+                buf[1..5].copy_from_slice(&hz.to_le_bytes());
+                5
+            }
+            Record::Name { id, name } => {
+                let name = cut(name);
+                buf[0] = TAG_NAME;
+                buf[1] = id;
+                buf[2] = name.len() as u8;
+                buf[3..3 + name.len()].copy_from_slice(name.as_bytes());
+                3 + name.len()
+            }
+            Record::Enter { id, cycles } | Record::Exit { id, cycles } => {
+                buf[0] = if matches!(self, Record::Enter { .. }) {
+                    TAG_ENTER
+                } else {
+                    TAG_EXIT
+                };
+                buf[1] = id;
+                buf[2..6].copy_from_slice(&cycles.to_le_bytes());
+                6
+            }
+        };
+        &buf[..len]
+    }
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+/// Decode the record at the start of `bytes`, with its length. Ok(None) if
+/// `bytes` ends within the record, Err(-1) if it does not start with a
+/// valid record.
+pub fn decode(bytes: &[u8]) -> Result<Option<(Record<'_>, usize)>, Error> {
+    let Some(&tag) = bytes.first() else {
+        return Ok(None);
+    };
+    let len = match tag {
+        TAG_CLOCK => 5,
+        TAG_NAME => match bytes.get(2) {
+            Some(&len) if len as usize > MAX_NAME_LEN => return Err(-1),
+            Some(&len) => 3 + len as usize,
+            None => return Ok(None),
+        },
+        TAG_ENTER | TAG_EXIT => 6,
+        _ => return Err(-1),
+    };
+    if bytes.len() < len {
+        return Ok(None);
+    }
+    let record = match tag {
+        TAG_CLOCK => Record::Clock {
+            hz: u32_at(bytes, 1),
+        },
+        TAG_NAME => Record::Name {
+            id: bytes[1],
+            name: core::str::from_utf8(&bytes[3..len]).map_err(|_| -1)?,
+        },
+        TAG_ENTER => Record::Enter {
+            id: bytes[1],
+            cycles: u32_at(bytes, 2),
+        },
+        _ => Record::Exit {
+            id: bytes[1],
+            cycles: u32_at(bytes, 2),
+        },
+    };
+    Ok(Some((record, len)))
+}
+
+/// The records of a byte stream. Bytes that do not start a valid record
+/// are skipped, as after bytes lost on the way; iteration ends at the end
+/// of the stream or within an incomplete record.
+pub struct Records<'a> {
+    bytes: &'a [u8],
+    consumed: usize,
+    skipped: usize,
+}
+
+impl<'a> Records<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            consumed: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Bytes decoded or skipped so far; the rest starts an incomplete
+    /// record once iteration ended
+    pub fn consumed(&self) -> usize {
+        self.consumed
+    }
+
+    /// Bytes skipped so far
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Record<'a>> {
+        loop {
+            match decode(&self.bytes[self.consumed..]) {
+                Ok(Some((record, len))) => {
+                    self.consumed += len;
+                    return Some(record);
+                }
+                Ok(None) => return None,
+                Err(_) => {
+                    self.consumed += 1;
+                    self.skipped += 1;
+                }
+            }
+        }
+    }
+}
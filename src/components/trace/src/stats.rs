@@ -0,0 +1,103 @@
+//! Span durations and per-name statistics, computed from the records on
+//! the host.
+//!
+//! Each id has at most one open span: spans of different names may nest
+//! or interleave (a span in an interrupt handler), but a span entered
+//! again before it was left restarts.
+
+use crate::record::Record;
+
+/// Statistics of the spans of one name, in cycles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpanStats {
+    pub count: u32,
+    pub total: u64,
+    pub min: u32,
+    pub max: u32,
+}
+
+impl SpanStats {
+    /// Mean duration in cycles, 0 without spans
+    pub fn mean(&self) -> u32 {
+        if self.count == 0 {
+            0
+        } else {
+            (self.total / self.count as u64) as u32
+        }
+    }
+
+    fn add(&mut self, cycles: u32) {
+        if self.count == 0 || cycles < self.min {
+            self.min = cycles;
+        }
+        self.max = self.max.max(cycles);
+        self.count += 1;
+        self.total += cycles as u64;
+    }
+}
+
+/// A span that was left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub id: u8,
+    pub start: u32,
+    pub cycles: u32,
+}
+
+/// Collects the spans of a record stream
+#[derive(Debug, Clone)]
+pub struct Collector {
+    hz: Option<u32>,
+    open: [Option<u32>; 256],
+    stats: [SpanStats; 256],
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            hz: None,
+            open: [None; 256],
+            stats: [SpanStats::default(); 256],
+        }
+    }
+
+    /// Account for `record`. Returns the span it completes, if any; an
+    /// exit without an enter is ignored.
+    pub fn push(&mut self, record: &Record) -> Option<Span> {
+        match *record {
+            Record::Clock { hz } => self.hz = Some(hz),
+            Record::Name { .. } => {}
+            Record::Enter { id, cycles } => self.open[id as usize] = Some(cycles),
+            Record::Exit { id, cycles } => {
+                let start = self.open[id as usize].take()?;
+                let cycles = cycles.wrapping_sub(start);
+                self.stats[id as usize].add(cycles);
+                return Some(Span { id, start, cycles });
+            }
+        }
+        None
+    }
+
+    /// Frequency of the cycle counter, once received
+    pub fn hz(&self) -> Option<u32> {
+        self.hz
+    }
+
+    /// Statistics of the spans of `id`
+    pub fn stats(&self, id: u8) -> SpanStats {
+        self.stats[id as usize]
+    }
+
+    /// `cycles` in microseconds, once the frequency is known
+    pub fn micros(&self, cycles: u64) -> Option<u64> {
+        self.hz
+            .filter(|&hz| hz > 0)
+            .map(|hz| cycles * 1_000_000 / hz as u64)
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,72 @@
+use trace::itm::*;
+
+fn demux(port: u8, stream: &[u8]) -> Vec<u8> {
+    let mut demux = Demux::new(port);
+    stream.iter().filter_map(|&b| demux.push(b)).collect()
+}
+
+/// Header of a stimulus port write of `size` bytes
+fn header(port: u8, size: u8) -> u8 {
+    port << 3 | [0, 1, 2, 0, 3][size as usize]
+}
+
+#[test]
+fn test_selects_port() {
+    let stream = [
+        header(1, 1),
+        0xA1,
+        header(0, 1),
+        b'x',
+        header(1, 4),
+        1,
+        2,
+        3,
+        4,
+        header(31, 2),
+        0xB0,
+        0xB1,
+        header(1, 2),
+        5,
+        6,
+    ];
+    assert_eq!(demux(1, &stream), [0xA1, 1, 2, 3, 4, 5, 6]);
+    assert_eq!(demux(0, &stream), [b'x']);
+    assert_eq!(demux(31, &stream), [0xB0, 0xB1]);
+}
+
+#[test]
+fn test_skips_protocol_and_hardware_packets() {
+    let stream = [
+        // Synchronization
+        0x00,
+        0x00,
+        0x00,
+        0x00,
+        0x00,
+        0x80,
+        header(1, 1),
+        7,
+        // Overflow
+        0x70,
+        // Local timestamp with continuation bytes, payload value headers
+        0xC0,
+        0x81,
+        0x09,
+        // Short local timestamp
+        0x10,
+        // Hardware source (DWT) packet, port 1 but not a stimulus port
+        0x08 | 0x04 | 0x02,
+        header(1, 1),
+        0xAA,
+        header(1, 1),
+        8,
+    ];
+    assert_eq!(demux(1, &stream), [7, 8]);
+}
+
+#[test]
+fn test_payload_bytes_look_like_headers() {
+    // Payload values are not taken as headers
+    let stream = [header(1, 4), 0x00, 0x80, 0x70, 0xC0, header(1, 1), 9];
+    assert_eq!(demux(1, &stream), [0x00, 0x80, 0x70, 0xC0, 9]);
+}
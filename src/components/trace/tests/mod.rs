@@ -0,0 +1,4 @@
+pub mod itm;
+pub mod names;
+pub mod record;
+pub mod stats;
@@ -0,0 +1,24 @@
+use trace::names::*;
+
+#[test]
+fn test_ids_assigned_once() {
+    let mut names = Names::<4>::new();
+    assert_eq!(names.id("loop"), Some((0, true)));
+    assert_eq!(names.id("usart"), Some((1, true)));
+    assert_eq!(names.id("loop"), Some((0, false)));
+    assert_eq!(names.name(1), Some("usart"));
+    assert_eq!(names.name(2), None);
+    assert_eq!(
+        names.iter().collect::<Vec<_>>(),
+        [(0, "loop"), (1, "usart")]
+    );
+}
+
+#[test]
+fn test_full() {
+    let mut names = Names::<2>::new();
+    names.id("a").unwrap();
+    names.id("b").unwrap();
+    assert_eq!(names.id("c"), None);
+    assert_eq!(names.id("b"), Some((1, false)));
+}
@@ -0,0 +1,83 @@
+use trace::record::*;
+
+fn roundtrip(record: Record) {
+    let mut buf = [0; MAX_RECORD_LEN];
+    let bytes = record.encode(&mut buf);
+    assert_eq!(decode(bytes), Ok(Some((record, bytes.len()))));
+}
+
+#[test]
+fn test_roundtrip() {
+    roundtrip(Record::Clock { hz: 168_000_000 });
+    roundtrip(Record::Name {
+        id: 3,
+        name: "blink",
+    });
+    roundtrip(Record::Enter {
+        id: 3,
+        cycles: 0x1234_5678,
+    });
+    roundtrip(Record::Exit {
+        id: 255,
+        cycles: u32::MAX,
+    });
+}
+
+#[test]
+fn test_encoding() {
+    let mut buf = [0; MAX_RECORD_LEN];
+    let bytes = Record::Enter {
+        id: 2,
+        cycles: 0x0403_0201,
+    }
+    .encode(&mut buf);
+    assert_eq!(bytes, &[TAG_ENTER, 2, 1, 2, 3, 4]);
+    let bytes = Record::Name { id: 1, name: "ab" }.encode(&mut buf);
+    assert_eq!(bytes, &[TAG_NAME, 1, 2, b'a', b'b']);
+}
+
+#[test]
+fn test_long_name_cut_on_char_boundary() {
+    let mut buf = [0; MAX_RECORD_LEN];
+    let name = "é".repeat(MAX_NAME_LEN);
+    let bytes = Record::Name { id: 0, name: &name }.encode(&mut buf);
+    match decode(bytes) {
+        Ok(Some((Record::Name { name: cut, .. }, _))) => {
+            assert_eq!(cut.len(), MAX_NAME_LEN);
+            assert!(name.starts_with(cut));
+        }
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn test_incomplete_and_invalid() {
+    assert_eq!(decode(&[]), Ok(None));
+    assert_eq!(decode(&[TAG_EXIT, 1, 2]), Ok(None));
+    assert_eq!(decode(&[TAG_NAME, 1]), Ok(None));
+    assert_eq!(decode(&[TAG_NAME, 1, 3, b'a']), Ok(None));
+    assert_eq!(decode(&[0xFF, 0, 0, 0, 0, 0]), Err(-1));
+    assert_eq!(decode(&[TAG_NAME, 1, MAX_NAME_LEN as u8 + 1]), Err(-1));
+    assert_eq!(decode(&[TAG_NAME, 1, 1, 0xFF]), Err(-1));
+}
+
+#[test]
+fn test_records_skip_garbage_and_stop_at_incomplete() {
+    let mut stream = vec![0xEE, 0xEE];
+    let mut buf = [0; MAX_RECORD_LEN];
+    stream.extend_from_slice(Record::Enter { id: 1, cycles: 10 }.encode(&mut buf));
+    stream.extend_from_slice(Record::Exit { id: 1, cycles: 20 }.encode(&mut buf));
+    stream.extend_from_slice(&[TAG_EXIT, 1]);
+
+    let mut records = Records::new(&stream);
+    let decoded: Vec<_> = records.by_ref().collect();
+    assert_eq!(
+        decoded,
+        [
+            Record::Enter { id: 1, cycles: 10 },
+            Record::Exit { id: 1, cycles: 20 }
+        ]
+    );
+    assert_eq!(records.skipped(), 2);
+    assert_eq!(stream.len() - records.consumed(), 2);
+}
@@ -0,0 +1,66 @@
+use trace::record::Record;
+use trace::stats::*;
+
+#[test]
+fn test_spans() {
+    let mut collector = Collector::new();
+    assert_eq!(collector.push(&Record::Enter { id: 1, cycles: 100 }), None);
+    // Another span nested within
+    assert_eq!(collector.push(&Record::Enter { id: 2, cycles: 110 }), None);
+    assert_eq!(
+        collector.push(&Record::Exit { id: 2, cycles: 130 }),
+        Some(Span {
+            id: 2,
+            start: 110,
+            cycles: 20
+        })
+    );
+    assert_eq!(
+        collector.push(&Record::Exit { id: 1, cycles: 400 }),
+        Some(Span {
+            id: 1,
+            start: 100,
+            cycles: 300
+        })
+    );
+    // Exit without enter
+    assert_eq!(collector.push(&Record::Exit { id: 1, cycles: 500 }), None);
+}
+
+#[test]
+fn test_span_across_counter_wrap() {
+    let mut collector = Collector::new();
+    collector.push(&Record::Enter {
+        id: 0,
+        cycles: u32::MAX - 9,
+    });
+    let span = collector.push(&Record::Exit { id: 0, cycles: 5 }).unwrap();
+    assert_eq!(span.cycles, 15);
+}
+
+#[test]
+fn test_stats() {
+    let mut collector = Collector::new();
+    for (start, end) in [(0, 10), (100, 130), (200, 220)] {
+        collector.push(&Record::Enter {
+            id: 4,
+            cycles: start,
+        });
+        collector.push(&Record::Exit { id: 4, cycles: end });
+    }
+    let stats = collector.stats(4);
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.total, 60);
+    assert_eq!((stats.min, stats.mean(), stats.max), (10, 20, 30));
+    assert_eq!(collector.stats(5), SpanStats::default());
+    assert_eq!(collector.stats(5).mean(), 0);
+}
+
+#[test]
+fn test_micros_needs_clock() {
+    let mut collector = Collector::new();
+    assert_eq!(collector.micros(168), None);
+    collector.push(&Record::Clock { hz: 168_000_000 });
+    assert_eq!(collector.hz(), Some(168_000_000));
+    assert_eq!(collector.micros(168_000), Some(1_000));
+}
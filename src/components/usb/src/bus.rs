@@ -0,0 +1,60 @@
+//! What the device stack needs from a device controller: endpoints that
+//! move one packet at a time, and the events of the bus.
+
+use crate::endpoint::EndpointType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The host reset the bus: the address is 0 and only endpoint 0 is open
+    Reset,
+    /// A SETUP packet arrived on endpoint 0
+    Setup([u8; 8]),
+    /// A packet arrived on the OUT endpoint of this address, to be read
+    Out(u8),
+    /// The packet written to the IN endpoint of this address was sent
+    InComplete(u8),
+    Suspend,
+    Resume,
+}
+
+/// A device controller. Endpoints are named by address, direction bit
+/// included, and each holds at most one packet in either direction; the
+/// host is answered NAK while an IN endpoint is empty or an OUT endpoint
+/// is full.
+pub trait Bus {
+    /// Whether the controller takes the address from SET_ADDRESS before
+    /// the status stage of the request, as OTG cores do, rather than after
+    const SET_ADDRESS_BEFORE_STATUS: bool = false;
+
+    fn set_address(&mut self, address: u8);
+
+    /// Open an endpoint. Returns Err(-1) for an address or packet size the
+    /// controller does not have.
+    fn open(
+        &mut self,
+        address: u8,
+        endpoint_type: EndpointType,
+        max_packet_size: u16,
+    ) -> Result<(), i32>;
+
+    fn close(&mut self, address: u8);
+
+    /// Queue a packet, possibly empty, on an IN endpoint. Returns Err(-5)
+    /// while the previous one has not gone out, Err(-1) if the endpoint is
+    /// not open or `data` is longer than a packet.
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), i32>;
+
+    /// Take the packet that arrived on an OUT endpoint and return its
+    /// length. Returns Err(-5) if none is waiting, Err(-1) if `buf` is
+    /// shorter than the packet, which is then dropped.
+    fn read(&mut self, address: u8, buf: &mut [u8]) -> Result<usize, i32>;
+
+    /// Stall or clear the stall of an endpoint. A SETUP packet clears the
+    /// stall of endpoint 0 by itself.
+    fn set_stalled(&mut self, address: u8, stalled: bool);
+
+    fn is_stalled(&self, address: u8) -> bool;
+
+    /// Take the next event
+    fn poll(&mut self) -> Option<Event>;
+}
@@ -0,0 +1,361 @@
+//! CDC-ACM, the class of a virtual serial port: a communication interface
+//! with a notification endpoint, taking the line settings from the host,
+//! and a data interface with a bulk endpoint each way.
+//!
+//! Hosts bind their stock driver to it (`/dev/ttyACM*`, `usbser.sys`).
+//! The line coding is only recorded, there being no real line; DTR tells
+//! whether a terminal has the port open. Bytes go through a FIFO each way,
+//! and the OUT endpoint is left full, so the host waits, while the receive
+//! FIFO has no room for a packet.
+
+use crate::bus::Bus;
+use crate::descriptor::{ConfigBuilder, kind};
+use crate::device::Class;
+use crate::endpoint::{self, EndpointType};
+use crate::setup::{Kind, Recipient, SetupPacket};
+
+/// Class-specific requests
+pub mod request {
+    pub const SET_LINE_CODING: u8 = 0x20;
+    pub const GET_LINE_CODING: u8 = 0x21;
+    pub const SET_CONTROL_LINE_STATE: u8 = 0x22;
+    pub const SEND_BREAK: u8 = 0x23;
+}
+
+pub const CLASS_COMMUNICATION: u8 = 0x02;
+pub const CLASS_DATA: u8 = 0x0A;
+pub const SUBCLASS_ACM: u8 = 0x02;
+
+/// Packet size of the bulk endpoints
+pub const PACKET_SIZE: usize = 64;
+
+/// Bytes each FIFO holds
+pub const FIFO_SIZE: usize = 256;
+
+const NOTIFY_PACKET_SIZE: u16 = 8;
+/// Frames between polls of the notification endpoint, the longest there is
+const NOTIFY_INTERVAL: u8 = 255;
+
+/// Functional descriptor subtypes
+const HEADER: u8 = 0x00;
+const CALL_MANAGEMENT: u8 = 0x01;
+const ACM: u8 = 0x02;
+const UNION: u8 = 0x06;
+
+/// bmCapabilities of the ACM descriptor: line coding and control line
+/// state requests
+const ACM_CAPABILITIES: u8 = 0x02;
+
+/// Line settings the host asks for, as in GET_LINE_CODING
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCoding {
+    pub baudrate: u32,
+    /// 0: 1 stop bit, 1: 1.5, 2: 2
+    pub stop_bits: u8,
+    /// 0: none, 1: odd, 2: even, 3: mark, 4: space
+    pub parity: u8,
+    pub data_bits: u8,
+}
+
+impl LineCoding {
+    pub const SIZE: usize = 7;
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let [b0, b1, b2, b3] = self.baudrate.to_le_bytes();
+        [b0, b1, b2, b3, self.stop_bits, self.parity, self.data_bits]
+    }
+
+    /// Returns Err(-1) unless `bytes` is seven bytes long
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        let bytes: &[u8; Self::SIZE] = bytes.try_into().map_err(|_| -1)?;
+        Ok(Self {
+            baudrate: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            stop_bits: bytes[4],
+            parity: bytes[5],
+            data_bits: bytes[6],
+        })
+    }
+}
+
+impl Default for LineCoding {
+    /// 115200 8N1
+    fn default() -> Self {
+        Self {
+            baudrate: 115_200,
+            stop_bits: 0,
+            parity: 0,
+            data_bits: 8,
+        }
+    }
+}
+
+struct Fifo {
+    buf: [u8; FIFO_SIZE],
+    head: usize,
+    len: usize,
+}
+
+impl Fifo {
+    const fn new() -> Self {
+        Self {
+            buf: [0; FIFO_SIZE],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn free(&self) -> usize {
+        FIFO_SIZE - self.len
+    }
+
+    fn push(&mut self, data: &[u8]) -> usize {
+        let count = data.len().min(self.free());
+        for &byte in &data[..count] {
+            self.buf[(self.head + self.len) % FIFO_SIZE] = byte;
+            self.len += 1;
+        }
+        count
+    }
+
+    fn pop(&mut self, out: &mut [u8]) -> usize {
+        let count = out.len().min(self.len);
+        for byte in &mut out[..count] {
+            *byte = self.buf[self.head];
+            self.head = (self.head + 1) % FIFO_SIZE;
+            self.len -= 1;
+        }
+        count
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+pub struct CdcAcm {
+    /// Interface numbers, assigned by `describe`
+    comm: u8,
+    data: u8,
+    notify_address: u8,
+    in_address: u8,
+    out_address: u8,
+    line_coding: LineCoding,
+    dtr: bool,
+    rts: bool,
+    configured: bool,
+    rx: Fifo,
+    tx: Fifo,
+    /// A packet is on the IN endpoint
+    in_busy: bool,
+    /// The last packet sent was full, so a transfer ending there needs an
+    /// empty one to close it
+    in_full: bool,
+    /// A packet waits on the OUT endpoint for room in `rx`
+    out_pending: bool,
+}
+
+impl CdcAcm {
+    /// A port with its notification endpoint `notify_number` and its bulk
+    /// endpoints `data_number`, IN and OUT
+    pub const fn new(notify_number: u8, data_number: u8) -> Self {
+        Self {
+            comm: 0,
+            data: 0,
+            notify_address: endpoint::DIRECTION_MASK | (notify_number & endpoint::NUMBER_MASK),
+            in_address: endpoint::DIRECTION_MASK | (data_number & endpoint::NUMBER_MASK),
+            out_address: data_number & endpoint::NUMBER_MASK,
+            line_coding: LineCoding {
+                baudrate: 115_200,
+                stop_bits: 0,
+                parity: 0,
+                data_bits: 8,
+            },
+            dtr: false,
+            rts: false,
+            configured: false,
+            rx: Fifo::new(),
+            tx: Fifo::new(),
+            in_busy: false,
+            in_full: false,
+            out_pending: false,
+        }
+    }
+
+    /// Add the interface association, the two interfaces with their
+    /// functional descriptors and the three endpoints to a configuration
+    pub fn describe(&mut self, config: &mut ConfigBuilder) -> Result<(), i32> {
+        let comm = config.next_interface();
+        config.association(comm, 2, CLASS_COMMUNICATION, SUBCLASS_ACM, 0)?;
+        self.comm = config.interface(CLASS_COMMUNICATION, SUBCLASS_ACM, 0)?;
+        self.data = self.comm + 1;
+        // CDC 1.10
+        config.descriptor(kind::CS_INTERFACE, &[HEADER, 0x10, 0x01])?;
+        config.descriptor(kind::CS_INTERFACE, &[CALL_MANAGEMENT, 0x00, self.data])?;
+        config.descriptor(kind::CS_INTERFACE, &[ACM, ACM_CAPABILITIES])?;
+        config.descriptor(kind::CS_INTERFACE, &[UNION, self.comm, self.data])?;
+        config.endpoint(
+            self.notify_address,
+            EndpointType::Interrupt,
+            NOTIFY_PACKET_SIZE,
+            NOTIFY_INTERVAL,
+        )?;
+        config.interface(CLASS_DATA, 0, 0)?;
+        config.endpoint(self.out_address, EndpointType::Bulk, PACKET_SIZE as u16, 0)?;
+        config.endpoint(self.in_address, EndpointType::Bulk, PACKET_SIZE as u16, 0)?;
+        Ok(())
+    }
+
+    pub fn line_coding(&self) -> LineCoding {
+        self.line_coding
+    }
+
+    pub fn dtr(&self) -> bool {
+        self.dtr
+    }
+
+    pub fn rts(&self) -> bool {
+        self.rts
+    }
+
+    /// Whether the device is configured and a terminal has the port open
+    pub fn is_open(&self) -> bool {
+        self.configured && self.dtr
+    }
+
+    /// Take received bytes into `buf` and return how many
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        self.rx.pop(buf)
+    }
+
+    /// Queue bytes to send and return how many fit; `poll` sends them.
+    /// Nothing is queued while the device is not configured.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        if !self.configured {
+            return 0;
+        }
+        self.tx.push(data)
+    }
+
+    /// Bytes queued and not yet sent
+    pub fn pending(&self) -> usize {
+        self.tx.len
+    }
+
+    /// Send queued bytes and take a packet left waiting for room
+    pub fn poll<B: Bus>(&mut self, bus: &mut B) {
+        if !self.configured {
+            return;
+        }
+        if self.out_pending && self.rx.free() >= PACKET_SIZE {
+            self.receive(bus);
+        }
+        if self.in_busy {
+            return;
+        }
+        if self.tx.len == 0 {
+            if self.in_full {
+                // Close the transfer for the host
+                if bus.write(self.in_address, &[]).is_ok() {
+                    self.in_busy = true;
+                    self.in_full = false;
+                }
+            }
+            return;
+        }
+        let mut packet = [0; PACKET_SIZE];
+        let head = self.tx.head;
+        let len = self.tx.pop(&mut packet);
+        if bus.write(self.in_address, &packet[..len]).is_ok() {
+            self.in_busy = true;
+            self.in_full = len == PACKET_SIZE;
+        } else {
+            // Put the bytes back
+            self.tx.head = head;
+            self.tx.len += len;
+        }
+    }
+
+    fn receive<B: Bus>(&mut self, bus: &mut B) {
+        let mut packet = [0; PACKET_SIZE];
+        // A packet too long for an endpoint of this size is dropped
+        if let Ok(len) = bus.read(self.out_address, &mut packet) {
+            self.rx.push(&packet[..len]);
+        }
+        self.out_pending = false;
+    }
+
+    fn is_mine(&self, request: &SetupPacket) -> bool {
+        request.kind() == Kind::Class
+            && request.recipient() == Recipient::Interface
+            && request.index == self.comm as u16
+    }
+}
+
+impl<B: Bus> Class<B> for CdcAcm {
+    fn reset(&mut self) {
+        self.configured = false;
+        self.dtr = false;
+        self.rts = false;
+        self.rx.clear();
+        self.tx.clear();
+        self.in_busy = false;
+        self.in_full = false;
+        self.out_pending = false;
+    }
+
+    fn configured(&mut self, _bus: &mut B) {
+        self.configured = true;
+    }
+
+    fn control_in(&mut self, request: &SetupPacket, buf: &mut [u8]) -> Result<usize, i32> {
+        if !self.is_mine(request) {
+            return Err(-2);
+        }
+        match request.request {
+            request::GET_LINE_CODING => {
+                buf[..LineCoding::SIZE].copy_from_slice(&self.line_coding.encode());
+                Ok(LineCoding::SIZE)
+            }
+            _ => Err(-1),
+        }
+    }
+
+    fn control_out(&mut self, request: &SetupPacket, data: &[u8]) -> Result<(), i32> {
+        if !self.is_mine(request) {
+            return Err(-2);
+        }
+        match request.request {
+            request::SET_LINE_CODING => {
+                self.line_coding = LineCoding::parse(data)?;
+                Ok(())
+            }
+            request::SET_CONTROL_LINE_STATE => {
+                self.dtr = request.value & 0x01 != 0;
+                self.rts = request.value & 0x02 != 0;
+                Ok(())
+            }
+            // No line to break
+            request::SEND_BREAK => Ok(()),
+            _ => Err(-1),
+        }
+    }
+
+    fn endpoint_out(&mut self, bus: &mut B, address: u8) {
+        if address != self.out_address || !self.configured {
+            return;
+        }
+        self.out_pending = true;
+        if self.rx.free() >= PACKET_SIZE {
+            self.receive(bus);
+        }
+    }
+
+    fn endpoint_in_complete(&mut self, bus: &mut B, address: u8) {
+        if address != self.in_address {
+            return;
+        }
+        self.in_busy = false;
+        self.poll(bus);
+    }
+}
@@ -0,0 +1,221 @@
+//! Control transfers on endpoint 0: the SETUP, data and status stages of
+//! one request at a time, leaving what the request means to the caller.
+//!
+//! `setup` and `out` hand over a request once everything the host sends
+//! with it has arrived; the caller answers it with `respond` (device to
+//! host), `accept` (host to device) or `reject`, which stalls. `in_complete`
+//! hands back a host-to-device request once its status stage is over, for
+//! requests such as SET_ADDRESS that take effect only then.
+
+use crate::bus::Bus;
+use crate::setup::{Direction, SetupPacket};
+
+/// Longest data stage, either way
+pub const BUFFER_SIZE: usize = 256;
+
+const EP0_OUT: u8 = 0x00;
+const EP0_IN: u8 = 0x80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// No transfer, or a request waiting for an answer
+    Idle,
+    /// Sending the answer
+    DataIn,
+    /// Receiving what the host sends with the request
+    DataOut,
+    /// Sending the empty status packet of a request that was accepted
+    StatusIn,
+    /// Waiting for the empty status packet of the host
+    StatusOut,
+    /// Endpoint 0 stalled until the next SETUP
+    Stalled,
+}
+
+pub struct ControlPipe {
+    max_packet_size: u16,
+    stage: Stage,
+    request: Option<SetupPacket>,
+    buf: [u8; BUFFER_SIZE],
+    /// Bytes of the data stage
+    len: usize,
+    /// Bytes of the data stage sent or received so far
+    pos: usize,
+    /// Whether an empty packet must end the data stage
+    zlp: bool,
+}
+
+impl ControlPipe {
+    pub const fn new(max_packet_size: u16) -> Self {
+        Self {
+            max_packet_size,
+            stage: Stage::Idle,
+            request: None,
+            buf: [0; BUFFER_SIZE],
+            len: 0,
+            pos: 0,
+            zlp: false,
+        }
+    }
+
+    pub fn max_packet_size(&self) -> u16 {
+        self.max_packet_size
+    }
+
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Drop the transfer under way, as on a bus reset
+    pub fn reset(&mut self) {
+        self.stage = Stage::Idle;
+        self.request = None;
+        self.len = 0;
+        self.pos = 0;
+        self.zlp = false;
+    }
+
+    /// What the host sent with the request handed over by `out`
+    pub fn data(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Start a transfer with the SETUP packet `bytes`, dropping any under
+    /// way. Returns the request unless data from the host must come first;
+    /// a malformed one, or one with more data than `BUFFER_SIZE`, stalls.
+    pub fn setup<B: Bus>(&mut self, bus: &mut B, bytes: &[u8]) -> Option<SetupPacket> {
+        self.reset();
+        let Ok(request) = SetupPacket::parse(bytes) else {
+            self.reject(bus);
+            return None;
+        };
+        self.request = Some(request);
+        if request.direction() == Direction::Out && request.length > 0 {
+            if request.length as usize > BUFFER_SIZE {
+                self.reject(bus);
+                return None;
+            }
+            self.len = request.length as usize;
+            self.stage = Stage::DataOut;
+            return None;
+        }
+        Some(request)
+    }
+
+    /// Take a packet that arrived on endpoint 0. Returns the request once
+    /// its data stage is complete, the data in `data()`.
+    pub fn out<B: Bus>(&mut self, bus: &mut B) -> Option<SetupPacket> {
+        let mut packet = [0; 64];
+        let len = bus.read(EP0_OUT, &mut packet).ok()?;
+        match self.stage {
+            Stage::DataOut => {
+                let take = len.min(self.len - self.pos);
+                self.buf[self.pos..self.pos + take].copy_from_slice(&packet[..take]);
+                self.pos += take;
+                // A short packet ends the stage early
+                if self.pos == self.len || len < self.max_packet_size as usize {
+                    self.len = self.pos;
+                    self.stage = Stage::Idle;
+                    return self.request;
+                }
+                None
+            }
+            // The status packet, or the host cutting the data stage short
+            Stage::StatusOut | Stage::DataIn => {
+                self.stage = Stage::Idle;
+                self.request = None;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Answer a device-to-host request with `data`, cut to the length the
+    /// host asked for
+    pub fn respond<B: Bus>(&mut self, bus: &mut B, data: &[u8]) {
+        let len = data.len().min(BUFFER_SIZE);
+        let result = self.respond_with(bus, |buf| {
+            buf[..len].copy_from_slice(&data[..len]);
+            Ok(len)
+        });
+        debug_assert!(result.is_ok());
+    }
+
+    /// Answer a device-to-host request with what `fill` writes into the
+    /// buffer of the pipe. If `fill` fails the request is left unanswered
+    /// and its error returned.
+    pub fn respond_with<B: Bus>(
+        &mut self,
+        bus: &mut B,
+        fill: impl FnOnce(&mut [u8]) -> Result<usize, i32>,
+    ) -> Result<(), i32> {
+        let Some(request) = self.request else {
+            return Err(-1);
+        };
+        if self.stage != Stage::Idle || request.direction() != Direction::In {
+            return Err(-1);
+        }
+        let len = fill(&mut self.buf)?.min(BUFFER_SIZE);
+        self.len = len.min(request.length as usize);
+        self.pos = 0;
+        // A data stage shorter than asked for ends on a short packet
+        self.zlp = self.len < request.length as usize
+            && self.len.is_multiple_of(self.max_packet_size as usize);
+        self.stage = Stage::DataIn;
+        self.send(bus);
+        Ok(())
+    }
+
+    fn send<B: Bus>(&mut self, bus: &mut B) {
+        let end = (self.pos + self.max_packet_size as usize).min(self.len);
+        if bus.write(EP0_IN, &self.buf[self.pos..end]).is_err() {
+            self.reject(bus);
+            return;
+        }
+        if end == self.pos {
+            self.zlp = false;
+        }
+        self.pos = end;
+    }
+
+    /// Accept a host-to-device request: send the empty status packet
+    pub fn accept<B: Bus>(&mut self, bus: &mut B) {
+        let Some(request) = self.request else {
+            return;
+        };
+        if self.stage != Stage::Idle || request.direction() != Direction::Out {
+            return;
+        }
+        self.stage = Stage::StatusIn;
+        if bus.write(EP0_IN, &[]).is_err() {
+            self.reject(bus);
+        }
+    }
+
+    /// Stall endpoint 0 until the next SETUP packet
+    pub fn reject<B: Bus>(&mut self, bus: &mut B) {
+        bus.set_stalled(EP0_IN, true);
+        bus.set_stalled(EP0_OUT, true);
+        self.stage = Stage::Stalled;
+    }
+
+    /// The packet on endpoint 0 went out. Returns the request whose status
+    /// stage it ended.
+    pub fn in_complete<B: Bus>(&mut self, bus: &mut B) -> Option<SetupPacket> {
+        match self.stage {
+            Stage::DataIn => {
+                if self.pos < self.len || self.zlp {
+                    self.send(bus);
+                } else {
+                    self.stage = Stage::StatusOut;
+                }
+                None
+            }
+            Stage::StatusIn => {
+                self.stage = Stage::Idle;
+                self.request.take()
+            }
+            _ => None,
+        }
+    }
+}
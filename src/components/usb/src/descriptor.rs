@@ -0,0 +1,388 @@
+//! Standard descriptors: the device descriptor, a builder for the
+//! configuration descriptor with its interfaces, endpoints and class
+//! descriptors, string descriptors, and a walk over descriptors read back.
+
+use crate::endpoint::EndpointType;
+
+/// Descriptor types (bDescriptorType)
+pub mod kind {
+    pub const DEVICE: u8 = 0x01;
+    pub const CONFIGURATION: u8 = 0x02;
+    pub const STRING: u8 = 0x03;
+    pub const INTERFACE: u8 = 0x04;
+    pub const ENDPOINT: u8 = 0x05;
+    pub const DEVICE_QUALIFIER: u8 = 0x06;
+    pub const INTERFACE_ASSOCIATION: u8 = 0x0B;
+    pub const CS_INTERFACE: u8 = 0x24;
+    pub const CS_ENDPOINT: u8 = 0x25;
+}
+
+/// bmAttributes of the configuration descriptor
+pub mod attributes {
+    /// Always set
+    pub const RESERVED: u8 = 0x80;
+    pub const SELF_POWERED: u8 = 0x40;
+    pub const REMOTE_WAKEUP: u8 = 0x20;
+}
+
+/// Class of a device made of functions grouped by interface associations
+pub const CLASS_MISCELLANEOUS: u8 = 0xEF;
+
+/// Language ID of the strings (wLANGID)
+pub const LANGUAGE_EN_US: u16 = 0x0409;
+
+const CONFIG_HEADER_SIZE: usize = 9;
+const INTERFACE_SIZE: usize = 9;
+const ASSOCIATION_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceDescriptor {
+    /// bcdUSB
+    pub usb: u16,
+    pub class: u8,
+    pub subclass: u8,
+    pub protocol: u8,
+    /// Of endpoint 0: 8, 16, 32 or 64
+    pub max_packet_size0: u8,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    /// bcdDevice
+    pub release: u16,
+    /// String indices, 0 for none
+    pub manufacturer: u8,
+    pub product: u8,
+    pub serial_number: u8,
+    pub configurations: u8,
+}
+
+impl DeviceDescriptor {
+    pub const SIZE: usize = 18;
+
+    /// A USB 2.0 full-speed device with one configuration, its class given
+    /// by its interfaces and no strings
+    pub const fn new(vendor_id: u16, product_id: u16) -> Self {
+        Self {
+            usb: 0x0200,
+            class: 0,
+            subclass: 0,
+            protocol: 0,
+            max_packet_size0: 64,
+            vendor_id,
+            product_id,
+            release: 0x0100,
+            manufacturer: 0,
+            product: 0,
+            serial_number: 0,
+            configurations: 1,
+        }
+    }
+
+    /// Like `new`, for a device whose functions span several interfaces
+    /// tied by interface association descriptors, such as CDC-ACM
+    pub const fn composite(vendor_id: u16, product_id: u16) -> Self {
+        let mut descriptor = Self::new(vendor_id, product_id);
+        descriptor.class = CLASS_MISCELLANEOUS;
+        descriptor.subclass = 0x02;
+        descriptor.protocol = 0x01;
+        descriptor
+    }
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let [usb_lo, usb_hi] = self.usb.to_le_bytes();
+        let [vendor_lo, vendor_hi] = self.vendor_id.to_le_bytes();
+        let [product_lo, product_hi] = self.product_id.to_le_bytes();
+        let [release_lo, release_hi] = self.release.to_le_bytes();
+        [
+            Self::SIZE as u8,
+            kind::DEVICE,
+            usb_lo,
+            usb_hi,
+            self.class,
+            self.subclass,
+            self.protocol,
+            self.max_packet_size0,
+            vendor_lo,
+            vendor_hi,
+            product_lo,
+            product_hi,
+            release_lo,
+            release_hi,
+            self.manufacturer,
+            self.product,
+            self.serial_number,
+            self.configurations,
+        ]
+    }
+
+    /// Returns Err(-1) unless `bytes` starts with a device descriptor
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        if bytes.len() < Self::SIZE || bytes[0] as usize != Self::SIZE || bytes[1] != kind::DEVICE {
+            return Err(-1);
+        }
+        let word = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+        Ok(Self {
+            usb: word(2),
+            class: bytes[4],
+            subclass: bytes[5],
+            protocol: bytes[6],
+            max_packet_size0: bytes[7],
+            vendor_id: word(8),
+            product_id: word(10),
+            release: word(12),
+            manufacturer: bytes[14],
+            product: bytes[15],
+            serial_number: bytes[16],
+            configurations: bytes[17],
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndpointDescriptor {
+    pub address: u8,
+    pub endpoint_type: EndpointType,
+    pub max_packet_size: u16,
+    /// Polling interval in frames, for interrupt endpoints
+    pub interval: u8,
+}
+
+impl EndpointDescriptor {
+    pub const SIZE: usize = 7;
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let [size_lo, size_hi] = self.max_packet_size.to_le_bytes();
+        [
+            Self::SIZE as u8,
+            kind::ENDPOINT,
+            self.address,
+            self.endpoint_type as u8,
+            size_lo,
+            size_hi,
+            self.interval,
+        ]
+    }
+
+    /// Returns Err(-1) unless `bytes` starts with an endpoint descriptor
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        if bytes.len() < Self::SIZE
+            || (bytes[0] as usize) < Self::SIZE
+            || bytes[1] != kind::ENDPOINT
+        {
+            return Err(-1);
+        }
+        Ok(Self {
+            address: bytes[2],
+            endpoint_type: EndpointType::from_attributes(bytes[3]),
+            max_packet_size: u16::from_le_bytes([bytes[4], bytes[5]]),
+            interval: bytes[6],
+        })
+    }
+}
+
+/// Writes a configuration descriptor followed by the descriptors of its
+/// interfaces into a buffer, numbering the interfaces in order and
+/// counting the endpoints of each. Every method returns Err(-5) when the
+/// buffer is too short for what it writes.
+pub struct ConfigBuilder<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+    interfaces: u8,
+    /// Offset of the last interface descriptor, whose endpoints are counted
+    interface: Option<usize>,
+}
+
+impl<'b> ConfigBuilder<'b> {
+    /// Start the configuration numbered `value`, drawing at most
+    /// `max_power_ma` from the bus; `attributes` takes `RESERVED` as given
+    pub fn new(
+        buf: &'b mut [u8],
+        value: u8,
+        attributes: u8,
+        max_power_ma: u16,
+    ) -> Result<Self, i32> {
+        if buf.len() < CONFIG_HEADER_SIZE {
+            return Err(-5);
+        }
+        buf[..CONFIG_HEADER_SIZE].copy_from_slice(&[
+            CONFIG_HEADER_SIZE as u8,
+            kind::CONFIGURATION,
+            0,
+            0,
+            0,
+            value,
+            0,
+            attributes | attributes::RESERVED,
+            (max_power_ma.min(500) / 2) as u8,
+        ]);
+        Ok(Self {
+            buf,
+            len: CONFIG_HEADER_SIZE,
+            interfaces: 0,
+            interface: None,
+        })
+    }
+
+    /// Number the next interface will get
+    pub fn next_interface(&self) -> u8 {
+        self.interfaces
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<usize, i32> {
+        let at = self.len;
+        let end = at + bytes.len();
+        if end > self.buf.len() || end > u16::MAX as usize {
+            return Err(-5);
+        }
+        self.buf[at..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(at)
+    }
+
+    /// Add an interface with alternate setting 0 and return its number
+    pub fn interface(&mut self, class: u8, subclass: u8, protocol: u8) -> Result<u8, i32> {
+        let number = self.interfaces;
+        let at = self.push(&[
+            INTERFACE_SIZE as u8,
+            kind::INTERFACE,
+            number,
+            0,
+            0,
+            class,
+            subclass,
+            protocol,
+            0,
+        ])?;
+        self.interfaces += 1;
+        self.interface = Some(at);
+        Ok(number)
+    }
+
+    /// Tie `count` interfaces from `first` on into one function
+    pub fn association(
+        &mut self,
+        first: u8,
+        count: u8,
+        class: u8,
+        subclass: u8,
+        protocol: u8,
+    ) -> Result<(), i32> {
+        self.push(&[
+            ASSOCIATION_SIZE as u8,
+            kind::INTERFACE_ASSOCIATION,
+            first,
+            count,
+            class,
+            subclass,
+            protocol,
+            0,
+        ])?;
+        Ok(())
+    }
+
+    /// Add an endpoint to the last interface. Returns Err(-1) if there is
+    /// no interface yet.
+    pub fn endpoint(
+        &mut self,
+        address: u8,
+        endpoint_type: EndpointType,
+        max_packet_size: u16,
+        interval: u8,
+    ) -> Result<(), i32> {
+        let interface = self.interface.ok_or(-1)?;
+        let descriptor = EndpointDescriptor {
+            address,
+            endpoint_type,
+            max_packet_size,
+            interval,
+        };
+        self.push(&descriptor.encode())?;
+        self.buf[interface + 4] += 1;
+        Ok(())
+    }
+
+    /// Add a descriptor of type `kind`, such as a class-specific one
+    pub fn descriptor(&mut self, kind: u8, body: &[u8]) -> Result<(), i32> {
+        if body.len() > u8::MAX as usize - 2 {
+            return Err(-1);
+        }
+        let at = self.push(&[body.len() as u8 + 2, kind])?;
+        if let Err(error) = self.push(body) {
+            self.len = at;
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Fill in the total length and the number of interfaces, and return
+    /// the length of the descriptors
+    pub fn finish(self) -> usize {
+        let [len_lo, len_hi] = (self.len as u16).to_le_bytes();
+        self.buf[2] = len_lo;
+        self.buf[3] = len_hi;
+        self.buf[4] = self.interfaces;
+        self.len
+    }
+}
+
+/// Write the string descriptor of `text` in UTF-16 into `buf` and return
+/// its length. Returns Err(-5) if `buf` is too short, Err(-1) if the
+/// descriptor would be over 255 bytes.
+pub fn string(text: &str, buf: &mut [u8]) -> Result<usize, i32> {
+    let len = 2 + 2 * text.encode_utf16().count();
+    if len > u8::MAX as usize {
+        return Err(-1);
+    }
+    if buf.len() < len {
+        return Err(-5);
+    }
+    buf[0] = len as u8;
+    buf[1] = kind::STRING;
+    for (unit, out) in text.encode_utf16().zip(buf[2..len].chunks_exact_mut(2)) {
+        out.copy_from_slice(&unit.to_le_bytes());
+    }
+    Ok(len)
+}
+
+/// Write string descriptor 0, the languages of the strings, into `buf`
+/// and return its length. Returns Err(-5) if `buf` is too short.
+pub fn languages(ids: &[u16], buf: &mut [u8]) -> Result<usize, i32> {
+    let len = 2 + 2 * ids.len();
+    if len > u8::MAX as usize {
+        return Err(-1);
+    }
+    if buf.len() < len {
+        return Err(-5);
+    }
+    buf[0] = len as u8;
+    buf[1] = kind::STRING;
+    for (id, out) in ids.iter().zip(buf[2..len].chunks_exact_mut(2)) {
+        out.copy_from_slice(&id.to_le_bytes());
+    }
+    Ok(len)
+}
+
+/// Walk the descriptors in `bytes`, such as a configuration with all that
+/// follows it, yielding each with its length and type bytes. Stops at the
+/// first malformed descriptor.
+pub fn iter(bytes: &[u8]) -> Descriptors<'_> {
+    Descriptors { bytes }
+}
+
+pub struct Descriptors<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for Descriptors<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let len = *self.bytes.first()? as usize;
+        if len < 2 || len > self.bytes.len() {
+            self.bytes = &[];
+            return None;
+        }
+        let (descriptor, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Some(descriptor)
+    }
+}
@@ -0,0 +1,450 @@
+//! The device: its descriptors, the standard requests of enumeration and
+//! the classes that make up its functions.
+//!
+//! `Device::poll` takes one event from the bus at a time. Requests the
+//! device does not handle itself go to the classes in turn, and so do the
+//! packets of endpoints other than 0.
+
+use crate::bus::{Bus, Event};
+use crate::control::ControlPipe;
+use crate::descriptor::{
+    self, ConfigBuilder, DeviceDescriptor, EndpointDescriptor, LANGUAGE_EN_US, kind,
+};
+use crate::endpoint::EndpointType;
+use crate::setup::{Direction, Kind, Recipient, SetupPacket, feature, request};
+
+/// Longest configuration descriptor, with all that follows it
+pub const CONFIG_SIZE: usize = 256;
+
+/// Value of the one configuration
+pub const CONFIGURATION_VALUE: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// After a reset, at address 0
+    Default,
+    Addressed,
+    Configured,
+}
+
+/// A function of the device, such as a CDC-ACM serial port, with its
+/// interfaces and endpoints.
+///
+/// The control methods default to Err(-2), which passes the request on to
+/// the next class; any other error stalls it.
+pub trait Class<B: Bus> {
+    /// The bus was reset, or the device unconfigured: endpoints are closed
+    fn reset(&mut self) {}
+
+    /// The configuration is set and its endpoints open
+    fn configured(&mut self, _bus: &mut B) {}
+
+    /// Answer a device-to-host request by writing into `buf`, returning
+    /// the length
+    fn control_in(&mut self, _request: &SetupPacket, _buf: &mut [u8]) -> Result<usize, i32> {
+        Err(-2)
+    }
+
+    /// Carry out a host-to-device request with the `data` sent with it
+    fn control_out(&mut self, _request: &SetupPacket, _data: &[u8]) -> Result<(), i32> {
+        Err(-2)
+    }
+
+    /// A packet arrived on an OUT endpoint, possibly not the class's own
+    fn endpoint_out(&mut self, _bus: &mut B, _address: u8) {}
+
+    /// A packet went out on an IN endpoint, possibly not the class's own
+    fn endpoint_in_complete(&mut self, _bus: &mut B, _address: u8) {}
+}
+
+pub struct Device<'d> {
+    descriptor: DeviceDescriptor,
+    config: [u8; CONFIG_SIZE],
+    config_len: usize,
+    /// String descriptors 1 on
+    strings: &'d [&'d str],
+    control: ControlPipe,
+    state: State,
+    /// Address of a SET_ADDRESS waiting for its status stage
+    address: u8,
+    configuration: u8,
+    suspended: bool,
+    remote_wakeup: bool,
+}
+
+impl<'d> Device<'d> {
+    /// A device with no interfaces until `configure`. String indices in
+    /// `descriptor` count from 1 into `strings`.
+    pub const fn new(descriptor: DeviceDescriptor, strings: &'d [&'d str]) -> Self {
+        Self {
+            descriptor,
+            config: [0; CONFIG_SIZE],
+            config_len: 0,
+            strings,
+            control: ControlPipe::new(descriptor.max_packet_size0 as u16),
+            state: State::Default,
+            address: 0,
+            configuration: 0,
+            suspended: false,
+            remote_wakeup: false,
+        }
+    }
+
+    /// Build the configuration descriptor: `describe` adds the interfaces,
+    /// typically through the `describe` of each class. Returns its error,
+    /// or Err(-5) if the descriptors do not fit `CONFIG_SIZE`.
+    pub fn configure(
+        &mut self,
+        attributes: u8,
+        max_power_ma: u16,
+        describe: impl FnOnce(&mut ConfigBuilder) -> Result<(), i32>,
+    ) -> Result<(), i32> {
+        let mut builder = ConfigBuilder::new(
+            &mut self.config,
+            CONFIGURATION_VALUE,
+            attributes,
+            max_power_ma,
+        )?;
+        describe(&mut builder)?;
+        self.config_len = builder.finish();
+        Ok(())
+    }
+
+    pub fn descriptor(&self) -> &DeviceDescriptor {
+        &self.descriptor
+    }
+
+    /// The configuration descriptor and all that follows it
+    pub fn config(&self) -> &[u8] {
+        &self.config[..self.config_len]
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.state == State::Configured
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Whether the host enabled remote wakeup
+    pub fn remote_wakeup(&self) -> bool {
+        self.remote_wakeup
+    }
+
+    fn endpoints(&self) -> impl Iterator<Item = EndpointDescriptor> + '_ {
+        descriptor::iter(self.config()).filter_map(|bytes| EndpointDescriptor::parse(bytes).ok())
+    }
+
+    fn interfaces(&self) -> u8 {
+        if self.config_len > 4 {
+            self.config[4]
+        } else {
+            0
+        }
+    }
+
+    fn has_endpoint(&self, address: u8) -> bool {
+        address & 0x0F == 0 || self.endpoints().any(|endpoint| endpoint.address == address)
+    }
+
+    /// Close the endpoints of the configuration and tell the classes
+    fn unconfigure<B: Bus>(&mut self, bus: &mut B, classes: &mut [&mut dyn Class<B>]) {
+        for endpoint in self.endpoints() {
+            bus.close(endpoint.address);
+        }
+        self.configuration = 0;
+        for class in classes.iter_mut() {
+            class.reset();
+        }
+    }
+
+    /// Take an event from the bus and act on it; returns it, or None if
+    /// there was none
+    pub fn poll<B: Bus>(
+        &mut self,
+        bus: &mut B,
+        classes: &mut [&mut dyn Class<B>],
+    ) -> Option<Event> {
+        let event = bus.poll()?;
+        match event {
+            Event::Reset => {
+                self.unconfigure(bus, classes);
+                self.control.reset();
+                self.state = State::Default;
+                self.address = 0;
+                self.suspended = false;
+                self.remote_wakeup = false;
+                bus.set_address(0);
+                let max_packet_size = self.control.max_packet_size();
+                let _ = bus.open(0x00, EndpointType::Control, max_packet_size);
+                let _ = bus.open(0x80, EndpointType::Control, max_packet_size);
+            }
+            Event::Setup(bytes) => {
+                if let Some(request) = self.control.setup(bus, &bytes) {
+                    self.handle(bus, classes, request);
+                }
+            }
+            Event::Out(address) if address & 0x0F == 0 => {
+                if let Some(request) = self.control.out(bus) {
+                    self.handle(bus, classes, request);
+                }
+            }
+            Event::Out(address) => {
+                for class in classes.iter_mut() {
+                    class.endpoint_out(bus, address);
+                }
+            }
+            Event::InComplete(address) if address & 0x0F == 0 => {
+                if let Some(request) = self.control.in_complete(bus) {
+                    self.complete(bus, &request);
+                }
+            }
+            Event::InComplete(address) => {
+                for class in classes.iter_mut() {
+                    class.endpoint_in_complete(bus, address);
+                }
+            }
+            Event::Suspend => self.suspended = true,
+            Event::Resume => self.suspended = false,
+        }
+        Some(event)
+    }
+
+    fn handle<B: Bus>(
+        &mut self,
+        bus: &mut B,
+        classes: &mut [&mut dyn Class<B>],
+        request: SetupPacket,
+    ) {
+        let standard = request.kind() == Kind::Standard
+            && match request.recipient() {
+                Recipient::Device | Recipient::Endpoint => true,
+                // Class descriptors of an interface are the class's own
+                Recipient::Interface => matches!(
+                    request.request,
+                    request::GET_STATUS | request::GET_INTERFACE | request::SET_INTERFACE
+                ),
+                _ => false,
+            };
+        if standard {
+            self.standard(bus, classes, &request);
+            return;
+        }
+        match request.direction() {
+            Direction::In => {
+                let result = self.control.respond_with(bus, |buf| {
+                    for class in classes.iter_mut() {
+                        match class.control_in(&request, buf) {
+                            Err(-2) => continue,
+                            result => return result,
+                        }
+                    }
+                    Err(-2)
+                });
+                if result.is_err() {
+                    self.control.reject(bus);
+                }
+            }
+            Direction::Out => {
+                let data = self.control.data();
+                let mut result = Err(-2);
+                for class in classes.iter_mut() {
+                    result = class.control_out(&request, data);
+                    if result != Err(-2) {
+                        break;
+                    }
+                }
+                match result {
+                    Ok(()) => self.control.accept(bus),
+                    Err(_) => self.control.reject(bus),
+                }
+            }
+        }
+    }
+
+    /// Carry out a standard request; stalls those the device does not have
+    fn standard<B: Bus>(
+        &mut self,
+        bus: &mut B,
+        classes: &mut [&mut dyn Class<B>],
+        request: &SetupPacket,
+    ) {
+        let configured = self.state == State::Configured;
+        let accepted = match (request.recipient(), request.request) {
+            (Recipient::Device, request::GET_STATUS) => {
+                let self_powered = self.config_len > 7
+                    && self.config[7] & descriptor::attributes::SELF_POWERED != 0;
+                let status = self_powered as u8 | (self.remote_wakeup as u8) << 1;
+                self.control.respond(bus, &[status, 0]);
+                true
+            }
+            (Recipient::Interface, request::GET_STATUS) => {
+                let exists = configured && request.index < self.interfaces() as u16;
+                if exists {
+                    self.control.respond(bus, &[0, 0]);
+                }
+                exists
+            }
+            (Recipient::Endpoint, request::GET_STATUS) => {
+                let address = request.index as u8;
+                let exists = self.has_endpoint(address) && (configured || address & 0x0F == 0);
+                if exists {
+                    let halted = bus.is_stalled(address) as u8;
+                    self.control.respond(bus, &[halted, 0]);
+                }
+                exists
+            }
+            (Recipient::Device, request::CLEAR_FEATURE | request::SET_FEATURE)
+                if request.value == feature::DEVICE_REMOTE_WAKEUP =>
+            {
+                self.remote_wakeup = request.request == request::SET_FEATURE;
+                self.control.accept(bus);
+                true
+            }
+            (Recipient::Endpoint, request::CLEAR_FEATURE | request::SET_FEATURE)
+                if request.value == feature::ENDPOINT_HALT =>
+            {
+                let address = request.index as u8;
+                // Endpoint 0 clears its own stall on the next SETUP
+                let exists = configured && address & 0x0F != 0 && self.has_endpoint(address);
+                if exists {
+                    bus.set_stalled(address, request.request == request::SET_FEATURE);
+                    self.control.accept(bus);
+                }
+                exists
+            }
+            (Recipient::Device, request::SET_ADDRESS) => {
+                let valid = request.value <= 127 && self.state != State::Configured;
+                if valid {
+                    self.address = request.value as u8;
+                    if B::SET_ADDRESS_BEFORE_STATUS {
+                        bus.set_address(self.address);
+                    }
+                    self.control.accept(bus);
+                }
+                valid
+            }
+            (Recipient::Device, request::GET_DESCRIPTOR) => self.get_descriptor(bus, request),
+            (Recipient::Device, request::GET_CONFIGURATION) => {
+                self.control.respond(bus, &[self.configuration]);
+                true
+            }
+            (Recipient::Device, request::SET_CONFIGURATION) => {
+                self.set_configuration(bus, classes, request.value)
+            }
+            (Recipient::Interface, request::GET_INTERFACE) => {
+                let exists = configured && request.index < self.interfaces() as u16;
+                if exists {
+                    self.control.respond(bus, &[0]);
+                }
+                exists
+            }
+            (Recipient::Interface, request::SET_INTERFACE) => {
+                // Interfaces have alternate setting 0 only
+                let exists =
+                    configured && request.index < self.interfaces() as u16 && request.value == 0;
+                if exists {
+                    self.control.accept(bus);
+                }
+                exists
+            }
+            _ => false,
+        };
+        if !accepted {
+            self.control.reject(bus);
+        }
+    }
+
+    fn get_descriptor<B: Bus>(&mut self, bus: &mut B, request: &SetupPacket) -> bool {
+        let index = request.descriptor_index();
+        let strings = self.strings;
+        match request.descriptor_type() {
+            kind::DEVICE => {
+                self.control.respond(bus, &self.descriptor.encode());
+                true
+            }
+            kind::CONFIGURATION if index == 0 && self.config_len > 0 => {
+                self.control.respond(bus, &self.config[..self.config_len]);
+                true
+            }
+            kind::STRING if index == 0 => self
+                .control
+                .respond_with(bus, |buf| descriptor::languages(&[LANGUAGE_EN_US], buf))
+                .is_ok(),
+            kind::STRING => match strings.get(index as usize - 1) {
+                Some(text) => self
+                    .control
+                    .respond_with(bus, |buf| descriptor::string(text, buf))
+                    .is_ok(),
+                None => false,
+            },
+            // Full speed only: no device qualifier, no other-speed
+            // configuration
+            _ => false,
+        }
+    }
+
+    fn set_configuration<B: Bus>(
+        &mut self,
+        bus: &mut B,
+        classes: &mut [&mut dyn Class<B>],
+        value: u16,
+    ) -> bool {
+        if self.state == State::Default {
+            return false;
+        }
+        match value {
+            0 => {
+                self.unconfigure(bus, classes);
+                self.state = State::Addressed;
+            }
+            value if value == CONFIGURATION_VALUE as u16 && self.config_len > 0 => {
+                if self.state == State::Configured {
+                    self.unconfigure(bus, classes);
+                }
+                let opened = self.endpoints().all(|endpoint| {
+                    bus.open(
+                        endpoint.address,
+                        endpoint.endpoint_type,
+                        endpoint.max_packet_size,
+                    )
+                    .is_ok()
+                });
+                if !opened {
+                    self.unconfigure(bus, classes);
+                    return false;
+                }
+                self.configuration = CONFIGURATION_VALUE;
+                self.state = State::Configured;
+                for class in classes.iter_mut() {
+                    class.configured(bus);
+                }
+            }
+            _ => return false,
+        }
+        self.control.accept(bus);
+        true
+    }
+
+    /// The status stage of `request` is over
+    fn complete<B: Bus>(&mut self, bus: &mut B, request: &SetupPacket) {
+        if request.kind() == Kind::Standard
+            && request.recipient() == Recipient::Device
+            && request.request == request::SET_ADDRESS
+        {
+            if !B::SET_ADDRESS_BEFORE_STATUS {
+                bus.set_address(self.address);
+            }
+            self.state = if self.address == 0 {
+                State::Default
+            } else {
+                State::Addressed
+            };
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Endpoint addresses and transfer types.
+
+pub const NUMBER_MASK: u8 = 0x0F;
+pub const DIRECTION_MASK: u8 = 0x80;
+
+#[inline]
+pub fn address(number: u8, direction_in: bool) -> u8 {
+    (number & NUMBER_MASK) | if direction_in { DIRECTION_MASK } else { 0 }
+}
+
+#[inline]
+pub fn number(address: u8) -> u8 {
+    address & NUMBER_MASK
+}
+
+#[inline]
+pub fn is_in(address: u8) -> bool {
+    address & DIRECTION_MASK != 0
+}
+
+/// Endpoint types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointType {
+    Control = 0,
+    Isochronous = 1,
+    Bulk = 2,
+    Interrupt = 3,
+}
+
+impl EndpointType {
+    /// The type in the low bits of bmAttributes
+    pub const fn from_attributes(attributes: u8) -> Self {
+        match attributes & 0x03 {
+            0 => EndpointType::Control,
+            1 => EndpointType::Isochronous,
+            2 => EndpointType::Bulk,
+            _ => EndpointType::Interrupt,
+        }
+    }
+}
@@ -0,0 +1,783 @@
+//! HID, the class of keyboards, mice and devices that talk in reports: an
+//! interface whose report descriptor tells the host the layout of each
+//! report, and an interrupt IN endpoint the input reports go out on.
+//!
+//! `ReportBuilder` writes report descriptors item by item. `Hid` is the
+//! class for any report descriptor; `Keyboard` is a boot keyboard on top
+//! of it, which BIOSes and the stock host drivers take without parsing
+//! its descriptor. Output reports, such as the LEDs of a keyboard, come
+//! with SET_REPORT on endpoint 0, there being no OUT endpoint.
+
+use crate::bus::Bus;
+use crate::descriptor::ConfigBuilder;
+use crate::device::Class;
+use crate::endpoint::{self, EndpointType};
+use crate::setup::{Kind, Recipient, SetupPacket, request as standard};
+
+/// Class-specific requests
+pub mod request {
+    pub const GET_REPORT: u8 = 0x01;
+    pub const GET_IDLE: u8 = 0x02;
+    pub const GET_PROTOCOL: u8 = 0x03;
+    pub const SET_REPORT: u8 = 0x09;
+    pub const SET_IDLE: u8 = 0x0A;
+    pub const SET_PROTOCOL: u8 = 0x0B;
+}
+
+/// Usage pages
+pub mod usage_page {
+    pub const GENERIC_DESKTOP: u16 = 0x01;
+    pub const KEYBOARD: u16 = 0x07;
+    pub const LEDS: u16 = 0x08;
+    pub const BUTTON: u16 = 0x09;
+    pub const CONSUMER: u16 = 0x0C;
+    pub const VENDOR: u16 = 0xFF00;
+}
+
+/// Usages of the generic desktop page
+pub mod usage {
+    pub const POINTER: u16 = 0x01;
+    pub const MOUSE: u16 = 0x02;
+    pub const KEYBOARD: u16 = 0x06;
+    pub const X: u16 = 0x30;
+    pub const Y: u16 = 0x31;
+    pub const WHEEL: u16 = 0x38;
+}
+
+/// Kinds of collection
+pub mod collection {
+    pub const PHYSICAL: u8 = 0x00;
+    pub const APPLICATION: u8 = 0x01;
+    pub const LOGICAL: u8 = 0x02;
+}
+
+/// Flags of the input, output and feature items, OR-ed together
+pub mod flags {
+    pub const DATA: u8 = 0x00;
+    pub const CONSTANT: u8 = 0x01;
+    pub const ARRAY: u8 = 0x00;
+    pub const VARIABLE: u8 = 0x02;
+    pub const ABSOLUTE: u8 = 0x00;
+    pub const RELATIVE: u8 = 0x04;
+}
+
+pub const CLASS_HID: u8 = 0x03;
+pub const SUBCLASS_NONE: u8 = 0x00;
+/// The interface speaks the boot protocol as well as reports
+pub const SUBCLASS_BOOT: u8 = 0x01;
+pub const PROTOCOL_NONE: u8 = 0x00;
+pub const PROTOCOL_KEYBOARD: u8 = 0x01;
+pub const PROTOCOL_MOUSE: u8 = 0x02;
+
+/// Class descriptor types
+pub const DESCRIPTOR_HID: u8 = 0x21;
+pub const DESCRIPTOR_REPORT: u8 = 0x22;
+
+/// Longest report, the packet size of a full-speed interrupt endpoint
+pub const REPORT_SIZE: usize = 64;
+
+/// Item tags with their type, before the size bits
+const INPUT: u8 = 0x80;
+const OUTPUT: u8 = 0x90;
+const COLLECTION: u8 = 0xA0;
+const FEATURE: u8 = 0xB0;
+const END_COLLECTION: u8 = 0xC0;
+const USAGE_PAGE: u8 = 0x04;
+const LOGICAL_MINIMUM: u8 = 0x14;
+const LOGICAL_MAXIMUM: u8 = 0x24;
+const PHYSICAL_MINIMUM: u8 = 0x34;
+const PHYSICAL_MAXIMUM: u8 = 0x44;
+const UNIT_EXPONENT: u8 = 0x54;
+const UNIT: u8 = 0x64;
+const REPORT_SIZE_ITEM: u8 = 0x74;
+const REPORT_ID: u8 = 0x84;
+const REPORT_COUNT: u8 = 0x94;
+const USAGE: u8 = 0x08;
+const USAGE_MINIMUM: u8 = 0x18;
+const USAGE_MAXIMUM: u8 = 0x28;
+
+/// Protocols of SET_PROTOCOL
+pub const BOOT_PROTOCOL: u8 = 0;
+pub const REPORT_PROTOCOL: u8 = 1;
+
+/// Report types of GET_REPORT and SET_REPORT, the high byte of wValue
+pub mod report_type {
+    pub const INPUT: u8 = 1;
+    pub const OUTPUT: u8 = 2;
+    pub const FEATURE: u8 = 3;
+}
+
+/// Writes a report descriptor into a buffer, each item in as few bytes as
+/// its value takes. Every method returns Err(-5) when the buffer is too
+/// short for the item.
+pub struct ReportBuilder<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+    /// Collections open
+    depth: u8,
+}
+
+impl<'b> ReportBuilder<'b> {
+    pub fn new(buf: &'b mut [u8]) -> Self {
+        Self {
+            buf,
+            len: 0,
+            depth: 0,
+        }
+    }
+
+    fn push(&mut self, prefix: u8, data: &[u8]) -> Result<(), i32> {
+        let end = self.len + 1 + data.len();
+        if end > self.buf.len() {
+            return Err(-5);
+        }
+        // Size code 3 stands for four bytes
+        let size = if data.len() == 4 { 3 } else { data.len() as u8 };
+        self.buf[self.len] = prefix | size;
+        self.buf[self.len + 1..end].copy_from_slice(data);
+        self.len = end;
+        Ok(())
+    }
+
+    fn unsigned(&mut self, prefix: u8, value: u32) -> Result<(), i32> {
+        let bytes = value.to_le_bytes();
+        let size = match value {
+            0..=0xFF => 1,
+            0x100..=0xFFFF => 2,
+            _ => 4,
+        };
+        self.push(prefix, &bytes[..size])
+    }
+
+    fn signed(&mut self, prefix: u8, value: i32) -> Result<(), i32> {
+        let bytes = value.to_le_bytes();
+        let size = if i8::try_from(value).is_ok() {
+            1
+        } else if i16::try_from(value).is_ok() {
+            2
+        } else {
+            4
+        };
+        self.push(prefix, &bytes[..size])
+    }
+
+    pub fn usage_page(&mut self, page: u16) -> Result<(), i32> {
+        self.unsigned(USAGE_PAGE, page as u32)
+    }
+
+    pub fn usage(&mut self, usage: u16) -> Result<(), i32> {
+        self.unsigned(USAGE, usage as u32)
+    }
+
+    pub fn usage_minimum(&mut self, usage: u16) -> Result<(), i32> {
+        self.unsigned(USAGE_MINIMUM, usage as u32)
+    }
+
+    pub fn usage_maximum(&mut self, usage: u16) -> Result<(), i32> {
+        self.unsigned(USAGE_MAXIMUM, usage as u32)
+    }
+
+    pub fn logical_minimum(&mut self, value: i32) -> Result<(), i32> {
+        self.signed(LOGICAL_MINIMUM, value)
+    }
+
+    pub fn logical_maximum(&mut self, value: i32) -> Result<(), i32> {
+        self.signed(LOGICAL_MAXIMUM, value)
+    }
+
+    pub fn physical_minimum(&mut self, value: i32) -> Result<(), i32> {
+        self.signed(PHYSICAL_MINIMUM, value)
+    }
+
+    pub fn physical_maximum(&mut self, value: i32) -> Result<(), i32> {
+        self.signed(PHYSICAL_MAXIMUM, value)
+    }
+
+    pub fn unit_exponent(&mut self, exponent: i8) -> Result<(), i32> {
+        self.signed(UNIT_EXPONENT, exponent as i32)
+    }
+
+    pub fn unit(&mut self, unit: u32) -> Result<(), i32> {
+        self.unsigned(UNIT, unit)
+    }
+
+    /// Bits of each field
+    pub fn report_size(&mut self, bits: u8) -> Result<(), i32> {
+        self.unsigned(REPORT_SIZE_ITEM, bits as u32)
+    }
+
+    /// Fields of the next input, output or feature item
+    pub fn report_count(&mut self, count: u8) -> Result<(), i32> {
+        self.unsigned(REPORT_COUNT, count as u32)
+    }
+
+    /// Prefix the reports that follow with `id`. Returns Err(-1) for 0,
+    /// which is reserved.
+    pub fn report_id(&mut self, id: u8) -> Result<(), i32> {
+        if id == 0 {
+            return Err(-1);
+        }
+        self.unsigned(REPORT_ID, id as u32)
+    }
+
+    pub fn input(&mut self, flags: u8) -> Result<(), i32> {
+        self.unsigned(INPUT, flags as u32)
+    }
+
+    pub fn output(&mut self, flags: u8) -> Result<(), i32> {
+        self.unsigned(OUTPUT, flags as u32)
+    }
+
+    pub fn feature(&mut self, flags: u8) -> Result<(), i32> {
+        self.unsigned(FEATURE, flags as u32)
+    }
+
+    pub fn collection(&mut self, kind: u8) -> Result<(), i32> {
+        self.unsigned(COLLECTION, kind as u32)?;
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Returns Err(-1) if no collection is open
+    pub fn end_collection(&mut self) -> Result<(), i32> {
+        if self.depth == 0 {
+            return Err(-1);
+        }
+        self.push(END_COLLECTION, &[])?;
+        self.depth -= 1;
+        Ok(())
+    }
+
+    /// Return the length of the descriptor, or Err(-1) if a collection is
+    /// left open
+    pub fn finish(self) -> Result<usize, i32> {
+        if self.depth != 0 {
+            return Err(-1);
+        }
+        Ok(self.len)
+    }
+}
+
+/// Report descriptor of the boot keyboard (HID 1.11, appendix B.1): a
+/// modifier byte, a reserved byte and six key codes in; five LEDs out
+pub const BOOT_KEYBOARD_REPORT: [u8; 63] = [
+    0x05, 0x01, // Usage page (generic desktop)
+    0x09, 0x06, // Usage (keyboard)
+    0xA1, 0x01, // Collection (application)
+    0x05, 0x07, //   Usage page (keyboard)
+    0x19, 0xE0, //   Usage minimum (left control)
+    0x29, 0xE7, //   Usage maximum (right GUI)
+    0x15, 0x00, //   Logical minimum (0)
+    0x25, 0x01, //   Logical maximum (1)
+    0x75, 0x01, //   Report size (1)
+    0x95, 0x08, //   Report count (8)
+    0x81, 0x02, //   Input (data, variable, absolute): modifiers
+    0x95, 0x01, //   Report count (1)
+    0x75, 0x08, //   Report size (8)
+    0x81, 0x01, //   Input (constant): reserved
+    0x95, 0x05, //   Report count (5)
+    0x75, 0x01, //   Report size (1)
+    0x05, 0x08, //   Usage page (LEDs)
+    0x19, 0x01, //   Usage minimum (num lock)
+    0x29, 0x05, //   Usage maximum (kana)
+    0x91, 0x02, //   Output (data, variable, absolute): LEDs
+    0x95, 0x01, //   Report count (1)
+    0x75, 0x03, //   Report size (3)
+    0x91, 0x01, //   Output (constant): padding
+    0x95, 0x06, //   Report count (6)
+    0x75, 0x08, //   Report size (8)
+    0x15, 0x00, //   Logical minimum (0)
+    0x25, 0x65, //   Logical maximum (101)
+    0x05, 0x07, //   Usage page (keyboard)
+    0x19, 0x00, //   Usage minimum (0)
+    0x29, 0x65, //   Usage maximum (101)
+    0x81, 0x00, //   Input (data, array): key codes
+    0xC0, // End collection
+];
+
+pub struct Hid<'d> {
+    report_descriptor: &'d [u8],
+    subclass: u8,
+    protocol: u8,
+    /// Interface number, assigned by `describe`
+    interface: u8,
+    in_address: u8,
+    packet_size: u16,
+    interval: u8,
+    /// The last input report sent, for GET_REPORT
+    input: [u8; REPORT_SIZE],
+    input_len: usize,
+    output: [u8; REPORT_SIZE],
+    output_len: usize,
+    /// An output report arrived that `take_output` has not taken
+    output_new: bool,
+    /// Idle rate in 4 ms units, 0 for reports on change only
+    idle: u8,
+    boot_protocol: bool,
+    configured: bool,
+    /// A report is on the IN endpoint
+    in_busy: bool,
+}
+
+impl<'d> Hid<'d> {
+    /// An interface described by `report_descriptor`, sending input reports
+    /// of up to `packet_size` bytes on endpoint `in_number` every
+    /// `interval` ms at most
+    pub const fn new(
+        in_number: u8,
+        report_descriptor: &'d [u8],
+        packet_size: u16,
+        interval: u8,
+    ) -> Self {
+        Self {
+            report_descriptor,
+            subclass: SUBCLASS_NONE,
+            protocol: PROTOCOL_NONE,
+            interface: 0,
+            in_address: endpoint::DIRECTION_MASK | (in_number & endpoint::NUMBER_MASK),
+            packet_size,
+            interval,
+            input: [0; REPORT_SIZE],
+            input_len: 0,
+            output: [0; REPORT_SIZE],
+            output_len: 0,
+            output_new: false,
+            idle: 0,
+            boot_protocol: false,
+            configured: false,
+            in_busy: false,
+        }
+    }
+
+    /// Make the interface a boot device with `protocol`, `PROTOCOL_KEYBOARD`
+    /// or `PROTOCOL_MOUSE`; its reports must then be those of the boot
+    /// protocol
+    pub const fn boot(mut self, protocol: u8) -> Self {
+        self.subclass = SUBCLASS_BOOT;
+        self.protocol = protocol;
+        self
+    }
+
+    fn hid_descriptor(&self) -> [u8; 9] {
+        let [len_lo, len_hi] = (self.report_descriptor.len() as u16).to_le_bytes();
+        // HID 1.11, no country, one report descriptor
+        [
+            9,
+            DESCRIPTOR_HID,
+            0x11,
+            0x01,
+            0,
+            1,
+            DESCRIPTOR_REPORT,
+            len_lo,
+            len_hi,
+        ]
+    }
+
+    /// Add the interface, its HID descriptor and its endpoint to a
+    /// configuration
+    pub fn describe(&mut self, config: &mut ConfigBuilder) -> Result<(), i32> {
+        self.interface = config.interface(CLASS_HID, self.subclass, self.protocol)?;
+        config.descriptor(DESCRIPTOR_HID, &self.hid_descriptor()[2..])?;
+        config.endpoint(
+            self.in_address,
+            EndpointType::Interrupt,
+            self.packet_size,
+            self.interval,
+        )?;
+        Ok(())
+    }
+
+    pub fn is_configured(&self) -> bool {
+        self.configured
+    }
+
+    /// Whether the host switched to the boot protocol
+    pub fn is_boot_protocol(&self) -> bool {
+        self.boot_protocol
+    }
+
+    /// Idle rate the host asked for, in 4 ms units
+    pub fn idle(&self) -> u8 {
+        self.idle
+    }
+
+    /// Send an input report. Returns Err(-5) while the last one has not
+    /// gone out or the device is not configured, Err(-1) if `report` is
+    /// longer than a packet.
+    pub fn send<B: Bus>(&mut self, bus: &mut B, report: &[u8]) -> Result<(), i32> {
+        if report.len() > self.packet_size as usize {
+            return Err(-1);
+        }
+        if !self.configured || self.in_busy {
+            return Err(-5);
+        }
+        bus.write(self.in_address, report)?;
+        self.in_busy = true;
+        self.input[..report.len()].copy_from_slice(report);
+        self.input_len = report.len();
+        Ok(())
+    }
+
+    /// Whether an input report waits to go out
+    pub fn is_busy(&self) -> bool {
+        self.in_busy
+    }
+
+    /// Take the output report that arrived since the last call into `buf`
+    /// and return its length
+    pub fn take_output(&mut self, buf: &mut [u8]) -> Option<usize> {
+        if !self.output_new {
+            return None;
+        }
+        self.output_new = false;
+        let len = self.output_len.min(buf.len());
+        buf[..len].copy_from_slice(&self.output[..len]);
+        Some(len)
+    }
+
+    /// The last output report, taken or not
+    pub fn output(&self) -> &[u8] {
+        &self.output[..self.output_len]
+    }
+
+    fn is_mine(&self, request: &SetupPacket) -> bool {
+        request.recipient() == Recipient::Interface && request.index == self.interface as u16
+    }
+}
+
+impl<B: Bus> Class<B> for Hid<'_> {
+    fn reset(&mut self) {
+        self.configured = false;
+        self.in_busy = false;
+        self.input_len = 0;
+        self.idle = 0;
+        self.boot_protocol = false;
+    }
+
+    fn configured(&mut self, _bus: &mut B) {
+        self.configured = true;
+    }
+
+    fn control_in(&mut self, request: &SetupPacket, buf: &mut [u8]) -> Result<usize, i32> {
+        if !self.is_mine(request) {
+            return Err(-2);
+        }
+        let hid_descriptor = self.hid_descriptor();
+        let protocol = if self.boot_protocol {
+            BOOT_PROTOCOL
+        } else {
+            REPORT_PROTOCOL
+        };
+        let idle = self.idle;
+        let answer: &[u8] = match (request.kind(), request.request) {
+            (Kind::Standard, standard::GET_DESCRIPTOR) => match request.descriptor_type() {
+                DESCRIPTOR_HID => &hid_descriptor,
+                DESCRIPTOR_REPORT => self.report_descriptor,
+                _ => return Err(-1),
+            },
+            (Kind::Class, request::GET_REPORT) => &self.input[..self.input_len],
+            (Kind::Class, request::GET_IDLE) => &[idle],
+            (Kind::Class, request::GET_PROTOCOL) => &[protocol],
+            (Kind::Class, _) => return Err(-1),
+            _ => return Err(-2),
+        };
+        let len = answer.len();
+        if len > buf.len() {
+            return Err(-5);
+        }
+        buf[..len].copy_from_slice(answer);
+        Ok(len)
+    }
+
+    fn control_out(&mut self, request: &SetupPacket, data: &[u8]) -> Result<(), i32> {
+        if !self.is_mine(request) || request.kind() != Kind::Class {
+            return Err(-2);
+        }
+        match request.request {
+            request::SET_REPORT => {
+                if data.len() > REPORT_SIZE {
+                    return Err(-1);
+                }
+                self.output[..data.len()].copy_from_slice(data);
+                self.output_len = data.len();
+                self.output_new = true;
+                Ok(())
+            }
+            request::SET_IDLE => {
+                self.idle = (request.value >> 8) as u8;
+                Ok(())
+            }
+            request::SET_PROTOCOL if self.subclass == SUBCLASS_BOOT => {
+                self.boot_protocol = request.value == BOOT_PROTOCOL as u16;
+                Ok(())
+            }
+            _ => Err(-1),
+        }
+    }
+
+    fn endpoint_in_complete(&mut self, _bus: &mut B, address: u8) {
+        if address == self.in_address {
+            self.in_busy = false;
+        }
+    }
+}
+
+/// Modifier bits, the first byte of a keyboard report
+pub mod modifier {
+    pub const LEFT_CTRL: u8 = 0x01;
+    pub const LEFT_SHIFT: u8 = 0x02;
+    pub const LEFT_ALT: u8 = 0x04;
+    pub const LEFT_GUI: u8 = 0x08;
+    pub const RIGHT_CTRL: u8 = 0x10;
+    pub const RIGHT_SHIFT: u8 = 0x20;
+    pub const RIGHT_ALT: u8 = 0x40;
+    pub const RIGHT_GUI: u8 = 0x80;
+}
+
+/// Key codes of the keyboard usage page
+pub mod key {
+    /// More keys are down than a report holds
+    pub const ERROR_ROLL_OVER: u8 = 0x01;
+    pub const A: u8 = 0x04;
+    pub const Z: u8 = 0x1D;
+    pub const N1: u8 = 0x1E;
+    pub const N0: u8 = 0x27;
+    pub const ENTER: u8 = 0x28;
+    pub const ESCAPE: u8 = 0x29;
+    pub const BACKSPACE: u8 = 0x2A;
+    pub const TAB: u8 = 0x2B;
+    pub const SPACE: u8 = 0x2C;
+    pub const CAPS_LOCK: u8 = 0x39;
+    pub const F1: u8 = 0x3A;
+    pub const F12: u8 = 0x45;
+    pub const RIGHT: u8 = 0x4F;
+    pub const LEFT: u8 = 0x50;
+    pub const DOWN: u8 = 0x51;
+    pub const UP: u8 = 0x52;
+}
+
+/// LED bits of the keyboard output report
+pub mod led {
+    pub const NUM_LOCK: u8 = 0x01;
+    pub const CAPS_LOCK: u8 = 0x02;
+    pub const SCROLL_LOCK: u8 = 0x04;
+    pub const COMPOSE: u8 = 0x08;
+    pub const KANA: u8 = 0x10;
+}
+
+/// Input report of the boot keyboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyboardReport {
+    pub modifiers: u8,
+    pub keys: [u8; 6],
+}
+
+impl KeyboardReport {
+    pub const SIZE: usize = 8;
+
+    /// A report with `keys` down; more than six report ERROR_ROLL_OVER
+    pub fn new(modifiers: u8, keys: &[u8]) -> Self {
+        let mut report = Self {
+            modifiers,
+            keys: [0; 6],
+        };
+        if keys.len() > report.keys.len() {
+            report.keys = [key::ERROR_ROLL_OVER; 6];
+        } else {
+            report.keys[..keys.len()].copy_from_slice(keys);
+        }
+        report
+    }
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let k = self.keys;
+        [self.modifiers, 0, k[0], k[1], k[2], k[3], k[4], k[5]]
+    }
+
+    /// Returns Err(-1) if `bytes` is shorter than a report
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        let bytes: &[u8; Self::SIZE] = bytes.get(..Self::SIZE).ok_or(-1)?.try_into().unwrap();
+        let mut keys = [0; 6];
+        keys.copy_from_slice(&bytes[2..]);
+        Ok(Self {
+            modifiers: bytes[0],
+            keys,
+        })
+    }
+
+    /// Whether the keyboard had more keys down than the report holds
+    pub fn is_roll_over(&self) -> bool {
+        self.keys[0] == key::ERROR_ROLL_OVER
+    }
+
+    /// Keys down in this report that were not in `before`
+    pub fn pressed_since(&self, before: &Self) -> impl Iterator<Item = u8> + '_ {
+        let before = before.keys;
+        self.keys
+            .iter()
+            .copied()
+            .filter(move |&key| key > key::ERROR_ROLL_OVER && !before.contains(&key))
+    }
+}
+
+/// Modifiers and key code typing `c` on a US layout, for the printable
+/// ASCII characters, newline and tab
+pub fn ascii_key(c: char) -> Option<(u8, u8)> {
+    const SHIFTED: &[u8; 21] = b"!@#$%^&*()_+{}|:\"~<>?";
+    const PLAIN: &[u8; 21] = b"1234567890-=[]\\;'`,./";
+    let shift = modifier::LEFT_SHIFT;
+    let key = match c {
+        'a'..='z' => (0, key::A + (c as u8 - b'a')),
+        'A'..='Z' => (shift, key::A + (c as u8 - b'A')),
+        '\n' => (0, key::ENTER),
+        '\t' => (0, key::TAB),
+        ' ' => (0, key::SPACE),
+        _ if c.is_ascii() => {
+            let byte = c as u8;
+            let (modifiers, index) = match PLAIN.iter().position(|&b| b == byte) {
+                Some(index) => (0, index),
+                None => (shift, SHIFTED.iter().position(|&b| b == byte)?),
+            };
+            (modifiers, usage_of(PLAIN[index]))
+        }
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// The character `key` types with `modifiers` on a US layout, the reverse
+/// of `ascii_key`; either shift key shifts and the other modifiers are
+/// ignored
+pub fn key_char(modifiers: u8, key: u8) -> Option<char> {
+    let shift = if modifiers & (modifier::LEFT_SHIFT | modifier::RIGHT_SHIFT) != 0 {
+        modifier::LEFT_SHIFT
+    } else {
+        0
+    };
+    (' '..='~')
+        .chain(['\n', '\t'])
+        .find(|&c| ascii_key(c) == Some((shift, key)))
+}
+
+fn usage_of(plain: u8) -> u8 {
+    match plain {
+        b'1'..=b'9' => key::N1 + (plain - b'1'),
+        b'0' => key::N0,
+        b'-' => 0x2D,
+        b'=' => 0x2E,
+        b'[' => 0x2F,
+        b']' => 0x30,
+        b'\\' => 0x31,
+        b';' => 0x33,
+        b'\'' => 0x34,
+        b'`' => 0x35,
+        b',' => 0x36,
+        b'.' => 0x37,
+        _ => 0x38, // '/'
+    }
+}
+
+/// A boot keyboard: reports the keys down and shows the LEDs the host sets
+pub struct Keyboard {
+    hid: Hid<'static>,
+    report: KeyboardReport,
+    /// A report is waiting for the endpoint
+    dirty: bool,
+}
+
+impl Keyboard {
+    /// Polling interval of the endpoint, in ms
+    pub const INTERVAL: u8 = 10;
+
+    pub const fn new(in_number: u8) -> Self {
+        Self {
+            hid: Hid::new(
+                in_number,
+                &BOOT_KEYBOARD_REPORT,
+                KeyboardReport::SIZE as u16,
+                Self::INTERVAL,
+            )
+            .boot(PROTOCOL_KEYBOARD),
+            report: KeyboardReport {
+                modifiers: 0,
+                keys: [0; 6],
+            },
+            dirty: false,
+        }
+    }
+
+    pub fn describe(&mut self, config: &mut ConfigBuilder) -> Result<(), i32> {
+        self.hid.describe(config)
+    }
+
+    pub fn hid(&self) -> &Hid<'static> {
+        &self.hid
+    }
+
+    /// Report `keys` down with `modifiers`, replacing what was down before;
+    /// `poll` sends the report once the endpoint is free
+    pub fn press<B: Bus>(&mut self, bus: &mut B, modifiers: u8, keys: &[u8]) {
+        let report = KeyboardReport::new(modifiers, keys);
+        if report != self.report {
+            self.report = report;
+            self.dirty = true;
+        }
+        self.poll(bus);
+    }
+
+    pub fn release_all<B: Bus>(&mut self, bus: &mut B) {
+        self.press(bus, 0, &[]);
+    }
+
+    /// Whether a report is still to go out
+    pub fn is_busy(&self) -> bool {
+        self.dirty || self.hid.is_busy()
+    }
+
+    /// The LEDs the host last set, `led` bits
+    pub fn leds(&self) -> u8 {
+        self.hid.output().first().copied().unwrap_or(0)
+    }
+
+    /// Send the report if it changed and the endpoint is free
+    pub fn poll<B: Bus>(&mut self, bus: &mut B) {
+        if self.dirty && self.hid.send(bus, &self.report.encode()).is_ok() {
+            self.dirty = false;
+        }
+    }
+}
+
+impl<B: Bus> Class<B> for Keyboard {
+    fn reset(&mut self) {
+        Class::<B>::reset(&mut self.hid);
+        self.report = KeyboardReport::default();
+        self.dirty = false;
+    }
+
+    fn configured(&mut self, bus: &mut B) {
+        self.hid.configured(bus);
+    }
+
+    fn control_in(&mut self, request: &SetupPacket, buf: &mut [u8]) -> Result<usize, i32> {
+        // GET_REPORT gets the keys down now, whether or not their report
+        // went out
+        if request.kind() == Kind::Class
+            && request.request == request::GET_REPORT
+            && self.hid.is_mine(request)
+        {
+            buf[..KeyboardReport::SIZE].copy_from_slice(&self.report.encode());
+            return Ok(KeyboardReport::SIZE);
+        }
+        Class::<B>::control_in(&mut self.hid, request, buf)
+    }
+
+    fn control_out(&mut self, request: &SetupPacket, data: &[u8]) -> Result<(), i32> {
+        Class::<B>::control_out(&mut self.hid, request, data)
+    }
+
+    fn endpoint_in_complete(&mut self, bus: &mut B, address: u8) {
+        self.hid.endpoint_in_complete(bus, address);
+        self.poll(bus);
+    }
+}
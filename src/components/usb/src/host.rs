@@ -0,0 +1,519 @@
+//! The host side of the bus: what the stack needs from a host controller,
+//! control, bulk and interrupt transfers made of its transactions, and
+//! enumeration of the device on the port. `host_hid` and `host_msc` drive
+//! the keyboard or the drive found there.
+//!
+//! A controller runs one transaction at a time on a channel and reports
+//! how the device answered. The data toggles of the pipes are kept here,
+//! and NAKs and transaction errors are retried here, so the controller
+//! does only what it is asked.
+//!
+//! ```ignore
+//! loop {
+//!     match bus.poll() {
+//!         Some(PortEvent::Connected) => {
+//!             let mut device = host::enumerate(&mut bus, 1)?;
+//!             keyboard = host_hid::Keyboard::open(&mut bus, &mut device).ok();
+//!         }
+//!         Some(PortEvent::Disconnected | PortEvent::OverCurrent) => keyboard = None,
+//!         None => {}
+//!     }
+//! }
+//! ```
+
+use crate::descriptor::{self, DeviceDescriptor, EndpointDescriptor, kind};
+use crate::endpoint::{self, EndpointType};
+use crate::setup::{Direction, Kind, Recipient, SetupPacket, feature, request};
+
+/// Longest configuration descriptor `enumerate` reads, with all that
+/// follows it
+pub const CONFIG_SIZE: usize = 256;
+
+/// Longest packet, full speed
+pub const MAX_PACKET: usize = 64;
+
+/// How long a transaction is retried while the device answers NAK, in ms
+pub const NAK_TIMEOUT_MS: u32 = 1000;
+
+const CONFIG_HEADER_SIZE: usize = 9;
+
+/// Tries of a transaction that gets no valid answer
+const ERROR_TRIES: u32 = 3;
+
+/// Recovery after a reset before the first request, and after
+/// SET_ADDRESS before the device answers at its address, in ms
+const RESET_RECOVERY_MS: u32 = 10;
+const SET_ADDRESS_RECOVERY_MS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    Low,
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortEvent {
+    /// A device was plugged in, to be reset and enumerated
+    Connected,
+    Disconnected,
+    /// The port power switch reported over-current and is off
+    OverCurrent,
+}
+
+/// Where transactions go: an endpoint of a device, on a channel of the
+/// controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pipe {
+    pub channel: u8,
+    /// Device address
+    pub address: u8,
+    /// Endpoint address; the direction bit does not matter for control
+    pub endpoint: u8,
+    pub endpoint_type: EndpointType,
+    pub max_packet_size: u16,
+    pub low_speed: bool,
+    /// Data toggle of the next transaction, DATA1 when set
+    pub toggle: bool,
+}
+
+/// A host controller with one port. Transactions move one packet with
+/// the data toggle of the pipe and do not change it.
+pub trait HostBus {
+    /// Take the next event of the port
+    fn poll(&mut self) -> Option<PortEvent>;
+
+    fn is_connected(&self) -> bool;
+
+    /// Reset the device on the port and return its speed once the port is
+    /// enabled. Returns Err(-2) if nothing is connected, Err(-3) if the
+    /// port does not come up.
+    fn reset(&mut self) -> Result<Speed, i32>;
+
+    /// Returns Err(-5) if every channel is taken
+    fn alloc_channel(&mut self) -> Result<u8, i32>;
+
+    fn free_channel(&mut self, channel: u8);
+
+    /// Send a SETUP packet on a control pipe. Returns Err(-4) if the
+    /// device did not acknowledge it.
+    fn setup(&mut self, pipe: &Pipe, packet: &[u8; SetupPacket::SIZE]) -> Result<(), i32>;
+
+    /// Run an IN transaction and return the length of the packet. Returns
+    /// Err(-5) on NAK, Err(-3) on STALL, Err(-4) when there is no valid
+    /// answer or the packet is longer than `buf`.
+    fn read(&mut self, pipe: &Pipe, buf: &mut [u8]) -> Result<usize, i32>;
+
+    /// Run an OUT transaction with `data`, possibly empty. Returns Err(-5)
+    /// on NAK, Err(-3) on STALL, Err(-4) when there is no valid answer.
+    fn write(&mut self, pipe: &Pipe, data: &[u8]) -> Result<(), i32>;
+
+    fn delay_ms(&mut self, ms: u32);
+}
+
+/// Run `transaction` until the device takes it, waiting out NAKs for
+/// `NAK_TIMEOUT_MS` and trying again after an error
+fn retry<H: HostBus, T>(
+    bus: &mut H,
+    mut transaction: impl FnMut(&mut H) -> Result<T, i32>,
+) -> Result<T, i32> {
+    let mut waited = 0;
+    let mut errors = 0;
+    loop {
+        match transaction(bus) {
+            Err(-5) if waited < NAK_TIMEOUT_MS => {
+                bus.delay_ms(1);
+                waited += 1;
+            }
+            Err(-4) if errors + 1 < ERROR_TRIES => errors += 1,
+            result => return result,
+        }
+    }
+}
+
+/// Read packets from `pipe` into `buf` until it is full or a packet is
+/// short, and return the length read. Returns Err(-4) if the device sends
+/// more than `buf` takes.
+pub fn bulk_in<H: HostBus>(bus: &mut H, pipe: &mut Pipe, buf: &mut [u8]) -> Result<usize, i32> {
+    let max_packet_size = pipe.max_packet_size as usize;
+    let mut len = 0;
+    while len < buf.len() {
+        let mut packet = [0; MAX_PACKET];
+        let count = retry(bus, |bus| bus.read(pipe, &mut packet[..max_packet_size]))?;
+        pipe.toggle = !pipe.toggle;
+        if count > buf.len() - len {
+            return Err(-4);
+        }
+        buf[len..len + count].copy_from_slice(&packet[..count]);
+        len += count;
+        if count < max_packet_size {
+            break;
+        }
+    }
+    Ok(len)
+}
+
+/// Write `data` to `pipe` in packets
+pub fn bulk_out<H: HostBus>(bus: &mut H, pipe: &mut Pipe, data: &[u8]) -> Result<(), i32> {
+    for chunk in data.chunks(pipe.max_packet_size as usize) {
+        retry(bus, |bus| bus.write(pipe, chunk))?;
+        pipe.toggle = !pipe.toggle;
+    }
+    Ok(())
+}
+
+/// Poll an interrupt IN pipe once: the length of the packet read, or None
+/// if the device had nothing to report
+pub fn interrupt_in<H: HostBus>(
+    bus: &mut H,
+    pipe: &mut Pipe,
+    buf: &mut [u8],
+) -> Result<Option<usize>, i32> {
+    match bus.read(pipe, buf) {
+        Ok(count) => {
+            pipe.toggle = !pipe.toggle;
+            Ok(Some(count))
+        }
+        Err(-5) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Run a control transfer with its data stage from the device into `buf`,
+/// `request.length` bytes at most, and return the length read. Returns
+/// Err(-3) if the device stalled the request.
+pub fn control_in<H: HostBus>(
+    bus: &mut H,
+    pipe: &mut Pipe,
+    request: &SetupPacket,
+    buf: &mut [u8],
+) -> Result<usize, i32> {
+    retry(bus, |bus| bus.setup(pipe, &request.encode()))?;
+    pipe.toggle = true;
+    let length = (request.length as usize).min(buf.len());
+    let len = bulk_in(bus, pipe, &mut buf[..length])?;
+    pipe.toggle = true;
+    retry(bus, |bus| bus.write(pipe, &[]))?;
+    Ok(len)
+}
+
+/// Run a control transfer with `data`, possibly empty, to the device.
+/// Returns Err(-3) if the device stalled the request.
+pub fn control_out<H: HostBus>(
+    bus: &mut H,
+    pipe: &mut Pipe,
+    request: &SetupPacket,
+    data: &[u8],
+) -> Result<(), i32> {
+    retry(bus, |bus| bus.setup(pipe, &request.encode()))?;
+    pipe.toggle = true;
+    bulk_out(bus, pipe, data)?;
+    pipe.toggle = true;
+    retry(bus, |bus| bus.read(pipe, &mut []))?;
+    Ok(())
+}
+
+/// An interface of a configuration read from a device, with the
+/// descriptors that follow it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interface<'a> {
+    pub number: u8,
+    pub alternate: u8,
+    pub class: u8,
+    pub subclass: u8,
+    pub protocol: u8,
+    descriptors: &'a [u8],
+}
+
+impl<'a> Interface<'a> {
+    /// The endpoint and class descriptors of the interface
+    pub fn descriptors(&self) -> &'a [u8] {
+        self.descriptors
+    }
+
+    pub fn endpoints(&self) -> impl Iterator<Item = EndpointDescriptor> + 'a {
+        descriptor::iter(self.descriptors).filter_map(|bytes| EndpointDescriptor::parse(bytes).ok())
+    }
+
+    /// The first endpoint of `endpoint_type` going in the direction given
+    pub fn endpoint(
+        &self,
+        endpoint_type: EndpointType,
+        direction_in: bool,
+    ) -> Option<EndpointDescriptor> {
+        self.endpoints().find(|endpoint| {
+            endpoint.endpoint_type == endpoint_type
+                && endpoint::is_in(endpoint.address) == direction_in
+        })
+    }
+}
+
+/// Walk the interfaces of a configuration, alternate settings included
+pub fn interfaces(config: &[u8]) -> Interfaces<'_> {
+    Interfaces { bytes: config }
+}
+
+pub struct Interfaces<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for Interfaces<'a> {
+    type Item = Interface<'a>;
+
+    fn next(&mut self) -> Option<Interface<'a>> {
+        loop {
+            let bytes = descriptor::iter(self.bytes).next()?;
+            self.bytes = &self.bytes[bytes.len()..];
+            if bytes[1] != kind::INTERFACE || bytes.len() < 9 {
+                continue;
+            }
+            let len = descriptor::iter(self.bytes)
+                .take_while(|next| next[1] != kind::INTERFACE)
+                .map(|next| next.len())
+                .sum();
+            let (descriptors, rest) = self.bytes.split_at(len);
+            self.bytes = rest;
+            return Some(Interface {
+                number: bytes[2],
+                alternate: bytes[3],
+                class: bytes[5],
+                subclass: bytes[6],
+                protocol: bytes[7],
+                descriptors,
+            });
+        }
+    }
+}
+
+/// A device on the port, addressed and configured, with the pipe of its
+/// endpoint 0
+pub struct Attached {
+    pub speed: Speed,
+    pub descriptor: DeviceDescriptor,
+    pub control: Pipe,
+    config: [u8; CONFIG_SIZE],
+    config_len: usize,
+}
+
+/// Reset the device on the port, give it `address`, read its descriptors
+/// and select its first configuration. Returns Err(-1) for an address
+/// outside 1 to 127 or descriptors that make no sense, Err(-5) if the
+/// configuration is longer than `CONFIG_SIZE`, and what the transfers
+/// return if the device does not answer.
+pub fn enumerate<H: HostBus>(bus: &mut H, address: u8) -> Result<Attached, i32> {
+    if !(1..=127).contains(&address) {
+        return Err(-1);
+    }
+    let speed = bus.reset()?;
+    bus.delay_ms(RESET_RECOVERY_MS);
+    let mut device = Attached {
+        speed,
+        descriptor: DeviceDescriptor::new(0, 0),
+        control: Pipe {
+            channel: bus.alloc_channel()?,
+            address: 0,
+            endpoint: 0,
+            endpoint_type: EndpointType::Control,
+            // Until the device descriptor tells
+            max_packet_size: 8,
+            low_speed: speed == Speed::Low,
+            toggle: false,
+        },
+        config: [0; CONFIG_SIZE],
+        config_len: 0,
+    };
+    if let Err(error) = device.set_up(bus, address) {
+        bus.free_channel(device.control.channel);
+        return Err(error);
+    }
+    Ok(device)
+}
+
+fn standard(
+    direction: Direction,
+    recipient: Recipient,
+    request: u8,
+    value: u16,
+    index: u16,
+    length: u16,
+) -> SetupPacket {
+    SetupPacket::new(
+        direction,
+        Kind::Standard,
+        recipient,
+        request,
+        value,
+        index,
+        length,
+    )
+}
+
+/// GET_DESCRIPTOR of `kind` and `index` into `buf`, as much as it takes
+fn get_descriptor<H: HostBus>(
+    bus: &mut H,
+    pipe: &mut Pipe,
+    kind: u8,
+    index: u8,
+    buf: &mut [u8],
+) -> Result<usize, i32> {
+    let value = (kind as u16) << 8 | index as u16;
+    let length = buf.len().min(u16::MAX as usize) as u16;
+    let request = standard(
+        Direction::In,
+        Recipient::Device,
+        request::GET_DESCRIPTOR,
+        value,
+        0,
+        length,
+    );
+    control_in(bus, pipe, &request, buf)
+}
+
+impl Attached {
+    fn set_up<H: HostBus>(&mut self, bus: &mut H, address: u8) -> Result<(), i32> {
+        // The first eight bytes hold the packet size of endpoint 0
+        let mut bytes = [0; DeviceDescriptor::SIZE];
+        let len = get_descriptor(bus, &mut self.control, kind::DEVICE, 0, &mut bytes[..8])?;
+        let max_packet_size = bytes[7];
+        if len < 8 || !matches!(max_packet_size, 8 | 16 | 32 | 64) {
+            return Err(-1);
+        }
+        self.control.max_packet_size = max_packet_size as u16;
+
+        let request = standard(
+            Direction::Out,
+            Recipient::Device,
+            request::SET_ADDRESS,
+            address as u16,
+            0,
+            0,
+        );
+        self.control_out(bus, &request, &[])?;
+        bus.delay_ms(SET_ADDRESS_RECOVERY_MS);
+        self.control.address = address;
+
+        let len = get_descriptor(bus, &mut self.control, kind::DEVICE, 0, &mut bytes)?;
+        self.descriptor = DeviceDescriptor::parse(&bytes[..len])?;
+
+        // The header of the configuration tells the length of the whole
+        let header = &mut self.config[..CONFIG_HEADER_SIZE];
+        let len = get_descriptor(bus, &mut self.control, kind::CONFIGURATION, 0, header)?;
+        if len < CONFIG_HEADER_SIZE || header[1] != kind::CONFIGURATION {
+            return Err(-1);
+        }
+        let total = u16::from_le_bytes([header[2], header[3]]) as usize;
+        if total < CONFIG_HEADER_SIZE {
+            return Err(-1);
+        }
+        if total > CONFIG_SIZE {
+            return Err(-5);
+        }
+        let config = &mut self.config[..total];
+        let len = get_descriptor(bus, &mut self.control, kind::CONFIGURATION, 0, config)?;
+        if len < total {
+            return Err(-1);
+        }
+        self.config_len = total;
+
+        let value = self.config[5] as u16;
+        let request = standard(
+            Direction::Out,
+            Recipient::Device,
+            request::SET_CONFIGURATION,
+            value,
+            0,
+            0,
+        );
+        self.control_out(bus, &request, &[])
+    }
+
+    /// The configuration selected, with all that follows it
+    pub fn config(&self) -> &[u8] {
+        &self.config[..self.config_len]
+    }
+
+    pub fn interfaces(&self) -> Interfaces<'_> {
+        interfaces(self.config())
+    }
+
+    pub fn address(&self) -> u8 {
+        self.control.address
+    }
+
+    /// A control transfer from the device on endpoint 0, see `control_in`
+    pub fn control_in<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        request: &SetupPacket,
+        buf: &mut [u8],
+    ) -> Result<usize, i32> {
+        control_in(bus, &mut self.control, request, buf)
+    }
+
+    /// A control transfer to the device on endpoint 0, see `control_out`
+    pub fn control_out<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        request: &SetupPacket,
+        data: &[u8],
+    ) -> Result<(), i32> {
+        control_out(bus, &mut self.control, request, data)
+    }
+
+    /// Read descriptor `kind` number `index` into `buf`
+    pub fn get_descriptor<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        kind: u8,
+        index: u8,
+        buf: &mut [u8],
+    ) -> Result<usize, i32> {
+        get_descriptor(bus, &mut self.control, kind, index, buf)
+    }
+
+    /// Take a channel for `endpoint`. Returns Err(-1) for a packet size
+    /// over `MAX_PACKET`, Err(-5) if every channel is taken.
+    pub fn open<H: HostBus>(
+        &self,
+        bus: &mut H,
+        endpoint: &EndpointDescriptor,
+    ) -> Result<Pipe, i32> {
+        if endpoint.max_packet_size as usize > MAX_PACKET || endpoint.max_packet_size == 0 {
+            return Err(-1);
+        }
+        Ok(Pipe {
+            channel: bus.alloc_channel()?,
+            address: self.control.address,
+            endpoint: endpoint.address,
+            endpoint_type: endpoint.endpoint_type,
+            max_packet_size: endpoint.max_packet_size,
+            low_speed: self.control.low_speed,
+            toggle: false,
+        })
+    }
+
+    /// CLEAR_FEATURE(ENDPOINT_HALT) on the endpoint of `pipe`, which starts
+    /// again from DATA0
+    pub fn clear_halt<H: HostBus>(&mut self, bus: &mut H, pipe: &mut Pipe) -> Result<(), i32> {
+        let request = standard(
+            Direction::Out,
+            Recipient::Endpoint,
+            request::CLEAR_FEATURE,
+            feature::ENDPOINT_HALT,
+            pipe.endpoint as u16,
+            0,
+        );
+        self.control_out(bus, &request, &[])?;
+        pipe.toggle = false;
+        Ok(())
+    }
+
+    /// Give back the channel of endpoint 0, the device being gone
+    pub fn release<H: HostBus>(self, bus: &mut H) {
+        bus.free_channel(self.control.channel);
+    }
+}
+
+/// Give back the channel of a pipe
+pub fn close<H: HostBus>(bus: &mut H, pipe: Pipe) {
+    bus.free_channel(pipe.channel);
+}
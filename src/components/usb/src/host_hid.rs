@@ -0,0 +1,166 @@
+//! Host driver of HID keyboards through the boot protocol, which every
+//! keyboard speaks whatever its report descriptor says: the interface is
+//! switched to it, reports are read from the interrupt IN endpoint, and
+//! the LEDs go out with SET_REPORT.
+//!
+//! ```ignore
+//! let mut keyboard = Keyboard::open(&mut bus, &mut device)?;
+//! loop {
+//!     if keyboard.poll(&mut bus)? {
+//!         for c in keyboard.chars() {
+//!             shell.input(c);
+//!         }
+//!     }
+//!     bus.delay_ms(keyboard.interval() as u32);
+//! }
+//! ```
+
+use crate::endpoint::EndpointType;
+use crate::hid::{
+    BOOT_PROTOCOL, CLASS_HID, KeyboardReport, PROTOCOL_KEYBOARD, SUBCLASS_BOOT, key_char,
+    report_type, request,
+};
+use crate::host::{self, Attached, HostBus, MAX_PACKET, Pipe};
+use crate::setup::{Direction, Kind, Recipient, SetupPacket};
+
+pub struct Keyboard {
+    interface: u8,
+    pipe: Pipe,
+    /// Polling interval of the endpoint, in ms
+    interval: u8,
+    report: KeyboardReport,
+    /// The report before `report`
+    before: KeyboardReport,
+    leds: u8,
+}
+
+fn class_request(
+    direction: Direction,
+    request: u8,
+    value: u16,
+    interface: u8,
+    length: u16,
+) -> SetupPacket {
+    SetupPacket::new(
+        direction,
+        Kind::Class,
+        Recipient::Interface,
+        request,
+        value,
+        interface as u16,
+        length,
+    )
+}
+
+impl Keyboard {
+    /// Find the boot keyboard interface of `device`, switch it to the boot
+    /// protocol with no repeat of unchanged reports and take a channel for
+    /// its interrupt endpoint. Returns Err(-2) if the device has none.
+    pub fn open<H: HostBus>(bus: &mut H, device: &mut Attached) -> Result<Self, i32> {
+        let (interface, endpoint) = device
+            .interfaces()
+            .filter(|interface| {
+                interface.alternate == 0
+                    && interface.class == CLASS_HID
+                    && interface.subclass == SUBCLASS_BOOT
+                    && interface.protocol == PROTOCOL_KEYBOARD
+            })
+            .find_map(|interface| {
+                let endpoint = interface.endpoint(EndpointType::Interrupt, true)?;
+                Some((interface.number, endpoint))
+            })
+            .ok_or(-2)?;
+        let set_protocol = class_request(
+            Direction::Out,
+            request::SET_PROTOCOL,
+            BOOT_PROTOCOL as u16,
+            interface,
+            0,
+        );
+        device.control_out(bus, &set_protocol, &[])?;
+        // SET_IDLE is optional, and some keyboards stall it
+        let set_idle = class_request(Direction::Out, request::SET_IDLE, 0, interface, 0);
+        match device.control_out(bus, &set_idle, &[]) {
+            Ok(()) | Err(-3) => {}
+            Err(error) => return Err(error),
+        }
+        Ok(Self {
+            interface,
+            pipe: device.open(bus, &endpoint)?,
+            interval: endpoint.interval.max(1),
+            report: KeyboardReport::default(),
+            before: KeyboardReport::default(),
+            leds: 0,
+        })
+    }
+
+    /// How often to `poll`, in ms
+    pub fn interval(&self) -> u8 {
+        self.interval
+    }
+
+    /// Read a report if the keyboard has one and return whether the keys
+    /// changed. A report of more keys than it holds leaves the keys as
+    /// they were.
+    pub fn poll<H: HostBus>(&mut self, bus: &mut H) -> Result<bool, i32> {
+        let mut packet = [0; MAX_PACKET];
+        let size = self.pipe.max_packet_size as usize;
+        let Some(len) = host::interrupt_in(bus, &mut self.pipe, &mut packet[..size])? else {
+            return Ok(false);
+        };
+        let report = KeyboardReport::parse(&packet[..len])?;
+        if report.is_roll_over() || report == self.report {
+            return Ok(false);
+        }
+        self.before = self.report;
+        self.report = report;
+        Ok(true)
+    }
+
+    /// The keys down, as the last report had them
+    pub fn report(&self) -> &KeyboardReport {
+        &self.report
+    }
+
+    /// Keys that went down with the last report
+    pub fn pressed(&self) -> impl Iterator<Item = u8> + '_ {
+        self.report.pressed_since(&self.before)
+    }
+
+    /// What the keys that went down with the last report type, on a US
+    /// layout
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        let modifiers = self.report.modifiers;
+        self.pressed()
+            .filter_map(move |key| key_char(modifiers, key))
+    }
+
+    /// Light the `led` bits given and put out the others
+    pub fn set_leds<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+        leds: u8,
+    ) -> Result<(), i32> {
+        let value = (report_type::OUTPUT as u16) << 8;
+        let request = class_request(
+            Direction::Out,
+            request::SET_REPORT,
+            value,
+            self.interface,
+            1,
+        );
+        device.control_out(bus, &request, &[leds])?;
+        self.leds = leds;
+        Ok(())
+    }
+
+    pub fn leds(&self) -> u8 {
+        self.leds
+    }
+
+    /// Give back the channel of the endpoint
+    pub fn close<H: HostBus>(self, bus: &mut H) {
+        host::close(bus, self.pipe);
+    }
+}
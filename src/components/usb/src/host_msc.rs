@@ -0,0 +1,349 @@
+//! Host driver of USB drives: mass storage with the bulk-only transport
+//! and SCSI commands, logical unit 0, 512-byte blocks.
+//!
+//! Each command goes out as a CBW, moves its data and ends with the CSW.
+//! A stalled data phase has its halt cleared before the CSW is read, a
+//! CSW that does not match the CBW or reports a phase error gets the
+//! reset recovery of the specification, and a failed command has its
+//! sense read with REQUEST SENSE.
+//!
+//! ```ignore
+//! let mut drive = Drive::open(&mut bus, &mut device)?;
+//! let mut block = [0; BLOCK_SIZE];
+//! drive.read_blocks(&mut bus, &mut device, 0, &mut block)?;
+//! ```
+
+use crate::endpoint::EndpointType;
+use crate::host::{self, Attached, HostBus, Pipe};
+use crate::msc::{
+    BLOCK_SIZE, CLASS_MASS_STORAGE, Cbw, Csw, PROTOCOL_BULK_ONLY, SUBCLASS_SCSI, Status, request,
+    scsi, sense,
+};
+use crate::setup::{Direction, Kind, Recipient, SetupPacket};
+
+/// Tries of TEST UNIT READY while the drive comes up
+const READY_TRIES: u32 = 10;
+const READY_DELAY_MS: u32 = 100;
+
+/// Length of the INQUIRY data read
+const INQUIRY_SIZE: usize = 36;
+/// Length of the fixed sense data read
+const SENSE_SIZE: usize = 18;
+
+enum Data<'b> {
+    None,
+    In(&'b mut [u8]),
+    Out(&'b [u8]),
+}
+
+pub struct Drive {
+    interface: u8,
+    bulk_in: Pipe,
+    bulk_out: Pipe,
+    tag: u32,
+    block_count: u32,
+    /// Vendor and product from INQUIRY
+    vendor: [u8; 8],
+    product: [u8; 16],
+    sense: (u8, u8),
+}
+
+impl Drive {
+    /// Find the bulk-only SCSI interface of `device`, take channels for its
+    /// endpoints and wait for the drive to be ready. Returns Err(-2) if the
+    /// device has no such interface or its blocks are not 512 bytes,
+    /// Err(-4) if the drive does not come ready.
+    pub fn open<H: HostBus>(bus: &mut H, device: &mut Attached) -> Result<Self, i32> {
+        let (interface, bulk_in, bulk_out) = device
+            .interfaces()
+            .filter(|interface| {
+                interface.alternate == 0
+                    && interface.class == CLASS_MASS_STORAGE
+                    && interface.subclass == SUBCLASS_SCSI
+                    && interface.protocol == PROTOCOL_BULK_ONLY
+            })
+            .find_map(|interface| {
+                let bulk_in = interface.endpoint(EndpointType::Bulk, true)?;
+                let bulk_out = interface.endpoint(EndpointType::Bulk, false)?;
+                Some((interface.number, bulk_in, bulk_out))
+            })
+            .ok_or(-2)?;
+        let bulk_in = device.open(bus, &bulk_in)?;
+        let bulk_out = match device.open(bus, &bulk_out) {
+            Ok(pipe) => pipe,
+            Err(error) => {
+                host::close(bus, bulk_in);
+                return Err(error);
+            }
+        };
+        let mut drive = Self {
+            interface,
+            bulk_in,
+            bulk_out,
+            tag: 0,
+            block_count: 0,
+            vendor: [b' '; 8],
+            product: [b' '; 16],
+            sense: sense::NO_SENSE,
+        };
+        if let Err(error) = drive.start(bus, device) {
+            drive.close(bus);
+            return Err(error);
+        }
+        Ok(drive)
+    }
+
+    fn start<H: HostBus>(&mut self, bus: &mut H, device: &mut Attached) -> Result<(), i32> {
+        // Only unit 0 is used, but a drive with one may stall the request
+        let request = self.class_request(Direction::In, request::GET_MAX_LUN, 1);
+        match device.control_in(bus, &request, &mut [0]) {
+            Ok(_) | Err(-3) => {}
+            Err(error) => return Err(error),
+        }
+
+        let mut inquiry = [0; INQUIRY_SIZE];
+        let command = [scsi::INQUIRY, 0, 0, 0, INQUIRY_SIZE as u8, 0];
+        let len = self.run(bus, device, &command, Data::In(&mut inquiry))?;
+        if len < INQUIRY_SIZE {
+            return Err(-4);
+        }
+        self.vendor.copy_from_slice(&inquiry[8..16]);
+        self.product.copy_from_slice(&inquiry[16..32]);
+
+        // The first command after power-up fails with a unit attention
+        let mut tries = 0;
+        loop {
+            match self.run(
+                bus,
+                device,
+                &[scsi::TEST_UNIT_READY, 0, 0, 0, 0, 0],
+                Data::None,
+            ) {
+                Ok(_) => break,
+                Err(-4) if tries + 1 < READY_TRIES => {
+                    tries += 1;
+                    bus.delay_ms(READY_DELAY_MS);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut capacity = [0; 8];
+        let command = [scsi::READ_CAPACITY_10, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        if self.run(bus, device, &command, Data::In(&mut capacity))? < capacity.len() {
+            return Err(-4);
+        }
+        let last = u32::from_be_bytes([capacity[0], capacity[1], capacity[2], capacity[3]]);
+        let block_size = u32::from_be_bytes([capacity[4], capacity[5], capacity[6], capacity[7]]);
+        if block_size != BLOCK_SIZE as u32 {
+            return Err(-2);
+        }
+        self.block_count = last.wrapping_add(1);
+        Ok(())
+    }
+
+    pub fn block_count(&self) -> u32 {
+        self.block_count
+    }
+
+    /// Vendor from INQUIRY, without the padding
+    pub fn vendor(&self) -> &str {
+        text(&self.vendor)
+    }
+
+    /// Product from INQUIRY, without the padding
+    pub fn product(&self) -> &str {
+        text(&self.product)
+    }
+
+    /// Sense key and additional sense code of the last failed command
+    pub fn sense(&self) -> (u8, u8) {
+        self.sense
+    }
+
+    /// Read blocks from `lba` on into `buf`, a whole number of them.
+    /// Returns Err(-1) for a length that is not or blocks past the end,
+    /// Err(-4) if the drive fails the command, with its `sense`.
+    pub fn read_blocks<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+        lba: u32,
+        buf: &mut [u8],
+    ) -> Result<(), i32> {
+        let command = self.block_command(scsi::READ_10, lba, buf.len())?;
+        let len = buf.len();
+        if self.run(bus, device, &command, Data::In(buf))? < len {
+            return Err(-4);
+        }
+        Ok(())
+    }
+
+    /// Write `data`, a whole number of blocks, from `lba` on. Returns
+    /// Err(-1) for a length that is not or blocks past the end, Err(-4) if
+    /// the drive fails the command, with its `sense`.
+    pub fn write_blocks<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+        lba: u32,
+        data: &[u8],
+    ) -> Result<(), i32> {
+        let command = self.block_command(scsi::WRITE_10, lba, data.len())?;
+        if self.run(bus, device, &command, Data::Out(data))? < data.len() {
+            return Err(-4);
+        }
+        Ok(())
+    }
+
+    /// Give back the channels of the endpoints
+    pub fn close<H: HostBus>(self, bus: &mut H) {
+        host::close(bus, self.bulk_in);
+        host::close(bus, self.bulk_out);
+    }
+
+    fn class_request(&self, direction: Direction, request: u8, length: u16) -> SetupPacket {
+        SetupPacket::new(
+            direction,
+            Kind::Class,
+            Recipient::Interface,
+            request,
+            0,
+            self.interface as u16,
+            length,
+        )
+    }
+
+    /// READ(10) or WRITE(10) of `len` bytes from `lba`
+    fn block_command(&self, opcode: u8, lba: u32, len: usize) -> Result<[u8; 10], i32> {
+        let blocks = len / BLOCK_SIZE;
+        if !len.is_multiple_of(BLOCK_SIZE) || blocks == 0 || blocks > u16::MAX as usize {
+            return Err(-1);
+        }
+        if lba as u64 + blocks as u64 > self.block_count as u64 {
+            return Err(-1);
+        }
+        let [a, b, c, d] = lba.to_be_bytes();
+        let [count_hi, count_lo] = (blocks as u16).to_be_bytes();
+        Ok([opcode, 0, a, b, c, d, 0, count_hi, count_lo, 0])
+    }
+
+    /// Run a command and return the length of its data moved. Returns
+    /// Err(-4) if the drive failed it, after reading why into `sense`.
+    fn run<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+        command: &[u8],
+        data: Data,
+    ) -> Result<usize, i32> {
+        let (status, len) = self.command(bus, device, command, data)?;
+        if status == Status::Passed {
+            self.sense = sense::NO_SENSE;
+            return Ok(len);
+        }
+        let mut data = [0; SENSE_SIZE];
+        let command = [scsi::REQUEST_SENSE, 0, 0, 0, SENSE_SIZE as u8, 0];
+        self.sense = match self.command(bus, device, &command, Data::In(&mut data))? {
+            (Status::Passed, len) if len >= 14 => (data[2] & 0x0F, data[12]),
+            // The drive does not say why
+            _ => (0xFF, 0xFF),
+        };
+        Err(-4)
+    }
+
+    /// Send the CBW of `command`, move its data and read the CSW, and
+    /// return the status with the length moved. A phase error or a CSW
+    /// that makes no sense resets the drive and returns Err(-4).
+    fn command<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+        command: &[u8],
+        data: Data,
+    ) -> Result<(Status, usize), i32> {
+        self.tag = self.tag.wrapping_add(1);
+        let (data_length, data_in) = match &data {
+            Data::None => (0, false),
+            Data::In(buf) => (buf.len(), true),
+            Data::Out(data) => (data.len(), false),
+        };
+        let mut cbw = Cbw {
+            tag: self.tag,
+            data_length: data_length as u32,
+            data_in,
+            lun: 0,
+            command: [0; 16],
+            command_length: command.len() as u8,
+        };
+        cbw.command[..command.len()].copy_from_slice(command);
+        if let Err(error) = host::bulk_out(bus, &mut self.bulk_out, &cbw.encode()) {
+            if error == -3 {
+                self.reset_recovery(bus, device)?;
+            }
+            return Err(error);
+        }
+
+        let moved = match data {
+            Data::None => Ok(0),
+            Data::In(buf) => host::bulk_in(bus, &mut self.bulk_in, buf),
+            Data::Out(data) => host::bulk_out(bus, &mut self.bulk_out, data).map(|()| data.len()),
+        };
+        let moved = match moved {
+            Ok(len) => len,
+            // The drive ends a data phase it will not finish with a stall
+            Err(-3) => {
+                let pipe = if data_in {
+                    &mut self.bulk_in
+                } else {
+                    &mut self.bulk_out
+                };
+                device.clear_halt(bus, pipe)?;
+                0
+            }
+            Err(error) => return Err(error),
+        };
+
+        let csw = match self.status(bus) {
+            Err(-3) => {
+                // A stall before the CSW is cleared and the CSW read again
+                device.clear_halt(bus, &mut self.bulk_in)?;
+                self.status(bus)
+            }
+            csw => csw,
+        };
+        match csw {
+            Ok(csw) if csw.tag == self.tag && csw.status != Status::PhaseError => {
+                let moved = moved.min(data_length - (csw.residue as usize).min(data_length));
+                Ok((csw.status, moved))
+            }
+            Ok(_) | Err(-1) => {
+                self.reset_recovery(bus, device)?;
+                Err(-4)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn status<H: HostBus>(&mut self, bus: &mut H) -> Result<Csw, i32> {
+        let mut bytes = [0; Csw::SIZE];
+        let len = host::bulk_in(bus, &mut self.bulk_in, &mut bytes)?;
+        Csw::parse(&bytes[..len])
+    }
+
+    /// BULK_ONLY_RESET, then clear the halt of both endpoints
+    fn reset_recovery<H: HostBus>(
+        &mut self,
+        bus: &mut H,
+        device: &mut Attached,
+    ) -> Result<(), i32> {
+        let request = self.class_request(Direction::Out, request::BULK_ONLY_RESET, 0);
+        device.control_out(bus, &request, &[])?;
+        device.clear_halt(bus, &mut self.bulk_in)?;
+        device.clear_halt(bus, &mut self.bulk_out)
+    }
+}
+
+fn text(bytes: &[u8]) -> &str {
+    core::str::from_utf8(bytes).unwrap_or("").trim_end()
+}
@@ -0,0 +1,15 @@
+#![no_std]
+
+pub mod bus;
+pub mod cdc;
+pub mod control;
+pub mod descriptor;
+pub mod device;
+pub mod endpoint;
+pub mod hid;
+pub mod host;
+pub mod host_hid;
+pub mod host_msc;
+pub mod mock;
+pub mod msc;
+pub mod setup;
@@ -0,0 +1,410 @@
+//! A device controller for host tests, driven from the host side of the
+//! bus, and `control`, which runs a whole control transfer through it.
+//! `Loopback` puts a device on it behind a host controller, for the host
+//! side of the stack.
+
+use crate::bus::{Bus, Event};
+use crate::device::{Class, Device};
+use crate::endpoint::{self, EndpointType};
+use crate::host::{self, HostBus, PortEvent, Speed};
+use crate::setup::{Direction, SetupPacket};
+
+/// Endpoint numbers 0 to 7
+pub const ENDPOINTS: usize = 8;
+
+/// Longest packet, full speed
+pub const MAX_PACKET: usize = 64;
+
+const EVENTS: usize = 32;
+
+#[derive(Clone, Copy)]
+struct Packet {
+    data: [u8; MAX_PACKET],
+    len: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Pipe {
+    /// Type and packet size while open
+    open: Option<(EndpointType, u16)>,
+    stalled: bool,
+    packet: Option<Packet>,
+}
+
+const CLOSED: Pipe = Pipe {
+    open: None,
+    stalled: false,
+    packet: None,
+};
+
+pub struct MockBus {
+    address: u8,
+    /// Indexed by endpoint number, OUT then IN
+    pipes: [[Pipe; ENDPOINTS]; 2],
+    events: [Option<Event>; EVENTS],
+    head: usize,
+    len: usize,
+}
+
+impl Default for MockBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBus {
+    pub const fn new() -> Self {
+        Self {
+            address: 0,
+            pipes: [[CLOSED; ENDPOINTS]; 2],
+            events: [None; EVENTS],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn pipe(&mut self, address: u8) -> Option<&mut Pipe> {
+        let direction = endpoint::is_in(address) as usize;
+        self.pipes[direction].get_mut(endpoint::number(address) as usize)
+    }
+
+    fn push(&mut self, event: Event) {
+        assert!(self.len < EVENTS, "event queue full");
+        self.events[(self.head + self.len) % EVENTS] = Some(event);
+        self.len += 1;
+    }
+
+    /// Address the device took
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    pub fn is_open(&self, address: u8) -> bool {
+        let direction = endpoint::is_in(address) as usize;
+        self.pipes[direction]
+            .get(endpoint::number(address) as usize)
+            .is_some_and(|pipe| pipe.open.is_some())
+    }
+
+    /// Reset the bus: everything closed, address 0
+    pub fn reset(&mut self) {
+        *self = Self::new();
+        self.push(Event::Reset);
+    }
+
+    pub fn suspend(&mut self) {
+        self.push(Event::Suspend);
+    }
+
+    pub fn resume(&mut self) {
+        self.push(Event::Resume);
+    }
+
+    /// Send a SETUP packet, which clears what is left on endpoint 0 and
+    /// its stall
+    pub fn setup(&mut self, packet: &SetupPacket) {
+        for address in [0x00, 0x80] {
+            if let Some(pipe) = self.pipe(address) {
+                pipe.stalled = false;
+                pipe.packet = None;
+            }
+        }
+        self.push(Event::Setup(packet.encode()));
+    }
+
+    /// Send a packet to an OUT endpoint. Returns Err(-5) (NAK) while the
+    /// last one is unread, Err(-3) if the endpoint is stalled, Err(-1) if
+    /// it is not open or `data` is longer than its packets.
+    pub fn host_out(&mut self, address: u8, data: &[u8]) -> Result<(), i32> {
+        let pipe = self.pipe(address & endpoint::NUMBER_MASK).ok_or(-1)?;
+        let (_, max_packet_size) = pipe.open.ok_or(-1)?;
+        if data.len() > max_packet_size as usize {
+            return Err(-1);
+        }
+        if pipe.stalled {
+            return Err(-3);
+        }
+        if pipe.packet.is_some() {
+            return Err(-5);
+        }
+        let mut packet = Packet {
+            data: [0; MAX_PACKET],
+            len: data.len(),
+        };
+        packet.data[..data.len()].copy_from_slice(data);
+        pipe.packet = Some(packet);
+        self.push(Event::Out(address & endpoint::NUMBER_MASK));
+        Ok(())
+    }
+
+    /// Take the packet on an IN endpoint into `buf` and return its length.
+    /// Returns Err(-5) (NAK) if there is none, Err(-3) if the endpoint is
+    /// stalled, Err(-1) if it is not open.
+    pub fn host_in(&mut self, address: u8, buf: &mut [u8]) -> Result<usize, i32> {
+        let address = address | endpoint::DIRECTION_MASK;
+        let pipe = self.pipe(address).ok_or(-1)?;
+        pipe.open.ok_or(-1)?;
+        if pipe.stalled {
+            return Err(-3);
+        }
+        let packet = pipe.packet.take().ok_or(-5)?;
+        buf[..packet.len].copy_from_slice(&packet.data[..packet.len]);
+        self.push(Event::InComplete(address));
+        Ok(packet.len)
+    }
+}
+
+impl Bus for MockBus {
+    fn set_address(&mut self, address: u8) {
+        self.address = address;
+    }
+
+    fn open(
+        &mut self,
+        address: u8,
+        endpoint_type: EndpointType,
+        max_packet_size: u16,
+    ) -> Result<(), i32> {
+        if max_packet_size as usize > MAX_PACKET {
+            return Err(-1);
+        }
+        let pipe = self.pipe(address).ok_or(-1)?;
+        *pipe = Pipe {
+            open: Some((endpoint_type, max_packet_size)),
+            ..CLOSED
+        };
+        Ok(())
+    }
+
+    fn close(&mut self, address: u8) {
+        if let Some(pipe) = self.pipe(address) {
+            *pipe = CLOSED;
+        }
+    }
+
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), i32> {
+        let pipe = self.pipe(address).ok_or(-1)?;
+        let (_, max_packet_size) = pipe.open.ok_or(-1)?;
+        if data.len() > max_packet_size as usize {
+            return Err(-1);
+        }
+        if pipe.packet.is_some() {
+            return Err(-5);
+        }
+        let mut packet = Packet {
+            data: [0; MAX_PACKET],
+            len: data.len(),
+        };
+        packet.data[..data.len()].copy_from_slice(data);
+        pipe.packet = Some(packet);
+        Ok(())
+    }
+
+    fn read(&mut self, address: u8, buf: &mut [u8]) -> Result<usize, i32> {
+        let pipe = self.pipe(address).ok_or(-1)?;
+        let packet = pipe.packet.take().ok_or(-5)?;
+        if buf.len() < packet.len {
+            return Err(-1);
+        }
+        buf[..packet.len].copy_from_slice(&packet.data[..packet.len]);
+        Ok(packet.len)
+    }
+
+    fn set_stalled(&mut self, address: u8, stalled: bool) {
+        if let Some(pipe) = self.pipe(address) {
+            pipe.stalled = stalled;
+        }
+    }
+
+    fn is_stalled(&self, address: u8) -> bool {
+        let direction = endpoint::is_in(address) as usize;
+        self.pipes[direction]
+            .get(endpoint::number(address) as usize)
+            .is_some_and(|pipe| pipe.stalled)
+    }
+
+    fn poll(&mut self) -> Option<Event> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[self.head].take();
+        self.head = (self.head + 1) % EVENTS;
+        self.len -= 1;
+        event
+    }
+}
+
+/// Let `device` take every event waiting on the bus
+pub fn run(bus: &mut MockBus, device: &mut Device, classes: &mut [&mut dyn Class<MockBus>]) {
+    while device.poll(bus, classes).is_some() {}
+}
+
+/// Run the control transfer of `request` as the host would: the SETUP
+/// packet, `data` to the device or the answer into `response`, then the
+/// status stage. Returns the length of the answer, or Err(-3) if the
+/// device stalled the request.
+pub fn control(
+    bus: &mut MockBus,
+    device: &mut Device,
+    classes: &mut [&mut dyn Class<MockBus>],
+    request: &SetupPacket,
+    data: &[u8],
+    response: &mut [u8],
+) -> Result<usize, i32> {
+    let max_packet_size = device.descriptor().max_packet_size0 as usize;
+    bus.setup(request);
+    run(bus, device, classes);
+    match request.direction() {
+        Direction::In => {
+            let mut len = 0;
+            loop {
+                let mut packet = [0; MAX_PACKET];
+                let count = bus.host_in(0x80, &mut packet)?;
+                response[len..len + count].copy_from_slice(&packet[..count]);
+                len += count;
+                run(bus, device, classes);
+                if count < max_packet_size || len >= request.length as usize {
+                    break;
+                }
+            }
+            bus.host_out(0x00, &[])?;
+            run(bus, device, classes);
+            Ok(len)
+        }
+        Direction::Out => {
+            for chunk in data.chunks(max_packet_size) {
+                bus.host_out(0x00, chunk)?;
+                run(bus, device, classes);
+            }
+            bus.host_in(0x80, &mut [])?;
+            run(bus, device, classes);
+            Ok(0)
+        }
+    }
+}
+
+/// Channels of the `Loopback` controller
+pub const CHANNELS: usize = 8;
+
+/// A host controller for host tests with `device` and `class` plugged into
+/// its port, each transaction run through a `MockBus`
+pub struct Loopback<'d, C> {
+    pub bus: MockBus,
+    pub device: Device<'d>,
+    pub class: C,
+    connected: bool,
+    event: Option<PortEvent>,
+    /// One bit per channel taken
+    channels: u8,
+}
+
+impl<'d, C: Class<MockBus>> Loopback<'d, C> {
+    /// Plug the device in; `poll` reports it connected
+    pub fn new(device: Device<'d>, class: C) -> Self {
+        Self {
+            bus: MockBus::new(),
+            device,
+            class,
+            connected: true,
+            event: Some(PortEvent::Connected),
+            channels: 0,
+        }
+    }
+
+    pub fn unplug(&mut self) {
+        self.connected = false;
+        self.event = Some(PortEvent::Disconnected);
+    }
+
+    /// Channels taken
+    pub fn channels(&self) -> u32 {
+        self.channels.count_ones()
+    }
+
+    /// Let the device take every event waiting on the bus
+    pub fn run(&mut self) {
+        run(&mut self.bus, &mut self.device, &mut [&mut self.class]);
+    }
+
+    /// Whether a transaction on `pipe` reaches the device, which answers at
+    /// its address only
+    fn reaches(&self, pipe: &host::Pipe) -> Result<(), i32> {
+        let taken = self.channels & (1 << pipe.channel) != 0;
+        if !taken {
+            return Err(-1);
+        }
+        if !self.connected || pipe.address != self.bus.address() {
+            return Err(-4);
+        }
+        Ok(())
+    }
+}
+
+/// An endpoint the device does not have answers nothing
+fn no_answer(error: i32) -> i32 {
+    if error == -1 { -4 } else { error }
+}
+
+impl<C: Class<MockBus>> HostBus for Loopback<'_, C> {
+    fn poll(&mut self) -> Option<PortEvent> {
+        self.event.take()
+    }
+
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn reset(&mut self) -> Result<Speed, i32> {
+        if !self.connected {
+            return Err(-2);
+        }
+        self.bus.reset();
+        self.run();
+        Ok(Speed::Full)
+    }
+
+    fn alloc_channel(&mut self) -> Result<u8, i32> {
+        let channel = (0..CHANNELS as u8)
+            .find(|channel| self.channels & (1 << channel) == 0)
+            .ok_or(-5)?;
+        self.channels |= 1 << channel;
+        Ok(channel)
+    }
+
+    fn free_channel(&mut self, channel: u8) {
+        self.channels &= !(1 << channel);
+    }
+
+    fn setup(&mut self, pipe: &host::Pipe, packet: &[u8; SetupPacket::SIZE]) -> Result<(), i32> {
+        self.reaches(pipe)?;
+        self.bus.setup(&SetupPacket::parse(packet)?);
+        self.run();
+        Ok(())
+    }
+
+    fn read(&mut self, pipe: &host::Pipe, buf: &mut [u8]) -> Result<usize, i32> {
+        self.reaches(pipe)?;
+        let mut packet = [0; MAX_PACKET];
+        let address = endpoint::address(pipe.endpoint, true);
+        let len = self.bus.host_in(address, &mut packet).map_err(no_answer)?;
+        self.run();
+        if len > buf.len() {
+            return Err(-4);
+        }
+        buf[..len].copy_from_slice(&packet[..len]);
+        Ok(len)
+    }
+
+    fn write(&mut self, pipe: &host::Pipe, data: &[u8]) -> Result<(), i32> {
+        self.reaches(pipe)?;
+        let address = endpoint::address(pipe.endpoint, false);
+        self.bus.host_out(address, data).map_err(no_answer)?;
+        self.run();
+        Ok(())
+    }
+
+    /// Time passes for the device too
+    fn delay_ms(&mut self, _ms: u32) {
+        self.run();
+    }
+}
@@ -0,0 +1,640 @@
+//! Mass storage, bulk-only transport: the host sends a command block
+//! wrapper (CBW) on the OUT endpoint, data goes one way or the other, and
+//! the device ends with a command status wrapper (CSW) on the IN endpoint.
+//!
+//! The commands are the SCSI block commands a host sends a USB drive, and
+//! they work on a `BlockDevice` of 512-byte blocks with one logical unit.
+//! A host asking for more data than a command has gets a short packet and
+//! the residue in the CSW; a host sending more than a command takes, or
+//! data to a command that takes none, finds the OUT endpoint stalled. An
+//! invalid CBW stalls both endpoints until the host resets the interface.
+
+use crate::bus::Bus;
+use crate::descriptor::ConfigBuilder;
+use crate::device::Class;
+use crate::endpoint::{self, EndpointType};
+use crate::setup::{Kind, Recipient, SetupPacket};
+
+/// Class-specific requests
+pub mod request {
+    pub const GET_MAX_LUN: u8 = 0xFE;
+    pub const BULK_ONLY_RESET: u8 = 0xFF;
+}
+
+/// SCSI operation codes
+pub mod scsi {
+    pub const TEST_UNIT_READY: u8 = 0x00;
+    pub const REQUEST_SENSE: u8 = 0x03;
+    pub const INQUIRY: u8 = 0x12;
+    pub const MODE_SENSE_6: u8 = 0x1A;
+    pub const START_STOP_UNIT: u8 = 0x1B;
+    pub const PREVENT_ALLOW_MEDIUM_REMOVAL: u8 = 0x1E;
+    pub const READ_FORMAT_CAPACITIES: u8 = 0x23;
+    pub const READ_CAPACITY_10: u8 = 0x25;
+    pub const READ_10: u8 = 0x28;
+    pub const WRITE_10: u8 = 0x2A;
+    pub const VERIFY_10: u8 = 0x2F;
+    pub const SYNCHRONIZE_CACHE_10: u8 = 0x35;
+    pub const MODE_SENSE_10: u8 = 0x5A;
+}
+
+/// Sense keys with their additional sense code, as REQUEST SENSE reports
+/// them after a command fails
+pub mod sense {
+    pub const NO_SENSE: (u8, u8) = (0x00, 0x00);
+    pub const READ_ERROR: (u8, u8) = (0x03, 0x11);
+    pub const WRITE_ERROR: (u8, u8) = (0x03, 0x0C);
+    pub const INVALID_COMMAND: (u8, u8) = (0x05, 0x20);
+    pub const LBA_OUT_OF_RANGE: (u8, u8) = (0x05, 0x21);
+    pub const INVALID_FIELD: (u8, u8) = (0x05, 0x24);
+    pub const WRITE_PROTECTED: (u8, u8) = (0x07, 0x27);
+}
+
+pub const CLASS_MASS_STORAGE: u8 = 0x08;
+/// SCSI transparent command set
+pub const SUBCLASS_SCSI: u8 = 0x06;
+pub const PROTOCOL_BULK_ONLY: u8 = 0x50;
+
+/// Packet size of the bulk endpoints
+pub const PACKET_SIZE: usize = 64;
+
+pub const BLOCK_SIZE: usize = 512;
+
+/// Status of a command in its CSW
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed = 0,
+    Failed = 1,
+    /// The host and the device disagree on the data phase
+    PhaseError = 2,
+}
+
+/// Storage the host sees as a drive
+pub trait BlockDevice {
+    /// Number of 512-byte blocks
+    fn block_count(&self) -> u32;
+
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    fn read(&mut self, lba: u32, block: &mut [u8; BLOCK_SIZE]) -> Result<(), i32>;
+
+    fn write(&mut self, lba: u32, block: &[u8; BLOCK_SIZE]) -> Result<(), i32>;
+
+    /// Write out whatever `write` left cached
+    fn flush(&mut self) -> Result<(), i32> {
+        Ok(())
+    }
+}
+
+/// Command block wrapper
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cbw {
+    pub tag: u32,
+    /// Bytes the host means to move in the data phase
+    pub data_length: u32,
+    /// Data goes to the host
+    pub data_in: bool,
+    pub lun: u8,
+    pub command: [u8; 16],
+    pub command_length: u8,
+}
+
+impl Cbw {
+    pub const SIZE: usize = 31;
+    pub const SIGNATURE: u32 = 0x4342_5355;
+
+    /// Returns Err(-1) unless `bytes` is 31 bytes long with the signature
+    /// and a command of 1 to 16 bytes
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        let bytes: &[u8; Self::SIZE] = bytes.try_into().map_err(|_| -1)?;
+        let word = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        let command_length = bytes[14] & 0x1F;
+        if word(0) != Self::SIGNATURE || !(1..=16).contains(&command_length) {
+            return Err(-1);
+        }
+        let mut command = [0; 16];
+        command.copy_from_slice(&bytes[15..31]);
+        Ok(Self {
+            tag: word(4),
+            data_length: word(8),
+            data_in: bytes[12] & 0x80 != 0,
+            lun: bytes[13] & 0x0F,
+            command,
+            command_length,
+        })
+    }
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&Self::SIGNATURE.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.tag.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.data_length.to_le_bytes());
+        bytes[12] = if self.data_in { 0x80 } else { 0x00 };
+        bytes[13] = self.lun;
+        bytes[14] = self.command_length;
+        bytes[15..31].copy_from_slice(&self.command);
+        bytes
+    }
+}
+
+/// Command status wrapper
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Csw {
+    pub tag: u32,
+    /// Bytes of the data phase the device did not move
+    pub residue: u32,
+    pub status: Status,
+}
+
+impl Csw {
+    pub const SIZE: usize = 13;
+    pub const SIGNATURE: u32 = 0x5342_5355;
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&Self::SIGNATURE.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.tag.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.residue.to_le_bytes());
+        bytes[12] = self.status as u8;
+        bytes
+    }
+
+    /// Returns Err(-1) unless `bytes` is 13 bytes long with the signature
+    /// and a known status
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        let bytes: &[u8; Self::SIZE] = bytes.try_into().map_err(|_| -1)?;
+        let word = |at: usize| {
+            u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+        };
+        if word(0) != Self::SIGNATURE {
+            return Err(-1);
+        }
+        let status = match bytes[12] {
+            0 => Status::Passed,
+            1 => Status::Failed,
+            2 => Status::PhaseError,
+            _ => return Err(-1),
+        };
+        Ok(Self {
+            tag: word(4),
+            residue: word(8),
+            status,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Waiting for a CBW
+    Command,
+    DataIn,
+    DataOut,
+    /// The CSW is on the IN endpoint
+    Status,
+    /// An invalid CBW stalled both endpoints until a reset
+    Stalled,
+}
+
+pub struct MassStorage<D: BlockDevice> {
+    disk: D,
+    /// Interface number, assigned by `describe`
+    interface: u8,
+    in_address: u8,
+    out_address: u8,
+    /// INQUIRY data, with the vendor and product
+    inquiry: [u8; 36],
+    configured: bool,
+    stage: Stage,
+    cbw: Cbw,
+    /// Bytes of the data phase still to move
+    residue: u32,
+    status: Status,
+    sense: (u8, u8),
+    /// Data for the host, or a block from it
+    buf: [u8; BLOCK_SIZE],
+    len: usize,
+    pos: usize,
+    /// Next block and blocks left of a READ or WRITE
+    lba: u32,
+    blocks: u32,
+    /// A packet went to the host shorter than a full one, which ends the
+    /// data phase
+    short_sent: bool,
+    /// A packet is on the IN endpoint
+    in_busy: bool,
+    /// A CBW waits on the OUT endpoint for the CSW before it to go out
+    out_pending: bool,
+}
+
+impl<D: BlockDevice> MassStorage<D> {
+    /// A drive of `disk` on the bulk endpoints `number`, IN and OUT, that
+    /// INQUIRY calls `vendor` (8 characters at most) and `product` (16)
+    pub fn new(number: u8, disk: D, vendor: &str, product: &str) -> Self {
+        let mut inquiry = [b' '; 36];
+        // Direct access block device, removable, SPC-2, response format 2,
+        // 31 more bytes
+        inquiry[..8].copy_from_slice(&[0x00, 0x80, 0x04, 0x02, 31, 0, 0, 0]);
+        for (at, text, len) in [(8, vendor, 8), (16, product, 16)] {
+            let bytes = &text.as_bytes()[..text.len().min(len)];
+            inquiry[at..at + bytes.len()].copy_from_slice(bytes);
+        }
+        inquiry[32..].copy_from_slice(b"1.00");
+        Self {
+            disk,
+            interface: 0,
+            in_address: endpoint::DIRECTION_MASK | (number & endpoint::NUMBER_MASK),
+            out_address: number & endpoint::NUMBER_MASK,
+            inquiry,
+            configured: false,
+            stage: Stage::Command,
+            cbw: Cbw {
+                tag: 0,
+                data_length: 0,
+                data_in: false,
+                lun: 0,
+                command: [0; 16],
+                command_length: 0,
+            },
+            residue: 0,
+            status: Status::Passed,
+            sense: sense::NO_SENSE,
+            buf: [0; BLOCK_SIZE],
+            len: 0,
+            pos: 0,
+            lba: 0,
+            blocks: 0,
+            short_sent: false,
+            in_busy: false,
+            out_pending: false,
+        }
+    }
+
+    /// Add the interface and its two endpoints to a configuration
+    pub fn describe(&mut self, config: &mut ConfigBuilder) -> Result<(), i32> {
+        self.interface = config.interface(CLASS_MASS_STORAGE, SUBCLASS_SCSI, PROTOCOL_BULK_ONLY)?;
+        config.endpoint(self.out_address, EndpointType::Bulk, PACKET_SIZE as u16, 0)?;
+        config.endpoint(self.in_address, EndpointType::Bulk, PACKET_SIZE as u16, 0)?;
+        Ok(())
+    }
+
+    pub fn disk(&self) -> &D {
+        &self.disk
+    }
+
+    pub fn disk_mut(&mut self) -> &mut D {
+        &mut self.disk
+    }
+
+    /// Sense key and additional sense code of the last failed command
+    pub fn sense(&self) -> (u8, u8) {
+        self.sense
+    }
+
+    /// Whether a command is under way
+    pub fn is_busy(&self) -> bool {
+        self.stage != Stage::Command
+    }
+
+    fn command(&mut self, bus: &mut impl Bus, bytes: &[u8]) {
+        let cbw = match Cbw::parse(bytes) {
+            Ok(cbw) => cbw,
+            Err(_) => {
+                bus.set_stalled(self.in_address, true);
+                bus.set_stalled(self.out_address, true);
+                self.stage = Stage::Stalled;
+                return;
+            }
+        };
+        self.cbw = cbw;
+        self.residue = cbw.data_length;
+        self.status = Status::Passed;
+        self.len = 0;
+        self.pos = 0;
+        self.blocks = 0;
+        self.short_sent = false;
+        if cbw.lun != 0 {
+            self.fail(bus, sense::INVALID_FIELD);
+            return;
+        }
+        let command = cbw.command;
+        match command[0] {
+            scsi::TEST_UNIT_READY
+            | scsi::START_STOP_UNIT
+            | scsi::PREVENT_ALLOW_MEDIUM_REMOVAL
+            | scsi::VERIFY_10 => self.end_data(bus),
+            scsi::SYNCHRONIZE_CACHE_10 => match self.disk.flush() {
+                Ok(()) => self.end_data(bus),
+                Err(_) => self.fail(bus, sense::WRITE_ERROR),
+            },
+            scsi::REQUEST_SENSE => {
+                let (key, code) = self.sense;
+                self.sense = sense::NO_SENSE;
+                let mut data = [0; 18];
+                // Current error, fixed format, 10 more bytes
+                data[0] = 0x70;
+                data[2] = key;
+                data[7] = 10;
+                data[12] = code;
+                self.data_in(bus, &data, command[4] as usize);
+            }
+            scsi::INQUIRY => {
+                let inquiry = self.inquiry;
+                let allocation = u16::from_be_bytes([command[3], command[4]]) as usize;
+                self.data_in(bus, &inquiry, allocation);
+            }
+            scsi::MODE_SENSE_6 => {
+                // Mode parameter header only: no block descriptors, no pages
+                let data = [3, 0, self.write_protect(), 0];
+                self.data_in(bus, &data, command[4] as usize);
+            }
+            scsi::MODE_SENSE_10 => {
+                let data = [0, 6, 0, self.write_protect(), 0, 0, 0, 0];
+                let allocation = u16::from_be_bytes([command[7], command[8]]) as usize;
+                self.data_in(bus, &data, allocation);
+            }
+            scsi::READ_CAPACITY_10 => {
+                let mut data = [0; 8];
+                let last = self.disk.block_count().saturating_sub(1);
+                data[..4].copy_from_slice(&last.to_be_bytes());
+                data[4..].copy_from_slice(&(BLOCK_SIZE as u32).to_be_bytes());
+                self.data_in(bus, &data, data.len());
+            }
+            scsi::READ_FORMAT_CAPACITIES => {
+                let mut data = [0; 12];
+                // One capacity descriptor: formatted media
+                data[3] = 8;
+                data[4..8].copy_from_slice(&self.disk.block_count().to_be_bytes());
+                data[8] = 0x02;
+                data[9..].copy_from_slice(&(BLOCK_SIZE as u32).to_be_bytes()[1..]);
+                let allocation = u16::from_be_bytes([command[7], command[8]]) as usize;
+                self.data_in(bus, &data, allocation);
+            }
+            scsi::READ_10 | scsi::WRITE_10 => self.transfer(bus),
+            _ => self.fail(bus, sense::INVALID_COMMAND),
+        }
+    }
+
+    fn write_protect(&self) -> u8 {
+        if self.disk.is_read_only() { 0x80 } else { 0x00 }
+    }
+
+    /// Start a READ(10) or WRITE(10)
+    fn transfer(&mut self, bus: &mut impl Bus) {
+        let command = self.cbw.command;
+        let write = command[0] == scsi::WRITE_10;
+        let lba = u32::from_be_bytes([command[2], command[3], command[4], command[5]]);
+        let blocks = u16::from_be_bytes([command[7], command[8]]) as u32;
+        let bytes = blocks as u64 * BLOCK_SIZE as u64;
+        if blocks > 0 && (self.cbw.data_in == write || (self.cbw.data_length as u64) < bytes) {
+            self.phase_error(bus);
+            return;
+        }
+        if lba as u64 + blocks as u64 > self.disk.block_count() as u64 {
+            self.fail(bus, sense::LBA_OUT_OF_RANGE);
+            return;
+        }
+        if write && self.disk.is_read_only() {
+            self.fail(bus, sense::WRITE_PROTECTED);
+            return;
+        }
+        self.lba = lba;
+        self.blocks = blocks;
+        if blocks == 0 {
+            self.end_data(bus);
+        } else if write {
+            self.stage = Stage::DataOut;
+        } else {
+            self.stage = Stage::DataIn;
+            self.send(bus);
+        }
+    }
+
+    /// Answer a command with `data`, cut to what the host allows and asks
+    /// for
+    fn data_in(&mut self, bus: &mut impl Bus, data: &[u8], allocation: usize) {
+        let len = data.len().min(allocation);
+        if len > 0 && (!self.cbw.data_in || (self.cbw.data_length as usize) < len) {
+            self.phase_error(bus);
+            return;
+        }
+        self.buf[..len].copy_from_slice(&data[..len]);
+        self.len = len;
+        self.pos = 0;
+        self.stage = Stage::DataIn;
+        self.send(bus);
+    }
+
+    /// End a command with CHECK CONDITION, the reason kept for REQUEST SENSE
+    fn fail(&mut self, bus: &mut impl Bus, reason: (u8, u8)) {
+        self.sense = reason;
+        self.status = Status::Failed;
+        self.end_data(bus);
+    }
+
+    fn phase_error(&mut self, bus: &mut impl Bus) {
+        self.status = Status::PhaseError;
+        self.end_data(bus);
+    }
+
+    /// The device is done with the data phase: close what the host still
+    /// expects of it, then send the CSW
+    fn end_data(&mut self, bus: &mut impl Bus) {
+        self.blocks = 0;
+        self.len = 0;
+        self.pos = 0;
+        if self.residue > 0 {
+            if self.cbw.data_in {
+                if !self.short_sent {
+                    // A short packet, possibly empty, ends the transfer
+                    self.stage = Stage::DataIn;
+                    self.short_sent = true;
+                    if bus.write(self.in_address, &[]).is_ok() {
+                        self.in_busy = true;
+                    }
+                    return;
+                }
+            } else {
+                bus.set_stalled(self.out_address, true);
+            }
+        }
+        self.send_status(bus);
+    }
+
+    fn send_status(&mut self, bus: &mut impl Bus) {
+        let csw = Csw {
+            tag: self.cbw.tag,
+            residue: self.residue,
+            status: self.status,
+        };
+        self.stage = Stage::Status;
+        if bus.write(self.in_address, &csw.encode()).is_ok() {
+            self.in_busy = true;
+        }
+    }
+
+    /// Send the next packet of the data phase, reading the next block when
+    /// the last one has gone out
+    fn send(&mut self, bus: &mut impl Bus) {
+        if self.in_busy {
+            return;
+        }
+        if self.pos == self.len && self.blocks > 0 {
+            if self.disk.read(self.lba, &mut self.buf).is_err() {
+                self.fail(bus, sense::READ_ERROR);
+                return;
+            }
+            self.lba += 1;
+            self.blocks -= 1;
+            self.len = BLOCK_SIZE;
+            self.pos = 0;
+        }
+        if self.pos == self.len {
+            self.end_data(bus);
+            return;
+        }
+        let count = (self.len - self.pos).min(PACKET_SIZE);
+        if bus
+            .write(self.in_address, &self.buf[self.pos..self.pos + count])
+            .is_ok()
+        {
+            self.in_busy = true;
+            self.pos += count;
+            self.residue -= count as u32;
+            self.short_sent = count < PACKET_SIZE;
+        }
+    }
+
+    /// Take a packet of the data phase of a WRITE, writing each block as
+    /// it fills
+    fn receive(&mut self, bus: &mut impl Bus, data: &[u8]) {
+        let count = data.len().min(self.residue as usize);
+        self.residue -= count as u32;
+        let room = (BLOCK_SIZE - self.len).min(count);
+        self.buf[self.len..self.len + room].copy_from_slice(&data[..room]);
+        self.len += room;
+        if self.len == BLOCK_SIZE {
+            self.len = 0;
+            if self.disk.write(self.lba, &self.buf).is_err() {
+                self.sense = sense::WRITE_ERROR;
+                self.status = Status::Failed;
+                self.blocks = 0;
+            } else {
+                self.lba += 1;
+                self.blocks -= 1;
+                // Blocks are written through before the command passes
+                if self.blocks == 0 && self.disk.flush().is_err() {
+                    self.sense = sense::WRITE_ERROR;
+                    self.status = Status::Failed;
+                }
+            }
+        }
+        if self.blocks == 0 || self.residue == 0 {
+            self.end_data(bus);
+        } else if data.len() < PACKET_SIZE {
+            // The host ended the transfer short of what it announced
+            self.status = Status::PhaseError;
+            self.send_status(bus);
+        }
+    }
+
+    fn take_out(&mut self, bus: &mut impl Bus) {
+        let mut packet = [0; PACKET_SIZE];
+        let Ok(len) = bus.read(self.out_address, &mut packet) else {
+            return;
+        };
+        match self.stage {
+            Stage::Command => self.command(bus, &packet[..len]),
+            Stage::DataOut => self.receive(bus, &packet[..len]),
+            // The host does not send during these; a packet stalls it
+            _ => bus.set_stalled(self.out_address, true),
+        }
+    }
+
+    fn is_mine(&self, request: &SetupPacket) -> bool {
+        request.kind() == Kind::Class
+            && request.recipient() == Recipient::Interface
+            && request.index == self.interface as u16
+    }
+
+    fn restart(&mut self) {
+        self.stage = Stage::Command;
+        self.len = 0;
+        self.pos = 0;
+        self.blocks = 0;
+        self.in_busy = false;
+        self.out_pending = false;
+    }
+}
+
+impl<B: Bus, D: BlockDevice> Class<B> for MassStorage<D> {
+    fn reset(&mut self) {
+        self.configured = false;
+        self.sense = sense::NO_SENSE;
+        self.restart();
+    }
+
+    fn configured(&mut self, _bus: &mut B) {
+        self.configured = true;
+    }
+
+    fn control_in(&mut self, request: &SetupPacket, buf: &mut [u8]) -> Result<usize, i32> {
+        if !self.is_mine(request) {
+            return Err(-2);
+        }
+        match request.request {
+            // One logical unit, number 0
+            request::GET_MAX_LUN => {
+                buf[0] = 0;
+                Ok(1)
+            }
+            _ => Err(-1),
+        }
+    }
+
+    fn control_out(&mut self, request: &SetupPacket, _data: &[u8]) -> Result<(), i32> {
+        if !self.is_mine(request) {
+            return Err(-2);
+        }
+        match request.request {
+            // The host clears the stalls itself after the reset
+            request::BULK_ONLY_RESET => {
+                self.restart();
+                Ok(())
+            }
+            _ => Err(-1),
+        }
+    }
+
+    fn endpoint_out(&mut self, bus: &mut B, address: u8) {
+        if address != self.out_address || !self.configured {
+            return;
+        }
+        if self.stage == Stage::Status {
+            self.out_pending = true;
+            return;
+        }
+        self.take_out(bus);
+    }
+
+    fn endpoint_in_complete(&mut self, bus: &mut B, address: u8) {
+        if address != self.in_address {
+            return;
+        }
+        self.in_busy = false;
+        match self.stage {
+            Stage::DataIn => self.send(bus),
+            Stage::Status => {
+                self.stage = Stage::Command;
+                if self.out_pending {
+                    self.out_pending = false;
+                    self.take_out(bus);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! The eight-byte SETUP packet that starts every control transfer, and the
+//! standard request codes of chapter 9 of the USB 2.0 specification.
+
+/// Standard request codes (bRequest)
+pub mod request {
+    pub const GET_STATUS: u8 = 0x00;
+    pub const CLEAR_FEATURE: u8 = 0x01;
+    pub const SET_FEATURE: u8 = 0x03;
+    pub const SET_ADDRESS: u8 = 0x05;
+    pub const GET_DESCRIPTOR: u8 = 0x06;
+    pub const SET_DESCRIPTOR: u8 = 0x07;
+    pub const GET_CONFIGURATION: u8 = 0x08;
+    pub const SET_CONFIGURATION: u8 = 0x09;
+    pub const GET_INTERFACE: u8 = 0x0A;
+    pub const SET_INTERFACE: u8 = 0x0B;
+    pub const SYNCH_FRAME: u8 = 0x0C;
+}
+
+/// Feature selectors of SET_FEATURE and CLEAR_FEATURE (wValue)
+pub mod feature {
+    pub const ENDPOINT_HALT: u16 = 0;
+    pub const DEVICE_REMOTE_WAKEUP: u16 = 1;
+}
+
+/// Direction of the data stage, bit 7 of bmRequestType
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Host to device
+    Out,
+    /// Device to host
+    In,
+}
+
+/// Bits 6..5 of bmRequestType
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Standard,
+    Class,
+    Vendor,
+    Reserved,
+}
+
+/// Bits 4..0 of bmRequestType
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recipient {
+    Device,
+    Interface,
+    Endpoint,
+    Other,
+    Reserved,
+}
+
+/// A SETUP packet, fields in host order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetupPacket {
+    pub request_type: u8,
+    pub request: u8,
+    pub value: u16,
+    pub index: u16,
+    pub length: u16,
+}
+
+impl SetupPacket {
+    pub const SIZE: usize = 8;
+
+    /// Build the packet of a request
+    pub const fn new(
+        direction: Direction,
+        kind: Kind,
+        recipient: Recipient,
+        request: u8,
+        value: u16,
+        index: u16,
+        length: u16,
+    ) -> Self {
+        let direction = match direction {
+            Direction::Out => 0x00,
+            Direction::In => 0x80,
+        };
+        let kind = match kind {
+            Kind::Standard => 0,
+            Kind::Class => 1,
+            Kind::Vendor => 2,
+            Kind::Reserved => 3,
+        };
+        let recipient = match recipient {
+            Recipient::Device => 0,
+            Recipient::Interface => 1,
+            Recipient::Endpoint => 2,
+            Recipient::Other => 3,
+            Recipient::Reserved => 31,
+        };
+        Self {
+            request_type: direction | (kind << 5) | recipient,
+            request,
+            value,
+            index,
+            length,
+        }
+    }
+
+    /// Returns Err(-1) unless `bytes` is eight bytes long
+    pub fn parse(bytes: &[u8]) -> Result<Self, i32> {
+        let bytes: &[u8; Self::SIZE] = bytes.try_into().map_err(|_| -1)?;
+        Ok(Self {
+            request_type: bytes[0],
+            request: bytes[1],
+            value: u16::from_le_bytes([bytes[2], bytes[3]]),
+            index: u16::from_le_bytes([bytes[4], bytes[5]]),
+            length: u16::from_le_bytes([bytes[6], bytes[7]]),
+        })
+    }
+
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let [value_lo, value_hi] = self.value.to_le_bytes();
+        let [index_lo, index_hi] = self.index.to_le_bytes();
+        let [length_lo, length_hi] = self.length.to_le_bytes();
+        [
+            self.request_type,
+            self.request,
+            value_lo,
+            value_hi,
+            index_lo,
+            index_hi,
+            length_lo,
+            length_hi,
+        ]
+    }
+
+    pub fn direction(&self) -> Direction {
+        if self.request_type & 0x80 != 0 {
+            Direction::In
+        } else {
+            Direction::Out
+        }
+    }
+
+    pub fn kind(&self) -> Kind {
+        match (self.request_type >> 5) & 0x03 {
+            0 => Kind::Standard,
+            1 => Kind::Class,
+            2 => Kind::Vendor,
+            _ => Kind::Reserved,
+        }
+    }
+
+    pub fn recipient(&self) -> Recipient {
+        match self.request_type & 0x1F {
+            0 => Recipient::Device,
+            1 => Recipient::Interface,
+            2 => Recipient::Endpoint,
+            3 => Recipient::Other,
+            _ => Recipient::Reserved,
+        }
+    }
+
+    /// Descriptor type of GET_DESCRIPTOR, the high byte of wValue
+    pub fn descriptor_type(&self) -> u8 {
+        (self.value >> 8) as u8
+    }
+
+    /// Descriptor index of GET_DESCRIPTOR, the low byte of wValue
+    pub fn descriptor_index(&self) -> u8 {
+        self.value as u8
+    }
+}
@@ -0,0 +1,248 @@
+use usb::cdc::*;
+use usb::descriptor::{self, DeviceDescriptor, kind};
+use usb::device::*;
+use usb::mock::{self, MockBus};
+use usb::setup::{self, Direction, Kind, Recipient, SetupPacket};
+
+struct Host {
+    bus: MockBus,
+    device: Device<'static>,
+    port: CdcAcm,
+}
+
+impl Host {
+    fn new() -> Self {
+        let mut device = Device::new(DeviceDescriptor::composite(0x1209, 0x0001), &[]);
+        let mut port = CdcAcm::new(1, 2);
+        device
+            .configure(0, 100, |config| port.describe(config))
+            .unwrap();
+        let mut host = Self {
+            bus: MockBus::new(),
+            device,
+            port,
+        };
+        host.bus.reset();
+        host.run();
+        for (request, value) in [
+            (setup::request::SET_ADDRESS, 3),
+            (setup::request::SET_CONFIGURATION, 1),
+        ] {
+            let setup = SetupPacket::new(
+                Direction::Out,
+                Kind::Standard,
+                Recipient::Device,
+                request,
+                value,
+                0,
+                0,
+            );
+            host.control(&setup, &[]).unwrap();
+        }
+        host
+    }
+
+    fn run(&mut self) {
+        mock::run(&mut self.bus, &mut self.device, &mut [&mut self.port]);
+    }
+
+    fn control(&mut self, request: &SetupPacket, data: &[u8]) -> Result<Vec<u8>, i32> {
+        let mut response = [0; 64];
+        let len = mock::control(
+            &mut self.bus,
+            &mut self.device,
+            &mut [&mut self.port],
+            request,
+            data,
+            &mut response,
+        )?;
+        Ok(response[..len].to_vec())
+    }
+
+    fn class(
+        &mut self,
+        direction: Direction,
+        request: u8,
+        value: u16,
+        data: &[u8],
+        length: u16,
+    ) -> Result<Vec<u8>, i32> {
+        let setup = SetupPacket::new(
+            direction,
+            Kind::Class,
+            Recipient::Interface,
+            request,
+            value,
+            0,
+            length,
+        );
+        self.control(&setup, data)
+    }
+
+    /// Read what the port sent, packet by packet
+    fn receive(&mut self) -> Vec<Vec<u8>> {
+        let mut packets = Vec::new();
+        loop {
+            self.port.poll(&mut self.bus);
+            let mut packet = [0; 64];
+            match self.bus.host_in(0x82, &mut packet) {
+                Ok(len) => packets.push(packet[..len].to_vec()),
+                Err(-5) => return packets,
+                Err(error) => panic!("IN failed: {error}"),
+            }
+            self.run();
+        }
+    }
+}
+
+#[test]
+fn test_descriptors() {
+    let host = Host::new();
+    let config = host.device.config();
+    let kinds: Vec<(u8, usize)> = descriptor::iter(config).map(|d| (d[1], d.len())).collect();
+    assert_eq!(
+        kinds,
+        [
+            (kind::CONFIGURATION, 9),
+            (kind::INTERFACE_ASSOCIATION, 8),
+            (kind::INTERFACE, 9),
+            (kind::CS_INTERFACE, 5),
+            (kind::CS_INTERFACE, 5),
+            (kind::CS_INTERFACE, 4),
+            (kind::CS_INTERFACE, 5),
+            (kind::ENDPOINT, 7),
+            (kind::INTERFACE, 9),
+            (kind::ENDPOINT, 7),
+            (kind::ENDPOINT, 7),
+        ]
+    );
+    assert_eq!(config[4], 2);
+    // Union of interfaces 0 and 1
+    let union = descriptor::iter(config).nth(6).unwrap();
+    assert_eq!(union, [5, 0x24, 0x06, 0, 1]);
+    let data = descriptor::iter(config).nth(8).unwrap();
+    assert_eq!((data[2], data[4], data[5]), (1, 2, CLASS_DATA));
+    assert!(host.bus.is_open(0x81) && host.bus.is_open(0x82) && host.bus.is_open(0x02));
+}
+
+#[test]
+fn test_line_coding_and_state() {
+    let mut host = Host::new();
+    let coding = host
+        .class(Direction::In, request::GET_LINE_CODING, 0, &[], 7)
+        .unwrap();
+    assert_eq!(coding, LineCoding::default().encode());
+
+    let wanted = LineCoding {
+        baudrate: 9600,
+        stop_bits: 2,
+        parity: 2,
+        data_bits: 7,
+    };
+    host.class(
+        Direction::Out,
+        request::SET_LINE_CODING,
+        0,
+        &wanted.encode(),
+        7,
+    )
+    .unwrap();
+    assert_eq!(host.port.line_coding(), wanted);
+    assert_eq!(
+        host.class(Direction::In, request::GET_LINE_CODING, 0, &[], 7)
+            .unwrap(),
+        [0x80, 0x25, 0, 0, 2, 2, 7]
+    );
+    // Short line coding
+    assert_eq!(
+        host.class(Direction::Out, request::SET_LINE_CODING, 0, &[0; 3], 3),
+        Err(-3)
+    );
+
+    assert!(!host.port.is_open());
+    host.class(
+        Direction::Out,
+        request::SET_CONTROL_LINE_STATE,
+        0x03,
+        &[],
+        0,
+    )
+    .unwrap();
+    assert!(host.port.is_open() && host.port.dtr() && host.port.rts());
+    host.class(Direction::Out, request::SEND_BREAK, 100, &[], 0)
+        .unwrap();
+    host.class(
+        Direction::Out,
+        request::SET_CONTROL_LINE_STATE,
+        0x00,
+        &[],
+        0,
+    )
+    .unwrap();
+    assert!(!host.port.is_open());
+}
+
+#[test]
+fn test_send() {
+    let mut host = Host::new();
+    assert_eq!(host.port.write(b"hi"), 2);
+    assert_eq!(host.receive(), [b"hi".to_vec()]);
+
+    // A full last packet is followed by an empty one
+    let data: Vec<u8> = (0..128).map(|i| i as u8).collect();
+    assert_eq!(host.port.write(&data), 128);
+    let packets = host.receive();
+    assert_eq!(packets.len(), 3);
+    assert_eq!(packets[0], &data[..64]);
+    assert_eq!(packets[1], &data[64..]);
+    assert!(packets[2].is_empty());
+
+    // The FIFO takes what fits
+    assert_eq!(host.port.write(&[0; 300]), FIFO_SIZE);
+    assert_eq!(host.port.pending(), FIFO_SIZE);
+}
+
+#[test]
+fn test_receive_holds_host_when_full() {
+    let mut host = Host::new();
+    host.bus.host_out(0x02, b"abc").unwrap();
+    host.run();
+    let mut buf = [0; 8];
+    assert_eq!(host.port.read(&mut buf), 3);
+    assert_eq!(&buf[..3], b"abc");
+
+    // Four packets fill the FIFO; the fifth waits on the endpoint
+    for _ in 0..4 {
+        host.bus.host_out(0x02, &[1; 64]).unwrap();
+        host.run();
+    }
+    host.bus.host_out(0x02, &[2; 64]).unwrap();
+    host.run();
+    assert_eq!(host.bus.host_out(0x02, &[3; 64]), Err(-5));
+    let mut buf = [0; 64];
+    assert_eq!(host.port.read(&mut buf), 64);
+    host.port.poll(&mut host.bus);
+    host.bus.host_out(0x02, &[3; 64]).unwrap();
+    let mut all = [0; 256];
+    assert_eq!(host.port.read(&mut all), 256);
+    assert_eq!(&all[192..], [2; 64]);
+}
+
+#[test]
+fn test_reset_closes_port() {
+    let mut host = Host::new();
+    host.class(
+        Direction::Out,
+        request::SET_CONTROL_LINE_STATE,
+        0x01,
+        &[],
+        0,
+    )
+    .unwrap();
+    host.port.write(b"queued");
+    host.bus.reset();
+    host.run();
+    assert!(!host.port.is_open());
+    assert_eq!(host.port.pending(), 0);
+    assert_eq!(host.port.write(b"x"), 0);
+}
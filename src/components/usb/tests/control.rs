@@ -0,0 +1,171 @@
+use usb::bus::{Bus, Event};
+use usb::control::*;
+use usb::endpoint::EndpointType;
+use usb::mock::MockBus;
+use usb::setup::*;
+
+fn bus() -> MockBus {
+    let mut bus = MockBus::new();
+    bus.open(0x00, EndpointType::Control, 8).unwrap();
+    bus.open(0x80, EndpointType::Control, 8).unwrap();
+    bus
+}
+
+fn get(length: u16) -> SetupPacket {
+    SetupPacket::new(
+        Direction::In,
+        Kind::Vendor,
+        Recipient::Device,
+        0x01,
+        0,
+        0,
+        length,
+    )
+}
+
+fn set(length: u16) -> SetupPacket {
+    SetupPacket::new(
+        Direction::Out,
+        Kind::Vendor,
+        Recipient::Device,
+        0x02,
+        0,
+        0,
+        length,
+    )
+}
+
+/// Take the IN packet of endpoint 0 and tell the pipe it went out
+fn take_in(bus: &mut MockBus, pipe: &mut ControlPipe) -> Result<Vec<u8>, i32> {
+    let mut packet = [0; 64];
+    let len = bus.host_in(0x80, &mut packet)?;
+    assert_eq!(bus.poll(), Some(Event::InComplete(0x80)));
+    pipe.in_complete(bus);
+    Ok(packet[..len].to_vec())
+}
+
+fn send_out(bus: &mut MockBus, pipe: &mut ControlPipe, data: &[u8]) -> Option<SetupPacket> {
+    bus.host_out(0x00, data).unwrap();
+    assert_eq!(bus.poll(), Some(Event::Out(0)));
+    pipe.out(bus)
+}
+
+#[test]
+fn test_in_split_into_packets() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    let request = get(64);
+    assert_eq!(pipe.setup(&mut bus, &request.encode()), Some(request));
+    let data: Vec<u8> = (0..20).collect();
+    pipe.respond(&mut bus, &data);
+    assert_eq!(pipe.stage(), Stage::DataIn);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap(), &data[..8]);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap(), &data[8..16]);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap(), &data[16..]);
+    assert_eq!(pipe.stage(), Stage::StatusOut);
+    assert_eq!(send_out(&mut bus, &mut pipe, &[]), None);
+    assert_eq!(pipe.stage(), Stage::Idle);
+}
+
+#[test]
+fn test_in_cut_to_length() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    pipe.setup(&mut bus, &get(4).encode()).unwrap();
+    pipe.respond(&mut bus, &[1, 2, 3, 4, 5, 6]);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(pipe.stage(), Stage::StatusOut);
+}
+
+#[test]
+fn test_short_answer_of_whole_packets_ends_with_empty_packet() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    pipe.setup(&mut bus, &get(255).encode()).unwrap();
+    pipe.respond(&mut bus, &[7; 16]);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap().len(), 8);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap().len(), 8);
+    assert_eq!(pipe.stage(), Stage::DataIn);
+    assert_eq!(take_in(&mut bus, &mut pipe).unwrap().len(), 0);
+    assert_eq!(pipe.stage(), Stage::StatusOut);
+
+    // Exactly the length asked for: no empty packet
+    pipe.setup(&mut bus, &get(16).encode()).unwrap();
+    pipe.respond(&mut bus, &[7; 16]);
+    take_in(&mut bus, &mut pipe).unwrap();
+    take_in(&mut bus, &mut pipe).unwrap();
+    assert_eq!(pipe.stage(), Stage::StatusOut);
+}
+
+#[test]
+fn test_out_with_data() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    let request = set(10);
+    assert_eq!(pipe.setup(&mut bus, &request.encode()), None);
+    assert_eq!(pipe.stage(), Stage::DataOut);
+    assert_eq!(send_out(&mut bus, &mut pipe, &[1; 8]), None);
+    assert_eq!(send_out(&mut bus, &mut pipe, &[2; 2]), Some(request));
+    assert_eq!(pipe.data(), [1, 1, 1, 1, 1, 1, 1, 1, 2, 2]);
+    pipe.accept(&mut bus);
+    assert_eq!(pipe.stage(), Stage::StatusIn);
+    // The status stage hands the request back
+    let mut packet = [0; 8];
+    assert_eq!(bus.host_in(0x80, &mut packet), Ok(0));
+    assert_eq!(bus.poll(), Some(Event::InComplete(0x80)));
+    assert_eq!(pipe.in_complete(&mut bus), Some(request));
+    assert_eq!(pipe.stage(), Stage::Idle);
+}
+
+#[test]
+fn test_out_without_data() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    let request = set(0);
+    assert_eq!(pipe.setup(&mut bus, &request.encode()), Some(request));
+    assert!(pipe.data().is_empty());
+    pipe.accept(&mut bus);
+    assert_eq!(bus.host_in(0x80, &mut []), Ok(0));
+}
+
+#[test]
+fn test_reject_stalls_until_setup() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    pipe.setup(&mut bus, &get(8).encode()).unwrap();
+    pipe.reject(&mut bus);
+    assert_eq!(pipe.stage(), Stage::Stalled);
+    assert_eq!(bus.host_in(0x80, &mut [0; 8]), Err(-3));
+    assert!(bus.is_stalled(0x00));
+    bus.setup(&get(8));
+    assert!(!bus.is_stalled(0x80));
+    let Some(Event::Setup(bytes)) = bus.poll() else {
+        panic!("no SETUP");
+    };
+    assert!(pipe.setup(&mut bus, &bytes).is_some());
+}
+
+#[test]
+fn test_too_much_data_stalls() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    assert_eq!(
+        pipe.setup(&mut bus, &set(BUFFER_SIZE as u16 + 1).encode()),
+        None
+    );
+    assert_eq!(pipe.stage(), Stage::Stalled);
+    assert_eq!(bus.host_out(0x00, &[0; 8]), Err(-3));
+    assert_eq!(pipe.setup(&mut bus, &[0; 7]), None);
+    assert_eq!(pipe.stage(), Stage::Stalled);
+}
+
+#[test]
+fn test_answer_must_fit_direction() {
+    let mut bus = bus();
+    let mut pipe = ControlPipe::new(8);
+    pipe.setup(&mut bus, &set(0).encode()).unwrap();
+    assert_eq!(pipe.respond_with(&mut bus, |_| Ok(0)), Err(-1));
+    pipe.setup(&mut bus, &get(8).encode()).unwrap();
+    assert_eq!(pipe.respond_with(&mut bus, |_| Err(-2)), Err(-2));
+    assert_eq!(pipe.stage(), Stage::Idle);
+}
@@ -0,0 +1,98 @@
+use usb::descriptor::*;
+use usb::endpoint::EndpointType;
+
+#[test]
+fn test_device_descriptor() {
+    let mut descriptor = DeviceDescriptor::new(0x0483, 0x5740);
+    descriptor.manufacturer = 1;
+    descriptor.product = 2;
+    let bytes = descriptor.encode();
+    assert_eq!(
+        bytes,
+        [
+            18, 0x01, 0x00, 0x02, 0, 0, 0, 64, 0x83, 0x04, 0x40, 0x57, 0x00, 0x01, 1, 2, 0, 1
+        ]
+    );
+    assert_eq!(DeviceDescriptor::parse(&bytes), Ok(descriptor));
+    assert_eq!(DeviceDescriptor::parse(&bytes[..17]), Err(-1));
+    let composite = DeviceDescriptor::composite(0x0483, 0x5740);
+    assert_eq!(
+        (composite.class, composite.subclass, composite.protocol),
+        (0xEF, 0x02, 0x01)
+    );
+}
+
+#[test]
+fn test_config_builder() {
+    let mut buf = [0; 64];
+    let mut config = ConfigBuilder::new(&mut buf, 1, attributes::SELF_POWERED, 100).unwrap();
+    assert_eq!(config.interface(0x03, 0, 0), Ok(0));
+    config
+        .descriptor(0x21, &[0x11, 0x01, 0, 1, 0x22, 0x20, 0x00])
+        .unwrap();
+    config
+        .endpoint(0x81, EndpointType::Interrupt, 8, 10)
+        .unwrap();
+    assert_eq!(config.next_interface(), 1);
+    assert_eq!(config.interface(0xFF, 0, 0), Ok(1));
+    let len = config.finish();
+    assert_eq!(len, 9 + 9 + 9 + 7 + 9);
+    // wTotalLength, bNumInterfaces, attributes, 50 units of 2 mA
+    assert_eq!(&buf[..9], [9, 0x02, len as u8, 0, 2, 1, 0, 0xC0, 50]);
+
+    let descriptors: Vec<&[u8]> = iter(&buf[..len]).collect();
+    let kinds: Vec<u8> = descriptors.iter().map(|d| d[1]).collect();
+    assert_eq!(
+        kinds,
+        [
+            kind::CONFIGURATION,
+            kind::INTERFACE,
+            0x21,
+            kind::ENDPOINT,
+            kind::INTERFACE
+        ]
+    );
+    // The endpoint is counted in its interface
+    assert_eq!(descriptors[1][4], 1);
+    assert_eq!(descriptors[4][4], 0);
+    assert_eq!(
+        EndpointDescriptor::parse(descriptors[3]),
+        Ok(EndpointDescriptor {
+            address: 0x81,
+            endpoint_type: EndpointType::Interrupt,
+            max_packet_size: 8,
+            interval: 10,
+        })
+    );
+}
+
+#[test]
+fn test_config_builder_errors() {
+    let mut short = [0; 8];
+    assert!(ConfigBuilder::new(&mut short, 1, 0, 100).is_err());
+    let mut buf = [0; 20];
+    let mut config = ConfigBuilder::new(&mut buf, 1, 0, 100).unwrap();
+    // No interface to add it to
+    assert_eq!(config.endpoint(0x81, EndpointType::Bulk, 64, 0), Err(-1));
+    config.interface(0x0A, 0, 0).unwrap();
+    assert_eq!(config.endpoint(0x81, EndpointType::Bulk, 64, 0), Err(-5));
+    assert_eq!(config.descriptor(0x24, &[0; 4]), Err(-5));
+    assert_eq!(config.finish(), 18);
+}
+
+#[test]
+fn test_strings() {
+    let mut buf = [0; 16];
+    assert_eq!(string("Hé", &mut buf), Ok(6));
+    assert_eq!(&buf[..6], [6, 0x03, b'H', 0, 0xE9, 0]);
+    assert_eq!(string("much too long", &mut buf), Err(-5));
+    assert_eq!(languages(&[LANGUAGE_EN_US], &mut buf), Ok(4));
+    assert_eq!(&buf[..4], [4, 0x03, 0x09, 0x04]);
+}
+
+#[test]
+fn test_iter_stops_on_malformed() {
+    let bytes = [3, 0x24, 0, 9, 0x04, 0];
+    assert_eq!(iter(&bytes).count(), 1);
+    assert_eq!(iter(&[0, 0x04]).count(), 0);
+}
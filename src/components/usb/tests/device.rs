@@ -0,0 +1,433 @@
+use usb::bus::Bus;
+use usb::descriptor::{DeviceDescriptor, attributes, kind};
+use usb::device::*;
+use usb::endpoint::EndpointType;
+use usb::mock::{self, MockBus};
+use usb::setup::*;
+
+const STRINGS: &[&str] = &["Acme", "Widget"];
+
+/// A vendor class with one interface and a bulk endpoint each way
+#[derive(Default)]
+struct Vendor {
+    configured: bool,
+    resets: usize,
+    value: u8,
+    received: Vec<u8>,
+}
+
+impl Class<MockBus> for Vendor {
+    fn reset(&mut self) {
+        self.configured = false;
+        self.resets += 1;
+    }
+
+    fn configured(&mut self, _bus: &mut MockBus) {
+        self.configured = true;
+    }
+
+    fn control_in(&mut self, request: &SetupPacket, buf: &mut [u8]) -> Result<usize, i32> {
+        match (request.kind(), request.request) {
+            (Kind::Vendor, 0x01) => {
+                buf[0] = self.value;
+                Ok(1)
+            }
+            (Kind::Vendor, _) => Err(-1),
+            _ => Err(-2),
+        }
+    }
+
+    fn control_out(&mut self, request: &SetupPacket, data: &[u8]) -> Result<(), i32> {
+        match (request.kind(), request.request) {
+            (Kind::Vendor, 0x02) => {
+                self.value = data[0];
+                Ok(())
+            }
+            _ => Err(-2),
+        }
+    }
+
+    fn endpoint_out(&mut self, bus: &mut MockBus, address: u8) {
+        let mut packet = [0; 64];
+        if address == 0x01
+            && let Ok(len) = bus.read(address, &mut packet)
+        {
+            self.received.extend_from_slice(&packet[..len]);
+        }
+    }
+}
+
+fn device() -> Device<'static> {
+    let mut descriptor = DeviceDescriptor::new(0x1209, 0x0001);
+    descriptor.manufacturer = 1;
+    descriptor.product = 2;
+    let mut device = Device::new(descriptor, STRINGS);
+    device
+        .configure(attributes::SELF_POWERED, 100, |config| {
+            config.interface(0xFF, 0, 0)?;
+            config.endpoint(0x01, EndpointType::Bulk, 64, 0)?;
+            config.endpoint(0x81, EndpointType::Bulk, 64, 0)
+        })
+        .unwrap();
+    device
+}
+
+fn standard(
+    direction: Direction,
+    recipient: Recipient,
+    request: u8,
+    value: u16,
+    index: u16,
+    length: u16,
+) -> SetupPacket {
+    SetupPacket::new(
+        direction,
+        Kind::Standard,
+        recipient,
+        request,
+        value,
+        index,
+        length,
+    )
+}
+
+fn get_descriptor(kind: u8, index: u8, length: u16) -> SetupPacket {
+    standard(
+        Direction::In,
+        Recipient::Device,
+        request::GET_DESCRIPTOR,
+        (kind as u16) << 8 | index as u16,
+        0,
+        length,
+    )
+}
+
+struct Host {
+    bus: MockBus,
+    device: Device<'static>,
+    class: Vendor,
+}
+
+impl Host {
+    fn new() -> Self {
+        let mut host = Self {
+            bus: MockBus::new(),
+            device: device(),
+            class: Vendor::default(),
+        };
+        host.bus.reset();
+        host.run();
+        host
+    }
+
+    fn run(&mut self) {
+        mock::run(&mut self.bus, &mut self.device, &mut [&mut self.class]);
+    }
+
+    fn control(&mut self, request: &SetupPacket, data: &[u8]) -> Result<Vec<u8>, i32> {
+        let mut response = [0; 512];
+        let len = mock::control(
+            &mut self.bus,
+            &mut self.device,
+            &mut [&mut self.class],
+            request,
+            data,
+            &mut response,
+        )?;
+        Ok(response[..len].to_vec())
+    }
+
+    fn enumerate(&mut self) {
+        let set_address = standard(
+            Direction::Out,
+            Recipient::Device,
+            request::SET_ADDRESS,
+            5,
+            0,
+            0,
+        );
+        self.control(&set_address, &[]).unwrap();
+        let set_configuration = standard(
+            Direction::Out,
+            Recipient::Device,
+            request::SET_CONFIGURATION,
+            1,
+            0,
+            0,
+        );
+        self.control(&set_configuration, &[]).unwrap();
+    }
+}
+
+#[test]
+fn test_enumeration() {
+    let mut host = Host::new();
+    assert_eq!(host.device.state(), State::Default);
+    assert!(host.bus.is_open(0x00) && host.bus.is_open(0x80));
+
+    let descriptor = host
+        .control(&get_descriptor(kind::DEVICE, 0, 64), &[])
+        .unwrap();
+    assert_eq!(
+        DeviceDescriptor::parse(&descriptor),
+        Ok(*host.device.descriptor())
+    );
+
+    let set_address = standard(
+        Direction::Out,
+        Recipient::Device,
+        request::SET_ADDRESS,
+        5,
+        0,
+        0,
+    );
+    host.control(&set_address, &[]).unwrap();
+    // Taken after the status stage
+    assert_eq!(host.bus.address(), 5);
+    assert_eq!(host.device.state(), State::Addressed);
+
+    let header = host
+        .control(&get_descriptor(kind::CONFIGURATION, 0, 9), &[])
+        .unwrap();
+    assert_eq!(header.len(), 9);
+    let total = u16::from_le_bytes([header[2], header[3]]);
+    let config = host
+        .control(&get_descriptor(kind::CONFIGURATION, 0, total), &[])
+        .unwrap();
+    assert_eq!(config, host.device.config());
+    assert_eq!(config.len(), 9 + 9 + 7 + 7);
+
+    let languages = host
+        .control(&get_descriptor(kind::STRING, 0, 255), &[])
+        .unwrap();
+    assert_eq!(languages, [4, 0x03, 0x09, 0x04]);
+    let product = host
+        .control(&get_descriptor(kind::STRING, 2, 255), &[])
+        .unwrap();
+    assert_eq!(product[0], 14);
+    assert_eq!(&product[2..6], [b'W', 0, b'i', 0]);
+    assert_eq!(
+        host.control(&get_descriptor(kind::STRING, 3, 255), &[]),
+        Err(-3)
+    );
+    assert_eq!(
+        host.control(&get_descriptor(kind::DEVICE_QUALIFIER, 0, 10), &[]),
+        Err(-3)
+    );
+
+    assert!(!host.bus.is_open(0x01));
+    let set_configuration = standard(
+        Direction::Out,
+        Recipient::Device,
+        request::SET_CONFIGURATION,
+        1,
+        0,
+        0,
+    );
+    host.control(&set_configuration, &[]).unwrap();
+    assert!(host.device.is_configured());
+    assert!(host.class.configured);
+    assert!(host.bus.is_open(0x01) && host.bus.is_open(0x81));
+    let get_configuration = standard(
+        Direction::In,
+        Recipient::Device,
+        request::GET_CONFIGURATION,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(host.control(&get_configuration, &[]).unwrap(), [1]);
+
+    host.bus.host_out(0x01, b"hello").unwrap();
+    host.run();
+    assert_eq!(host.class.received, b"hello");
+}
+
+#[test]
+fn test_unconfigure_and_reset() {
+    let mut host = Host::new();
+    host.enumerate();
+    let unconfigure = standard(
+        Direction::Out,
+        Recipient::Device,
+        request::SET_CONFIGURATION,
+        0,
+        0,
+        0,
+    );
+    host.control(&unconfigure, &[]).unwrap();
+    assert_eq!(host.device.state(), State::Addressed);
+    assert!(!host.class.configured);
+    assert!(!host.bus.is_open(0x01));
+    let bad = standard(
+        Direction::Out,
+        Recipient::Device,
+        request::SET_CONFIGURATION,
+        2,
+        0,
+        0,
+    );
+    assert_eq!(host.control(&bad, &[]), Err(-3));
+
+    host.enumerate();
+    let resets = host.class.resets;
+    host.bus.reset();
+    host.run();
+    assert_eq!(host.device.state(), State::Default);
+    assert_eq!(host.bus.address(), 0);
+    assert_eq!(host.class.resets, resets + 1);
+}
+
+#[test]
+fn test_status_and_features() {
+    let mut host = Host::new();
+    host.enumerate();
+    let get_status = standard(
+        Direction::In,
+        Recipient::Device,
+        request::GET_STATUS,
+        0,
+        0,
+        2,
+    );
+    assert_eq!(host.control(&get_status, &[]).unwrap(), [0x01, 0]);
+    let wakeup = standard(
+        Direction::Out,
+        Recipient::Device,
+        request::SET_FEATURE,
+        feature::DEVICE_REMOTE_WAKEUP,
+        0,
+        0,
+    );
+    host.control(&wakeup, &[]).unwrap();
+    assert!(host.device.remote_wakeup());
+    assert_eq!(host.control(&get_status, &[]).unwrap(), [0x03, 0]);
+
+    let halt = standard(
+        Direction::Out,
+        Recipient::Endpoint,
+        request::SET_FEATURE,
+        feature::ENDPOINT_HALT,
+        0x81,
+        0,
+    );
+    host.control(&halt, &[]).unwrap();
+    assert!(host.bus.is_stalled(0x81));
+    let endpoint_status = standard(
+        Direction::In,
+        Recipient::Endpoint,
+        request::GET_STATUS,
+        0,
+        0x81,
+        2,
+    );
+    assert_eq!(host.control(&endpoint_status, &[]).unwrap(), [1, 0]);
+    let clear = standard(
+        Direction::Out,
+        Recipient::Endpoint,
+        request::CLEAR_FEATURE,
+        feature::ENDPOINT_HALT,
+        0x81,
+        0,
+    );
+    host.control(&clear, &[]).unwrap();
+    assert!(!host.bus.is_stalled(0x81));
+    // No such endpoint
+    let missing = standard(
+        Direction::Out,
+        Recipient::Endpoint,
+        request::SET_FEATURE,
+        feature::ENDPOINT_HALT,
+        0x82,
+        0,
+    );
+    assert_eq!(host.control(&missing, &[]), Err(-3));
+
+    let get_interface = standard(
+        Direction::In,
+        Recipient::Interface,
+        request::GET_INTERFACE,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(host.control(&get_interface, &[]).unwrap(), [0]);
+    let alternate = standard(
+        Direction::Out,
+        Recipient::Interface,
+        request::SET_INTERFACE,
+        1,
+        0,
+        0,
+    );
+    assert_eq!(host.control(&alternate, &[]), Err(-3));
+    let other = standard(
+        Direction::In,
+        Recipient::Interface,
+        request::GET_INTERFACE,
+        0,
+        1,
+        1,
+    );
+    assert_eq!(host.control(&other, &[]), Err(-3));
+}
+
+#[test]
+fn test_class_requests() {
+    let mut host = Host::new();
+    host.enumerate();
+    let set = SetupPacket::new(
+        Direction::Out,
+        Kind::Vendor,
+        Recipient::Device,
+        0x02,
+        0,
+        0,
+        1,
+    );
+    host.control(&set, &[42]).unwrap();
+    let get = SetupPacket::new(
+        Direction::In,
+        Kind::Vendor,
+        Recipient::Device,
+        0x01,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(host.control(&get, &[]).unwrap(), [42]);
+    // Refused by the class, then by nobody
+    let refused = SetupPacket::new(
+        Direction::In,
+        Kind::Vendor,
+        Recipient::Device,
+        0x03,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(host.control(&refused, &[]), Err(-3));
+    let unknown = SetupPacket::new(
+        Direction::Out,
+        Kind::Class,
+        Recipient::Interface,
+        0x0A,
+        0,
+        0,
+        0,
+    );
+    assert_eq!(host.control(&unknown, &[]), Err(-3));
+    // The stall ends with the next request
+    assert_eq!(host.control(&get, &[]).unwrap(), [42]);
+}
+
+#[test]
+fn test_suspend() {
+    let mut host = Host::new();
+    host.bus.suspend();
+    host.run();
+    assert!(host.device.is_suspended());
+    host.bus.resume();
+    host.run();
+    assert!(!host.device.is_suspended());
+}
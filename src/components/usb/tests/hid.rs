@@ -0,0 +1,394 @@
+use usb::descriptor::{self, ConfigBuilder, DeviceDescriptor, kind};
+use usb::device::*;
+use usb::hid::*;
+use usb::mock::{self, MockBus};
+use usb::setup::{self, Direction, Kind, Recipient, SetupPacket};
+
+struct Host<C> {
+    bus: MockBus,
+    device: Device<'static>,
+    class: C,
+}
+
+impl<C: Class<MockBus>> Host<C> {
+    fn new(
+        mut class: C,
+        describe: impl FnOnce(&mut C, &mut ConfigBuilder) -> Result<(), i32>,
+    ) -> Self {
+        let mut device = Device::new(DeviceDescriptor::new(0x1209, 0x0001), &[]);
+        device
+            .configure(0, 100, |config| describe(&mut class, config))
+            .unwrap();
+        let mut host = Self {
+            bus: MockBus::new(),
+            device,
+            class,
+        };
+        host.bus.reset();
+        host.run();
+        for (request, value) in [
+            (setup::request::SET_ADDRESS, 3),
+            (setup::request::SET_CONFIGURATION, 1),
+        ] {
+            host.request(
+                Direction::Out,
+                Kind::Standard,
+                Recipient::Device,
+                request,
+                value,
+                &[],
+                0,
+            )
+            .unwrap();
+        }
+        host
+    }
+
+    fn run(&mut self) {
+        mock::run(&mut self.bus, &mut self.device, &mut [&mut self.class]);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn request(
+        &mut self,
+        direction: Direction,
+        kind: Kind,
+        recipient: Recipient,
+        request: u8,
+        value: u16,
+        data: &[u8],
+        length: u16,
+    ) -> Result<Vec<u8>, i32> {
+        let setup = SetupPacket::new(direction, kind, recipient, request, value, 0, length);
+        let mut response = [0; 256];
+        let len = mock::control(
+            &mut self.bus,
+            &mut self.device,
+            &mut [&mut self.class],
+            &setup,
+            data,
+            &mut response,
+        )?;
+        Ok(response[..len].to_vec())
+    }
+
+    fn class_request(
+        &mut self,
+        direction: Direction,
+        request: u8,
+        value: u16,
+        data: &[u8],
+        length: u16,
+    ) -> Result<Vec<u8>, i32> {
+        self.request(
+            direction,
+            Kind::Class,
+            Recipient::Interface,
+            request,
+            value,
+            data,
+            length,
+        )
+    }
+
+    fn class_descriptor(&mut self, kind: u8, length: u16) -> Result<Vec<u8>, i32> {
+        self.request(
+            Direction::In,
+            Kind::Standard,
+            Recipient::Interface,
+            setup::request::GET_DESCRIPTOR,
+            (kind as u16) << 8,
+            &[],
+            length,
+        )
+    }
+
+    /// The report on endpoint 1 IN, if any
+    fn report(&mut self) -> Option<Vec<u8>> {
+        let mut packet = [0; 64];
+        let len = self.bus.host_in(0x81, &mut packet).ok()?;
+        self.run();
+        Some(packet[..len].to_vec())
+    }
+}
+
+fn keyboard() -> Host<Keyboard> {
+    Host::new(Keyboard::new(1), |keyboard, config| {
+        keyboard.describe(config)
+    })
+}
+
+#[test]
+fn test_builder_makes_boot_keyboard() {
+    let mut buf = [0; 128];
+    let mut report = ReportBuilder::new(&mut buf);
+    report.usage_page(usage_page::GENERIC_DESKTOP).unwrap();
+    report.usage(usage::KEYBOARD).unwrap();
+    report.collection(collection::APPLICATION).unwrap();
+    report.usage_page(usage_page::KEYBOARD).unwrap();
+    report.usage_minimum(0xE0).unwrap();
+    report.usage_maximum(0xE7).unwrap();
+    report.logical_minimum(0).unwrap();
+    report.logical_maximum(1).unwrap();
+    report.report_size(1).unwrap();
+    report.report_count(8).unwrap();
+    report
+        .input(flags::DATA | flags::VARIABLE | flags::ABSOLUTE)
+        .unwrap();
+    report.report_count(1).unwrap();
+    report.report_size(8).unwrap();
+    report.input(flags::CONSTANT).unwrap();
+    report.report_count(5).unwrap();
+    report.report_size(1).unwrap();
+    report.usage_page(usage_page::LEDS).unwrap();
+    report.usage_minimum(1).unwrap();
+    report.usage_maximum(5).unwrap();
+    report.output(flags::DATA | flags::VARIABLE).unwrap();
+    report.report_count(1).unwrap();
+    report.report_size(3).unwrap();
+    report.output(flags::CONSTANT).unwrap();
+    report.report_count(6).unwrap();
+    report.report_size(8).unwrap();
+    report.logical_minimum(0).unwrap();
+    report.logical_maximum(101).unwrap();
+    report.usage_page(usage_page::KEYBOARD).unwrap();
+    report.usage_minimum(0).unwrap();
+    report.usage_maximum(101).unwrap();
+    report.input(flags::DATA | flags::ARRAY).unwrap();
+    report.end_collection().unwrap();
+    let len = report.finish().unwrap();
+    assert_eq!(&buf[..len], BOOT_KEYBOARD_REPORT);
+}
+
+#[test]
+fn test_builder_item_sizes() {
+    let mut buf = [0; 32];
+    let mut report = ReportBuilder::new(&mut buf);
+    report.usage_page(usage_page::VENDOR).unwrap();
+    // 255 does not fit a signed byte
+    report.logical_maximum(255).unwrap();
+    report.logical_minimum(-127).unwrap();
+    report.physical_maximum(100_000).unwrap();
+    report.unit(0x0001_0001).unwrap();
+    report.report_id(2).unwrap();
+    let len = report.finish().unwrap();
+    assert_eq!(
+        &buf[..len],
+        [
+            0x06, 0x00, 0xFF, // Usage page (vendor)
+            0x26, 0xFF, 0x00, // Logical maximum (255)
+            0x15, 0x81, // Logical minimum (-127)
+            0x47, 0xA0, 0x86, 0x01, 0x00, // Physical maximum (100000)
+            0x67, 0x01, 0x00, 0x01, 0x00, // Unit
+            0x85, 0x02, // Report ID (2)
+        ]
+    );
+}
+
+#[test]
+fn test_builder_errors() {
+    let mut buf = [0; 3];
+    let mut report = ReportBuilder::new(&mut buf);
+    assert_eq!(report.end_collection(), Err(-1));
+    assert_eq!(report.report_id(0), Err(-1));
+    report.collection(collection::APPLICATION).unwrap();
+    // One byte left
+    assert_eq!(report.usage(usage::MOUSE), Err(-5));
+    assert_eq!(report.finish(), Err(-1));
+}
+
+#[test]
+fn test_keyboard_descriptors() {
+    let mut host = keyboard();
+    let config = host.device.config();
+    let kinds: Vec<(u8, usize)> = descriptor::iter(config).map(|d| (d[1], d.len())).collect();
+    assert_eq!(
+        kinds,
+        [
+            (kind::CONFIGURATION, 9),
+            (kind::INTERFACE, 9),
+            (DESCRIPTOR_HID, 9),
+            (kind::ENDPOINT, 7),
+        ]
+    );
+    let interface = descriptor::iter(config).nth(1).unwrap();
+    assert_eq!(
+        (interface[5], interface[6], interface[7]),
+        (CLASS_HID, SUBCLASS_BOOT, PROTOCOL_KEYBOARD)
+    );
+    let hid = descriptor::iter(config).nth(2).unwrap().to_vec();
+    assert_eq!(hid, [9, 0x21, 0x11, 0x01, 0, 1, 0x22, 63, 0]);
+    let endpoint = descriptor::iter(config).nth(3).unwrap();
+    assert_eq!(endpoint, [7, kind::ENDPOINT, 0x81, 0x03, 8, 0, 10]);
+
+    assert_eq!(host.class_descriptor(DESCRIPTOR_HID, 9).unwrap(), hid);
+    assert_eq!(
+        host.class_descriptor(DESCRIPTOR_REPORT, 63).unwrap(),
+        BOOT_KEYBOARD_REPORT
+    );
+    // Physical descriptors are not there
+    assert_eq!(host.class_descriptor(0x23, 9), Err(-3));
+}
+
+#[test]
+fn test_keyboard_reports() {
+    let mut host = keyboard();
+    assert_eq!(host.report(), None);
+    host.class
+        .press(&mut host.bus, modifier::LEFT_SHIFT, &[key::A]);
+    assert_eq!(host.report().unwrap(), [0x02, 0, key::A, 0, 0, 0, 0, 0]);
+    // Nothing changed, nothing sent
+    host.class
+        .press(&mut host.bus, modifier::LEFT_SHIFT, &[key::A]);
+    assert_eq!(host.report(), None);
+
+    // The second report waits for the first to go out
+    host.class.press(&mut host.bus, 0, &[key::A, key::Z]);
+    host.class.release_all(&mut host.bus);
+    assert!(host.class.is_busy());
+    assert_eq!(host.report().unwrap(), [0, 0, key::A, key::Z, 0, 0, 0, 0]);
+    assert_eq!(host.report().unwrap(), [0; 8]);
+    assert!(!host.class.is_busy());
+
+    host.class.press(&mut host.bus, 0, &[4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(host.report().unwrap(), [0, 0, 1, 1, 1, 1, 1, 1]);
+    assert_eq!(
+        host.class_request(Direction::In, request::GET_REPORT, 0x0100, &[], 8)
+            .unwrap(),
+        [0, 0, 1, 1, 1, 1, 1, 1]
+    );
+}
+
+#[test]
+fn test_keyboard_leds_idle_and_protocol() {
+    let mut host = keyboard();
+    assert_eq!(host.class.leds(), 0);
+    host.class_request(
+        Direction::Out,
+        request::SET_REPORT,
+        0x0200,
+        &[led::CAPS_LOCK | led::NUM_LOCK],
+        1,
+    )
+    .unwrap();
+    assert_eq!(host.class.leds(), led::CAPS_LOCK | led::NUM_LOCK);
+
+    host.class_request(Direction::Out, request::SET_IDLE, 125 << 8, &[], 0)
+        .unwrap();
+    assert_eq!(host.class.hid().idle(), 125);
+    assert_eq!(
+        host.class_request(Direction::In, request::GET_IDLE, 0, &[], 1)
+            .unwrap(),
+        [125]
+    );
+
+    assert_eq!(
+        host.class_request(Direction::In, request::GET_PROTOCOL, 0, &[], 1)
+            .unwrap(),
+        [1]
+    );
+    host.class_request(Direction::Out, request::SET_PROTOCOL, 0, &[], 0)
+        .unwrap();
+    assert!(host.class.hid().is_boot_protocol());
+    assert_eq!(
+        host.class_request(Direction::In, request::GET_PROTOCOL, 0, &[], 1)
+            .unwrap(),
+        [0]
+    );
+
+    host.bus.reset();
+    host.run();
+    assert!(!host.class.hid().is_boot_protocol());
+    assert!(!host.class.hid().is_configured());
+}
+
+/// A vendor-defined report: 4 bytes in, 2 bytes out
+const VENDOR_REPORT: [u8; 23] = [
+    0x06, 0x00, 0xFF, 0x09, 0x01, 0xA1, 0x01, 0x15, 0x00, 0x26, 0xFF, 0x00, 0x75, 0x08, 0x95, 0x04,
+    0x81, 0x02, 0x95, 0x02, 0x91, 0x02, 0xC0,
+];
+
+#[test]
+fn test_generic_reports() {
+    let mut buf = [0; 32];
+    let mut report = ReportBuilder::new(&mut buf);
+    report.usage_page(usage_page::VENDOR).unwrap();
+    report.usage(0x01).unwrap();
+    report.collection(collection::APPLICATION).unwrap();
+    report.logical_minimum(0).unwrap();
+    report.logical_maximum(255).unwrap();
+    report.report_size(8).unwrap();
+    report.report_count(4).unwrap();
+    report.input(flags::DATA | flags::VARIABLE).unwrap();
+    report.report_count(2).unwrap();
+    report.output(flags::DATA | flags::VARIABLE).unwrap();
+    report.end_collection().unwrap();
+    let len = report.finish().unwrap();
+    assert_eq!(&buf[..len], VENDOR_REPORT);
+
+    let mut host = Host::new(Hid::new(1, &VENDOR_REPORT, 4, 1), |hid, config| {
+        hid.describe(config)
+    });
+    let interface = descriptor::iter(host.device.config()).nth(1).unwrap();
+    assert_eq!((interface[6], interface[7]), (SUBCLASS_NONE, PROTOCOL_NONE));
+    // No boot protocol to switch to
+    assert_eq!(
+        host.class_request(Direction::Out, request::SET_PROTOCOL, 0, &[], 0),
+        Err(-3)
+    );
+
+    assert_eq!(host.class.send(&mut host.bus, &[1, 2, 3, 4, 5]), Err(-1));
+    host.class.send(&mut host.bus, &[1, 2, 3, 4]).unwrap();
+    assert_eq!(host.class.send(&mut host.bus, &[5, 6, 7, 8]), Err(-5));
+    assert_eq!(host.report().unwrap(), [1, 2, 3, 4]);
+    host.class.send(&mut host.bus, &[5, 6, 7, 8]).unwrap();
+    assert_eq!(
+        host.class_request(Direction::In, request::GET_REPORT, 0x0100, &[], 4)
+            .unwrap(),
+        [5, 6, 7, 8]
+    );
+
+    let mut output = [0; 8];
+    assert_eq!(host.class.take_output(&mut output), None);
+    host.class_request(Direction::Out, request::SET_REPORT, 0x0200, &[9, 10], 2)
+        .unwrap();
+    assert_eq!(host.class.take_output(&mut output), Some(2));
+    assert_eq!(output[..2], [9, 10]);
+    assert_eq!(host.class.take_output(&mut output), None);
+    assert_eq!(host.class.output(), [9, 10]);
+}
+
+#[test]
+fn test_ascii_keys() {
+    assert_eq!(ascii_key('a'), Some((0, key::A)));
+    assert_eq!(ascii_key('Z'), Some((modifier::LEFT_SHIFT, key::Z)));
+    assert_eq!(ascii_key('1'), Some((0, key::N1)));
+    assert_eq!(ascii_key('0'), Some((0, key::N0)));
+    assert_eq!(ascii_key('!'), Some((modifier::LEFT_SHIFT, key::N1)));
+    assert_eq!(ascii_key('?'), Some((modifier::LEFT_SHIFT, 0x38)));
+    assert_eq!(ascii_key('\n'), Some((0, key::ENTER)));
+    assert_eq!(ascii_key(' '), Some((0, key::SPACE)));
+    assert_eq!(ascii_key('é'), None);
+    assert_eq!(ascii_key('\u{7}'), None);
+}
+
+#[test]
+fn test_key_chars_and_report_parse() {
+    for c in (' '..='~').chain(['\n', '\t']) {
+        let (modifiers, key) = ascii_key(c).unwrap();
+        assert_eq!(key_char(modifiers, key), Some(c));
+    }
+    assert_eq!(
+        key_char(modifier::RIGHT_SHIFT | modifier::LEFT_CTRL, key::A),
+        Some('A')
+    );
+    assert_eq!(key_char(0, key::F1), None);
+
+    let report = KeyboardReport::new(modifier::LEFT_SHIFT, &[key::A, key::Z]);
+    assert_eq!(KeyboardReport::parse(&report.encode()), Ok(report));
+    assert_eq!(KeyboardReport::parse(&[0; 7]), Err(-1));
+    let before = KeyboardReport::new(0, &[key::A]);
+    assert_eq!(report.pressed_since(&before).collect::<Vec<_>>(), [key::Z]);
+    assert!(KeyboardReport::new(0, &[key::A; 7]).is_roll_over());
+    assert!(!report.is_roll_over());
+}
@@ -0,0 +1,282 @@
+use usb::descriptor::{DeviceDescriptor, kind};
+use usb::device::*;
+use usb::hid::{self, key, led, modifier};
+use usb::host::{self, HostBus, PortEvent, Speed};
+use usb::host_hid;
+use usb::host_msc::Drive;
+use usb::mock::Loopback;
+use usb::msc::*;
+use usb::setup::{Direction, Kind, Recipient, SetupPacket, request};
+
+const BLOCKS: usize = 64;
+
+struct RamDisk {
+    blocks: Vec<[u8; BLOCK_SIZE]>,
+    read_only: bool,
+}
+
+impl BlockDevice for RamDisk {
+    fn block_count(&self) -> u32 {
+        self.blocks.len() as u32
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn read(&mut self, lba: u32, block: &mut [u8; BLOCK_SIZE]) -> Result<(), i32> {
+        *block = self.blocks[lba as usize];
+        Ok(())
+    }
+
+    fn write(&mut self, lba: u32, block: &[u8; BLOCK_SIZE]) -> Result<(), i32> {
+        self.blocks[lba as usize] = *block;
+        Ok(())
+    }
+}
+
+fn keyboard() -> Loopback<'static, hid::Keyboard> {
+    let mut device = Device::new(DeviceDescriptor::new(0x1209, 0x0002), &[]);
+    let mut class = hid::Keyboard::new(1);
+    device
+        .configure(0, 100, |config| class.describe(config))
+        .unwrap();
+    Loopback::new(device, class)
+}
+
+fn drive(read_only: bool) -> Loopback<'static, MassStorage<RamDisk>> {
+    let mut device = Device::new(DeviceDescriptor::new(0x1209, 0x0003), &[]);
+    let disk = RamDisk {
+        blocks: vec![[0; BLOCK_SIZE]; BLOCKS],
+        read_only,
+    };
+    let mut class = MassStorage::new(2, disk, "Acme", "Flash drive");
+    device
+        .configure(0, 100, |config| class.describe(config))
+        .unwrap();
+    Loopback::new(device, class)
+}
+
+#[test]
+fn test_enumeration() {
+    let mut bus = keyboard();
+    assert_eq!(bus.poll(), Some(PortEvent::Connected));
+    assert_eq!(bus.poll(), None);
+    assert_eq!(host::enumerate(&mut bus, 0).err(), Some(-1));
+    assert_eq!(host::enumerate(&mut bus, 128).err(), Some(-1));
+
+    let mut device = host::enumerate(&mut bus, 5).unwrap();
+    assert_eq!(device.speed, Speed::Full);
+    assert_eq!(device.address(), 5);
+    assert_eq!(bus.bus.address(), 5);
+    assert!(bus.device.is_configured());
+    assert_eq!(device.descriptor, *bus.device.descriptor());
+    assert_eq!(device.control.max_packet_size, 64);
+    assert_eq!(device.config(), bus.device.config());
+
+    let interfaces: Vec<_> = device.interfaces().collect();
+    assert_eq!(interfaces.len(), 1);
+    let interface = interfaces[0];
+    assert_eq!(
+        (
+            interface.number,
+            interface.class,
+            interface.subclass,
+            interface.protocol
+        ),
+        (
+            0,
+            hid::CLASS_HID,
+            hid::SUBCLASS_BOOT,
+            hid::PROTOCOL_KEYBOARD
+        )
+    );
+    let endpoints: Vec<_> = interface.endpoints().collect();
+    assert_eq!(endpoints.len(), 1);
+    assert_eq!(endpoints[0].address, 0x81);
+    // The HID descriptor comes before the endpoint
+    assert_eq!(interface.descriptors()[1], hid::DESCRIPTOR_HID);
+
+    // Requests go on after enumeration; a missing string is stalled
+    let mut buf = [0; 64];
+    let len = device
+        .get_descriptor(&mut bus, kind::DEVICE, 0, &mut buf)
+        .unwrap();
+    assert_eq!(len, DeviceDescriptor::SIZE);
+    assert_eq!(
+        device.get_descriptor(&mut bus, kind::STRING, 9, &mut buf),
+        Err(-3)
+    );
+    let request = SetupPacket::new(
+        Direction::In,
+        Kind::Standard,
+        Recipient::Device,
+        request::GET_CONFIGURATION,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(device.control_in(&mut bus, &request, &mut buf), Ok(1));
+    assert_eq!(buf[0], 1);
+
+    assert_eq!(bus.channels(), 1);
+    device.release(&mut bus);
+    assert_eq!(bus.channels(), 0);
+}
+
+#[test]
+fn test_unplugged() {
+    let mut bus = keyboard();
+    let mut device = host::enumerate(&mut bus, 1).unwrap();
+    bus.unplug();
+    assert_eq!(bus.poll(), Some(PortEvent::Disconnected));
+    assert!(!bus.is_connected());
+    let mut buf = [0; 18];
+    assert_eq!(
+        device.get_descriptor(&mut bus, kind::DEVICE, 0, &mut buf),
+        Err(-4)
+    );
+    device.release(&mut bus);
+    assert_eq!(host::enumerate(&mut bus, 1).err(), Some(-2));
+    assert_eq!(bus.channels(), 0);
+}
+
+#[test]
+fn test_channels_run_out() {
+    let mut bus = keyboard();
+    for _ in 0..usb::mock::CHANNELS - 1 {
+        bus.alloc_channel().unwrap();
+    }
+    let device = host::enumerate(&mut bus, 1).unwrap();
+    assert_eq!(bus.alloc_channel(), Err(-5));
+    bus.free_channel(device.control.channel);
+    assert!(bus.alloc_channel().is_ok());
+}
+
+#[test]
+fn test_keyboard() {
+    let mut bus = keyboard();
+    let mut device = host::enumerate(&mut bus, 1).unwrap();
+    let mut keyboard = host_hid::Keyboard::open(&mut bus, &mut device).unwrap();
+    assert!(bus.class.hid().is_boot_protocol());
+    assert_eq!(bus.class.hid().idle(), 0);
+    assert_eq!(keyboard.interval(), hid::Keyboard::INTERVAL);
+
+    // Nothing to report is a NAK
+    assert_eq!(keyboard.poll(&mut bus), Ok(false));
+
+    bus.class.press(&mut bus.bus, 0, &[key::A]);
+    assert_eq!(keyboard.poll(&mut bus), Ok(true));
+    assert_eq!(keyboard.chars().collect::<String>(), "a");
+    assert_eq!(keyboard.poll(&mut bus), Ok(false));
+
+    // Shift down with a second key: only the new key types
+    bus.class
+        .press(&mut bus.bus, modifier::LEFT_SHIFT, &[key::A, key::N1]);
+    assert_eq!(keyboard.poll(&mut bus), Ok(true));
+    assert_eq!(keyboard.pressed().collect::<Vec<_>>(), [key::N1]);
+    assert_eq!(keyboard.chars().collect::<String>(), "!");
+
+    // Roll over keeps the keys as they were
+    bus.class.press(&mut bus.bus, 0, &[key::A; 7]);
+    assert_eq!(keyboard.poll(&mut bus), Ok(false));
+    assert_eq!(keyboard.report().keys[..2], [key::A, key::N1]);
+
+    bus.class.release_all(&mut bus.bus);
+    assert_eq!(keyboard.poll(&mut bus), Ok(true));
+    assert_eq!(keyboard.chars().count(), 0);
+
+    keyboard
+        .set_leds(&mut bus, &mut device, led::CAPS_LOCK | led::NUM_LOCK)
+        .unwrap();
+    assert_eq!(bus.class.leds(), led::CAPS_LOCK | led::NUM_LOCK);
+    assert_eq!(keyboard.leds(), led::CAPS_LOCK | led::NUM_LOCK);
+
+    assert_eq!(bus.channels(), 2);
+    keyboard.close(&mut bus);
+    assert_eq!(bus.channels(), 1);
+}
+
+#[test]
+fn test_drivers_match_their_class() {
+    let mut bus = drive(false);
+    let mut device = host::enumerate(&mut bus, 1).unwrap();
+    assert_eq!(
+        host_hid::Keyboard::open(&mut bus, &mut device).err(),
+        Some(-2)
+    );
+
+    let mut bus = keyboard();
+    let mut device = host::enumerate(&mut bus, 1).unwrap();
+    assert_eq!(Drive::open(&mut bus, &mut device).err(), Some(-2));
+    assert_eq!(bus.channels(), 1);
+}
+
+#[test]
+fn test_drive() {
+    let mut bus = drive(false);
+    let mut device = host::enumerate(&mut bus, 7).unwrap();
+    let mut drive = Drive::open(&mut bus, &mut device).unwrap();
+    assert_eq!(drive.block_count(), BLOCKS as u32);
+    assert_eq!(drive.vendor(), "Acme");
+    assert_eq!(drive.product(), "Flash drive");
+    assert_eq!(bus.channels(), 3);
+
+    let mut data = vec![0; 3 * BLOCK_SIZE];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i * 7) as u8;
+    }
+    drive
+        .write_blocks(&mut bus, &mut device, 10, &data)
+        .unwrap();
+    assert_eq!(
+        bus.class.disk().blocks[11][..],
+        data[BLOCK_SIZE..2 * BLOCK_SIZE]
+    );
+
+    let mut back = vec![0; 3 * BLOCK_SIZE];
+    drive
+        .read_blocks(&mut bus, &mut device, 10, &mut back)
+        .unwrap();
+    assert_eq!(back, data);
+
+    // The last block and no further
+    let mut block = [0; BLOCK_SIZE];
+    drive
+        .read_blocks(&mut bus, &mut device, BLOCKS as u32 - 1, &mut block)
+        .unwrap();
+    assert_eq!(
+        drive.read_blocks(&mut bus, &mut device, BLOCKS as u32, &mut block),
+        Err(-1)
+    );
+    assert_eq!(
+        drive.read_blocks(&mut bus, &mut device, 0, &mut block[..100]),
+        Err(-1)
+    );
+    assert_eq!(drive.write_blocks(&mut bus, &mut device, 0, &[]), Err(-1));
+
+    drive.close(&mut bus);
+    assert_eq!(bus.channels(), 1);
+}
+
+#[test]
+fn test_drive_failed_command() {
+    let mut bus = drive(true);
+    let mut device = host::enumerate(&mut bus, 1).unwrap();
+    let mut drive = Drive::open(&mut bus, &mut device).unwrap();
+    let block = [0x55; BLOCK_SIZE];
+    assert_eq!(
+        drive.write_blocks(&mut bus, &mut device, 0, &block),
+        Err(-4)
+    );
+    assert_eq!(drive.sense(), sense::WRITE_PROTECTED);
+    assert_eq!(bus.class.disk().blocks[0], [0; BLOCK_SIZE]);
+
+    // The next command goes through, and clears the sense
+    let mut back = [0; BLOCK_SIZE];
+    drive
+        .read_blocks(&mut bus, &mut device, 0, &mut back)
+        .unwrap();
+    assert_eq!(drive.sense(), sense::NO_SENSE);
+    assert!(!bus.class.is_busy());
+}
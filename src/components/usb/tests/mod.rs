@@ -0,0 +1,8 @@
+pub mod cdc;
+pub mod control;
+pub mod descriptor;
+pub mod device;
+pub mod hid;
+pub mod host;
+pub mod msc;
+pub mod setup;
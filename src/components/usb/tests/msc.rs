@@ -0,0 +1,468 @@
+use usb::bus::Bus;
+use usb::descriptor::{self, DeviceDescriptor, kind};
+use usb::device::*;
+use usb::mock::{self, MockBus};
+use usb::msc::*;
+use usb::setup::{self, Direction, Kind, Recipient, SetupPacket, feature};
+
+const BLOCKS: usize = 64;
+
+/// Disk in memory, which can be made to fail
+struct RamDisk {
+    blocks: Vec<[u8; BLOCK_SIZE]>,
+    read_only: bool,
+    fail_at: Option<u32>,
+    flushes: usize,
+}
+
+impl RamDisk {
+    fn new() -> Self {
+        Self {
+            blocks: vec![[0; BLOCK_SIZE]; BLOCKS],
+            read_only: false,
+            fail_at: None,
+            flushes: 0,
+        }
+    }
+}
+
+impl BlockDevice for RamDisk {
+    fn block_count(&self) -> u32 {
+        self.blocks.len() as u32
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn read(&mut self, lba: u32, block: &mut [u8; BLOCK_SIZE]) -> Result<(), i32> {
+        if self.fail_at == Some(lba) {
+            return Err(-3);
+        }
+        *block = self.blocks[lba as usize];
+        Ok(())
+    }
+
+    fn write(&mut self, lba: u32, block: &[u8; BLOCK_SIZE]) -> Result<(), i32> {
+        if self.fail_at == Some(lba) {
+            return Err(-3);
+        }
+        self.blocks[lba as usize] = *block;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), i32> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
+struct Host {
+    bus: MockBus,
+    device: Device<'static>,
+    drive: MassStorage<RamDisk>,
+}
+
+impl Host {
+    fn new(disk: RamDisk) -> Self {
+        let mut device = Device::new(DeviceDescriptor::new(0x1209, 0x0001), &[]);
+        let mut drive = MassStorage::new(1, disk, "Acme", "Flash drive");
+        device
+            .configure(0, 100, |config| drive.describe(config))
+            .unwrap();
+        let mut host = Self {
+            bus: MockBus::new(),
+            device,
+            drive,
+        };
+        host.bus.reset();
+        host.run();
+        for (request, value) in [
+            (setup::request::SET_ADDRESS, 3),
+            (setup::request::SET_CONFIGURATION, 1),
+        ] {
+            let setup = SetupPacket::new(
+                Direction::Out,
+                Kind::Standard,
+                Recipient::Device,
+                request,
+                value,
+                0,
+                0,
+            );
+            host.control(&setup, &[]).unwrap();
+        }
+        host
+    }
+
+    fn run(&mut self) {
+        mock::run(&mut self.bus, &mut self.device, &mut [&mut self.drive]);
+    }
+
+    fn control(&mut self, request: &SetupPacket, data: &[u8]) -> Result<Vec<u8>, i32> {
+        let mut response = [0; 64];
+        let len = mock::control(
+            &mut self.bus,
+            &mut self.device,
+            &mut [&mut self.drive],
+            request,
+            data,
+            &mut response,
+        )?;
+        Ok(response[..len].to_vec())
+    }
+
+    fn clear_halt(&mut self, address: u8) {
+        let setup = SetupPacket::new(
+            Direction::Out,
+            Kind::Standard,
+            Recipient::Endpoint,
+            setup::request::CLEAR_FEATURE,
+            feature::ENDPOINT_HALT,
+            address as u16,
+            0,
+        );
+        self.control(&setup, &[]).unwrap();
+    }
+
+    /// Run a command as a host driver does: the CBW, the data phase, with
+    /// the halt cleared if the device stalls it, then the CSW
+    fn command(&mut self, cbw: &[u8], data_out: &[u8]) -> (Vec<u8>, Csw) {
+        let parsed = Cbw::parse(cbw).unwrap();
+        self.bus.host_out(0x01, cbw).unwrap();
+        self.run();
+        let mut data = Vec::new();
+        if parsed.data_in {
+            while data.len() < parsed.data_length as usize {
+                let mut packet = [0; 64];
+                match self.bus.host_in(0x81, &mut packet) {
+                    Ok(len) => {
+                        data.extend_from_slice(&packet[..len]);
+                        self.run();
+                        if len < packet.len() {
+                            break;
+                        }
+                    }
+                    Err(-3) => {
+                        self.clear_halt(0x81);
+                        break;
+                    }
+                    Err(error) => panic!("IN failed: {error}"),
+                }
+            }
+        } else {
+            for chunk in data_out.chunks(64) {
+                match self.bus.host_out(0x01, chunk) {
+                    Ok(()) => self.run(),
+                    Err(-3) => {
+                        self.clear_halt(0x01);
+                        break;
+                    }
+                    Err(error) => panic!("OUT failed: {error}"),
+                }
+            }
+        }
+        let mut csw = [0; 64];
+        let len = self.bus.host_in(0x81, &mut csw).unwrap();
+        self.run();
+        let csw = Csw::parse(&csw[..len]).unwrap();
+        assert_eq!(csw.tag, parsed.tag);
+        (data, csw)
+    }
+}
+
+/// CBWs as a Linux host sends them to a new drive
+const INQUIRY: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x01, 0x00, 0x00, 0x00, 0x24, 0x00, 0x00, 0x00, 0x80, 0x00, 0x06, 0x12,
+    0x00, 0x00, 0x00, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const TEST_UNIT_READY: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const READ_CAPACITY: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x03, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x80, 0x00, 0x0A, 0x25,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const MODE_SENSE: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x04, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x80, 0x00, 0x06, 0x1A,
+    0x00, 0x3F, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+/// READ(10) of the first eight blocks
+const READ_FIRST_BLOCKS: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x05, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x80, 0x00, 0x0A, 0x28,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+/// From a Windows host
+const READ_FORMAT_CAPACITIES: [u8; 31] = [
+    0x55, 0x53, 0x42, 0x43, 0x06, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x00, 0x80, 0x00, 0x0A, 0x23,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+fn request_sense(host: &mut Host) -> (u8, u8) {
+    let cbw = scsi_cbw(0x20, true, 18, &[0x03, 0, 0, 0, 18, 0]);
+    let (data, csw) = host.command(&cbw, &[]);
+    assert_eq!(csw.status, Status::Passed);
+    assert_eq!((data.len(), data[0], data[7]), (18, 0x70, 10));
+    (data[2], data[12])
+}
+
+fn scsi_cbw(tag: u32, data_in: bool, data_length: u32, command: &[u8]) -> [u8; 31] {
+    let mut block = [0; 16];
+    block[..command.len()].copy_from_slice(command);
+    Cbw {
+        tag,
+        data_length,
+        data_in,
+        lun: 0,
+        command: block,
+        command_length: command.len() as u8,
+    }
+    .encode()
+}
+
+fn read_10(tag: u32, lba: u32, blocks: u16) -> [u8; 31] {
+    let [l0, l1, l2, l3] = lba.to_be_bytes();
+    let [b0, b1] = blocks.to_be_bytes();
+    let length = blocks as u32 * BLOCK_SIZE as u32;
+    scsi_cbw(tag, true, length, &[0x28, 0, l0, l1, l2, l3, 0, b0, b1, 0])
+}
+
+fn write_10(tag: u32, lba: u32, blocks: u16) -> [u8; 31] {
+    let mut cbw = read_10(tag, lba, blocks);
+    cbw[12] = 0x00;
+    cbw[15] = scsi::WRITE_10;
+    cbw
+}
+
+#[test]
+fn test_wrappers() {
+    let cbw = Cbw::parse(&INQUIRY).unwrap();
+    assert_eq!(
+        (
+            cbw.tag,
+            cbw.data_length,
+            cbw.data_in,
+            cbw.lun,
+            cbw.command_length
+        ),
+        (1, 36, true, 0, 6)
+    );
+    assert_eq!(cbw.command[..6], [0x12, 0, 0, 0, 0x24, 0]);
+    assert_eq!(cbw.encode(), INQUIRY);
+
+    let mut bad = INQUIRY;
+    bad[0] = 0x00;
+    assert_eq!(Cbw::parse(&bad), Err(-1));
+    let mut bad = INQUIRY;
+    bad[14] = 0;
+    assert_eq!(Cbw::parse(&bad), Err(-1));
+    assert_eq!(Cbw::parse(&INQUIRY[..30]), Err(-1));
+
+    let csw = Csw {
+        tag: 7,
+        residue: 12,
+        status: Status::Failed,
+    };
+    let bytes = csw.encode();
+    assert_eq!(bytes, [0x55, 0x53, 0x42, 0x53, 7, 0, 0, 0, 12, 0, 0, 0, 1]);
+    assert_eq!(Csw::parse(&bytes), Ok(csw));
+}
+
+#[test]
+fn test_descriptors_and_max_lun() {
+    let mut host = Host::new(RamDisk::new());
+    let config = host.device.config();
+    let kinds: Vec<u8> = descriptor::iter(config).map(|d| d[1]).collect();
+    assert_eq!(
+        kinds,
+        [
+            kind::CONFIGURATION,
+            kind::INTERFACE,
+            kind::ENDPOINT,
+            kind::ENDPOINT
+        ]
+    );
+    let interface = descriptor::iter(config).nth(1).unwrap();
+    assert_eq!(
+        (interface[5], interface[6], interface[7]),
+        (CLASS_MASS_STORAGE, SUBCLASS_SCSI, PROTOCOL_BULK_ONLY)
+    );
+    assert!(host.bus.is_open(0x81) && host.bus.is_open(0x01));
+
+    let setup = SetupPacket::new(
+        Direction::In,
+        Kind::Class,
+        Recipient::Interface,
+        request::GET_MAX_LUN,
+        0,
+        0,
+        1,
+    );
+    assert_eq!(host.control(&setup, &[]).unwrap(), [0]);
+}
+
+#[test]
+fn test_recorded_attach() {
+    let mut host = Host::new(RamDisk::new());
+
+    let (data, csw) = host.command(&INQUIRY, &[]);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 0));
+    assert_eq!(data.len(), 36);
+    assert_eq!(data[..5], [0x00, 0x80, 0x04, 0x02, 31]);
+    assert_eq!(&data[8..16], b"Acme    ");
+    assert_eq!(&data[16..32], b"Flash drive     ");
+
+    let (data, csw) = host.command(&TEST_UNIT_READY, &[]);
+    assert_eq!((data.len(), csw.status), (0, Status::Passed));
+
+    let (data, csw) = host.command(&READ_CAPACITY, &[]);
+    assert_eq!(csw.status, Status::Passed);
+    assert_eq!(data, [0, 0, 0, BLOCKS as u8 - 1, 0, 0, 0x02, 0x00]);
+
+    // The host allows 192 bytes; the header alone comes back
+    let (data, csw) = host.command(&MODE_SENSE, &[]);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 188));
+    assert_eq!(data, [3, 0, 0, 0]);
+
+    host.drive.disk_mut().blocks[0][510..].copy_from_slice(&[0x55, 0xAA]);
+    let (data, csw) = host.command(&READ_FIRST_BLOCKS, &[]);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 0));
+    assert_eq!(data.len(), 8 * BLOCK_SIZE);
+    assert_eq!(data[510..512], [0x55, 0xAA]);
+
+    let (data, csw) = host.command(&READ_FORMAT_CAPACITIES, &[]);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 252 - 12));
+    assert_eq!(data, [0, 0, 0, 8, 0, 0, 0, BLOCKS as u8, 0x02, 0, 0x02, 0]);
+}
+
+#[test]
+fn test_write_then_read() {
+    let mut host = Host::new(RamDisk::new());
+    let data: Vec<u8> = (0..2 * BLOCK_SIZE).map(|i| (i % 251) as u8).collect();
+    let (_, csw) = host.command(&write_10(1, 5, 2), &data);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 0));
+    assert_eq!(host.drive.disk().blocks[5][..], data[..BLOCK_SIZE]);
+    assert_eq!(host.drive.disk().blocks[6][..], data[BLOCK_SIZE..]);
+    assert_eq!(host.drive.disk().flushes, 1);
+
+    let (read, csw) = host.command(&read_10(2, 5, 2), &[]);
+    assert_eq!((csw.status, csw.residue), (Status::Passed, 0));
+    assert_eq!(read, data);
+
+    // SYNCHRONIZE CACHE flushes, VERIFY passes
+    let (_, csw) = host.command(
+        &scsi_cbw(3, false, 0, &[0x35, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        &[],
+    );
+    assert_eq!(csw.status, Status::Passed);
+    assert_eq!(host.drive.disk().flushes, 2);
+    let (_, csw) = host.command(
+        &scsi_cbw(4, false, 0, &[0x2F, 0, 0, 0, 0, 5, 0, 0, 2, 0]),
+        &[],
+    );
+    assert_eq!(csw.status, Status::Passed);
+}
+
+#[test]
+fn test_out_of_range_and_unknown_commands() {
+    let mut host = Host::new(RamDisk::new());
+    let (data, csw) = host.command(&read_10(1, BLOCKS as u32 - 1, 2), &[]);
+    assert!(data.is_empty());
+    assert_eq!(
+        (csw.status, csw.residue),
+        (Status::Failed, 2 * BLOCK_SIZE as u32)
+    );
+    assert_eq!(request_sense(&mut host), (0x05, 0x21));
+    // REQUEST SENSE clears the sense
+    assert_eq!(request_sense(&mut host), (0x00, 0x00));
+
+    // REPORT LUNS is not there
+    let cbw = scsi_cbw(2, true, 16, &[0xA0, 0, 0, 0, 0, 0, 0, 0, 0, 16, 0, 0]);
+    let (data, csw) = host.command(&cbw, &[]);
+    assert!(data.is_empty());
+    assert_eq!((csw.status, csw.residue), (Status::Failed, 16));
+    assert_eq!(request_sense(&mut host), (0x05, 0x20));
+
+    // The OUT endpoint stalls on data for a write out of range
+    let (_, csw) = host.command(&write_10(3, BLOCKS as u32, 1), &[0; BLOCK_SIZE]);
+    assert_eq!(
+        (csw.status, csw.residue),
+        (Status::Failed, BLOCK_SIZE as u32)
+    );
+    let (_, csw) = host.command(&TEST_UNIT_READY, &[]);
+    assert_eq!(csw.status, Status::Passed);
+}
+
+#[test]
+fn test_read_only_and_disk_errors() {
+    let mut disk = RamDisk::new();
+    disk.read_only = true;
+    let mut host = Host::new(disk);
+    let (data, _) = host.command(&MODE_SENSE, &[]);
+    assert_eq!(data, [3, 0, 0x80, 0]);
+    let (_, csw) = host.command(&write_10(1, 0, 1), &[0xFF; BLOCK_SIZE]);
+    assert_eq!(csw.status, Status::Failed);
+    assert_eq!(host.drive.disk().blocks[0], [0; BLOCK_SIZE]);
+    assert_eq!(request_sense(&mut host), (0x07, 0x27));
+
+    let mut disk = RamDisk::new();
+    disk.fail_at = Some(3);
+    let mut host = Host::new(disk);
+    // Blocks 2 goes out, block 3 fails
+    let (data, csw) = host.command(&read_10(1, 2, 2), &[]);
+    assert_eq!(data.len(), BLOCK_SIZE);
+    assert_eq!(
+        (csw.status, csw.residue),
+        (Status::Failed, BLOCK_SIZE as u32)
+    );
+    assert_eq!(request_sense(&mut host), (0x03, 0x11));
+
+    let (_, csw) = host.command(&write_10(2, 3, 2), &[0xAB; 2 * BLOCK_SIZE]);
+    assert_eq!(csw.status, Status::Failed);
+    assert_eq!(request_sense(&mut host), (0x03, 0x0C));
+}
+
+#[test]
+fn test_phase_errors() {
+    let mut host = Host::new(RamDisk::new());
+    // READ(10) with the data going the wrong way
+    let mut cbw = read_10(1, 0, 1);
+    cbw[12] = 0x00;
+    let (_, csw) = host.command(&cbw, &[0; BLOCK_SIZE]);
+    assert_eq!(csw.status, Status::PhaseError);
+
+    // INQUIRY with no room announced for the answer
+    let cbw = scsi_cbw(2, false, 0, &[0x12, 0, 0, 0, 36, 0]);
+    let (_, csw) = host.command(&cbw, &[]);
+    assert_eq!((csw.status, csw.residue), (Status::PhaseError, 0));
+
+    let (_, csw) = host.command(&TEST_UNIT_READY, &[]);
+    assert_eq!(csw.status, Status::Passed);
+}
+
+#[test]
+fn test_invalid_cbw_stalls_until_reset() {
+    let mut host = Host::new(RamDisk::new());
+    host.bus.host_out(0x01, &[0; 31]).unwrap();
+    host.run();
+    assert!(host.bus.is_stalled(0x81) && host.bus.is_stalled(0x01));
+    assert_eq!(host.bus.host_out(0x01, &TEST_UNIT_READY), Err(-3));
+
+    // Reset recovery: the class reset, then both halts cleared
+    let setup = SetupPacket::new(
+        Direction::Out,
+        Kind::Class,
+        Recipient::Interface,
+        request::BULK_ONLY_RESET,
+        0,
+        0,
+        0,
+    );
+    host.control(&setup, &[]).unwrap();
+    host.clear_halt(0x81);
+    host.clear_halt(0x01);
+    assert!(!host.drive.is_busy());
+    let (_, csw) = host.command(&TEST_UNIT_READY, &[]);
+    assert_eq!(csw.status, Status::Passed);
+}
@@ -0,0 +1,36 @@
+use usb::setup::*;
+
+#[test]
+fn test_parse_get_descriptor() {
+    let packet = SetupPacket::parse(&[0x80, 0x06, 0x00, 0x01, 0x00, 0x00, 0x40, 0x00]).unwrap();
+    assert_eq!(packet.request, request::GET_DESCRIPTOR);
+    assert_eq!(packet.direction(), Direction::In);
+    assert_eq!(packet.kind(), Kind::Standard);
+    assert_eq!(packet.recipient(), Recipient::Device);
+    assert_eq!(packet.descriptor_type(), 0x01);
+    assert_eq!(packet.descriptor_index(), 0);
+    assert_eq!(packet.length, 64);
+}
+
+#[test]
+fn test_class_request_round_trip() {
+    let packet = SetupPacket::new(
+        Direction::Out,
+        Kind::Class,
+        Recipient::Interface,
+        0x22,
+        0x0003,
+        1,
+        0,
+    );
+    assert_eq!(packet.request_type, 0x21);
+    let bytes = packet.encode();
+    assert_eq!(bytes, [0x21, 0x22, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00]);
+    assert_eq!(SetupPacket::parse(&bytes), Ok(packet));
+}
+
+#[test]
+fn test_parse_rejects_wrong_length() {
+    assert_eq!(SetupPacket::parse(&[0x80, 0x06, 0x00]), Err(-1));
+    assert_eq!(SetupPacket::parse(&[0; 9]), Err(-1));
+}
@@ -0,0 +1,50 @@
+//! # EXTI Driver
+//!
+//! Routes GPIO pins and internal events (RTC alarm and wakeup) to the
+//! External Interrupt/Event Controller. An unmasked line raises its
+//! interrupt and wakes the core from Sleep and Stop.
+//!
+//! Lines 0-15 belong to the pins of the same number; only one port can use
+//! a line at a time. The pin itself must be configured as an input.
+//!
+//! ```ignore
+//! exti::configure_pin(button, EventTrigger::FallingEdge)?;
+//! if exti::is_pending(button_line) {
+//!     exti::clear_pending(button_line);
+//! }
+//! ```
+#![allow(dead_code)]
+
+/// A generic error type for the EXTI driver, using i32 for error codes.
+pub type Error = i32;
+
+/// A specialized Result type for EXTI operations.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// EXTI v1 IP (single pending register, port selection in SYSCFG or AFIO)
+/// of the STM32F1 and STM32F4 families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub mod v1;
+
+/// EXTI v2 IP (separate rising/falling pending registers, port selection
+/// in EXTI) of the STM32G0 family
+#[cfg(feature = "stm32g030")]
+pub mod v2;
+
+/// EXTI driver of the selected MCU, for board-agnostic code.
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub use v1::{clear_pending, configure_line, configure_pin, irqn, is_pending};
+
+/// EXTI driver of the selected MCU, for board-agnostic code.
+#[cfg(feature = "stm32g030")]
+pub use v2::{clear_pending, configure_line, configure_pin, irqn, is_pending};
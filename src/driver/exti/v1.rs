@@ -0,0 +1,108 @@
+use super::Result;
+use crate::driver::gpio::{EventTrigger, Pin, decode_pin};
+use crate::mcu::device::{self, IRQn, PeripheralAccess, exti::*, rcc};
+use crate::utils;
+use core::ptr;
+
+/// Enable the clock of the block holding the EXTI port selection
+fn enable_selection_clock() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    #[cfg(feature = "stm32f103")]
+    let mask = rcc::APB2ENR_AFIOEN_MASK;
+    #[cfg(not(feature = "stm32f103"))]
+    let mask = rcc::APB2ENR_SYSCFGEN_MASK;
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).apb2enr);
+        ptr::write_volatile(&mut (*rcc).apb2enr, v | mask);
+    }
+}
+
+/// The EXTICR registers: in AFIO on the STM32F1, in SYSCFG on the STM32F4
+fn exticr() -> *mut [u32; 4] {
+    #[cfg(feature = "stm32f103")]
+    let exticr = unsafe { &raw mut (*device::gpio::AFIO::ptr_mut()).exticr };
+    #[cfg(not(feature = "stm32f103"))]
+    let exticr = unsafe { &raw mut (*SYSCFG::ptr_mut()).exticr };
+    exticr
+}
+
+fn update(reg: *mut u32, line: u8, set: bool) {
+    unsafe {
+        let v = ptr::read_volatile(reg);
+        ptr::write_volatile(reg, utils::set_bit(v, line as u32, set));
+    }
+}
+
+/// Connect a pin to the EXTI line of its number and trigger it on `trigger`;
+/// `EventTrigger::None` masks the line. Returns Err(-1) for an invalid pin.
+pub fn configure_pin(pin: Pin, trigger: EventTrigger) -> Result<()> {
+    let (port, number) = decode_pin(pin)?;
+    enable_selection_clock();
+    let exticr = exticr();
+    let index = (number / 4) as usize;
+    let shift = (number % 4) as u32 * EXTICR_EXTI_WIDTH;
+    unsafe {
+        let reg = &raw mut (*exticr)[index];
+        let v = ptr::read_volatile(reg);
+        ptr::write_volatile(
+            reg,
+            utils::set_bits(v, port.index() as u32, shift, EXTICR_EXTI_WIDTH),
+        );
+    }
+    configure_line(number, trigger)
+}
+
+/// Trigger an EXTI line on `trigger` and unmask its interrupt;
+/// `EventTrigger::None` masks it. Returns Err(-1) for an invalid line.
+pub fn configure_line(line: u8, trigger: EventTrigger) -> Result<()> {
+    if line >= EXTI_LINES {
+        return Err(-1);
+    }
+    let (rising, falling) = match trigger {
+        EventTrigger::None => (false, false),
+        EventTrigger::RisingEdge => (true, false),
+        EventTrigger::FallingEdge => (false, true),
+        EventTrigger::EitherEdge => (true, true),
+    };
+    let exti = EXTI::ptr_mut();
+    unsafe {
+        update(&raw mut (*exti).imr, line, false);
+        update(&raw mut (*exti).rtsr, line, rising);
+        update(&raw mut (*exti).ftsr, line, falling);
+    }
+    clear_pending(line);
+    if trigger != EventTrigger::None {
+        unsafe { update(&raw mut (*EXTI::ptr_mut()).imr, line, true) };
+    }
+    Ok(())
+}
+
+/// Returns true if the line saw its trigger since it was last cleared
+pub fn is_pending(line: u8) -> bool {
+    let pr = unsafe { ptr::read_volatile(&(*EXTI::ptr()).pr) };
+    line < EXTI_LINES && utils::read_bit(pr, line as u32)
+}
+
+pub fn clear_pending(line: u8) {
+    if line < EXTI_LINES {
+        unsafe { ptr::write_volatile(&mut (*EXTI::ptr_mut()).pr, 1 << line) };
+    }
+}
+
+/// Interrupt of an EXTI line, shared by lines 5-9 and 10-15
+pub fn irqn(line: u8) -> Option<u32> {
+    let irqn = match line {
+        0 => IRQn::EXTI0,
+        1 => IRQn::EXTI1,
+        2 => IRQn::EXTI2,
+        3 => IRQn::EXTI3,
+        4 => IRQn::EXTI4,
+        5..=9 => IRQn::EXTI9_5,
+        10..=15 => IRQn::EXTI15_10,
+        EXTI_LINE_RTC_ALARM => IRQn::RTC_Alarm,
+        #[cfg(not(feature = "stm32f103"))]
+        EXTI_LINE_RTC_WAKEUP => IRQn::RTC_WKUP,
+        _ => return None,
+    };
+    Some(irqn as u32)
+}
@@ -0,0 +1,89 @@
+use super::Result;
+use crate::driver::gpio::{EventTrigger, Pin, decode_pin};
+use crate::mcu::device::{IRQn, PeripheralAccess, exti::*};
+use crate::utils;
+use core::ptr;
+
+fn update(reg: *mut u32, line: u8, set: bool) {
+    unsafe {
+        let v = ptr::read_volatile(reg);
+        ptr::write_volatile(reg, utils::set_bit(v, line as u32, set));
+    }
+}
+
+/// Connect a pin to the EXTI line of its number and trigger it on `trigger`;
+/// `EventTrigger::None` masks the line. Returns Err(-1) for an invalid pin.
+pub fn configure_pin(pin: Pin, trigger: EventTrigger) -> Result<()> {
+    let (port, number) = decode_pin(pin)?;
+    let exti = EXTI::ptr_mut();
+    let index = (number / 4) as usize;
+    let shift = (number % 4) as u32 * EXTICR_EXTI_WIDTH;
+    unsafe {
+        let reg = &raw mut (*exti).exticr[index];
+        let v = ptr::read_volatile(reg);
+        ptr::write_volatile(
+            reg,
+            utils::set_bits(v, port.index() as u32, shift, EXTICR_EXTI_WIDTH),
+        );
+    }
+    configure_line(number, trigger)
+}
+
+/// Trigger an EXTI line on `trigger` and unmask its interrupt;
+/// `EventTrigger::None` masks it. The RTC line is a direct line whose
+/// trigger is set in the RTC, any other trigger unmasks it. Returns Err(-1)
+/// for an invalid line.
+pub fn configure_line(line: u8, trigger: EventTrigger) -> Result<()> {
+    let exti = EXTI::ptr_mut();
+    if line < EXTI_LINES {
+        let (rising, falling) = match trigger {
+            EventTrigger::None => (false, false),
+            EventTrigger::RisingEdge => (true, false),
+            EventTrigger::FallingEdge => (false, true),
+            EventTrigger::EitherEdge => (true, true),
+        };
+        unsafe {
+            update(&raw mut (*exti).imr1, line, false);
+            update(&raw mut (*exti).rtsr1, line, rising);
+            update(&raw mut (*exti).ftsr1, line, falling);
+        }
+        clear_pending(line);
+    } else if line != EXTI_LINE_RTC {
+        return Err(-1);
+    }
+    unsafe { update(&raw mut (*exti).imr1, line, trigger != EventTrigger::None) };
+    Ok(())
+}
+
+/// Returns true if the line saw its trigger since it was last cleared.
+/// Always false for the RTC line, whose flags are in the RTC.
+pub fn is_pending(line: u8) -> bool {
+    if line >= EXTI_LINES {
+        return false;
+    }
+    let exti = EXTI::ptr();
+    let pending = unsafe { ptr::read_volatile(&(*exti).rpr1) | ptr::read_volatile(&(*exti).fpr1) };
+    utils::read_bit(pending, line as u32)
+}
+
+pub fn clear_pending(line: u8) {
+    if line < EXTI_LINES {
+        let exti = EXTI::ptr_mut();
+        unsafe {
+            ptr::write_volatile(&mut (*exti).rpr1, 1 << line);
+            ptr::write_volatile(&mut (*exti).fpr1, 1 << line);
+        }
+    }
+}
+
+/// Interrupt of an EXTI line, shared by pairs of lines and by lines 4-15
+pub fn irqn(line: u8) -> Option<u32> {
+    let irqn = match line {
+        0..=1 => IRQn::EXTI0_1,
+        2..=3 => IRQn::EXTI2_3,
+        4..=15 => IRQn::EXTI4_15,
+        EXTI_LINE_RTC => IRQn::RTC_TAMP,
+        _ => return None,
+    };
+    Some(irqn as u32)
+}
@@ -0,0 +1,67 @@
+//! # Boot slots
+//!
+//! A `Flash` driver as the storage of the host-tested `boot` component,
+//! for the bootloader in `bootloader/` and for an application built for
+//! one of its slots, which confirms its image through the journal in the
+//! state sector (see `crate::slot`).
+
+use super::Flash;
+
+#[allow(unused_imports)]
+pub use boot::{
+    layout::{Layout, STM32F407, Slot},
+    state::{Journal, Record, State},
+    storage::{Region, Storage},
+};
+
+/// Flash layout of the board: bootloader, state and the two slots
+#[cfg(feature = "stm32f407")]
+pub const LAYOUT: Layout = STM32F407;
+
+/// A `Flash` driver as the `Storage` of the boot component
+pub struct Adapter<'b, F>(pub &'b mut F);
+
+impl<'a, F: Flash<'a>> Storage for Adapter<'_, F> {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), i32> {
+        self.0.read_data(address, buf)
+    }
+
+    fn program(&mut self, address: u32, data: &[u8]) -> Result<(), i32> {
+        self.0.program_data(address, data)
+    }
+
+    /// Returns Err(-1) unless `region` starts and ends on sector bounds
+    fn erase(&mut self, region: Region) -> Result<(), i32> {
+        let info = self.0.get_info();
+        let (sectors, sector_size) = (info.sector_info, info.sector_size);
+        match sectors {
+            Some(sectors) => {
+                let starts = sectors.iter().any(|s| s.start == region.start);
+                let ends = sectors
+                    .iter()
+                    .any(|s| s.end.wrapping_add(1) == region.end());
+                if !starts || !ends {
+                    return Err(-1);
+                }
+                for sector in sectors
+                    .iter()
+                    .filter(|s| s.start >= region.start && s.end < region.end())
+                {
+                    self.0.erase_sector(sector.start)?;
+                }
+            }
+            None => {
+                if sector_size == 0
+                    || !region.start.is_multiple_of(sector_size)
+                    || !region.size.is_multiple_of(sector_size)
+                {
+                    return Err(-1);
+                }
+                for start in (region.start..region.end()).step_by(sector_size as usize) {
+                    self.0.erase_sector(start)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
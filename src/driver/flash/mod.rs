@@ -102,3 +102,10 @@ pub trait Flash<'a> {
     /// Gets information about the flash memory device.
     fn get_info(&self) -> &FlashInfo;
 }
+
+/// Embedded flash of the STM32F4 family
+#[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
+pub mod v1;
+
+/// Slots and boot state of the bootloader, on a `Flash` driver
+pub mod boot;
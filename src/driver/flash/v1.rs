@@ -0,0 +1,230 @@
+//! Embedded flash of the STM32F4: four 16K sectors, one 64K sector and
+//! 128K sectors up to the size of the part, programmed a word at a time
+//! (bytes at unaligned ends) with 32-bit parallelism, which needs a supply
+//! of 2.7 V to 3.6 V.
+//!
+//! Operations are synchronous. The parts have a single bank, so the core
+//! stalls on any fetch from flash while a sector erases (up to 2 s for
+//! 128K) and interrupts wait as long. The MPU layout keeps flash
+//! read-only, so code that programs it clears `mpu::layout::FLASH_REGION`
+//! first.
+
+use super::{Event, Flash, FlashInfo, Result, SectorInfo, Status};
+use crate::mcu::device::{PeripheralAccess, flash::*};
+use core::ptr;
+
+/// Sectors of the part: four small, one medium, then large ones
+const SECTOR_COUNT: usize = 5 + ((FLASH_SIZE - LARGE_SECTOR_SIZE) / LARGE_SECTOR_SIZE) as usize;
+
+static SECTORS: [SectorInfo; SECTOR_COUNT] = sectors();
+
+const fn sector_start(index: usize) -> u32 {
+    match index {
+        0..4 => FLASH_BASE + index as u32 * SMALL_SECTOR_SIZE,
+        4 => FLASH_BASE + 4 * SMALL_SECTOR_SIZE,
+        _ => FLASH_BASE + (index as u32 - 4) * LARGE_SECTOR_SIZE,
+    }
+}
+
+const fn sectors() -> [SectorInfo; SECTOR_COUNT] {
+    let mut sectors = [SectorInfo { start: 0, end: 0 }; SECTOR_COUNT];
+    let mut index = 0;
+    while index < SECTOR_COUNT {
+        sectors[index] = SectorInfo {
+            start: sector_start(index),
+            end: sector_start(index + 1) - 1,
+        };
+        index += 1;
+    }
+    sectors
+}
+
+/// Number of the sector holding `addr`
+fn sector_number(addr: u32) -> Option<u32> {
+    SECTORS
+        .iter()
+        .position(|sector| (sector.start..=sector.end).contains(&addr))
+        .map(|index| index as u32)
+}
+
+/// Returns Err(-1) unless `len` bytes at `addr` are all in main memory
+fn check_range(addr: u32, len: usize) -> Result<()> {
+    let inside = addr >= FLASH_BASE
+        && len as u32 <= FLASH_SIZE
+        && addr - FLASH_BASE <= FLASH_SIZE - len as u32;
+    if inside { Ok(()) } else { Err(-1) }
+}
+
+fn regs() -> *mut RegisterBlock {
+    FLASH::ptr_mut()
+}
+
+/// Wait for the current operation to end. Returns Err(-3) if it failed,
+/// clearing the error flags.
+fn wait_ready() -> Result<()> {
+    let flash = regs();
+    unsafe {
+        while ptr::read_volatile(&(*flash).sr) & SR_BSY_MASK != 0 {}
+        let sr = ptr::read_volatile(&(*flash).sr);
+        ptr::write_volatile(&mut (*flash).sr, sr & (SR_ERRORS_MASK | SR_EOP_MASK));
+        if sr & SR_ERRORS_MASK != 0 {
+            return Err(-3);
+        }
+    }
+    Ok(())
+}
+
+fn unlock() {
+    let flash = regs();
+    unsafe {
+        if ptr::read_volatile(&(*flash).cr) & CR_LOCK_MASK != 0 {
+            ptr::write_volatile(&mut (*flash).keyr, KEYR_KEY1);
+            ptr::write_volatile(&mut (*flash).keyr, KEYR_KEY2);
+        }
+    }
+}
+
+fn lock() {
+    let flash = regs();
+    unsafe {
+        ptr::write_volatile(&mut (*flash).cr, CR_LOCK_MASK);
+    }
+}
+
+/// Run `operation` with CR unlocked, relocking it whatever the outcome
+fn unlocked(operation: impl FnOnce() -> Result<()>) -> Result<()> {
+    unlock();
+    // Flags left by an earlier operation would fail this one
+    let result = wait_ready().and_then(|()| operation());
+    lock();
+    result
+}
+
+/// Reset the data and instruction caches, which may hold what flash held
+/// before an erase. They are disabled while reset.
+fn reset_caches() {
+    let flash = regs();
+    unsafe {
+        let acr = ptr::read_volatile(&(*flash).acr);
+        let enabled = acr & (ACR_DCEN_MASK | ACR_ICEN_MASK);
+        ptr::write_volatile(&mut (*flash).acr, acr & !enabled);
+        ptr::write_volatile(
+            &mut (*flash).acr,
+            (acr & !enabled) | ACR_DCRST_MASK | ACR_ICRST_MASK,
+        );
+        ptr::write_volatile(&mut (*flash).acr, acr & !enabled);
+        ptr::write_volatile(&mut (*flash).acr, acr);
+    }
+}
+
+/// Driver for the embedded flash
+pub struct InternalFlash {
+    info: FlashInfo,
+}
+
+impl Default for InternalFlash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InternalFlash {
+    pub const fn new() -> Self {
+        Self {
+            info: FlashInfo {
+                sector_info: Some(&SECTORS),
+                sector_count: SECTOR_COUNT as u32,
+                sector_size: 0,
+                page_size: 4,
+                program_unit: 1,
+                erased_value: 0xFF,
+            },
+        }
+    }
+}
+
+impl<'a> Flash<'a> for InternalFlash {
+    /// Operations complete before they return, so `callback` is dropped
+    fn initialize(&mut self, _callback: impl FnMut(Event) + 'a) -> Result<()> {
+        Ok(())
+    }
+
+    fn uninitialize(&mut self) -> Result<()> {
+        lock();
+        Ok(())
+    }
+
+    fn read_data(&mut self, addr: u32, data: &mut [u8]) -> Result<()> {
+        check_range(addr, data.len())?;
+        for (offset, byte) in data.iter_mut().enumerate() {
+            *byte = unsafe { ptr::read_volatile((addr as usize + offset) as *const u8) };
+        }
+        Ok(())
+    }
+
+    /// Returns Err(-1) outside main memory, Err(-3) if programming fails,
+    /// which it does over bytes that are not erased
+    fn program_data(&mut self, addr: u32, data: &[u8]) -> Result<()> {
+        check_range(addr, data.len())?;
+        let flash = regs();
+        unlocked(|| {
+            let mut offset = 0;
+            while offset < data.len() {
+                let target = addr + offset as u32;
+                let word = data.len() - offset >= 4 && target.is_multiple_of(4);
+                unsafe {
+                    if word {
+                        ptr::write_volatile(&mut (*flash).cr, CR_PSIZE_X32 | CR_PG_MASK);
+                        let bytes = [
+                            data[offset],
+                            data[offset + 1],
+                            data[offset + 2],
+                            data[offset + 3],
+                        ];
+                        ptr::write_volatile(target as *mut u32, u32::from_le_bytes(bytes));
+                        offset += 4;
+                    } else {
+                        ptr::write_volatile(&mut (*flash).cr, CR_PSIZE_X8 | CR_PG_MASK);
+                        ptr::write_volatile(target as *mut u8, data[offset]);
+                        offset += 1;
+                    }
+                }
+                wait_ready()?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns Err(-1) outside main memory, Err(-3) if the erase fails
+    fn erase_sector(&mut self, addr: u32) -> Result<()> {
+        let number = sector_number(addr).ok_or(-1)?;
+        let flash = regs();
+        let result = unlocked(|| {
+            let cr = CR_PSIZE_X32 | CR_SER_MASK | (number << CR_SNB_POS);
+            unsafe {
+                ptr::write_volatile(&mut (*flash).cr, cr);
+                ptr::write_volatile(&mut (*flash).cr, cr | CR_STRT_MASK);
+            }
+            wait_ready()
+        });
+        reset_caches();
+        result
+    }
+
+    /// Returns Err(-2): a mass erase would take the running code with it
+    fn erase_chip(&mut self) -> Result<()> {
+        Err(-2)
+    }
+
+    fn get_status(&self) -> Status {
+        let sr = unsafe { ptr::read_volatile(&(*regs()).sr) };
+        Status {
+            busy: sr & SR_BSY_MASK != 0,
+            error: sr & SR_ERRORS_MASK != 0,
+        }
+    }
+
+    fn get_info(&self) -> &FlashInfo {
+        &self.info
+    }
+}
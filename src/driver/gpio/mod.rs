@@ -7,15 +7,150 @@
 //! interrupt handling, and digital I/O operations across different STM32 families.
 #![allow(dead_code)]
 
+use crate::logging::warn;
 use bitflags::bitflags;
 use core::ops::FnMut;
 
 /// Represents a GPIO pin identifier.
-/// The interpretation of this identifier is implementation-specific.
-/// For example, it could be a simple number (0-15) for a single port,
-/// or a value like `(port << 4) | pin` for multiple ports.
+/// The identifier is encoded as `(port << 4) | pin`, see [`encode_pin`] and
+/// [`decode_pin`]. Typed handles ([`GpioPin`]) convert into this form.
 pub type Pin = u32;
 
+/// Identifies a GPIO port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Port {
+    A = 0,
+    B = 1,
+    C = 2,
+    D = 3,
+    E = 4,
+    F = 5,
+    G = 6,
+    H = 7,
+    I = 8,
+    J = 9,
+    K = 10,
+}
+
+impl Port {
+    /// Returns the port for a zero-based index (0=A, 1=B, ...).
+    pub const fn from_index(index: u8) -> Option<Port> {
+        match index {
+            0 => Some(Port::A),
+            1 => Some(Port::B),
+            2 => Some(Port::C),
+            3 => Some(Port::D),
+            4 => Some(Port::E),
+            5 => Some(Port::F),
+            6 => Some(Port::G),
+            7 => Some(Port::H),
+            8 => Some(Port::I),
+            9 => Some(Port::J),
+            10 => Some(Port::K),
+            _ => None,
+        }
+    }
+
+    /// Returns the port for an upper-case port letter ('A'..='K').
+    /// Panics (at compile time when used in a const context) for other letters.
+    pub const fn from_letter(letter: char) -> Port {
+        match letter {
+            'A' => Port::A,
+            'B' => Port::B,
+            'C' => Port::C,
+            'D' => Port::D,
+            'E' => Port::E,
+            'F' => Port::F,
+            'G' => Port::G,
+            'H' => Port::H,
+            'I' => Port::I,
+            'J' => Port::J,
+            'K' => Port::K,
+            _ => panic!("invalid GPIO port letter"),
+        }
+    }
+
+    /// Returns the zero-based index of the port.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Encodes a port and pin number into a [`Pin`] identifier.
+pub const fn encode_pin(port: Port, pin_num: u8) -> Pin {
+    ((port as u32) << 4) | ((pin_num as u32) & 0xF)
+}
+
+/// Decodes a [`Pin`] identifier into its port and pin number.
+/// Returns an error for identifiers that do not name a valid port.
+pub fn decode_pin(pin: Pin) -> Result<(Port, u8)> {
+    match Port::from_index(((pin >> 4) & 0xF) as u8) {
+        Some(port) if pin <= 0xFF => Ok((port, (pin & 0xF) as u8)),
+        _ => {
+            warn!("0x{:x} does not name a GPIO pin", pin);
+            Err(-1)
+        }
+    }
+}
+
+/// A zero-sized handle for a single GPIO pin.
+/// The port letter and pin number are part of the type, so a handle costs
+/// nothing at runtime and register addresses resolve at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GpioPin<const P: char, const N: u8>;
+
+impl<const P: char, const N: u8> GpioPin<P, N> {
+    /// Port of this pin.
+    pub const PORT: Port = Port::from_letter(P);
+    /// Pin number within the port.
+    pub const NUMBER: u8 = {
+        assert!(N < 16, "GPIO pin number must be 0-15");
+        N
+    };
+    /// Encoded identifier of this pin.
+    pub const ID: Pin = encode_pin(Self::PORT, Self::NUMBER);
+
+    /// Creates a new pin handle.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Returns the encoded identifier of this pin.
+    pub const fn id(&self) -> Pin {
+        Self::ID
+    }
+}
+
+impl<const P: char, const N: u8> From<GpioPin<P, N>> for Pin {
+    fn from(_: GpioPin<P, N>) -> Pin {
+        GpioPin::<P, N>::ID
+    }
+}
+
+/// Typed pin handle on port A, e.g. `PA<9>`.
+pub type PA<const N: u8> = GpioPin<'A', N>;
+/// Typed pin handle on port B.
+pub type PB<const N: u8> = GpioPin<'B', N>;
+/// Typed pin handle on port C.
+pub type PC<const N: u8> = GpioPin<'C', N>;
+/// Typed pin handle on port D.
+pub type PD<const N: u8> = GpioPin<'D', N>;
+/// Typed pin handle on port E.
+pub type PE<const N: u8> = GpioPin<'E', N>;
+/// Typed pin handle on port F.
+pub type PF<const N: u8> = GpioPin<'F', N>;
+/// Typed pin handle on port G.
+pub type PG<const N: u8> = GpioPin<'G', N>;
+/// Typed pin handle on port H.
+pub type PH<const N: u8> = GpioPin<'H', N>;
+/// Typed pin handle on port I.
+pub type PI<const N: u8> = GpioPin<'I', N>;
+/// Typed pin handle on port J.
+pub type PJ<const N: u8> = GpioPin<'J', N>;
+/// Typed pin handle on port K.
+pub type PK<const N: u8> = GpioPin<'K', N>;
+
 /// Defines the direction of a GPIO pin.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -45,6 +180,30 @@ pub enum PullResistor {
     PullDown,
 }
 
+/// Defines the output speed (slew rate) of a GPIO pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Speed {
+    /// Low speed
+    Low,
+    /// Medium speed (default)
+    Medium,
+    /// High speed
+    High,
+    /// Very high speed
+    VeryHigh,
+}
+
+/// Defines the function a GPIO pin is connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    /// Software controlled input/output (default)
+    Gpio,
+    /// On-chip peripheral selected by alternate function number
+    Alternate(u8),
+    /// Analog (ADC/DAC), digital input buffer disabled
+    Analog,
+}
+
 /// Defines the trigger condition for an external interrupt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EventTrigger {
@@ -102,16 +261,46 @@ pub trait Gpio<'a> {
     /// Gets the input level of a GPIO pin.
     /// Returns true for high, false for low.
     fn get_input(&self, pin: Pin) -> bool;
-}
 
-#[cfg(feature = "stm32f407")]
-pub mod stm32f407;
+    /// Sets the output speed of a GPIO pin.
+    fn set_speed(&mut self, pin: Pin, speed: Speed) -> Result<()>;
 
-#[cfg(feature = "stm32f401")]
-pub mod stm32f401;
+    /// Connects a GPIO pin to an on-chip peripheral using alternate function `af`.
+    fn set_alternate_function(&mut self, pin: Pin, af: u8) -> Result<()>;
 
-#[cfg(feature = "stm32f411")]
-pub mod stm32f411;
+    /// Puts a GPIO pin into analog mode.
+    fn set_analog(&mut self, pin: Pin) -> Result<()>;
+
+    /// Locks the configuration of a GPIO pin until the next reset.
+    fn lock(&mut self, pin: Pin) -> Result<()>;
+}
 
+/// GPIO v1 IP (CRL/CRH configuration, AFIO remapping) of the STM32F1 family
 #[cfg(feature = "stm32f103")]
-pub mod stm32f103;
+pub mod v1;
+
+/// GPIO v2 IP (MODER/AFR configuration) of the STM32F4 and STM32G0 families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32g030"
+))]
+pub mod v2;
+
+/// GPIO driver of the selected MCU, for board-agnostic code.
+#[cfg(feature = "stm32f103")]
+pub use v1::{
+    AfSignal, GpioConfig, GpioDriver, configure_alternate, configure_pin, read_pin, write_pin,
+};
+
+/// GPIO driver of the selected MCU, for board-agnostic code.
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32g030"
+))]
+pub use v2::{
+    AfSignal, GpioConfig, GpioDriver, configure_alternate, configure_pin, read_pin, write_pin,
+};
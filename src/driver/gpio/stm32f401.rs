@@ -1,2 +0,0 @@
-// GPIO driver implementation for STM32F401
-// TODO: Implement GPIO driver functions
@@ -1,2 +0,0 @@
-// GPIO driver implementation for STM32F411
-// TODO: Implement GPIO driver functions
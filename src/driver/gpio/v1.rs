@@ -0,0 +1,621 @@
+extern crate alloc;
+
+use super::{
+    Direction, EventTrigger, EventType, Function, Gpio, GpioPin, OutputMode, Pin, Port,
+    PullResistor, Result, Speed, decode_pin,
+};
+use crate::logging::warn;
+use crate::mcu::device::{self, gpio, rcc};
+use alloc::boxed::Box;
+use core::ops::FnMut;
+use core::ptr;
+
+pub use crate::mcu::device::gpio::AfSignal;
+
+/// Configuration for a GPIO pin
+#[derive(Clone, Copy, Debug)]
+pub struct GpioConfig {
+    pub direction: Direction,
+    pub output_mode: OutputMode,
+    pub pull_resistor: PullResistor,
+    pub event_trigger: EventTrigger,
+    pub speed: Speed,
+    pub function: Function,
+}
+
+impl Default for GpioConfig {
+    fn default() -> Self {
+        Self {
+            direction: Direction::Input,
+            output_mode: OutputMode::PushPull,
+            pull_resistor: PullResistor::None,
+            event_trigger: EventTrigger::None,
+            speed: Speed::Medium,
+            function: Function::Gpio,
+        }
+    }
+}
+
+/// A GPIO driver for one STM32F103 port
+pub struct GpioDriver<'a> {
+    /// Port handled by this driver
+    port: Port,
+    /// Callbacks for each pin (indexed by pin number)
+    callbacks: [Option<Box<dyn FnMut(Pin, EventType) + 'a>>; 16],
+    /// Configuration for each pin
+    configs: [GpioConfig; 16],
+}
+
+impl<'a> GpioDriver<'a> {
+    pub fn new(port: Port) -> Self {
+        Self {
+            port,
+            callbacks: [
+                None, None, None, None, None, None, None, None, None, None, None, None, None, None,
+                None, None,
+            ],
+            configs: [GpioConfig::default(); 16],
+        }
+    }
+
+    /// Create a new GPIOA driver instance
+    pub fn new_gpioa() -> Self {
+        Self::new(Port::A)
+    }
+
+    /// Create a new GPIOB driver instance
+    pub fn new_gpiob() -> Self {
+        Self::new(Port::B)
+    }
+
+    /// Create a new GPIOC driver instance
+    pub fn new_gpioc() -> Self {
+        Self::new(Port::C)
+    }
+
+    /// Create a new GPIOD driver instance
+    pub fn new_gpiod() -> Self {
+        Self::new(Port::D)
+    }
+
+    /// Create a new GPIOE driver instance
+    pub fn new_gpioe() -> Self {
+        Self::new(Port::E)
+    }
+
+    /// Create a new GPIOF driver instance
+    pub fn new_gpiof() -> Self {
+        Self::new(Port::F)
+    }
+
+    /// Create a new GPIOG driver instance
+    pub fn new_gpiog() -> Self {
+        Self::new(Port::G)
+    }
+
+    /// Port handled by this driver
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    /// Decode a pin identifier and check that it belongs to this driver's port
+    fn pin_number(&self, pin: Pin) -> Result<u8> {
+        let (port, pin_num) = decode_pin(pin)?;
+        if port != self.port {
+            warn!("0x{:x} is not on this port", pin);
+            return Err(-1); // Pin is on another port
+        }
+        Ok(pin_num)
+    }
+
+    /// Modify the stored configuration of a pin and apply it to the hardware
+    fn update_config(&mut self, pin: Pin, f: impl FnOnce(&mut GpioConfig)) -> Result<()> {
+        let pin_num = self.pin_number(pin)?;
+        let mut config = self.configs[pin_num as usize];
+        f(&mut config);
+        configure_pin(pin, &config)?;
+        self.configs[pin_num as usize] = config;
+        Ok(())
+    }
+}
+
+impl<'a> Gpio<'a> for GpioDriver<'a> {
+    fn setup(&mut self, pin: Pin, callback: impl FnMut(Pin, EventType) + 'a) -> Result<()> {
+        let pin_num = self.pin_number(pin)?;
+
+        // Store callback
+        self.callbacks[pin_num as usize] = Some(Box::new(callback));
+
+        // Configure pin with default settings (input)
+        self.update_config(pin, |config| *config = GpioConfig::default())
+    }
+
+    fn set_direction(&mut self, pin: Pin, direction: Direction) -> Result<()> {
+        self.update_config(pin, |config| {
+            config.direction = direction;
+            config.function = Function::Gpio;
+        })
+    }
+
+    fn set_output_mode(&mut self, pin: Pin, mode: OutputMode) -> Result<()> {
+        self.update_config(pin, |config| config.output_mode = mode)
+    }
+
+    fn set_pull_resistor(&mut self, pin: Pin, resistor: PullResistor) -> Result<()> {
+        self.update_config(pin, |config| config.pull_resistor = resistor)
+    }
+
+    fn set_event_trigger(&mut self, pin: Pin, trigger: EventTrigger) -> Result<()> {
+        self.update_config(pin, |config| config.event_trigger = trigger)?;
+        crate::driver::exti::configure_pin(pin, trigger)
+    }
+
+    fn set_output(&mut self, pin: Pin, value: bool) {
+        if self.pin_number(pin).is_ok() {
+            write_pin(pin, value);
+        }
+    }
+
+    fn get_input(&self, pin: Pin) -> bool {
+        self.pin_number(pin).is_ok() && read_pin(pin)
+    }
+
+    fn set_speed(&mut self, pin: Pin, speed: Speed) -> Result<()> {
+        self.update_config(pin, |config| config.speed = speed)
+    }
+
+    fn set_alternate_function(&mut self, pin: Pin, af: u8) -> Result<()> {
+        if af != 0 {
+            warn!("alternate function {} out of range, only 0", af);
+            return Err(-1); // The F1 has no AF multiplexer, only AF0 is valid
+        }
+        self.update_config(pin, |config| config.function = Function::Alternate(af))
+    }
+
+    fn set_analog(&mut self, pin: Pin) -> Result<()> {
+        self.update_config(pin, |config| {
+            config.function = Function::Analog;
+            config.pull_resistor = PullResistor::None;
+        })
+    }
+
+    fn lock(&mut self, pin: Pin) -> Result<()> {
+        self.pin_number(pin)?;
+        lock_pin(pin)
+    }
+}
+
+/// Base address of a GPIO port. Only used in const context, where a port
+/// the part does not have becomes a compile error.
+const fn gpio_base(port: Port) -> u32 {
+    match gpio::port_base(port.index()) {
+        Some(base) => base,
+        None => panic!("GPIO port not available on this MCU"),
+    }
+}
+
+/// Get GPIO register block for a given port, if the part has that port
+fn get_gpio_regs(port: Port) -> Result<*mut gpio::RegisterBlock> {
+    gpio::port_base(port.index())
+        .map(|base| base as *mut gpio::RegisterBlock)
+        .ok_or(-1)
+}
+
+/// Enable peripheral clocks on APB2 (GPIO ports and AFIO)
+fn enable_apb2_clock(mask: u32) {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).apb2enr);
+        ptr::write_volatile(&mut (*rcc).apb2enr, v | mask);
+    }
+}
+
+/// Enable GPIO port clock
+fn enable_gpio_clock(port: Port) -> Result<()> {
+    let mask = match port {
+        Port::A => rcc::APB2ENR_IOPAEN_MASK,
+        Port::B => rcc::APB2ENR_IOPBEN_MASK,
+        Port::C => rcc::APB2ENR_IOPCEN_MASK,
+        Port::D => rcc::APB2ENR_IOPDEN_MASK,
+        Port::E => rcc::APB2ENR_IOPEEN_MASK,
+        Port::F => rcc::APB2ENR_IOPFEN_MASK,
+        Port::G => rcc::APB2ENR_IOPGEN_MASK,
+        _ => {
+            warn!("port not available on this MCU");
+            return Err(-1);
+        }
+    };
+    enable_apb2_clock(mask);
+    Ok(())
+}
+
+/// Update the AFIO remap bits selected by `mask`
+fn set_remap(mask: u32, value: u32) {
+    enable_apb2_clock(rcc::APB2ENR_AFIOEN_MASK);
+    let afio = device::AFIO_BASEADDR as *mut gpio::AfioRegisterBlock;
+    unsafe {
+        // SWJ_CFG reads back undefined; writing the reserved value 0b111
+        // leaves the debug port configuration unchanged
+        let v = ptr::read_volatile(&(*afio).mapr);
+        let v = (v & !mask) | (value & mask) | gpio::MAPR_SWJ_CFG_MASK;
+        ptr::write_volatile(&mut (*afio).mapr, v);
+    }
+}
+
+/// Disable the JTAG port but keep SWD, releasing PA15, PB3 and PB4
+pub fn disable_jtag() {
+    enable_apb2_clock(rcc::APB2ENR_AFIOEN_MASK);
+    let afio = device::AFIO_BASEADDR as *mut gpio::AfioRegisterBlock;
+    unsafe {
+        let v = ptr::read_volatile(&(*afio).mapr) & !gpio::MAPR_SWJ_CFG_MASK;
+        ptr::write_volatile(&mut (*afio).mapr, v | gpio::MAPR_SWJ_CFG_SWD_ONLY);
+    }
+}
+
+/// Configure a GPIO pin: enables the port clock and programs the CNF/MODE
+/// field of CRL/CRH. Pull-up/pull-down of inputs is selected through ODR.
+/// The F1 has no AF multiplexer, so `Function::Alternate` only accepts 0.
+pub fn configure_pin(pin: Pin, config: &GpioConfig) -> Result<()> {
+    let (port, pin_num) = decode_pin(pin)?;
+    let regs = get_gpio_regs(port)?;
+
+    if let Function::Alternate(af) = config.function {
+        if af != 0 {
+            warn!("alternate function {} out of range, only 0", af);
+            return Err(-1); // Invalid alternate function number
+        }
+    }
+
+    // Enable GPIO port clock
+    enable_gpio_clock(port)?;
+
+    // Output MODE encodes the maximum output speed
+    let output_mode = match config.speed {
+        Speed::Low => gpio::MODE_OUTPUT_2MHZ,
+        Speed::Medium => gpio::MODE_OUTPUT_10MHZ,
+        Speed::High | Speed::VeryHigh => gpio::MODE_OUTPUT_50MHZ,
+    };
+
+    let (cnf, mode) = match config.function {
+        Function::Analog => (gpio::CNF_INPUT_ANALOG, gpio::MODE_INPUT),
+        Function::Gpio if config.direction == Direction::Input => {
+            match config.pull_resistor {
+                PullResistor::None => (gpio::CNF_INPUT_FLOATING, gpio::MODE_INPUT),
+                PullResistor::PullUp | PullResistor::PullDown => {
+                    // Pull direction follows the ODR bit
+                    write_pin(pin, config.pull_resistor == PullResistor::PullUp);
+                    (gpio::CNF_INPUT_PULL, gpio::MODE_INPUT)
+                }
+            }
+        }
+        Function::Gpio => match config.output_mode {
+            OutputMode::PushPull => (gpio::CNF_OUTPUT_PUSHPULL, output_mode),
+            OutputMode::OpenDrain => (gpio::CNF_OUTPUT_OPENDRAIN, output_mode),
+        },
+        Function::Alternate(_) => match config.output_mode {
+            OutputMode::PushPull => (gpio::CNF_ALTERNATE_PUSHPULL, output_mode),
+            OutputMode::OpenDrain => (gpio::CNF_ALTERNATE_OPENDRAIN, output_mode),
+        },
+    };
+
+    // Pins 0-7 live in CRL, pins 8-15 in CRH
+    let (reg, shift) = unsafe {
+        if pin_num < 8 {
+            (
+                &mut (*regs).crl as *mut u32,
+                pin_num as u32 * gpio::CR_PIN_WIDTH,
+            )
+        } else {
+            (
+                &mut (*regs).crh as *mut u32,
+                (pin_num as u32 - 8) * gpio::CR_PIN_WIDTH,
+            )
+        }
+    };
+    unsafe {
+        let v = ptr::read_volatile(reg) & !(gpio::CR_PIN_MASK << shift);
+        ptr::write_volatile(reg, v | (((cnf << 2) | mode) << shift));
+    }
+
+    Ok(())
+}
+
+/// Route a peripheral signal to a pin, programming the AFIO remap the pin
+/// requires. Input signals (RX, CTS, MISO) become plain inputs with the
+/// given pull resistor. Returns an error if `signal` is not available on `pin`.
+pub fn configure_alternate(
+    pin: Pin,
+    signal: AfSignal,
+    output_mode: OutputMode,
+    pull_resistor: PullResistor,
+    speed: Speed,
+) -> Result<()> {
+    let (port, pin_num) = decode_pin(pin)?;
+    let mapping = gpio::find_remap(signal, port.index(), pin_num).ok_or(-1)?;
+    if mapping.remap_mask != 0 {
+        set_remap(mapping.remap_mask, mapping.remap);
+    }
+    let config = if signal.is_input() {
+        GpioConfig {
+            pull_resistor,
+            ..GpioConfig::default()
+        }
+    } else {
+        GpioConfig {
+            output_mode,
+            speed,
+            function: Function::Alternate(0),
+            ..GpioConfig::default()
+        }
+    };
+    configure_pin(pin, &config)
+}
+
+/// Drive an output pin high or low using BSRR
+pub fn write_pin(pin: Pin, value: bool) {
+    if let Ok((port, pin_num)) = decode_pin(pin) {
+        if let Ok(regs) = get_gpio_regs(port) {
+            let bit = if value {
+                1 << pin_num
+            } else {
+                1 << (pin_num + 16)
+            };
+            unsafe { ptr::write_volatile(&mut (*regs).bsrr, bit) };
+        }
+    }
+}
+
+/// Read the input level of a pin
+pub fn read_pin(pin: Pin) -> bool {
+    match decode_pin(pin).and_then(|(port, pin_num)| Ok((get_gpio_regs(port)?, pin_num))) {
+        Ok((regs, pin_num)) => {
+            let idr = unsafe { ptr::read_volatile(&(*regs).idr) };
+            (idr & (1 << pin_num)) != 0
+        }
+        Err(_) => false,
+    }
+}
+
+/// Lock the configuration of a pin until the next reset using the LCKR
+/// key sequence. Returns an error if the lock was not applied.
+pub fn lock_pin(pin: Pin) -> Result<()> {
+    let (port, pin_num) = decode_pin(pin)?;
+    let regs = get_gpio_regs(port)?;
+    unsafe {
+        let lckr = &mut (*regs).lckr as *mut u32;
+        let bits = (ptr::read_volatile(lckr) & gpio::LCKR_LCK_MASK) | (1 << pin_num);
+        // Write 1, write 0, write 1, read, then check LCKK
+        ptr::write_volatile(lckr, gpio::LCKR_LCKK_MASK | bits);
+        ptr::write_volatile(lckr, bits);
+        ptr::write_volatile(lckr, gpio::LCKR_LCKK_MASK | bits);
+        let _ = ptr::read_volatile(lckr);
+        if ptr::read_volatile(lckr) & gpio::LCKR_LCKK_MASK == 0 {
+            warn!("lock sequence failed");
+            return Err(-1); // Lock sequence failed
+        }
+    }
+    Ok(())
+}
+
+/// Register-level operations on typed pin handles. Addresses are constants,
+/// so each call compiles down to a single register access.
+impl<const P: char, const N: u8> GpioPin<P, N> {
+    const REGS: *mut gpio::RegisterBlock = gpio_base(Self::PORT) as *mut gpio::RegisterBlock;
+
+    /// Apply a full configuration to this pin
+    pub fn configure(&self, config: &GpioConfig) -> Result<()> {
+        configure_pin(Self::ID, config)
+    }
+
+    /// Configure this pin as a push-pull output
+    pub fn configure_as_output(&self) -> Result<()> {
+        self.configure(&GpioConfig {
+            direction: Direction::Output,
+            ..GpioConfig::default()
+        })
+    }
+
+    /// Configure this pin as an input with the given pull resistor
+    pub fn configure_as_input(&self, pull_resistor: PullResistor) -> Result<()> {
+        self.configure(&GpioConfig {
+            pull_resistor,
+            ..GpioConfig::default()
+        })
+    }
+
+    /// Configure this pin as an analog pin
+    pub fn configure_as_analog(&self) -> Result<()> {
+        self.configure(&GpioConfig {
+            function: Function::Analog,
+            ..GpioConfig::default()
+        })
+    }
+
+    /// Route a peripheral signal to this pin
+    pub fn configure_as_alternate(
+        &self,
+        signal: AfSignal,
+        output_mode: OutputMode,
+        pull_resistor: PullResistor,
+        speed: Speed,
+    ) -> Result<()> {
+        configure_alternate(Self::ID, signal, output_mode, pull_resistor, speed)
+    }
+
+    /// Drive the pin high
+    #[inline(always)]
+    pub fn set_high(&self) {
+        unsafe { ptr::write_volatile(&mut (*Self::REGS).bsrr, 1 << N) };
+    }
+
+    /// Drive the pin low
+    #[inline(always)]
+    pub fn set_low(&self) {
+        unsafe { ptr::write_volatile(&mut (*Self::REGS).bsrr, 1 << (N + 16)) };
+    }
+
+    /// Drive the pin to the given level
+    #[inline(always)]
+    pub fn set(&self, value: bool) {
+        if value {
+            self.set_high()
+        } else {
+            self.set_low()
+        }
+    }
+
+    /// Invert the output level of the pin
+    #[inline(always)]
+    pub fn toggle(&self) {
+        let odr = unsafe { ptr::read_volatile(&(*Self::REGS).odr) };
+        self.set(odr & (1 << N) == 0);
+    }
+
+    /// Read the input level of the pin
+    #[inline(always)]
+    pub fn is_high(&self) -> bool {
+        unsafe { ptr::read_volatile(&(*Self::REGS).idr) & (1 << N) != 0 }
+    }
+
+    /// Read the input level of the pin
+    #[inline(always)]
+    pub fn is_low(&self) -> bool {
+        !self.is_high()
+    }
+
+    /// Lock the configuration of this pin until the next reset
+    pub fn lock(&self) -> Result<()> {
+        lock_pin(Self::ID)
+    }
+}
+
+// Helper functions for creating pin identifiers
+pub mod pins {
+    use super::Pin;
+
+    // GPIOA pins
+    pub const PA0: Pin = 0x00;
+    pub const PA1: Pin = 0x01;
+    pub const PA2: Pin = 0x02;
+    pub const PA3: Pin = 0x03;
+    pub const PA4: Pin = 0x04;
+    pub const PA5: Pin = 0x05;
+    pub const PA6: Pin = 0x06;
+    pub const PA7: Pin = 0x07;
+    pub const PA8: Pin = 0x08;
+    pub const PA9: Pin = 0x09;
+    pub const PA10: Pin = 0x0A;
+    pub const PA11: Pin = 0x0B;
+    pub const PA12: Pin = 0x0C;
+    pub const PA13: Pin = 0x0D;
+    pub const PA14: Pin = 0x0E;
+    pub const PA15: Pin = 0x0F;
+
+    // GPIOB pins
+    pub const PB0: Pin = 0x10;
+    pub const PB1: Pin = 0x11;
+    pub const PB2: Pin = 0x12;
+    pub const PB3: Pin = 0x13;
+    pub const PB4: Pin = 0x14;
+    pub const PB5: Pin = 0x15;
+    pub const PB6: Pin = 0x16;
+    pub const PB7: Pin = 0x17;
+    pub const PB8: Pin = 0x18;
+    pub const PB9: Pin = 0x19;
+    pub const PB10: Pin = 0x1A;
+    pub const PB11: Pin = 0x1B;
+    pub const PB12: Pin = 0x1C;
+    pub const PB13: Pin = 0x1D;
+    pub const PB14: Pin = 0x1E;
+    pub const PB15: Pin = 0x1F;
+
+    // GPIOC pins
+    pub const PC0: Pin = 0x20;
+    pub const PC1: Pin = 0x21;
+    pub const PC2: Pin = 0x22;
+    pub const PC3: Pin = 0x23;
+    pub const PC4: Pin = 0x24;
+    pub const PC5: Pin = 0x25;
+    pub const PC6: Pin = 0x26;
+    pub const PC7: Pin = 0x27;
+    pub const PC8: Pin = 0x28;
+    pub const PC9: Pin = 0x29;
+    pub const PC10: Pin = 0x2A;
+    pub const PC11: Pin = 0x2B;
+    pub const PC12: Pin = 0x2C;
+    pub const PC13: Pin = 0x2D;
+    pub const PC14: Pin = 0x2E;
+    pub const PC15: Pin = 0x2F;
+
+    // GPIOD pins
+    pub const PD0: Pin = 0x30;
+    pub const PD1: Pin = 0x31;
+    pub const PD2: Pin = 0x32;
+    pub const PD3: Pin = 0x33;
+    pub const PD4: Pin = 0x34;
+    pub const PD5: Pin = 0x35;
+    pub const PD6: Pin = 0x36;
+    pub const PD7: Pin = 0x37;
+    pub const PD8: Pin = 0x38;
+    pub const PD9: Pin = 0x39;
+    pub const PD10: Pin = 0x3A;
+    pub const PD11: Pin = 0x3B;
+    pub const PD12: Pin = 0x3C;
+    pub const PD13: Pin = 0x3D;
+    pub const PD14: Pin = 0x3E;
+    pub const PD15: Pin = 0x3F;
+
+    // GPIOE pins
+    pub const PE0: Pin = 0x40;
+    pub const PE1: Pin = 0x41;
+    pub const PE2: Pin = 0x42;
+    pub const PE3: Pin = 0x43;
+    pub const PE4: Pin = 0x44;
+    pub const PE5: Pin = 0x45;
+    pub const PE6: Pin = 0x46;
+    pub const PE7: Pin = 0x47;
+    pub const PE8: Pin = 0x48;
+    pub const PE9: Pin = 0x49;
+    pub const PE10: Pin = 0x4A;
+    pub const PE11: Pin = 0x4B;
+    pub const PE12: Pin = 0x4C;
+    pub const PE13: Pin = 0x4D;
+    pub const PE14: Pin = 0x4E;
+    pub const PE15: Pin = 0x4F;
+
+    // GPIOF pins
+    pub const PF0: Pin = 0x50;
+    pub const PF1: Pin = 0x51;
+    pub const PF2: Pin = 0x52;
+    pub const PF3: Pin = 0x53;
+    pub const PF4: Pin = 0x54;
+    pub const PF5: Pin = 0x55;
+    pub const PF6: Pin = 0x56;
+    pub const PF7: Pin = 0x57;
+    pub const PF8: Pin = 0x58;
+    pub const PF9: Pin = 0x59;
+    pub const PF10: Pin = 0x5A;
+    pub const PF11: Pin = 0x5B;
+    pub const PF12: Pin = 0x5C;
+    pub const PF13: Pin = 0x5D;
+    pub const PF14: Pin = 0x5E;
+    pub const PF15: Pin = 0x5F;
+
+    // GPIOG pins
+    pub const PG0: Pin = 0x60;
+    pub const PG1: Pin = 0x61;
+    pub const PG2: Pin = 0x62;
+    pub const PG3: Pin = 0x63;
+    pub const PG4: Pin = 0x64;
+    pub const PG5: Pin = 0x65;
+    pub const PG6: Pin = 0x66;
+    pub const PG7: Pin = 0x67;
+    pub const PG8: Pin = 0x68;
+    pub const PG9: Pin = 0x69;
+    pub const PG10: Pin = 0x6A;
+    pub const PG11: Pin = 0x6B;
+    pub const PG12: Pin = 0x6C;
+    pub const PG13: Pin = 0x6D;
+    pub const PG14: Pin = 0x6E;
+    pub const PG15: Pin = 0x6F;
+}
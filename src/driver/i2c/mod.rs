@@ -8,8 +8,13 @@
 //! different STM32 families.
 #![allow(dead_code)]
 
+use crate::driver::gpio::{
+    Direction, GpioConfig, OutputMode, Pin, PullResistor, configure_pin, read_pin, write_pin,
+};
+use crate::logging::warn;
 use bitflags::bitflags;
 use core::ops::FnMut;
+use cortex_m::asm;
 
 /// Defines the possible bus speeds for I2C communication.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,6 +71,12 @@ bitflags! {
     }
 }
 
+/// Flag of a 10-bit slave address: `ADDRESS_10BIT | 0x2A5`
+pub const ADDRESS_10BIT: u32 = i2cbus::bus::ADDRESS_10BIT as u32;
+
+/// One part of a `transaction`: a write or a read
+pub use i2cbus::bus::Operation;
+
 /// A generic error type for the I2C driver, using i32 for error codes.
 pub type Error = i32;
 
@@ -86,26 +97,51 @@ pub trait I2c<'a> {
     ///
     /// # Arguments
     ///
-    /// * `addr` - 7-bit slave address.
+    /// * `addr` - 7-bit slave address, or 10-bit with `ADDRESS_10BIT`.
     /// * `data` - The data buffer to transmit.
-    /// * `xfer_pending` - If true, a STOP condition is not generated after the transfer,
-    ///                    allowing for a subsequent transfer to the same or different device.
+    /// * `xfer_pending` - If true, the bus is kept (no STOP) so the next call starts
+    ///                    with a repeated START, to the same or a different device.
+    ///
+    /// Returns Err(-1) if the target does not acknowledge its address or a
+    /// byte, Err(-3) on a bus error or lost arbitration. With empty `data`
+    /// only the address is sent, see `probe`.
     fn master_transmit(&mut self, addr: u32, data: &[u8], xfer_pending: bool) -> Result<()>;
 
     /// Receives data as an I2C master.
     ///
     /// # Arguments
     ///
-    /// * `addr` - 7-bit slave address.
+    /// * `addr` - 7-bit slave address, or 10-bit with `ADDRESS_10BIT`.
     /// * `data` - A mutable buffer to store the received data.
-    /// * `xfer_pending` - If true, a repeated START is generated after the transfer,
-    ///                    instead of a STOP condition.
+    /// * `xfer_pending` - If true, the bus is kept (no STOP) so the next call starts
+    ///                    with a repeated START.
+    ///
+    /// Returns Err(-1) if the target does not acknowledge its address,
+    /// Err(-3) on a bus error or lost arbitration.
     fn master_receive(&mut self, addr: u32, data: &mut [u8], xfer_pending: bool) -> Result<()>;
 
-    /// Transmits data as an I2C slave.
+    /// Runs `operations` on the slave at `addr` as one transaction: a
+    /// repeated START between operations and STOP after the last, so a
+    /// register number written is still selected for the read after it.
+    /// Errors as `master_transmit` and `master_receive`.
+    fn transaction(&mut self, addr: u32, operations: &mut [Operation<'_>]) -> Result<()> {
+        let count = operations.len();
+        for (i, operation) in operations.iter_mut().enumerate() {
+            let xfer_pending = i + 1 < count;
+            match operation {
+                Operation::Write(data) => self.master_transmit(addr, data, xfer_pending)?,
+                Operation::Read(data) => self.master_receive(addr, data, xfer_pending)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Transmits data as an I2C slave. Returns Err(-2) if the driver has
+    /// no slave mode.
     fn slave_transmit(&mut self, data: &[u8]) -> Result<()>;
 
-    /// Receives data as an I2C slave.
+    /// Receives data as an I2C slave. Returns Err(-2) if the driver has no
+    /// slave mode.
     fn slave_receive(&mut self, data: &mut [u8]) -> Result<()>;
 
     /// Gets the number of bytes transferred in the last transaction.
@@ -117,7 +153,10 @@ pub trait I2c<'a> {
     /// Sets the slave address for the I2C peripheral when in slave mode.
     fn set_own_address(&mut self, address: u32) -> Result<()>;
 
-    /// Initiates a bus clear operation.
+    /// Frees a bus that a slave holds low by clocking SCL until it lets SDA
+    /// go, then sends a STOP and notifies `Event::BUS_CLEARED`. Returns
+    /// Err(-1) if the pins were not set with `configure_pins`, Err(-3) if
+    /// the bus stays held.
     fn clear_bus(&mut self) -> Result<()>;
 
     /// Aborts an ongoing I2C transfer.
@@ -127,14 +166,93 @@ pub trait I2c<'a> {
     fn get_status(&self) -> Status;
 }
 
-#[cfg(feature = "stm32f407")]
-pub mod stm32f407;
+/// Interrupt-driven counterpart of the master transfers of `I2c`: the task
+/// sleeps on the I2C event interrupt instead of polling the flags. Needs a
+/// driver initialized through `I2c`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncI2c {
+    /// Transmits data as an I2C master, see `I2c::master_transmit`.
+    async fn master_transmit_async(
+        &mut self,
+        addr: u32,
+        data: &[u8],
+        xfer_pending: bool,
+    ) -> Result<()>;
+
+    /// Receives data as an I2C master, see `I2c::master_receive`.
+    async fn master_receive_async(
+        &mut self,
+        addr: u32,
+        data: &mut [u8],
+        xfer_pending: bool,
+    ) -> Result<()>;
+}
+
+/// Cycles of half a clock period of the bus recovery: 5 µs at a 200 MHz
+/// core clock, longer at slower ones, which a slave does not mind. A busy
+/// loop, so it does not need the SysTick interrupt.
+const RECOVERY_HALF_PERIOD_CYCLES: u32 = 1_000;
+
+/// Free a bus held by a slave stuck in the middle of a byte (UM10204,
+/// 3.1.16 "Bus clear"): SCL and SDA are taken over as open-drain GPIOs, SCL
+/// is clocked up to nine times until the slave lets SDA go, then a STOP is
+/// sent. The pins are left as GPIOs, for the driver to route back to the
+/// peripheral. Returns Err(-3) if SCL or SDA is still held low.
+fn recover_bus(scl: Pin, sda: Pin) -> Result<()> {
+    let output = GpioConfig {
+        direction: Direction::Output,
+        output_mode: OutputMode::OpenDrain,
+        pull_resistor: PullResistor::PullUp,
+        ..GpioConfig::default()
+    };
+    write_pin(scl, true);
+    write_pin(sda, true);
+    configure_pin(scl, &output)?;
+    configure_pin(sda, &output)?;
+    asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+
+    for _ in 0..9 {
+        if read_pin(sda) {
+            break;
+        }
+        write_pin(scl, false);
+        asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+        write_pin(scl, true);
+        asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+    }
+
+    // STOP: SDA rises while SCL is high
+    write_pin(scl, false);
+    asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+    write_pin(sda, false);
+    asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+    write_pin(scl, true);
+    asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+    write_pin(sda, true);
+    asm::delay(RECOVERY_HALF_PERIOD_CYCLES);
+
+    if !read_pin(scl) || !read_pin(sda) {
+        warn!("bus still held low after recovery");
+        return Err(-3);
+    }
+    Ok(())
+}
+
+/// Bus scan and known device identification over any `I2c`
+pub mod probe;
 
-#[cfg(feature = "stm32f401")]
-pub mod stm32f401;
+/// SMBus and PMBus over any `I2c`
+pub mod smbus;
 
-#[cfg(feature = "stm32f411")]
-pub mod stm32f411;
+/// I2C v1 IP, shared by the STM32F1 and STM32F4 families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub mod v1;
 
-#[cfg(feature = "stm32f103")]
-pub mod stm32f103;
+/// I2C v2 IP (TIMINGR, NBYTES/AUTOEND transfers) of the STM32G0 family
+#[cfg(feature = "stm32g030")]
+pub mod v2;
@@ -0,0 +1,48 @@
+//! # I2C Probe
+//!
+//! Finds out what is on a bus: whether a target acknowledges its address,
+//! which addresses do, and which known device answers at one. The address
+//! map and the device table are in the host-tested `i2cbus` component.
+//!
+//! ```ignore
+//! let found = probe::scan(&mut i2c)?;
+//! for address in found.iter() {
+//!     let name = probe::identify(&mut i2c, address).map_or("?", |d| d.name);
+//!     info!("0x{:x}: {}", address, name);
+//! }
+//! ```
+
+use super::{I2c, Operation, Result};
+use i2cbus::scan;
+
+pub use i2cbus::device::Device;
+pub use i2cbus::scan::{AddressMap, Response};
+
+/// Address `address` with an address-only write. Returns Err(-1) for a
+/// reserved address and Err(-3) on a bus fault.
+pub fn probe<'a>(bus: &mut impl I2c<'a>, address: u8) -> Result<Response> {
+    if scan::is_reserved(address) {
+        return Err(-1);
+    }
+    scan::classify(bus.master_transmit(address as u32, &[], false))
+}
+
+/// Probe every non-reserved address. Returns Err(-3) on a bus fault.
+pub fn scan<'a>(bus: &mut impl I2c<'a>) -> Result<AddressMap> {
+    scan::scan(|address| bus.master_transmit(address as u32, &[], false))
+}
+
+/// Read one register: write its number, then read with a repeated START
+pub fn read_register<'a>(bus: &mut impl I2c<'a>, address: u8, register: u8) -> Result<u8> {
+    let mut value = [0];
+    bus.transaction(
+        address as u32,
+        &mut [Operation::Write(&[register]), Operation::Read(&mut value)],
+    )?;
+    Ok(value[0])
+}
+
+/// Name the known device at `address` by its registers, or None
+pub fn identify<'a>(bus: &mut impl I2c<'a>, address: u8) -> Option<&'static Device> {
+    i2cbus::device::identify(address, |register| read_register(bus, address, register))
+}
@@ -0,0 +1,29 @@
+//! # SMBus and PMBus
+//!
+//! The SMBus protocols and PMBus helpers of the host-tested `i2cbus`
+//! component, run on an `I2c` driver through `Adapter`.
+//!
+//! ```ignore
+//! let mut bus = Adapter(&mut i2c);
+//! let mut psu = Smbus::new(&mut bus, 0x40).with_pec(true);
+//! pmbus::set_page(&mut psu, 0)?;
+//! let volts = pmbus::read_vout(&mut psu)?;
+//! let status = pmbus::status_word(&mut psu)?;
+//! ```
+
+use super::{I2c, Operation};
+
+#[allow(unused_imports)]
+pub use i2cbus::{
+    pmbus,
+    smbus::{MAX_BLOCK, Smbus},
+};
+
+/// An `I2c` driver as the bus of `Smbus`
+pub struct Adapter<'b, I>(pub &'b mut I);
+
+impl<'a, I: I2c<'a>> i2cbus::bus::Bus for Adapter<'_, I> {
+    fn transaction(&mut self, address: u16, operations: &mut [Operation<'_>]) -> Result<(), i32> {
+        self.0.transaction(address as u32, operations)
+    }
+}
@@ -1,2 +0,0 @@
-// I2C driver implementation for STM32F103
-// TODO: Implement I2C driver functions
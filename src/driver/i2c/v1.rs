@@ -0,0 +1,583 @@
+extern crate alloc;
+use super::{AsyncI2c, BusSpeed, Event, I2c, Result, Status, recover_bus};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::{debug, warn};
+use crate::mcu::device::{self, i2c::*};
+use crate::runtime::irq;
+use crate::utils;
+use alloc::boxed::Box;
+use core::ops::FnMut;
+use core::ptr;
+use i2cbus::bus;
+
+/// The APB1 peripheral clock frequency in Hz.
+/// This value is used for timing calculations in the I2C peripheral.
+///
+/// # Note
+/// This should be updated to match the actual clock configuration of your MCU.
+const PCLK1_HZ: u32 = device::HSI_HZ;
+
+/// Polls of SR1 before a transfer counts as stuck: tens of ms at 16 MHz,
+/// well past the clock stretching of a slow slave
+const TIMEOUT: u32 = 100_000;
+
+/// Configuration for the I2C driver.
+#[derive(Clone, Copy)]
+pub struct I2cConfig {
+    pub bus_speed: BusSpeed,
+    pub own_address: u32,
+}
+
+impl Default for I2cConfig {
+    fn default() -> Self {
+        Self {
+            bus_speed: BusSpeed::Standard,
+            own_address: 0,
+        }
+    }
+}
+
+impl Default for BusSpeed {
+    fn default() -> Self {
+        BusSpeed::Standard
+    }
+}
+
+/// `addr` as a 7-bit or flagged 10-bit address, Err(-1) if it is neither
+fn valid_address(addr: u32) -> Result<u16> {
+    u16::try_from(addr)
+        .ok()
+        .filter(|&addr| bus::is_valid(addr))
+        .ok_or(-1)
+}
+
+/// A polling-based I2C driver for the STM32F1/F4 (v1 IP).
+pub struct I2cDriver<'a> {
+    regs: *mut RegisterBlock,
+    _callback: Option<Box<dyn FnMut(Event) + 'a>>,
+    config: I2cConfig,
+    data_count: u32,
+    /// SCL and SDA, once routed by `configure_pins`
+    pins: Option<(Pin, Pin)>,
+}
+
+impl<'a> I2cDriver<'a> {
+    pub fn new(i2c_base_addr: u32, config: I2cConfig) -> Self {
+        Self {
+            regs: i2c_base_addr as *mut RegisterBlock,
+            _callback: None,
+            config,
+            data_count: 0,
+            pins: None,
+        }
+    }
+
+    /// Create a new I2C1 driver instance
+    pub fn new_i2c1(config: I2cConfig) -> Self {
+        Self::new(device::I2C1_BASEADDR, config)
+    }
+
+    /// Create a new I2C2 driver instance
+    pub fn new_i2c2(config: I2cConfig) -> Self {
+        Self::new(device::I2C2_BASEADDR, config)
+    }
+
+    /// Create a new I2C3 driver instance
+    #[cfg(not(feature = "stm32f103"))]
+    pub fn new_i2c3(config: I2cConfig) -> Self {
+        Self::new(device::I2C3_BASEADDR, config)
+    }
+
+    /// Route SCL and SDA of this I2C to the given pins using the alternate
+    /// function map. Pins are configured open-drain with internal pull-ups;
+    /// external pull-ups are still recommended for fast mode.
+    pub fn configure_pins(&mut self, scl: impl Into<Pin>, sda: impl Into<Pin>) -> Result<()> {
+        let (scl_signal, sda_signal) = match self.regs as u32 {
+            device::I2C1_BASEADDR => (AfSignal::I2C1_SCL, AfSignal::I2C1_SDA),
+            device::I2C2_BASEADDR => (AfSignal::I2C2_SCL, AfSignal::I2C2_SDA),
+            #[cfg(not(feature = "stm32f103"))]
+            device::I2C3_BASEADDR => (AfSignal::I2C3_SCL, AfSignal::I2C3_SDA),
+            _ => return Err(-1),
+        };
+        let (scl, sda) = (scl.into(), sda.into());
+        self.pins = Some((scl, sda));
+        configure_alternate(
+            scl,
+            scl_signal,
+            OutputMode::OpenDrain,
+            PullResistor::PullUp,
+            Speed::High,
+        )?;
+        configure_alternate(
+            sda,
+            sda_signal,
+            OutputMode::OpenDrain,
+            PullResistor::PullUp,
+            Speed::High,
+        )
+    }
+
+    /// NVIC event and error interrupt lines of this instance
+    fn irqns(&self) -> Option<[u32; 2]> {
+        let (ev, er) = match self.regs as u32 {
+            device::I2C1_BASEADDR => (device::IRQn::I2C1_EV, device::IRQn::I2C1_ER),
+            device::I2C2_BASEADDR => (device::IRQn::I2C2_EV, device::IRQn::I2C2_ER),
+            #[cfg(not(feature = "stm32f103"))]
+            device::I2C3_BASEADDR => (device::IRQn::I2C3_EV, device::IRQn::I2C3_ER),
+            _ => return None,
+        };
+        Some([ev as u32, er as u32])
+    }
+
+    fn regs(&self) -> &mut RegisterBlock {
+        unsafe { &mut *self.regs }
+    }
+
+    fn generate_start_condition(&mut self) {
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_START_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+    }
+
+    fn generate_stop_condition(&mut self) {
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_STOP_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+    }
+
+    fn write_dr(&mut self, byte: u8) {
+        unsafe { ptr::write_volatile(&mut self.regs().dr, byte as u32) };
+    }
+
+    /// START and the address phase, until ADDR is set. A 10-bit address
+    /// goes out as header and low byte; a read then turns the direction
+    /// around with a repeated START and the header alone.
+    fn address_phase(&mut self, addr: u32, read: bool) -> Result<()> {
+        let addr = valid_address(addr)?;
+        self.generate_start_condition();
+        self.wait_flag(SR1_SB_MASK)?;
+        if !bus::is_ten_bit(addr) {
+            self.write_dr(bus::address_byte(addr as u8, read));
+            return self.wait_flag(SR1_ADDR_MASK);
+        }
+        self.write_dr(bus::header(addr, false));
+        self.wait_flag(SR1_ADD10_MASK)?;
+        self.write_dr(bus::low_byte(addr));
+        self.wait_flag(SR1_ADDR_MASK)?;
+        if read {
+            self.clear_addr_flag();
+            self.generate_start_condition();
+            self.wait_flag(SR1_SB_MASK)?;
+            self.write_dr(bus::header(addr, true));
+            self.wait_flag(SR1_ADDR_MASK)?;
+        }
+        Ok(())
+    }
+
+    /// `address_phase` sleeping on the interrupts
+    async fn address_phase_async(&mut self, addr: u32, read: bool) -> Result<()> {
+        let addr = valid_address(addr)?;
+        self.generate_start_condition();
+        self.wait_flag_async(SR1_SB_MASK).await?;
+        if !bus::is_ten_bit(addr) {
+            self.write_dr(bus::address_byte(addr as u8, read));
+            return self.wait_flag_async(SR1_ADDR_MASK).await;
+        }
+        self.write_dr(bus::header(addr, false));
+        self.wait_flag_async(SR1_ADD10_MASK).await?;
+        self.write_dr(bus::low_byte(addr));
+        self.wait_flag_async(SR1_ADDR_MASK).await?;
+        if read {
+            self.clear_addr_flag();
+            self.generate_start_condition();
+            self.wait_flag_async(SR1_SB_MASK).await?;
+            self.write_dr(bus::header(addr, true));
+            self.wait_flag_async(SR1_ADDR_MASK).await?;
+        }
+        Ok(())
+    }
+
+    /// Program CR1, CR2, OAR1, CCR and TRISE from `config` and enable the
+    /// peripheral.
+    fn configure(&mut self) -> Result<()> {
+        // Note: The peripheral clock must be enabled before calling this function.
+
+        // Disable peripheral for configuration.
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_PE_POS, false);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+
+        // Configure CR1: Ack control - always enable for master.
+        cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_ACK_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+
+        // Configure CR2: Peripheral clock frequency.
+        let mut cr2 = unsafe { ptr::read_volatile(&self.regs().cr2) };
+        let freq_mhz = PCLK1_HZ / 1_000_000;
+        cr2 = utils::set_bits(cr2, freq_mhz, 0, 6);
+        unsafe { ptr::write_volatile(&mut self.regs().cr2, cr2) };
+
+        // Configure OAR1: Own address.
+        let mut oar1 = unsafe { ptr::read_volatile(&self.regs().oar1) };
+        oar1 = utils::set_bits(oar1, self.config.own_address, 1, 7);
+        oar1 = utils::set_bit(oar1, 14, true); // This bit must be kept at 1.
+        unsafe { ptr::write_volatile(&mut self.regs().oar1, oar1) };
+
+        // Configure CCR: Clock control register.
+        let ccr_val;
+        let mut ccr_reg = 0;
+        match self.config.bus_speed {
+            BusSpeed::Standard => {
+                ccr_val = PCLK1_HZ / (2 * 100_000); // 100kHz
+            }
+            BusSpeed::Fast => {
+                ccr_reg = utils::set_bit(ccr_reg, 15, true); // F/S mode
+                // I2C_FM_DUTY_2 = 0
+                ccr_val = PCLK1_HZ / (3 * 400_000); // 400kHz
+            }
+            _ => return Err(-1),
+        }
+        ccr_reg |= ccr_val & 0xFFF;
+        unsafe { ptr::write_volatile(&mut self.regs().ccr, ccr_reg) };
+
+        // Configure TRISE: Rise time.
+        let trise_val = match self.config.bus_speed {
+            BusSpeed::Standard => (PCLK1_HZ / 1_000_000) + 1,
+            BusSpeed::Fast => (PCLK1_HZ / 1_000_000 * 300 / 1000) + 1,
+            _ => return Err(-1),
+        };
+        unsafe { ptr::write_volatile(&mut self.regs().trise, trise_val & 0x3F) };
+
+        // Enable the peripheral.
+        cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_PE_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+
+        Ok(())
+    }
+
+    fn notify(&mut self, event: Event) {
+        if let Some(cb) = &mut self._callback {
+            cb(event);
+        }
+    }
+
+    /// Clear the error flags in `mask`; they are cleared by writing 0 and
+    /// the other SR1 bits ignore writes.
+    fn clear_error_flags(&mut self, mask: u32) {
+        unsafe { ptr::write_volatile(&mut self.regs().sr1, !mask & 0xFFFF) };
+    }
+
+    /// Wait for `flag` in SR1. A NACK (AF) ends the transfer: STOP is
+    /// generated, AF cleared and Err(-1) returned. A bus error or lost
+    /// arbitration is cleared and returns Err(-3); after a bus error STOP
+    /// is generated, after lost arbitration the peripheral has already
+    /// left the bus. A flag that does not come within `TIMEOUT` polls, as
+    /// when a slave holds SCL or SDA low, is a bus error too.
+    fn wait_flag(&mut self, flag: u32) -> Result<()> {
+        for _ in 0..TIMEOUT {
+            let sr1 = unsafe { ptr::read_volatile(&self.regs().sr1) };
+            if sr1 & SR1_AF_MASK != 0 {
+                self.generate_stop_condition();
+                self.clear_error_flags(SR1_AF_MASK);
+                if flag & (SR1_ADD10_MASK | SR1_ADDR_MASK) != 0 {
+                    debug!("address not acknowledged");
+                    self.notify(Event::ADDRESS_NACK);
+                } else {
+                    debug!("data not acknowledged");
+                    self.notify(Event::TRANSFER_INCOMPLETE);
+                }
+                return Err(-1);
+            }
+            if sr1 & SR1_ARLO_MASK != 0 {
+                self.clear_error_flags(SR1_ARLO_MASK);
+                warn!("arbitration lost");
+                self.notify(Event::ARBITRATION_LOST);
+                return Err(-3);
+            }
+            if sr1 & SR1_BERR_MASK != 0 {
+                self.generate_stop_condition();
+                self.clear_error_flags(SR1_BERR_MASK);
+                warn!("bus error");
+                self.notify(Event::BUS_ERROR);
+                return Err(-3);
+            }
+            if sr1 & flag != 0 {
+                return Ok(());
+            }
+        }
+        warn!("timed out waiting for SR1 0x{:x}", flag);
+        self.generate_stop_condition();
+        self.notify(Event::BUS_ERROR);
+        Err(-3)
+    }
+
+    fn clear_addr_flag(&mut self) {
+        let _ = unsafe { ptr::read_volatile(&self.regs().sr1) };
+        let _ = unsafe { ptr::read_volatile(&self.regs().sr2) };
+    }
+
+    fn manage_acking(&mut self, enable: bool) {
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_ACK_POS, enable);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+    }
+
+    /// Data phase of `master_receive`, after the address: ACK is turned off
+    /// before the last byte so the slave lets go of the bus.
+    fn receive_data(&mut self, data: &mut [u8], xfer_pending: bool) -> Result<()> {
+        let len = data.len();
+        if len == 1 {
+            self.manage_acking(false);
+            self.clear_addr_flag();
+            if !xfer_pending {
+                self.generate_stop_condition();
+            }
+            self.wait_flag(SR1_RXNE_MASK)?;
+            data[0] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.data_count = 1;
+        } else {
+            self.clear_addr_flag();
+            for i in (1..=len).rev() {
+                self.wait_flag(SR1_RXNE_MASK)?;
+                if i == 2 {
+                    self.manage_acking(false);
+                    if !xfer_pending {
+                        self.generate_stop_condition();
+                    }
+                }
+                data[len - i] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+                self.data_count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Data phase of `master_receive_async`, see `receive_data`.
+    async fn receive_data_async(&mut self, data: &mut [u8], xfer_pending: bool) -> Result<()> {
+        let len = data.len();
+        if len == 1 {
+            self.manage_acking(false);
+            self.clear_addr_flag();
+            if !xfer_pending {
+                self.generate_stop_condition();
+            }
+            self.wait_flag_async(SR1_RXNE_MASK).await?;
+            data[0] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.data_count = 1;
+        } else {
+            self.clear_addr_flag();
+            for i in (1..=len).rev() {
+                self.wait_flag_async(SR1_RXNE_MASK).await?;
+                if i == 2 {
+                    self.manage_acking(false);
+                    if !xfer_pending {
+                        self.generate_stop_condition();
+                    }
+                }
+                data[len - i] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+                self.data_count += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sleep until `flag` or an error is set in SR1, then handle it like
+    /// `wait_flag`. SB, ADDR and BTF raise the event interrupt, TXE and
+    /// RXNE also need the buffer interrupt; errors raise the separate
+    /// error interrupt.
+    async fn wait_flag_async(&mut self, flag: u32) -> Result<()> {
+        let irqns = self.irqns().ok_or(-1)?;
+        let irq_mask = if flag & (SR1_TXE_MASK | SR1_RXNE_MASK) != 0 {
+            CR2_ITEVTEN_MASK | CR2_ITBUFEN_MASK | CR2_ITERREN_MASK
+        } else {
+            CR2_ITEVTEN_MASK | CR2_ITERREN_MASK
+        };
+        let errors = SR1_AF_MASK | SR1_ARLO_MASK | SR1_BERR_MASK;
+        let regs = self.regs;
+        irq::wait_for_any(
+            &irqns,
+            || unsafe { ptr::read_volatile(&(*regs).sr1) } & (flag | errors) != 0,
+            |enable| unsafe {
+                let cr2 = ptr::read_volatile(&(*regs).cr2);
+                let cr2 = if enable {
+                    cr2 | irq_mask
+                } else {
+                    cr2 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr2, cr2);
+            },
+        )
+        .await;
+        self.wait_flag(flag)
+    }
+}
+
+impl<'a> I2c<'a> for I2cDriver<'a> {
+    fn initialize(&mut self, callback: impl FnMut(Event) + 'a) -> Result<()> {
+        self._callback = Some(Box::new(callback));
+        self.configure()
+    }
+
+    fn uninitialize(&mut self) -> Result<()> {
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_PE_POS, false);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+        self._callback = None;
+        Ok(())
+    }
+
+    fn master_transmit(&mut self, addr: u32, data: &[u8], xfer_pending: bool) -> Result<()> {
+        self.data_count = 0;
+        self.address_phase(addr, false)?;
+        self.clear_addr_flag();
+
+        for byte in data {
+            self.wait_flag(SR1_TXE_MASK)?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, *byte as u32) };
+            self.data_count += 1;
+        }
+
+        // BTF never sets for an address-only write
+        if !data.is_empty() {
+            self.wait_flag(SR1_TXE_MASK)?;
+            self.wait_flag(SR1_BTF_MASK)?;
+        }
+
+        if !xfer_pending {
+            self.generate_stop_condition();
+        }
+        Ok(())
+    }
+
+    fn master_receive(&mut self, addr: u32, data: &mut [u8], xfer_pending: bool) -> Result<()> {
+        self.data_count = 0;
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        self.address_phase(addr, true)?;
+        // ACK is turned off for the last byte; it is turned back on even
+        // when the transfer fails partway, or the next receive NACKs its
+        // first byte
+        let result = self.receive_data(data, xfer_pending);
+        self.manage_acking(true);
+        result
+    }
+
+    /// Slave mode is not supported: returns Err(-2).
+    fn slave_transmit(&mut self, _data: &[u8]) -> Result<()> {
+        Err(-2)
+    }
+
+    /// Slave mode is not supported: returns Err(-2).
+    fn slave_receive(&mut self, _data: &mut [u8]) -> Result<()> {
+        Err(-2)
+    }
+
+    fn get_data_count(&self) -> Result<u32> {
+        Ok(self.data_count)
+    }
+
+    fn set_bus_speed(&mut self, speed: BusSpeed) -> Result<()> {
+        if !matches!(speed, BusSpeed::Standard | BusSpeed::Fast) {
+            return Err(-1);
+        }
+        self.config.bus_speed = speed;
+        self.configure()
+    }
+
+    fn set_own_address(&mut self, address: u32) -> Result<()> {
+        if address > 0x7F {
+            return Err(-1);
+        }
+        self.config.own_address = address;
+        self.configure()
+    }
+
+    fn clear_bus(&mut self) -> Result<()> {
+        let (scl, sda) = self.pins.ok_or(-1)?;
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_PE_POS, false);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+        let recovered = recover_bus(scl, sda);
+        self.configure_pins(scl, sda)?;
+
+        // BUSY stays set after the lines were held; only SWRST clears it
+        unsafe {
+            ptr::write_volatile(&mut self.regs().cr1, CR1_SWRST_MASK);
+            ptr::write_volatile(&mut self.regs().cr1, 0);
+        }
+        self.configure()?;
+        recovered?;
+        debug!("bus cleared");
+        self.notify(Event::BUS_CLEARED);
+        Ok(())
+    }
+
+    fn abort_transfer(&mut self) -> Result<()> {
+        self.generate_stop_condition();
+        Ok(())
+    }
+
+    fn get_status(&self) -> Status {
+        let sr1 = unsafe { ptr::read_volatile(&self.regs().sr1) };
+        let sr2 = unsafe { ptr::read_volatile(&self.regs().sr2) };
+
+        Status {
+            busy: utils::read_bit(sr2, SR2_BUSY_POS),
+            master_mode: utils::read_bit(sr2, SR2_MSL_POS),
+            receiving: !utils::read_bit(sr2, SR2_TRA_POS),
+            general_call: utils::read_bit(sr2, SR2_GENCALL_POS),
+            arbitration_lost: utils::read_bit(sr1, SR1_ARLO_POS),
+            bus_error: utils::read_bit(sr1, SR1_BERR_POS),
+        }
+    }
+}
+
+impl AsyncI2c for I2cDriver<'_> {
+    async fn master_transmit_async(
+        &mut self,
+        addr: u32,
+        data: &[u8],
+        xfer_pending: bool,
+    ) -> Result<()> {
+        self.data_count = 0;
+        self.address_phase_async(addr, false).await?;
+        self.clear_addr_flag();
+
+        for byte in data {
+            self.wait_flag_async(SR1_TXE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, *byte as u32) };
+            self.data_count += 1;
+        }
+
+        if !data.is_empty() {
+            self.wait_flag_async(SR1_TXE_MASK).await?;
+            self.wait_flag_async(SR1_BTF_MASK).await?;
+        }
+
+        if !xfer_pending {
+            self.generate_stop_condition();
+        }
+        Ok(())
+    }
+
+    async fn master_receive_async(
+        &mut self,
+        addr: u32,
+        data: &mut [u8],
+        xfer_pending: bool,
+    ) -> Result<()> {
+        self.data_count = 0;
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        self.address_phase_async(addr, true).await?;
+        let result = self.receive_data_async(data, xfer_pending).await;
+        self.manage_acking(true);
+        result
+    }
+}
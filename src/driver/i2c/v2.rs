@@ -0,0 +1,527 @@
+extern crate alloc;
+use super::{ADDRESS_10BIT, AsyncI2c, BusSpeed, Event, I2c, Result, Status, recover_bus};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::{debug, warn};
+use crate::mcu::device::{self, i2c::*};
+use crate::runtime::irq;
+use crate::utils;
+use alloc::boxed::Box;
+use core::ops::FnMut;
+use core::ptr;
+
+/// The I2C kernel clock frequency in Hz (PCLK after reset).
+/// This value selects the TIMINGR presets below.
+///
+/// # Note
+/// This should be updated to match the actual clock configuration of your MCU.
+const I2CCLK_HZ: u32 = device::HSI_HZ;
+
+/// Largest NBYTES value, longer transfers are split with RELOAD
+const MAX_NBYTES: usize = 255;
+
+/// Polls of ISR before a transfer counts as stuck: tens of ms at 16 MHz,
+/// well past the clock stretching of a slow slave
+const TIMEOUT: u32 = 100_000;
+
+/// Configuration for the I2C driver.
+#[derive(Clone, Copy)]
+pub struct I2cConfig {
+    pub bus_speed: BusSpeed,
+    pub own_address: u32,
+}
+
+impl Default for I2cConfig {
+    fn default() -> Self {
+        Self {
+            bus_speed: BusSpeed::Standard,
+            own_address: 0,
+        }
+    }
+}
+
+impl Default for BusSpeed {
+    fn default() -> Self {
+        BusSpeed::Standard
+    }
+}
+
+/// TIMINGR value for a bus speed at a 16 MHz kernel clock (RM0444, I2C timing
+/// settings table). Other kernel clocks need values from the reference
+/// manual or STM32CubeMX.
+fn timing(speed: BusSpeed) -> Result<u32> {
+    if I2CCLK_HZ != 16_000_000 {
+        return Err(-2);
+    }
+    match speed {
+        BusSpeed::Standard => Ok(0x3042_0F13), // PRESC=3 SCLDEL=4 SDADEL=2 SCLH=0x0F SCLL=0x13
+        BusSpeed::Fast => Ok(0x1032_0309),     // PRESC=1 SCLDEL=3 SDADEL=2 SCLH=0x03 SCLL=0x09
+        BusSpeed::FastPlus => Ok(0x0020_0204), // PRESC=0 SCLDEL=2 SDADEL=0 SCLH=0x02 SCLL=0x04
+        BusSpeed::High => Err(-2),
+    }
+}
+
+/// A polling-based I2C driver for the STM32G0 (v2 IP).
+pub struct I2cDriver<'a> {
+    regs: *mut RegisterBlock,
+    _callback: Option<Box<dyn FnMut(Event) + 'a>>,
+    config: I2cConfig,
+    data_count: u32,
+    /// Set once the slave acknowledged its address, so a NACK after that
+    /// ends the data phase
+    addressed: bool,
+    /// SCL and SDA, once routed by `configure_pins`
+    pins: Option<(Pin, Pin)>,
+}
+
+impl<'a> I2cDriver<'a> {
+    pub fn new(i2c_base_addr: u32, config: I2cConfig) -> Self {
+        Self {
+            regs: i2c_base_addr as *mut RegisterBlock,
+            _callback: None,
+            config,
+            data_count: 0,
+            addressed: false,
+            pins: None,
+        }
+    }
+
+    /// Create a new I2C1 driver instance
+    pub fn new_i2c1(config: I2cConfig) -> Self {
+        Self::new(device::I2C1_BASEADDR, config)
+    }
+
+    /// Create a new I2C2 driver instance
+    pub fn new_i2c2(config: I2cConfig) -> Self {
+        Self::new(device::I2C2_BASEADDR, config)
+    }
+
+    /// Route SCL and SDA of this I2C to the given pins using the alternate
+    /// function map. Pins are configured open-drain with internal pull-ups;
+    /// external pull-ups are still recommended for fast mode.
+    pub fn configure_pins(&mut self, scl: impl Into<Pin>, sda: impl Into<Pin>) -> Result<()> {
+        let (scl_signal, sda_signal) = match self.regs as u32 {
+            device::I2C1_BASEADDR => (AfSignal::I2C1_SCL, AfSignal::I2C1_SDA),
+            device::I2C2_BASEADDR => (AfSignal::I2C2_SCL, AfSignal::I2C2_SDA),
+            _ => return Err(-1),
+        };
+        let (scl, sda) = (scl.into(), sda.into());
+        self.pins = Some((scl, sda));
+        configure_alternate(
+            scl,
+            scl_signal,
+            OutputMode::OpenDrain,
+            PullResistor::PullUp,
+            Speed::High,
+        )?;
+        configure_alternate(
+            sda,
+            sda_signal,
+            OutputMode::OpenDrain,
+            PullResistor::PullUp,
+            Speed::High,
+        )
+    }
+
+    /// NVIC interrupt line of this instance
+    fn irqn(&self) -> Option<u32> {
+        let irqn = match self.regs as u32 {
+            device::I2C1_BASEADDR => device::IRQn::I2C1,
+            device::I2C2_BASEADDR => device::IRQn::I2C2,
+            _ => return None,
+        };
+        Some(irqn as u32)
+    }
+
+    fn regs(&self) -> &mut RegisterBlock {
+        unsafe { &mut *self.regs }
+    }
+
+    fn isr(&self) -> u32 {
+        unsafe { ptr::read_volatile(&self.regs().isr) }
+    }
+
+    fn clear_flags(&mut self, mask: u32) {
+        unsafe { ptr::write_volatile(&mut self.regs().icr, mask) };
+    }
+
+    fn set_peripheral_enable(&mut self, enable: bool) {
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_PE_POS, enable);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+    }
+
+    /// Program CR2 for the next chunk of a transfer. `start` generates a
+    /// (repeated) START with the address phase, otherwise the chunk continues
+    /// the current transfer after a TCR. `reload` keeps the transfer open for
+    /// another chunk.
+    /// A 10-bit `addr` is sent in full, header and low byte, before a read
+    /// too (HEAD10R = 0). Returns Err(-1) if `addr` is out of range.
+    fn setup_chunk(
+        &mut self,
+        addr: u32,
+        len: usize,
+        read: bool,
+        start: bool,
+        reload: bool,
+    ) -> Result<()> {
+        let mut cr2 = 0;
+        if addr & ADDRESS_10BIT != 0 {
+            if addr & !ADDRESS_10BIT > 0x3FF {
+                return Err(-1);
+            }
+            cr2 = utils::set_bit(cr2, CR2_ADD10_POS, true);
+            cr2 = utils::set_bits(cr2, addr & 0x3FF, CR2_SADD_POS, CR2_SADD_WIDTH);
+        } else {
+            if addr > 0x7F {
+                return Err(-1);
+            }
+            cr2 = utils::set_bits(cr2, addr << 1, CR2_SADD_POS, CR2_SADD_WIDTH);
+        }
+        cr2 = utils::set_bit(cr2, CR2_RD_WRN_POS, read);
+        cr2 = utils::set_bits(cr2, len as u32, CR2_NBYTES_POS, CR2_NBYTES_WIDTH);
+        cr2 = utils::set_bit(cr2, CR2_RELOAD_POS, reload);
+        cr2 = utils::set_bit(cr2, CR2_START_POS, start);
+        if start {
+            self.addressed = false;
+        }
+        unsafe { ptr::write_volatile(&mut self.regs().cr2, cr2) };
+        Ok(())
+    }
+
+    fn notify(&mut self, event: Event) {
+        if let Some(cb) = &mut self._callback {
+            cb(event);
+        }
+    }
+
+    /// Wait for `flag` in ISR. A NACK ends the transfer: STOP is generated if
+    /// it is not already on its way and Err(-1) is returned. A bus error or
+    /// lost arbitration, after which the peripheral has left the bus, is
+    /// cleared and returns Err(-3). A flag that does not come within
+    /// `TIMEOUT` polls, as when a slave holds SCL or SDA low, is a bus error
+    /// too.
+    fn wait_flag(&mut self, flag: u32) -> Result<()> {
+        for _ in 0..TIMEOUT {
+            let isr = self.isr();
+            if isr & ISR_NACKF_MASK != 0 {
+                if isr & ISR_STOPF_MASK == 0 {
+                    self.generate_stop_condition();
+                }
+                let stopped = self.wait_stop();
+                self.clear_flags(ICR_NACKCF_MASK);
+                stopped?;
+                if self.addressed {
+                    debug!("data not acknowledged");
+                    self.notify(Event::TRANSFER_INCOMPLETE);
+                } else {
+                    debug!("address not acknowledged");
+                    self.notify(Event::ADDRESS_NACK);
+                }
+                return Err(-1);
+            }
+            if isr & ISR_ARLO_MASK != 0 {
+                self.clear_flags(ICR_ARLOCF_MASK);
+                warn!("arbitration lost");
+                self.notify(Event::ARBITRATION_LOST);
+                return Err(-3);
+            }
+            if isr & ISR_BERR_MASK != 0 {
+                self.clear_flags(ICR_BERRCF_MASK);
+                warn!("bus error");
+                self.notify(Event::BUS_ERROR);
+                return Err(-3);
+            }
+            if isr & flag != 0 {
+                // TXIS, RXNE and TC only follow an acknowledged address
+                self.addressed = true;
+                return Ok(());
+            }
+        }
+        warn!("timed out waiting for ISR 0x{:x}", flag);
+        self.generate_stop_condition();
+        self.notify(Event::BUS_ERROR);
+        Err(-3)
+    }
+
+    /// Wait for the STOP to be sent and clear STOPF. Returns Err(-3) if it
+    /// does not go out within `TIMEOUT` polls.
+    fn wait_stop(&mut self) -> Result<()> {
+        for _ in 0..TIMEOUT {
+            if self.isr() & ISR_STOPF_MASK != 0 {
+                self.clear_flags(ICR_STOPCF_MASK);
+                return Ok(());
+            }
+        }
+        warn!("timed out waiting for the STOP");
+        self.notify(Event::BUS_ERROR);
+        Err(-3)
+    }
+
+    /// Sleep until `flag`, a NACK or an error is set in ISR, then handle it
+    /// like `wait_flag`.
+    async fn wait_flag_async(&mut self, flag: u32) -> Result<()> {
+        let irqn = self.irqn().ok_or(-1)?;
+        let irq_mask =
+            CR1_TXIE_MASK | CR1_RXIE_MASK | CR1_TCIE_MASK | CR1_NACKIE_MASK | CR1_ERRIE_MASK;
+        let stops = ISR_NACKF_MASK | ISR_ARLO_MASK | ISR_BERR_MASK;
+        let regs = self.regs;
+        irq::wait_for(
+            irqn,
+            || unsafe { ptr::read_volatile(&(*regs).isr) } & (flag | stops) != 0,
+            |enable| unsafe {
+                let cr1 = ptr::read_volatile(&(*regs).cr1);
+                let cr1 = if enable {
+                    cr1 | irq_mask
+                } else {
+                    cr1 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr1, cr1);
+            },
+        )
+        .await;
+        self.wait_flag(flag)
+    }
+
+    fn generate_stop_condition(&mut self) {
+        let mut cr2 = unsafe { ptr::read_volatile(&self.regs().cr2) };
+        cr2 = utils::set_bit(cr2, CR2_STOP_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr2, cr2) };
+    }
+
+    /// Close a transfer after TC: STOP unless another transfer follows with a
+    /// repeated START. Returns Err(-3) if the STOP does not go out.
+    fn finish(&mut self, xfer_pending: bool) -> Result<()> {
+        if xfer_pending {
+            return Ok(());
+        }
+        self.generate_stop_condition();
+        self.wait_stop()
+    }
+}
+
+impl<'a> I2c<'a> for I2cDriver<'a> {
+    fn initialize(&mut self, callback: impl FnMut(Event) + 'a) -> Result<()> {
+        self._callback = Some(Box::new(callback));
+
+        // Note: The peripheral clock must be enabled before calling this function.
+
+        // Disable peripheral for configuration.
+        self.set_peripheral_enable(false);
+
+        // Configure TIMINGR: SCL low/high periods and data setup/hold times.
+        let timingr = timing(self.config.bus_speed)?;
+        unsafe { ptr::write_volatile(&mut self.regs().timingr, timingr) };
+
+        // Configure OAR1: 7-bit own address, OA1 is only writable while OA1EN = 0.
+        unsafe { ptr::write_volatile(&mut self.regs().oar1, 0) };
+        let mut oar1 = 0;
+        oar1 = utils::set_bits(
+            oar1,
+            self.config.own_address << 1,
+            OAR1_OA1_POS,
+            OAR1_OA1_WIDTH,
+        );
+        oar1 = utils::set_bit(oar1, OAR1_OA1EN_POS, self.config.own_address != 0);
+        unsafe { ptr::write_volatile(&mut self.regs().oar1, oar1) };
+
+        // Enable the peripheral.
+        self.set_peripheral_enable(true);
+
+        Ok(())
+    }
+
+    fn uninitialize(&mut self) -> Result<()> {
+        self.set_peripheral_enable(false);
+        self._callback = None;
+        Ok(())
+    }
+
+    fn master_transmit(&mut self, addr: u32, data: &[u8], xfer_pending: bool) -> Result<()> {
+        self.data_count = 0;
+
+        let mut chunks = data.chunks(MAX_NBYTES).peekable();
+        let mut start = true;
+        if data.is_empty() {
+            // Address-only write
+            self.setup_chunk(addr, 0, false, true, false)?;
+            self.wait_flag(ISR_TC_MASK)?;
+        }
+        while let Some(chunk) = chunks.next() {
+            let reload = chunks.peek().is_some();
+            self.setup_chunk(addr, chunk.len(), false, start, reload)?;
+            start = false;
+
+            for byte in chunk {
+                self.wait_flag(ISR_TXIS_MASK)?;
+                unsafe { ptr::write_volatile(&mut self.regs().txdr, *byte as u32) };
+                self.data_count += 1;
+            }
+
+            self.wait_flag(if reload { ISR_TCR_MASK } else { ISR_TC_MASK })?;
+        }
+
+        self.finish(xfer_pending)
+    }
+
+    fn master_receive(&mut self, addr: u32, data: &mut [u8], xfer_pending: bool) -> Result<()> {
+        self.data_count = 0;
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let mut chunks = data.chunks_mut(MAX_NBYTES).peekable();
+        let mut start = true;
+        while let Some(chunk) = chunks.next() {
+            let reload = chunks.peek().is_some();
+            self.setup_chunk(addr, chunk.len(), true, start, reload)?;
+            start = false;
+
+            for b in chunk.iter_mut() {
+                self.wait_flag(ISR_RXNE_MASK)?;
+                *b = unsafe { ptr::read_volatile(&self.regs().rxdr) as u8 };
+                self.data_count += 1;
+            }
+
+            self.wait_flag(if reload { ISR_TCR_MASK } else { ISR_TC_MASK })?;
+        }
+
+        self.finish(xfer_pending)
+    }
+
+    /// Slave mode is not supported: returns Err(-2).
+    fn slave_transmit(&mut self, _data: &[u8]) -> Result<()> {
+        Err(-2)
+    }
+
+    /// Slave mode is not supported: returns Err(-2).
+    fn slave_receive(&mut self, _data: &mut [u8]) -> Result<()> {
+        Err(-2)
+    }
+
+    fn get_data_count(&self) -> Result<u32> {
+        Ok(self.data_count)
+    }
+
+    fn set_bus_speed(&mut self, speed: BusSpeed) -> Result<()> {
+        // TIMINGR can only be written while PE = 0
+        let timingr = timing(speed)?;
+        self.set_peripheral_enable(false);
+        unsafe { ptr::write_volatile(&mut self.regs().timingr, timingr) };
+        self.set_peripheral_enable(true);
+        self.config.bus_speed = speed;
+        Ok(())
+    }
+
+    fn set_own_address(&mut self, address: u32) -> Result<()> {
+        if address > 0x7F {
+            return Err(-1);
+        }
+        // OA1 is only writable while OA1EN = 0
+        unsafe { ptr::write_volatile(&mut self.regs().oar1, 0) };
+        let mut oar1 = utils::set_bits(0, address << 1, OAR1_OA1_POS, OAR1_OA1_WIDTH);
+        oar1 = utils::set_bit(oar1, OAR1_OA1EN_POS, address != 0);
+        unsafe { ptr::write_volatile(&mut self.regs().oar1, oar1) };
+        self.config.own_address = address;
+        Ok(())
+    }
+
+    fn clear_bus(&mut self) -> Result<()> {
+        let (scl, sda) = self.pins.ok_or(-1)?;
+        // PE = 0 resets the state machine and releases the lines
+        self.set_peripheral_enable(false);
+        let recovered = recover_bus(scl, sda);
+        self.configure_pins(scl, sda)?;
+        self.clear_flags(ICR_NACKCF_MASK | ICR_STOPCF_MASK | ICR_BERRCF_MASK | ICR_ARLOCF_MASK);
+        self.set_peripheral_enable(true);
+        recovered?;
+        debug!("bus cleared");
+        self.notify(Event::BUS_CLEARED);
+        Ok(())
+    }
+
+    fn abort_transfer(&mut self) -> Result<()> {
+        self.generate_stop_condition();
+        Ok(())
+    }
+
+    fn get_status(&self) -> Status {
+        let isr = self.isr();
+        let cr2 = unsafe { ptr::read_volatile(&self.regs().cr2) };
+
+        Status {
+            busy: utils::read_bit(isr, ISR_BUSY_POS),
+            // The v2 IP has no MSL flag, an address match means slave mode
+            master_mode: utils::read_bit(isr, ISR_BUSY_POS) && !utils::read_bit(isr, ISR_ADDR_POS),
+            receiving: utils::read_bit(cr2, CR2_RD_WRN_POS),
+            general_call: utils::read_bit(isr, ISR_ADDR_POS) && (isr & ISR_ADDCODE_MASK) == 0,
+            arbitration_lost: utils::read_bit(isr, ISR_ARLO_POS),
+            bus_error: utils::read_bit(isr, ISR_BERR_POS),
+        }
+    }
+}
+
+impl AsyncI2c for I2cDriver<'_> {
+    async fn master_transmit_async(
+        &mut self,
+        addr: u32,
+        data: &[u8],
+        xfer_pending: bool,
+    ) -> Result<()> {
+        self.data_count = 0;
+
+        let mut chunks = data.chunks(MAX_NBYTES).peekable();
+        let mut start = true;
+        if data.is_empty() {
+            // Address-only write
+            self.setup_chunk(addr, 0, false, true, false)?;
+            self.wait_flag_async(ISR_TC_MASK).await?;
+        }
+        while let Some(chunk) = chunks.next() {
+            let reload = chunks.peek().is_some();
+            self.setup_chunk(addr, chunk.len(), false, start, reload)?;
+            start = false;
+
+            for byte in chunk {
+                self.wait_flag_async(ISR_TXIS_MASK).await?;
+                unsafe { ptr::write_volatile(&mut self.regs().txdr, *byte as u32) };
+                self.data_count += 1;
+            }
+
+            self.wait_flag_async(if reload { ISR_TCR_MASK } else { ISR_TC_MASK })
+                .await?;
+        }
+
+        self.finish(xfer_pending)
+    }
+
+    async fn master_receive_async(
+        &mut self,
+        addr: u32,
+        data: &mut [u8],
+        xfer_pending: bool,
+    ) -> Result<()> {
+        self.data_count = 0;
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        let mut chunks = data.chunks_mut(MAX_NBYTES).peekable();
+        let mut start = true;
+        while let Some(chunk) = chunks.next() {
+            let reload = chunks.peek().is_some();
+            self.setup_chunk(addr, chunk.len(), true, start, reload)?;
+            start = false;
+
+            for b in chunk.iter_mut() {
+                self.wait_flag_async(ISR_RXNE_MASK).await?;
+                *b = unsafe { ptr::read_volatile(&self.regs().rxdr) as u8 };
+                self.data_count += 1;
+            }
+
+            self.wait_flag_async(if reload { ISR_TCR_MASK } else { ISR_TC_MASK })
+                .await?;
+        }
+
+        self.finish(xfer_pending)
+    }
+}
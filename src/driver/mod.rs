@@ -9,9 +9,13 @@
 pub mod adc;
 pub mod can;
 pub mod dac;
+pub mod exti;
 pub mod flash;
 pub mod gpio;
 pub mod i2c;
+pub mod power;
+pub mod reset;
+pub mod rtc;
 pub mod sai;
 pub mod spi;
 pub mod timer;
@@ -0,0 +1,118 @@
+//! # Power Driver
+//!
+//! Puts the MCU into its low-power modes and configures what wakes it:
+//!
+//! - Sleep: the core clock stops; any enabled interrupt wakes it.
+//! - Stop: all clocks stop with the regulator in low-power mode and RAM
+//!   kept; an EXTI line (a pin, the RTC) wakes it. The core restarts on the
+//!   HSI, so `enter_stop()` brings back the HSE, PLL and system clock that
+//!   were running before it returns. SysTick does not count in Stop.
+//! - Standby: the core powers down and RAM is lost; the WKUP pin, the RTC
+//!   or a reset wakes it through a reset, see `woke_from_standby()`.
+//!
+//! Interrupts may be masked with PRIMASK while entering a mode: a pending
+//! interrupt still wakes the core, and runs once they are unmasked.
+#![allow(dead_code)]
+
+use crate::arch::cpu::scb::{SCB, SCB_SCR_SLEEPDEEP_MSK};
+use crate::mcu::device::{self, PeripheralAccess, rcc};
+use core::ptr;
+
+/// A generic error type for the power driver, using i32 for error codes.
+pub type Error = i32;
+
+/// A specialized Result type for power operations.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Select deep sleep (Stop, Standby) or sleep for the next WFI
+fn set_sleepdeep(deep: bool) {
+    let scb = SCB::ptr_mut();
+    unsafe {
+        let scr = ptr::read_volatile(&(*scb).scr);
+        let scr = if deep {
+            scr | SCB_SCR_SLEEPDEEP_MSK
+        } else {
+            scr & !SCB_SCR_SLEEPDEEP_MSK
+        };
+        ptr::write_volatile(&mut (*scb).scr, scr);
+    }
+}
+
+/// Wait for an interrupt in the selected sleep mode
+fn wait_for_interrupt() {
+    // Complete outstanding register writes before the clocks stop
+    cortex_m::asm::dsb();
+    cortex_m::asm::wfi();
+}
+
+/// Enter Sleep until an interrupt.
+pub fn enter_sleep() {
+    set_sleepdeep(false);
+    wait_for_interrupt();
+}
+
+/// Clock configuration to return to after Stop
+struct Clocks {
+    cr: u32,
+    cfgr: u32,
+}
+
+fn save_clocks() -> Clocks {
+    let rcc = device::RCC_BASEADDR as *const rcc::RegisterBlock;
+    unsafe {
+        Clocks {
+            cr: ptr::read_volatile(&(*rcc).cr),
+            cfgr: ptr::read_volatile(&(*rcc).cfgr),
+        }
+    }
+}
+
+/// Restart the HSE and PLL if they were on and switch the system clock back
+/// from the HSI
+fn restore_clocks(saved: &Clocks) {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        for (on, ready) in [
+            (rcc::CR_HSEON_MASK, rcc::CR_HSERDY_MASK),
+            (rcc::CR_PLLON_MASK, rcc::CR_PLLRDY_MASK),
+        ] {
+            if saved.cr & on != 0 {
+                let cr = ptr::read_volatile(&(*rcc).cr);
+                ptr::write_volatile(&mut (*rcc).cr, cr | on);
+                while ptr::read_volatile(&(*rcc).cr) & ready == 0 {}
+            }
+        }
+        let sw = (saved.cfgr & rcc::CFGR_SW_MASK) >> rcc::CFGR_SW_POS;
+        let cfgr = ptr::read_volatile(&(*rcc).cfgr) & !rcc::CFGR_SW_MASK;
+        ptr::write_volatile(&mut (*rcc).cfgr, cfgr | (sw << rcc::CFGR_SW_POS));
+        while (ptr::read_volatile(&(*rcc).cfgr) & rcc::CFGR_SWS_MASK) >> rcc::CFGR_SWS_POS != sw {}
+    }
+}
+
+/// Power v1 IP (PWR_CR/PWR_CSR, single WKUP pin) of the STM32F1 and STM32F4
+/// families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub mod v1;
+
+/// Power v2 IP (low-power mode selection in PWR_CR1, several WKUP pins) of
+/// the STM32G0 family
+#[cfg(feature = "stm32g030")]
+pub mod v2;
+
+/// Power driver of the selected MCU, for board-agnostic code.
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub use v1::{enable_wakeup_pin, enter_standby, enter_stop, woke_from_standby};
+
+/// Power driver of the selected MCU, for board-agnostic code.
+#[cfg(feature = "stm32g030")]
+pub use v2::{enable_wakeup_pin, enter_standby, enter_stop, woke_from_standby};
@@ -0,0 +1,78 @@
+use super::{Result, restore_clocks, save_clocks, set_sleepdeep, wait_for_interrupt};
+use crate::mcu::device::{self, PeripheralAccess, pwr::*, rcc};
+use core::ptr;
+
+fn regs() -> *mut RegisterBlock {
+    PWR::ptr_mut()
+}
+
+fn enable_clock() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).apb1enr);
+        ptr::write_volatile(&mut (*rcc).apb1enr, v | rcc::APB1ENR_PWREN_MASK);
+    }
+}
+
+/// Enter Stop with the regulator in low-power mode until an EXTI line
+/// fires, then restore the clocks.
+pub fn enter_stop() {
+    enable_clock();
+    let clocks = save_clocks();
+    let pwr = regs();
+    unsafe {
+        let cr = ptr::read_volatile(&(*pwr).cr) & !CR_PDDS_MASK;
+        ptr::write_volatile(&mut (*pwr).cr, cr | CR_LPDS_MASK | CR_CWUF_MASK);
+    }
+    set_sleepdeep(true);
+    wait_for_interrupt();
+    set_sleepdeep(false);
+    restore_clocks(&clocks);
+}
+
+/// Enter Standby. Only the WKUP pin, the RTC or a reset leave it, through
+/// a reset.
+pub fn enter_standby() -> ! {
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        let cr = ptr::read_volatile(&(*pwr).cr);
+        ptr::write_volatile(&mut (*pwr).cr, cr | CR_PDDS_MASK | CR_CWUF_MASK);
+    }
+    set_sleepdeep(true);
+    loop {
+        wait_for_interrupt();
+    }
+}
+
+/// Let a rising edge on WKUP pin `pin` (1, on PA0) wake the MCU from
+/// Standby. Returns Err(-1) for another pin.
+pub fn enable_wakeup_pin(pin: u8, enable: bool) -> Result<()> {
+    if pin != 1 {
+        return Err(-1);
+    }
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        let csr = ptr::read_volatile(&(*pwr).csr);
+        let csr = if enable {
+            csr | CSR_EWUP_MASK
+        } else {
+            csr & !CSR_EWUP_MASK
+        };
+        ptr::write_volatile(&mut (*pwr).csr, csr);
+    }
+    Ok(())
+}
+
+/// Returns true if the MCU was reset out of Standby, and clears the flag
+pub fn woke_from_standby() -> bool {
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        let standby = ptr::read_volatile(&(*pwr).csr) & CSR_SBF_MASK != 0;
+        let cr = ptr::read_volatile(&(*pwr).cr);
+        ptr::write_volatile(&mut (*pwr).cr, cr | CR_CSBF_MASK);
+        standby
+    }
+}
@@ -0,0 +1,83 @@
+use super::{Result, restore_clocks, save_clocks, set_sleepdeep, wait_for_interrupt};
+use crate::mcu::device::{self, PeripheralAccess, pwr::*, rcc};
+use crate::utils;
+use core::ptr;
+
+fn regs() -> *mut RegisterBlock {
+    PWR::ptr_mut()
+}
+
+fn enable_clock() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).apbenr1);
+        ptr::write_volatile(&mut (*rcc).apbenr1, v | rcc::APBENR1_PWREN_MASK);
+    }
+}
+
+fn select_mode(lpms: u32) {
+    let pwr = regs();
+    unsafe {
+        let cr1 = ptr::read_volatile(&(*pwr).cr1) & !CR1_LPMS_MASK;
+        ptr::write_volatile(&mut (*pwr).cr1, cr1 | lpms);
+        ptr::write_volatile(&mut (*pwr).scr, SCR_CWUF_MASK);
+    }
+}
+
+/// Enter Stop 1 (main regulator off, low-power regulator on) until an EXTI
+/// line fires, then restore the clocks.
+pub fn enter_stop() {
+    enable_clock();
+    let clocks = save_clocks();
+    select_mode(CR1_LPMS_STOP1);
+    set_sleepdeep(true);
+    wait_for_interrupt();
+    set_sleepdeep(false);
+    restore_clocks(&clocks);
+}
+
+/// Enter Standby. Only a WKUP pin, the RTC or a reset leave it, through a
+/// reset.
+pub fn enter_standby() -> ! {
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        // The RTC wakes the MCU through the internal wakeup line
+        let cr3 = ptr::read_volatile(&(*pwr).cr3);
+        ptr::write_volatile(&mut (*pwr).cr3, cr3 | CR3_EIWUL_MASK);
+    }
+    select_mode(CR1_LPMS_STANDBY);
+    set_sleepdeep(true);
+    loop {
+        wait_for_interrupt();
+    }
+}
+
+/// Let a rising edge on WKUP pin `pin` (1 on PA0, 2 on PC13, 4 on PA2) wake
+/// the MCU from Standby. Returns Err(-1) for another pin.
+pub fn enable_wakeup_pin(pin: u8, enable: bool) -> Result<()> {
+    let bit = match pin {
+        1 => CR3_EWUP1_POS,
+        2 => CR3_EWUP2_POS,
+        4 => CR3_EWUP4_POS,
+        _ => return Err(-1),
+    };
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        let cr3 = ptr::read_volatile(&(*pwr).cr3);
+        ptr::write_volatile(&mut (*pwr).cr3, utils::set_bit(cr3, bit, enable));
+    }
+    Ok(())
+}
+
+/// Returns true if the MCU was reset out of Standby, and clears the flag
+pub fn woke_from_standby() -> bool {
+    enable_clock();
+    let pwr = regs();
+    unsafe {
+        let standby = ptr::read_volatile(&(*pwr).sr1) & SR1_SBF_MASK != 0;
+        ptr::write_volatile(&mut (*pwr).scr, SCR_CSBF_MASK);
+        standby
+    }
+}
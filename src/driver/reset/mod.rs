@@ -0,0 +1,66 @@
+//! # Reset Reason
+//!
+//! Reports why the MCU last came out of reset by combining the RCC CSR
+//! reset flags with the crash records left by the panic and fault handlers.
+use crate::arch::cpu::fault;
+use crate::mcu::device::{self, rcc};
+use core::ptr;
+
+pub use ::fault::reset::{ResetFlags, ResetReason};
+
+/// Reason computed by `init()`
+static mut RESET_REASON: ResetReason = ResetReason::Unknown;
+
+/// Read the reset flags latched in RCC CSR
+pub fn read_reset_flags() -> ResetFlags {
+    let rcc = device::RCC_BASEADDR as *const rcc::RegisterBlock;
+    let csr = unsafe { ptr::read_volatile(&(*rcc).csr) };
+    let flag = |mask: u32| csr & mask != 0;
+
+    ResetFlags {
+        low_power: flag(rcc::CSR_LPWRRSTF_MASK),
+        window_watchdog: flag(rcc::CSR_WWDGRSTF_MASK),
+        independent_watchdog: flag(rcc::CSR_IWDGRSTF_MASK),
+        software: flag(rcc::CSR_SFTRSTF_MASK),
+        #[cfg(not(feature = "stm32g030"))]
+        power_on: flag(rcc::CSR_PORRSTF_MASK),
+        #[cfg(feature = "stm32g030")]
+        power_on: flag(rcc::CSR_PWRRSTF_MASK),
+        pin: flag(rcc::CSR_PINRSTF_MASK),
+        #[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
+        brown_out: flag(rcc::CSR_BORRSTF_MASK),
+        #[cfg(not(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411")))]
+        brown_out: false,
+        #[cfg(feature = "stm32g030")]
+        option_byte_load: flag(rcc::CSR_OBLRSTF_MASK),
+        #[cfg(not(feature = "stm32g030"))]
+        option_byte_load: false,
+    }
+}
+
+/// Clear the reset flags (RMVF) so the next reset starts from a clean state
+pub fn clear_reset_flags() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        let csr = ptr::read_volatile(&(*rcc).csr);
+        ptr::write_volatile(&mut (*rcc).csr, csr | rcc::CSR_RMVF_MASK);
+    }
+}
+
+/// Determine the reason of the last reset and clear the RCC flags. Call
+/// once at boot, after the fault and panic records have been recovered.
+pub fn init() -> ResetReason {
+    let reason = ResetReason::from_flags(
+        read_reset_flags(),
+        crate::panic::last_panic().is_some(),
+        fault::last_fault().is_some(),
+    );
+    clear_reset_flags();
+    unsafe { RESET_REASON = reason };
+    reason
+}
+
+/// The reason of the last reset, as determined by `init()`
+pub fn reset_reason() -> ResetReason {
+    unsafe { RESET_REASON }
+}
@@ -0,0 +1,137 @@
+//! # RTC Driver
+//!
+//! Runs the real-time clock as the time base that keeps counting while the
+//! core is in Stop mode, and as the timer that wakes it: the alarm of the
+//! STM32F1 counter, the wakeup timer of the STM32F4/G0 calendar.
+//!
+//! The RTC lives in the backup domain: once running it survives Standby and
+//! resets other than power loss, and `init()` leaves it running. The LSI is
+//! an RC oscillator a good ten percent off its nominal frequency (see
+//! `device::LSI_HZ`), so time measured on it drifts accordingly; fit a
+//! 32.768 kHz crystal and use the LSE for accurate time.
+#![allow(dead_code)]
+
+use crate::logging::{error, info, warn};
+use crate::mcu::device::{self, rcc::*};
+use crate::utils;
+use core::ptr;
+
+/// A generic error type for the RTC driver, using i32 for error codes.
+pub type Error = i32;
+
+/// A specialized Result type for RTC operations.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Frequency of the LSE watch crystal
+pub const LSE_HZ: u32 = 32_768;
+
+/// Spins to wait for the LSE crystal to start before giving up
+const LSE_STARTUP_SPINS: u32 = 5_000_000;
+
+/// Clock of the RTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+    /// Internal low-speed RC oscillator, always available but inaccurate
+    Lsi,
+    /// External 32.768 kHz crystal
+    Lse,
+}
+
+impl ClockSource {
+    /// Nominal frequency in Hz
+    pub const fn hz(self) -> u32 {
+        match self {
+            ClockSource::Lsi => device::LSI_HZ,
+            ClockSource::Lse => LSE_HZ,
+        }
+    }
+
+    /// Name for logs
+    pub const fn name(self) -> &'static str {
+        match self {
+            ClockSource::Lsi => "LSI",
+            ClockSource::Lse => "LSE",
+        }
+    }
+
+    /// RCC_BDCR RTCSEL value of this source
+    const fn rtcsel(self) -> u32 {
+        match self {
+            ClockSource::Lse => 1,
+            ClockSource::Lsi => 2,
+        }
+    }
+}
+
+/// Start the clock source and select it for the RTC, resetting the backup
+/// domain if another source was selected. Backup domain access must be
+/// enabled. Returns Err(-2) if the LSE crystal does not start.
+fn select_clock(source: ClockSource) -> Result<()> {
+    let rcc = device::RCC_BASEADDR as *mut RegisterBlock;
+    unsafe {
+        let bdcr = ptr::read_volatile(&(*rcc).bdcr);
+        let selected = (bdcr & BDCR_RTCSEL_MASK) >> BDCR_RTCSEL_POS;
+        if selected == source.rtcsel() && bdcr & BDCR_RTCEN_MASK != 0 {
+            return Ok(());
+        }
+        // RTCSEL can only be changed through a backup domain reset
+        if selected != 0 && selected != source.rtcsel() {
+            warn!("backup domain reset to switch the RTC to {}", source.name());
+            ptr::write_volatile(&mut (*rcc).bdcr, bdcr | BDCR_BDRST_MASK);
+            ptr::write_volatile(&mut (*rcc).bdcr, 0);
+        }
+
+        match source {
+            ClockSource::Lsi => {
+                let csr = ptr::read_volatile(&(*rcc).csr);
+                ptr::write_volatile(&mut (*rcc).csr, csr | CSR_LSION_MASK);
+                while ptr::read_volatile(&(*rcc).csr) & CSR_LSIRDY_MASK == 0 {}
+            }
+            ClockSource::Lse => {
+                let bdcr = ptr::read_volatile(&(*rcc).bdcr);
+                ptr::write_volatile(&mut (*rcc).bdcr, bdcr | BDCR_LSEON_MASK);
+                let mut spins = 0;
+                while ptr::read_volatile(&(*rcc).bdcr) & BDCR_LSERDY_MASK == 0 {
+                    spins += 1;
+                    if spins == LSE_STARTUP_SPINS {
+                        error!("LSE did not start");
+                        return Err(-2);
+                    }
+                }
+            }
+        }
+
+        let bdcr = ptr::read_volatile(&(*rcc).bdcr);
+        let bdcr = utils::set_bits(bdcr, source.rtcsel(), BDCR_RTCSEL_POS, BDCR_RTCSEL_WIDTH);
+        ptr::write_volatile(&mut (*rcc).bdcr, bdcr | BDCR_RTCEN_MASK);
+    }
+    info!("RTC running on {}", source.name());
+    Ok(())
+}
+
+/// RTC v1 IP (32-bit counter with alarm) of the STM32F1 family
+#[cfg(feature = "stm32f103")]
+pub mod v1;
+
+/// RTC v2 IP (BCD calendar with wakeup timer) of the STM32F4 and STM32G0
+/// families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32g030"
+))]
+pub mod v2;
+
+/// RTC driver of the selected MCU, for board-agnostic code.
+#[cfg(feature = "stm32f103")]
+pub use v1::{elapsed_ms, init, start_wakeup, stop_wakeup, timestamp};
+
+/// RTC driver of the selected MCU, for board-agnostic code.
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32g030"
+))]
+pub use v2::{elapsed_ms, init, start_wakeup, stop_wakeup, timestamp};
@@ -0,0 +1,145 @@
+use super::{ClockSource, Result, select_clock};
+use crate::arch::cpu::nvic;
+use crate::driver::exti;
+use crate::driver::gpio::EventTrigger;
+use crate::mcu::device::{
+    self, IRQn, PeripheralAccess, exti::EXTI_LINE_RTC_ALARM, pwr, rcc, rtc::*,
+};
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Frequency the counter runs at, 0 until `init()`
+static COUNTER_HZ: AtomicU32 = AtomicU32::new(0);
+
+fn regs() -> *mut RegisterBlock {
+    RTC::ptr_mut()
+}
+
+/// Enable the PWR and BKP clocks and write access to the backup domain
+fn enable_backup_access() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    let pwr = pwr::PWR::ptr_mut();
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).apb1enr);
+        ptr::write_volatile(
+            &mut (*rcc).apb1enr,
+            v | rcc::APB1ENR_PWREN_MASK | rcc::APB1ENR_BKPEN_MASK,
+        );
+        let v = ptr::read_volatile(&(*pwr).cr);
+        ptr::write_volatile(&mut (*pwr).cr, v | pwr::CR_DBP_MASK);
+    }
+}
+
+/// Run `write` in configuration mode, once the previous write is done
+fn configure(write: impl FnOnce(*mut RegisterBlock)) {
+    let rtc = regs();
+    unsafe {
+        while ptr::read_volatile(&(*rtc).crl) & CRL_RTOFF_MASK == 0 {}
+        let crl = ptr::read_volatile(&(*rtc).crl);
+        ptr::write_volatile(&mut (*rtc).crl, crl | CRL_CNF_MASK);
+        write(rtc);
+        let crl = ptr::read_volatile(&(*rtc).crl);
+        ptr::write_volatile(&mut (*rtc).crl, crl & !CRL_CNF_MASK);
+        while ptr::read_volatile(&(*rtc).crl) & CRL_RTOFF_MASK == 0 {}
+    }
+}
+
+/// Wait for the registers to resynchronize to the RTC clock, needed after
+/// reset and after Stop, when the APB1 clock was off.
+fn synchronize() {
+    let rtc = regs();
+    unsafe {
+        let crl = ptr::read_volatile(&(*rtc).crl);
+        ptr::write_volatile(&mut (*rtc).crl, crl & !CRL_RSF_MASK);
+        while ptr::read_volatile(&(*rtc).crl) & CRL_RSF_MASK == 0 {}
+    }
+}
+
+/// Start the RTC on `source`, counting at about 1 kHz. A counter already
+/// running on `source` keeps its count. Returns Err(-2) if the LSE crystal
+/// does not start.
+pub fn init(source: ClockSource) -> Result<()> {
+    enable_backup_access();
+    let bdcr =
+        unsafe { ptr::read_volatile(&(*(device::RCC_BASEADDR as *const rcc::RegisterBlock)).bdcr) };
+    let running = bdcr & rcc::BDCR_RTCEN_MASK != 0
+        && (bdcr & rcc::BDCR_RTCSEL_MASK) >> rcc::BDCR_RTCSEL_POS == source.rtcsel();
+    select_clock(source)?;
+    synchronize();
+
+    let prescaler = (source.hz() / 1000).max(1);
+    if !running {
+        // The prescaler is write-only, so it is only set on a fresh start
+        configure(|rtc| unsafe {
+            ptr::write_volatile(&mut (*rtc).prlh, (prescaler - 1) >> 16);
+            ptr::write_volatile(&mut (*rtc).prll, (prescaler - 1) & 0xFFFF);
+        });
+    }
+    COUNTER_HZ.store(source.hz() / prescaler, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Current count of the RTC, for `elapsed_ms()`
+pub fn timestamp() -> u32 {
+    synchronize();
+    let rtc = regs();
+    unsafe {
+        loop {
+            let high = ptr::read_volatile(&(*rtc).cnth);
+            let low = ptr::read_volatile(&(*rtc).cntl);
+            // Read the high half again in case the low half wrapped
+            if ptr::read_volatile(&(*rtc).cnth) == high {
+                return (high << 16) | (low & 0xFFFF);
+            }
+        }
+    }
+}
+
+/// Milliseconds between two `timestamp()`s
+pub fn elapsed_ms(from: u32, to: u32) -> u32 {
+    time::rtc::counts_to_ms(to.wrapping_sub(from), COUNTER_HZ.load(Ordering::Relaxed))
+}
+
+/// Raise the RTC alarm at most `ms` milliseconds from now, waking the core
+/// from Sleep and Stop. Returns the milliseconds actually programmed, or
+/// Err(-1) before `init()` or for a delay below one count.
+pub fn start_wakeup(ms: u32) -> Result<u32> {
+    let hz = COUNTER_HZ.load(Ordering::Relaxed);
+    if hz == 0 {
+        return Err(-1);
+    }
+    let counts = time::rtc::ms_to_counts(ms, hz);
+    if counts == 0 {
+        return Err(-1);
+    }
+    let alarm = timestamp().wrapping_add(counts);
+    configure(|rtc| unsafe {
+        ptr::write_volatile(&mut (*rtc).alrh, alarm >> 16);
+        ptr::write_volatile(&mut (*rtc).alrl, alarm & 0xFFFF);
+    });
+    let rtc = regs();
+    unsafe {
+        let crl = ptr::read_volatile(&(*rtc).crl);
+        ptr::write_volatile(&mut (*rtc).crl, crl & !CRL_ALRF_MASK);
+        let crh = ptr::read_volatile(&(*rtc).crh);
+        ptr::write_volatile(&mut (*rtc).crh, crh | CRH_ALRIE_MASK);
+    }
+    // The alarm reaches the core through EXTI line 17, the only way out of Stop
+    exti::configure_line(EXTI_LINE_RTC_ALARM, EventTrigger::RisingEdge)?;
+    nvic::nvic_enable_irq(IRQn::RTC_Alarm as u32);
+    Ok(time::rtc::counts_to_ms(counts, hz))
+}
+
+/// Cancel the alarm and clear its flags
+pub fn stop_wakeup() {
+    let rtc = regs();
+    unsafe {
+        let crh = ptr::read_volatile(&(*rtc).crh);
+        ptr::write_volatile(&mut (*rtc).crh, crh & !CRH_ALRIE_MASK);
+        let crl = ptr::read_volatile(&(*rtc).crl);
+        ptr::write_volatile(&mut (*rtc).crl, crl & !CRL_ALRF_MASK);
+    }
+    let _ = exti::configure_line(EXTI_LINE_RTC_ALARM, EventTrigger::None);
+    nvic::nvic_disable_irq(IRQn::RTC_Alarm as u32);
+    nvic::nvic_clear_pending_irq(IRQn::RTC_Alarm as u32);
+}
@@ -0,0 +1,190 @@
+use super::{ClockSource, Result, select_clock};
+use crate::arch::cpu::nvic;
+use crate::driver::exti;
+use crate::driver::gpio::EventTrigger;
+use crate::mcu::device::{self, IRQn, PeripheralAccess, pwr, rcc, rtc::*};
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+use time::rtc::WakeupClock;
+
+// The STM32F4 keeps the status flags in ISR; the STM32G0 splits them into
+// ICSR (initialization) and SR/SCR (events)
+#[cfg(feature = "stm32g030")]
+use device::rtc::{
+    ICSR_INIT_MASK as INIT_MASK, ICSR_INITF_MASK as INITF_MASK, ICSR_WUTWF_MASK as WUTWF_MASK,
+};
+#[cfg(not(feature = "stm32g030"))]
+use device::rtc::{
+    ISR_INIT_MASK as INIT_MASK, ISR_INITF_MASK as INITF_MASK, ISR_WUTWF_MASK as WUTWF_MASK,
+};
+
+#[cfg(not(feature = "stm32g030"))]
+const WAKEUP_EXTI_LINE: u8 = device::exti::EXTI_LINE_RTC_WAKEUP;
+#[cfg(feature = "stm32g030")]
+const WAKEUP_EXTI_LINE: u8 = device::exti::EXTI_LINE_RTC;
+
+#[cfg(not(feature = "stm32g030"))]
+const WAKEUP_IRQN: IRQn = IRQn::RTC_WKUP;
+#[cfg(feature = "stm32g030")]
+const WAKEUP_IRQN: IRQn = IRQn::RTC_TAMP;
+
+/// RTC clock frequency, 0 until `init()`
+static CLOCK_HZ: AtomicU32 = AtomicU32::new(0);
+
+fn regs() -> *mut RegisterBlock {
+    RTC::ptr_mut()
+}
+
+/// The register holding INIT, INITF and WUTWF
+fn init_status() -> *mut u32 {
+    #[cfg(not(feature = "stm32g030"))]
+    let reg = unsafe { &raw mut (*regs()).isr };
+    #[cfg(feature = "stm32g030")]
+    let reg = unsafe { &raw mut (*regs()).icsr };
+    reg
+}
+
+/// Enable the PWR (and RTC bus) clocks and write access to the backup domain
+fn enable_backup_access() {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    let pwr = pwr::PWR::ptr_mut();
+    unsafe {
+        #[cfg(not(feature = "stm32g030"))]
+        {
+            let v = ptr::read_volatile(&(*rcc).apb1enr);
+            ptr::write_volatile(&mut (*rcc).apb1enr, v | rcc::APB1ENR_PWREN_MASK);
+            let v = ptr::read_volatile(&(*pwr).cr);
+            ptr::write_volatile(&mut (*pwr).cr, v | pwr::CR_DBP_MASK);
+        }
+        #[cfg(feature = "stm32g030")]
+        {
+            let v = ptr::read_volatile(&(*rcc).apbenr1);
+            ptr::write_volatile(
+                &mut (*rcc).apbenr1,
+                v | rcc::APBENR1_PWREN_MASK | rcc::APBENR1_RTCAPBEN_MASK,
+            );
+            let v = ptr::read_volatile(&(*pwr).cr1);
+            ptr::write_volatile(&mut (*pwr).cr1, v | pwr::CR1_DBP_MASK);
+        }
+    }
+}
+
+/// Run `write` with the RTC registers unlocked
+fn unlocked(write: impl FnOnce(*mut RegisterBlock)) {
+    let rtc = regs();
+    unsafe {
+        ptr::write_volatile(&mut (*rtc).wpr, WPR_KEY1);
+        ptr::write_volatile(&mut (*rtc).wpr, WPR_KEY2);
+        write(rtc);
+        ptr::write_volatile(&mut (*rtc).wpr, 0xFF);
+    }
+}
+
+/// Start the RTC on `source` with a 1 Hz calendar and shadow registers
+/// bypassed, so the time can be read right after Stop. A calendar already
+/// running on `source` keeps its time. Returns Err(-2) if the LSE crystal
+/// does not start.
+pub fn init(source: ClockSource) -> Result<()> {
+    enable_backup_access();
+    select_clock(source)?;
+
+    let (prediv_a, prediv_s) = time::rtc::prescalers(source.hz());
+    let prer = (prediv_a << PRER_PREDIV_A_POS) | (prediv_s << PRER_PREDIV_S_POS);
+    let status = init_status();
+    unlocked(|rtc| unsafe {
+        if ptr::read_volatile(&(*rtc).prer) != prer {
+            let v = ptr::read_volatile(status);
+            ptr::write_volatile(status, v | INIT_MASK);
+            while ptr::read_volatile(status) & INITF_MASK == 0 {}
+            // The synchronous prescaler is written first, in its own access
+            ptr::write_volatile(&mut (*rtc).prer, prediv_s << PRER_PREDIV_S_POS);
+            ptr::write_volatile(&mut (*rtc).prer, prer);
+            let v = ptr::read_volatile(status);
+            ptr::write_volatile(status, v & !INIT_MASK);
+        }
+        let cr = ptr::read_volatile(&(*rtc).cr);
+        ptr::write_volatile(&mut (*rtc).cr, cr | CR_BYPSHAD_MASK);
+    });
+    CLOCK_HZ.store(source.hz(), Ordering::Relaxed);
+    Ok(())
+}
+
+/// Current time of day in milliseconds, for `elapsed_ms()`
+pub fn timestamp() -> u32 {
+    let rtc = regs();
+    let prediv_s = unsafe { ptr::read_volatile(&(*rtc).prer) } & PRER_PREDIV_S_MASK;
+    unsafe {
+        loop {
+            let ssr = ptr::read_volatile(&(*rtc).ssr);
+            let tr = ptr::read_volatile(&(*rtc).tr);
+            // Without shadow registers, a second may tick between the reads
+            if ptr::read_volatile(&(*rtc).ssr) == ssr {
+                return time::rtc::ms_of_day(tr, ssr, prediv_s);
+            }
+        }
+    }
+}
+
+/// Milliseconds between two `timestamp()`s, less than a day apart
+pub fn elapsed_ms(from: u32, to: u32) -> u32 {
+    time::rtc::elapsed_ms_of_day(from, to)
+}
+
+/// Fire the wakeup timer at most `ms` milliseconds from now, waking the
+/// core from Sleep, Stop and Standby. Returns the milliseconds actually
+/// programmed, or Err(-1) before `init()` or for a delay below one step.
+pub fn start_wakeup(ms: u32) -> Result<u32> {
+    let hz = CLOCK_HZ.load(Ordering::Relaxed);
+    if hz == 0 {
+        return Err(-1);
+    }
+    let config = time::rtc::wakeup_config(ms, hz).ok_or(-1)?;
+    let wucksel = match config.clock {
+        WakeupClock::RtcDiv16 => CR_WUCKSEL_RTC_DIV16,
+        WakeupClock::Spre => CR_WUCKSEL_SPRE,
+    };
+    let status = init_status();
+    unlocked(|rtc| unsafe {
+        let cr = ptr::read_volatile(&(*rtc).cr) & !(CR_WUTE_MASK | CR_WUTIE_MASK);
+        ptr::write_volatile(&mut (*rtc).cr, cr);
+        while ptr::read_volatile(status) & WUTWF_MASK == 0 {}
+        ptr::write_volatile(&mut (*rtc).wutr, config.reload as u32);
+        clear_wakeup_flag();
+        let cr = (cr & !CR_WUCKSEL_MASK) | wucksel;
+        ptr::write_volatile(&mut (*rtc).cr, cr | CR_WUTE_MASK | CR_WUTIE_MASK);
+    });
+    // The timer reaches the core through its EXTI line, the only way out of Stop
+    exti::configure_line(WAKEUP_EXTI_LINE, EventTrigger::RisingEdge)?;
+    nvic::nvic_enable_irq(WAKEUP_IRQN as u32);
+    Ok(config.period_ms(hz))
+}
+
+fn clear_wakeup_flag() {
+    let rtc = regs();
+    unsafe {
+        // The other flags are cleared by writing 0, so they are written 1
+        #[cfg(not(feature = "stm32g030"))]
+        {
+            let isr = ptr::read_volatile(&(*rtc).isr);
+            ptr::write_volatile(
+                &mut (*rtc).isr,
+                !(ISR_WUTF_MASK | ISR_INIT_MASK) | (isr & ISR_INIT_MASK),
+            );
+        }
+        #[cfg(feature = "stm32g030")]
+        ptr::write_volatile(&mut (*rtc).scr, SCR_CWUTF_MASK);
+    }
+}
+
+/// Stop the wakeup timer and clear its flags
+pub fn stop_wakeup() {
+    unlocked(|rtc| unsafe {
+        let cr = ptr::read_volatile(&(*rtc).cr);
+        ptr::write_volatile(&mut (*rtc).cr, cr & !(CR_WUTE_MASK | CR_WUTIE_MASK));
+    });
+    clear_wakeup_flag();
+    #[cfg(not(feature = "stm32g030"))]
+    exti::clear_pending(WAKEUP_EXTI_LINE);
+    nvic::nvic_disable_irq(WAKEUP_IRQN as u32);
+    nvic::nvic_clear_pending_irq(WAKEUP_IRQN as u32);
+}
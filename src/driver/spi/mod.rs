@@ -146,14 +146,30 @@ pub trait Spi<'a> {
     fn control_slave_select(&mut self, active: bool) -> Result<()>;
 }
 
-#[cfg(feature = "stm32f407")]
-pub mod stm32f407;
+/// Interrupt-driven counterpart of the data transfers of `Spi`: the task
+/// sleeps on the SPI interrupt instead of polling the flags. Needs a driver
+/// initialized through `Spi`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncSpi {
+    /// Transmits data over the SPI bus.
+    async fn send_async(&mut self, data: &[u8]) -> Result<()>;
 
-#[cfg(feature = "stm32f401")]
-pub mod stm32f401;
+    /// Receives data from the SPI bus.
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()>;
 
-#[cfg(feature = "stm32f411")]
-pub mod stm32f411;
+    /// Simultaneously sends and receives data.
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()>;
+}
 
-#[cfg(feature = "stm32f103")]
-pub mod stm32f103;
+/// SPI v1 IP, shared by the STM32F1 and STM32F4 families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub mod v1;
+
+/// SPI v2 IP (data size and FIFO threshold in CR2) of the STM32G0 family
+#[cfg(feature = "stm32g030")]
+pub mod v2;
@@ -1,2 +0,0 @@
-// SPI driver implementation for STM32F103
-// TODO: Implement SPI driver functions
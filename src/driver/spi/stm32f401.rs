@@ -1,2 +0,0 @@
-// SPI driver implementation for STM32F401
-// TODO: Implement SPI driver functions
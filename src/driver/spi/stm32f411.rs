@@ -1,2 +0,0 @@
-// SPI driver implementation for STM32F411
-// TODO: Implement SPI driver functions
@@ -1,8 +1,12 @@
-#[cfg(feature = "stm32f407")]
 extern crate alloc;
 
-use super::{BitOrder, Config, Event, FrameFormat, Mode, Result, SlaveSelectMode, Spi, Status};
-use crate::mcu::stm32f407::{self, spi::*};
+use super::{
+    AsyncSpi, BitOrder, Config, Event, FrameFormat, Mode, Result, SlaveSelectMode, Spi, Status,
+};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::warn;
+use crate::mcu::device::{self, spi::*};
+use crate::runtime::irq;
 use crate::utils;
 use alloc::boxed::Box;
 use core::ops::FnMut;
@@ -10,10 +14,10 @@ use core::ptr;
 
 /// Default APB bus clock frequencies in Hz.
 /// Update these to match your clock configuration.
-const PCLK1_HZ: u32 = 16_000_000; // APB1 (SPI2/3)
-const PCLK2_HZ: u32 = 16_000_000; // APB2 (SPI1)
+const PCLK1_HZ: u32 = device::HSI_HZ; // APB1 (SPI2/3)
+const PCLK2_HZ: u32 = device::HSI_HZ; // APB2 (SPI1)
 
-/// A polling-based SPI driver for STM32F407.
+/// A polling-based SPI driver for the STM32F1/F4 (v1 IP).
 pub struct SpiDriver<'a> {
     regs: *mut RegisterBlock,
     _callback: Option<Box<dyn FnMut(Event) + 'a>>,
@@ -33,17 +37,96 @@ impl<'a> SpiDriver<'a> {
 
     /// Create a new SPI1 driver instance (APB2 clock)
     pub fn new_spi1(config: Config) -> Self {
-        Self::new(stm32f407::SPI1_BASEADDR, config)
+        Self::new(device::SPI1_BASEADDR, config)
     }
 
     /// Create a new SPI2 driver instance (APB1 clock)
     pub fn new_spi2(config: Config) -> Self {
-        Self::new(stm32f407::SPI2_BASEADDR, config)
+        Self::new(device::SPI2_BASEADDR, config)
     }
 
     /// Create a new SPI3 driver instance (APB1 clock)
     pub fn new_spi3(config: Config) -> Self {
-        Self::new(stm32f407::SPI3_BASEADDR, config)
+        Self::new(device::SPI3_BASEADDR, config)
+    }
+
+    /// Route SCK, MISO and MOSI of this SPI to the given pins using the
+    /// alternate function map. Fails if a pin cannot carry the signal.
+    pub fn configure_pins(
+        &mut self,
+        sck: impl Into<Pin>,
+        miso: impl Into<Pin>,
+        mosi: impl Into<Pin>,
+    ) -> Result<()> {
+        let (sck_signal, miso_signal, mosi_signal) = self.pin_signals().ok_or(-1)?;
+        configure_alternate(
+            sck.into(),
+            sck_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )?;
+        configure_alternate(
+            miso.into(),
+            miso_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )?;
+        configure_alternate(
+            mosi.into(),
+            mosi_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )
+    }
+
+    /// Route the hardware NSS signal of this SPI to the given pin.
+    pub fn configure_nss_pin(&mut self, nss: impl Into<Pin>) -> Result<()> {
+        let nss_signal = match self.regs as u32 {
+            device::SPI1_BASEADDR => AfSignal::SPI1_NSS,
+            device::SPI2_BASEADDR => AfSignal::SPI2_NSS,
+            device::SPI3_BASEADDR => AfSignal::SPI3_NSS,
+            _ => {
+                warn!("no NSS signal for this SPI instance");
+                return Err(-1);
+            }
+        };
+        configure_alternate(
+            nss.into(),
+            nss_signal,
+            OutputMode::PushPull,
+            PullResistor::PullUp,
+            Speed::VeryHigh,
+        )
+    }
+
+    /// SCK/MISO/MOSI alternate function signals of this instance
+    fn pin_signals(&self) -> Option<(AfSignal, AfSignal, AfSignal)> {
+        match self.regs as u32 {
+            device::SPI1_BASEADDR => {
+                Some((AfSignal::SPI1_SCK, AfSignal::SPI1_MISO, AfSignal::SPI1_MOSI))
+            }
+            device::SPI2_BASEADDR => {
+                Some((AfSignal::SPI2_SCK, AfSignal::SPI2_MISO, AfSignal::SPI2_MOSI))
+            }
+            device::SPI3_BASEADDR => {
+                Some((AfSignal::SPI3_SCK, AfSignal::SPI3_MISO, AfSignal::SPI3_MOSI))
+            }
+            _ => None,
+        }
+    }
+
+    /// NVIC interrupt line of this instance
+    fn irqn(&self) -> Option<u32> {
+        let irqn = match self.regs as u32 {
+            device::SPI1_BASEADDR => device::IRQn::SPI1,
+            device::SPI2_BASEADDR => device::IRQn::SPI2,
+            device::SPI3_BASEADDR => device::IRQn::SPI3,
+            _ => return None,
+        };
+        Some(irqn as u32)
     }
 
     fn regs(&self) -> &mut RegisterBlock {
@@ -52,7 +135,7 @@ impl<'a> SpiDriver<'a> {
 
     fn is_on_apb2(&self) -> bool {
         let base = self.regs as u32;
-        base == stm32f407::SPI1_BASEADDR
+        base == device::SPI1_BASEADDR
     }
 
     fn get_flag_status(&self, flag_bit: u32) -> bool {
@@ -72,6 +155,28 @@ impl<'a> SpiDriver<'a> {
         while self.get_flag_status(SR_BSY_POS) {}
     }
 
+    /// Sleep until `flag` is set in SR, with the interrupt `irq_mask` of CR2
+    /// enabled while waiting.
+    async fn wait_flag_async(&self, flag: u32, irq_mask: u32) -> Result<()> {
+        let irqn = self.irqn().ok_or(-1)?;
+        let regs = self.regs;
+        irq::wait_for(
+            irqn,
+            || unsafe { ptr::read_volatile(&(*regs).sr) } & flag != 0,
+            |enable| unsafe {
+                let cr2 = ptr::read_volatile(&(*regs).cr2);
+                let cr2 = if enable {
+                    cr2 | irq_mask
+                } else {
+                    cr2 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr2, cr2);
+            },
+        )
+        .await;
+        Ok(())
+    }
+
     fn clear_ovr_flag(&mut self) {
         // Read DR and SR to clear OVR flag
         let _ = unsafe { ptr::read_volatile(&self.regs().dr) };
@@ -212,7 +317,8 @@ impl<'a> SpiDriver<'a> {
             cr2 = utils::set_bit(cr2, CR2_SSOE_POS, true);
         }
 
-        // Configure frame format
+        // Configure frame format (the F1 has no TI mode)
+        #[cfg(not(feature = "stm32f103"))]
         match self.config.frame_format {
             FrameFormat::TI_SSI => {
                 cr2 = utils::set_bit(cr2, CR2_FRF_POS, true);
@@ -232,6 +338,12 @@ impl<'a> Spi<'a> for SpiDriver<'a> {
 
         // Note: The peripheral clock must be enabled before calling this function.
 
+        #[cfg(feature = "stm32f103")]
+        if self.config.frame_format == FrameFormat::TI_SSI {
+            warn!("TI frame format not supported");
+            return Err(-2); // TI frame format not supported
+        }
+
         // Disable peripheral for configuration
         let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
         cr1 = utils::set_bit(cr1, CR1_SPE_POS, false);
@@ -240,6 +352,7 @@ impl<'a> Spi<'a> for SpiDriver<'a> {
         // Configure CR1 register
         let cr1_config = self.configure_cr1();
         if cr1_config == 0 {
+            warn!("invalid SPI configuration");
             return Err(-1); // Invalid configuration
         }
         unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1_config) };
@@ -316,6 +429,11 @@ impl<'a> Spi<'a> for SpiDriver<'a> {
 
     fn transfer(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
         if data_in.len() != data_out.len() {
+            warn!(
+                "transfer of {} bytes into a {} byte buffer",
+                data_out.len(),
+                data_in.len()
+            );
             return Err(-1);
         }
 
@@ -365,7 +483,80 @@ impl<'a> Spi<'a> for SpiDriver<'a> {
             unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
             Ok(())
         } else {
+            warn!("slave select is only driven in software slave select mode");
             Err(-2) // Not supported in current mode
         }
     }
 }
+
+impl AsyncSpi for SpiDriver<'_> {
+    async fn send_async(&mut self, data: &[u8]) -> Result<()> {
+        self.data_count = 0;
+
+        for &byte in data {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, byte as u32) };
+            self.data_count += 1;
+        }
+
+        // BSY has no interrupt; it clears one frame after the last TXE
+        self.wait_not_busy();
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()> {
+        self.data_count = 0;
+
+        // For receive-only mode, we need to send dummy data
+        for b in data.iter_mut() {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, 0xFF as u32) };
+
+            self.wait_flag_async(SR_RXNE_MASK, CR2_RXNEIE_MASK).await?;
+            *b = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.data_count += 1;
+        }
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            warn!(
+                "transfer of {} bytes into a {} byte buffer",
+                data_out.len(),
+                data_in.len()
+            );
+            return Err(-1);
+        }
+
+        self.data_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, byte as u32) };
+
+            self.wait_flag_async(SR_RXNE_MASK, CR2_RXNEIE_MASK).await?;
+            data_in[i] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.data_count += 1;
+        }
+
+        // BSY has no interrupt; it clears one frame after the last RXNE
+        self.wait_not_busy();
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,527 @@
+extern crate alloc;
+
+use super::{
+    AsyncSpi, BitOrder, Config, Event, FrameFormat, Mode, Result, SlaveSelectMode, Spi, Status,
+};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::warn;
+use crate::mcu::device::{self, spi::*};
+use crate::runtime::irq;
+use crate::utils;
+use alloc::boxed::Box;
+use core::ops::FnMut;
+use core::ptr;
+
+/// Default APB clock frequency in Hz (HSI16 after reset).
+/// Update this to match your clock configuration.
+const PCLK_HZ: u32 = device::HSI_HZ;
+
+/// A polling-based SPI driver for the STM32G0 (v2 IP).
+pub struct SpiDriver<'a> {
+    regs: *mut RegisterBlock,
+    _callback: Option<Box<dyn FnMut(Event) + 'a>>,
+    config: Config,
+    data_count: u32,
+}
+
+impl<'a> SpiDriver<'a> {
+    pub fn new(spi_base_addr: u32, config: Config) -> Self {
+        Self {
+            regs: spi_base_addr as *mut RegisterBlock,
+            _callback: None,
+            config,
+            data_count: 0,
+        }
+    }
+
+    /// Create a new SPI1 driver instance
+    pub fn new_spi1(config: Config) -> Self {
+        Self::new(device::SPI1_BASEADDR, config)
+    }
+
+    /// Create a new SPI2 driver instance
+    pub fn new_spi2(config: Config) -> Self {
+        Self::new(device::SPI2_BASEADDR, config)
+    }
+
+    /// Route SCK, MISO and MOSI of this SPI to the given pins using the
+    /// alternate function map. Fails if a pin cannot carry the signal.
+    pub fn configure_pins(
+        &mut self,
+        sck: impl Into<Pin>,
+        miso: impl Into<Pin>,
+        mosi: impl Into<Pin>,
+    ) -> Result<()> {
+        let (sck_signal, miso_signal, mosi_signal) = self.pin_signals().ok_or(-1)?;
+        configure_alternate(
+            sck.into(),
+            sck_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )?;
+        configure_alternate(
+            miso.into(),
+            miso_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )?;
+        configure_alternate(
+            mosi.into(),
+            mosi_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )
+    }
+
+    /// Route the hardware NSS signal of this SPI to the given pin.
+    pub fn configure_nss_pin(&mut self, nss: impl Into<Pin>) -> Result<()> {
+        let nss_signal = match self.regs as u32 {
+            device::SPI1_BASEADDR => AfSignal::SPI1_NSS,
+            device::SPI2_BASEADDR => AfSignal::SPI2_NSS,
+            _ => {
+                warn!("no NSS signal for this SPI instance");
+                return Err(-1);
+            }
+        };
+        configure_alternate(
+            nss.into(),
+            nss_signal,
+            OutputMode::PushPull,
+            PullResistor::PullUp,
+            Speed::VeryHigh,
+        )
+    }
+
+    /// SCK/MISO/MOSI alternate function signals of this instance
+    fn pin_signals(&self) -> Option<(AfSignal, AfSignal, AfSignal)> {
+        match self.regs as u32 {
+            device::SPI1_BASEADDR => {
+                Some((AfSignal::SPI1_SCK, AfSignal::SPI1_MISO, AfSignal::SPI1_MOSI))
+            }
+            device::SPI2_BASEADDR => {
+                Some((AfSignal::SPI2_SCK, AfSignal::SPI2_MISO, AfSignal::SPI2_MOSI))
+            }
+            _ => None,
+        }
+    }
+
+    /// NVIC interrupt line of this instance
+    fn irqn(&self) -> Option<u32> {
+        let irqn = match self.regs as u32 {
+            device::SPI1_BASEADDR => device::IRQn::SPI1,
+            device::SPI2_BASEADDR => device::IRQn::SPI2,
+            _ => return None,
+        };
+        Some(irqn as u32)
+    }
+
+    fn regs(&self) -> &mut RegisterBlock {
+        unsafe { &mut *self.regs }
+    }
+
+    fn get_flag_status(&self, flag_bit: u32) -> bool {
+        let sr = unsafe { ptr::read_volatile(&self.regs().sr) };
+        utils::read_bit(sr, flag_bit)
+    }
+
+    fn wait_txe(&self) {
+        while !self.get_flag_status(SR_TXE_POS) {}
+    }
+
+    fn wait_rxne(&self) {
+        while !self.get_flag_status(SR_RXNE_POS) {}
+    }
+
+    fn wait_not_busy(&self) {
+        // FTLVL must drain before BSY is meaningful
+        while unsafe { ptr::read_volatile(&self.regs().sr) } & SR_FTLVL_MASK != 0 {}
+        while self.get_flag_status(SR_BSY_POS) {}
+    }
+
+    /// Sleep until `flag` is set in SR, with the interrupt `irq_mask` of CR2
+    /// enabled while waiting.
+    async fn wait_flag_async(&self, flag: u32, irq_mask: u32) -> Result<()> {
+        let irqn = self.irqn().ok_or(-1)?;
+        let regs = self.regs;
+        irq::wait_for(
+            irqn,
+            || unsafe { ptr::read_volatile(&(*regs).sr) } & flag != 0,
+            |enable| unsafe {
+                let cr2 = ptr::read_volatile(&(*regs).cr2);
+                let cr2 = if enable {
+                    cr2 | irq_mask
+                } else {
+                    cr2 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr2, cr2);
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Write one frame. Frames of up to 8 bits need a byte access, otherwise
+    /// the TX FIFO packs two frames from a single 16-bit write.
+    fn write_dr(&mut self, byte: u8) {
+        let dr = &mut self.regs().dr as *mut u32;
+        if self.config.data_bits <= 8 {
+            unsafe { ptr::write_volatile(dr as *mut u8, byte) };
+        } else {
+            unsafe { ptr::write_volatile(dr as *mut u16, byte as u16) };
+        }
+    }
+
+    fn read_dr(&self) -> u8 {
+        let dr = &self.regs().dr as *const u32;
+        if self.config.data_bits <= 8 {
+            unsafe { ptr::read_volatile(dr as *const u8) }
+        } else {
+            unsafe { ptr::read_volatile(dr as *const u16) as u8 }
+        }
+    }
+
+    fn configure_cr1(&mut self) -> u32 {
+        let mut cr1 = 0;
+
+        // 1. Configure device mode
+        match self.config.mode {
+            Mode::Master => {
+                cr1 = utils::set_bit(cr1, CR1_MSTR_POS, true);
+            }
+            Mode::Slave => {
+                cr1 = utils::set_bit(cr1, CR1_MSTR_POS, false);
+            }
+            Mode::Inactive => {
+                // Keep peripheral disabled
+                return cr1;
+            }
+        }
+
+        // 2. Configure bus configuration (bidirectional mode)
+        match self.config.slave_select_mode {
+            SlaveSelectMode::MasterHwInput => {
+                // Half-duplex mode (BIDIMODE=1, BIDIOE=0)
+                cr1 = utils::set_bit(cr1, CR1_BIDIMODE_POS, true);
+                cr1 = utils::set_bit(cr1, CR1_BIDIOE_POS, false);
+            }
+            SlaveSelectMode::MasterHwOutput | SlaveSelectMode::MasterUnused => {
+                // Full-duplex mode (BIDIMODE=0)
+                cr1 = utils::set_bit(cr1, CR1_BIDIMODE_POS, false);
+            }
+            SlaveSelectMode::MasterSoftware => {
+                // Full-duplex mode with software SS control
+                cr1 = utils::set_bit(cr1, CR1_BIDIMODE_POS, false);
+                cr1 = utils::set_bit(cr1, CR1_SSM_POS, true);
+                cr1 = utils::set_bit(cr1, CR1_SSI_POS, true);
+            }
+            SlaveSelectMode::SlaveHardware => {
+                cr1 = utils::set_bit(cr1, CR1_SSM_POS, false);
+            }
+            SlaveSelectMode::SlaveSoftware => {
+                cr1 = utils::set_bit(cr1, CR1_SSM_POS, true);
+            }
+        }
+
+        // 3. Configure serial clock speed (baud rate)
+        cr1 |= match self.config.bus_speed_hz {
+            speed if speed >= PCLK_HZ / 2 => CR1_BR_DIV2,
+            speed if speed >= PCLK_HZ / 4 => CR1_BR_DIV4,
+            speed if speed >= PCLK_HZ / 8 => CR1_BR_DIV8,
+            speed if speed >= PCLK_HZ / 16 => CR1_BR_DIV16,
+            speed if speed >= PCLK_HZ / 32 => CR1_BR_DIV32,
+            speed if speed >= PCLK_HZ / 64 => CR1_BR_DIV64,
+            speed if speed >= PCLK_HZ / 128 => CR1_BR_DIV128,
+            _ => CR1_BR_DIV256,
+        };
+
+        // 4. Configure CPOL and CPHA (ignored by the TI frame format)
+        let (cpol, cpha) = match self.config.frame_format {
+            FrameFormat::CPOL0_CPHA1 => (false, true),
+            FrameFormat::CPOL1_CPHA0 => (true, false),
+            FrameFormat::CPOL1_CPHA1 => (true, true),
+            _ => (false, false),
+        };
+        cr1 = utils::set_bit(cr1, CR1_CPOL_POS, cpol);
+        cr1 = utils::set_bit(cr1, CR1_CPHA_POS, cpha);
+
+        // 5. Configure bit order
+        cr1 = utils::set_bit(
+            cr1,
+            CR1_LSBFIRST_POS,
+            matches!(self.config.bit_order, BitOrder::LSB_MSB),
+        );
+
+        cr1
+    }
+
+    fn configure_cr2(&mut self) -> u32 {
+        // Data size is DS + 1 bits
+        let mut cr2 = utils::set_bits(
+            0,
+            (self.config.data_bits - 1) as u32,
+            CR2_DS_POS,
+            CR2_DS_WIDTH,
+        );
+
+        // RXNE on every byte for 8-bit frames, on every half-word otherwise
+        cr2 = utils::set_bit(cr2, CR2_FRXTH_POS, self.config.data_bits <= 8);
+
+        // Configure SSOE for hardware slave select output
+        if matches!(
+            self.config.slave_select_mode,
+            SlaveSelectMode::MasterHwOutput
+        ) {
+            cr2 = utils::set_bit(cr2, CR2_SSOE_POS, true);
+        }
+
+        // Configure frame format
+        if self.config.frame_format == FrameFormat::TI_SSI {
+            cr2 |= CR2_FRF_TI;
+        }
+
+        cr2
+    }
+}
+
+impl<'a> Spi<'a> for SpiDriver<'a> {
+    fn initialize(&mut self, callback: impl FnMut(Event) + 'a) -> Result<()> {
+        self._callback = Some(Box::new(callback));
+
+        // Note: The peripheral clock must be enabled before calling this function.
+
+        if self.config.frame_format == FrameFormat::Microwire {
+            warn!("Microwire frame format not supported");
+            return Err(-2); // Microwire frame format not supported
+        }
+        if !(4..=16).contains(&self.config.data_bits) {
+            warn!("unsupported data size, only 4 to 16 bits");
+            return Err(-1); // Unsupported data size
+        }
+
+        // Disable peripheral for configuration
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_SPE_POS, false);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+
+        // Configure CR1 register
+        let cr1_config = self.configure_cr1();
+        if cr1_config == 0 {
+            warn!("invalid SPI configuration");
+            return Err(-1); // Invalid configuration
+        }
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1_config) };
+
+        // Configure CR2 register
+        let cr2_config = self.configure_cr2();
+        unsafe { ptr::write_volatile(&mut self.regs().cr2, cr2_config) };
+
+        // Enable the peripheral
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_SPE_POS, true);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+
+        Ok(())
+    }
+
+    fn uninitialize(&mut self) -> Result<()> {
+        self.wait_not_busy();
+        let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        cr1 = utils::set_bit(cr1, CR1_SPE_POS, false);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+        self._callback = None;
+        Ok(())
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        self.config = config.clone();
+
+        // Re-initialize with new configuration
+        if let Some(callback) = self._callback.take() {
+            self.initialize(callback)?;
+        }
+
+        Ok(())
+    }
+
+    fn send(&mut self, data: &[u8]) -> Result<()> {
+        self.data_count = 0;
+
+        for &byte in data {
+            self.wait_txe();
+            self.write_dr(byte);
+            self.data_count += 1;
+        }
+
+        // Wait for transmission to complete, then drop what was clocked in
+        self.wait_not_busy();
+        while self.get_flag_status(SR_RXNE_POS) {
+            let _ = self.read_dr();
+        }
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    fn receive(&mut self, data: &mut [u8]) -> Result<()> {
+        self.data_count = 0;
+
+        // For receive-only mode, we need to send dummy data
+        for b in data.iter_mut() {
+            self.wait_txe();
+            self.write_dr(0xFF);
+
+            self.wait_rxne();
+            *b = self.read_dr();
+            self.data_count += 1;
+        }
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    fn transfer(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            warn!(
+                "transfer of {} bytes into a {} byte buffer",
+                data_out.len(),
+                data_in.len()
+            );
+            return Err(-1);
+        }
+
+        self.data_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_txe();
+            self.write_dr(byte);
+
+            self.wait_rxne();
+            data_in[i] = self.read_dr();
+            self.data_count += 1;
+        }
+
+        // Wait for transmission to complete
+        self.wait_not_busy();
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    fn get_data_count(&self) -> u32 {
+        self.data_count
+    }
+
+    fn get_status(&self) -> Status {
+        let sr = unsafe { ptr::read_volatile(&self.regs().sr) };
+
+        Status {
+            busy: utils::read_bit(sr, SR_BSY_POS),
+            data_lost: utils::read_bit(sr, SR_OVR_POS),
+            mode_fault: utils::read_bit(sr, SR_MODF_POS),
+        }
+    }
+
+    fn control_slave_select(&mut self, active: bool) -> Result<()> {
+        // Only applicable for software slave select mode
+        if matches!(
+            self.config.slave_select_mode,
+            SlaveSelectMode::MasterSoftware
+        ) {
+            let mut cr1 = unsafe { ptr::read_volatile(&self.regs().cr1) };
+            cr1 = utils::set_bit(cr1, CR1_SSI_POS, active);
+            unsafe { ptr::write_volatile(&mut self.regs().cr1, cr1) };
+            Ok(())
+        } else {
+            warn!("slave select is only driven in software slave select mode");
+            Err(-2) // Not supported in current mode
+        }
+    }
+}
+
+impl AsyncSpi for SpiDriver<'_> {
+    async fn send_async(&mut self, data: &[u8]) -> Result<()> {
+        self.data_count = 0;
+
+        for &byte in data {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            self.write_dr(byte);
+            self.data_count += 1;
+        }
+
+        // BSY has no interrupt; it clears one frame after the last TXE,
+        // then drop what was clocked in
+        self.wait_not_busy();
+        while self.get_flag_status(SR_RXNE_POS) {
+            let _ = self.read_dr();
+        }
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()> {
+        self.data_count = 0;
+
+        // For receive-only mode, we need to send dummy data
+        for b in data.iter_mut() {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            self.write_dr(0xFF);
+
+            self.wait_flag_async(SR_RXNE_MASK, CR2_RXNEIE_MASK).await?;
+            *b = self.read_dr();
+            self.data_count += 1;
+        }
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            warn!(
+                "transfer of {} bytes into a {} byte buffer",
+                data_out.len(),
+                data_in.len()
+            );
+            return Err(-1);
+        }
+
+        self.data_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_flag_async(SR_TXE_MASK, CR2_TXEIE_MASK).await?;
+            self.write_dr(byte);
+
+            self.wait_flag_async(SR_RXNE_MASK, CR2_RXNEIE_MASK).await?;
+            data_in[i] = self.read_dr();
+            self.data_count += 1;
+        }
+
+        // BSY has no interrupt; it clears one frame after the last RXNE
+        self.wait_not_busy();
+
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! # Host link
+//!
+//! Reliable framed packets to a host over a `Usart`, with the host-tested
+//! `link` component: COBS or SLIP frames, a CRC-16 or CRC-32 check, and
+//! sequence numbers acknowledged by the other end, retransmitted when
+//! the acknowledgement does not come. The host runs the same component
+//! with `link::host::SerialPort`.
+//!
+//! `poll` reads the data register, so it must run more often than one
+//! character time (87 us at 115200 baud), or through a driver that
+//! buffers received bytes.
+//!
+//! ```ignore
+//! let mut link = Endpoint::new(Adapter(&mut usart), Config::default());
+//! link.send(&telemetry, now_ms())?;
+//! loop {
+//!     match link.poll(now_ms()) {
+//!         Some(Event::Received(command)) => handle(command),
+//!         Some(Event::Failed(seq)) => warn!("packet {} lost", seq),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use super::Usart;
+use crate::clock;
+
+#[allow(unused_imports)]
+pub use link::{
+    endpoint::{Config, Endpoint, Event, Port, Stats},
+    frame::Encoding,
+    packet::{Check, MAX_PAYLOAD},
+};
+
+/// A `Usart` driver as the port of an `Endpoint`
+pub struct Adapter<'b, U>(pub &'b mut U);
+
+impl<'a, U: Usart<'a>> Port for Adapter<'_, U> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        self.0.send(bytes)
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        self.0.read_byte()
+    }
+}
+
+/// Time to poll an `Endpoint` with
+pub fn now_ms() -> u64 {
+    clock::now().as_millis()
+}
@@ -215,6 +215,10 @@ pub trait Usart<'a> {
     /// Receives data from the USART bus.
     fn receive(&mut self, data: &mut [u8]) -> Result<()>;
 
+    /// Takes the received byte waiting in the data register, if any,
+    /// without blocking. An overrun is cleared so reception goes on.
+    fn read_byte(&mut self) -> Option<u8>;
+
     /// Simultaneously sends and receives data.
     fn transfer(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()>;
 
@@ -252,14 +256,36 @@ pub trait Usart<'a> {
     fn abort_transfer(&mut self) -> Result<()>;
 }
 
-#[cfg(feature = "stm32f407")]
-pub mod stm32f407;
+/// Interrupt-driven counterpart of the data transfers of `Usart`: the task
+/// sleeps on the USART interrupt instead of polling the flags. Needs a
+/// driver initialized through `Usart`.
+#[allow(async_fn_in_trait)]
+pub trait AsyncUsart {
+    /// Transmits data over the USART bus.
+    async fn send_async(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Receives data from the USART bus.
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()>;
+
+    /// Simultaneously sends and receives data.
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()>;
+}
+
+/// Framed, acknowledged packets to a host over any `Usart`
+pub mod link;
 
-#[cfg(feature = "stm32f401")]
-pub mod stm32f401;
+/// Modbus RTU master and slave over any `Usart` and an RS-485 transceiver
+pub mod modbus;
 
-#[cfg(feature = "stm32f411")]
-pub mod stm32f411;
+/// USART v1 IP, shared by the STM32F1 and STM32F4 families
+#[cfg(any(
+    feature = "stm32f407",
+    feature = "stm32f401",
+    feature = "stm32f411",
+    feature = "stm32f103"
+))]
+pub mod v1;
 
-#[cfg(feature = "stm32f103")]
-pub mod stm32f103;
+/// USART v2 IP (ISR/ICR status, separate TDR/RDR) of the STM32G0 family
+#[cfg(feature = "stm32g030")]
+pub mod v2;
@@ -0,0 +1,81 @@
+//! # Modbus RTU
+//!
+//! The master and slave of the host-tested `modbus` component, run on a
+//! `Usart` driving an RS-485 transceiver through `Rs485`. The DE/RE pin
+//! is high while a frame is sent and low otherwise; `Usart::send` waits
+//! for transmission complete, so the last stop bit is out before the
+//! transceiver turns around.
+//!
+//! Bytes are read from the data register and timed with `clock::now`, so
+//! `poll` must run more often than one character time (about 1 ms at
+//! 9600 baud) or bytes are lost to overruns and frames end early.
+//!
+//! ```ignore
+//! let timing = Timing::new(config.baudrate);
+//! let line = Rs485::new(&mut usart, &mut gpio, PA::<8>::ID)?;
+//! let mut master = Master::new(line, timing).with_timeout_ms(200);
+//! master.send(17, &Request::WriteSingleRegister { address: 1, value: 3 })?;
+//! let outcome = loop {
+//!     if let Some(outcome) = master.poll() {
+//!         break outcome.map(|_| ());
+//!     }
+//! };
+//!
+//! // Or answer as slave 17 from a `RegisterMap`
+//! let line = Rs485::new(&mut usart, &mut gpio, PA::<8>::ID)?;
+//! let mut slave = Slave::new(line, 17, registers, timing);
+//! loop {
+//!     slave.poll()?;
+//! }
+//! ```
+
+use super::Usart;
+use crate::clock;
+use crate::driver::gpio::{Direction, Gpio, Pin};
+
+#[allow(unused_imports)]
+pub use modbus::{
+    master::{BROADCAST, Error, Master, Response},
+    pdu::{Bits, Exception, Request, Words},
+    serial::Serial,
+    slave::{RegisterMap, Slave},
+    timing::Timing,
+};
+
+/// A `Usart` behind an RS-485 transceiver whose DE and RE pins are driven
+/// by `direction`, as the serial line of a `Master` or `Slave`
+pub struct Rs485<'b, U, G> {
+    usart: &'b mut U,
+    gpio: &'b mut G,
+    direction: Pin,
+}
+
+impl<'a, 'b, U: Usart<'a>, G: Gpio<'a>> Rs485<'b, U, G> {
+    /// Make `direction` an output and set the transceiver to receive
+    pub fn new(usart: &'b mut U, gpio: &'b mut G, direction: Pin) -> Result<Self, i32> {
+        gpio.set_direction(direction, Direction::Output)?;
+        gpio.set_output(direction, false);
+        Ok(Self {
+            usart,
+            gpio,
+            direction,
+        })
+    }
+}
+
+impl<'a, U: Usart<'a>, G: Gpio<'a>> Serial for Rs485<'_, U, G> {
+    fn send(&mut self, bytes: &[u8]) -> Result<(), i32> {
+        self.gpio.set_output(self.direction, true);
+        let result = self.usart.send(bytes);
+        self.gpio.set_output(self.direction, false);
+        result
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        self.usart.read_byte()
+    }
+
+    fn now_us(&self) -> u64 {
+        clock::now().as_micros()
+    }
+}
@@ -1,2 +0,0 @@
-// USART driver implementation for STM32F103
-// TODO: Implement USART driver functions
@@ -1,2 +0,0 @@
-// USART driver implementation for STM32F401
-// TODO: Implement USART driver functions
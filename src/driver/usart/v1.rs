@@ -1,22 +1,24 @@
-#[cfg(feature = "stm32f407")]
 extern crate alloc;
 
 use super::{
-    ClockPhase, ClockPolarity, Config, DataBits, Event, FlowControl, Mode, ModemControl,
-    ModemStatus, Parity, Result, Status, Usart,
+    AsyncUsart, ClockPhase, ClockPolarity, Config, DataBits, Event, FlowControl, Mode,
+    ModemControl, ModemStatus, Parity, Result, Status, Usart,
 };
-use crate::mcu::stm32f407::{self, usart::*};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::warn;
+use crate::mcu::device::{self, usart::*};
+use crate::runtime::irq;
 use crate::utils;
 use alloc::boxed::Box;
 use core::ops::FnMut;
 use core::ptr;
 
-/// Default APB bus clock frequencies in Hz.
+/// Default APB bus clock frequencies in Hz (HSI after reset).
 /// Update these to match your clock configuration.
-const PCLK1_HZ: u32 = 16_000_000; // APB1 (USART2/3)
-const PCLK2_HZ: u32 = 16_000_000; // APB2 (USART1)
+const PCLK1_HZ: u32 = device::HSI_HZ; // APB1 (USART2/3)
+const PCLK2_HZ: u32 = device::HSI_HZ; // APB2 (USART1/6)
 
-/// A polling-based USART driver for STM32F407.
+/// A polling-based USART driver for the STM32F1/F4 (v1 IP).
 pub struct UsartDriver<'a> {
     regs: *mut RegisterBlock,
     _callback: Option<Box<dyn FnMut(Event) + 'a>>,
@@ -38,17 +40,79 @@ impl<'a> UsartDriver<'a> {
 
     /// Create a new USART1 driver instance (APB2 clock)
     pub fn new_usart1(config: Config) -> Self {
-        Self::new(stm32f407::USART1_BASEADDR, config)
+        Self::new(device::USART1_BASEADDR, config)
     }
 
     /// Create a new USART2 driver instance (APB1 clock)
     pub fn new_usart2(config: Config) -> Self {
-        Self::new(stm32f407::USART2_BASEADDR, config)
+        Self::new(device::USART2_BASEADDR, config)
     }
 
     /// Create a new USART3 driver instance (APB1 clock)
+    #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
     pub fn new_usart3(config: Config) -> Self {
-        Self::new(stm32f407::USART3_BASEADDR, config)
+        Self::new(device::USART3_BASEADDR, config)
+    }
+
+    /// Create a new USART6 driver instance (APB2 clock)
+    #[cfg(not(feature = "stm32f103"))]
+    pub fn new_usart6(config: Config) -> Self {
+        Self::new(device::USART6_BASEADDR, config)
+    }
+
+    /// Route TX and RX of this USART to the given pins using the
+    /// alternate function map. Fails if a pin cannot carry the signal.
+    pub fn configure_pins(&mut self, tx: impl Into<Pin>, rx: impl Into<Pin>) -> Result<()> {
+        let (tx_signal, rx_signal) = self.pin_signals().ok_or(-1)?;
+        configure_alternate(
+            tx.into(),
+            tx_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::High,
+        )?;
+        configure_alternate(
+            rx.into(),
+            rx_signal,
+            OutputMode::PushPull,
+            PullResistor::PullUp,
+            Speed::High,
+        )
+    }
+
+    /// TX/RX alternate function signals of this instance
+    fn pin_signals(&self) -> Option<(AfSignal, AfSignal)> {
+        match self.regs as u32 {
+            device::USART1_BASEADDR => Some((AfSignal::USART1_TX, AfSignal::USART1_RX)),
+            device::USART2_BASEADDR => Some((AfSignal::USART2_TX, AfSignal::USART2_RX)),
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::USART3_BASEADDR => Some((AfSignal::USART3_TX, AfSignal::USART3_RX)),
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::UART4_BASEADDR => Some((AfSignal::UART4_TX, AfSignal::UART4_RX)),
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::UART5_BASEADDR => Some((AfSignal::UART5_TX, AfSignal::UART5_RX)),
+            #[cfg(not(feature = "stm32f103"))]
+            device::USART6_BASEADDR => Some((AfSignal::USART6_TX, AfSignal::USART6_RX)),
+            _ => None,
+        }
+    }
+
+    /// NVIC interrupt line of this instance
+    fn irqn(&self) -> Option<u32> {
+        let irqn = match self.regs as u32 {
+            device::USART1_BASEADDR => device::IRQn::USART1,
+            device::USART2_BASEADDR => device::IRQn::USART2,
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::USART3_BASEADDR => device::IRQn::USART3,
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::UART4_BASEADDR => device::IRQn::UART4,
+            #[cfg(any(feature = "stm32f407", feature = "stm32f103"))]
+            device::UART5_BASEADDR => device::IRQn::UART5,
+            #[cfg(not(feature = "stm32f103"))]
+            device::USART6_BASEADDR => device::IRQn::USART6,
+            _ => return None,
+        };
+        Some(irqn as u32)
     }
 
     fn regs(&self) -> &mut RegisterBlock {
@@ -56,8 +120,12 @@ impl<'a> UsartDriver<'a> {
     }
 
     fn is_on_apb2(&self) -> bool {
-        let base = self.regs as u32;
-        base == stm32f407::USART1_BASEADDR
+        match self.regs as u32 {
+            device::USART1_BASEADDR => true,
+            #[cfg(not(feature = "stm32f103"))]
+            device::USART6_BASEADDR => true,
+            _ => false,
+        }
     }
 
     fn compute_brr(&self, baudrate: u32) -> u32 {
@@ -103,6 +171,28 @@ impl<'a> UsartDriver<'a> {
     fn wait_tc(&self) {
         while unsafe { ptr::read_volatile(&self.regs().sr) } & SR_TC_MASK == 0 {}
     }
+
+    /// Sleep until `flag` is set in SR, with the interrupt `irq_mask` of CR1
+    /// enabled while waiting.
+    async fn wait_flag_async(&self, flag: u32, irq_mask: u32) -> Result<()> {
+        let irqn = self.irqn().ok_or(-1)?;
+        let regs = self.regs;
+        irq::wait_for(
+            irqn,
+            || unsafe { ptr::read_volatile(&(*regs).sr) } & flag != 0,
+            |enable| unsafe {
+                let cr1 = ptr::read_volatile(&(*regs).cr1);
+                let cr1 = if enable {
+                    cr1 | irq_mask
+                } else {
+                    cr1 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr1, cr1);
+            },
+        )
+        .await;
+        Ok(())
+    }
 }
 
 impl<'a> Usart<'a> for UsartDriver<'a> {
@@ -141,7 +231,6 @@ impl<'a> Usart<'a> for UsartDriver<'a> {
                 | CR1_PS_MASK
                 | CR1_TE_MASK
                 | CR1_RE_MASK
-                | CR1_OVER8_MASK
                 | CR1_RWU_MASK
                 | CR1_SBK_MASK
                 | CR1_IDLEIE_MASK
@@ -150,6 +239,12 @@ impl<'a> Usart<'a> for UsartDriver<'a> {
                 | CR1_TXEIE_MASK
                 | CR1_PEIE_MASK);
 
+            // Oversampling by 16 (OVER8 does not exist on the F1)
+            #[cfg(not(feature = "stm32f103"))]
+            {
+                v &= !CR1_OVER8_MASK;
+            }
+
             // Word length
             v = match config.data_bits {
                 DataBits::Bits9 => utils::set_bit(v, CR1_M_POS, true),
@@ -200,7 +295,10 @@ impl<'a> Usart<'a> for UsartDriver<'a> {
         // Reject unsupported data bits (anything other than 8 or 9)
         match config.data_bits {
             DataBits::Bits8 | DataBits::Bits9 => {}
-            _ => return Err(-1),
+            _ => {
+                warn!("unsupported data bits, only 8 or 9");
+                return Err(-1);
+            }
         }
 
         // CR2: STOP bits, clock settings for synchronous mode
@@ -311,6 +409,16 @@ impl<'a> Usart<'a> for UsartDriver<'a> {
         Ok(())
     }
 
+    fn read_byte(&mut self) -> Option<u8> {
+        // Reading SR then DR also clears an overrun
+        let sr = unsafe { ptr::read_volatile(&self.regs().sr) };
+        if sr & SR_RXNE_MASK == 0 {
+            return None;
+        }
+        self.rx_count = 1;
+        Some(unsafe { ptr::read_volatile(&self.regs().dr) as u8 })
+    }
+
     fn transfer(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
         if data_in.len() != data_out.len() {
             return Err(-1);
@@ -416,3 +524,56 @@ impl<'a> Usart<'a> for UsartDriver<'a> {
         Ok(())
     }
 }
+
+impl AsyncUsart for UsartDriver<'_> {
+    async fn send_async(&mut self, data: &[u8]) -> Result<()> {
+        self.tx_count = 0;
+        for &byte in data {
+            self.wait_flag_async(SR_TXE_MASK, CR1_TXEIE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, byte as u32) };
+            self.tx_count += 1;
+        }
+        self.wait_flag_async(SR_TC_MASK, CR1_TCIE_MASK).await?;
+        if let Some(cb) = &mut self._callback {
+            cb(Event::SEND_COMPLETE | Event::TX_COMPLETE);
+        }
+        Ok(())
+    }
+
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()> {
+        self.rx_count = 0;
+        for b in data.iter_mut() {
+            self.wait_flag_async(SR_RXNE_MASK, CR1_RXNEIE_MASK).await?;
+            *b = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.rx_count += 1;
+        }
+        if let Some(cb) = &mut self._callback {
+            cb(Event::RECEIVE_COMPLETE);
+        }
+        Ok(())
+    }
+
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            return Err(-1);
+        }
+
+        self.tx_count = 0;
+        self.rx_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_flag_async(SR_TXE_MASK, CR1_TXEIE_MASK).await?;
+            unsafe { ptr::write_volatile(&mut self.regs().dr, byte as u32) };
+            self.tx_count += 1;
+
+            self.wait_flag_async(SR_RXNE_MASK, CR1_RXNEIE_MASK).await?;
+            data_in[i] = unsafe { ptr::read_volatile(&self.regs().dr) as u8 };
+            self.rx_count += 1;
+        }
+        self.wait_flag_async(SR_TC_MASK, CR1_TCIE_MASK).await?;
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,508 @@
+extern crate alloc;
+
+use super::{
+    AsyncUsart, ClockPhase, ClockPolarity, Config, DataBits, Event, FlowControl, Mode,
+    ModemControl, ModemStatus, Parity, Result, Status, Usart,
+};
+use crate::driver::gpio::{AfSignal, OutputMode, Pin, PullResistor, Speed, configure_alternate};
+use crate::logging::warn;
+use crate::mcu::device::{self, usart::*};
+use crate::runtime::irq;
+use crate::utils;
+use alloc::boxed::Box;
+use core::ops::FnMut;
+use core::ptr;
+
+/// Default APB clock frequency in Hz (HSI16 after reset).
+/// Update this to match your clock configuration.
+const PCLK_HZ: u32 = device::HSI_HZ;
+
+/// A polling-based USART driver for the STM32G0 (v2 IP).
+pub struct UsartDriver<'a> {
+    regs: *mut RegisterBlock,
+    _callback: Option<Box<dyn FnMut(Event) + 'a>>,
+    config: Config,
+    tx_count: u32,
+    rx_count: u32,
+}
+
+impl<'a> UsartDriver<'a> {
+    pub fn new(usart_base_addr: u32, config: Config) -> Self {
+        Self {
+            regs: usart_base_addr as *mut RegisterBlock,
+            _callback: None,
+            config,
+            tx_count: 0,
+            rx_count: 0,
+        }
+    }
+
+    /// Create a new USART1 driver instance
+    pub fn new_usart1(config: Config) -> Self {
+        Self::new(device::USART1_BASEADDR, config)
+    }
+
+    /// Create a new USART2 driver instance
+    pub fn new_usart2(config: Config) -> Self {
+        Self::new(device::USART2_BASEADDR, config)
+    }
+
+    /// Route TX and RX of this USART to the given pins using the
+    /// alternate function map. Fails if a pin cannot carry the signal.
+    pub fn configure_pins(&mut self, tx: impl Into<Pin>, rx: impl Into<Pin>) -> Result<()> {
+        let (tx_signal, rx_signal) = self.pin_signals().ok_or(-1)?;
+        configure_alternate(
+            tx.into(),
+            tx_signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::High,
+        )?;
+        configure_alternate(
+            rx.into(),
+            rx_signal,
+            OutputMode::PushPull,
+            PullResistor::PullUp,
+            Speed::High,
+        )
+    }
+
+    /// TX/RX alternate function signals of this instance
+    fn pin_signals(&self) -> Option<(AfSignal, AfSignal)> {
+        match self.regs as u32 {
+            device::USART1_BASEADDR => Some((AfSignal::USART1_TX, AfSignal::USART1_RX)),
+            device::USART2_BASEADDR => Some((AfSignal::USART2_TX, AfSignal::USART2_RX)),
+            _ => None,
+        }
+    }
+
+    /// NVIC interrupt line of this instance
+    fn irqn(&self) -> Option<u32> {
+        let irqn = match self.regs as u32 {
+            device::USART1_BASEADDR => device::IRQn::USART1,
+            device::USART2_BASEADDR => device::IRQn::USART2,
+            _ => return None,
+        };
+        Some(irqn as u32)
+    }
+
+    fn regs(&self) -> &mut RegisterBlock {
+        unsafe { &mut *self.regs }
+    }
+
+    fn compute_brr(&self, baudrate: u32) -> u32 {
+        // Oversampling by 16 (OVER8 = 0): BRR = usartdiv = pclk / baud (rounded)
+        (PCLK_HZ + (baudrate / 2)) / baudrate
+    }
+
+    fn write_cr1(&mut self, f: impl FnOnce(u32) -> u32) {
+        let mut v = unsafe { ptr::read_volatile(&self.regs().cr1) };
+        v = f(v);
+        unsafe { ptr::write_volatile(&mut self.regs().cr1, v) };
+    }
+
+    fn write_cr2(&mut self, f: impl FnOnce(u32) -> u32) {
+        let mut v = unsafe { ptr::read_volatile(&self.regs().cr2) };
+        v = f(v);
+        unsafe { ptr::write_volatile(&mut self.regs().cr2, v) };
+    }
+
+    fn write_cr3(&mut self, f: impl FnOnce(u32) -> u32) {
+        let mut v = unsafe { ptr::read_volatile(&self.regs().cr3) };
+        v = f(v);
+        unsafe { ptr::write_volatile(&mut self.regs().cr3, v) };
+    }
+
+    fn isr(&self) -> u32 {
+        unsafe { ptr::read_volatile(&self.regs().isr) }
+    }
+
+    fn wait_txe(&self) {
+        while self.isr() & ISR_TXE_MASK == 0 {}
+    }
+
+    fn wait_rxne(&self) {
+        while self.isr() & ISR_RXNE_MASK == 0 {}
+    }
+
+    fn wait_tc(&self) {
+        while self.isr() & ISR_TC_MASK == 0 {}
+    }
+
+    fn write_tdr(&mut self, byte: u8) {
+        unsafe { ptr::write_volatile(&mut self.regs().tdr, byte as u32) };
+    }
+
+    fn read_rdr(&self) -> u8 {
+        unsafe { ptr::read_volatile(&self.regs().rdr) as u8 }
+    }
+
+    /// Sleep until `flag` is set in ISR, with the interrupt `irq_mask` of
+    /// CR1 enabled while waiting.
+    async fn wait_flag_async(&self, flag: u32, irq_mask: u32) -> Result<()> {
+        let irqn = self.irqn().ok_or(-1)?;
+        let regs = self.regs;
+        irq::wait_for(
+            irqn,
+            || unsafe { ptr::read_volatile(&(*regs).isr) } & flag != 0,
+            |enable| unsafe {
+                let cr1 = ptr::read_volatile(&(*regs).cr1);
+                let cr1 = if enable {
+                    cr1 | irq_mask
+                } else {
+                    cr1 & !irq_mask
+                };
+                ptr::write_volatile(&mut (*regs).cr1, cr1);
+            },
+        )
+        .await;
+        Ok(())
+    }
+}
+
+impl<'a> Usart<'a> for UsartDriver<'a> {
+    fn initialize(&mut self, callback: impl FnMut(Event) + 'a) -> Result<()> {
+        self._callback = Some(Box::new(callback));
+
+        // Disable USART before configuration
+        self.write_cr1(|v| utils::set_bit(v, CR1_UE_POS, false));
+
+        // Apply configuration
+        let cfg = self.config.clone();
+        self.configure(&cfg)?;
+
+        // Enable USART
+        self.write_cr1(|v| utils::set_bit(v, CR1_UE_POS, true));
+        Ok(())
+    }
+
+    fn uninitialize(&mut self) -> Result<()> {
+        self.write_cr1(|v| utils::set_bit(v, CR1_UE_POS, false));
+        self._callback = None;
+        Ok(())
+    }
+
+    fn configure(&mut self, config: &Config) -> Result<()> {
+        // Word length M[1:0]: 00 = 8 bits, 01 = 9 bits, 10 = 7 bits
+        let (m1, m0) = match config.data_bits {
+            DataBits::Bits7 => (true, false),
+            DataBits::Bits8 => (false, false),
+            DataBits::Bits9 => (false, true),
+            _ => {
+                warn!("unsupported data bits, only 7, 8 or 9");
+                return Err(-1);
+            }
+        };
+
+        self.config = config.clone();
+
+        // Disable before reconfig, most CR1/CR2/CR3 fields are locked while UE = 1
+        self.write_cr1(|v| utils::set_bit(v, CR1_UE_POS, false));
+
+        // CR1: word length, parity, RX/TX enable, oversampling by 16
+        self.write_cr1(|mut v| {
+            v &= !(CR1_M1_MASK
+                | CR1_M0_MASK
+                | CR1_PCE_MASK
+                | CR1_PS_MASK
+                | CR1_TE_MASK
+                | CR1_RE_MASK
+                | CR1_OVER8_MASK
+                | CR1_FIFOEN_MASK
+                | CR1_IDLEIE_MASK
+                | CR1_RXNEIE_MASK
+                | CR1_TCIE_MASK
+                | CR1_TXEIE_MASK
+                | CR1_PEIE_MASK);
+            v = utils::set_bit(v, CR1_M1_POS, m1);
+            v = utils::set_bit(v, CR1_M0_POS, m0);
+
+            // Parity
+            match config.parity {
+                Parity::None => {}
+                Parity::Even => {
+                    v = utils::set_bit(v, CR1_PCE_POS, true);
+                }
+                Parity::Odd => {
+                    v = utils::set_bit(v, CR1_PCE_POS, true);
+                    v = utils::set_bit(v, CR1_PS_POS, true);
+                }
+            }
+
+            // Every mode uses both directions
+            v = utils::set_bit(v, CR1_TE_POS, true);
+            v = utils::set_bit(v, CR1_RE_POS, true);
+            v
+        });
+
+        // CR2: STOP bits, clock settings for synchronous mode
+        self.write_cr2(|mut v| {
+            v &= !(CR2_STOP_MASK | CR2_CLKEN_MASK | CR2_CPOL_MASK | CR2_CPHA_MASK | CR2_LBCL_MASK);
+
+            // STOP bits
+            let stop_bits_val = match config.stop_bits {
+                super::StopBits::Bits1 => 0,
+                super::StopBits::Bits0_5 => 1,
+                super::StopBits::Bits2 => 2,
+                super::StopBits::Bits1_5 => 3,
+            } as u32;
+            v = utils::set_bits(v, stop_bits_val, CR2_STOP_POS, CR2_STOP_WIDTH);
+
+            // Synchronous clock settings when required
+            let sync_mode = matches!(
+                config.mode,
+                Mode::SynchronousMaster | Mode::SynchronousSlave
+            );
+            v = utils::set_bit(v, CR2_CLKEN_POS, sync_mode);
+            if sync_mode {
+                v = utils::set_bit(
+                    v,
+                    CR2_CPOL_POS,
+                    matches!(config.clock_polarity, ClockPolarity::CPOL1),
+                );
+                v = utils::set_bit(
+                    v,
+                    CR2_CPHA_POS,
+                    matches!(config.clock_phase, ClockPhase::CPHA1),
+                );
+            }
+
+            v
+        });
+
+        // CR3: hardware flow control and special modes
+        self.write_cr3(|mut v| {
+            v &= !(CR3_RTSE_MASK | CR3_CTSE_MASK | CR3_HDSEL_MASK | CR3_IREN_MASK | CR3_SCEN_MASK);
+
+            match config.flow_control {
+                FlowControl::None => {}
+                FlowControl::RTS => v |= CR3_RTSE_MASK,
+                FlowControl::CTS => v |= CR3_CTSE_MASK,
+                FlowControl::RTS_CTS => v |= CR3_RTSE_MASK | CR3_CTSE_MASK,
+            }
+
+            match config.mode {
+                Mode::SingleWire => v |= CR3_HDSEL_MASK,
+                Mode::IrDA => v |= CR3_IREN_MASK,
+                Mode::SmartCard => v |= CR3_SCEN_MASK,
+                _ => {}
+            }
+
+            v
+        });
+
+        // Baud rate (USART kernel clock prescaler left at /1)
+        let brr = self.compute_brr(config.baudrate);
+        if brr < 16 || brr > BRR_BRR_MASK {
+            // Baud rate out of range for the kernel clock
+            warn!("baud rate {} out of range", config.baudrate);
+            return Err(-1);
+        }
+        unsafe {
+            ptr::write_volatile(&mut self.regs().presc, 0);
+            ptr::write_volatile(&mut self.regs().brr, brr);
+        }
+
+        // Re-enable USART
+        self.write_cr1(|v| utils::set_bit(v, CR1_UE_POS, true));
+        Ok(())
+    }
+
+    fn send(&mut self, data: &[u8]) -> Result<()> {
+        self.tx_count = 0;
+        for &byte in data {
+            self.wait_txe();
+            self.write_tdr(byte);
+            self.tx_count += 1;
+        }
+        self.wait_tc();
+        if let Some(cb) = &mut self._callback {
+            cb(Event::SEND_COMPLETE | Event::TX_COMPLETE);
+        }
+        Ok(())
+    }
+
+    fn receive(&mut self, data: &mut [u8]) -> Result<()> {
+        self.rx_count = 0;
+        for b in data.iter_mut() {
+            self.wait_rxne();
+            *b = self.read_rdr();
+            self.rx_count += 1;
+        }
+        if let Some(cb) = &mut self._callback {
+            cb(Event::RECEIVE_COMPLETE);
+        }
+        Ok(())
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let isr = self.isr();
+        // An overrun stops reception until cleared
+        if isr & ISR_ORE_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut self.regs().icr, ICR_ORECF_MASK) };
+        }
+        if isr & ISR_RXNE_MASK == 0 {
+            return None;
+        }
+        self.rx_count = 1;
+        Some(self.read_rdr())
+    }
+
+    fn transfer(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            return Err(-1);
+        }
+
+        self.tx_count = 0;
+        self.rx_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_txe();
+            self.write_tdr(byte);
+            self.tx_count += 1;
+
+            self.wait_rxne();
+            data_in[i] = self.read_rdr();
+            self.rx_count += 1;
+        }
+        self.wait_tc();
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+        Ok(())
+    }
+
+    fn get_tx_count(&self) -> u32 {
+        self.tx_count
+    }
+
+    fn get_rx_count(&self) -> u32 {
+        self.rx_count
+    }
+
+    fn get_status(&self) -> Status {
+        let isr = self.isr();
+        Status {
+            tx_busy: (isr & ISR_TC_MASK) == 0,
+            rx_busy: (isr & ISR_BUSY_MASK) != 0,
+            tx_underflow: (isr & ISR_UDR_MASK) != 0,
+            rx_overflow: (isr & ISR_ORE_MASK) != 0,
+            rx_break: (isr & ISR_LBDF_MASK) != 0,
+            rx_framing_error: (isr & ISR_FE_MASK) != 0,
+            rx_parity_error: (isr & ISR_PE_MASK) != 0,
+        }
+    }
+
+    fn set_modem_control(&mut self, control: ModemControl) -> Result<()> {
+        match control {
+            // RTS is driven by hardware flow control and there is no DTR line
+            ModemControl::RTSClear
+            | ModemControl::RTSSet
+            | ModemControl::DTRClear
+            | ModemControl::DTRSet => Err(-2),
+        }
+    }
+
+    fn get_modem_status(&self) -> ModemStatus {
+        ModemStatus {
+            cts: (self.isr() & ISR_CTS_MASK) != 0,
+            dsr: false,
+            dcd: false,
+            ri: false,
+        }
+    }
+
+    fn tx_enable(&mut self, enable: bool) -> Result<()> {
+        self.write_cr1(|v| utils::set_bit(v, CR1_TE_POS, enable));
+        Ok(())
+    }
+
+    fn rx_enable(&mut self, enable: bool) -> Result<()> {
+        self.write_cr1(|v| utils::set_bit(v, CR1_RE_POS, enable));
+        Ok(())
+    }
+
+    fn control_break(&mut self, enable: bool) -> Result<()> {
+        // A break request is a one-shot; SBKF clears when the break is sent
+        if enable {
+            unsafe { ptr::write_volatile(&mut self.regs().rqr, RQR_SBKRQ_MASK) };
+        }
+        Ok(())
+    }
+
+    fn abort_send(&mut self) -> Result<()> {
+        self.write_cr1(|v| utils::set_bit(v, CR1_TE_POS, false));
+        Ok(())
+    }
+
+    fn abort_receive(&mut self) -> Result<()> {
+        // Disable the receiver and drop pending data and error flags
+        self.write_cr1(|v| utils::set_bit(v, CR1_RE_POS, false));
+        unsafe {
+            ptr::write_volatile(&mut self.regs().rqr, RQR_RXFRQ_MASK);
+            ptr::write_volatile(
+                &mut self.regs().icr,
+                ICR_ORECF_MASK | ICR_FECF_MASK | ICR_NECF_MASK | ICR_PECF_MASK,
+            );
+        }
+        Ok(())
+    }
+
+    fn abort_transfer(&mut self) -> Result<()> {
+        self.abort_send()?;
+        self.abort_receive()
+    }
+}
+
+impl AsyncUsart for UsartDriver<'_> {
+    async fn send_async(&mut self, data: &[u8]) -> Result<()> {
+        self.tx_count = 0;
+        for &byte in data {
+            self.wait_flag_async(ISR_TXE_MASK, CR1_TXEIE_MASK).await?;
+            self.write_tdr(byte);
+            self.tx_count += 1;
+        }
+        self.wait_flag_async(ISR_TC_MASK, CR1_TCIE_MASK).await?;
+        if let Some(cb) = &mut self._callback {
+            cb(Event::SEND_COMPLETE | Event::TX_COMPLETE);
+        }
+        Ok(())
+    }
+
+    async fn receive_async(&mut self, data: &mut [u8]) -> Result<()> {
+        self.rx_count = 0;
+        for b in data.iter_mut() {
+            self.wait_flag_async(ISR_RXNE_MASK, CR1_RXNEIE_MASK).await?;
+            *b = self.read_rdr();
+            self.rx_count += 1;
+        }
+        if let Some(cb) = &mut self._callback {
+            cb(Event::RECEIVE_COMPLETE);
+        }
+        Ok(())
+    }
+
+    async fn transfer_async(&mut self, data_out: &[u8], data_in: &mut [u8]) -> Result<()> {
+        if data_in.len() != data_out.len() {
+            return Err(-1);
+        }
+
+        self.tx_count = 0;
+        self.rx_count = 0;
+
+        for (i, &byte) in data_out.iter().enumerate() {
+            self.wait_flag_async(ISR_TXE_MASK, CR1_TXEIE_MASK).await?;
+            self.write_tdr(byte);
+            self.tx_count += 1;
+
+            self.wait_flag_async(ISR_RXNE_MASK, CR1_RXNEIE_MASK).await?;
+            data_in[i] = self.read_rdr();
+            self.rx_count += 1;
+        }
+        self.wait_flag_async(ISR_TC_MASK, CR1_TCIE_MASK).await?;
+        if let Some(cb) = &mut self._callback {
+            cb(Event::TRANSFER_COMPLETE);
+        }
+        Ok(())
+    }
+}
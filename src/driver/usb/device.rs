@@ -1 +1,498 @@
+//! # USB Device Controller
+//!
+//! The OTG_FS core of the STM32F4 in device mode, full speed on PA11 (D-)
+//! and PA12 (D+), as the `Bus` of the `usb` stack. It is polled: `poll`
+//! reads the core interrupt flags, so the OTG interrupt stays disabled and
+//! the application calls `Device::poll` from its loop or a task.
+//!
+//! The core runs from the 48 MHz PLL output `otg` starts. Without a
+//! crystal the HSI feeds the PLL, and enumeration works with most hosts but
+//! not all.
+//!
+//! VBUS sensing is off: the device attaches as soon as it is created, and
+//! suspends when the cable is pulled rather than detaching.
+//!
+//! Each endpoint holds one packet either way: IN packets go straight to the
+//! TX FIFO of their endpoint, and an OUT packet is copied out of the RX FIFO
+//! and the endpoint left NAKing until `read` takes it.
 
+use super::otg::{self, wait_until};
+use crate::mcu::device::otg_fs::*;
+use core::ptr;
+use usb::bus::{Bus, Event};
+use usb::endpoint::{self, EndpointType};
+
+/// Largest full-speed packet of the endpoints the driver opens
+pub const MAX_PACKET_SIZE: usize = 64;
+
+/// Words of FIFO RAM for received packets, shared by all OUT endpoints
+const RX_FIFO_WORDS: u32 = 128;
+/// Words of TX FIFO for each IN endpoint
+const TX_FIFO_WORDS: u32 = (FIFO_RAM_WORDS - RX_FIFO_WORDS) / DEVICE_ENDPOINTS as u32;
+
+/// An OUT packet copied out of the RX FIFO
+#[derive(Clone, Copy)]
+struct Packet {
+    data: [u8; MAX_PACKET_SIZE],
+    len: usize,
+    full: bool,
+}
+
+const EMPTY: Packet = Packet {
+    data: [0; MAX_PACKET_SIZE],
+    len: 0,
+    full: false,
+};
+
+/// OTG_FS in device mode
+pub struct UsbBus {
+    regs: *mut RegisterBlock,
+    /// Packet sizes of the open endpoints, OUT then IN
+    max_packet_size: [[u16; DEVICE_ENDPOINTS]; 2],
+    setup: [u8; 8],
+    out: [Packet; DEVICE_ENDPOINTS],
+    in_busy: [bool; DEVICE_ENDPOINTS],
+}
+
+impl UsbBus {
+    /// Clock, reset and attach the core. Returns Err(-3) if a clock does
+    /// not start or the core does not come out of reset.
+    pub fn new() -> Result<Self, i32> {
+        let mut bus = Self {
+            regs: otg::init(GUSBCFG_FDMOD_MASK)?,
+            max_packet_size: [[0; DEVICE_ENDPOINTS]; 2],
+            setup: [0; 8],
+            out: [EMPTY; DEVICE_ENDPOINTS],
+            in_busy: [false; DEVICE_ENDPOINTS],
+        };
+        bus.init_core()?;
+        Ok(bus)
+    }
+
+    fn init_core(&mut self) -> Result<(), i32> {
+        let regs = self.regs;
+        unsafe {
+            // Detached while being set up
+            ptr::write_volatile(&mut (*regs).dctl, DCTL_SDIS_MASK);
+            ptr::write_volatile(&mut (*regs).dcfg, DCFG_DSPD_FULL_SPEED);
+
+            ptr::write_volatile(&mut (*regs).grxfsiz, RX_FIFO_WORDS);
+            for number in 0..DEVICE_ENDPOINTS as u32 {
+                let size = (TX_FIFO_WORDS << DIEPTXF_INEPTXFD_POS)
+                    | (RX_FIFO_WORDS + number * TX_FIFO_WORDS);
+                if number == 0 {
+                    ptr::write_volatile(&mut (*regs).dieptxf0, size);
+                } else {
+                    ptr::write_volatile(&mut (*regs).dieptxf[number as usize - 1], size);
+                }
+            }
+        }
+        otg::flush_fifos(self.regs)?;
+
+        let regs = self.regs;
+        unsafe {
+            // The flags are polled; GAHBCFG.GINT stays clear so none
+            // reaches the NVIC
+            ptr::write_volatile(&mut (*regs).diepmsk, DIEPMSK_XFRCM_MASK);
+            ptr::write_volatile(
+                &mut (*regs).doepmsk,
+                DOEPMSK_XFRCM_MASK | DOEPMSK_STUPM_MASK,
+            );
+            ptr::write_volatile(&mut (*regs).daintmsk, 0);
+            ptr::write_volatile(&mut (*regs).gintsts, u32::MAX);
+            ptr::write_volatile(
+                &mut (*regs).gintmsk,
+                GINTSTS_USBRST_MASK
+                    | GINTSTS_ENUMDNE_MASK
+                    | GINTSTS_RXFLVL_MASK
+                    | GINTSTS_IEPINT_MASK
+                    | GINTSTS_OEPINT_MASK
+                    | GINTSTS_USBSUSP_MASK
+                    | GINTSTS_WKUPINT_MASK,
+            );
+            // Attach
+            ptr::write_volatile(&mut (*regs).dctl, 0);
+        }
+        Ok(())
+    }
+
+    /// Let OUT endpoint `number` take one more packet
+    fn arm_out(&mut self, number: usize) {
+        let size = self.max_packet_size[0][number] as u32;
+        let regs = self.regs;
+        let setups = if number == 0 {
+            3 << DOEPTSIZ_STUPCNT_POS
+        } else {
+            0
+        };
+        unsafe {
+            ptr::write_volatile(
+                &mut (*regs).doep[number].doeptsiz,
+                setups | (1 << DOEPTSIZ_PKTCNT_POS) | size,
+            );
+            let ctl = ptr::read_volatile(&(*regs).doep[number].doepctl);
+            ptr::write_volatile(
+                &mut (*regs).doep[number].doepctl,
+                ctl | DOEPCTL_EPENA_MASK | DOEPCTL_CNAK_MASK,
+            );
+        }
+    }
+
+    /// Forget every endpoint but 0 after a bus reset
+    fn bus_reset(&mut self) {
+        let regs = self.regs;
+        unsafe {
+            for number in 0..DEVICE_ENDPOINTS {
+                ptr::write_volatile(&mut (*regs).doep[number].doepctl, DOEPCTL_SNAK_MASK);
+                ptr::write_volatile(&mut (*regs).diep[number].diepint, u32::MAX);
+                ptr::write_volatile(&mut (*regs).doep[number].doepint, u32::MAX);
+            }
+            ptr::write_volatile(&mut (*regs).daintmsk, 0);
+            let dcfg = ptr::read_volatile(&(*regs).dcfg) & !DCFG_DAD_MASK;
+            ptr::write_volatile(&mut (*regs).dcfg, dcfg);
+        }
+        let _ = otg::flush_fifos(self.regs);
+        self.max_packet_size = [[0; DEVICE_ENDPOINTS]; 2];
+        self.out = [EMPTY; DEVICE_ENDPOINTS];
+        self.in_busy = [false; DEVICE_ENDPOINTS];
+    }
+
+    /// Take an entry of the RX FIFO: returns the SETUP packet once it is
+    /// complete, or the endpoint that received a packet
+    fn pop_rx(&mut self) -> Option<Event> {
+        let status = unsafe { ptr::read_volatile(&(*self.regs).grxstsp) };
+        let number = ((status & GRXSTSP_EPNUM_MASK) >> GRXSTSP_EPNUM_POS) as usize;
+        let len = ((status & GRXSTSP_BCNT_MASK) >> GRXSTSP_BCNT_POS) as usize;
+        match status & GRXSTSP_PKTSTS_MASK {
+            GRXSTSP_PKTSTS_SETUP_DATA => {
+                let mut setup = [0; 8];
+                otg::read_fifo(self.regs, &mut setup, len);
+                self.setup = setup;
+                None
+            }
+            GRXSTSP_PKTSTS_SETUP_COMPLETE => {
+                self.arm_out(0);
+                Some(Event::Setup(self.setup))
+            }
+            GRXSTSP_PKTSTS_OUT_DATA if number < DEVICE_ENDPOINTS => {
+                let mut data = [0; MAX_PACKET_SIZE];
+                otg::read_fifo(self.regs, &mut data, len);
+                self.out[number] = Packet {
+                    data,
+                    len: len.min(MAX_PACKET_SIZE),
+                    full: true,
+                };
+                Some(Event::Out(number as u8))
+            }
+            _ => None,
+        }
+    }
+
+    /// The first IN endpoint whose packet went out, its flag cleared
+    fn in_complete(&mut self) -> Option<Event> {
+        let regs = self.regs;
+        let daint = unsafe { ptr::read_volatile(&(*regs).daint) } & DAINT_IEPINT_MASK;
+        let number = (0..DEVICE_ENDPOINTS).find(|number| daint & (1 << number) != 0)?;
+        unsafe {
+            let flags = ptr::read_volatile(&(*regs).diep[number].diepint);
+            ptr::write_volatile(&mut (*regs).diep[number].diepint, flags);
+            if flags & DIEPINT_XFRC_MASK == 0 {
+                return None;
+            }
+        }
+        self.in_busy[number] = false;
+        Some(Event::InComplete(endpoint::address(number as u8, true)))
+    }
+}
+
+fn in_type(endpoint_type: EndpointType) -> u32 {
+    match endpoint_type {
+        EndpointType::Control => DIEPCTL_EPTYP_CONTROL,
+        EndpointType::Isochronous => DIEPCTL_EPTYP_ISOCHRONOUS,
+        EndpointType::Bulk => DIEPCTL_EPTYP_BULK,
+        EndpointType::Interrupt => DIEPCTL_EPTYP_INTERRUPT,
+    }
+}
+
+/// MPSIZ of endpoint 0, which takes a code rather than the size
+fn ep0_size(max_packet_size: u16) -> Option<u32> {
+    match max_packet_size {
+        64 => Some(DEPCTL0_MPSIZ_64),
+        32 => Some(DEPCTL0_MPSIZ_32),
+        16 => Some(DEPCTL0_MPSIZ_16),
+        8 => Some(DEPCTL0_MPSIZ_8),
+        _ => None,
+    }
+}
+
+impl Bus for UsbBus {
+    const SET_ADDRESS_BEFORE_STATUS: bool = true;
+
+    fn set_address(&mut self, address: u8) {
+        let regs = self.regs;
+        unsafe {
+            let dcfg = ptr::read_volatile(&(*regs).dcfg) & !DCFG_DAD_MASK;
+            ptr::write_volatile(
+                &mut (*regs).dcfg,
+                dcfg | ((address as u32) << DCFG_DAD_POS) & DCFG_DAD_MASK,
+            );
+        }
+    }
+
+    fn open(
+        &mut self,
+        address: u8,
+        endpoint_type: EndpointType,
+        max_packet_size: u16,
+    ) -> Result<(), i32> {
+        let number = endpoint::number(address) as usize;
+        if number >= DEVICE_ENDPOINTS
+            || max_packet_size as usize > MAX_PACKET_SIZE
+            || (endpoint_type == EndpointType::Control) != (number == 0)
+        {
+            return Err(-1);
+        }
+        let size = if number == 0 {
+            ep0_size(max_packet_size).ok_or(-1)?
+        } else {
+            max_packet_size as u32
+        };
+        let toggle = if number == 0 { 0 } else { DIEPCTL_SD0PID_MASK };
+        let ctl = size | in_type(endpoint_type) | DIEPCTL_USBAEP_MASK | toggle;
+        let direction_in = endpoint::is_in(address);
+        self.max_packet_size[direction_in as usize][number] = max_packet_size;
+        let regs = self.regs;
+        unsafe {
+            let daintmsk = ptr::read_volatile(&(*regs).daintmsk);
+            if direction_in {
+                ptr::write_volatile(
+                    &mut (*regs).diep[number].diepctl,
+                    ctl | ((number as u32) << DIEPCTL_TXFNUM_POS) | DIEPCTL_SNAK_MASK,
+                );
+                ptr::write_volatile(&mut (*regs).daintmsk, daintmsk | (1 << number));
+            } else {
+                ptr::write_volatile(&mut (*regs).doep[number].doepctl, ctl);
+                ptr::write_volatile(
+                    &mut (*regs).daintmsk,
+                    daintmsk | (1 << (number as u32 + DAINT_OEPINT_POS)),
+                );
+            }
+        }
+        if direction_in {
+            self.in_busy[number] = false;
+        } else {
+            self.out[number] = EMPTY;
+            self.arm_out(number);
+        }
+        Ok(())
+    }
+
+    fn close(&mut self, address: u8) {
+        let number = endpoint::number(address) as usize;
+        if number == 0 || number >= DEVICE_ENDPOINTS {
+            return;
+        }
+        let direction_in = endpoint::is_in(address);
+        self.max_packet_size[direction_in as usize][number] = 0;
+        let regs = self.regs;
+        unsafe {
+            let daintmsk = ptr::read_volatile(&(*regs).daintmsk);
+            if direction_in {
+                let ctl = ptr::read_volatile(&(*regs).diep[number].diepctl);
+                if ctl & DIEPCTL_EPENA_MASK != 0 {
+                    ptr::write_volatile(
+                        &mut (*regs).diep[number].diepctl,
+                        ctl | DIEPCTL_EPDIS_MASK | DIEPCTL_SNAK_MASK,
+                    );
+                    let _ = wait_until(|| {
+                        ptr::read_volatile(&(*regs).diep[number].diepint) & DIEPINT_EPDISD_MASK != 0
+                    });
+                }
+                ptr::write_volatile(&mut (*regs).diep[number].diepctl, DIEPCTL_SNAK_MASK);
+                ptr::write_volatile(&mut (*regs).diep[number].diepint, u32::MAX);
+                ptr::write_volatile(
+                    &mut (*regs).grstctl,
+                    GRSTCTL_TXFFLSH_MASK | ((number as u32) << GRSTCTL_TXFNUM_POS),
+                );
+                let _ =
+                    wait_until(|| ptr::read_volatile(&(*regs).grstctl) & GRSTCTL_TXFFLSH_MASK == 0);
+                ptr::write_volatile(&mut (*regs).daintmsk, daintmsk & !(1 << number));
+            } else {
+                // An enabled OUT endpoint is disabled under global OUT NAK
+                let ctl = ptr::read_volatile(&(*regs).doep[number].doepctl);
+                if ctl & DOEPCTL_EPENA_MASK != 0 {
+                    let dctl = ptr::read_volatile(&(*regs).dctl);
+                    ptr::write_volatile(&mut (*regs).dctl, dctl | DCTL_SGONAK_MASK);
+                    let _ = wait_until(|| {
+                        ptr::read_volatile(&(*regs).gintsts) & GINTSTS_GONAKEFF_MASK != 0
+                    });
+                    ptr::write_volatile(
+                        &mut (*regs).doep[number].doepctl,
+                        ctl | DOEPCTL_EPDIS_MASK | DOEPCTL_SNAK_MASK,
+                    );
+                    let _ = wait_until(|| {
+                        ptr::read_volatile(&(*regs).doep[number].doepint) & DOEPINT_EPDISD_MASK != 0
+                    });
+                    let dctl = ptr::read_volatile(&(*regs).dctl);
+                    ptr::write_volatile(&mut (*regs).dctl, dctl | DCTL_CGONAK_MASK);
+                }
+                ptr::write_volatile(&mut (*regs).doep[number].doepctl, DOEPCTL_SNAK_MASK);
+                ptr::write_volatile(&mut (*regs).doep[number].doepint, u32::MAX);
+                ptr::write_volatile(
+                    &mut (*regs).daintmsk,
+                    daintmsk & !(1 << (number as u32 + DAINT_OEPINT_POS)),
+                );
+            }
+        }
+        if direction_in {
+            self.in_busy[number] = false;
+        } else {
+            self.out[number] = EMPTY;
+        }
+    }
+
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), i32> {
+        let number = endpoint::number(address) as usize;
+        let max_packet_size = *self.max_packet_size[1].get(number).ok_or(-1)?;
+        if max_packet_size == 0 || data.len() > max_packet_size as usize {
+            return Err(-1);
+        }
+        if self.in_busy[number] {
+            return Err(-5);
+        }
+        let words = data.len().div_ceil(4) as u32;
+        let regs = self.regs;
+        unsafe {
+            let space = ptr::read_volatile(&(*regs).diep[number].dtxfsts) & DTXFSTS_INEPTFSAV_MASK;
+            if space < words {
+                return Err(-5);
+            }
+            ptr::write_volatile(
+                &mut (*regs).diep[number].dieptsiz,
+                (1 << DIEPTSIZ_PKTCNT_POS) | data.len() as u32,
+            );
+            let ctl = ptr::read_volatile(&(*regs).diep[number].diepctl);
+            ptr::write_volatile(
+                &mut (*regs).diep[number].diepctl,
+                (ctl & !DIEPCTL_EPDIS_MASK) | DIEPCTL_EPENA_MASK | DIEPCTL_CNAK_MASK,
+            );
+        }
+        otg::write_fifo(regs, number, data);
+        self.in_busy[number] = true;
+        Ok(())
+    }
+
+    fn read(&mut self, address: u8, buf: &mut [u8]) -> Result<usize, i32> {
+        let number = endpoint::number(address) as usize;
+        let packet = self.out.get_mut(number).ok_or(-1)?;
+        if !packet.full {
+            return Err(-5);
+        }
+        packet.full = false;
+        let len = packet.len;
+        let fits = buf.len() >= len;
+        if fits {
+            buf[..len].copy_from_slice(&packet.data[..len]);
+        }
+        self.arm_out(number);
+        if fits { Ok(len) } else { Err(-1) }
+    }
+
+    fn set_stalled(&mut self, address: u8, stalled: bool) {
+        let number = endpoint::number(address) as usize;
+        if number >= DEVICE_ENDPOINTS {
+            return;
+        }
+        let regs = self.regs;
+        unsafe {
+            if endpoint::is_in(address) {
+                let ctl = ptr::read_volatile(&(*regs).diep[number].diepctl);
+                let ctl = if stalled {
+                    let disable = if ctl & DIEPCTL_EPENA_MASK != 0 {
+                        DIEPCTL_EPDIS_MASK
+                    } else {
+                        0
+                    };
+                    ctl | DIEPCTL_STALL_MASK | disable
+                } else if number == 0 {
+                    ctl & !DIEPCTL_STALL_MASK
+                } else {
+                    // Back to DATA0, as after SET_CONFIGURATION
+                    (ctl & !DIEPCTL_STALL_MASK) | DIEPCTL_SD0PID_MASK
+                };
+                ptr::write_volatile(&mut (*regs).diep[number].diepctl, ctl & !DIEPCTL_EPENA_MASK);
+                if stalled {
+                    self.in_busy[number] = false;
+                }
+            } else {
+                let ctl = ptr::read_volatile(&(*regs).doep[number].doepctl);
+                let ctl = if stalled {
+                    ctl | DOEPCTL_STALL_MASK
+                } else if number == 0 {
+                    ctl & !DOEPCTL_STALL_MASK
+                } else {
+                    (ctl & !DOEPCTL_STALL_MASK) | DOEPCTL_SD0PID_MASK
+                };
+                ptr::write_volatile(&mut (*regs).doep[number].doepctl, ctl);
+            }
+        }
+    }
+
+    fn is_stalled(&self, address: u8) -> bool {
+        let number = endpoint::number(address) as usize;
+        if number >= DEVICE_ENDPOINTS {
+            return false;
+        }
+        let regs = self.regs;
+        unsafe {
+            if endpoint::is_in(address) {
+                ptr::read_volatile(&(*regs).diep[number].diepctl) & DIEPCTL_STALL_MASK != 0
+            } else {
+                ptr::read_volatile(&(*regs).doep[number].doepctl) & DOEPCTL_STALL_MASK != 0
+            }
+        }
+    }
+
+    fn poll(&mut self) -> Option<Event> {
+        let regs = self.regs;
+        let gintsts = unsafe { ptr::read_volatile(&(*regs).gintsts) };
+        if gintsts & GINTSTS_USBRST_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).gintsts, GINTSTS_USBRST_MASK) };
+            self.bus_reset();
+            return None;
+        }
+        // The reset is over once the speed is known
+        if gintsts & GINTSTS_ENUMDNE_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).gintsts, GINTSTS_ENUMDNE_MASK) };
+            return Some(Event::Reset);
+        }
+        if gintsts & GINTSTS_RXFLVL_MASK != 0 {
+            return self.pop_rx();
+        }
+        if gintsts & GINTSTS_IEPINT_MASK != 0
+            && let Some(event) = self.in_complete()
+        {
+            return Some(event);
+        }
+        if gintsts & GINTSTS_OEPINT_MASK != 0 {
+            // Packets are taken from the RX FIFO; the transfer and SETUP
+            // done flags only need clearing
+            let daint = unsafe { ptr::read_volatile(&(*regs).daint) } >> DAINT_OEPINT_POS;
+            for number in (0..DEVICE_ENDPOINTS).filter(|number| daint & (1 << number) != 0) {
+                unsafe {
+                    let flags = ptr::read_volatile(&(*regs).doep[number].doepint);
+                    ptr::write_volatile(&mut (*regs).doep[number].doepint, flags);
+                }
+            }
+        }
+        if gintsts & GINTSTS_USBSUSP_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).gintsts, GINTSTS_USBSUSP_MASK) };
+            return Some(Event::Suspend);
+        }
+        if gintsts & GINTSTS_WKUPINT_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).gintsts, GINTSTS_WKUPINT_MASK) };
+            return Some(Event::Resume);
+        }
+        None
+    }
+}
@@ -0,0 +1,110 @@
+//! # SPI flash drive
+//!
+//! A `SpiFlash` as the `BlockDevice` of the `usb` mass-storage class. The
+//! host writes 512-byte blocks while the flash erases 4K sectors, so
+//! writes collect in a copy of one sector, which goes back to the flash
+//! when a write moves to another sector or on `flush`. The class flushes
+//! at the end of every WRITE command, so unplugging between commands
+//! loses nothing.
+//!
+//! ```ignore
+//! let mut flash = SpiFlash::new(spi, cs);
+//! flash.find_chip()?;
+//! let mut drive = MassStorage::new(1, FlashDisk::new(flash), "STM32", "SPI flash");
+//! device.configure(0, 100, |config| drive.describe(config))?;
+//! loop {
+//!     device.poll(&mut bus, &mut [&mut drive]);
+//! }
+//! ```
+
+use crate::bsp::spi_flash::{SPIF_SECTOR_SIZE, SpiFlash};
+use crate::clock;
+use crate::driver::spi::Spi;
+
+#[allow(unused_imports)]
+pub use usb::msc::{BLOCK_SIZE, BlockDevice, MassStorage};
+
+const BLOCKS_PER_SECTOR: u32 = (SPIF_SECTOR_SIZE / BLOCK_SIZE) as u32;
+
+pub struct FlashDisk<'a, S: Spi<'a>> {
+    flash: SpiFlash<'a, S>,
+    /// Copy of the sector being written
+    cache: [u8; SPIF_SECTOR_SIZE],
+    sector: Option<u32>,
+    /// The copy differs from the flash
+    dirty: bool,
+}
+
+impl<'a, S: Spi<'a>> FlashDisk<'a, S> {
+    /// A drive over the whole of `flash`, which must have found its chip
+    pub fn new(flash: SpiFlash<'a, S>) -> Self {
+        Self {
+            flash,
+            cache: [0; SPIF_SECTOR_SIZE],
+            sector: None,
+            dirty: false,
+        }
+    }
+
+    /// Write out the sector being written and give the flash back
+    pub fn release(mut self) -> Result<SpiFlash<'a, S>, i32> {
+        self.flush()?;
+        Ok(self.flash)
+    }
+
+    fn cached(&self, lba: u32) -> Option<usize> {
+        let offset = (lba % BLOCKS_PER_SECTOR) as usize * BLOCK_SIZE;
+        (self.sector == Some(lba / BLOCKS_PER_SECTOR)).then_some(offset)
+    }
+}
+
+impl<'a, S: Spi<'a>> BlockDevice for FlashDisk<'a, S> {
+    fn block_count(&self) -> u32 {
+        self.flash.sector_count * BLOCKS_PER_SECTOR
+    }
+
+    fn read(&mut self, lba: u32, block: &mut [u8; BLOCK_SIZE]) -> Result<(), i32> {
+        match self.cached(lba) {
+            Some(offset) => {
+                block.copy_from_slice(&self.cache[offset..offset + BLOCK_SIZE]);
+                Ok(())
+            }
+            None => self.flash.read_address(lba * BLOCK_SIZE as u32, block),
+        }
+    }
+
+    fn write(&mut self, lba: u32, block: &[u8; BLOCK_SIZE]) -> Result<(), i32> {
+        let offset = match self.cached(lba) {
+            Some(offset) => offset,
+            None => {
+                self.flush()?;
+                let sector = lba / BLOCKS_PER_SECTOR;
+                // Forget the old copy should the read fail half way
+                self.sector = None;
+                self.flash.read_sector(sector, &mut self.cache, 0)?;
+                self.sector = Some(sector);
+                (lba % BLOCKS_PER_SECTOR) as usize * BLOCK_SIZE
+            }
+        };
+        let cached = &mut self.cache[offset..offset + BLOCK_SIZE];
+        // Rewriting what is there costs no erase
+        if cached != block {
+            cached.copy_from_slice(block);
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), i32> {
+        let Some(sector) = self.sector else {
+            return Ok(());
+        };
+        if self.dirty {
+            self.flash.erase_sector(sector, clock::delay_ms)?;
+            self.flash
+                .write_sector(sector, &self.cache, 0, clock::delay_ms)?;
+            self.dirty = false;
+        }
+        Ok(())
+    }
+}
@@ -1 +1,442 @@
+//! # USB Host Controller
+//!
+//! The OTG_FS core of the STM32F4 in host mode, one full- or low-speed
+//! port on PA11 (D-) and PA12 (D+), as the `HostBus` of the `usb` stack.
+//! Like the device driver it is polled, with the OTG interrupt disabled:
+//! `poll` reports the port events, and each transaction runs to the end
+//! before it returns.
+//!
+//! The port gets VBUS from a power switch the board drives through
+//! `PortPower`. An over-current flag from the switch, or from the core,
+//! turns the port off and is reported once by `poll`; `set_power` turns
+//! it back on.
+//!
+//! ```ignore
+//! let mut bus = UsbHost::new(f4_discovery::USB_POWER)?;
+//! loop {
+//!     if bus.poll() == Some(PortEvent::Connected) {
+//!         let mut device = host::enumerate(&mut bus, 1)?;
+//!         let mut drive = host_msc::Drive::open(&mut bus, &mut device)?;
+//!     }
+//! }
+//! ```
 
+use super::UsbPin;
+use super::otg::{self, wait_until};
+use crate::board::Polarity;
+use crate::clock;
+use crate::driver::gpio::v2::{GpioConfig, configure_pin, read_pin, write_pin};
+use crate::driver::gpio::{Direction, Pin, PullResistor};
+use crate::mcu::device::otg_fs::*;
+use core::ptr;
+use usb::endpoint::{self, EndpointType};
+use usb::host::{HostBus, Pipe, PortEvent, Speed};
+
+/// Words of FIFO RAM for received packets, shared by all channels
+const RX_FIFO_WORDS: u32 = 128;
+/// Words of the non-periodic (control and bulk) and periodic TX FIFOs
+const TX_FIFO_WORDS: u32 = (FIFO_RAM_WORDS - RX_FIFO_WORDS) / 2;
+
+/// Frame interval in PHY clocks: 1 ms at 48 MHz, or at 6 MHz for a
+/// low-speed device
+const FRAME_48MHZ: u32 = 48_000;
+const FRAME_6MHZ: u32 = 6_000;
+
+/// Reset driven on the port, and wait after power-up, in ms
+const RESET_MS: u32 = 50;
+const POWER_UP_MS: u32 = 100;
+
+/// Flags that end a transaction on a channel
+const DONE: u32 = HCINT_XFRC_MASK
+    | HCINT_STALL_MASK
+    | HCINT_NAK_MASK
+    | HCINT_TXERR_MASK
+    | HCINT_BBERR_MASK
+    | HCINT_FRMOR_MASK
+    | HCINT_DTERR_MASK;
+
+/// HPRT bits that clear on writing 1, kept 0 when writing the others
+const HPRT_W1C: u32 = HPRT_PENA_MASK | HPRT_PCDET_MASK | HPRT_PENCHNG_MASK | HPRT_POCCHNG_MASK;
+
+/// The switch that gives the port VBUS. Of the pins, those in `pins` are
+/// wired: `UsbPin::VBUS` drives the enable of the switch, `UsbPin::OC`
+/// reads its over-current flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortPower {
+    pub pins: UsbPin,
+    pub vbus: Pin,
+    pub vbus_polarity: Polarity,
+    pub over_current: Pin,
+    pub over_current_polarity: Polarity,
+}
+
+impl PortPower {
+    /// VBUS always on and no over-current flag
+    pub const NONE: Self = Self {
+        pins: UsbPin::empty(),
+        vbus: 0,
+        vbus_polarity: Polarity::ActiveHigh,
+        over_current: 0,
+        over_current_polarity: Polarity::ActiveHigh,
+    };
+}
+
+/// OTG_FS in host mode
+pub struct UsbHost {
+    regs: *mut RegisterBlock,
+    power: PortPower,
+    powered: bool,
+    /// One bit per channel taken
+    channels: u8,
+}
+
+impl UsbHost {
+    /// Clock and reset the core in host mode and power the port. Returns
+    /// Err(-3) if a clock does not start or the core does not come out of
+    /// reset.
+    pub fn new(power: PortPower) -> Result<Self, i32> {
+        if power.pins.contains(UsbPin::VBUS) {
+            let output = GpioConfig {
+                direction: Direction::Output,
+                ..GpioConfig::default()
+            };
+            write_pin(power.vbus, power.vbus_polarity.level(false));
+            configure_pin(power.vbus, &output)?;
+        }
+        if power.pins.contains(UsbPin::OC) {
+            // The flag of the switch is open drain
+            let input = GpioConfig {
+                pull_resistor: PullResistor::PullUp,
+                ..GpioConfig::default()
+            };
+            configure_pin(power.over_current, &input)?;
+        }
+        let mut bus = Self {
+            regs: otg::init(GUSBCFG_FHMOD_MASK)?,
+            power,
+            powered: false,
+            channels: 0,
+        };
+        bus.init_core()?;
+        bus.set_power(true);
+        Ok(bus)
+    }
+
+    fn init_core(&mut self) -> Result<(), i32> {
+        let regs = self.regs;
+        unsafe {
+            ptr::write_volatile(&mut (*regs).hcfg, HCFG_FSLSPCS_48MHZ);
+            ptr::write_volatile(&mut (*regs).hfir, FRAME_48MHZ);
+
+            ptr::write_volatile(&mut (*regs).grxfsiz, RX_FIFO_WORDS);
+            ptr::write_volatile(
+                &mut (*regs).dieptxf0,
+                (TX_FIFO_WORDS << DIEPTXF_INEPTXFD_POS) | RX_FIFO_WORDS,
+            );
+            ptr::write_volatile(
+                &mut (*regs).hptxfsiz,
+                (TX_FIFO_WORDS << DIEPTXF_INEPTXFD_POS) | (RX_FIFO_WORDS + TX_FIFO_WORDS),
+            );
+        }
+        otg::flush_fifos(self.regs)?;
+
+        let regs = self.regs;
+        unsafe {
+            for channel in 0..HOST_CHANNELS {
+                ptr::write_volatile(
+                    &mut (*regs).channels[channel].hcintmsk,
+                    DONE | HCINT_CHH_MASK,
+                );
+                ptr::write_volatile(&mut (*regs).channels[channel].hcint, u32::MAX);
+            }
+            // The flags are polled; GAHBCFG.GINT stays clear so none
+            // reaches the NVIC
+            ptr::write_volatile(&mut (*regs).haintmsk, (1 << HOST_CHANNELS) - 1);
+            ptr::write_volatile(&mut (*regs).gintsts, u32::MAX);
+            ptr::write_volatile(
+                &mut (*regs).gintmsk,
+                GINTSTS_HPRTINT_MASK
+                    | GINTSTS_HCINT_MASK
+                    | GINTSTS_RXFLVL_MASK
+                    | GINTSTS_DISCINT_MASK,
+            );
+        }
+        Ok(())
+    }
+
+    /// HPRT with its write-1-to-clear bits 0, to write back changed
+    fn hprt(&self) -> u32 {
+        unsafe { ptr::read_volatile(&(*self.regs).hprt) & !HPRT_W1C }
+    }
+
+    /// Turn VBUS of the port on or off, through the switch and the core
+    pub fn set_power(&mut self, on: bool) {
+        if self.power.pins.contains(UsbPin::VBUS) {
+            write_pin(self.power.vbus, self.power.vbus_polarity.level(on));
+        }
+        let hprt = self.hprt() & !HPRT_PPWR_MASK;
+        let ppwr = if on { HPRT_PPWR_MASK } else { 0 };
+        unsafe { ptr::write_volatile(&mut (*self.regs).hprt, hprt | ppwr) };
+        if on && !self.powered {
+            clock::delay_ms(POWER_UP_MS);
+        }
+        self.powered = on;
+    }
+
+    pub fn is_powered(&self) -> bool {
+        self.powered
+    }
+
+    fn over_current(&self) -> bool {
+        let flag = self.power.pins.contains(UsbPin::OC)
+            && read_pin(self.power.over_current) == self.power.over_current_polarity.level(true);
+        let hprt = unsafe { ptr::read_volatile(&(*self.regs).hprt) };
+        flag || hprt & HPRT_POCA_MASK != 0
+    }
+
+    fn channel(&self, pipe: &Pipe) -> Result<usize, i32> {
+        let channel = pipe.channel as usize;
+        if channel >= HOST_CHANNELS || self.channels & (1 << channel) == 0 {
+            return Err(-1);
+        }
+        Ok(channel)
+    }
+
+    /// Program channel `channel` for one packet of `len` bytes to or from
+    /// the endpoint of `pipe` and enable it
+    fn start(&mut self, channel: usize, pipe: &Pipe, direction_in: bool, pid: u32, len: usize) {
+        let regs = self.regs;
+        let mut hcchar = (pipe.max_packet_size as u32 & HCCHAR_MPSIZ_MASK)
+            | ((endpoint::number(pipe.endpoint) as u32) << HCCHAR_EPNUM_POS)
+            | ((pipe.endpoint_type as u32) << HCCHAR_EPTYP_POS)
+            | ((pipe.address as u32) << HCCHAR_DAD_POS)
+            | (1 << HCCHAR_MCNT_POS)
+            | HCCHAR_CHENA_MASK;
+        if direction_in {
+            hcchar |= HCCHAR_EPDIR_MASK;
+        }
+        if pipe.low_speed {
+            hcchar |= HCCHAR_LSDEV_MASK;
+        }
+        unsafe {
+            // A periodic transaction goes out in the next frame
+            let frame = ptr::read_volatile(&(*regs).hfnum) & HFNUM_FRNUM_MASK;
+            if pipe.endpoint_type == EndpointType::Interrupt && frame & 1 == 0 {
+                hcchar |= HCCHAR_ODDFRM_MASK;
+            }
+            let ch = &mut (*regs).channels[channel];
+            ptr::write_volatile(&mut ch.hcint, u32::MAX);
+            ptr::write_volatile(
+                &mut ch.hctsiz,
+                pid | (1 << HCTSIZ_PKTCNT_POS) | (len as u32 & HCTSIZ_XFRSIZ_MASK),
+            );
+            ptr::write_volatile(&mut ch.hcchar, hcchar);
+        }
+    }
+
+    fn pid(pipe: &Pipe) -> u32 {
+        if pipe.toggle {
+            HCTSIZ_DPID_DATA1
+        } else {
+            HCTSIZ_DPID_DATA0
+        }
+    }
+
+    /// Take an entry of the RX FIFO, copying the data of `channel` into
+    /// `buf` after the `len` bytes it holds. Returns Err(-4) if it does not
+    /// fit.
+    fn pop_rx(&mut self, channel: usize, buf: &mut [u8], len: &mut usize) -> Result<(), i32> {
+        let status = unsafe { ptr::read_volatile(&(*self.regs).grxstsp) };
+        let number = ((status & GRXSTSP_EPNUM_MASK) >> GRXSTSP_EPNUM_POS) as usize;
+        let count = ((status & GRXSTSP_BCNT_MASK) >> GRXSTSP_BCNT_POS) as usize;
+        if status & GRXSTSP_PKTSTS_MASK != GRXSTSP_PKTSTS_IN_DATA || count == 0 {
+            return Ok(());
+        }
+        if number != channel || *len + count > buf.len() {
+            otg::read_fifo(self.regs, &mut [], count);
+            return Err(-4);
+        }
+        otg::read_fifo(self.regs, &mut buf[*len..], count);
+        *len += count;
+        Ok(())
+    }
+
+    /// Take what the RX FIFO holds for `channel` and return its HCINT.
+    /// Sets `overflow` if a packet does not fit in `buf`.
+    fn drain(
+        &mut self,
+        channel: usize,
+        buf: &mut [u8],
+        len: &mut usize,
+        overflow: &mut bool,
+    ) -> u32 {
+        let regs = self.regs;
+        let gintsts = unsafe { ptr::read_volatile(&(*regs).gintsts) };
+        if gintsts & GINTSTS_RXFLVL_MASK != 0 && self.pop_rx(channel, buf, len).is_err() {
+            *overflow = true;
+        }
+        unsafe { ptr::read_volatile(&(*regs).channels[channel].hcint) }
+    }
+
+    /// Wait for the transaction on `channel` to end, taking what it reads
+    /// into `buf`, then halt the channel. Returns the length read.
+    fn finish(&mut self, channel: usize, buf: &mut [u8]) -> Result<usize, i32> {
+        let mut len = 0;
+        let mut overflow = false;
+        let mut flags = 0;
+        let ended = wait_until(|| {
+            flags = self.drain(channel, buf, &mut len, &mut overflow);
+            flags & DONE != 0
+        });
+
+        // A channel is halted after every transaction in slave mode
+        let regs = self.regs;
+        unsafe {
+            let hcchar = ptr::read_volatile(&(*regs).channels[channel].hcchar);
+            if hcchar & HCCHAR_CHENA_MASK != 0 {
+                ptr::write_volatile(
+                    &mut (*regs).channels[channel].hcchar,
+                    hcchar | HCCHAR_CHDIS_MASK | HCCHAR_CHENA_MASK,
+                );
+            }
+        }
+        let _ =
+            wait_until(|| self.drain(channel, buf, &mut len, &mut overflow) & HCINT_CHH_MASK != 0);
+        unsafe { ptr::write_volatile(&mut (*regs).channels[channel].hcint, u32::MAX) };
+
+        if ended.is_err() || overflow {
+            return Err(-4);
+        }
+        if flags & HCINT_XFRC_MASK != 0 {
+            Ok(len)
+        } else if flags & HCINT_STALL_MASK != 0 {
+            Err(-3)
+        } else if flags & HCINT_NAK_MASK != 0 {
+            Err(-5)
+        } else {
+            Err(-4)
+        }
+    }
+
+    /// Run an OUT or SETUP transaction with `data`
+    fn out(&mut self, pipe: &Pipe, pid: u32, data: &[u8]) -> Result<(), i32> {
+        let channel = self.channel(pipe)?;
+        if data.len() > pipe.max_packet_size as usize {
+            return Err(-1);
+        }
+        let words = data.len().div_ceil(4) as u32;
+        let regs = self.regs;
+        let space = unsafe {
+            if pipe.endpoint_type == EndpointType::Interrupt {
+                ptr::read_volatile(&(*regs).hptxsts) & HNPTXSTS_NPTXFSAV_MASK
+            } else {
+                ptr::read_volatile(&(*regs).hnptxsts) & HNPTXSTS_NPTXFSAV_MASK
+            }
+        };
+        if space < words {
+            return Err(-5);
+        }
+        self.start(channel, pipe, false, pid, data.len());
+        otg::write_fifo(regs, channel, data);
+        self.finish(channel, &mut []).map(|_| ())
+    }
+}
+
+impl HostBus for UsbHost {
+    fn poll(&mut self) -> Option<PortEvent> {
+        if self.powered && self.over_current() {
+            self.set_power(false);
+            return Some(PortEvent::OverCurrent);
+        }
+        let regs = self.regs;
+        let hprt = unsafe { ptr::read_volatile(&(*regs).hprt) };
+        let changes = hprt & HPRT_W1C & !HPRT_PENA_MASK;
+        if changes != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).hprt, (hprt & !HPRT_W1C) | changes) };
+        }
+        let gintsts = unsafe { ptr::read_volatile(&(*regs).gintsts) };
+        if gintsts & GINTSTS_DISCINT_MASK != 0 {
+            unsafe { ptr::write_volatile(&mut (*regs).gintsts, GINTSTS_DISCINT_MASK) };
+            return Some(PortEvent::Disconnected);
+        }
+        if changes & HPRT_PCDET_MASK != 0 && hprt & HPRT_PCSTS_MASK != 0 {
+            return Some(PortEvent::Connected);
+        }
+        None
+    }
+
+    fn is_connected(&self) -> bool {
+        unsafe { ptr::read_volatile(&(*self.regs).hprt) & HPRT_PCSTS_MASK != 0 }
+    }
+
+    fn reset(&mut self) -> Result<Speed, i32> {
+        let regs = self.regs;
+        // A low-speed device needs the PHY clock at 6 MHz, which takes
+        // another reset to apply
+        for _ in 0..2 {
+            if !self.is_connected() {
+                return Err(-2);
+            }
+            let hprt = self.hprt();
+            unsafe { ptr::write_volatile(&mut (*regs).hprt, hprt | HPRT_PRST_MASK) };
+            clock::delay_ms(RESET_MS);
+            unsafe { ptr::write_volatile(&mut (*regs).hprt, hprt & !HPRT_PRST_MASK) };
+            wait_until(|| unsafe { ptr::read_volatile(&(*regs).hprt) } & HPRT_PENA_MASK != 0)?;
+
+            let hprt = unsafe { ptr::read_volatile(&(*regs).hprt) };
+            let (speed, clock, frame) = match hprt & HPRT_PSPD_MASK {
+                HPRT_PSPD_LOW_SPEED => (Speed::Low, HCFG_FSLSPCS_6MHZ, FRAME_6MHZ),
+                _ => (Speed::Full, HCFG_FSLSPCS_48MHZ, FRAME_48MHZ),
+            };
+            let hcfg = unsafe { ptr::read_volatile(&(*regs).hcfg) };
+            if hcfg & HCFG_FSLSPCS_MASK == clock {
+                return Ok(speed);
+            }
+            unsafe {
+                ptr::write_volatile(&mut (*regs).hcfg, (hcfg & !HCFG_FSLSPCS_MASK) | clock);
+                ptr::write_volatile(&mut (*regs).hfir, frame);
+            }
+        }
+        Err(-3)
+    }
+
+    fn alloc_channel(&mut self) -> Result<u8, i32> {
+        let channel = (0..HOST_CHANNELS as u8)
+            .find(|channel| self.channels & (1 << channel) == 0)
+            .ok_or(-5)?;
+        self.channels |= 1 << channel;
+        Ok(channel)
+    }
+
+    fn free_channel(&mut self, channel: u8) {
+        if (channel as usize) < HOST_CHANNELS {
+            self.channels &= !(1 << channel);
+        }
+    }
+
+    fn setup(&mut self, pipe: &Pipe, packet: &[u8; 8]) -> Result<(), i32> {
+        self.out(pipe, HCTSIZ_DPID_SETUP, packet)
+    }
+
+    fn read(&mut self, pipe: &Pipe, buf: &mut [u8]) -> Result<usize, i32> {
+        let channel = self.channel(pipe)?;
+        // The channel takes up to a whole packet whatever `buf` holds, and
+        // anything longer than `buf` is babble
+        self.start(
+            channel,
+            pipe,
+            true,
+            Self::pid(pipe),
+            pipe.max_packet_size as usize,
+        );
+        self.finish(channel, buf)
+    }
+
+    fn write(&mut self, pipe: &Pipe, data: &[u8]) -> Result<(), i32> {
+        self.out(pipe, Self::pid(pipe), data)
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        clock::delay_ms(ms);
+    }
+}
@@ -52,35 +52,9 @@ pub enum UsbPid {
     Mdata = 15,
 }
 
-/// Endpoint address helpers
-pub mod endpoint {
-    pub const NUMBER_MASK: u8 = 0x0F;
-    pub const DIRECTION_MASK: u8 = 0x80;
-
-    #[inline]
-    pub fn address(number: u8, direction_in: bool) -> u8 {
-        (number & NUMBER_MASK) | if direction_in { DIRECTION_MASK } else { 0 }
-    }
-
-    #[inline]
-    pub fn number(address: u8) -> u8 {
-        address & NUMBER_MASK
-    }
-
-    #[inline]
-    pub fn is_in(address: u8) -> bool {
-        address & DIRECTION_MASK != 0
-    }
-}
-
-/// Endpoint types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum EndpointType {
-    Control = 0,
-    Isochronous = 1,
-    Bulk = 2,
-    Interrupt = 3,
-}
+/// Endpoint addresses and types, shared with the `usb` device stack
+#[allow(unused_imports)]
+pub use usb::endpoint::{self, EndpointType};
 
 /// Endpoint packet size helpers
 pub mod packet_size {
@@ -92,5 +66,13 @@ pub mod packet_size {
     pub const MICROFRAME_TRANSACTIONS_3: u16 = 0x1000;
 }
 
+/// OTG_FS device controller, the `Bus` of the `usb` stack
+#[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
 pub mod device;
+/// SPI flash as the block device of the mass-storage class
+pub mod disk;
+/// OTG_FS host controller, the `HostBus` of the `usb` stack
+#[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
 pub mod host;
+#[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
+mod otg;
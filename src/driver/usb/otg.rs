@@ -0,0 +1,157 @@
+//! # OTG_FS Core
+//!
+//! What the device and host drivers share: the 48 MHz clock, the pins, the
+//! core reset into either mode and the data FIFOs.
+//!
+//! The core needs a 48 MHz clock, taken from the Q output of the main PLL,
+//! fed from the board's HSE or else from the HSI. The HSI is only accurate
+//! to about 1%, well outside the 0.25% USB allows, so a crystal is needed
+//! to work with every peer. SYSCLK stays on the HSI; a PLL already running
+//! is left as it is and must have 48 MHz on Q.
+
+use crate::board::BOARD;
+use crate::driver::gpio::{
+    AfSignal, OutputMode, Port, PullResistor, Speed, configure_alternate, encode_pin,
+};
+use crate::mcu::device::{self, PeripheralAccess, otg_fs::*, rcc};
+use core::ptr;
+
+/// PLL input of 1 MHz times 192, divided by 4 on Q: 48 MHz for the core
+const PLL_INPUT_HZ: u32 = 1_000_000;
+const PLL_N: u32 = 192;
+const PLL_P_DIV4: u32 = rcc::PLLCFGR_PLLP_DIV4;
+const PLL_Q: u32 = 4;
+
+/// USB turnaround time in PHY clocks for an AHB clock of 16 to 17.2 MHz,
+/// the HSI (RM0090, table "TRDT values")
+const TRDT_HCLK_16MHZ: u32 = 0xD;
+
+/// Polls of a flag before it counts as stuck
+const TIMEOUT: u32 = 1_000_000;
+
+pub(super) fn wait_until(mut done: impl FnMut() -> bool) -> Result<(), i32> {
+    for _ in 0..TIMEOUT {
+        if done() {
+            return Ok(());
+        }
+    }
+    Err(-3)
+}
+
+/// Start the main PLL for 48 MHz on its Q output, from the HSE if the board
+/// has one. Returns Err(-3) if the oscillator or the PLL does not start.
+fn start_pll() -> Result<(), i32> {
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    unsafe {
+        if ptr::read_volatile(&(*rcc).cr) & rcc::CR_PLLON_MASK != 0 {
+            return Ok(());
+        }
+        let (source, source_hz) = match BOARD.hse_hz {
+            Some(hse_hz) => {
+                let cr = ptr::read_volatile(&(*rcc).cr);
+                ptr::write_volatile(&mut (*rcc).cr, cr | rcc::CR_HSEON_MASK);
+                wait_until(|| ptr::read_volatile(&(*rcc).cr) & rcc::CR_HSERDY_MASK != 0)?;
+                (rcc::PLLCFGR_PLLSRC_HSE, hse_hz)
+            }
+            None => (rcc::PLLCFGR_PLLSRC_HSI, device::HSI_HZ),
+        };
+        let pllm = source_hz / PLL_INPUT_HZ;
+        ptr::write_volatile(
+            &mut (*rcc).pllcfgr,
+            source
+                | (pllm << rcc::PLLCFGR_PLLM_POS)
+                | (PLL_N << rcc::PLLCFGR_PLLN_POS)
+                | PLL_P_DIV4
+                | (PLL_Q << rcc::PLLCFGR_PLLQ_POS),
+        );
+        let cr = ptr::read_volatile(&(*rcc).cr);
+        ptr::write_volatile(&mut (*rcc).cr, cr | rcc::CR_PLLON_MASK);
+        wait_until(|| ptr::read_volatile(&(*rcc).cr) & rcc::CR_PLLRDY_MASK != 0)
+    }
+}
+
+/// Clock the core, route PA11 (D-) and PA12 (D+) to it, and reset it into
+/// device or host mode, `GUSBCFG_FDMOD_MASK` or `GUSBCFG_FHMOD_MASK`, with
+/// the transceiver on and VBUS sensing off. Returns Err(-3) if a clock does
+/// not start or the core does not come out of reset.
+pub(super) fn init(mode: u32) -> Result<*mut RegisterBlock, i32> {
+    start_pll()?;
+    for (pin, signal) in [(11, AfSignal::OTG_FS_DM), (12, AfSignal::OTG_FS_DP)] {
+        configure_alternate(
+            encode_pin(Port::A, pin),
+            signal,
+            OutputMode::PushPull,
+            PullResistor::None,
+            Speed::VeryHigh,
+        )?;
+    }
+    let rcc = device::RCC_BASEADDR as *mut rcc::RegisterBlock;
+    let regs = USB_OTG_FS::ptr_mut();
+    unsafe {
+        let v = ptr::read_volatile(&(*rcc).ahb2enr);
+        ptr::write_volatile(&mut (*rcc).ahb2enr, v | rcc::AHB2ENR_OTGFSEN_MASK);
+
+        wait_until(|| ptr::read_volatile(&(*regs).grstctl) & GRSTCTL_AHBIDL_MASK != 0)?;
+        ptr::write_volatile(&mut (*regs).grstctl, GRSTCTL_CSRST_MASK);
+        wait_until(|| ptr::read_volatile(&(*regs).grstctl) & GRSTCTL_CSRST_MASK == 0)?;
+
+        ptr::write_volatile(
+            &mut (*regs).gccfg,
+            GCCFG_PWRDWN_MASK | GCCFG_NOVBUSSENS_MASK,
+        );
+        ptr::write_volatile(
+            &mut (*regs).gusbcfg,
+            mode | GUSBCFG_PHYSEL_MASK
+                | (TRDT_HCLK_16MHZ << GUSBCFG_TRDT_POS)
+                | (0x7 << GUSBCFG_TOCAL_POS),
+        );
+        // The core takes up to 25 ms to change mode. Counted in cycles of the
+        // HSI SYSCLK so this does not depend on the SysTick interrupt
+        cortex_m::asm::delay(device::HSI_HZ / 1000 * 25);
+        ptr::write_volatile(&mut (*regs).pcgcctl, 0);
+    }
+    Ok(regs)
+}
+
+/// Flush the RX FIFO and every TX FIFO
+pub(super) fn flush_fifos(regs: *mut RegisterBlock) -> Result<(), i32> {
+    unsafe {
+        ptr::write_volatile(
+            &mut (*regs).grstctl,
+            GRSTCTL_TXFFLSH_MASK | (0x10 << GRSTCTL_TXFNUM_POS),
+        );
+        wait_until(|| ptr::read_volatile(&(*regs).grstctl) & GRSTCTL_TXFFLSH_MASK == 0)?;
+        ptr::write_volatile(&mut (*regs).grstctl, GRSTCTL_RXFFLSH_MASK);
+        wait_until(|| ptr::read_volatile(&(*regs).grstctl) & GRSTCTL_RXFFLSH_MASK == 0)
+    }
+}
+
+/// The data FIFO of endpoint or channel `number`; reads pop the RX FIFO
+pub(super) fn fifo(regs: *mut RegisterBlock, number: usize) -> *mut u32 {
+    (regs as u32 + FIFO_OFFSET + number as u32 * FIFO_STRIDE) as *mut u32
+}
+
+/// Copy `len` bytes out of the RX FIFO, dropping what `buf` has no room
+/// for
+pub(super) fn read_fifo(regs: *mut RegisterBlock, buf: &mut [u8], len: usize) {
+    let fifo = fifo(regs, 0);
+    for offset in (0..len).step_by(4) {
+        let word = unsafe { ptr::read_volatile(fifo) }.to_le_bytes();
+        for (index, byte) in word.iter().enumerate() {
+            if let Some(out) = buf.get_mut(offset + index) {
+                *out = *byte;
+            }
+        }
+    }
+}
+
+/// Push `data` into the TX FIFO of endpoint or channel `number`, the last
+/// word padded
+pub(super) fn write_fifo(regs: *mut RegisterBlock, number: usize, data: &[u8]) {
+    let fifo = fifo(regs, number);
+    for chunk in data.chunks(4) {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        unsafe { ptr::write_volatile(fifo, u32::from_le_bytes(word)) };
+    }
+}
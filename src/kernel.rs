@@ -0,0 +1,361 @@
+//! # Preemptive Kernel
+//!
+//! Optional alternative to the cooperative app scheduler, enabled by the
+//! `rtos` feature: threads with their own stacks, preempted by priority
+//! through PendSV, with round-robin between threads of equal priority on
+//! every SysTick. The scheduling policy, mutexes with priority inheritance,
+//! semaphores and message queues come from the host-tested `rtos` crate;
+//! this module keeps the thread stacks and wraps them for thread use.
+//!
+//! Timeouts are in SysTick ticks (1 ms); `None` waits forever and
+//! `Some(0)` only tries.
+//!
+//! ```ignore
+//! fn worker() {
+//!     loop {
+//!         kernel::sleep(100);
+//!     }
+//! }
+//!
+//! let stack = cortex_m::singleton!(: [u32; 256] = [0; 256]).unwrap();
+//! kernel::spawn(worker, stack, 2).unwrap();
+//! kernel::start()
+//! ```
+
+use crate::arch::cpu::{context, systick};
+use core::cell::{RefCell, UnsafeCell};
+use core::ops::{Deref, DerefMut};
+use cortex_m::interrupt::{self, Mutex as CsMutex};
+use rtos::kernel::{ERR_TIMEOUT, IDLE_PRIORITY, Kernel, WakeReason, reached};
+use rtos::sync::{self, Wait};
+
+pub use rtos::kernel::ThreadId;
+
+/// Maximum number of threads, including the idle thread
+pub const MAX_THREADS: usize = 8;
+
+struct State {
+    kernel: Kernel<MAX_THREADS>,
+    /// Saved stack pointer of each thread while it is switched out
+    stack_pointers: [u32; MAX_THREADS],
+    started: bool,
+}
+
+static STATE: CsMutex<RefCell<State>> = CsMutex::new(RefCell::new(State {
+    kernel: Kernel::new(),
+    stack_pointers: [0; MAX_THREADS],
+    started: false,
+}));
+
+static mut IDLE_STACK: [u32; 128] = [0; 128];
+
+/// Takes the registers `start()` leaves behind on the first switch
+static mut START_STACK: [u32; 32] = [0; 32];
+
+fn with_state<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    interrupt::free(|cs| f(&mut STATE.borrow(cs).borrow_mut()))
+}
+
+/// Switch threads if the kernel picks another one, once no handler is active.
+fn switch() {
+    if with_state(|s| s.started && s.kernel.needs_switch()) {
+        context::pend_switch();
+    }
+}
+
+/// Switch out the current thread, which the kernel just blocked, and return
+/// why it was woken.
+fn wait_for_wake() -> WakeReason {
+    context::pend_switch();
+    with_state(|s| s.kernel.current().and_then(|id| s.kernel.wake_reason(id)))
+        .unwrap_or(WakeReason::Signaled)
+}
+
+fn deadline(timeout: Option<u32>) -> Option<u32> {
+    timeout.map(|ticks| systick::get_ticks().wrapping_add(ticks))
+}
+
+/// First code of every thread: run its entry function, then exit.
+extern "C" fn thread_entry(entry: usize) -> ! {
+    let entry: fn() = unsafe { core::mem::transmute(entry) };
+    entry();
+    exit()
+}
+
+fn idle() {
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
+
+/// Create a thread running `entry` on `stack` at `priority` (higher runs
+/// first, 0 is the idle priority). Threads can be spawned before or after
+/// `start()`. Returns Err(-1) if the stack is too small or all threads are
+/// in use.
+pub fn spawn(entry: fn(), stack: &'static mut [u32], priority: u8) -> Result<ThreadId, i32> {
+    let trampoline: extern "C" fn(usize) -> ! = thread_entry;
+    let exit: fn() -> ! = exit;
+    let sp = context::init_stack(stack, trampoline as usize, entry as usize, exit as usize)?;
+    let id = with_state(|s| {
+        let id = s.kernel.spawn(priority)?;
+        s.stack_pointers[id] = sp;
+        Ok::<_, i32>(id)
+    })?;
+    switch();
+    Ok(id)
+}
+
+/// Start running the spawned threads; `main` does not continue.
+pub fn start() -> ! {
+    let idle_stack = unsafe { &mut *&raw mut IDLE_STACK };
+    if spawn(idle, idle_stack, IDLE_PRIORITY).is_err() {
+        panic!("no thread left for the idle thread");
+    }
+
+    context::init();
+    unsafe {
+        let start_stack = &raw mut START_STACK;
+        context::set_psp(start_stack as u32 + core::mem::size_of_val(&*start_stack) as u32);
+    }
+    with_state(|s| s.started = true);
+    context::pend_switch();
+
+    // The first switch leaves main for good
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
+
+/// Called by PendSV with the stack pointer of the outgoing thread; returns
+/// the stack pointer of the thread to run.
+#[unsafe(no_mangle)]
+extern "C" fn rtos_switch_context(sp: u32) -> u32 {
+    with_state(|s| {
+        if let Some(id) = s.kernel.current() {
+            s.stack_pointers[id] = sp;
+        }
+        match s.kernel.schedule() {
+            Some(id) => s.stack_pointers[id],
+            None => sp,
+        }
+    })
+}
+
+/// Advance the kernel by one tick. Called from the SysTick handler.
+pub fn tick() {
+    let now = systick::get_ticks();
+    if with_state(|s| s.started && s.kernel.tick(now)) {
+        context::pend_switch();
+    }
+}
+
+/// The running thread
+pub fn current() -> Option<ThreadId> {
+    with_state(|s| s.kernel.current())
+}
+
+/// Let other ready threads of the same priority run.
+pub fn yield_now() {
+    with_state(|s| s.kernel.yield_now());
+    switch();
+}
+
+/// Suspend the running thread for `ticks` ticks.
+pub fn sleep(ticks: u32) {
+    if ticks == 0 {
+        return yield_now();
+    }
+    let deadline = systick::get_ticks().wrapping_add(ticks);
+    if with_state(|s| s.kernel.sleep_until(deadline)).is_ok() {
+        context::pend_switch();
+    }
+}
+
+/// End the running thread.
+pub fn exit() -> ! {
+    with_state(|s| s.kernel.exit());
+    context::pend_switch();
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
+
+/// Mutex with priority inheritance, for use from threads only.
+pub struct Mutex<T> {
+    raw: UnsafeCell<sync::Mutex>,
+    data: UnsafeCell<T>,
+}
+
+// The raw mutex is only accessed inside critical sections and the data only
+// through the guard of the owning thread
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    pub const fn new(data: T) -> Self {
+        Self {
+            raw: UnsafeCell::new(sync::Mutex::new()),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Lock, waiting up to `timeout` ticks for the owner to unlock.
+    /// Returns Err(ERR_TIMEOUT) if it did not, Err(-1) outside a thread or
+    /// if the thread already holds the lock.
+    pub fn lock(&self, timeout: Option<u32>) -> Result<MutexGuard<'_, T>, i32> {
+        let deadline = deadline(timeout);
+        let wait = with_state(|s| {
+            let raw = unsafe { &mut *self.raw.get() };
+            match timeout {
+                Some(0) if !raw.try_lock(&s.kernel)? => Err(ERR_TIMEOUT),
+                Some(0) => Ok(Wait::Done(())),
+                _ => raw.lock(&mut s.kernel, deadline),
+            }
+        })?;
+        if wait == Wait::Blocked && wait_for_wake() == WakeReason::Timeout {
+            return Err(ERR_TIMEOUT);
+        }
+        Ok(MutexGuard { mutex: self })
+    }
+}
+
+/// Access to the data of a locked `Mutex`, unlocked when dropped
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.data.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.data.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        let _ = with_state(|s| unsafe { &mut *self.mutex.raw.get() }.unlock(&mut s.kernel));
+        switch();
+    }
+}
+
+/// Counting semaphore; `release()` may be called from interrupts.
+pub struct Semaphore {
+    raw: UnsafeCell<sync::Semaphore>,
+}
+
+// Only accessed inside critical sections
+unsafe impl Sync for Semaphore {}
+
+impl Semaphore {
+    /// Create a semaphore holding `count` of at most `max` permits.
+    pub const fn new(count: u32, max: u32) -> Self {
+        Self {
+            raw: UnsafeCell::new(sync::Semaphore::new(count, max)),
+        }
+    }
+
+    /// Take a permit, waiting up to `timeout` ticks for one.
+    /// Returns Err(ERR_TIMEOUT) if none came, Err(-1) outside a thread.
+    pub fn acquire(&self, timeout: Option<u32>) -> Result<(), i32> {
+        let deadline = deadline(timeout);
+        let wait = with_state(|s| {
+            let raw = unsafe { &mut *self.raw.get() };
+            match timeout {
+                Some(0) if !raw.try_acquire() => Err(ERR_TIMEOUT),
+                Some(0) => Ok(Wait::Done(())),
+                _ => raw.acquire(&mut s.kernel, deadline),
+            }
+        })?;
+        if wait == Wait::Blocked && wait_for_wake() == WakeReason::Timeout {
+            return Err(ERR_TIMEOUT);
+        }
+        Ok(())
+    }
+
+    /// Give a permit back. Returns Err(-1) if the semaphore is full.
+    pub fn release(&self) -> Result<(), i32> {
+        with_state(|s| unsafe { &mut *self.raw.get() }.release(&mut s.kernel))?;
+        switch();
+        Ok(())
+    }
+}
+
+/// Message queue of up to `N` items. Interrupts may send and receive with
+/// a timeout of `Some(0)`.
+pub struct MessageQueue<T, const N: usize> {
+    raw: UnsafeCell<sync::MessageQueue<T, N>>,
+}
+
+// Only accessed inside critical sections
+unsafe impl<T: Send, const N: usize> Sync for MessageQueue<T, N> {}
+
+impl<T, const N: usize> MessageQueue<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            raw: UnsafeCell::new(sync::MessageQueue::new()),
+        }
+    }
+
+    /// Send an item, waiting up to `timeout` ticks for room.
+    /// Returns the item back if the queue stayed full.
+    pub fn send(&self, item: T, timeout: Option<u32>) -> Result<(), T> {
+        let deadline = deadline(timeout);
+        let mut item = item;
+        loop {
+            // Trying and blocking in one critical section, so a receiver
+            // cannot make room in between without waking this thread
+            let result = with_state(|s| {
+                let queue = unsafe { &mut *self.raw.get() };
+                queue.try_send(&mut s.kernel, item).map_err(|item| {
+                    let expired = timeout == Some(0)
+                        || deadline.is_some_and(|d| reached(systick::get_ticks(), d));
+                    let blocked = !expired && queue.wait_send(&mut s.kernel, deadline).is_ok();
+                    (item, blocked)
+                })
+            });
+            match result {
+                Ok(()) => {
+                    switch();
+                    return Ok(());
+                }
+                Err((unsent, true)) if wait_for_wake() == WakeReason::Signaled => item = unsent,
+                Err((unsent, _)) => return Err(unsent),
+            }
+        }
+    }
+
+    /// Receive the oldest item, waiting up to `timeout` ticks for one.
+    pub fn receive(&self, timeout: Option<u32>) -> Option<T> {
+        let deadline = deadline(timeout);
+        loop {
+            let result = with_state(|s| {
+                let queue = unsafe { &mut *self.raw.get() };
+                queue.try_receive(&mut s.kernel).ok_or_else(|| {
+                    let expired = timeout == Some(0)
+                        || deadline.is_some_and(|d| reached(systick::get_ticks(), d));
+                    !expired && queue.wait_receive(&mut s.kernel, deadline).is_ok()
+                })
+            });
+            match result {
+                Ok(item) => {
+                    switch();
+                    return Some(item);
+                }
+                Err(true) if wait_for_wake() == WakeReason::Signaled => {}
+                Err(_) => return None,
+            }
+        }
+    }
+}
+
+impl<T, const N: usize> Default for MessageQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
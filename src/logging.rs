@@ -0,0 +1,318 @@
+//! # Logging
+//!
+//! One set of macros, `error!`, `warn!`, `info!`, `debug!` and `trace!`,
+//! over the logging feature of the build:
+//!
+//! - `defmt`: the macros are defmt's, encoded on the target and formatted
+//!   on the host, written to RTT channel 0. Levels are filtered at compile
+//!   time with the `DEFMT_LOG` environment variable.
+//! - `log`: the macros are log's, formatted on the target and written to
+//!   RTT channel 0 or a USART (`init_usart`), one line per record, filtered
+//!   per module at run time (`set_level`).
+//! - neither: the macros compile to nothing; their arguments are still
+//!   type-checked.
+//!
+//! Records carry the time since boot from SysTick (`clock::now`). With
+//! `log` a line reads:
+//!
+//! ```text
+//! [     1.204518] ERROR driver::rtc: LSE did not start, error -1
+//! ```
+//!
+//! Format strings must be valid for both backends: plain `{}` of integers,
+//! `&str` and `bool`.
+//!
+//! ```ignore
+//! use crate::logging::{info, warn};
+//!
+//! logging::init();
+//! logging::set_level("driver::i2c", Level::Debug)?;
+//! info!("booted, reset reason {}", reason.as_str());
+//! ```
+
+use crate::driver::usart::Usart;
+
+pub use logfmt::filter::Level;
+
+#[cfg(feature = "defmt")]
+macro_rules! log_at {
+    ($level:ident, $($arg:tt)+) => {
+        defmt::$level!($($arg)+)
+    };
+}
+
+#[cfg(all(feature = "log", not(feature = "defmt")))]
+macro_rules! log_at {
+    ($level:ident, $($arg:tt)+) => {
+        log::$level!($($arg)+)
+    };
+}
+
+#[cfg(not(any(feature = "log", feature = "defmt")))]
+macro_rules! log_at {
+    ($level:ident, $($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::logging::log_at!(error, $($arg)+) };
+}
+
+// Named apart from the built-in `warn` attribute, exported as `warn`
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::logging::log_at!(warn, $($arg)+) };
+}
+
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::logging::log_at!(info, $($arg)+) };
+}
+
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::logging::log_at!(debug, $($arg)+) };
+}
+
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::logging::log_at!(trace, $($arg)+) };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {debug, error, info, log_at, log_warn as warn, trace};
+
+/// Level of modules without their own, until `set_default_level`
+pub const DEFAULT_LEVEL: Level = Level::Info;
+
+/// Maximum number of modules with their own level
+pub const MAX_MODULE_LEVELS: usize = 8;
+
+#[cfg(feature = "defmt")]
+mod backend {
+    use super::Level;
+    use crate::driver::usart::Usart;
+
+    defmt::timestamp!("{=u64:us}", crate::clock::now().as_micros());
+
+    pub fn init() {
+        if let Some(terminal) = crate::rtt::take_terminal() {
+            rtt_target::set_defmt_channel(terminal);
+        }
+    }
+
+    pub fn init_usart<U: Usart<'static>>(_usart: &'static mut U) -> Result<(), i32> {
+        Err(-2)
+    }
+
+    pub fn set_level(_module: &'static str, _level: Level) -> Result<(), i32> {
+        Err(-2)
+    }
+
+    pub fn set_default_level(_level: Level) -> Result<(), i32> {
+        Err(-2)
+    }
+}
+
+#[cfg(all(feature = "log", not(feature = "defmt")))]
+mod backend {
+    use super::{DEFAULT_LEVEL, Level, MAX_MODULE_LEVELS};
+    use crate::driver::usart::Usart;
+    use core::cell::RefCell;
+    use core::fmt::{self, Write};
+    use cortex_m::interrupt::{self, Mutex};
+    use log::{LevelFilter, Metadata, Record};
+    use logfmt::filter::Filter;
+    use logfmt::line;
+    use rtt_target::UpChannel;
+
+    /// Module paths in records start with the crate name, rules do not
+    const CRATE: &str = env!("CARGO_CRATE_NAME");
+
+    /// Byte output of a USART driver, as a trait object so the sink is not
+    /// generic over the driver type
+    trait Output {
+        fn write_bytes(&mut self, bytes: &[u8]);
+    }
+
+    impl<U: Usart<'static>> Output for U {
+        fn write_bytes(&mut self, bytes: &[u8]) {
+            let _ = self.send(bytes);
+        }
+    }
+
+    enum Sink {
+        Rtt(UpChannel),
+        Usart(&'static mut dyn Output),
+    }
+
+    impl Write for Sink {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match self {
+                // Output beyond the free buffer space is dropped rather
+                // than waited on
+                Sink::Rtt(channel) => {
+                    channel.write(s.as_bytes());
+                }
+                Sink::Usart(usart) => usart.write_bytes(s.as_bytes()),
+            }
+            Ok(())
+        }
+    }
+
+    struct Logger {
+        filter: Filter<MAX_MODULE_LEVELS>,
+        sink: Option<Sink>,
+    }
+
+    // Drivers are not Send; the sink is only used inside critical sections
+    struct Shared(Logger);
+    unsafe impl Send for Shared {}
+
+    static LOGGER: Mutex<RefCell<Shared>> = Mutex::new(RefCell::new(Shared(Logger {
+        filter: Filter::new(DEFAULT_LEVEL),
+        sink: None,
+    })));
+
+    fn with_logger<R>(f: impl FnOnce(&mut Logger) -> R) -> R {
+        interrupt::free(|cs| f(&mut LOGGER.borrow(cs).borrow_mut().0))
+    }
+
+    fn level(level: log::Level) -> Level {
+        match level {
+            log::Level::Error => Level::Error,
+            log::Level::Warn => Level::Warn,
+            log::Level::Info => Level::Info,
+            log::Level::Debug => Level::Debug,
+            log::Level::Trace => Level::Trace,
+        }
+    }
+
+    fn level_filter(level: Level) -> LevelFilter {
+        match level {
+            Level::Off => LevelFilter::Off,
+            Level::Error => LevelFilter::Error,
+            Level::Warn => LevelFilter::Warn,
+            Level::Info => LevelFilter::Info,
+            Level::Debug => LevelFilter::Debug,
+            Level::Trace => LevelFilter::Trace,
+        }
+    }
+
+    struct Facade;
+
+    impl log::Log for Facade {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            let module = line::module(metadata.target(), CRATE);
+            with_logger(|logger| logger.filter.enabled(module, level(metadata.level())))
+        }
+
+        fn log(&self, record: &Record) {
+            let module = line::module(record.target(), CRATE);
+            let micros = crate::clock::now().as_micros();
+            with_logger(|logger| {
+                if !logger.filter.enabled(module, level(record.level())) {
+                    return;
+                }
+                if let Some(sink) = &mut logger.sink {
+                    let _ = line::write_prefix(sink, micros, level(record.level()), module);
+                    let _ = writeln!(sink, "{}", record.args());
+                }
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    static FACADE: Facade = Facade;
+
+    fn install(sink: Sink) {
+        let max_level = with_logger(|logger| {
+            logger.sink = Some(sink);
+            logger.filter.max_level()
+        });
+        // Only called from `main`, so never concurrently with another install
+        unsafe {
+            let _ = log::set_logger_racy(&FACADE);
+            log::set_max_level_racy(level_filter(max_level));
+        }
+    }
+
+    pub fn init() {
+        if let Some(terminal) = crate::rtt::take_terminal() {
+            install(Sink::Rtt(terminal));
+        }
+    }
+
+    pub fn init_usart<U: Usart<'static>>(usart: &'static mut U) -> Result<(), i32> {
+        install(Sink::Usart(usart));
+        Ok(())
+    }
+
+    fn update_max_level() {
+        // `log::set_max_level` needs atomic CAS, which ARMv6-M lacks; inside
+        // the critical section no other update can interleave
+        with_logger(|logger| unsafe {
+            log::set_max_level_racy(level_filter(logger.filter.max_level()));
+        });
+    }
+
+    pub fn set_level(module: &'static str, level: Level) -> Result<(), i32> {
+        with_logger(|logger| logger.filter.set(module, level))?;
+        update_max_level();
+        Ok(())
+    }
+
+    pub fn set_default_level(level: Level) -> Result<(), i32> {
+        with_logger(|logger| logger.filter.set_default(level));
+        update_max_level();
+        Ok(())
+    }
+}
+
+#[cfg(not(any(feature = "log", feature = "defmt")))]
+mod backend {
+    use super::Level;
+    use crate::driver::usart::Usart;
+
+    pub fn init() {}
+
+    pub fn init_usart<U: Usart<'static>>(_usart: &'static mut U) -> Result<(), i32> {
+        Err(-2)
+    }
+
+    pub fn set_level(_module: &'static str, _level: Level) -> Result<(), i32> {
+        Err(-2)
+    }
+
+    pub fn set_default_level(_level: Level) -> Result<(), i32> {
+        Err(-2)
+    }
+}
+
+/// Set up the backend of the build and send records to RTT channel 0,
+/// "Terminal". Call once, early in `main`; records before it are dropped.
+pub fn init() {
+    backend::init();
+}
+
+/// Send the records to `usart` instead of RTT, as text lines. The USART
+/// must be configured; lines are sent blocking with interrupts masked, so
+/// logging from timing-sensitive code is better left on RTT. Returns
+/// Err(-2) unless the build uses `log`.
+pub fn init_usart<U: Usart<'static>>(usart: &'static mut U) -> Result<(), i32> {
+    backend::init_usart(usart)
+}
+
+/// Log `module` (a path like `driver::i2c`, without the crate name) and
+/// its submodules at `level`. Returns Err(-1) once `MAX_MODULE_LEVELS`
+/// modules have their own level, Err(-2) unless the build uses `log`.
+pub fn set_level(module: &'static str, level: Level) -> Result<(), i32> {
+    backend::set_level(module, level)
+}
+
+/// Log modules without their own level at `level`. Returns Err(-2) unless
+/// the build uses `log`.
+pub fn set_default_level(level: Level) -> Result<(), i32> {
+    backend::set_default_level(level)
+}
@@ -5,56 +5,129 @@
 //! application management.
 //!
 //! This crate serves as the main entry point, initializing the system and
-//! orchestrating application execution on Cortex-M4 based STM32 devices.
+//! orchestrating application execution on Cortex-M based STM32 devices.
 #![no_std]
 #![no_main]
 
 extern crate alloc;
-use alloc::boxed::Box;
 use alloc_cortex_m::CortexMHeap;
 use cortex_m_rt::entry;
-use panic_halt as _;
 
 #[global_allocator]
 static ALLOCATOR: CortexMHeap = CortexMHeap::empty();
 
+// Only used by driver callbacks, the app registry is static
 const HEAP_SIZE: usize = 1024; // 1 KB
 
 mod apps;
 mod arch;
+mod board;
 mod bsp;
+mod clock;
 mod components;
 mod driver;
+#[cfg(feature = "rtos")]
+mod kernel;
+mod logging;
 mod mcu;
+mod panic;
+mod power;
+mod profile;
+#[cfg(any(feature = "log", feature = "defmt"))]
+mod rtt;
+mod runtime;
+#[cfg(any(feature = "slot-a", feature = "slot-b"))]
+mod slot;
 mod utils;
 
-use crate::apps::{init_all_apps, init_app_registry, register_app, run_all_loop_steps};
+use crate::apps::blink::BlinkApp;
+use crate::apps::{AppSlot, init_all_apps, register_app, run_scheduler};
+use crate::logging::{info, warn};
+use executor::app::ExecutorApp;
+
+static BLINK_APP: AppSlot<BlinkApp> = AppSlot::new();
+static ASYNC_APP: AppSlot<ExecutorApp<{ runtime::MAX_TASKS }>> = AppSlot::new();
+#[cfg(feature = "usb-serial")]
+static USB_SERIAL_APP: AppSlot<apps::usb_serial::UsbSerialApp> = AppSlot::new();
 
 #[entry]
 fn main() -> ! {
     // Initialize the allocator
-    unsafe { ALLOCATOR.init(cortex_m_rt::heap_start() as usize, HEAP_SIZE) };
+    let heap_start = cortex_m_rt::heap_start() as usize;
+    unsafe { ALLOCATOR.init(heap_start, HEAP_SIZE) };
+
+    // Log to RTT; `logging::init_usart` switches to a serial port
+    logging::init();
 
-    // Initialize SysTick for 1ms interrupts (assuming 16 MHz system clock)
-    let _ = arch::cortex_m4::systick::systick_init_1ms(16_000_000);
+    // Install fault handlers, recover crash records and find the reset reason
+    arch::cpu::fault::init();
+    // Guard the stack, flash read-only, RAM not executable; the STM32F1 has
+    // no MPU and the Cortex-M0+ support leaves it off
+    #[cfg(not(feature = "stm32g030"))]
+    let mpu_result = arch::cpu::mpu::init_default((heap_start + HEAP_SIZE) as u32);
+    panic::init();
+    let reset_reason = driver::reset::init();
+    let from_standby = power::woke_from_standby();
 
-    // Initialize app registry
-    init_app_registry();
+    // Initialize SysTick for 1ms interrupts (system clock still on HSI)
+    let _ = arch::cpu::systick::systick_init_1ms(mcu::device::HSI_HZ);
+
+    info!("boot, reset reason {}", reset_reason.as_str());
+    if from_standby {
+        info!("woke from standby");
+    }
+    if let Some(fault) = arch::cpu::fault::last_fault() {
+        warn!(
+            "last run ended in {} at pc {}",
+            fault.kind.as_str(),
+            fault.pc()
+        );
+    }
+    if let Some(report) = panic::last_panic() {
+        warn!(
+            "last run panicked at {}:{}: {}",
+            report.file, report.line, report.message
+        );
+    }
+    // Err(-2) is the STM32F1 without an MPU
+    #[cfg(not(feature = "stm32g030"))]
+    if mpu_result == Err(-1) {
+        warn!("stack guard does not fit, memory protection off");
+    }
 
     // Register apps
-    // register_app(Box::new(apps::empty::create_empty_app()));
-    register_app(Box::new(apps::blink::create_simple_blink_app()));
-
-    // Initialize all apps
-    if let Err(_) = init_all_apps() {
-        // Handle initialization error
-        loop {
-            cortex_m::asm::nop();
-        }
+    if let Some(blink) = BLINK_APP.init(apps::blink::create_simple_blink_app()) {
+        let _ = register_app("blink", blink);
+    }
+    // Runs the tasks passed to `runtime::spawn`
+    if let Some(tasks) = ASYNC_APP.init(runtime::app()) {
+        let _ = register_app("async", tasks);
     }
+    #[cfg(feature = "usb-serial")]
+    if let Some(usb_serial) = USB_SERIAL_APP.init(apps::usb_serial::UsbSerialApp::new()) {
+        let _ = register_app("usb-serial", usb_serial);
+    }
+
+    // Initialize all apps; failed ones are reported by `apps::app_state`
+    // and the others still run
+    let _ = init_all_apps();
 
-    // Run the main loop
-    loop {
-        run_all_loop_steps();
+    // Up and running: keep this image rather than let the bootloader roll
+    // it back at the next reset
+    #[cfg(any(feature = "slot-a", feature = "slot-b"))]
+    {
+        if slot::is_trial() == Ok(true) {
+            info!("confirming the new image in slot {}", slot::SLOT.name());
+        }
+        if let Err(err) = slot::confirm() {
+            warn!(
+                "cannot confirm the image in slot {}: {}",
+                slot::SLOT.name(),
+                err
+            );
+        }
     }
+
+    // Run the apps, sleeping while none is due
+    run_scheduler()
 }
@@ -5,6 +5,9 @@
 //!
 //! Each MCU module provides type-safe access to hardware registers and
 //! implements the low-level functionality required by the driver layer.
+#[cfg(any(feature = "stm32f407", feature = "stm32f401", feature = "stm32f411"))]
+pub mod stm32f4;
+
 #[cfg(feature = "stm32f407")]
 pub mod stm32f407;
 
@@ -19,3 +22,19 @@ pub mod stm32f103;
 
 #[cfg(feature = "stm32g030")]
 pub mod stm32g030;
+
+// Register map of the selected MCU, for code shared between parts
+#[cfg(feature = "stm32f407")]
+pub use stm32f407 as device;
+
+#[cfg(feature = "stm32f401")]
+pub use stm32f401 as device;
+
+#[cfg(feature = "stm32f411")]
+pub use stm32f411 as device;
+
+#[cfg(feature = "stm32f103")]
+pub use stm32f103 as device;
+
+#[cfg(feature = "stm32g030")]
+pub use stm32g030 as device;
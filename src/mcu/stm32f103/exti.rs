@@ -0,0 +1,35 @@
+// EXTI (External Interrupt) peripheral definitions
+// Generated from STM32F103 SVD file
+//
+// The port of lines 0-15 is selected in AFIO_EXTICR, see `gpio::AFIO`.
+
+use super::{EXTI_BASEADDR, PeripheralAccess};
+
+// EXTI Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub imr: u32,   // RW: Interrupt mask register
+    pub emr: u32,   // RW: Event mask register
+    pub rtsr: u32,  // RW: Rising Trigger selection register
+    pub ftsr: u32,  // RW: Falling Trigger selection register
+    pub swier: u32, // RW: Software interrupt event register
+    pub pr: u32,    // RW: Pending register (write 1 to clear)
+}
+
+// EXTI peripheral instance
+pub struct EXTI;
+
+impl PeripheralAccess for EXTI {
+    const BASE_ADDRESS: u32 = EXTI_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+/// Number of EXTI lines
+pub const EXTI_LINES: u8 = 19;
+
+/// EXTI line of the RTC alarm
+pub const EXTI_LINE_RTC_ALARM: u8 = 17;
+
+// AFIO_EXTICR register fields, four bits per line selecting the port
+pub const EXTICR_EXTI_WIDTH: u32 = 4;
+pub const EXTICR_EXTI_MASK: u32 = 0xF;
@@ -0,0 +1,752 @@
+// GPIO (General Purpose Input/Output) and AFIO peripheral definitions
+// Generated from STM32F103 SVD file
+
+use super::{
+    AFIO_BASEADDR, GPIOA_BASEADDR, GPIOB_BASEADDR, GPIOC_BASEADDR, GPIOD_BASEADDR, GPIOE_BASEADDR,
+    GPIOF_BASEADDR, GPIOG_BASEADDR, PeripheralAccess,
+};
+
+// GPIO Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub crl: u32,  // RW: Port configuration register low (pins 0-7)
+    pub crh: u32,  // RW: Port configuration register high (pins 8-15)
+    pub idr: u32,  // RO: Port input data register
+    pub odr: u32,  // RW: Port output data register
+    pub bsrr: u32, // WO: Port bit set/reset register
+    pub brr: u32,  // WO: Port bit reset register
+    pub lckr: u32, // RW: Port configuration lock register
+}
+
+// GPIO Register Field Definitions
+
+// CRL register fields
+pub const CRL_CNF7_POS: u32 = 30;
+pub const CRL_CNF7_WIDTH: u32 = 2;
+pub const CRL_CNF7_MASK: u32 = 0x3 << 30;
+
+pub const CRL_MODE7_POS: u32 = 28;
+pub const CRL_MODE7_WIDTH: u32 = 2;
+pub const CRL_MODE7_MASK: u32 = 0x3 << 28;
+
+pub const CRL_CNF6_POS: u32 = 26;
+pub const CRL_CNF6_WIDTH: u32 = 2;
+pub const CRL_CNF6_MASK: u32 = 0x3 << 26;
+
+pub const CRL_MODE6_POS: u32 = 24;
+pub const CRL_MODE6_WIDTH: u32 = 2;
+pub const CRL_MODE6_MASK: u32 = 0x3 << 24;
+
+pub const CRL_CNF5_POS: u32 = 22;
+pub const CRL_CNF5_WIDTH: u32 = 2;
+pub const CRL_CNF5_MASK: u32 = 0x3 << 22;
+
+pub const CRL_MODE5_POS: u32 = 20;
+pub const CRL_MODE5_WIDTH: u32 = 2;
+pub const CRL_MODE5_MASK: u32 = 0x3 << 20;
+
+pub const CRL_CNF4_POS: u32 = 18;
+pub const CRL_CNF4_WIDTH: u32 = 2;
+pub const CRL_CNF4_MASK: u32 = 0x3 << 18;
+
+pub const CRL_MODE4_POS: u32 = 16;
+pub const CRL_MODE4_WIDTH: u32 = 2;
+pub const CRL_MODE4_MASK: u32 = 0x3 << 16;
+
+pub const CRL_CNF3_POS: u32 = 14;
+pub const CRL_CNF3_WIDTH: u32 = 2;
+pub const CRL_CNF3_MASK: u32 = 0x3 << 14;
+
+pub const CRL_MODE3_POS: u32 = 12;
+pub const CRL_MODE3_WIDTH: u32 = 2;
+pub const CRL_MODE3_MASK: u32 = 0x3 << 12;
+
+pub const CRL_CNF2_POS: u32 = 10;
+pub const CRL_CNF2_WIDTH: u32 = 2;
+pub const CRL_CNF2_MASK: u32 = 0x3 << 10;
+
+pub const CRL_MODE2_POS: u32 = 8;
+pub const CRL_MODE2_WIDTH: u32 = 2;
+pub const CRL_MODE2_MASK: u32 = 0x3 << 8;
+
+pub const CRL_CNF1_POS: u32 = 6;
+pub const CRL_CNF1_WIDTH: u32 = 2;
+pub const CRL_CNF1_MASK: u32 = 0x3 << 6;
+
+pub const CRL_MODE1_POS: u32 = 4;
+pub const CRL_MODE1_WIDTH: u32 = 2;
+pub const CRL_MODE1_MASK: u32 = 0x3 << 4;
+
+pub const CRL_CNF0_POS: u32 = 2;
+pub const CRL_CNF0_WIDTH: u32 = 2;
+pub const CRL_CNF0_MASK: u32 = 0x3 << 2;
+
+pub const CRL_MODE0_POS: u32 = 0;
+pub const CRL_MODE0_WIDTH: u32 = 2;
+pub const CRL_MODE0_MASK: u32 = 0x3 << 0;
+
+// CRH register fields
+pub const CRH_CNF15_POS: u32 = 30;
+pub const CRH_CNF15_WIDTH: u32 = 2;
+pub const CRH_CNF15_MASK: u32 = 0x3 << 30;
+
+pub const CRH_MODE15_POS: u32 = 28;
+pub const CRH_MODE15_WIDTH: u32 = 2;
+pub const CRH_MODE15_MASK: u32 = 0x3 << 28;
+
+pub const CRH_CNF14_POS: u32 = 26;
+pub const CRH_CNF14_WIDTH: u32 = 2;
+pub const CRH_CNF14_MASK: u32 = 0x3 << 26;
+
+pub const CRH_MODE14_POS: u32 = 24;
+pub const CRH_MODE14_WIDTH: u32 = 2;
+pub const CRH_MODE14_MASK: u32 = 0x3 << 24;
+
+pub const CRH_CNF13_POS: u32 = 22;
+pub const CRH_CNF13_WIDTH: u32 = 2;
+pub const CRH_CNF13_MASK: u32 = 0x3 << 22;
+
+pub const CRH_MODE13_POS: u32 = 20;
+pub const CRH_MODE13_WIDTH: u32 = 2;
+pub const CRH_MODE13_MASK: u32 = 0x3 << 20;
+
+pub const CRH_CNF12_POS: u32 = 18;
+pub const CRH_CNF12_WIDTH: u32 = 2;
+pub const CRH_CNF12_MASK: u32 = 0x3 << 18;
+
+pub const CRH_MODE12_POS: u32 = 16;
+pub const CRH_MODE12_WIDTH: u32 = 2;
+pub const CRH_MODE12_MASK: u32 = 0x3 << 16;
+
+pub const CRH_CNF11_POS: u32 = 14;
+pub const CRH_CNF11_WIDTH: u32 = 2;
+pub const CRH_CNF11_MASK: u32 = 0x3 << 14;
+
+pub const CRH_MODE11_POS: u32 = 12;
+pub const CRH_MODE11_WIDTH: u32 = 2;
+pub const CRH_MODE11_MASK: u32 = 0x3 << 12;
+
+pub const CRH_CNF10_POS: u32 = 10;
+pub const CRH_CNF10_WIDTH: u32 = 2;
+pub const CRH_CNF10_MASK: u32 = 0x3 << 10;
+
+pub const CRH_MODE10_POS: u32 = 8;
+pub const CRH_MODE10_WIDTH: u32 = 2;
+pub const CRH_MODE10_MASK: u32 = 0x3 << 8;
+
+pub const CRH_CNF9_POS: u32 = 6;
+pub const CRH_CNF9_WIDTH: u32 = 2;
+pub const CRH_CNF9_MASK: u32 = 0x3 << 6;
+
+pub const CRH_MODE9_POS: u32 = 4;
+pub const CRH_MODE9_WIDTH: u32 = 2;
+pub const CRH_MODE9_MASK: u32 = 0x3 << 4;
+
+pub const CRH_CNF8_POS: u32 = 2;
+pub const CRH_CNF8_WIDTH: u32 = 2;
+pub const CRH_CNF8_MASK: u32 = 0x3 << 2;
+
+pub const CRH_MODE8_POS: u32 = 0;
+pub const CRH_MODE8_WIDTH: u32 = 2;
+pub const CRH_MODE8_MASK: u32 = 0x3 << 0;
+
+// MODE enumerated values (same for all pins)
+pub const MODE_INPUT: u32 = 0;
+pub const MODE_OUTPUT_10MHZ: u32 = 1;
+pub const MODE_OUTPUT_2MHZ: u32 = 2;
+pub const MODE_OUTPUT_50MHZ: u32 = 3;
+
+// CNF enumerated values in input mode (MODE = 0)
+pub const CNF_INPUT_ANALOG: u32 = 0;
+pub const CNF_INPUT_FLOATING: u32 = 1;
+pub const CNF_INPUT_PULL: u32 = 2;
+
+// CNF enumerated values in output mode (MODE > 0)
+pub const CNF_OUTPUT_PUSHPULL: u32 = 0;
+pub const CNF_OUTPUT_OPENDRAIN: u32 = 1;
+pub const CNF_ALTERNATE_PUSHPULL: u32 = 2;
+pub const CNF_ALTERNATE_OPENDRAIN: u32 = 3;
+
+// CRL/CRH pin field (4 bits per pin: CNF[1:0] MODE[1:0])
+pub const CR_PIN_WIDTH: u32 = 4;
+pub const CR_PIN_MASK: u32 = 0xF;
+
+// IDR register fields (individual pins)
+pub const IDR_IDR15_POS: u32 = 15;
+pub const IDR_IDR15_WIDTH: u32 = 1;
+pub const IDR_IDR15_MASK: u32 = 0x1 << 15;
+
+pub const IDR_IDR14_POS: u32 = 14;
+pub const IDR_IDR14_WIDTH: u32 = 1;
+pub const IDR_IDR14_MASK: u32 = 0x1 << 14;
+
+pub const IDR_IDR13_POS: u32 = 13;
+pub const IDR_IDR13_WIDTH: u32 = 1;
+pub const IDR_IDR13_MASK: u32 = 0x1 << 13;
+
+pub const IDR_IDR12_POS: u32 = 12;
+pub const IDR_IDR12_WIDTH: u32 = 1;
+pub const IDR_IDR12_MASK: u32 = 0x1 << 12;
+
+pub const IDR_IDR11_POS: u32 = 11;
+pub const IDR_IDR11_WIDTH: u32 = 1;
+pub const IDR_IDR11_MASK: u32 = 0x1 << 11;
+
+pub const IDR_IDR10_POS: u32 = 10;
+pub const IDR_IDR10_WIDTH: u32 = 1;
+pub const IDR_IDR10_MASK: u32 = 0x1 << 10;
+
+pub const IDR_IDR9_POS: u32 = 9;
+pub const IDR_IDR9_WIDTH: u32 = 1;
+pub const IDR_IDR9_MASK: u32 = 0x1 << 9;
+
+pub const IDR_IDR8_POS: u32 = 8;
+pub const IDR_IDR8_WIDTH: u32 = 1;
+pub const IDR_IDR8_MASK: u32 = 0x1 << 8;
+
+pub const IDR_IDR7_POS: u32 = 7;
+pub const IDR_IDR7_WIDTH: u32 = 1;
+pub const IDR_IDR7_MASK: u32 = 0x1 << 7;
+
+pub const IDR_IDR6_POS: u32 = 6;
+pub const IDR_IDR6_WIDTH: u32 = 1;
+pub const IDR_IDR6_MASK: u32 = 0x1 << 6;
+
+pub const IDR_IDR5_POS: u32 = 5;
+pub const IDR_IDR5_WIDTH: u32 = 1;
+pub const IDR_IDR5_MASK: u32 = 0x1 << 5;
+
+pub const IDR_IDR4_POS: u32 = 4;
+pub const IDR_IDR4_WIDTH: u32 = 1;
+pub const IDR_IDR4_MASK: u32 = 0x1 << 4;
+
+pub const IDR_IDR3_POS: u32 = 3;
+pub const IDR_IDR3_WIDTH: u32 = 1;
+pub const IDR_IDR3_MASK: u32 = 0x1 << 3;
+
+pub const IDR_IDR2_POS: u32 = 2;
+pub const IDR_IDR2_WIDTH: u32 = 1;
+pub const IDR_IDR2_MASK: u32 = 0x1 << 2;
+
+pub const IDR_IDR1_POS: u32 = 1;
+pub const IDR_IDR1_WIDTH: u32 = 1;
+pub const IDR_IDR1_MASK: u32 = 0x1 << 1;
+
+pub const IDR_IDR0_POS: u32 = 0;
+pub const IDR_IDR0_WIDTH: u32 = 1;
+pub const IDR_IDR0_MASK: u32 = 0x1 << 0;
+
+// ODR register fields (individual pins)
+pub const ODR_ODR15_POS: u32 = 15;
+pub const ODR_ODR15_WIDTH: u32 = 1;
+pub const ODR_ODR15_MASK: u32 = 0x1 << 15;
+
+pub const ODR_ODR14_POS: u32 = 14;
+pub const ODR_ODR14_WIDTH: u32 = 1;
+pub const ODR_ODR14_MASK: u32 = 0x1 << 14;
+
+pub const ODR_ODR13_POS: u32 = 13;
+pub const ODR_ODR13_WIDTH: u32 = 1;
+pub const ODR_ODR13_MASK: u32 = 0x1 << 13;
+
+pub const ODR_ODR12_POS: u32 = 12;
+pub const ODR_ODR12_WIDTH: u32 = 1;
+pub const ODR_ODR12_MASK: u32 = 0x1 << 12;
+
+pub const ODR_ODR11_POS: u32 = 11;
+pub const ODR_ODR11_WIDTH: u32 = 1;
+pub const ODR_ODR11_MASK: u32 = 0x1 << 11;
+
+pub const ODR_ODR10_POS: u32 = 10;
+pub const ODR_ODR10_WIDTH: u32 = 1;
+pub const ODR_ODR10_MASK: u32 = 0x1 << 10;
+
+pub const ODR_ODR9_POS: u32 = 9;
+pub const ODR_ODR9_WIDTH: u32 = 1;
+pub const ODR_ODR9_MASK: u32 = 0x1 << 9;
+
+pub const ODR_ODR8_POS: u32 = 8;
+pub const ODR_ODR8_WIDTH: u32 = 1;
+pub const ODR_ODR8_MASK: u32 = 0x1 << 8;
+
+pub const ODR_ODR7_POS: u32 = 7;
+pub const ODR_ODR7_WIDTH: u32 = 1;
+pub const ODR_ODR7_MASK: u32 = 0x1 << 7;
+
+pub const ODR_ODR6_POS: u32 = 6;
+pub const ODR_ODR6_WIDTH: u32 = 1;
+pub const ODR_ODR6_MASK: u32 = 0x1 << 6;
+
+pub const ODR_ODR5_POS: u32 = 5;
+pub const ODR_ODR5_WIDTH: u32 = 1;
+pub const ODR_ODR5_MASK: u32 = 0x1 << 5;
+
+pub const ODR_ODR4_POS: u32 = 4;
+pub const ODR_ODR4_WIDTH: u32 = 1;
+pub const ODR_ODR4_MASK: u32 = 0x1 << 4;
+
+pub const ODR_ODR3_POS: u32 = 3;
+pub const ODR_ODR3_WIDTH: u32 = 1;
+pub const ODR_ODR3_MASK: u32 = 0x1 << 3;
+
+pub const ODR_ODR2_POS: u32 = 2;
+pub const ODR_ODR2_WIDTH: u32 = 1;
+pub const ODR_ODR2_MASK: u32 = 0x1 << 2;
+
+pub const ODR_ODR1_POS: u32 = 1;
+pub const ODR_ODR1_WIDTH: u32 = 1;
+pub const ODR_ODR1_MASK: u32 = 0x1 << 1;
+
+pub const ODR_ODR0_POS: u32 = 0;
+pub const ODR_ODR0_WIDTH: u32 = 1;
+pub const ODR_ODR0_MASK: u32 = 0x1 << 0;
+
+// LCKR register fields
+pub const LCKR_LCKK_POS: u32 = 16;
+pub const LCKR_LCKK_WIDTH: u32 = 1;
+pub const LCKR_LCKK_MASK: u32 = 0x1 << 16;
+
+pub const LCKR_LCK_POS: u32 = 0;
+pub const LCKR_LCK_WIDTH: u32 = 16;
+pub const LCKR_LCK_MASK: u32 = 0xFFFF << 0;
+
+// AFIO Register Block
+#[repr(C)]
+pub struct AfioRegisterBlock {
+    pub evcr: u32,        // RW: Event control register
+    pub mapr: u32,        // RW: AF remap and debug I/O configuration register
+    pub exticr: [u32; 4], // RW: External interrupt configuration registers 1-4
+    _reserved0: u32,
+    pub mapr2: u32, // RW: AF remap and debug I/O configuration register 2
+}
+
+// MAPR register fields
+pub const MAPR_SWJ_CFG_POS: u32 = 24;
+pub const MAPR_SWJ_CFG_WIDTH: u32 = 3;
+pub const MAPR_SWJ_CFG_MASK: u32 = 0x7 << 24;
+// SWJ_CFG enumerated values (write-only, reads back undefined)
+pub const MAPR_SWJ_CFG_FULL: u32 = 0 << 24;
+pub const MAPR_SWJ_CFG_NO_NJTRST: u32 = 1 << 24;
+pub const MAPR_SWJ_CFG_SWD_ONLY: u32 = 2 << 24;
+pub const MAPR_SWJ_CFG_DISABLED: u32 = 4 << 24;
+
+pub const MAPR_ADC2_ETRGREG_REMAP_POS: u32 = 20;
+pub const MAPR_ADC2_ETRGREG_REMAP_WIDTH: u32 = 1;
+pub const MAPR_ADC2_ETRGREG_REMAP_MASK: u32 = 0x1 << 20;
+
+pub const MAPR_ADC2_ETRGINJ_REMAP_POS: u32 = 19;
+pub const MAPR_ADC2_ETRGINJ_REMAP_WIDTH: u32 = 1;
+pub const MAPR_ADC2_ETRGINJ_REMAP_MASK: u32 = 0x1 << 19;
+
+pub const MAPR_ADC1_ETRGREG_REMAP_POS: u32 = 18;
+pub const MAPR_ADC1_ETRGREG_REMAP_WIDTH: u32 = 1;
+pub const MAPR_ADC1_ETRGREG_REMAP_MASK: u32 = 0x1 << 18;
+
+pub const MAPR_ADC1_ETRGINJ_REMAP_POS: u32 = 17;
+pub const MAPR_ADC1_ETRGINJ_REMAP_WIDTH: u32 = 1;
+pub const MAPR_ADC1_ETRGINJ_REMAP_MASK: u32 = 0x1 << 17;
+
+pub const MAPR_TIM5CH4_IREMAP_POS: u32 = 16;
+pub const MAPR_TIM5CH4_IREMAP_WIDTH: u32 = 1;
+pub const MAPR_TIM5CH4_IREMAP_MASK: u32 = 0x1 << 16;
+
+pub const MAPR_PD01_REMAP_POS: u32 = 15;
+pub const MAPR_PD01_REMAP_WIDTH: u32 = 1;
+pub const MAPR_PD01_REMAP_MASK: u32 = 0x1 << 15;
+
+pub const MAPR_CAN_REMAP_POS: u32 = 13;
+pub const MAPR_CAN_REMAP_WIDTH: u32 = 2;
+pub const MAPR_CAN_REMAP_MASK: u32 = 0x3 << 13;
+
+pub const MAPR_TIM4_REMAP_POS: u32 = 12;
+pub const MAPR_TIM4_REMAP_WIDTH: u32 = 1;
+pub const MAPR_TIM4_REMAP_MASK: u32 = 0x1 << 12;
+
+pub const MAPR_TIM3_REMAP_POS: u32 = 10;
+pub const MAPR_TIM3_REMAP_WIDTH: u32 = 2;
+pub const MAPR_TIM3_REMAP_MASK: u32 = 0x3 << 10;
+
+pub const MAPR_TIM2_REMAP_POS: u32 = 8;
+pub const MAPR_TIM2_REMAP_WIDTH: u32 = 2;
+pub const MAPR_TIM2_REMAP_MASK: u32 = 0x3 << 8;
+
+pub const MAPR_TIM1_REMAP_POS: u32 = 6;
+pub const MAPR_TIM1_REMAP_WIDTH: u32 = 2;
+pub const MAPR_TIM1_REMAP_MASK: u32 = 0x3 << 6;
+
+pub const MAPR_USART3_REMAP_POS: u32 = 4;
+pub const MAPR_USART3_REMAP_WIDTH: u32 = 2;
+pub const MAPR_USART3_REMAP_MASK: u32 = 0x3 << 4;
+// USART3_REMAP enumerated values
+pub const MAPR_USART3_REMAP_NONE: u32 = 0 << 4;
+pub const MAPR_USART3_REMAP_PARTIAL: u32 = 1 << 4;
+pub const MAPR_USART3_REMAP_FULL: u32 = 3 << 4;
+
+pub const MAPR_USART2_REMAP_POS: u32 = 3;
+pub const MAPR_USART2_REMAP_WIDTH: u32 = 1;
+pub const MAPR_USART2_REMAP_MASK: u32 = 0x1 << 3;
+
+pub const MAPR_USART1_REMAP_POS: u32 = 2;
+pub const MAPR_USART1_REMAP_WIDTH: u32 = 1;
+pub const MAPR_USART1_REMAP_MASK: u32 = 0x1 << 2;
+
+pub const MAPR_I2C1_REMAP_POS: u32 = 1;
+pub const MAPR_I2C1_REMAP_WIDTH: u32 = 1;
+pub const MAPR_I2C1_REMAP_MASK: u32 = 0x1 << 1;
+
+pub const MAPR_SPI1_REMAP_POS: u32 = 0;
+pub const MAPR_SPI1_REMAP_WIDTH: u32 = 1;
+pub const MAPR_SPI1_REMAP_MASK: u32 = 0x1 << 0;
+
+// GPIO peripheral instances
+pub struct GPIOA;
+pub struct GPIOB;
+pub struct GPIOC;
+pub struct GPIOD;
+pub struct GPIOE;
+pub struct GPIOF;
+pub struct GPIOG;
+pub struct AFIO;
+
+impl PeripheralAccess for GPIOA {
+    const BASE_ADDRESS: u32 = GPIOA_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOB {
+    const BASE_ADDRESS: u32 = GPIOB_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOC {
+    const BASE_ADDRESS: u32 = GPIOC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOD {
+    const BASE_ADDRESS: u32 = GPIOD_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOE {
+    const BASE_ADDRESS: u32 = GPIOE_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOF {
+    const BASE_ADDRESS: u32 = GPIOF_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOG {
+    const BASE_ADDRESS: u32 = GPIOG_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for AFIO {
+    const BASE_ADDRESS: u32 = AFIO_BASEADDR;
+    type RegisterBlock = AfioRegisterBlock;
+}
+
+/// Base address of GPIO port `port` (0=GPIOA), or `None` if the part has
+/// no such port. Ports F and G only exist on the 144-pin high-density parts.
+pub const fn port_base(port: u8) -> Option<u32> {
+    match port {
+        0 => Some(GPIOA_BASEADDR),
+        1 => Some(GPIOB_BASEADDR),
+        2 => Some(GPIOC_BASEADDR),
+        3 => Some(GPIOD_BASEADDR),
+        4 => Some(GPIOE_BASEADDR),
+        5 => Some(GPIOF_BASEADDR),
+        6 => Some(GPIOG_BASEADDR),
+        _ => None,
+    }
+}
+
+/// Peripheral signals that can be routed to a pin, either at their default
+/// location or through an AFIO remap
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AfSignal {
+    USART1_TX,
+    USART1_RX,
+    USART1_CK,
+    USART1_CTS,
+    USART1_RTS,
+    USART2_TX,
+    USART2_RX,
+    USART2_CK,
+    USART2_CTS,
+    USART2_RTS,
+    USART3_TX,
+    USART3_RX,
+    USART3_CK,
+    USART3_CTS,
+    USART3_RTS,
+    UART4_TX,
+    UART4_RX,
+    UART5_TX,
+    UART5_RX,
+    SPI1_NSS,
+    SPI1_SCK,
+    SPI1_MISO,
+    SPI1_MOSI,
+    SPI2_NSS,
+    SPI2_SCK,
+    SPI2_MISO,
+    SPI2_MOSI,
+    SPI3_NSS,
+    SPI3_SCK,
+    SPI3_MISO,
+    SPI3_MOSI,
+    I2C1_SCL,
+    I2C1_SDA,
+    I2C1_SMBA,
+    I2C2_SCL,
+    I2C2_SDA,
+    I2C2_SMBA,
+}
+
+impl AfSignal {
+    /// Whether the signal is a peripheral input. The F1 GPIO has no alternate
+    /// function input mode: inputs are configured as plain (floating or
+    /// pulled) inputs, outputs as alternate function push-pull/open-drain.
+    /// SPI pins are classified for master mode.
+    pub const fn is_input(self) -> bool {
+        matches!(
+            self,
+            AfSignal::USART1_RX
+                | AfSignal::USART1_CTS
+                | AfSignal::USART2_RX
+                | AfSignal::USART2_CTS
+                | AfSignal::USART3_RX
+                | AfSignal::USART3_CTS
+                | AfSignal::UART4_RX
+                | AfSignal::UART5_RX
+                | AfSignal::SPI1_MISO
+                | AfSignal::SPI2_MISO
+                | AfSignal::SPI3_MISO
+        )
+    }
+}
+
+/// One entry of the remap table: `signal` is available on `port`/`pin`
+/// (port 0=GPIOA) when the MAPR bits in `remap_mask` hold `remap`.
+/// A zero `remap_mask` means the pin does not depend on the remap setting.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AfMapping {
+    pub signal: AfSignal,
+    pub port: u8,
+    pub pin: u8,
+    pub remap_mask: u32,
+    pub remap: u32,
+}
+
+const fn af(signal: AfSignal, port: u8, pin: u8, remap_mask: u32, remap: u32) -> AfMapping {
+    AfMapping {
+        signal,
+        port,
+        pin,
+        remap_mask,
+        remap,
+    }
+}
+
+const PA: u8 = 0;
+const PB: u8 = 1;
+const PC: u8 = 2;
+const PD: u8 = 3;
+
+const NO_REMAP: u32 = 0;
+const USART1_REMAP: u32 = MAPR_USART1_REMAP_MASK;
+const USART2_REMAP: u32 = MAPR_USART2_REMAP_MASK;
+const USART3_REMAP: u32 = MAPR_USART3_REMAP_MASK;
+const SPI1_REMAP: u32 = MAPR_SPI1_REMAP_MASK;
+const I2C1_REMAP: u32 = MAPR_I2C1_REMAP_MASK;
+
+/// Pin locations of the communication peripherals (RM0008, section 9.3).
+/// Remapping SPI1 onto PA15/PB3/PB4 also requires JTAG to be disabled.
+pub const AF_MAP: &[AfMapping] = &[
+    // USART1
+    af(AfSignal::USART1_TX, PA, 9, USART1_REMAP, 0),
+    af(
+        AfSignal::USART1_TX,
+        PB,
+        6,
+        USART1_REMAP,
+        MAPR_USART1_REMAP_MASK,
+    ),
+    af(AfSignal::USART1_RX, PA, 10, USART1_REMAP, 0),
+    af(
+        AfSignal::USART1_RX,
+        PB,
+        7,
+        USART1_REMAP,
+        MAPR_USART1_REMAP_MASK,
+    ),
+    af(AfSignal::USART1_CK, PA, 8, NO_REMAP, 0),
+    af(AfSignal::USART1_CTS, PA, 11, NO_REMAP, 0),
+    af(AfSignal::USART1_RTS, PA, 12, NO_REMAP, 0),
+    // USART2
+    af(AfSignal::USART2_TX, PA, 2, USART2_REMAP, 0),
+    af(
+        AfSignal::USART2_TX,
+        PD,
+        5,
+        USART2_REMAP,
+        MAPR_USART2_REMAP_MASK,
+    ),
+    af(AfSignal::USART2_RX, PA, 3, USART2_REMAP, 0),
+    af(
+        AfSignal::USART2_RX,
+        PD,
+        6,
+        USART2_REMAP,
+        MAPR_USART2_REMAP_MASK,
+    ),
+    af(AfSignal::USART2_CK, PA, 4, USART2_REMAP, 0),
+    af(
+        AfSignal::USART2_CK,
+        PD,
+        7,
+        USART2_REMAP,
+        MAPR_USART2_REMAP_MASK,
+    ),
+    af(AfSignal::USART2_CTS, PA, 0, USART2_REMAP, 0),
+    af(
+        AfSignal::USART2_CTS,
+        PD,
+        3,
+        USART2_REMAP,
+        MAPR_USART2_REMAP_MASK,
+    ),
+    af(AfSignal::USART2_RTS, PA, 1, USART2_REMAP, 0),
+    af(
+        AfSignal::USART2_RTS,
+        PD,
+        4,
+        USART2_REMAP,
+        MAPR_USART2_REMAP_MASK,
+    ),
+    // USART3 (CTS/RTS on PB13/PB14 are shared by the default and partial remap)
+    af(
+        AfSignal::USART3_TX,
+        PB,
+        10,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_NONE,
+    ),
+    af(
+        AfSignal::USART3_TX,
+        PC,
+        10,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_PARTIAL,
+    ),
+    af(
+        AfSignal::USART3_TX,
+        PD,
+        8,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_FULL,
+    ),
+    af(
+        AfSignal::USART3_RX,
+        PB,
+        11,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_NONE,
+    ),
+    af(
+        AfSignal::USART3_RX,
+        PC,
+        11,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_PARTIAL,
+    ),
+    af(
+        AfSignal::USART3_RX,
+        PD,
+        9,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_FULL,
+    ),
+    af(
+        AfSignal::USART3_CK,
+        PB,
+        12,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_NONE,
+    ),
+    af(
+        AfSignal::USART3_CK,
+        PC,
+        12,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_PARTIAL,
+    ),
+    af(
+        AfSignal::USART3_CK,
+        PD,
+        10,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_FULL,
+    ),
+    af(AfSignal::USART3_CTS, PB, 13, NO_REMAP, 0),
+    af(
+        AfSignal::USART3_CTS,
+        PD,
+        11,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_FULL,
+    ),
+    af(AfSignal::USART3_RTS, PB, 14, NO_REMAP, 0),
+    af(
+        AfSignal::USART3_RTS,
+        PD,
+        12,
+        USART3_REMAP,
+        MAPR_USART3_REMAP_FULL,
+    ),
+    // UART4/UART5
+    af(AfSignal::UART4_TX, PC, 10, NO_REMAP, 0),
+    af(AfSignal::UART4_RX, PC, 11, NO_REMAP, 0),
+    af(AfSignal::UART5_TX, PC, 12, NO_REMAP, 0),
+    af(AfSignal::UART5_RX, PD, 2, NO_REMAP, 0),
+    // SPI1
+    af(AfSignal::SPI1_NSS, PA, 4, SPI1_REMAP, 0),
+    af(AfSignal::SPI1_NSS, PA, 15, SPI1_REMAP, MAPR_SPI1_REMAP_MASK),
+    af(AfSignal::SPI1_SCK, PA, 5, SPI1_REMAP, 0),
+    af(AfSignal::SPI1_SCK, PB, 3, SPI1_REMAP, MAPR_SPI1_REMAP_MASK),
+    af(AfSignal::SPI1_MISO, PA, 6, SPI1_REMAP, 0),
+    af(AfSignal::SPI1_MISO, PB, 4, SPI1_REMAP, MAPR_SPI1_REMAP_MASK),
+    af(AfSignal::SPI1_MOSI, PA, 7, SPI1_REMAP, 0),
+    af(AfSignal::SPI1_MOSI, PB, 5, SPI1_REMAP, MAPR_SPI1_REMAP_MASK),
+    // SPI2
+    af(AfSignal::SPI2_NSS, PB, 12, NO_REMAP, 0),
+    af(AfSignal::SPI2_SCK, PB, 13, NO_REMAP, 0),
+    af(AfSignal::SPI2_MISO, PB, 14, NO_REMAP, 0),
+    af(AfSignal::SPI2_MOSI, PB, 15, NO_REMAP, 0),
+    // SPI3 (high-density parts only)
+    af(AfSignal::SPI3_NSS, PA, 15, NO_REMAP, 0),
+    af(AfSignal::SPI3_SCK, PB, 3, NO_REMAP, 0),
+    af(AfSignal::SPI3_MISO, PB, 4, NO_REMAP, 0),
+    af(AfSignal::SPI3_MOSI, PB, 5, NO_REMAP, 0),
+    // I2C1
+    af(AfSignal::I2C1_SCL, PB, 6, I2C1_REMAP, 0),
+    af(AfSignal::I2C1_SCL, PB, 8, I2C1_REMAP, MAPR_I2C1_REMAP_MASK),
+    af(AfSignal::I2C1_SDA, PB, 7, I2C1_REMAP, 0),
+    af(AfSignal::I2C1_SDA, PB, 9, I2C1_REMAP, MAPR_I2C1_REMAP_MASK),
+    af(AfSignal::I2C1_SMBA, PB, 5, NO_REMAP, 0),
+    // I2C2
+    af(AfSignal::I2C2_SCL, PB, 10, NO_REMAP, 0),
+    af(AfSignal::I2C2_SDA, PB, 11, NO_REMAP, 0),
+    af(AfSignal::I2C2_SMBA, PB, 12, NO_REMAP, 0),
+];
+
+/// Look up the remap entry that routes `signal` to `port`/`pin`
+pub fn find_remap(signal: AfSignal, port: u8, pin: u8) -> Option<&'static AfMapping> {
+    AF_MAP
+        .iter()
+        .find(|m| m.signal == signal && m.port == port && m.pin == pin)
+}
@@ -1 +1,151 @@
-// STM32F103 MCU-specific definitions
+//! # STM32F103 MCU Implementation
+//!
+//! Base addresses, interrupt numbers and peripheral instances of the
+//! STM32F103x8/B (medium density) and STM32F103xC/D/E (high density).
+//! USART, SPI and I2C use the same v1 IP as the STM32F4 family; GPIO is the
+//! older CRL/CRH design with pin remapping through AFIO.
+
+// Base addresses
+pub const TIM2_BASEADDR: u32 = 0x40000000;
+pub const TIM3_BASEADDR: u32 = 0x40000400;
+pub const TIM4_BASEADDR: u32 = 0x40000800;
+pub const TIM5_BASEADDR: u32 = 0x40000C00;
+pub const TIM6_BASEADDR: u32 = 0x40001000;
+pub const TIM7_BASEADDR: u32 = 0x40001400;
+pub const RTC_BASEADDR: u32 = 0x40002800;
+pub const WWDG_BASEADDR: u32 = 0x40002C00;
+pub const IWDG_BASEADDR: u32 = 0x40003000;
+pub const SPI2_BASEADDR: u32 = 0x40003800;
+pub const SPI3_BASEADDR: u32 = 0x40003C00;
+pub const USART2_BASEADDR: u32 = 0x40004400;
+pub const USART3_BASEADDR: u32 = 0x40004800;
+pub const UART4_BASEADDR: u32 = 0x40004C00;
+pub const UART5_BASEADDR: u32 = 0x40005000;
+pub const I2C1_BASEADDR: u32 = 0x40005400;
+pub const I2C2_BASEADDR: u32 = 0x40005800;
+pub const USB_BASEADDR: u32 = 0x40005C00;
+pub const USB_PMA_BASEADDR: u32 = 0x40006000;
+pub const CAN1_BASEADDR: u32 = 0x40006400;
+pub const BKP_BASEADDR: u32 = 0x40006C00;
+pub const PWR_BASEADDR: u32 = 0x40007000;
+pub const DAC_BASEADDR: u32 = 0x40007400;
+pub const AFIO_BASEADDR: u32 = 0x40010000;
+pub const EXTI_BASEADDR: u32 = 0x40010400;
+pub const GPIOA_BASEADDR: u32 = 0x40010800;
+pub const GPIOB_BASEADDR: u32 = 0x40010C00;
+pub const GPIOC_BASEADDR: u32 = 0x40011000;
+pub const GPIOD_BASEADDR: u32 = 0x40011400;
+pub const GPIOE_BASEADDR: u32 = 0x40011800;
+pub const GPIOF_BASEADDR: u32 = 0x40011C00;
+pub const GPIOG_BASEADDR: u32 = 0x40012000;
+pub const ADC1_BASEADDR: u32 = 0x40012400;
+pub const ADC2_BASEADDR: u32 = 0x40012800;
+pub const TIM1_BASEADDR: u32 = 0x40012C00;
+pub const SPI1_BASEADDR: u32 = 0x40013000;
+pub const TIM8_BASEADDR: u32 = 0x40013400;
+pub const USART1_BASEADDR: u32 = 0x40013800;
+pub const ADC3_BASEADDR: u32 = 0x40013C00;
+pub const SDIO_BASEADDR: u32 = 0x40018000;
+pub const DMA1_BASEADDR: u32 = 0x40020000;
+pub const DMA2_BASEADDR: u32 = 0x40020400;
+pub const RCC_BASEADDR: u32 = 0x40021000;
+pub const FLASH_R_BASEADDR: u32 = 0x40022000;
+pub const CRC_BASEADDR: u32 = 0x40023000;
+pub const FSMC_BASEADDR: u32 = 0xA0000000;
+pub const DBGMCU_BASEADDR: u32 = 0xE0042000;
+
+/*
+ * IRQ(Interrupt Request) Numbers of STM32F103xE MCU
+ */
+#[allow(non_camel_case_types)]
+#[repr(u8)]
+pub enum IRQn {
+    WWDG = 0,
+    PVD = 1,
+    TAMPER = 2,
+    RTC = 3,
+    FLASH = 4,
+    RCC = 5,
+    EXTI0 = 6,
+    EXTI1 = 7,
+    EXTI2 = 8,
+    EXTI3 = 9,
+    EXTI4 = 10,
+    DMA1_Channel1 = 11,
+    DMA1_Channel2 = 12,
+    DMA1_Channel3 = 13,
+    DMA1_Channel4 = 14,
+    DMA1_Channel5 = 15,
+    DMA1_Channel6 = 16,
+    DMA1_Channel7 = 17,
+    ADC1_2 = 18,
+    USB_HP_CAN1_TX = 19,
+    USB_LP_CAN1_RX0 = 20,
+    CAN1_RX1 = 21,
+    CAN1_SCE = 22,
+    EXTI9_5 = 23,
+    TIM1_BRK = 24,
+    TIM1_UP = 25,
+    TIM1_TRG_COM = 26,
+    TIM1_CC = 27,
+    TIM2 = 28,
+    TIM3 = 29,
+    TIM4 = 30,
+    I2C1_EV = 31,
+    I2C1_ER = 32,
+    I2C2_EV = 33,
+    I2C2_ER = 34,
+    SPI1 = 35,
+    SPI2 = 36,
+    USART1 = 37,
+    USART2 = 38,
+    USART3 = 39,
+    EXTI15_10 = 40,
+    RTC_Alarm = 41,
+    USBWakeUp = 42,
+    TIM8_BRK = 43,
+    TIM8_UP = 44,
+    TIM8_TRG_COM = 45,
+    TIM8_CC = 46,
+    ADC3 = 47,
+    FSMC = 48,
+    SDIO = 49,
+    TIM5 = 50,
+    SPI3 = 51,
+    UART4 = 52,
+    UART5 = 53,
+    TIM6 = 54,
+    TIM7 = 55,
+    DMA2_Channel1 = 56,
+    DMA2_Channel2 = 57,
+    DMA2_Channel3 = 58,
+    DMA2_Channel4_5 = 59,
+}
+
+/// HSI RC oscillator frequency, the system clock after reset
+pub const HSI_HZ: u32 = 8_000_000;
+
+/// Nominal LSI RC oscillator frequency; parts range from 30 to 60 kHz
+pub const LSI_HZ: u32 = 40_000;
+
+pub trait PeripheralAccess {
+    const BASE_ADDRESS: u32;
+    type RegisterBlock;
+
+    fn ptr() -> *const Self::RegisterBlock {
+        Self::BASE_ADDRESS as *const Self::RegisterBlock
+    }
+
+    fn ptr_mut() -> *mut Self::RegisterBlock {
+        Self::BASE_ADDRESS as *mut Self::RegisterBlock
+    }
+}
+
+pub mod exti;
+pub mod gpio;
+pub mod i2c;
+pub mod pwr;
+pub mod rcc;
+pub mod rtc;
+pub mod spi;
+pub mod usart;
@@ -0,0 +1,567 @@
+// RCC (Reset and Clock Control) peripheral definitions
+// Generated from STM32F103 SVD file
+
+use super::{PeripheralAccess, RCC_BASEADDR};
+
+// RCC Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr: u32,       // RW: Clock control register
+    pub cfgr: u32,     // RW: Clock configuration register
+    pub cir: u32,      // RW: Clock interrupt register
+    pub apb2rstr: u32, // RW: APB2 peripheral reset register
+    pub apb1rstr: u32, // RW: APB1 peripheral reset register
+    pub ahbenr: u32,   // RW: AHB peripheral clock enable register
+    pub apb2enr: u32,  // RW: APB2 peripheral clock enable register
+    pub apb1enr: u32,  // RW: APB1 peripheral clock enable register
+    pub bdcr: u32,     // RW: Backup domain control register
+    pub csr: u32,      // RW: Control/status register
+}
+
+// RCC Register Field Definitions
+
+// CR register fields
+pub const CR_PLLRDY_POS: u32 = 25;
+pub const CR_PLLRDY_WIDTH: u32 = 1;
+pub const CR_PLLRDY_MASK: u32 = 0x1 << 25;
+
+pub const CR_PLLON_POS: u32 = 24;
+pub const CR_PLLON_WIDTH: u32 = 1;
+pub const CR_PLLON_MASK: u32 = 0x1 << 24;
+
+pub const CR_CSSON_POS: u32 = 19;
+pub const CR_CSSON_WIDTH: u32 = 1;
+pub const CR_CSSON_MASK: u32 = 0x1 << 19;
+
+pub const CR_HSEBYP_POS: u32 = 18;
+pub const CR_HSEBYP_WIDTH: u32 = 1;
+pub const CR_HSEBYP_MASK: u32 = 0x1 << 18;
+
+pub const CR_HSERDY_POS: u32 = 17;
+pub const CR_HSERDY_WIDTH: u32 = 1;
+pub const CR_HSERDY_MASK: u32 = 0x1 << 17;
+
+pub const CR_HSEON_POS: u32 = 16;
+pub const CR_HSEON_WIDTH: u32 = 1;
+pub const CR_HSEON_MASK: u32 = 0x1 << 16;
+
+pub const CR_HSICAL_POS: u32 = 8;
+pub const CR_HSICAL_WIDTH: u32 = 8;
+pub const CR_HSICAL_MASK: u32 = 0xFF << 8;
+
+pub const CR_HSITRIM_POS: u32 = 3;
+pub const CR_HSITRIM_WIDTH: u32 = 5;
+pub const CR_HSITRIM_MASK: u32 = 0x1F << 3;
+
+pub const CR_HSIRDY_POS: u32 = 1;
+pub const CR_HSIRDY_WIDTH: u32 = 1;
+pub const CR_HSIRDY_MASK: u32 = 0x1 << 1;
+
+pub const CR_HSION_POS: u32 = 0;
+pub const CR_HSION_WIDTH: u32 = 1;
+pub const CR_HSION_MASK: u32 = 0x1 << 0;
+
+// CFGR register fields
+pub const CFGR_MCO_POS: u32 = 24;
+pub const CFGR_MCO_WIDTH: u32 = 3;
+pub const CFGR_MCO_MASK: u32 = 0x7 << 24;
+
+pub const CFGR_USBPRE_POS: u32 = 22;
+pub const CFGR_USBPRE_WIDTH: u32 = 1;
+pub const CFGR_USBPRE_MASK: u32 = 0x1 << 22;
+
+pub const CFGR_PLLMUL_POS: u32 = 18;
+pub const CFGR_PLLMUL_WIDTH: u32 = 4;
+pub const CFGR_PLLMUL_MASK: u32 = 0xF << 18;
+
+pub const CFGR_PLLXTPRE_POS: u32 = 17;
+pub const CFGR_PLLXTPRE_WIDTH: u32 = 1;
+pub const CFGR_PLLXTPRE_MASK: u32 = 0x1 << 17;
+
+pub const CFGR_PLLSRC_POS: u32 = 16;
+pub const CFGR_PLLSRC_WIDTH: u32 = 1;
+pub const CFGR_PLLSRC_MASK: u32 = 0x1 << 16;
+// PLLSRC enumerated values
+pub const CFGR_PLLSRC_HSI_DIV2: u32 = 0 << 16;
+pub const CFGR_PLLSRC_HSE_DIV_PREDIV: u32 = 1 << 16;
+
+pub const CFGR_ADCPRE_POS: u32 = 14;
+pub const CFGR_ADCPRE_WIDTH: u32 = 2;
+pub const CFGR_ADCPRE_MASK: u32 = 0x3 << 14;
+
+pub const CFGR_PPRE2_POS: u32 = 11;
+pub const CFGR_PPRE2_WIDTH: u32 = 3;
+pub const CFGR_PPRE2_MASK: u32 = 0x7 << 11;
+
+pub const CFGR_PPRE1_POS: u32 = 8;
+pub const CFGR_PPRE1_WIDTH: u32 = 3;
+pub const CFGR_PPRE1_MASK: u32 = 0x7 << 8;
+
+pub const CFGR_HPRE_POS: u32 = 4;
+pub const CFGR_HPRE_WIDTH: u32 = 4;
+pub const CFGR_HPRE_MASK: u32 = 0xF << 4;
+
+pub const CFGR_SWS_POS: u32 = 2;
+pub const CFGR_SWS_WIDTH: u32 = 2;
+pub const CFGR_SWS_MASK: u32 = 0x3 << 2;
+// SWS enumerated values
+pub const CFGR_SWS_HSI: u32 = 0 << 2;
+pub const CFGR_SWS_HSE: u32 = 1 << 2;
+pub const CFGR_SWS_PLL: u32 = 2 << 2;
+
+pub const CFGR_SW_POS: u32 = 0;
+pub const CFGR_SW_WIDTH: u32 = 2;
+pub const CFGR_SW_MASK: u32 = 0x3 << 0;
+// SW enumerated values
+pub const CFGR_SW_HSI: u32 = 0 << 0;
+pub const CFGR_SW_HSE: u32 = 1 << 0;
+pub const CFGR_SW_PLL: u32 = 2 << 0;
+
+// CIR register fields
+pub const CIR_CSSC_POS: u32 = 23;
+pub const CIR_CSSC_WIDTH: u32 = 1;
+pub const CIR_CSSC_MASK: u32 = 0x1 << 23;
+
+pub const CIR_PLLRDYC_POS: u32 = 20;
+pub const CIR_PLLRDYC_WIDTH: u32 = 1;
+pub const CIR_PLLRDYC_MASK: u32 = 0x1 << 20;
+
+pub const CIR_HSERDYC_POS: u32 = 19;
+pub const CIR_HSERDYC_WIDTH: u32 = 1;
+pub const CIR_HSERDYC_MASK: u32 = 0x1 << 19;
+
+pub const CIR_HSIRDYC_POS: u32 = 18;
+pub const CIR_HSIRDYC_WIDTH: u32 = 1;
+pub const CIR_HSIRDYC_MASK: u32 = 0x1 << 18;
+
+pub const CIR_LSERDYC_POS: u32 = 17;
+pub const CIR_LSERDYC_WIDTH: u32 = 1;
+pub const CIR_LSERDYC_MASK: u32 = 0x1 << 17;
+
+pub const CIR_LSIRDYC_POS: u32 = 16;
+pub const CIR_LSIRDYC_WIDTH: u32 = 1;
+pub const CIR_LSIRDYC_MASK: u32 = 0x1 << 16;
+
+pub const CIR_PLLRDYIE_POS: u32 = 12;
+pub const CIR_PLLRDYIE_WIDTH: u32 = 1;
+pub const CIR_PLLRDYIE_MASK: u32 = 0x1 << 12;
+
+pub const CIR_HSERDYIE_POS: u32 = 11;
+pub const CIR_HSERDYIE_WIDTH: u32 = 1;
+pub const CIR_HSERDYIE_MASK: u32 = 0x1 << 11;
+
+pub const CIR_HSIRDYIE_POS: u32 = 10;
+pub const CIR_HSIRDYIE_WIDTH: u32 = 1;
+pub const CIR_HSIRDYIE_MASK: u32 = 0x1 << 10;
+
+pub const CIR_LSERDYIE_POS: u32 = 9;
+pub const CIR_LSERDYIE_WIDTH: u32 = 1;
+pub const CIR_LSERDYIE_MASK: u32 = 0x1 << 9;
+
+pub const CIR_LSIRDYIE_POS: u32 = 8;
+pub const CIR_LSIRDYIE_WIDTH: u32 = 1;
+pub const CIR_LSIRDYIE_MASK: u32 = 0x1 << 8;
+
+pub const CIR_CSSF_POS: u32 = 7;
+pub const CIR_CSSF_WIDTH: u32 = 1;
+pub const CIR_CSSF_MASK: u32 = 0x1 << 7;
+
+pub const CIR_PLLRDYF_POS: u32 = 4;
+pub const CIR_PLLRDYF_WIDTH: u32 = 1;
+pub const CIR_PLLRDYF_MASK: u32 = 0x1 << 4;
+
+pub const CIR_HSERDYF_POS: u32 = 3;
+pub const CIR_HSERDYF_WIDTH: u32 = 1;
+pub const CIR_HSERDYF_MASK: u32 = 0x1 << 3;
+
+pub const CIR_HSIRDYF_POS: u32 = 2;
+pub const CIR_HSIRDYF_WIDTH: u32 = 1;
+pub const CIR_HSIRDYF_MASK: u32 = 0x1 << 2;
+
+pub const CIR_LSERDYF_POS: u32 = 1;
+pub const CIR_LSERDYF_WIDTH: u32 = 1;
+pub const CIR_LSERDYF_MASK: u32 = 0x1 << 1;
+
+pub const CIR_LSIRDYF_POS: u32 = 0;
+pub const CIR_LSIRDYF_WIDTH: u32 = 1;
+pub const CIR_LSIRDYF_MASK: u32 = 0x1 << 0;
+
+// APB2RSTR register fields
+pub const APB2RSTR_ADC3RST_POS: u32 = 15;
+pub const APB2RSTR_ADC3RST_WIDTH: u32 = 1;
+pub const APB2RSTR_ADC3RST_MASK: u32 = 0x1 << 15;
+
+pub const APB2RSTR_USART1RST_POS: u32 = 14;
+pub const APB2RSTR_USART1RST_WIDTH: u32 = 1;
+pub const APB2RSTR_USART1RST_MASK: u32 = 0x1 << 14;
+
+pub const APB2RSTR_TIM8RST_POS: u32 = 13;
+pub const APB2RSTR_TIM8RST_WIDTH: u32 = 1;
+pub const APB2RSTR_TIM8RST_MASK: u32 = 0x1 << 13;
+
+pub const APB2RSTR_SPI1RST_POS: u32 = 12;
+pub const APB2RSTR_SPI1RST_WIDTH: u32 = 1;
+pub const APB2RSTR_SPI1RST_MASK: u32 = 0x1 << 12;
+
+pub const APB2RSTR_TIM1RST_POS: u32 = 11;
+pub const APB2RSTR_TIM1RST_WIDTH: u32 = 1;
+pub const APB2RSTR_TIM1RST_MASK: u32 = 0x1 << 11;
+
+pub const APB2RSTR_ADC2RST_POS: u32 = 10;
+pub const APB2RSTR_ADC2RST_WIDTH: u32 = 1;
+pub const APB2RSTR_ADC2RST_MASK: u32 = 0x1 << 10;
+
+pub const APB2RSTR_ADC1RST_POS: u32 = 9;
+pub const APB2RSTR_ADC1RST_WIDTH: u32 = 1;
+pub const APB2RSTR_ADC1RST_MASK: u32 = 0x1 << 9;
+
+pub const APB2RSTR_IOPGRST_POS: u32 = 8;
+pub const APB2RSTR_IOPGRST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPGRST_MASK: u32 = 0x1 << 8;
+
+pub const APB2RSTR_IOPFRST_POS: u32 = 7;
+pub const APB2RSTR_IOPFRST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPFRST_MASK: u32 = 0x1 << 7;
+
+pub const APB2RSTR_IOPERST_POS: u32 = 6;
+pub const APB2RSTR_IOPERST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPERST_MASK: u32 = 0x1 << 6;
+
+pub const APB2RSTR_IOPDRST_POS: u32 = 5;
+pub const APB2RSTR_IOPDRST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPDRST_MASK: u32 = 0x1 << 5;
+
+pub const APB2RSTR_IOPCRST_POS: u32 = 4;
+pub const APB2RSTR_IOPCRST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPCRST_MASK: u32 = 0x1 << 4;
+
+pub const APB2RSTR_IOPBRST_POS: u32 = 3;
+pub const APB2RSTR_IOPBRST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPBRST_MASK: u32 = 0x1 << 3;
+
+pub const APB2RSTR_IOPARST_POS: u32 = 2;
+pub const APB2RSTR_IOPARST_WIDTH: u32 = 1;
+pub const APB2RSTR_IOPARST_MASK: u32 = 0x1 << 2;
+
+pub const APB2RSTR_AFIORST_POS: u32 = 0;
+pub const APB2RSTR_AFIORST_WIDTH: u32 = 1;
+pub const APB2RSTR_AFIORST_MASK: u32 = 0x1 << 0;
+
+// APB1RSTR register fields
+pub const APB1RSTR_DACRST_POS: u32 = 29;
+pub const APB1RSTR_DACRST_WIDTH: u32 = 1;
+pub const APB1RSTR_DACRST_MASK: u32 = 0x1 << 29;
+
+pub const APB1RSTR_PWRRST_POS: u32 = 28;
+pub const APB1RSTR_PWRRST_WIDTH: u32 = 1;
+pub const APB1RSTR_PWRRST_MASK: u32 = 0x1 << 28;
+
+pub const APB1RSTR_BKPRST_POS: u32 = 27;
+pub const APB1RSTR_BKPRST_WIDTH: u32 = 1;
+pub const APB1RSTR_BKPRST_MASK: u32 = 0x1 << 27;
+
+pub const APB1RSTR_CANRST_POS: u32 = 25;
+pub const APB1RSTR_CANRST_WIDTH: u32 = 1;
+pub const APB1RSTR_CANRST_MASK: u32 = 0x1 << 25;
+
+pub const APB1RSTR_USBRST_POS: u32 = 23;
+pub const APB1RSTR_USBRST_WIDTH: u32 = 1;
+pub const APB1RSTR_USBRST_MASK: u32 = 0x1 << 23;
+
+pub const APB1RSTR_I2C2RST_POS: u32 = 22;
+pub const APB1RSTR_I2C2RST_WIDTH: u32 = 1;
+pub const APB1RSTR_I2C2RST_MASK: u32 = 0x1 << 22;
+
+pub const APB1RSTR_I2C1RST_POS: u32 = 21;
+pub const APB1RSTR_I2C1RST_WIDTH: u32 = 1;
+pub const APB1RSTR_I2C1RST_MASK: u32 = 0x1 << 21;
+
+pub const APB1RSTR_UART5RST_POS: u32 = 20;
+pub const APB1RSTR_UART5RST_WIDTH: u32 = 1;
+pub const APB1RSTR_UART5RST_MASK: u32 = 0x1 << 20;
+
+pub const APB1RSTR_UART4RST_POS: u32 = 19;
+pub const APB1RSTR_UART4RST_WIDTH: u32 = 1;
+pub const APB1RSTR_UART4RST_MASK: u32 = 0x1 << 19;
+
+pub const APB1RSTR_USART3RST_POS: u32 = 18;
+pub const APB1RSTR_USART3RST_WIDTH: u32 = 1;
+pub const APB1RSTR_USART3RST_MASK: u32 = 0x1 << 18;
+
+pub const APB1RSTR_USART2RST_POS: u32 = 17;
+pub const APB1RSTR_USART2RST_WIDTH: u32 = 1;
+pub const APB1RSTR_USART2RST_MASK: u32 = 0x1 << 17;
+
+pub const APB1RSTR_SPI3RST_POS: u32 = 15;
+pub const APB1RSTR_SPI3RST_WIDTH: u32 = 1;
+pub const APB1RSTR_SPI3RST_MASK: u32 = 0x1 << 15;
+
+pub const APB1RSTR_SPI2RST_POS: u32 = 14;
+pub const APB1RSTR_SPI2RST_WIDTH: u32 = 1;
+pub const APB1RSTR_SPI2RST_MASK: u32 = 0x1 << 14;
+
+pub const APB1RSTR_WWDGRST_POS: u32 = 11;
+pub const APB1RSTR_WWDGRST_WIDTH: u32 = 1;
+pub const APB1RSTR_WWDGRST_MASK: u32 = 0x1 << 11;
+
+pub const APB1RSTR_TIM7RST_POS: u32 = 5;
+pub const APB1RSTR_TIM7RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM7RST_MASK: u32 = 0x1 << 5;
+
+pub const APB1RSTR_TIM6RST_POS: u32 = 4;
+pub const APB1RSTR_TIM6RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM6RST_MASK: u32 = 0x1 << 4;
+
+pub const APB1RSTR_TIM5RST_POS: u32 = 3;
+pub const APB1RSTR_TIM5RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM5RST_MASK: u32 = 0x1 << 3;
+
+pub const APB1RSTR_TIM4RST_POS: u32 = 2;
+pub const APB1RSTR_TIM4RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM4RST_MASK: u32 = 0x1 << 2;
+
+pub const APB1RSTR_TIM3RST_POS: u32 = 1;
+pub const APB1RSTR_TIM3RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM3RST_MASK: u32 = 0x1 << 1;
+
+pub const APB1RSTR_TIM2RST_POS: u32 = 0;
+pub const APB1RSTR_TIM2RST_WIDTH: u32 = 1;
+pub const APB1RSTR_TIM2RST_MASK: u32 = 0x1 << 0;
+
+// AHBENR register fields
+pub const AHBENR_SDIOEN_POS: u32 = 10;
+pub const AHBENR_SDIOEN_WIDTH: u32 = 1;
+pub const AHBENR_SDIOEN_MASK: u32 = 0x1 << 10;
+
+pub const AHBENR_FSMCEN_POS: u32 = 8;
+pub const AHBENR_FSMCEN_WIDTH: u32 = 1;
+pub const AHBENR_FSMCEN_MASK: u32 = 0x1 << 8;
+
+pub const AHBENR_CRCEN_POS: u32 = 6;
+pub const AHBENR_CRCEN_WIDTH: u32 = 1;
+pub const AHBENR_CRCEN_MASK: u32 = 0x1 << 6;
+
+pub const AHBENR_FLITFEN_POS: u32 = 4;
+pub const AHBENR_FLITFEN_WIDTH: u32 = 1;
+pub const AHBENR_FLITFEN_MASK: u32 = 0x1 << 4;
+
+pub const AHBENR_SRAMEN_POS: u32 = 2;
+pub const AHBENR_SRAMEN_WIDTH: u32 = 1;
+pub const AHBENR_SRAMEN_MASK: u32 = 0x1 << 2;
+
+pub const AHBENR_DMA2EN_POS: u32 = 1;
+pub const AHBENR_DMA2EN_WIDTH: u32 = 1;
+pub const AHBENR_DMA2EN_MASK: u32 = 0x1 << 1;
+
+pub const AHBENR_DMA1EN_POS: u32 = 0;
+pub const AHBENR_DMA1EN_WIDTH: u32 = 1;
+pub const AHBENR_DMA1EN_MASK: u32 = 0x1 << 0;
+
+// APB2ENR register fields
+pub const APB2ENR_ADC3EN_POS: u32 = 15;
+pub const APB2ENR_ADC3EN_WIDTH: u32 = 1;
+pub const APB2ENR_ADC3EN_MASK: u32 = 0x1 << 15;
+
+pub const APB2ENR_USART1EN_POS: u32 = 14;
+pub const APB2ENR_USART1EN_WIDTH: u32 = 1;
+pub const APB2ENR_USART1EN_MASK: u32 = 0x1 << 14;
+
+pub const APB2ENR_TIM8EN_POS: u32 = 13;
+pub const APB2ENR_TIM8EN_WIDTH: u32 = 1;
+pub const APB2ENR_TIM8EN_MASK: u32 = 0x1 << 13;
+
+pub const APB2ENR_SPI1EN_POS: u32 = 12;
+pub const APB2ENR_SPI1EN_WIDTH: u32 = 1;
+pub const APB2ENR_SPI1EN_MASK: u32 = 0x1 << 12;
+
+pub const APB2ENR_TIM1EN_POS: u32 = 11;
+pub const APB2ENR_TIM1EN_WIDTH: u32 = 1;
+pub const APB2ENR_TIM1EN_MASK: u32 = 0x1 << 11;
+
+pub const APB2ENR_ADC2EN_POS: u32 = 10;
+pub const APB2ENR_ADC2EN_WIDTH: u32 = 1;
+pub const APB2ENR_ADC2EN_MASK: u32 = 0x1 << 10;
+
+pub const APB2ENR_ADC1EN_POS: u32 = 9;
+pub const APB2ENR_ADC1EN_WIDTH: u32 = 1;
+pub const APB2ENR_ADC1EN_MASK: u32 = 0x1 << 9;
+
+pub const APB2ENR_IOPGEN_POS: u32 = 8;
+pub const APB2ENR_IOPGEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPGEN_MASK: u32 = 0x1 << 8;
+
+pub const APB2ENR_IOPFEN_POS: u32 = 7;
+pub const APB2ENR_IOPFEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPFEN_MASK: u32 = 0x1 << 7;
+
+pub const APB2ENR_IOPEEN_POS: u32 = 6;
+pub const APB2ENR_IOPEEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPEEN_MASK: u32 = 0x1 << 6;
+
+pub const APB2ENR_IOPDEN_POS: u32 = 5;
+pub const APB2ENR_IOPDEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPDEN_MASK: u32 = 0x1 << 5;
+
+pub const APB2ENR_IOPCEN_POS: u32 = 4;
+pub const APB2ENR_IOPCEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPCEN_MASK: u32 = 0x1 << 4;
+
+pub const APB2ENR_IOPBEN_POS: u32 = 3;
+pub const APB2ENR_IOPBEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPBEN_MASK: u32 = 0x1 << 3;
+
+pub const APB2ENR_IOPAEN_POS: u32 = 2;
+pub const APB2ENR_IOPAEN_WIDTH: u32 = 1;
+pub const APB2ENR_IOPAEN_MASK: u32 = 0x1 << 2;
+
+pub const APB2ENR_AFIOEN_POS: u32 = 0;
+pub const APB2ENR_AFIOEN_WIDTH: u32 = 1;
+pub const APB2ENR_AFIOEN_MASK: u32 = 0x1 << 0;
+
+// APB1ENR register fields
+pub const APB1ENR_DACEN_POS: u32 = 29;
+pub const APB1ENR_DACEN_WIDTH: u32 = 1;
+pub const APB1ENR_DACEN_MASK: u32 = 0x1 << 29;
+
+pub const APB1ENR_PWREN_POS: u32 = 28;
+pub const APB1ENR_PWREN_WIDTH: u32 = 1;
+pub const APB1ENR_PWREN_MASK: u32 = 0x1 << 28;
+
+pub const APB1ENR_BKPEN_POS: u32 = 27;
+pub const APB1ENR_BKPEN_WIDTH: u32 = 1;
+pub const APB1ENR_BKPEN_MASK: u32 = 0x1 << 27;
+
+pub const APB1ENR_CANEN_POS: u32 = 25;
+pub const APB1ENR_CANEN_WIDTH: u32 = 1;
+pub const APB1ENR_CANEN_MASK: u32 = 0x1 << 25;
+
+pub const APB1ENR_USBEN_POS: u32 = 23;
+pub const APB1ENR_USBEN_WIDTH: u32 = 1;
+pub const APB1ENR_USBEN_MASK: u32 = 0x1 << 23;
+
+pub const APB1ENR_I2C2EN_POS: u32 = 22;
+pub const APB1ENR_I2C2EN_WIDTH: u32 = 1;
+pub const APB1ENR_I2C2EN_MASK: u32 = 0x1 << 22;
+
+pub const APB1ENR_I2C1EN_POS: u32 = 21;
+pub const APB1ENR_I2C1EN_WIDTH: u32 = 1;
+pub const APB1ENR_I2C1EN_MASK: u32 = 0x1 << 21;
+
+pub const APB1ENR_UART5EN_POS: u32 = 20;
+pub const APB1ENR_UART5EN_WIDTH: u32 = 1;
+pub const APB1ENR_UART5EN_MASK: u32 = 0x1 << 20;
+
+pub const APB1ENR_UART4EN_POS: u32 = 19;
+pub const APB1ENR_UART4EN_WIDTH: u32 = 1;
+pub const APB1ENR_UART4EN_MASK: u32 = 0x1 << 19;
+
+pub const APB1ENR_USART3EN_POS: u32 = 18;
+pub const APB1ENR_USART3EN_WIDTH: u32 = 1;
+pub const APB1ENR_USART3EN_MASK: u32 = 0x1 << 18;
+
+pub const APB1ENR_USART2EN_POS: u32 = 17;
+pub const APB1ENR_USART2EN_WIDTH: u32 = 1;
+pub const APB1ENR_USART2EN_MASK: u32 = 0x1 << 17;
+
+pub const APB1ENR_SPI3EN_POS: u32 = 15;
+pub const APB1ENR_SPI3EN_WIDTH: u32 = 1;
+pub const APB1ENR_SPI3EN_MASK: u32 = 0x1 << 15;
+
+pub const APB1ENR_SPI2EN_POS: u32 = 14;
+pub const APB1ENR_SPI2EN_WIDTH: u32 = 1;
+pub const APB1ENR_SPI2EN_MASK: u32 = 0x1 << 14;
+
+pub const APB1ENR_WWDGEN_POS: u32 = 11;
+pub const APB1ENR_WWDGEN_WIDTH: u32 = 1;
+pub const APB1ENR_WWDGEN_MASK: u32 = 0x1 << 11;
+
+pub const APB1ENR_TIM7EN_POS: u32 = 5;
+pub const APB1ENR_TIM7EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM7EN_MASK: u32 = 0x1 << 5;
+
+pub const APB1ENR_TIM6EN_POS: u32 = 4;
+pub const APB1ENR_TIM6EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM6EN_MASK: u32 = 0x1 << 4;
+
+pub const APB1ENR_TIM5EN_POS: u32 = 3;
+pub const APB1ENR_TIM5EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM5EN_MASK: u32 = 0x1 << 3;
+
+pub const APB1ENR_TIM4EN_POS: u32 = 2;
+pub const APB1ENR_TIM4EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM4EN_MASK: u32 = 0x1 << 2;
+
+pub const APB1ENR_TIM3EN_POS: u32 = 1;
+pub const APB1ENR_TIM3EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM3EN_MASK: u32 = 0x1 << 1;
+
+pub const APB1ENR_TIM2EN_POS: u32 = 0;
+pub const APB1ENR_TIM2EN_WIDTH: u32 = 1;
+pub const APB1ENR_TIM2EN_MASK: u32 = 0x1 << 0;
+
+// BDCR register fields
+pub const BDCR_BDRST_POS: u32 = 16;
+pub const BDCR_BDRST_WIDTH: u32 = 1;
+pub const BDCR_BDRST_MASK: u32 = 0x1 << 16;
+
+pub const BDCR_RTCEN_POS: u32 = 15;
+pub const BDCR_RTCEN_WIDTH: u32 = 1;
+pub const BDCR_RTCEN_MASK: u32 = 0x1 << 15;
+
+pub const BDCR_RTCSEL_POS: u32 = 8;
+pub const BDCR_RTCSEL_WIDTH: u32 = 2;
+pub const BDCR_RTCSEL_MASK: u32 = 0x3 << 8;
+
+pub const BDCR_LSEBYP_POS: u32 = 2;
+pub const BDCR_LSEBYP_WIDTH: u32 = 1;
+pub const BDCR_LSEBYP_MASK: u32 = 0x1 << 2;
+
+pub const BDCR_LSERDY_POS: u32 = 1;
+pub const BDCR_LSERDY_WIDTH: u32 = 1;
+pub const BDCR_LSERDY_MASK: u32 = 0x1 << 1;
+
+pub const BDCR_LSEON_POS: u32 = 0;
+pub const BDCR_LSEON_WIDTH: u32 = 1;
+pub const BDCR_LSEON_MASK: u32 = 0x1 << 0;
+
+// CSR register fields
+pub const CSR_LPWRRSTF_POS: u32 = 31;
+pub const CSR_LPWRRSTF_WIDTH: u32 = 1;
+pub const CSR_LPWRRSTF_MASK: u32 = 0x1 << 31;
+
+pub const CSR_WWDGRSTF_POS: u32 = 30;
+pub const CSR_WWDGRSTF_WIDTH: u32 = 1;
+pub const CSR_WWDGRSTF_MASK: u32 = 0x1 << 30;
+
+pub const CSR_IWDGRSTF_POS: u32 = 29;
+pub const CSR_IWDGRSTF_WIDTH: u32 = 1;
+pub const CSR_IWDGRSTF_MASK: u32 = 0x1 << 29;
+
+pub const CSR_SFTRSTF_POS: u32 = 28;
+pub const CSR_SFTRSTF_WIDTH: u32 = 1;
+pub const CSR_SFTRSTF_MASK: u32 = 0x1 << 28;
+
+pub const CSR_PORRSTF_POS: u32 = 27;
+pub const CSR_PORRSTF_WIDTH: u32 = 1;
+pub const CSR_PORRSTF_MASK: u32 = 0x1 << 27;
+
+pub const CSR_PINRSTF_POS: u32 = 26;
+pub const CSR_PINRSTF_WIDTH: u32 = 1;
+pub const CSR_PINRSTF_MASK: u32 = 0x1 << 26;
+
+pub const CSR_RMVF_POS: u32 = 24;
+pub const CSR_RMVF_WIDTH: u32 = 1;
+pub const CSR_RMVF_MASK: u32 = 0x1 << 24;
+
+pub const CSR_LSIRDY_POS: u32 = 1;
+pub const CSR_LSIRDY_WIDTH: u32 = 1;
+pub const CSR_LSIRDY_MASK: u32 = 0x1 << 1;
+
+pub const CSR_LSION_POS: u32 = 0;
+pub const CSR_LSION_WIDTH: u32 = 1;
+pub const CSR_LSION_MASK: u32 = 0x1 << 0;
+
+impl PeripheralAccess for RegisterBlock {
+    const BASE_ADDRESS: u32 = RCC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
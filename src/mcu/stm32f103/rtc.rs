@@ -0,0 +1,67 @@
+// RTC (Real-Time Clock) peripheral definitions
+// Generated from STM32F103 SVD file
+
+use super::{PeripheralAccess, RTC_BASEADDR};
+
+// RTC Register Block, 16-bit registers on 32-bit boundaries
+#[repr(C)]
+pub struct RegisterBlock {
+    pub crh: u32,  // RW: RTC Control Register High
+    pub crl: u32,  // RW: RTC Control Register Low
+    pub prlh: u32, // WO: RTC Prescaler Load Register High
+    pub prll: u32, // WO: RTC Prescaler Load Register Low
+    pub divh: u32, // RO: RTC Prescaler Divider Register High
+    pub divl: u32, // RO: RTC Prescaler Divider Register Low
+    pub cnth: u32, // RW: RTC Counter Register High
+    pub cntl: u32, // RW: RTC Counter Register Low
+    pub alrh: u32, // WO: RTC Alarm Register High
+    pub alrl: u32, // WO: RTC Alarm Register Low
+}
+
+// RTC peripheral instance
+pub struct RTC;
+
+impl PeripheralAccess for RTC {
+    const BASE_ADDRESS: u32 = RTC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+// RTC Register Field Definitions
+
+// CRH register fields
+pub const CRH_OWIE_POS: u32 = 2;
+pub const CRH_OWIE_WIDTH: u32 = 1;
+pub const CRH_OWIE_MASK: u32 = 0x1 << 2;
+
+pub const CRH_ALRIE_POS: u32 = 1;
+pub const CRH_ALRIE_WIDTH: u32 = 1;
+pub const CRH_ALRIE_MASK: u32 = 0x1 << 1;
+
+pub const CRH_SECIE_POS: u32 = 0;
+pub const CRH_SECIE_WIDTH: u32 = 1;
+pub const CRH_SECIE_MASK: u32 = 0x1 << 0;
+
+// CRL register fields
+pub const CRL_RTOFF_POS: u32 = 5;
+pub const CRL_RTOFF_WIDTH: u32 = 1;
+pub const CRL_RTOFF_MASK: u32 = 0x1 << 5;
+
+pub const CRL_CNF_POS: u32 = 4;
+pub const CRL_CNF_WIDTH: u32 = 1;
+pub const CRL_CNF_MASK: u32 = 0x1 << 4;
+
+pub const CRL_RSF_POS: u32 = 3;
+pub const CRL_RSF_WIDTH: u32 = 1;
+pub const CRL_RSF_MASK: u32 = 0x1 << 3;
+
+pub const CRL_OWF_POS: u32 = 2;
+pub const CRL_OWF_WIDTH: u32 = 1;
+pub const CRL_OWF_MASK: u32 = 0x1 << 2;
+
+pub const CRL_ALRF_POS: u32 = 1;
+pub const CRL_ALRF_WIDTH: u32 = 1;
+pub const CRL_ALRF_MASK: u32 = 0x1 << 1;
+
+pub const CRL_SECF_POS: u32 = 0;
+pub const CRL_SECF_WIDTH: u32 = 1;
+pub const CRL_SECF_MASK: u32 = 0x1 << 0;
@@ -0,0 +1,38 @@
+// EXTI (External Interrupt/Event Controller) and SYSCFG peripheral definitions
+// Shared by the STM32F4 family, generated from the STM32F407 SVD file
+
+// EXTI Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub imr: u32,   // RW: Interrupt mask register
+    pub emr: u32,   // RW: Event mask register
+    pub rtsr: u32,  // RW: Rising trigger selection register
+    pub ftsr: u32,  // RW: Falling trigger selection register
+    pub swier: u32, // RW: Software interrupt event register
+    pub pr: u32,    // RW: Pending register (write 1 to clear)
+}
+
+// SYSCFG Register Block
+#[repr(C)]
+pub struct SyscfgRegisterBlock {
+    pub memrmp: u32,      // RW: memory remap register
+    pub pmc: u32,         // RW: peripheral mode configuration register
+    pub exticr: [u32; 4], // RW: external interrupt configuration registers 1-4
+    _reserved0: [u32; 2],
+    pub cmpcr: u32, // RO: Compensation cell control register
+}
+
+/// Number of EXTI lines
+pub const EXTI_LINES: u8 = 23;
+
+/// EXTI line of the RTC alarm
+pub const EXTI_LINE_RTC_ALARM: u8 = 17;
+
+/// EXTI line of the RTC wakeup timer
+pub const EXTI_LINE_RTC_WAKEUP: u8 = 22;
+
+// SYSCFG Register Field Definitions
+
+// EXTICR register fields, four bits per line selecting the port
+pub const EXTICR_EXTI_WIDTH: u32 = 4;
+pub const EXTICR_EXTI_MASK: u32 = 0xF;
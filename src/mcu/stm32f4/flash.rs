@@ -0,0 +1,126 @@
+// FLASH (embedded flash interface) peripheral definitions
+// Shared by the STM32F4 family, generated from the STM32F407 SVD file
+
+// FLASH Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub acr: u32,     // RW: Flash access control register
+    pub keyr: u32,    // WO: Flash key register
+    pub optkeyr: u32, // WO: Flash option key register
+    pub sr: u32,      // RW: Status register
+    pub cr: u32,      // RW: Control register
+    pub optcr: u32,   // RW: Flash option control register
+}
+
+// FLASH Register Field Definitions
+
+// ACR register fields
+pub const ACR_DCRST_POS: u32 = 12;
+pub const ACR_DCRST_WIDTH: u32 = 1;
+pub const ACR_DCRST_MASK: u32 = 0x1 << 12;
+
+pub const ACR_ICRST_POS: u32 = 11;
+pub const ACR_ICRST_WIDTH: u32 = 1;
+pub const ACR_ICRST_MASK: u32 = 0x1 << 11;
+
+pub const ACR_DCEN_POS: u32 = 10;
+pub const ACR_DCEN_WIDTH: u32 = 1;
+pub const ACR_DCEN_MASK: u32 = 0x1 << 10;
+
+pub const ACR_ICEN_POS: u32 = 9;
+pub const ACR_ICEN_WIDTH: u32 = 1;
+pub const ACR_ICEN_MASK: u32 = 0x1 << 9;
+
+pub const ACR_PRFTEN_POS: u32 = 8;
+pub const ACR_PRFTEN_WIDTH: u32 = 1;
+pub const ACR_PRFTEN_MASK: u32 = 0x1 << 8;
+
+pub const ACR_LATENCY_POS: u32 = 0;
+pub const ACR_LATENCY_WIDTH: u32 = 3;
+pub const ACR_LATENCY_MASK: u32 = 0x7 << 0;
+
+// KEYR values unlocking CR, written in this order
+pub const KEYR_KEY1: u32 = 0x4567_0123;
+pub const KEYR_KEY2: u32 = 0xCDEF_89AB;
+
+// SR register fields
+pub const SR_BSY_POS: u32 = 16;
+pub const SR_BSY_WIDTH: u32 = 1;
+pub const SR_BSY_MASK: u32 = 0x1 << 16;
+
+pub const SR_PGSERR_POS: u32 = 7;
+pub const SR_PGSERR_WIDTH: u32 = 1;
+pub const SR_PGSERR_MASK: u32 = 0x1 << 7;
+
+pub const SR_PGPERR_POS: u32 = 6;
+pub const SR_PGPERR_WIDTH: u32 = 1;
+pub const SR_PGPERR_MASK: u32 = 0x1 << 6;
+
+pub const SR_PGAERR_POS: u32 = 5;
+pub const SR_PGAERR_WIDTH: u32 = 1;
+pub const SR_PGAERR_MASK: u32 = 0x1 << 5;
+
+pub const SR_WRPERR_POS: u32 = 4;
+pub const SR_WRPERR_WIDTH: u32 = 1;
+pub const SR_WRPERR_MASK: u32 = 0x1 << 4;
+
+pub const SR_OPERR_POS: u32 = 1;
+pub const SR_OPERR_WIDTH: u32 = 1;
+pub const SR_OPERR_MASK: u32 = 0x1 << 1;
+
+pub const SR_EOP_POS: u32 = 0;
+pub const SR_EOP_WIDTH: u32 = 1;
+pub const SR_EOP_MASK: u32 = 0x1 << 0;
+
+// Error flags, cleared by writing 1
+pub const SR_ERRORS_MASK: u32 =
+    SR_PGSERR_MASK | SR_PGPERR_MASK | SR_PGAERR_MASK | SR_WRPERR_MASK | SR_OPERR_MASK;
+
+// CR register fields
+pub const CR_LOCK_POS: u32 = 31;
+pub const CR_LOCK_WIDTH: u32 = 1;
+pub const CR_LOCK_MASK: u32 = 0x1 << 31;
+
+pub const CR_ERRIE_POS: u32 = 25;
+pub const CR_ERRIE_WIDTH: u32 = 1;
+pub const CR_ERRIE_MASK: u32 = 0x1 << 25;
+
+pub const CR_EOPIE_POS: u32 = 24;
+pub const CR_EOPIE_WIDTH: u32 = 1;
+pub const CR_EOPIE_MASK: u32 = 0x1 << 24;
+
+pub const CR_STRT_POS: u32 = 16;
+pub const CR_STRT_WIDTH: u32 = 1;
+pub const CR_STRT_MASK: u32 = 0x1 << 16;
+
+pub const CR_PSIZE_POS: u32 = 8;
+pub const CR_PSIZE_WIDTH: u32 = 2;
+pub const CR_PSIZE_MASK: u32 = 0x3 << 8;
+// PSIZE enumerated values, the parallelism programming and erasing use
+pub const CR_PSIZE_X8: u32 = 0 << 8;
+pub const CR_PSIZE_X16: u32 = 1 << 8;
+pub const CR_PSIZE_X32: u32 = 2 << 8;
+pub const CR_PSIZE_X64: u32 = 3 << 8;
+
+pub const CR_SNB_POS: u32 = 3;
+pub const CR_SNB_WIDTH: u32 = 4;
+pub const CR_SNB_MASK: u32 = 0xF << 3;
+
+pub const CR_MER_POS: u32 = 2;
+pub const CR_MER_WIDTH: u32 = 1;
+pub const CR_MER_MASK: u32 = 0x1 << 2;
+
+pub const CR_SER_POS: u32 = 1;
+pub const CR_SER_WIDTH: u32 = 1;
+pub const CR_SER_MASK: u32 = 0x1 << 1;
+
+pub const CR_PG_POS: u32 = 0;
+pub const CR_PG_WIDTH: u32 = 1;
+pub const CR_PG_MASK: u32 = 0x1 << 0;
+
+// Main memory, organized as four 16K sectors, one 64K sector and 128K
+// sectors up to the size of the part
+pub const FLASH_BASE: u32 = 0x0800_0000;
+pub const SMALL_SECTOR_SIZE: u32 = 16 * 1024;
+pub const MEDIUM_SECTOR_SIZE: u32 = 64 * 1024;
+pub const LARGE_SECTOR_SIZE: u32 = 128 * 1024;
@@ -0,0 +1,18 @@
+//! # STM32F4 Family Register Definitions
+//!
+//! Register blocks and field definitions of the peripherals that are
+//! identical across the STM32F401, STM32F407 and STM32F411 (GPIO, RCC, USART,
+//! SPI, I2C, PWR, EXTI/SYSCFG, RTC, the flash interface and USB OTG_FS).
+//!
+//! Part modules re-export these and add what differs between parts: base
+//! addresses, peripheral instances, IRQ numbers and alternate function maps.
+pub mod exti;
+pub mod flash;
+pub mod gpio;
+pub mod i2c;
+pub mod otg_fs;
+pub mod pwr;
+pub mod rcc;
+pub mod rtc;
+pub mod spi;
+pub mod usart;
@@ -0,0 +1,880 @@
+// OTG_FS (USB on-the-go full-speed) peripheral definitions
+// Shared by the STM32F4 family, generated from the STM32F407 SVD file
+
+// Host channel registers
+#[repr(C)]
+pub struct Channel {
+    pub hcchar: u32, // RW: characteristics register
+    _reserved0: u32,
+    pub hcint: u32,    // RW: interrupt register
+    pub hcintmsk: u32, // RW: interrupt mask register
+    pub hctsiz: u32,   // RW: transfer size register
+    _reserved1: [u32; 3],
+}
+
+// Device IN endpoint registers
+#[repr(C)]
+pub struct InEndpoint {
+    pub diepctl: u32, // RW: control register
+    _reserved0: u32,
+    pub diepint: u32, // RW: interrupt register
+    _reserved1: u32,
+    pub dieptsiz: u32, // RW: transfer size register
+    _reserved2: u32,
+    pub dtxfsts: u32, // RO: transmit FIFO status register
+    _reserved3: u32,
+}
+
+// Device OUT endpoint registers
+#[repr(C)]
+pub struct OutEndpoint {
+    pub doepctl: u32, // RW: control register
+    _reserved0: u32,
+    pub doepint: u32, // RW: interrupt register
+    _reserved1: u32,
+    pub doeptsiz: u32, // RW: transfer size register
+    _reserved2: [u32; 3],
+}
+
+// OTG_FS Register Block: global registers from 0x000, host from 0x400,
+// device from 0x800, power and clock gating at 0xE00
+#[repr(C)]
+pub struct RegisterBlock {
+    pub gotgctl: u32,  // RW: control and status register
+    pub gotgint: u32,  // RW: interrupt register
+    pub gahbcfg: u32,  // RW: AHB configuration register
+    pub gusbcfg: u32,  // RW: USB configuration register
+    pub grstctl: u32,  // RW: reset register
+    pub gintsts: u32,  // RW: core interrupt register
+    pub gintmsk: u32,  // RW: interrupt mask register
+    pub grxstsr: u32,  // RO: receive status debug read register
+    pub grxstsp: u32,  // RO: receive status read and pop register
+    pub grxfsiz: u32,  // RW: receive FIFO size register
+    pub dieptxf0: u32, // RW: endpoint 0 / non-periodic transmit FIFO size register
+    pub hnptxsts: u32, // RO: non-periodic transmit FIFO/queue status register
+    _reserved0: [u32; 2],
+    pub gccfg: u32, // RW: general core configuration register
+    pub cid: u32,   // RW: core ID register
+    _reserved1: [u32; 48],
+    pub hptxfsiz: u32,     // RW: host periodic transmit FIFO size register
+    pub dieptxf: [u32; 3], // RW: device IN endpoint 1-3 transmit FIFO size registers
+    _reserved2: [u32; 188],
+    pub hcfg: u32,  // RW: host configuration register
+    pub hfir: u32,  // RW: host frame interval register
+    pub hfnum: u32, // RO: host frame number/frame time remaining register
+    _reserved3: u32,
+    pub hptxsts: u32,  // RW: host periodic transmit FIFO/queue status register
+    pub haint: u32,    // RO: host all channels interrupt register
+    pub haintmsk: u32, // RW: host all channels interrupt mask register
+    _reserved4: [u32; 9],
+    pub hprt: u32, // RW: host port control and status register
+    _reserved5: [u32; 47],
+    pub channels: [Channel; 8], // Host channels 0-7
+    _reserved6: [u32; 128],
+    pub dcfg: u32, // RW: device configuration register
+    pub dctl: u32, // RW: device control register
+    pub dsts: u32, // RO: device status register
+    _reserved7: u32,
+    pub diepmsk: u32,  // RW: device IN endpoint common interrupt mask register
+    pub doepmsk: u32,  // RW: device OUT endpoint common interrupt mask register
+    pub daint: u32,    // RO: device all endpoints interrupt register
+    pub daintmsk: u32, // RW: device all endpoints interrupt mask register
+    _reserved8: [u32; 2],
+    pub dvbusdis: u32,   // RW: device VBUS discharge time register
+    pub dvbuspulse: u32, // RW: device VBUS pulsing time register
+    _reserved9: u32,
+    pub diepempmsk: u32, // RW: device IN endpoint FIFO empty interrupt mask register
+    _reserved10: [u32; 50],
+    pub diep: [InEndpoint; 4], // Device IN endpoints 0-3
+    _reserved11: [u32; 96],
+    pub doep: [OutEndpoint; 4], // Device OUT endpoints 0-3
+    _reserved12: [u32; 160],
+    pub pcgcctl: u32, // RW: power and clock gating control register
+}
+
+// Data FIFO of endpoint or channel n: pushed and popped a word at a time
+// at base + FIFO_OFFSET + n * FIFO_STRIDE
+pub const FIFO_OFFSET: u32 = 0x1000;
+pub const FIFO_STRIDE: u32 = 0x1000;
+
+// Words of FIFO RAM, shared by the receive and transmit FIFOs
+pub const FIFO_RAM_WORDS: u32 = 320;
+
+// Device endpoints, 0 to 3 each way
+pub const DEVICE_ENDPOINTS: usize = 4;
+
+// Host channels
+pub const HOST_CHANNELS: usize = 8;
+
+// OTG_FS Register Field Definitions
+
+// GAHBCFG register fields
+pub const GAHBCFG_PTXFELVL_POS: u32 = 8;
+pub const GAHBCFG_PTXFELVL_WIDTH: u32 = 1;
+pub const GAHBCFG_PTXFELVL_MASK: u32 = 0x1 << 8;
+
+pub const GAHBCFG_TXFELVL_POS: u32 = 7;
+pub const GAHBCFG_TXFELVL_WIDTH: u32 = 1;
+pub const GAHBCFG_TXFELVL_MASK: u32 = 0x1 << 7;
+
+pub const GAHBCFG_GINT_POS: u32 = 0;
+pub const GAHBCFG_GINT_WIDTH: u32 = 1;
+pub const GAHBCFG_GINT_MASK: u32 = 0x1 << 0;
+
+// GUSBCFG register fields
+pub const GUSBCFG_CTXPKT_POS: u32 = 31;
+pub const GUSBCFG_CTXPKT_WIDTH: u32 = 1;
+pub const GUSBCFG_CTXPKT_MASK: u32 = 0x1 << 31;
+
+pub const GUSBCFG_FDMOD_POS: u32 = 30;
+pub const GUSBCFG_FDMOD_WIDTH: u32 = 1;
+pub const GUSBCFG_FDMOD_MASK: u32 = 0x1 << 30;
+
+pub const GUSBCFG_FHMOD_POS: u32 = 29;
+pub const GUSBCFG_FHMOD_WIDTH: u32 = 1;
+pub const GUSBCFG_FHMOD_MASK: u32 = 0x1 << 29;
+
+pub const GUSBCFG_TRDT_POS: u32 = 10;
+pub const GUSBCFG_TRDT_WIDTH: u32 = 4;
+pub const GUSBCFG_TRDT_MASK: u32 = 0xF << 10;
+
+pub const GUSBCFG_HNPCAP_POS: u32 = 9;
+pub const GUSBCFG_HNPCAP_WIDTH: u32 = 1;
+pub const GUSBCFG_HNPCAP_MASK: u32 = 0x1 << 9;
+
+pub const GUSBCFG_SRPCAP_POS: u32 = 8;
+pub const GUSBCFG_SRPCAP_WIDTH: u32 = 1;
+pub const GUSBCFG_SRPCAP_MASK: u32 = 0x1 << 8;
+
+pub const GUSBCFG_PHYSEL_POS: u32 = 6;
+pub const GUSBCFG_PHYSEL_WIDTH: u32 = 1;
+pub const GUSBCFG_PHYSEL_MASK: u32 = 0x1 << 6;
+
+pub const GUSBCFG_TOCAL_POS: u32 = 0;
+pub const GUSBCFG_TOCAL_WIDTH: u32 = 3;
+pub const GUSBCFG_TOCAL_MASK: u32 = 0x7 << 0;
+
+// GRSTCTL register fields
+pub const GRSTCTL_AHBIDL_POS: u32 = 31;
+pub const GRSTCTL_AHBIDL_WIDTH: u32 = 1;
+pub const GRSTCTL_AHBIDL_MASK: u32 = 0x1 << 31;
+
+pub const GRSTCTL_TXFNUM_POS: u32 = 6;
+pub const GRSTCTL_TXFNUM_WIDTH: u32 = 5;
+pub const GRSTCTL_TXFNUM_MASK: u32 = 0x1F << 6;
+
+pub const GRSTCTL_TXFFLSH_POS: u32 = 5;
+pub const GRSTCTL_TXFFLSH_WIDTH: u32 = 1;
+pub const GRSTCTL_TXFFLSH_MASK: u32 = 0x1 << 5;
+
+pub const GRSTCTL_RXFFLSH_POS: u32 = 4;
+pub const GRSTCTL_RXFFLSH_WIDTH: u32 = 1;
+pub const GRSTCTL_RXFFLSH_MASK: u32 = 0x1 << 4;
+
+pub const GRSTCTL_FCRST_POS: u32 = 2;
+pub const GRSTCTL_FCRST_WIDTH: u32 = 1;
+pub const GRSTCTL_FCRST_MASK: u32 = 0x1 << 2;
+
+pub const GRSTCTL_HSRST_POS: u32 = 1;
+pub const GRSTCTL_HSRST_WIDTH: u32 = 1;
+pub const GRSTCTL_HSRST_MASK: u32 = 0x1 << 1;
+
+pub const GRSTCTL_CSRST_POS: u32 = 0;
+pub const GRSTCTL_CSRST_WIDTH: u32 = 1;
+pub const GRSTCTL_CSRST_MASK: u32 = 0x1 << 0;
+
+// GINTSTS register fields
+// GINTMSK takes the same bits
+pub const GINTSTS_WKUPINT_POS: u32 = 31;
+pub const GINTSTS_WKUPINT_WIDTH: u32 = 1;
+pub const GINTSTS_WKUPINT_MASK: u32 = 0x1 << 31;
+
+pub const GINTSTS_SRQINT_POS: u32 = 30;
+pub const GINTSTS_SRQINT_WIDTH: u32 = 1;
+pub const GINTSTS_SRQINT_MASK: u32 = 0x1 << 30;
+
+pub const GINTSTS_DISCINT_POS: u32 = 29;
+pub const GINTSTS_DISCINT_WIDTH: u32 = 1;
+pub const GINTSTS_DISCINT_MASK: u32 = 0x1 << 29;
+
+pub const GINTSTS_CIDSCHG_POS: u32 = 28;
+pub const GINTSTS_CIDSCHG_WIDTH: u32 = 1;
+pub const GINTSTS_CIDSCHG_MASK: u32 = 0x1 << 28;
+
+pub const GINTSTS_PTXFE_POS: u32 = 26;
+pub const GINTSTS_PTXFE_WIDTH: u32 = 1;
+pub const GINTSTS_PTXFE_MASK: u32 = 0x1 << 26;
+
+pub const GINTSTS_HCINT_POS: u32 = 25;
+pub const GINTSTS_HCINT_WIDTH: u32 = 1;
+pub const GINTSTS_HCINT_MASK: u32 = 0x1 << 25;
+
+pub const GINTSTS_HPRTINT_POS: u32 = 24;
+pub const GINTSTS_HPRTINT_WIDTH: u32 = 1;
+pub const GINTSTS_HPRTINT_MASK: u32 = 0x1 << 24;
+
+pub const GINTSTS_IPXFR_INCOMPISOOUT_POS: u32 = 21;
+pub const GINTSTS_IPXFR_INCOMPISOOUT_WIDTH: u32 = 1;
+pub const GINTSTS_IPXFR_INCOMPISOOUT_MASK: u32 = 0x1 << 21;
+
+pub const GINTSTS_IISOIXFR_POS: u32 = 20;
+pub const GINTSTS_IISOIXFR_WIDTH: u32 = 1;
+pub const GINTSTS_IISOIXFR_MASK: u32 = 0x1 << 20;
+
+pub const GINTSTS_OEPINT_POS: u32 = 19;
+pub const GINTSTS_OEPINT_WIDTH: u32 = 1;
+pub const GINTSTS_OEPINT_MASK: u32 = 0x1 << 19;
+
+pub const GINTSTS_IEPINT_POS: u32 = 18;
+pub const GINTSTS_IEPINT_WIDTH: u32 = 1;
+pub const GINTSTS_IEPINT_MASK: u32 = 0x1 << 18;
+
+pub const GINTSTS_EOPF_POS: u32 = 15;
+pub const GINTSTS_EOPF_WIDTH: u32 = 1;
+pub const GINTSTS_EOPF_MASK: u32 = 0x1 << 15;
+
+pub const GINTSTS_ISOODRP_POS: u32 = 14;
+pub const GINTSTS_ISOODRP_WIDTH: u32 = 1;
+pub const GINTSTS_ISOODRP_MASK: u32 = 0x1 << 14;
+
+pub const GINTSTS_ENUMDNE_POS: u32 = 13;
+pub const GINTSTS_ENUMDNE_WIDTH: u32 = 1;
+pub const GINTSTS_ENUMDNE_MASK: u32 = 0x1 << 13;
+
+pub const GINTSTS_USBRST_POS: u32 = 12;
+pub const GINTSTS_USBRST_WIDTH: u32 = 1;
+pub const GINTSTS_USBRST_MASK: u32 = 0x1 << 12;
+
+pub const GINTSTS_USBSUSP_POS: u32 = 11;
+pub const GINTSTS_USBSUSP_WIDTH: u32 = 1;
+pub const GINTSTS_USBSUSP_MASK: u32 = 0x1 << 11;
+
+pub const GINTSTS_ESUSP_POS: u32 = 10;
+pub const GINTSTS_ESUSP_WIDTH: u32 = 1;
+pub const GINTSTS_ESUSP_MASK: u32 = 0x1 << 10;
+
+pub const GINTSTS_GONAKEFF_POS: u32 = 7;
+pub const GINTSTS_GONAKEFF_WIDTH: u32 = 1;
+pub const GINTSTS_GONAKEFF_MASK: u32 = 0x1 << 7;
+
+pub const GINTSTS_GINAKEFF_POS: u32 = 6;
+pub const GINTSTS_GINAKEFF_WIDTH: u32 = 1;
+pub const GINTSTS_GINAKEFF_MASK: u32 = 0x1 << 6;
+
+pub const GINTSTS_NPTXFE_POS: u32 = 5;
+pub const GINTSTS_NPTXFE_WIDTH: u32 = 1;
+pub const GINTSTS_NPTXFE_MASK: u32 = 0x1 << 5;
+
+pub const GINTSTS_RXFLVL_POS: u32 = 4;
+pub const GINTSTS_RXFLVL_WIDTH: u32 = 1;
+pub const GINTSTS_RXFLVL_MASK: u32 = 0x1 << 4;
+
+pub const GINTSTS_SOF_POS: u32 = 3;
+pub const GINTSTS_SOF_WIDTH: u32 = 1;
+pub const GINTSTS_SOF_MASK: u32 = 0x1 << 3;
+
+pub const GINTSTS_OTGINT_POS: u32 = 2;
+pub const GINTSTS_OTGINT_WIDTH: u32 = 1;
+pub const GINTSTS_OTGINT_MASK: u32 = 0x1 << 2;
+
+pub const GINTSTS_MMIS_POS: u32 = 1;
+pub const GINTSTS_MMIS_WIDTH: u32 = 1;
+pub const GINTSTS_MMIS_MASK: u32 = 0x1 << 1;
+
+pub const GINTSTS_CMOD_POS: u32 = 0;
+pub const GINTSTS_CMOD_WIDTH: u32 = 1;
+pub const GINTSTS_CMOD_MASK: u32 = 0x1 << 0;
+
+// GRXSTSP register fields
+pub const GRXSTSP_FRMNUM_POS: u32 = 21;
+pub const GRXSTSP_FRMNUM_WIDTH: u32 = 4;
+pub const GRXSTSP_FRMNUM_MASK: u32 = 0xF << 21;
+
+pub const GRXSTSP_PKTSTS_POS: u32 = 17;
+pub const GRXSTSP_PKTSTS_WIDTH: u32 = 4;
+pub const GRXSTSP_PKTSTS_MASK: u32 = 0xF << 17;
+
+pub const GRXSTSP_DPID_POS: u32 = 15;
+pub const GRXSTSP_DPID_WIDTH: u32 = 2;
+pub const GRXSTSP_DPID_MASK: u32 = 0x3 << 15;
+
+pub const GRXSTSP_BCNT_POS: u32 = 4;
+pub const GRXSTSP_BCNT_WIDTH: u32 = 11;
+pub const GRXSTSP_BCNT_MASK: u32 = 0x7FF << 4;
+
+pub const GRXSTSP_EPNUM_POS: u32 = 0;
+pub const GRXSTSP_EPNUM_WIDTH: u32 = 4;
+pub const GRXSTSP_EPNUM_MASK: u32 = 0xF << 0;
+// PKTSTS values in device mode
+pub const GRXSTSP_PKTSTS_GLOBAL_OUT_NAK: u32 = 1 << 17;
+pub const GRXSTSP_PKTSTS_OUT_DATA: u32 = 2 << 17;
+pub const GRXSTSP_PKTSTS_OUT_COMPLETE: u32 = 3 << 17;
+pub const GRXSTSP_PKTSTS_SETUP_COMPLETE: u32 = 4 << 17;
+pub const GRXSTSP_PKTSTS_SETUP_DATA: u32 = 6 << 17;
+// PKTSTS values in host mode
+pub const GRXSTSP_PKTSTS_IN_DATA: u32 = 2 << 17;
+pub const GRXSTSP_PKTSTS_IN_COMPLETE: u32 = 3 << 17;
+pub const GRXSTSP_PKTSTS_TOGGLE_ERROR: u32 = 5 << 17;
+pub const GRXSTSP_PKTSTS_CHANNEL_HALTED: u32 = 7 << 17;
+
+// GRXFSIZ register fields
+pub const GRXFSIZ_RXFD_POS: u32 = 0;
+pub const GRXFSIZ_RXFD_WIDTH: u32 = 16;
+pub const GRXFSIZ_RXFD_MASK: u32 = 0xFFFF << 0;
+
+// DIEPTXF register fields
+// DIEPTXF0 (TX FIFO of endpoint 0) and DIEPTXFx take the same fields, as
+// do HNPTXFSIZ and HPTXFSIZ in host mode
+pub const DIEPTXF_INEPTXFD_POS: u32 = 16;
+pub const DIEPTXF_INEPTXFD_WIDTH: u32 = 16;
+pub const DIEPTXF_INEPTXFD_MASK: u32 = 0xFFFF << 16;
+
+pub const DIEPTXF_INEPTXSA_POS: u32 = 0;
+pub const DIEPTXF_INEPTXSA_WIDTH: u32 = 16;
+pub const DIEPTXF_INEPTXSA_MASK: u32 = 0xFFFF << 0;
+
+// GCCFG register fields
+pub const GCCFG_NOVBUSSENS_POS: u32 = 21;
+pub const GCCFG_NOVBUSSENS_WIDTH: u32 = 1;
+pub const GCCFG_NOVBUSSENS_MASK: u32 = 0x1 << 21;
+
+pub const GCCFG_SOFOUTEN_POS: u32 = 20;
+pub const GCCFG_SOFOUTEN_WIDTH: u32 = 1;
+pub const GCCFG_SOFOUTEN_MASK: u32 = 0x1 << 20;
+
+pub const GCCFG_VBUSBSEN_POS: u32 = 19;
+pub const GCCFG_VBUSBSEN_WIDTH: u32 = 1;
+pub const GCCFG_VBUSBSEN_MASK: u32 = 0x1 << 19;
+
+pub const GCCFG_VBUSASEN_POS: u32 = 18;
+pub const GCCFG_VBUSASEN_WIDTH: u32 = 1;
+pub const GCCFG_VBUSASEN_MASK: u32 = 0x1 << 18;
+
+pub const GCCFG_PWRDWN_POS: u32 = 16;
+pub const GCCFG_PWRDWN_WIDTH: u32 = 1;
+pub const GCCFG_PWRDWN_MASK: u32 = 0x1 << 16;
+
+// HNPTXSTS register fields
+// HPTXSTS takes the same fields
+pub const HNPTXSTS_NPTQXSAV_POS: u32 = 16;
+pub const HNPTXSTS_NPTQXSAV_WIDTH: u32 = 8;
+pub const HNPTXSTS_NPTQXSAV_MASK: u32 = 0xFF << 16;
+
+pub const HNPTXSTS_NPTXFSAV_POS: u32 = 0;
+pub const HNPTXSTS_NPTXFSAV_WIDTH: u32 = 16;
+pub const HNPTXSTS_NPTXFSAV_MASK: u32 = 0xFFFF << 0;
+
+// HCFG register fields
+pub const HCFG_FSLSS_POS: u32 = 2;
+pub const HCFG_FSLSS_WIDTH: u32 = 1;
+pub const HCFG_FSLSS_MASK: u32 = 0x1 << 2;
+
+pub const HCFG_FSLSPCS_POS: u32 = 0;
+pub const HCFG_FSLSPCS_WIDTH: u32 = 2;
+pub const HCFG_FSLSPCS_MASK: u32 = 0x3 << 0;
+// FSLSPCS enumerated values
+pub const HCFG_FSLSPCS_48MHZ: u32 = 1 << 0;
+pub const HCFG_FSLSPCS_6MHZ: u32 = 2 << 0;
+
+// HFIR register fields
+pub const HFIR_FRIVL_POS: u32 = 0;
+pub const HFIR_FRIVL_WIDTH: u32 = 16;
+pub const HFIR_FRIVL_MASK: u32 = 0xFFFF << 0;
+
+// HFNUM register fields
+pub const HFNUM_FTREM_POS: u32 = 16;
+pub const HFNUM_FTREM_WIDTH: u32 = 16;
+pub const HFNUM_FTREM_MASK: u32 = 0xFFFF << 16;
+
+pub const HFNUM_FRNUM_POS: u32 = 0;
+pub const HFNUM_FRNUM_WIDTH: u32 = 16;
+pub const HFNUM_FRNUM_MASK: u32 = 0xFFFF << 0;
+
+// HPRT register fields
+// PENA, PENCHNG, POCCHNG and PCDET clear on writing 1, so writes of the
+// other bits leave them 0
+pub const HPRT_PSPD_POS: u32 = 17;
+pub const HPRT_PSPD_WIDTH: u32 = 2;
+pub const HPRT_PSPD_MASK: u32 = 0x3 << 17;
+// PSPD enumerated values
+pub const HPRT_PSPD_FULL_SPEED: u32 = 1 << 17;
+pub const HPRT_PSPD_LOW_SPEED: u32 = 2 << 17;
+
+pub const HPRT_PTCTL_POS: u32 = 13;
+pub const HPRT_PTCTL_WIDTH: u32 = 4;
+pub const HPRT_PTCTL_MASK: u32 = 0xF << 13;
+
+pub const HPRT_PPWR_POS: u32 = 12;
+pub const HPRT_PPWR_WIDTH: u32 = 1;
+pub const HPRT_PPWR_MASK: u32 = 0x1 << 12;
+
+pub const HPRT_PLSTS_POS: u32 = 10;
+pub const HPRT_PLSTS_WIDTH: u32 = 2;
+pub const HPRT_PLSTS_MASK: u32 = 0x3 << 10;
+
+pub const HPRT_PRST_POS: u32 = 8;
+pub const HPRT_PRST_WIDTH: u32 = 1;
+pub const HPRT_PRST_MASK: u32 = 0x1 << 8;
+
+pub const HPRT_PSUSP_POS: u32 = 7;
+pub const HPRT_PSUSP_WIDTH: u32 = 1;
+pub const HPRT_PSUSP_MASK: u32 = 0x1 << 7;
+
+pub const HPRT_PRES_POS: u32 = 6;
+pub const HPRT_PRES_WIDTH: u32 = 1;
+pub const HPRT_PRES_MASK: u32 = 0x1 << 6;
+
+pub const HPRT_POCCHNG_POS: u32 = 5;
+pub const HPRT_POCCHNG_WIDTH: u32 = 1;
+pub const HPRT_POCCHNG_MASK: u32 = 0x1 << 5;
+
+pub const HPRT_POCA_POS: u32 = 4;
+pub const HPRT_POCA_WIDTH: u32 = 1;
+pub const HPRT_POCA_MASK: u32 = 0x1 << 4;
+
+pub const HPRT_PENCHNG_POS: u32 = 3;
+pub const HPRT_PENCHNG_WIDTH: u32 = 1;
+pub const HPRT_PENCHNG_MASK: u32 = 0x1 << 3;
+
+pub const HPRT_PENA_POS: u32 = 2;
+pub const HPRT_PENA_WIDTH: u32 = 1;
+pub const HPRT_PENA_MASK: u32 = 0x1 << 2;
+
+pub const HPRT_PCDET_POS: u32 = 1;
+pub const HPRT_PCDET_WIDTH: u32 = 1;
+pub const HPRT_PCDET_MASK: u32 = 0x1 << 1;
+
+pub const HPRT_PCSTS_POS: u32 = 0;
+pub const HPRT_PCSTS_WIDTH: u32 = 1;
+pub const HPRT_PCSTS_MASK: u32 = 0x1 << 0;
+
+// HCCHAR register fields
+pub const HCCHAR_CHENA_POS: u32 = 31;
+pub const HCCHAR_CHENA_WIDTH: u32 = 1;
+pub const HCCHAR_CHENA_MASK: u32 = 0x1 << 31;
+
+pub const HCCHAR_CHDIS_POS: u32 = 30;
+pub const HCCHAR_CHDIS_WIDTH: u32 = 1;
+pub const HCCHAR_CHDIS_MASK: u32 = 0x1 << 30;
+
+pub const HCCHAR_ODDFRM_POS: u32 = 29;
+pub const HCCHAR_ODDFRM_WIDTH: u32 = 1;
+pub const HCCHAR_ODDFRM_MASK: u32 = 0x1 << 29;
+
+pub const HCCHAR_DAD_POS: u32 = 22;
+pub const HCCHAR_DAD_WIDTH: u32 = 7;
+pub const HCCHAR_DAD_MASK: u32 = 0x7F << 22;
+
+pub const HCCHAR_MCNT_POS: u32 = 20;
+pub const HCCHAR_MCNT_WIDTH: u32 = 2;
+pub const HCCHAR_MCNT_MASK: u32 = 0x3 << 20;
+
+pub const HCCHAR_EPTYP_POS: u32 = 18;
+pub const HCCHAR_EPTYP_WIDTH: u32 = 2;
+pub const HCCHAR_EPTYP_MASK: u32 = 0x3 << 18;
+
+pub const HCCHAR_LSDEV_POS: u32 = 17;
+pub const HCCHAR_LSDEV_WIDTH: u32 = 1;
+pub const HCCHAR_LSDEV_MASK: u32 = 0x1 << 17;
+
+pub const HCCHAR_EPDIR_POS: u32 = 15;
+pub const HCCHAR_EPDIR_WIDTH: u32 = 1;
+pub const HCCHAR_EPDIR_MASK: u32 = 0x1 << 15;
+
+pub const HCCHAR_EPNUM_POS: u32 = 11;
+pub const HCCHAR_EPNUM_WIDTH: u32 = 4;
+pub const HCCHAR_EPNUM_MASK: u32 = 0xF << 11;
+
+pub const HCCHAR_MPSIZ_POS: u32 = 0;
+pub const HCCHAR_MPSIZ_WIDTH: u32 = 11;
+pub const HCCHAR_MPSIZ_MASK: u32 = 0x7FF << 0;
+
+// HCINT register fields
+// HCINTMSK takes the same bits
+pub const HCINT_DTERR_POS: u32 = 10;
+pub const HCINT_DTERR_WIDTH: u32 = 1;
+pub const HCINT_DTERR_MASK: u32 = 0x1 << 10;
+
+pub const HCINT_FRMOR_POS: u32 = 9;
+pub const HCINT_FRMOR_WIDTH: u32 = 1;
+pub const HCINT_FRMOR_MASK: u32 = 0x1 << 9;
+
+pub const HCINT_BBERR_POS: u32 = 8;
+pub const HCINT_BBERR_WIDTH: u32 = 1;
+pub const HCINT_BBERR_MASK: u32 = 0x1 << 8;
+
+pub const HCINT_TXERR_POS: u32 = 7;
+pub const HCINT_TXERR_WIDTH: u32 = 1;
+pub const HCINT_TXERR_MASK: u32 = 0x1 << 7;
+
+pub const HCINT_ACK_POS: u32 = 5;
+pub const HCINT_ACK_WIDTH: u32 = 1;
+pub const HCINT_ACK_MASK: u32 = 0x1 << 5;
+
+pub const HCINT_NAK_POS: u32 = 4;
+pub const HCINT_NAK_WIDTH: u32 = 1;
+pub const HCINT_NAK_MASK: u32 = 0x1 << 4;
+
+pub const HCINT_STALL_POS: u32 = 3;
+pub const HCINT_STALL_WIDTH: u32 = 1;
+pub const HCINT_STALL_MASK: u32 = 0x1 << 3;
+
+pub const HCINT_CHH_POS: u32 = 1;
+pub const HCINT_CHH_WIDTH: u32 = 1;
+pub const HCINT_CHH_MASK: u32 = 0x1 << 1;
+
+pub const HCINT_XFRC_POS: u32 = 0;
+pub const HCINT_XFRC_WIDTH: u32 = 1;
+pub const HCINT_XFRC_MASK: u32 = 0x1 << 0;
+
+// HCTSIZ register fields
+pub const HCTSIZ_DPID_POS: u32 = 29;
+pub const HCTSIZ_DPID_WIDTH: u32 = 2;
+pub const HCTSIZ_DPID_MASK: u32 = 0x3 << 29;
+// DPID enumerated values
+pub const HCTSIZ_DPID_DATA0: u32 = 0 << 29;
+pub const HCTSIZ_DPID_DATA2: u32 = 1 << 29;
+pub const HCTSIZ_DPID_DATA1: u32 = 2 << 29;
+pub const HCTSIZ_DPID_SETUP: u32 = 3 << 29;
+
+pub const HCTSIZ_PKTCNT_POS: u32 = 19;
+pub const HCTSIZ_PKTCNT_WIDTH: u32 = 10;
+pub const HCTSIZ_PKTCNT_MASK: u32 = 0x3FF << 19;
+
+pub const HCTSIZ_XFRSIZ_POS: u32 = 0;
+pub const HCTSIZ_XFRSIZ_WIDTH: u32 = 19;
+pub const HCTSIZ_XFRSIZ_MASK: u32 = 0x7FFFF << 0;
+
+// DCFG register fields
+pub const DCFG_PFIVL_POS: u32 = 11;
+pub const DCFG_PFIVL_WIDTH: u32 = 2;
+pub const DCFG_PFIVL_MASK: u32 = 0x3 << 11;
+
+pub const DCFG_DAD_POS: u32 = 4;
+pub const DCFG_DAD_WIDTH: u32 = 7;
+pub const DCFG_DAD_MASK: u32 = 0x7F << 4;
+
+pub const DCFG_NZLSOHSK_POS: u32 = 2;
+pub const DCFG_NZLSOHSK_WIDTH: u32 = 1;
+pub const DCFG_NZLSOHSK_MASK: u32 = 0x1 << 2;
+
+pub const DCFG_DSPD_POS: u32 = 0;
+pub const DCFG_DSPD_WIDTH: u32 = 2;
+pub const DCFG_DSPD_MASK: u32 = 0x3 << 0;
+// DSPD enumerated values
+pub const DCFG_DSPD_FULL_SPEED: u32 = 3 << 0;
+
+// DCTL register fields
+pub const DCTL_POPRGDNE_POS: u32 = 11;
+pub const DCTL_POPRGDNE_WIDTH: u32 = 1;
+pub const DCTL_POPRGDNE_MASK: u32 = 0x1 << 11;
+
+pub const DCTL_CGONAK_POS: u32 = 10;
+pub const DCTL_CGONAK_WIDTH: u32 = 1;
+pub const DCTL_CGONAK_MASK: u32 = 0x1 << 10;
+
+pub const DCTL_SGONAK_POS: u32 = 9;
+pub const DCTL_SGONAK_WIDTH: u32 = 1;
+pub const DCTL_SGONAK_MASK: u32 = 0x1 << 9;
+
+pub const DCTL_CGINAK_POS: u32 = 8;
+pub const DCTL_CGINAK_WIDTH: u32 = 1;
+pub const DCTL_CGINAK_MASK: u32 = 0x1 << 8;
+
+pub const DCTL_SGINAK_POS: u32 = 7;
+pub const DCTL_SGINAK_WIDTH: u32 = 1;
+pub const DCTL_SGINAK_MASK: u32 = 0x1 << 7;
+
+pub const DCTL_TCTL_POS: u32 = 4;
+pub const DCTL_TCTL_WIDTH: u32 = 3;
+pub const DCTL_TCTL_MASK: u32 = 0x7 << 4;
+
+pub const DCTL_GONSTS_POS: u32 = 3;
+pub const DCTL_GONSTS_WIDTH: u32 = 1;
+pub const DCTL_GONSTS_MASK: u32 = 0x1 << 3;
+
+pub const DCTL_GINSTS_POS: u32 = 2;
+pub const DCTL_GINSTS_WIDTH: u32 = 1;
+pub const DCTL_GINSTS_MASK: u32 = 0x1 << 2;
+
+pub const DCTL_SDIS_POS: u32 = 1;
+pub const DCTL_SDIS_WIDTH: u32 = 1;
+pub const DCTL_SDIS_MASK: u32 = 0x1 << 1;
+
+pub const DCTL_RWUSIG_POS: u32 = 0;
+pub const DCTL_RWUSIG_WIDTH: u32 = 1;
+pub const DCTL_RWUSIG_MASK: u32 = 0x1 << 0;
+
+// DSTS register fields
+pub const DSTS_FNSOF_POS: u32 = 8;
+pub const DSTS_FNSOF_WIDTH: u32 = 14;
+pub const DSTS_FNSOF_MASK: u32 = 0x3FFF << 8;
+
+pub const DSTS_EERR_POS: u32 = 3;
+pub const DSTS_EERR_WIDTH: u32 = 1;
+pub const DSTS_EERR_MASK: u32 = 0x1 << 3;
+
+pub const DSTS_ENUMSPD_POS: u32 = 1;
+pub const DSTS_ENUMSPD_WIDTH: u32 = 2;
+pub const DSTS_ENUMSPD_MASK: u32 = 0x3 << 1;
+
+pub const DSTS_SUSPSTS_POS: u32 = 0;
+pub const DSTS_SUSPSTS_WIDTH: u32 = 1;
+pub const DSTS_SUSPSTS_MASK: u32 = 0x1 << 0;
+
+// DIEPMSK register fields
+pub const DIEPMSK_INEPNEM_POS: u32 = 6;
+pub const DIEPMSK_INEPNEM_WIDTH: u32 = 1;
+pub const DIEPMSK_INEPNEM_MASK: u32 = 0x1 << 6;
+
+pub const DIEPMSK_INEPNMM_POS: u32 = 5;
+pub const DIEPMSK_INEPNMM_WIDTH: u32 = 1;
+pub const DIEPMSK_INEPNMM_MASK: u32 = 0x1 << 5;
+
+pub const DIEPMSK_ITTXFEMSK_POS: u32 = 4;
+pub const DIEPMSK_ITTXFEMSK_WIDTH: u32 = 1;
+pub const DIEPMSK_ITTXFEMSK_MASK: u32 = 0x1 << 4;
+
+pub const DIEPMSK_TOM_POS: u32 = 3;
+pub const DIEPMSK_TOM_WIDTH: u32 = 1;
+pub const DIEPMSK_TOM_MASK: u32 = 0x1 << 3;
+
+pub const DIEPMSK_EPDM_POS: u32 = 1;
+pub const DIEPMSK_EPDM_WIDTH: u32 = 1;
+pub const DIEPMSK_EPDM_MASK: u32 = 0x1 << 1;
+
+pub const DIEPMSK_XFRCM_POS: u32 = 0;
+pub const DIEPMSK_XFRCM_WIDTH: u32 = 1;
+pub const DIEPMSK_XFRCM_MASK: u32 = 0x1 << 0;
+
+// DOEPMSK register fields
+pub const DOEPMSK_OTEPDM_POS: u32 = 4;
+pub const DOEPMSK_OTEPDM_WIDTH: u32 = 1;
+pub const DOEPMSK_OTEPDM_MASK: u32 = 0x1 << 4;
+
+pub const DOEPMSK_STUPM_POS: u32 = 3;
+pub const DOEPMSK_STUPM_WIDTH: u32 = 1;
+pub const DOEPMSK_STUPM_MASK: u32 = 0x1 << 3;
+
+pub const DOEPMSK_EPDM_POS: u32 = 1;
+pub const DOEPMSK_EPDM_WIDTH: u32 = 1;
+pub const DOEPMSK_EPDM_MASK: u32 = 0x1 << 1;
+
+pub const DOEPMSK_XFRCM_POS: u32 = 0;
+pub const DOEPMSK_XFRCM_WIDTH: u32 = 1;
+pub const DOEPMSK_XFRCM_MASK: u32 = 0x1 << 0;
+
+// DAINT register fields
+pub const DAINT_OEPINT_POS: u32 = 16;
+pub const DAINT_OEPINT_WIDTH: u32 = 16;
+pub const DAINT_OEPINT_MASK: u32 = 0xFFFF << 16;
+
+pub const DAINT_IEPINT_POS: u32 = 0;
+pub const DAINT_IEPINT_WIDTH: u32 = 16;
+pub const DAINT_IEPINT_MASK: u32 = 0xFFFF << 0;
+
+// DIEPCTL register fields
+pub const DIEPCTL_EPENA_POS: u32 = 31;
+pub const DIEPCTL_EPENA_WIDTH: u32 = 1;
+pub const DIEPCTL_EPENA_MASK: u32 = 0x1 << 31;
+
+pub const DIEPCTL_EPDIS_POS: u32 = 30;
+pub const DIEPCTL_EPDIS_WIDTH: u32 = 1;
+pub const DIEPCTL_EPDIS_MASK: u32 = 0x1 << 30;
+
+pub const DIEPCTL_SODDFRM_POS: u32 = 29;
+pub const DIEPCTL_SODDFRM_WIDTH: u32 = 1;
+pub const DIEPCTL_SODDFRM_MASK: u32 = 0x1 << 29;
+
+pub const DIEPCTL_SD0PID_POS: u32 = 28;
+pub const DIEPCTL_SD0PID_WIDTH: u32 = 1;
+pub const DIEPCTL_SD0PID_MASK: u32 = 0x1 << 28;
+
+pub const DIEPCTL_SNAK_POS: u32 = 27;
+pub const DIEPCTL_SNAK_WIDTH: u32 = 1;
+pub const DIEPCTL_SNAK_MASK: u32 = 0x1 << 27;
+
+pub const DIEPCTL_CNAK_POS: u32 = 26;
+pub const DIEPCTL_CNAK_WIDTH: u32 = 1;
+pub const DIEPCTL_CNAK_MASK: u32 = 0x1 << 26;
+
+pub const DIEPCTL_TXFNUM_POS: u32 = 22;
+pub const DIEPCTL_TXFNUM_WIDTH: u32 = 4;
+pub const DIEPCTL_TXFNUM_MASK: u32 = 0xF << 22;
+
+pub const DIEPCTL_STALL_POS: u32 = 21;
+pub const DIEPCTL_STALL_WIDTH: u32 = 1;
+pub const DIEPCTL_STALL_MASK: u32 = 0x1 << 21;
+
+pub const DIEPCTL_EPTYP_POS: u32 = 18;
+pub const DIEPCTL_EPTYP_WIDTH: u32 = 2;
+pub const DIEPCTL_EPTYP_MASK: u32 = 0x3 << 18;
+// EPTYP enumerated values
+pub const DIEPCTL_EPTYP_CONTROL: u32 = 0 << 18;
+pub const DIEPCTL_EPTYP_ISOCHRONOUS: u32 = 1 << 18;
+pub const DIEPCTL_EPTYP_BULK: u32 = 2 << 18;
+pub const DIEPCTL_EPTYP_INTERRUPT: u32 = 3 << 18;
+
+pub const DIEPCTL_NAKSTS_POS: u32 = 17;
+pub const DIEPCTL_NAKSTS_WIDTH: u32 = 1;
+pub const DIEPCTL_NAKSTS_MASK: u32 = 0x1 << 17;
+
+pub const DIEPCTL_EONUM_DPID_POS: u32 = 16;
+pub const DIEPCTL_EONUM_DPID_WIDTH: u32 = 1;
+pub const DIEPCTL_EONUM_DPID_MASK: u32 = 0x1 << 16;
+
+pub const DIEPCTL_USBAEP_POS: u32 = 15;
+pub const DIEPCTL_USBAEP_WIDTH: u32 = 1;
+pub const DIEPCTL_USBAEP_MASK: u32 = 0x1 << 15;
+
+pub const DIEPCTL_MPSIZ_POS: u32 = 0;
+pub const DIEPCTL_MPSIZ_WIDTH: u32 = 11;
+pub const DIEPCTL_MPSIZ_MASK: u32 = 0x7FF << 0;
+// MPSIZ of endpoint 0, in DIEPCTL0 and DOEPCTL0
+pub const DEPCTL0_MPSIZ_64: u32 = 0;
+pub const DEPCTL0_MPSIZ_32: u32 = 1;
+pub const DEPCTL0_MPSIZ_16: u32 = 2;
+pub const DEPCTL0_MPSIZ_8: u32 = 3;
+
+// DOEPCTL register fields
+pub const DOEPCTL_EPENA_POS: u32 = 31;
+pub const DOEPCTL_EPENA_WIDTH: u32 = 1;
+pub const DOEPCTL_EPENA_MASK: u32 = 0x1 << 31;
+
+pub const DOEPCTL_EPDIS_POS: u32 = 30;
+pub const DOEPCTL_EPDIS_WIDTH: u32 = 1;
+pub const DOEPCTL_EPDIS_MASK: u32 = 0x1 << 30;
+
+pub const DOEPCTL_SODDFRM_POS: u32 = 29;
+pub const DOEPCTL_SODDFRM_WIDTH: u32 = 1;
+pub const DOEPCTL_SODDFRM_MASK: u32 = 0x1 << 29;
+
+pub const DOEPCTL_SD0PID_POS: u32 = 28;
+pub const DOEPCTL_SD0PID_WIDTH: u32 = 1;
+pub const DOEPCTL_SD0PID_MASK: u32 = 0x1 << 28;
+
+pub const DOEPCTL_SNAK_POS: u32 = 27;
+pub const DOEPCTL_SNAK_WIDTH: u32 = 1;
+pub const DOEPCTL_SNAK_MASK: u32 = 0x1 << 27;
+
+pub const DOEPCTL_CNAK_POS: u32 = 26;
+pub const DOEPCTL_CNAK_WIDTH: u32 = 1;
+pub const DOEPCTL_CNAK_MASK: u32 = 0x1 << 26;
+
+pub const DOEPCTL_STALL_POS: u32 = 21;
+pub const DOEPCTL_STALL_WIDTH: u32 = 1;
+pub const DOEPCTL_STALL_MASK: u32 = 0x1 << 21;
+
+pub const DOEPCTL_SNPM_POS: u32 = 20;
+pub const DOEPCTL_SNPM_WIDTH: u32 = 1;
+pub const DOEPCTL_SNPM_MASK: u32 = 0x1 << 20;
+
+pub const DOEPCTL_EPTYP_POS: u32 = 18;
+pub const DOEPCTL_EPTYP_WIDTH: u32 = 2;
+pub const DOEPCTL_EPTYP_MASK: u32 = 0x3 << 18;
+
+pub const DOEPCTL_NAKSTS_POS: u32 = 17;
+pub const DOEPCTL_NAKSTS_WIDTH: u32 = 1;
+pub const DOEPCTL_NAKSTS_MASK: u32 = 0x1 << 17;
+
+pub const DOEPCTL_EONUM_DPID_POS: u32 = 16;
+pub const DOEPCTL_EONUM_DPID_WIDTH: u32 = 1;
+pub const DOEPCTL_EONUM_DPID_MASK: u32 = 0x1 << 16;
+
+pub const DOEPCTL_USBAEP_POS: u32 = 15;
+pub const DOEPCTL_USBAEP_WIDTH: u32 = 1;
+pub const DOEPCTL_USBAEP_MASK: u32 = 0x1 << 15;
+
+pub const DOEPCTL_MPSIZ_POS: u32 = 0;
+pub const DOEPCTL_MPSIZ_WIDTH: u32 = 11;
+pub const DOEPCTL_MPSIZ_MASK: u32 = 0x7FF << 0;
+
+// DIEPINT register fields
+pub const DIEPINT_TXFE_POS: u32 = 7;
+pub const DIEPINT_TXFE_WIDTH: u32 = 1;
+pub const DIEPINT_TXFE_MASK: u32 = 0x1 << 7;
+
+pub const DIEPINT_INEPNE_POS: u32 = 6;
+pub const DIEPINT_INEPNE_WIDTH: u32 = 1;
+pub const DIEPINT_INEPNE_MASK: u32 = 0x1 << 6;
+
+pub const DIEPINT_ITTXFE_POS: u32 = 4;
+pub const DIEPINT_ITTXFE_WIDTH: u32 = 1;
+pub const DIEPINT_ITTXFE_MASK: u32 = 0x1 << 4;
+
+pub const DIEPINT_TOC_POS: u32 = 3;
+pub const DIEPINT_TOC_WIDTH: u32 = 1;
+pub const DIEPINT_TOC_MASK: u32 = 0x1 << 3;
+
+pub const DIEPINT_EPDISD_POS: u32 = 1;
+pub const DIEPINT_EPDISD_WIDTH: u32 = 1;
+pub const DIEPINT_EPDISD_MASK: u32 = 0x1 << 1;
+
+pub const DIEPINT_XFRC_POS: u32 = 0;
+pub const DIEPINT_XFRC_WIDTH: u32 = 1;
+pub const DIEPINT_XFRC_MASK: u32 = 0x1 << 0;
+
+// DOEPINT register fields
+pub const DOEPINT_B2BSTUP_POS: u32 = 6;
+pub const DOEPINT_B2BSTUP_WIDTH: u32 = 1;
+pub const DOEPINT_B2BSTUP_MASK: u32 = 0x1 << 6;
+
+pub const DOEPINT_OTEPDIS_POS: u32 = 4;
+pub const DOEPINT_OTEPDIS_WIDTH: u32 = 1;
+pub const DOEPINT_OTEPDIS_MASK: u32 = 0x1 << 4;
+
+pub const DOEPINT_STUP_POS: u32 = 3;
+pub const DOEPINT_STUP_WIDTH: u32 = 1;
+pub const DOEPINT_STUP_MASK: u32 = 0x1 << 3;
+
+pub const DOEPINT_EPDISD_POS: u32 = 1;
+pub const DOEPINT_EPDISD_WIDTH: u32 = 1;
+pub const DOEPINT_EPDISD_MASK: u32 = 0x1 << 1;
+
+pub const DOEPINT_XFRC_POS: u32 = 0;
+pub const DOEPINT_XFRC_WIDTH: u32 = 1;
+pub const DOEPINT_XFRC_MASK: u32 = 0x1 << 0;
+
+// DIEPTSIZ register fields
+pub const DIEPTSIZ_MCNT_POS: u32 = 29;
+pub const DIEPTSIZ_MCNT_WIDTH: u32 = 2;
+pub const DIEPTSIZ_MCNT_MASK: u32 = 0x3 << 29;
+
+pub const DIEPTSIZ_PKTCNT_POS: u32 = 19;
+pub const DIEPTSIZ_PKTCNT_WIDTH: u32 = 10;
+pub const DIEPTSIZ_PKTCNT_MASK: u32 = 0x3FF << 19;
+
+pub const DIEPTSIZ_XFRSIZ_POS: u32 = 0;
+pub const DIEPTSIZ_XFRSIZ_WIDTH: u32 = 19;
+pub const DIEPTSIZ_XFRSIZ_MASK: u32 = 0x7FFFF << 0;
+
+// DOEPTSIZ register fields
+pub const DOEPTSIZ_STUPCNT_POS: u32 = 29;
+pub const DOEPTSIZ_STUPCNT_WIDTH: u32 = 2;
+pub const DOEPTSIZ_STUPCNT_MASK: u32 = 0x3 << 29;
+
+pub const DOEPTSIZ_PKTCNT_POS: u32 = 19;
+pub const DOEPTSIZ_PKTCNT_WIDTH: u32 = 10;
+pub const DOEPTSIZ_PKTCNT_MASK: u32 = 0x3FF << 19;
+
+pub const DOEPTSIZ_XFRSIZ_POS: u32 = 0;
+pub const DOEPTSIZ_XFRSIZ_WIDTH: u32 = 19;
+pub const DOEPTSIZ_XFRSIZ_MASK: u32 = 0x7FFFF << 0;
+
+// DTXFSTS register fields
+pub const DTXFSTS_INEPTFSAV_POS: u32 = 0;
+pub const DTXFSTS_INEPTFSAV_WIDTH: u32 = 16;
+pub const DTXFSTS_INEPTFSAV_MASK: u32 = 0xFFFF << 0;
+
+// PCGCCTL register fields
+pub const PCGCCTL_PHYSUSP_POS: u32 = 4;
+pub const PCGCCTL_PHYSUSP_WIDTH: u32 = 1;
+pub const PCGCCTL_PHYSUSP_MASK: u32 = 0x1 << 4;
+
+pub const PCGCCTL_GATEHCLK_POS: u32 = 1;
+pub const PCGCCTL_GATEHCLK_WIDTH: u32 = 1;
+pub const PCGCCTL_GATEHCLK_MASK: u32 = 0x1 << 1;
+
+pub const PCGCCTL_STPPCLK_POS: u32 = 0;
+pub const PCGCCTL_STPPCLK_WIDTH: u32 = 1;
+pub const PCGCCTL_STPPCLK_MASK: u32 = 0x1 << 0;
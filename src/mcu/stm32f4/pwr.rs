@@ -0,0 +1,77 @@
+// PWR (Power Controller) peripheral definitions
+// Shared by the STM32F4 family, generated from the STM32F407 SVD file
+
+// PWR Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr: u32,  // RW: power control register
+    pub csr: u32, // RW: power control/status register
+}
+
+// PWR Register Field Definitions
+
+// CR register fields
+pub const CR_VOS_POS: u32 = 14;
+pub const CR_VOS_WIDTH: u32 = 1;
+pub const CR_VOS_MASK: u32 = 0x1 << 14;
+
+pub const CR_FPDS_POS: u32 = 9;
+pub const CR_FPDS_WIDTH: u32 = 1;
+pub const CR_FPDS_MASK: u32 = 0x1 << 9;
+
+pub const CR_DBP_POS: u32 = 8;
+pub const CR_DBP_WIDTH: u32 = 1;
+pub const CR_DBP_MASK: u32 = 0x1 << 8;
+
+pub const CR_PLS_POS: u32 = 5;
+pub const CR_PLS_WIDTH: u32 = 3;
+pub const CR_PLS_MASK: u32 = 0x7 << 5;
+
+pub const CR_PVDE_POS: u32 = 4;
+pub const CR_PVDE_WIDTH: u32 = 1;
+pub const CR_PVDE_MASK: u32 = 0x1 << 4;
+
+pub const CR_CSBF_POS: u32 = 3;
+pub const CR_CSBF_WIDTH: u32 = 1;
+pub const CR_CSBF_MASK: u32 = 0x1 << 3;
+
+pub const CR_CWUF_POS: u32 = 2;
+pub const CR_CWUF_WIDTH: u32 = 1;
+pub const CR_CWUF_MASK: u32 = 0x1 << 2;
+
+pub const CR_PDDS_POS: u32 = 1;
+pub const CR_PDDS_WIDTH: u32 = 1;
+pub const CR_PDDS_MASK: u32 = 0x1 << 1;
+
+pub const CR_LPDS_POS: u32 = 0;
+pub const CR_LPDS_WIDTH: u32 = 1;
+pub const CR_LPDS_MASK: u32 = 0x1 << 0;
+
+// CSR register fields
+pub const CSR_VOSRDY_POS: u32 = 14;
+pub const CSR_VOSRDY_WIDTH: u32 = 1;
+pub const CSR_VOSRDY_MASK: u32 = 0x1 << 14;
+
+pub const CSR_BRE_POS: u32 = 9;
+pub const CSR_BRE_WIDTH: u32 = 1;
+pub const CSR_BRE_MASK: u32 = 0x1 << 9;
+
+pub const CSR_EWUP_POS: u32 = 8;
+pub const CSR_EWUP_WIDTH: u32 = 1;
+pub const CSR_EWUP_MASK: u32 = 0x1 << 8;
+
+pub const CSR_BRR_POS: u32 = 3;
+pub const CSR_BRR_WIDTH: u32 = 1;
+pub const CSR_BRR_MASK: u32 = 0x1 << 3;
+
+pub const CSR_PVDO_POS: u32 = 2;
+pub const CSR_PVDO_WIDTH: u32 = 1;
+pub const CSR_PVDO_MASK: u32 = 0x1 << 2;
+
+pub const CSR_SBF_POS: u32 = 1;
+pub const CSR_SBF_WIDTH: u32 = 1;
+pub const CSR_SBF_MASK: u32 = 0x1 << 1;
+
+pub const CSR_WUF_POS: u32 = 0;
+pub const CSR_WUF_WIDTH: u32 = 1;
+pub const CSR_WUF_MASK: u32 = 0x1 << 0;
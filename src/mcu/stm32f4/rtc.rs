@@ -0,0 +1,109 @@
+// RTC (Real-Time Clock) peripheral definitions
+// Shared by the STM32F4 family, generated from the STM32F407 SVD file
+
+// RTC Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub tr: u32,       // RW: time register
+    pub dr: u32,       // RW: date register
+    pub cr: u32,       // RW: control register
+    pub isr: u32,      // RW: initialization and status register
+    pub prer: u32,     // RW: prescaler register
+    pub wutr: u32,     // RW: wakeup timer register
+    pub calibr: u32,   // RW: calibration register
+    pub alrmar: u32,   // RW: alarm A register
+    pub alrmbr: u32,   // RW: alarm B register
+    pub wpr: u32,      // WO: write protection register
+    pub ssr: u32,      // RO: sub second register
+    pub shiftr: u32,   // WO: shift control register
+    pub tstr: u32,     // RO: time stamp time register
+    pub tsdr: u32,     // RO: time stamp date register
+    pub tsssr: u32,    // RO: timestamp sub second register
+    pub calr: u32,     // RW: calibration register
+    pub tafcr: u32,    // RW: tamper and alternate function configuration register
+    pub alrmassr: u32, // RW: alarm A sub second register
+    pub alrmbssr: u32, // RW: alarm B sub second register
+    _reserved0: u32,
+    pub bkpr: [u32; 20], // RW: backup registers
+}
+
+// Write protection keys, written in this order to unlock
+pub const WPR_KEY1: u32 = 0xCA;
+pub const WPR_KEY2: u32 = 0x53;
+
+// RTC Register Field Definitions
+
+// CR register fields
+pub const CR_WUTIE_POS: u32 = 14;
+pub const CR_WUTIE_WIDTH: u32 = 1;
+pub const CR_WUTIE_MASK: u32 = 0x1 << 14;
+
+pub const CR_ALRAIE_POS: u32 = 12;
+pub const CR_ALRAIE_WIDTH: u32 = 1;
+pub const CR_ALRAIE_MASK: u32 = 0x1 << 12;
+
+pub const CR_WUTE_POS: u32 = 10;
+pub const CR_WUTE_WIDTH: u32 = 1;
+pub const CR_WUTE_MASK: u32 = 0x1 << 10;
+
+pub const CR_ALRAE_POS: u32 = 8;
+pub const CR_ALRAE_WIDTH: u32 = 1;
+pub const CR_ALRAE_MASK: u32 = 0x1 << 8;
+
+pub const CR_FMT_POS: u32 = 6;
+pub const CR_FMT_WIDTH: u32 = 1;
+pub const CR_FMT_MASK: u32 = 0x1 << 6;
+
+pub const CR_BYPSHAD_POS: u32 = 5;
+pub const CR_BYPSHAD_WIDTH: u32 = 1;
+pub const CR_BYPSHAD_MASK: u32 = 0x1 << 5;
+
+pub const CR_WUCKSEL_POS: u32 = 0;
+pub const CR_WUCKSEL_WIDTH: u32 = 3;
+pub const CR_WUCKSEL_MASK: u32 = 0x7 << 0;
+
+pub const CR_WUCKSEL_RTC_DIV16: u32 = 0 << 0;
+pub const CR_WUCKSEL_SPRE: u32 = 4 << 0;
+
+// ISR register fields
+pub const ISR_WUTF_POS: u32 = 10;
+pub const ISR_WUTF_WIDTH: u32 = 1;
+pub const ISR_WUTF_MASK: u32 = 0x1 << 10;
+
+pub const ISR_ALRAF_POS: u32 = 8;
+pub const ISR_ALRAF_WIDTH: u32 = 1;
+pub const ISR_ALRAF_MASK: u32 = 0x1 << 8;
+
+pub const ISR_INIT_POS: u32 = 7;
+pub const ISR_INIT_WIDTH: u32 = 1;
+pub const ISR_INIT_MASK: u32 = 0x1 << 7;
+
+pub const ISR_INITF_POS: u32 = 6;
+pub const ISR_INITF_WIDTH: u32 = 1;
+pub const ISR_INITF_MASK: u32 = 0x1 << 6;
+
+pub const ISR_RSF_POS: u32 = 5;
+pub const ISR_RSF_WIDTH: u32 = 1;
+pub const ISR_RSF_MASK: u32 = 0x1 << 5;
+
+pub const ISR_INITS_POS: u32 = 4;
+pub const ISR_INITS_WIDTH: u32 = 1;
+pub const ISR_INITS_MASK: u32 = 0x1 << 4;
+
+pub const ISR_WUTWF_POS: u32 = 2;
+pub const ISR_WUTWF_WIDTH: u32 = 1;
+pub const ISR_WUTWF_MASK: u32 = 0x1 << 2;
+
+// PRER register fields
+pub const PRER_PREDIV_A_POS: u32 = 16;
+pub const PRER_PREDIV_A_WIDTH: u32 = 7;
+pub const PRER_PREDIV_A_MASK: u32 = 0x7f << 16;
+
+pub const PRER_PREDIV_S_POS: u32 = 0;
+pub const PRER_PREDIV_S_WIDTH: u32 = 15;
+pub const PRER_PREDIV_S_MASK: u32 = 0x7fff << 0;
+
+// WUTR register fields
+pub const WUTR_WUT_POS: u32 = 0;
+pub const WUTR_WUT_WIDTH: u32 = 16;
+pub const WUTR_WUT_MASK: u32 = 0xffff << 0;
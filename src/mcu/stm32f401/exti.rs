@@ -0,0 +1,20 @@
+// EXTI and SYSCFG peripheral definitions
+// Generated from STM32F401 SVD file
+
+use super::{EXTI_BASEADDR, PeripheralAccess, SYSCFG_BASEADDR};
+
+pub use super::super::stm32f4::exti::*;
+
+// EXTI and SYSCFG peripheral instances
+pub struct EXTI;
+pub struct SYSCFG;
+
+impl PeripheralAccess for EXTI {
+    const BASE_ADDRESS: u32 = EXTI_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SYSCFG {
+    const BASE_ADDRESS: u32 = SYSCFG_BASEADDR;
+    type RegisterBlock = SyscfgRegisterBlock;
+}
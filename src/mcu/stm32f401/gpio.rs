@@ -1,2 +1,67 @@
-// GPIO module for STM32F401
-// TODO: Implement GPIO register definitions and functions
+// GPIO (General Purpose Input/Output) peripheral definitions
+// Generated from STM32F401 SVD file
+
+use super::{
+    GPIOA_BASEADDR, GPIOB_BASEADDR, GPIOC_BASEADDR, GPIOD_BASEADDR, GPIOE_BASEADDR, GPIOH_BASEADDR,
+    PeripheralAccess,
+};
+
+pub use super::super::stm32f4::gpio::*;
+
+// GPIO peripheral instances
+pub struct GPIOA;
+pub struct GPIOB;
+pub struct GPIOC;
+pub struct GPIOD;
+pub struct GPIOE;
+pub struct GPIOH;
+
+impl PeripheralAccess for GPIOA {
+    const BASE_ADDRESS: u32 = GPIOA_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOB {
+    const BASE_ADDRESS: u32 = GPIOB_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOC {
+    const BASE_ADDRESS: u32 = GPIOC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOD {
+    const BASE_ADDRESS: u32 = GPIOD_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOE {
+    const BASE_ADDRESS: u32 = GPIOE_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for GPIOH {
+    const BASE_ADDRESS: u32 = GPIOH_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+/// Base address of GPIO port `port` (0=GPIOA), or `None` if the part has
+/// no such port.
+pub const fn port_base(port: u8) -> Option<u32> {
+    match port {
+        0 => Some(GPIOA_BASEADDR),
+        1 => Some(GPIOB_BASEADDR),
+        2 => Some(GPIOC_BASEADDR),
+        3 => Some(GPIOD_BASEADDR),
+        4 => Some(GPIOE_BASEADDR),
+        7 => Some(GPIOH_BASEADDR),
+        _ => None,
+    }
+}
+
+/// Look up the alternate function number that routes `signal` to `port`/`pin`.
+/// Returns `None` if the signal is not available on that pin.
+pub fn find_alternate_function(signal: AfSignal, port: u8, pin: u8) -> Option<u8> {
+    find_in_map(AF_MAP_F4X1, signal, port, pin)
+}
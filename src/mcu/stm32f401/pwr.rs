@@ -0,0 +1,14 @@
+// PWR (Power Controller) peripheral definitions
+// Generated from STM32F401 SVD file
+
+use super::{PWR_BASEADDR, PeripheralAccess};
+
+pub use super::super::stm32f4::pwr::*;
+
+// PWR peripheral instance
+pub struct PWR;
+
+impl PeripheralAccess for PWR {
+    const BASE_ADDRESS: u32 = PWR_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,26 @@
+// USART peripheral definitions
+// Generated from STM32F401 SVD file
+
+use super::{PeripheralAccess, USART1_BASEADDR, USART2_BASEADDR, USART6_BASEADDR};
+
+pub use super::super::stm32f4::usart::*;
+
+// USART peripheral instances
+pub struct USART1;
+pub struct USART2;
+pub struct USART6;
+
+impl PeripheralAccess for USART1 {
+    const BASE_ADDRESS: u32 = USART1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for USART2 {
+    const BASE_ADDRESS: u32 = USART2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for USART6 {
+    const BASE_ADDRESS: u32 = USART6_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,17 @@
+// FLASH (embedded flash interface) peripheral definitions
+// Generated from STM32F407 SVD file
+
+use super::{FLASH_R_BASEADDR, PeripheralAccess};
+
+pub use super::super::stm32f4::flash::*;
+
+// Main memory of the part
+pub const FLASH_SIZE: u32 = 1024 * 1024;
+
+// FLASH peripheral instance
+pub struct FLASH;
+
+impl PeripheralAccess for FLASH {
+    const BASE_ADDRESS: u32 = FLASH_R_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -175,6 +175,12 @@ pub enum IRQn {
     FPU = 81,
 }
 
+/// HSI RC oscillator frequency, the system clock after reset
+pub const HSI_HZ: u32 = 16_000_000;
+
+/// Nominal LSI RC oscillator frequency; parts range from 17 to 47 kHz
+pub const LSI_HZ: u32 = 32_000;
+
 pub trait PeripheralAccess {
     const BASE_ADDRESS: u32;
     type RegisterBlock;
@@ -189,9 +195,14 @@ pub trait PeripheralAccess {
 }
 
 pub mod adc;
+pub mod exti;
+pub mod flash;
 pub mod gpio;
 pub mod i2c;
+pub mod otg_fs;
+pub mod pwr;
 pub mod rcc;
+pub mod rtc;
 pub mod spi;
 pub mod timer;
 pub mod usart;
@@ -0,0 +1,26 @@
+// I2C peripheral definitions
+// Generated from STM32F411 SVD file
+
+use super::{I2C1_BASEADDR, I2C2_BASEADDR, I2C3_BASEADDR, PeripheralAccess};
+
+pub use super::super::stm32f4::i2c::*;
+
+// I2C peripheral instances
+pub struct I2C1;
+pub struct I2C2;
+pub struct I2C3;
+
+impl PeripheralAccess for I2C1 {
+    const BASE_ADDRESS: u32 = I2C1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for I2C2 {
+    const BASE_ADDRESS: u32 = I2C2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for I2C3 {
+    const BASE_ADDRESS: u32 = I2C3_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -1,4 +1,8 @@
-// STM32F411 MCU-specific definitions
+//! # STM32F411 MCU Implementation
+//!
+//! Base addresses, interrupt numbers and peripheral instances of the
+//! STM32F411xC/E. Register blocks shared with the rest of the STM32F4 family
+//! live in `mcu::stm32f4` and are re-exported by the peripheral modules.
 
 // Base addresses
 pub const TIM2_BASEADDR: u32 = 0x40000000;
@@ -41,6 +45,7 @@ pub const FLASH_R_BASEADDR: u32 = 0x40023C00;
 pub const DMA1_BASEADDR: u32 = 0x40026000;
 pub const DMA2_BASEADDR: u32 = 0x40026400;
 pub const USB_OTG_FS_BASEADDR: u32 = 0x50000000;
+pub const DBGMCU_BASEADDR: u32 = 0xE0042000;
 
 /*
  * IRQ(Interrupt Request) Numbers of STM32F411xE MCU
@@ -136,6 +141,12 @@ pub enum IRQn {
     SPI5 = 85,
 }
 
+/// HSI RC oscillator frequency, the system clock after reset
+pub const HSI_HZ: u32 = 16_000_000;
+
+/// Nominal LSI RC oscillator frequency; parts range from 17 to 47 kHz
+pub const LSI_HZ: u32 = 32_000;
+
 pub trait PeripheralAccess {
     const BASE_ADDRESS: u32;
     type RegisterBlock;
@@ -149,7 +160,13 @@ pub trait PeripheralAccess {
     }
 }
 
-// Include peripheral modules
+pub mod exti;
+pub mod flash;
 pub mod gpio;
+pub mod i2c;
+pub mod otg_fs;
+pub mod pwr;
 pub mod rcc;
-// Add other peripherals as needed
+pub mod rtc;
+pub mod spi;
+pub mod usart;
@@ -0,0 +1,15 @@
+// OTG_FS (USB on-the-go full-speed) peripheral definitions
+// Generated from STM32F411 SVD file
+
+use super::{PeripheralAccess, USB_OTG_FS_BASEADDR};
+
+pub use super::super::stm32f4::otg_fs::*;
+
+// OTG_FS peripheral instance
+#[allow(non_camel_case_types)]
+pub struct USB_OTG_FS;
+
+impl PeripheralAccess for USB_OTG_FS {
+    const BASE_ADDRESS: u32 = USB_OTG_FS_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
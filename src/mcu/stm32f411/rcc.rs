@@ -1,2 +1,11 @@
-// RCC (Reset and Clock Control) module for STM32F411
-// TODO: Implement RCC register definitions and functions
+// RCC (Reset and Clock Control) peripheral definitions
+// Generated from STM32F411 SVD file
+
+use super::{PeripheralAccess, RCC_BASEADDR};
+
+pub use super::super::stm32f4::rcc::*;
+
+impl PeripheralAccess for RegisterBlock {
+    const BASE_ADDRESS: u32 = RCC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
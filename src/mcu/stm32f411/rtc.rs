@@ -0,0 +1,14 @@
+// RTC (Real-Time Clock) peripheral definitions
+// Generated from STM32F411 SVD file
+
+use super::{PeripheralAccess, RTC_BASEADDR};
+
+pub use super::super::stm32f4::rtc::*;
+
+// RTC peripheral instance
+pub struct RTC;
+
+impl PeripheralAccess for RTC {
+    const BASE_ADDRESS: u32 = RTC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,40 @@
+// SPI peripheral definitions
+// Generated from STM32F411 SVD file
+
+use super::{
+    PeripheralAccess, SPI1_BASEADDR, SPI2_BASEADDR, SPI3_BASEADDR, SPI4_BASEADDR, SPI5_BASEADDR,
+};
+
+pub use super::super::stm32f4::spi::*;
+
+// SPI peripheral instances
+pub struct SPI1;
+pub struct SPI2;
+pub struct SPI3;
+pub struct SPI4;
+pub struct SPI5;
+
+impl PeripheralAccess for SPI1 {
+    const BASE_ADDRESS: u32 = SPI1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SPI2 {
+    const BASE_ADDRESS: u32 = SPI2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SPI3 {
+    const BASE_ADDRESS: u32 = SPI3_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SPI4 {
+    const BASE_ADDRESS: u32 = SPI4_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SPI5 {
+    const BASE_ADDRESS: u32 = SPI5_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,40 @@
+// EXTI (Extended Interrupt and Event Controller) peripheral definitions
+// Generated from STM32G030 SVD file
+//
+// Rising and falling edges latch in separate pending registers, and the
+// port of lines 0-15 is selected in EXTI_EXTICR rather than SYSCFG.
+
+use super::{EXTI_BASEADDR, PeripheralAccess};
+
+// EXTI Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub rtsr1: u32,  // RW: Rising Trigger selection register
+    pub ftsr1: u32,  // RW: Falling Trigger selection register
+    pub swier1: u32, // RW: Software interrupt event register
+    pub rpr1: u32,   // RW: Rising pending register (write 1 to clear)
+    pub fpr1: u32,   // RW: Falling pending register (write 1 to clear)
+    _reserved0: [u32; 19],
+    pub exticr: [u32; 4], // RW: External interrupt selection registers 1-4
+    _reserved1: [u32; 4],
+    pub imr1: u32, // RW: CPU wakeup with interrupt mask register
+    pub emr1: u32, // RW: CPU wakeup with event mask register
+}
+
+// EXTI peripheral instance
+pub struct EXTI;
+
+impl PeripheralAccess for EXTI {
+    const BASE_ADDRESS: u32 = EXTI_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+/// Number of configurable (edge-triggered) EXTI lines
+pub const EXTI_LINES: u8 = 16;
+
+/// Direct EXTI line of the RTC alarm and wakeup timer
+pub const EXTI_LINE_RTC: u8 = 19;
+
+// EXTICR register fields, eight bits per line selecting the port
+pub const EXTICR_EXTI_WIDTH: u32 = 8;
+pub const EXTICR_EXTI_MASK: u32 = 0xFF;
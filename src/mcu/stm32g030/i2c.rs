@@ -0,0 +1,354 @@
+// I2C peripheral definitions
+// Generated from STM32G030 SVD file
+
+use super::{I2C1_BASEADDR, I2C2_BASEADDR, PeripheralAccess};
+
+// I2C Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr1: u32,      // RW: Control register 1
+    pub cr2: u32,      // RW: Control register 2
+    pub oar1: u32,     // RW: Own address register 1
+    pub oar2: u32,     // RW: Own address register 2
+    pub timingr: u32,  // RW: Timing register
+    pub timeoutr: u32, // RW: Timeout register
+    pub isr: u32,      // RW: Interrupt and status register
+    pub icr: u32,      // WO: Interrupt clear register
+    pub pecr: u32,     // RO: PEC register
+    pub rxdr: u32,     // RO: Receive data register
+    pub txdr: u32,     // RW: Transmit data register
+}
+
+// I2C Register Field Definitions
+
+// CR1 register fields
+pub const CR1_PECEN_POS: u32 = 23;
+pub const CR1_PECEN_WIDTH: u32 = 1;
+pub const CR1_PECEN_MASK: u32 = 0x1 << 23;
+
+pub const CR1_ALERTEN_POS: u32 = 22;
+pub const CR1_ALERTEN_WIDTH: u32 = 1;
+pub const CR1_ALERTEN_MASK: u32 = 0x1 << 22;
+
+pub const CR1_SMBDEN_POS: u32 = 21;
+pub const CR1_SMBDEN_WIDTH: u32 = 1;
+pub const CR1_SMBDEN_MASK: u32 = 0x1 << 21;
+
+pub const CR1_SMBHEN_POS: u32 = 20;
+pub const CR1_SMBHEN_WIDTH: u32 = 1;
+pub const CR1_SMBHEN_MASK: u32 = 0x1 << 20;
+
+pub const CR1_GCEN_POS: u32 = 19;
+pub const CR1_GCEN_WIDTH: u32 = 1;
+pub const CR1_GCEN_MASK: u32 = 0x1 << 19;
+
+pub const CR1_WUPEN_POS: u32 = 18;
+pub const CR1_WUPEN_WIDTH: u32 = 1;
+pub const CR1_WUPEN_MASK: u32 = 0x1 << 18;
+
+pub const CR1_NOSTRETCH_POS: u32 = 17;
+pub const CR1_NOSTRETCH_WIDTH: u32 = 1;
+pub const CR1_NOSTRETCH_MASK: u32 = 0x1 << 17;
+
+pub const CR1_SBC_POS: u32 = 16;
+pub const CR1_SBC_WIDTH: u32 = 1;
+pub const CR1_SBC_MASK: u32 = 0x1 << 16;
+
+pub const CR1_RXDMAEN_POS: u32 = 15;
+pub const CR1_RXDMAEN_WIDTH: u32 = 1;
+pub const CR1_RXDMAEN_MASK: u32 = 0x1 << 15;
+
+pub const CR1_TXDMAEN_POS: u32 = 14;
+pub const CR1_TXDMAEN_WIDTH: u32 = 1;
+pub const CR1_TXDMAEN_MASK: u32 = 0x1 << 14;
+
+pub const CR1_ANFOFF_POS: u32 = 12;
+pub const CR1_ANFOFF_WIDTH: u32 = 1;
+pub const CR1_ANFOFF_MASK: u32 = 0x1 << 12;
+
+pub const CR1_DNF_POS: u32 = 8;
+pub const CR1_DNF_WIDTH: u32 = 4;
+pub const CR1_DNF_MASK: u32 = 0xF << 8;
+
+pub const CR1_ERRIE_POS: u32 = 7;
+pub const CR1_ERRIE_WIDTH: u32 = 1;
+pub const CR1_ERRIE_MASK: u32 = 0x1 << 7;
+
+pub const CR1_TCIE_POS: u32 = 6;
+pub const CR1_TCIE_WIDTH: u32 = 1;
+pub const CR1_TCIE_MASK: u32 = 0x1 << 6;
+
+pub const CR1_STOPIE_POS: u32 = 5;
+pub const CR1_STOPIE_WIDTH: u32 = 1;
+pub const CR1_STOPIE_MASK: u32 = 0x1 << 5;
+
+pub const CR1_NACKIE_POS: u32 = 4;
+pub const CR1_NACKIE_WIDTH: u32 = 1;
+pub const CR1_NACKIE_MASK: u32 = 0x1 << 4;
+
+pub const CR1_ADDRIE_POS: u32 = 3;
+pub const CR1_ADDRIE_WIDTH: u32 = 1;
+pub const CR1_ADDRIE_MASK: u32 = 0x1 << 3;
+
+pub const CR1_RXIE_POS: u32 = 2;
+pub const CR1_RXIE_WIDTH: u32 = 1;
+pub const CR1_RXIE_MASK: u32 = 0x1 << 2;
+
+pub const CR1_TXIE_POS: u32 = 1;
+pub const CR1_TXIE_WIDTH: u32 = 1;
+pub const CR1_TXIE_MASK: u32 = 0x1 << 1;
+
+pub const CR1_PE_POS: u32 = 0;
+pub const CR1_PE_WIDTH: u32 = 1;
+pub const CR1_PE_MASK: u32 = 0x1 << 0;
+
+// CR2 register fields
+pub const CR2_PECBYTE_POS: u32 = 26;
+pub const CR2_PECBYTE_WIDTH: u32 = 1;
+pub const CR2_PECBYTE_MASK: u32 = 0x1 << 26;
+
+pub const CR2_AUTOEND_POS: u32 = 25;
+pub const CR2_AUTOEND_WIDTH: u32 = 1;
+pub const CR2_AUTOEND_MASK: u32 = 0x1 << 25;
+
+pub const CR2_RELOAD_POS: u32 = 24;
+pub const CR2_RELOAD_WIDTH: u32 = 1;
+pub const CR2_RELOAD_MASK: u32 = 0x1 << 24;
+
+pub const CR2_NBYTES_POS: u32 = 16;
+pub const CR2_NBYTES_WIDTH: u32 = 8;
+pub const CR2_NBYTES_MASK: u32 = 0xFF << 16;
+
+pub const CR2_NACK_POS: u32 = 15;
+pub const CR2_NACK_WIDTH: u32 = 1;
+pub const CR2_NACK_MASK: u32 = 0x1 << 15;
+
+pub const CR2_STOP_POS: u32 = 14;
+pub const CR2_STOP_WIDTH: u32 = 1;
+pub const CR2_STOP_MASK: u32 = 0x1 << 14;
+
+pub const CR2_START_POS: u32 = 13;
+pub const CR2_START_WIDTH: u32 = 1;
+pub const CR2_START_MASK: u32 = 0x1 << 13;
+
+pub const CR2_HEAD10R_POS: u32 = 12;
+pub const CR2_HEAD10R_WIDTH: u32 = 1;
+pub const CR2_HEAD10R_MASK: u32 = 0x1 << 12;
+
+pub const CR2_ADD10_POS: u32 = 11;
+pub const CR2_ADD10_WIDTH: u32 = 1;
+pub const CR2_ADD10_MASK: u32 = 0x1 << 11;
+
+pub const CR2_RD_WRN_POS: u32 = 10;
+pub const CR2_RD_WRN_WIDTH: u32 = 1;
+pub const CR2_RD_WRN_MASK: u32 = 0x1 << 10;
+// RD_WRN enumerated values
+pub const CR2_RD_WRN_WRITE: u32 = 0 << 10;
+pub const CR2_RD_WRN_READ: u32 = 1 << 10;
+
+pub const CR2_SADD_POS: u32 = 0;
+pub const CR2_SADD_WIDTH: u32 = 10;
+pub const CR2_SADD_MASK: u32 = 0x3FF << 0;
+
+// OAR1 register fields
+pub const OAR1_OA1EN_POS: u32 = 15;
+pub const OAR1_OA1EN_WIDTH: u32 = 1;
+pub const OAR1_OA1EN_MASK: u32 = 0x1 << 15;
+
+pub const OAR1_OA1MODE_POS: u32 = 10;
+pub const OAR1_OA1MODE_WIDTH: u32 = 1;
+pub const OAR1_OA1MODE_MASK: u32 = 0x1 << 10;
+
+pub const OAR1_OA1_POS: u32 = 0;
+pub const OAR1_OA1_WIDTH: u32 = 10;
+pub const OAR1_OA1_MASK: u32 = 0x3FF << 0;
+
+// OAR2 register fields
+pub const OAR2_OA2EN_POS: u32 = 15;
+pub const OAR2_OA2EN_WIDTH: u32 = 1;
+pub const OAR2_OA2EN_MASK: u32 = 0x1 << 15;
+
+pub const OAR2_OA2MSK_POS: u32 = 8;
+pub const OAR2_OA2MSK_WIDTH: u32 = 3;
+pub const OAR2_OA2MSK_MASK: u32 = 0x7 << 8;
+
+pub const OAR2_OA2_POS: u32 = 1;
+pub const OAR2_OA2_WIDTH: u32 = 7;
+pub const OAR2_OA2_MASK: u32 = 0x7F << 1;
+
+// TIMINGR register fields
+pub const TIMINGR_PRESC_POS: u32 = 28;
+pub const TIMINGR_PRESC_WIDTH: u32 = 4;
+pub const TIMINGR_PRESC_MASK: u32 = 0xF << 28;
+
+pub const TIMINGR_SCLDEL_POS: u32 = 20;
+pub const TIMINGR_SCLDEL_WIDTH: u32 = 4;
+pub const TIMINGR_SCLDEL_MASK: u32 = 0xF << 20;
+
+pub const TIMINGR_SDADEL_POS: u32 = 16;
+pub const TIMINGR_SDADEL_WIDTH: u32 = 4;
+pub const TIMINGR_SDADEL_MASK: u32 = 0xF << 16;
+
+pub const TIMINGR_SCLH_POS: u32 = 8;
+pub const TIMINGR_SCLH_WIDTH: u32 = 8;
+pub const TIMINGR_SCLH_MASK: u32 = 0xFF << 8;
+
+pub const TIMINGR_SCLL_POS: u32 = 0;
+pub const TIMINGR_SCLL_WIDTH: u32 = 8;
+pub const TIMINGR_SCLL_MASK: u32 = 0xFF << 0;
+
+// TIMEOUTR register fields
+pub const TIMEOUTR_TEXTEN_POS: u32 = 31;
+pub const TIMEOUTR_TEXTEN_WIDTH: u32 = 1;
+pub const TIMEOUTR_TEXTEN_MASK: u32 = 0x1 << 31;
+
+pub const TIMEOUTR_TIMEOUTB_POS: u32 = 16;
+pub const TIMEOUTR_TIMEOUTB_WIDTH: u32 = 12;
+pub const TIMEOUTR_TIMEOUTB_MASK: u32 = 0xFFF << 16;
+
+pub const TIMEOUTR_TIMOUTEN_POS: u32 = 15;
+pub const TIMEOUTR_TIMOUTEN_WIDTH: u32 = 1;
+pub const TIMEOUTR_TIMOUTEN_MASK: u32 = 0x1 << 15;
+
+pub const TIMEOUTR_TIDLE_POS: u32 = 12;
+pub const TIMEOUTR_TIDLE_WIDTH: u32 = 1;
+pub const TIMEOUTR_TIDLE_MASK: u32 = 0x1 << 12;
+
+pub const TIMEOUTR_TIMEOUTA_POS: u32 = 0;
+pub const TIMEOUTR_TIMEOUTA_WIDTH: u32 = 12;
+pub const TIMEOUTR_TIMEOUTA_MASK: u32 = 0xFFF << 0;
+
+// ISR register fields
+pub const ISR_ADDCODE_POS: u32 = 17;
+pub const ISR_ADDCODE_WIDTH: u32 = 7;
+pub const ISR_ADDCODE_MASK: u32 = 0x7F << 17;
+
+pub const ISR_DIR_POS: u32 = 16;
+pub const ISR_DIR_WIDTH: u32 = 1;
+pub const ISR_DIR_MASK: u32 = 0x1 << 16;
+
+pub const ISR_BUSY_POS: u32 = 15;
+pub const ISR_BUSY_WIDTH: u32 = 1;
+pub const ISR_BUSY_MASK: u32 = 0x1 << 15;
+
+pub const ISR_ALERT_POS: u32 = 13;
+pub const ISR_ALERT_WIDTH: u32 = 1;
+pub const ISR_ALERT_MASK: u32 = 0x1 << 13;
+
+pub const ISR_TIMEOUT_POS: u32 = 12;
+pub const ISR_TIMEOUT_WIDTH: u32 = 1;
+pub const ISR_TIMEOUT_MASK: u32 = 0x1 << 12;
+
+pub const ISR_PECERR_POS: u32 = 11;
+pub const ISR_PECERR_WIDTH: u32 = 1;
+pub const ISR_PECERR_MASK: u32 = 0x1 << 11;
+
+pub const ISR_OVR_POS: u32 = 10;
+pub const ISR_OVR_WIDTH: u32 = 1;
+pub const ISR_OVR_MASK: u32 = 0x1 << 10;
+
+pub const ISR_ARLO_POS: u32 = 9;
+pub const ISR_ARLO_WIDTH: u32 = 1;
+pub const ISR_ARLO_MASK: u32 = 0x1 << 9;
+
+pub const ISR_BERR_POS: u32 = 8;
+pub const ISR_BERR_WIDTH: u32 = 1;
+pub const ISR_BERR_MASK: u32 = 0x1 << 8;
+
+pub const ISR_TCR_POS: u32 = 7;
+pub const ISR_TCR_WIDTH: u32 = 1;
+pub const ISR_TCR_MASK: u32 = 0x1 << 7;
+
+pub const ISR_TC_POS: u32 = 6;
+pub const ISR_TC_WIDTH: u32 = 1;
+pub const ISR_TC_MASK: u32 = 0x1 << 6;
+
+pub const ISR_STOPF_POS: u32 = 5;
+pub const ISR_STOPF_WIDTH: u32 = 1;
+pub const ISR_STOPF_MASK: u32 = 0x1 << 5;
+
+pub const ISR_NACKF_POS: u32 = 4;
+pub const ISR_NACKF_WIDTH: u32 = 1;
+pub const ISR_NACKF_MASK: u32 = 0x1 << 4;
+
+pub const ISR_ADDR_POS: u32 = 3;
+pub const ISR_ADDR_WIDTH: u32 = 1;
+pub const ISR_ADDR_MASK: u32 = 0x1 << 3;
+
+pub const ISR_RXNE_POS: u32 = 2;
+pub const ISR_RXNE_WIDTH: u32 = 1;
+pub const ISR_RXNE_MASK: u32 = 0x1 << 2;
+
+pub const ISR_TXIS_POS: u32 = 1;
+pub const ISR_TXIS_WIDTH: u32 = 1;
+pub const ISR_TXIS_MASK: u32 = 0x1 << 1;
+
+pub const ISR_TXE_POS: u32 = 0;
+pub const ISR_TXE_WIDTH: u32 = 1;
+pub const ISR_TXE_MASK: u32 = 0x1 << 0;
+
+// ICR register fields
+pub const ICR_ALERTCF_POS: u32 = 13;
+pub const ICR_ALERTCF_WIDTH: u32 = 1;
+pub const ICR_ALERTCF_MASK: u32 = 0x1 << 13;
+
+pub const ICR_TIMOUTCF_POS: u32 = 12;
+pub const ICR_TIMOUTCF_WIDTH: u32 = 1;
+pub const ICR_TIMOUTCF_MASK: u32 = 0x1 << 12;
+
+pub const ICR_PECCF_POS: u32 = 11;
+pub const ICR_PECCF_WIDTH: u32 = 1;
+pub const ICR_PECCF_MASK: u32 = 0x1 << 11;
+
+pub const ICR_OVRCF_POS: u32 = 10;
+pub const ICR_OVRCF_WIDTH: u32 = 1;
+pub const ICR_OVRCF_MASK: u32 = 0x1 << 10;
+
+pub const ICR_ARLOCF_POS: u32 = 9;
+pub const ICR_ARLOCF_WIDTH: u32 = 1;
+pub const ICR_ARLOCF_MASK: u32 = 0x1 << 9;
+
+pub const ICR_BERRCF_POS: u32 = 8;
+pub const ICR_BERRCF_WIDTH: u32 = 1;
+pub const ICR_BERRCF_MASK: u32 = 0x1 << 8;
+
+pub const ICR_STOPCF_POS: u32 = 5;
+pub const ICR_STOPCF_WIDTH: u32 = 1;
+pub const ICR_STOPCF_MASK: u32 = 0x1 << 5;
+
+pub const ICR_NACKCF_POS: u32 = 4;
+pub const ICR_NACKCF_WIDTH: u32 = 1;
+pub const ICR_NACKCF_MASK: u32 = 0x1 << 4;
+
+pub const ICR_ADDRCF_POS: u32 = 3;
+pub const ICR_ADDRCF_WIDTH: u32 = 1;
+pub const ICR_ADDRCF_MASK: u32 = 0x1 << 3;
+
+// PECR register fields
+pub const PECR_PEC_POS: u32 = 0;
+pub const PECR_PEC_WIDTH: u32 = 8;
+pub const PECR_PEC_MASK: u32 = 0xFF << 0;
+
+// RXDR register fields
+pub const RXDR_RXDATA_POS: u32 = 0;
+pub const RXDR_RXDATA_WIDTH: u32 = 8;
+pub const RXDR_RXDATA_MASK: u32 = 0xFF << 0;
+
+// TXDR register fields
+pub const TXDR_TXDATA_POS: u32 = 0;
+pub const TXDR_TXDATA_WIDTH: u32 = 8;
+pub const TXDR_TXDATA_MASK: u32 = 0xFF << 0;
+
+// I2C peripheral instances
+pub struct I2C1;
+pub struct I2C2;
+
+impl PeripheralAccess for I2C1 {
+    const BASE_ADDRESS: u32 = I2C1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for I2C2 {
+    const BASE_ADDRESS: u32 = I2C2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
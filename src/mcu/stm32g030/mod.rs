@@ -1,7 +1,10 @@
-// STM32G030 MCU-specific definitions
+//! # STM32G030 MCU Implementation
+//!
+//! Base addresses, interrupt numbers and peripheral instances of the
+//! STM32G030x6/x8 (Cortex-M0+). USART, SPI and I2C are the newer v2 IP
+//! (ISR/ICR status, TIMINGR-based I2C); GPIO sits on the IOPORT bus.
 
 // Base addresses
-pub const TIM2_BASEADDR: u32 = 0x40000000;
 pub const TIM3_BASEADDR: u32 = 0x40000400;
 pub const TIM14_BASEADDR: u32 = 0x40002000;
 pub const RTC_BASEADDR: u32 = 0x40002800;
@@ -49,7 +52,6 @@ pub enum IRQn {
     ADC = 12,
     TIM1_BRK_UP_TRG_COM = 13,
     TIM1_CC = 14,
-    TIM2 = 15,
     TIM3 = 16,
     TIM14 = 19,
     TIM16 = 21,
@@ -60,13 +62,14 @@ pub enum IRQn {
     SPI2 = 26,
     USART1 = 27,
     USART2 = 28,
-    LPTIM1 = 29,
-    LPTIM2 = 30,
-    TIM6_DAC_LPTIM1 = 31,
-    TIM7_LPTIM2 = 32,
-    LPUART1 = 33,
 }
 
+/// HSI16 RC oscillator frequency, the system clock after reset
+pub const HSI_HZ: u32 = 16_000_000;
+
+/// Nominal LSI RC oscillator frequency; parts range from 29.5 to 34 kHz
+pub const LSI_HZ: u32 = 32_000;
+
 pub trait PeripheralAccess {
     const BASE_ADDRESS: u32;
     type RegisterBlock;
@@ -80,7 +83,11 @@ pub trait PeripheralAccess {
     }
 }
 
-// Include peripheral modules
+pub mod exti;
 pub mod gpio;
+pub mod i2c;
+pub mod pwr;
 pub mod rcc;
-// Add other peripherals as needed
+pub mod rtc;
+pub mod spi;
+pub mod usart;
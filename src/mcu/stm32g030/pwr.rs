@@ -0,0 +1,108 @@
+// PWR (Power Control) peripheral definitions
+// Generated from STM32G030 SVD file
+
+use super::{PWR_BASEADDR, PeripheralAccess};
+
+// PWR Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr1: u32, // RW: Power control register 1
+    pub cr2: u32, // RW: Power control register 2
+    pub cr3: u32, // RW: Power control register 3
+    pub cr4: u32, // RW: Power control register 4
+    pub sr1: u32, // RO: Power status register 1
+    pub sr2: u32, // RO: Power status register 2
+    pub scr: u32, // WO: Power status clear register
+}
+
+// PWR peripheral instance
+pub struct PWR;
+
+impl PeripheralAccess for PWR {
+    const BASE_ADDRESS: u32 = PWR_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+// PWR Register Field Definitions
+
+// CR1 register fields
+pub const CR1_LPR_POS: u32 = 14;
+pub const CR1_LPR_WIDTH: u32 = 1;
+pub const CR1_LPR_MASK: u32 = 0x1 << 14;
+
+pub const CR1_VOS_POS: u32 = 9;
+pub const CR1_VOS_WIDTH: u32 = 2;
+pub const CR1_VOS_MASK: u32 = 0x3 << 9;
+
+pub const CR1_DBP_POS: u32 = 8;
+pub const CR1_DBP_WIDTH: u32 = 1;
+pub const CR1_DBP_MASK: u32 = 0x1 << 8;
+
+pub const CR1_FPD_STOP_POS: u32 = 3;
+pub const CR1_FPD_STOP_WIDTH: u32 = 1;
+pub const CR1_FPD_STOP_MASK: u32 = 0x1 << 3;
+
+pub const CR1_LPMS_POS: u32 = 0;
+pub const CR1_LPMS_WIDTH: u32 = 3;
+pub const CR1_LPMS_MASK: u32 = 0x7 << 0;
+
+pub const CR1_LPMS_STOP0: u32 = 0 << 0;
+pub const CR1_LPMS_STOP1: u32 = 1 << 0;
+pub const CR1_LPMS_STANDBY: u32 = 3 << 0;
+pub const CR1_LPMS_SHUTDOWN: u32 = 4 << 0;
+
+// CR3 register fields
+pub const CR3_EIWUL_POS: u32 = 15;
+pub const CR3_EIWUL_WIDTH: u32 = 1;
+pub const CR3_EIWUL_MASK: u32 = 0x1 << 15;
+
+pub const CR3_APC_POS: u32 = 10;
+pub const CR3_APC_WIDTH: u32 = 1;
+pub const CR3_APC_MASK: u32 = 0x1 << 10;
+
+pub const CR3_EWUP4_POS: u32 = 3;
+pub const CR3_EWUP4_WIDTH: u32 = 1;
+pub const CR3_EWUP4_MASK: u32 = 0x1 << 3;
+
+pub const CR3_EWUP2_POS: u32 = 1;
+pub const CR3_EWUP2_WIDTH: u32 = 1;
+pub const CR3_EWUP2_MASK: u32 = 0x1 << 1;
+
+pub const CR3_EWUP1_POS: u32 = 0;
+pub const CR3_EWUP1_WIDTH: u32 = 1;
+pub const CR3_EWUP1_MASK: u32 = 0x1 << 0;
+
+// CR4 register fields (wakeup pin polarity, set for falling edge)
+pub const CR4_WP4_POS: u32 = 3;
+pub const CR4_WP4_WIDTH: u32 = 1;
+pub const CR4_WP4_MASK: u32 = 0x1 << 3;
+
+pub const CR4_WP2_POS: u32 = 1;
+pub const CR4_WP2_WIDTH: u32 = 1;
+pub const CR4_WP2_MASK: u32 = 0x1 << 1;
+
+pub const CR4_WP1_POS: u32 = 0;
+pub const CR4_WP1_WIDTH: u32 = 1;
+pub const CR4_WP1_MASK: u32 = 0x1 << 0;
+
+// SR1 register fields
+pub const SR1_WUFI_POS: u32 = 15;
+pub const SR1_WUFI_WIDTH: u32 = 1;
+pub const SR1_WUFI_MASK: u32 = 0x1 << 15;
+
+pub const SR1_SBF_POS: u32 = 8;
+pub const SR1_SBF_WIDTH: u32 = 1;
+pub const SR1_SBF_MASK: u32 = 0x1 << 8;
+
+pub const SR1_WUF_POS: u32 = 0;
+pub const SR1_WUF_WIDTH: u32 = 6;
+pub const SR1_WUF_MASK: u32 = 0x3f << 0;
+
+// SCR register fields
+pub const SCR_CSBF_POS: u32 = 8;
+pub const SCR_CSBF_WIDTH: u32 = 1;
+pub const SCR_CSBF_MASK: u32 = 0x1 << 8;
+
+pub const SCR_CWUF_POS: u32 = 0;
+pub const SCR_CWUF_WIDTH: u32 = 6;
+pub const SCR_CWUF_MASK: u32 = 0x3f << 0;
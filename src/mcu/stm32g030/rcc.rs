@@ -1 +1,427 @@
+// RCC (Reset and Clock Control) peripheral definitions
+// Generated from STM32G030 SVD file
 
+use super::{PeripheralAccess, RCC_BASEADDR};
+
+// RCC Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr: u32,      // RW: Clock control register
+    pub icscr: u32,   // RW: Internal clock sources calibration register
+    pub cfgr: u32,    // RW: Clock configuration register
+    pub pllcfgr: u32, // RW: PLL configuration register
+    _reserved0: [u32; 2],
+    pub cier: u32,      // RW: Clock interrupt enable register
+    pub cifr: u32,      // RO: Clock interrupt flag register
+    pub cicr: u32,      // WO: Clock interrupt clear register
+    pub ioprstr: u32,   // RW: I/O port reset register
+    pub ahbrstr: u32,   // RW: AHB peripheral reset register
+    pub apbrstr1: u32,  // RW: APB peripheral reset register 1
+    pub apbrstr2: u32,  // RW: APB peripheral reset register 2
+    pub iopenr: u32,    // RW: I/O port clock enable register
+    pub ahbenr: u32,    // RW: AHB peripheral clock enable register
+    pub apbenr1: u32,   // RW: APB peripheral clock enable register 1
+    pub apbenr2: u32,   // RW: APB peripheral clock enable register 2
+    pub iopsmenr: u32,  // RW: I/O port in Sleep mode clock enable register
+    pub ahbsmenr: u32,  // RW: AHB peripheral clock enable in Sleep mode register
+    pub apbsmenr1: u32, // RW: APB peripheral clock enable in Sleep mode register 1
+    pub apbsmenr2: u32, // RW: APB peripheral clock enable in Sleep mode register 2
+    pub ccipr: u32,     // RW: Peripherals independent clock configuration register
+    _reserved1: u32,
+    pub bdcr: u32, // RW: RTC domain control register
+    pub csr: u32,  // RW: Control/status register
+}
+
+// RCC Register Field Definitions
+
+// CR register fields
+pub const CR_PLLRDY_POS: u32 = 25;
+pub const CR_PLLRDY_WIDTH: u32 = 1;
+pub const CR_PLLRDY_MASK: u32 = 0x1 << 25;
+
+pub const CR_PLLON_POS: u32 = 24;
+pub const CR_PLLON_WIDTH: u32 = 1;
+pub const CR_PLLON_MASK: u32 = 0x1 << 24;
+
+pub const CR_CSSON_POS: u32 = 19;
+pub const CR_CSSON_WIDTH: u32 = 1;
+pub const CR_CSSON_MASK: u32 = 0x1 << 19;
+
+pub const CR_HSEBYP_POS: u32 = 18;
+pub const CR_HSEBYP_WIDTH: u32 = 1;
+pub const CR_HSEBYP_MASK: u32 = 0x1 << 18;
+
+pub const CR_HSERDY_POS: u32 = 17;
+pub const CR_HSERDY_WIDTH: u32 = 1;
+pub const CR_HSERDY_MASK: u32 = 0x1 << 17;
+
+pub const CR_HSEON_POS: u32 = 16;
+pub const CR_HSEON_WIDTH: u32 = 1;
+pub const CR_HSEON_MASK: u32 = 0x1 << 16;
+
+pub const CR_HSIDIV_POS: u32 = 11;
+pub const CR_HSIDIV_WIDTH: u32 = 3;
+pub const CR_HSIDIV_MASK: u32 = 0x7 << 11;
+
+pub const CR_HSIRDY_POS: u32 = 10;
+pub const CR_HSIRDY_WIDTH: u32 = 1;
+pub const CR_HSIRDY_MASK: u32 = 0x1 << 10;
+
+pub const CR_HSIKERON_POS: u32 = 9;
+pub const CR_HSIKERON_WIDTH: u32 = 1;
+pub const CR_HSIKERON_MASK: u32 = 0x1 << 9;
+
+pub const CR_HSION_POS: u32 = 8;
+pub const CR_HSION_WIDTH: u32 = 1;
+pub const CR_HSION_MASK: u32 = 0x1 << 8;
+
+// CFGR register fields
+pub const CFGR_MCOPRE_POS: u32 = 28;
+pub const CFGR_MCOPRE_WIDTH: u32 = 3;
+pub const CFGR_MCOPRE_MASK: u32 = 0x7 << 28;
+
+pub const CFGR_MCOSEL_POS: u32 = 24;
+pub const CFGR_MCOSEL_WIDTH: u32 = 3;
+pub const CFGR_MCOSEL_MASK: u32 = 0x7 << 24;
+
+pub const CFGR_PPRE_POS: u32 = 12;
+pub const CFGR_PPRE_WIDTH: u32 = 3;
+pub const CFGR_PPRE_MASK: u32 = 0x7 << 12;
+
+pub const CFGR_HPRE_POS: u32 = 8;
+pub const CFGR_HPRE_WIDTH: u32 = 4;
+pub const CFGR_HPRE_MASK: u32 = 0xF << 8;
+
+pub const CFGR_SWS_POS: u32 = 3;
+pub const CFGR_SWS_WIDTH: u32 = 3;
+pub const CFGR_SWS_MASK: u32 = 0x7 << 3;
+
+pub const CFGR_SW_POS: u32 = 0;
+pub const CFGR_SW_WIDTH: u32 = 3;
+pub const CFGR_SW_MASK: u32 = 0x7 << 0;
+// SW enumerated values
+pub const CFGR_SW_HSISYS: u32 = 0 << 0;
+pub const CFGR_SW_HSE: u32 = 1 << 0;
+pub const CFGR_SW_PLLRCLK: u32 = 2 << 0;
+pub const CFGR_SW_LSI: u32 = 3 << 0;
+pub const CFGR_SW_LSE: u32 = 4 << 0;
+
+// PLLCFGR register fields
+pub const PLLCFGR_PLLR_POS: u32 = 29;
+pub const PLLCFGR_PLLR_WIDTH: u32 = 3;
+pub const PLLCFGR_PLLR_MASK: u32 = 0x7 << 29;
+
+pub const PLLCFGR_PLLREN_POS: u32 = 28;
+pub const PLLCFGR_PLLREN_WIDTH: u32 = 1;
+pub const PLLCFGR_PLLREN_MASK: u32 = 0x1 << 28;
+
+pub const PLLCFGR_PLLP_POS: u32 = 17;
+pub const PLLCFGR_PLLP_WIDTH: u32 = 5;
+pub const PLLCFGR_PLLP_MASK: u32 = 0x1F << 17;
+
+pub const PLLCFGR_PLLPEN_POS: u32 = 16;
+pub const PLLCFGR_PLLPEN_WIDTH: u32 = 1;
+pub const PLLCFGR_PLLPEN_MASK: u32 = 0x1 << 16;
+
+pub const PLLCFGR_PLLN_POS: u32 = 8;
+pub const PLLCFGR_PLLN_WIDTH: u32 = 7;
+pub const PLLCFGR_PLLN_MASK: u32 = 0x7F << 8;
+
+pub const PLLCFGR_PLLM_POS: u32 = 4;
+pub const PLLCFGR_PLLM_WIDTH: u32 = 3;
+pub const PLLCFGR_PLLM_MASK: u32 = 0x7 << 4;
+
+pub const PLLCFGR_PLLSRC_POS: u32 = 0;
+pub const PLLCFGR_PLLSRC_WIDTH: u32 = 2;
+pub const PLLCFGR_PLLSRC_MASK: u32 = 0x3 << 0;
+
+// IOPRSTR register fields
+pub const IOPRSTR_GPIOFRST_POS: u32 = 5;
+pub const IOPRSTR_GPIOFRST_WIDTH: u32 = 1;
+pub const IOPRSTR_GPIOFRST_MASK: u32 = 0x1 << 5;
+
+pub const IOPRSTR_GPIODRST_POS: u32 = 3;
+pub const IOPRSTR_GPIODRST_WIDTH: u32 = 1;
+pub const IOPRSTR_GPIODRST_MASK: u32 = 0x1 << 3;
+
+pub const IOPRSTR_GPIOCRST_POS: u32 = 2;
+pub const IOPRSTR_GPIOCRST_WIDTH: u32 = 1;
+pub const IOPRSTR_GPIOCRST_MASK: u32 = 0x1 << 2;
+
+pub const IOPRSTR_GPIOBRST_POS: u32 = 1;
+pub const IOPRSTR_GPIOBRST_WIDTH: u32 = 1;
+pub const IOPRSTR_GPIOBRST_MASK: u32 = 0x1 << 1;
+
+pub const IOPRSTR_GPIOARST_POS: u32 = 0;
+pub const IOPRSTR_GPIOARST_WIDTH: u32 = 1;
+pub const IOPRSTR_GPIOARST_MASK: u32 = 0x1 << 0;
+
+// APBRSTR1 register fields
+pub const APBRSTR1_PWRRST_POS: u32 = 28;
+pub const APBRSTR1_PWRRST_WIDTH: u32 = 1;
+pub const APBRSTR1_PWRRST_MASK: u32 = 0x1 << 28;
+
+pub const APBRSTR1_DBGRST_POS: u32 = 27;
+pub const APBRSTR1_DBGRST_WIDTH: u32 = 1;
+pub const APBRSTR1_DBGRST_MASK: u32 = 0x1 << 27;
+
+pub const APBRSTR1_I2C2RST_POS: u32 = 22;
+pub const APBRSTR1_I2C2RST_WIDTH: u32 = 1;
+pub const APBRSTR1_I2C2RST_MASK: u32 = 0x1 << 22;
+
+pub const APBRSTR1_I2C1RST_POS: u32 = 21;
+pub const APBRSTR1_I2C1RST_WIDTH: u32 = 1;
+pub const APBRSTR1_I2C1RST_MASK: u32 = 0x1 << 21;
+
+pub const APBRSTR1_USART2RST_POS: u32 = 17;
+pub const APBRSTR1_USART2RST_WIDTH: u32 = 1;
+pub const APBRSTR1_USART2RST_MASK: u32 = 0x1 << 17;
+
+pub const APBRSTR1_SPI2RST_POS: u32 = 14;
+pub const APBRSTR1_SPI2RST_WIDTH: u32 = 1;
+pub const APBRSTR1_SPI2RST_MASK: u32 = 0x1 << 14;
+
+pub const APBRSTR1_TIM3RST_POS: u32 = 1;
+pub const APBRSTR1_TIM3RST_WIDTH: u32 = 1;
+pub const APBRSTR1_TIM3RST_MASK: u32 = 0x1 << 1;
+
+// APBRSTR2 register fields
+pub const APBRSTR2_ADCRST_POS: u32 = 20;
+pub const APBRSTR2_ADCRST_WIDTH: u32 = 1;
+pub const APBRSTR2_ADCRST_MASK: u32 = 0x1 << 20;
+
+pub const APBRSTR2_TIM17RST_POS: u32 = 18;
+pub const APBRSTR2_TIM17RST_WIDTH: u32 = 1;
+pub const APBRSTR2_TIM17RST_MASK: u32 = 0x1 << 18;
+
+pub const APBRSTR2_TIM16RST_POS: u32 = 17;
+pub const APBRSTR2_TIM16RST_WIDTH: u32 = 1;
+pub const APBRSTR2_TIM16RST_MASK: u32 = 0x1 << 17;
+
+pub const APBRSTR2_TIM14RST_POS: u32 = 15;
+pub const APBRSTR2_TIM14RST_WIDTH: u32 = 1;
+pub const APBRSTR2_TIM14RST_MASK: u32 = 0x1 << 15;
+
+pub const APBRSTR2_USART1RST_POS: u32 = 14;
+pub const APBRSTR2_USART1RST_WIDTH: u32 = 1;
+pub const APBRSTR2_USART1RST_MASK: u32 = 0x1 << 14;
+
+pub const APBRSTR2_SPI1RST_POS: u32 = 12;
+pub const APBRSTR2_SPI1RST_WIDTH: u32 = 1;
+pub const APBRSTR2_SPI1RST_MASK: u32 = 0x1 << 12;
+
+pub const APBRSTR2_TIM1RST_POS: u32 = 11;
+pub const APBRSTR2_TIM1RST_WIDTH: u32 = 1;
+pub const APBRSTR2_TIM1RST_MASK: u32 = 0x1 << 11;
+
+pub const APBRSTR2_SYSCFGRST_POS: u32 = 0;
+pub const APBRSTR2_SYSCFGRST_WIDTH: u32 = 1;
+pub const APBRSTR2_SYSCFGRST_MASK: u32 = 0x1 << 0;
+
+// IOPENR register fields
+pub const IOPENR_GPIOFEN_POS: u32 = 5;
+pub const IOPENR_GPIOFEN_WIDTH: u32 = 1;
+pub const IOPENR_GPIOFEN_MASK: u32 = 0x1 << 5;
+
+pub const IOPENR_GPIODEN_POS: u32 = 3;
+pub const IOPENR_GPIODEN_WIDTH: u32 = 1;
+pub const IOPENR_GPIODEN_MASK: u32 = 0x1 << 3;
+
+pub const IOPENR_GPIOCEN_POS: u32 = 2;
+pub const IOPENR_GPIOCEN_WIDTH: u32 = 1;
+pub const IOPENR_GPIOCEN_MASK: u32 = 0x1 << 2;
+
+pub const IOPENR_GPIOBEN_POS: u32 = 1;
+pub const IOPENR_GPIOBEN_WIDTH: u32 = 1;
+pub const IOPENR_GPIOBEN_MASK: u32 = 0x1 << 1;
+
+pub const IOPENR_GPIOAEN_POS: u32 = 0;
+pub const IOPENR_GPIOAEN_WIDTH: u32 = 1;
+pub const IOPENR_GPIOAEN_MASK: u32 = 0x1 << 0;
+
+// AHBENR register fields
+pub const AHBENR_CRCEN_POS: u32 = 12;
+pub const AHBENR_CRCEN_WIDTH: u32 = 1;
+pub const AHBENR_CRCEN_MASK: u32 = 0x1 << 12;
+
+pub const AHBENR_FLASHEN_POS: u32 = 8;
+pub const AHBENR_FLASHEN_WIDTH: u32 = 1;
+pub const AHBENR_FLASHEN_MASK: u32 = 0x1 << 8;
+
+pub const AHBENR_DMA1EN_POS: u32 = 0;
+pub const AHBENR_DMA1EN_WIDTH: u32 = 1;
+pub const AHBENR_DMA1EN_MASK: u32 = 0x1 << 0;
+
+// APBENR1 register fields
+pub const APBENR1_PWREN_POS: u32 = 28;
+pub const APBENR1_PWREN_WIDTH: u32 = 1;
+pub const APBENR1_PWREN_MASK: u32 = 0x1 << 28;
+
+pub const APBENR1_DBGEN_POS: u32 = 27;
+pub const APBENR1_DBGEN_WIDTH: u32 = 1;
+pub const APBENR1_DBGEN_MASK: u32 = 0x1 << 27;
+
+pub const APBENR1_I2C2EN_POS: u32 = 22;
+pub const APBENR1_I2C2EN_WIDTH: u32 = 1;
+pub const APBENR1_I2C2EN_MASK: u32 = 0x1 << 22;
+
+pub const APBENR1_I2C1EN_POS: u32 = 21;
+pub const APBENR1_I2C1EN_WIDTH: u32 = 1;
+pub const APBENR1_I2C1EN_MASK: u32 = 0x1 << 21;
+
+pub const APBENR1_USART2EN_POS: u32 = 17;
+pub const APBENR1_USART2EN_WIDTH: u32 = 1;
+pub const APBENR1_USART2EN_MASK: u32 = 0x1 << 17;
+
+pub const APBENR1_SPI2EN_POS: u32 = 14;
+pub const APBENR1_SPI2EN_WIDTH: u32 = 1;
+pub const APBENR1_SPI2EN_MASK: u32 = 0x1 << 14;
+
+pub const APBENR1_WWDGEN_POS: u32 = 11;
+pub const APBENR1_WWDGEN_WIDTH: u32 = 1;
+pub const APBENR1_WWDGEN_MASK: u32 = 0x1 << 11;
+
+pub const APBENR1_RTCAPBEN_POS: u32 = 10;
+pub const APBENR1_RTCAPBEN_WIDTH: u32 = 1;
+pub const APBENR1_RTCAPBEN_MASK: u32 = 0x1 << 10;
+
+pub const APBENR1_TIM3EN_POS: u32 = 1;
+pub const APBENR1_TIM3EN_WIDTH: u32 = 1;
+pub const APBENR1_TIM3EN_MASK: u32 = 0x1 << 1;
+
+// APBENR2 register fields
+pub const APBENR2_ADCEN_POS: u32 = 20;
+pub const APBENR2_ADCEN_WIDTH: u32 = 1;
+pub const APBENR2_ADCEN_MASK: u32 = 0x1 << 20;
+
+pub const APBENR2_TIM17EN_POS: u32 = 18;
+pub const APBENR2_TIM17EN_WIDTH: u32 = 1;
+pub const APBENR2_TIM17EN_MASK: u32 = 0x1 << 18;
+
+pub const APBENR2_TIM16EN_POS: u32 = 17;
+pub const APBENR2_TIM16EN_WIDTH: u32 = 1;
+pub const APBENR2_TIM16EN_MASK: u32 = 0x1 << 17;
+
+pub const APBENR2_TIM14EN_POS: u32 = 15;
+pub const APBENR2_TIM14EN_WIDTH: u32 = 1;
+pub const APBENR2_TIM14EN_MASK: u32 = 0x1 << 15;
+
+pub const APBENR2_USART1EN_POS: u32 = 14;
+pub const APBENR2_USART1EN_WIDTH: u32 = 1;
+pub const APBENR2_USART1EN_MASK: u32 = 0x1 << 14;
+
+pub const APBENR2_SPI1EN_POS: u32 = 12;
+pub const APBENR2_SPI1EN_WIDTH: u32 = 1;
+pub const APBENR2_SPI1EN_MASK: u32 = 0x1 << 12;
+
+pub const APBENR2_TIM1EN_POS: u32 = 11;
+pub const APBENR2_TIM1EN_WIDTH: u32 = 1;
+pub const APBENR2_TIM1EN_MASK: u32 = 0x1 << 11;
+
+pub const APBENR2_SYSCFGEN_POS: u32 = 0;
+pub const APBENR2_SYSCFGEN_WIDTH: u32 = 1;
+pub const APBENR2_SYSCFGEN_MASK: u32 = 0x1 << 0;
+
+// CCIPR register fields
+pub const CCIPR_ADCSEL_POS: u32 = 30;
+pub const CCIPR_ADCSEL_WIDTH: u32 = 2;
+pub const CCIPR_ADCSEL_MASK: u32 = 0x3 << 30;
+
+pub const CCIPR_I2C1SEL_POS: u32 = 12;
+pub const CCIPR_I2C1SEL_WIDTH: u32 = 2;
+pub const CCIPR_I2C1SEL_MASK: u32 = 0x3 << 12;
+
+pub const CCIPR_USART1SEL_POS: u32 = 0;
+pub const CCIPR_USART1SEL_WIDTH: u32 = 2;
+pub const CCIPR_USART1SEL_MASK: u32 = 0x3 << 0;
+
+// BDCR register fields
+pub const BDCR_LSCOSEL_POS: u32 = 25;
+pub const BDCR_LSCOSEL_WIDTH: u32 = 1;
+pub const BDCR_LSCOSEL_MASK: u32 = 0x1 << 25;
+
+pub const BDCR_LSCOEN_POS: u32 = 24;
+pub const BDCR_LSCOEN_WIDTH: u32 = 1;
+pub const BDCR_LSCOEN_MASK: u32 = 0x1 << 24;
+
+pub const BDCR_BDRST_POS: u32 = 16;
+pub const BDCR_BDRST_WIDTH: u32 = 1;
+pub const BDCR_BDRST_MASK: u32 = 0x1 << 16;
+
+pub const BDCR_RTCEN_POS: u32 = 15;
+pub const BDCR_RTCEN_WIDTH: u32 = 1;
+pub const BDCR_RTCEN_MASK: u32 = 0x1 << 15;
+
+pub const BDCR_RTCSEL_POS: u32 = 8;
+pub const BDCR_RTCSEL_WIDTH: u32 = 2;
+pub const BDCR_RTCSEL_MASK: u32 = 0x3 << 8;
+
+pub const BDCR_LSECSSD_POS: u32 = 6;
+pub const BDCR_LSECSSD_WIDTH: u32 = 1;
+pub const BDCR_LSECSSD_MASK: u32 = 0x1 << 6;
+
+pub const BDCR_LSECSSON_POS: u32 = 5;
+pub const BDCR_LSECSSON_WIDTH: u32 = 1;
+pub const BDCR_LSECSSON_MASK: u32 = 0x1 << 5;
+
+pub const BDCR_LSEDRV_POS: u32 = 3;
+pub const BDCR_LSEDRV_WIDTH: u32 = 2;
+pub const BDCR_LSEDRV_MASK: u32 = 0x3 << 3;
+
+pub const BDCR_LSEBYP_POS: u32 = 2;
+pub const BDCR_LSEBYP_WIDTH: u32 = 1;
+pub const BDCR_LSEBYP_MASK: u32 = 0x1 << 2;
+
+pub const BDCR_LSERDY_POS: u32 = 1;
+pub const BDCR_LSERDY_WIDTH: u32 = 1;
+pub const BDCR_LSERDY_MASK: u32 = 0x1 << 1;
+
+pub const BDCR_LSEON_POS: u32 = 0;
+pub const BDCR_LSEON_WIDTH: u32 = 1;
+pub const BDCR_LSEON_MASK: u32 = 0x1 << 0;
+
+// CSR register fields
+pub const CSR_LPWRRSTF_POS: u32 = 31;
+pub const CSR_LPWRRSTF_WIDTH: u32 = 1;
+pub const CSR_LPWRRSTF_MASK: u32 = 0x1 << 31;
+
+pub const CSR_WWDGRSTF_POS: u32 = 30;
+pub const CSR_WWDGRSTF_WIDTH: u32 = 1;
+pub const CSR_WWDGRSTF_MASK: u32 = 0x1 << 30;
+
+pub const CSR_IWDGRSTF_POS: u32 = 29;
+pub const CSR_IWDGRSTF_WIDTH: u32 = 1;
+pub const CSR_IWDGRSTF_MASK: u32 = 0x1 << 29;
+
+pub const CSR_SFTRSTF_POS: u32 = 28;
+pub const CSR_SFTRSTF_WIDTH: u32 = 1;
+pub const CSR_SFTRSTF_MASK: u32 = 0x1 << 28;
+
+pub const CSR_PWRRSTF_POS: u32 = 27;
+pub const CSR_PWRRSTF_WIDTH: u32 = 1;
+pub const CSR_PWRRSTF_MASK: u32 = 0x1 << 27;
+
+pub const CSR_PINRSTF_POS: u32 = 26;
+pub const CSR_PINRSTF_WIDTH: u32 = 1;
+pub const CSR_PINRSTF_MASK: u32 = 0x1 << 26;
+
+pub const CSR_OBLRSTF_POS: u32 = 25;
+pub const CSR_OBLRSTF_WIDTH: u32 = 1;
+pub const CSR_OBLRSTF_MASK: u32 = 0x1 << 25;
+
+pub const CSR_RMVF_POS: u32 = 23;
+pub const CSR_RMVF_WIDTH: u32 = 1;
+pub const CSR_RMVF_MASK: u32 = 0x1 << 23;
+
+pub const CSR_LSIRDY_POS: u32 = 1;
+pub const CSR_LSIRDY_WIDTH: u32 = 1;
+pub const CSR_LSIRDY_MASK: u32 = 0x1 << 1;
+
+pub const CSR_LSION_POS: u32 = 0;
+pub const CSR_LSION_WIDTH: u32 = 1;
+pub const CSR_LSION_MASK: u32 = 0x1 << 0;
+
+impl PeripheralAccess for RegisterBlock {
+    const BASE_ADDRESS: u32 = RCC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
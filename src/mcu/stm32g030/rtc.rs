@@ -0,0 +1,110 @@
+// RTC (Real-Time Clock) peripheral definitions
+// Generated from STM32G030 SVD file
+
+use super::{PeripheralAccess, RTC_BASEADDR};
+
+// RTC Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub tr: u32,   // RW: time register
+    pub dr: u32,   // RW: date register
+    pub ssr: u32,  // RO: sub second register
+    pub icsr: u32, // RW: initialization control and status register
+    pub prer: u32, // RW: prescaler register
+    pub wutr: u32, // RW: wakeup timer register
+    pub cr: u32,   // RW: control register
+    _reserved0: [u32; 2],
+    pub wpr: u32,    // WO: write protection register
+    pub calr: u32,   // RW: calibration register
+    pub shiftr: u32, // WO: shift control register
+    pub tstr: u32,   // RO: time stamp time register
+    pub tsdr: u32,   // RO: time stamp date register
+    pub tsssr: u32,  // RO: timestamp sub second register
+    _reserved1: u32,
+    pub alrmar: u32,   // RW: alarm A register
+    pub alrmassr: u32, // RW: alarm A sub second register
+    pub alrmbr: u32,   // RW: alarm B register
+    pub alrmbssr: u32, // RW: alarm B sub second register
+    pub sr: u32,       // RO: status register
+    pub misr: u32,     // RO: masked interrupt status register
+    _reserved2: u32,
+    pub scr: u32, // WO: status clear register
+}
+
+// RTC peripheral instance
+pub struct RTC;
+
+impl PeripheralAccess for RTC {
+    const BASE_ADDRESS: u32 = RTC_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+// Write protection keys, written in this order to unlock
+pub const WPR_KEY1: u32 = 0xCA;
+pub const WPR_KEY2: u32 = 0x53;
+
+// RTC Register Field Definitions
+
+// ICSR register fields
+pub const ICSR_INIT_POS: u32 = 7;
+pub const ICSR_INIT_WIDTH: u32 = 1;
+pub const ICSR_INIT_MASK: u32 = 0x1 << 7;
+
+pub const ICSR_INITF_POS: u32 = 6;
+pub const ICSR_INITF_WIDTH: u32 = 1;
+pub const ICSR_INITF_MASK: u32 = 0x1 << 6;
+
+pub const ICSR_RSF_POS: u32 = 5;
+pub const ICSR_RSF_WIDTH: u32 = 1;
+pub const ICSR_RSF_MASK: u32 = 0x1 << 5;
+
+pub const ICSR_INITS_POS: u32 = 4;
+pub const ICSR_INITS_WIDTH: u32 = 1;
+pub const ICSR_INITS_MASK: u32 = 0x1 << 4;
+
+pub const ICSR_WUTWF_POS: u32 = 2;
+pub const ICSR_WUTWF_WIDTH: u32 = 1;
+pub const ICSR_WUTWF_MASK: u32 = 0x1 << 2;
+
+// CR register fields
+pub const CR_WUTIE_POS: u32 = 14;
+pub const CR_WUTIE_WIDTH: u32 = 1;
+pub const CR_WUTIE_MASK: u32 = 0x1 << 14;
+
+pub const CR_WUTE_POS: u32 = 10;
+pub const CR_WUTE_WIDTH: u32 = 1;
+pub const CR_WUTE_MASK: u32 = 0x1 << 10;
+
+pub const CR_FMT_POS: u32 = 6;
+pub const CR_FMT_WIDTH: u32 = 1;
+pub const CR_FMT_MASK: u32 = 0x1 << 6;
+
+pub const CR_BYPSHAD_POS: u32 = 5;
+pub const CR_BYPSHAD_WIDTH: u32 = 1;
+pub const CR_BYPSHAD_MASK: u32 = 0x1 << 5;
+
+pub const CR_WUCKSEL_POS: u32 = 0;
+pub const CR_WUCKSEL_WIDTH: u32 = 3;
+pub const CR_WUCKSEL_MASK: u32 = 0x7 << 0;
+
+pub const CR_WUCKSEL_RTC_DIV16: u32 = 0 << 0;
+pub const CR_WUCKSEL_SPRE: u32 = 4 << 0;
+
+// PRER register fields
+pub const PRER_PREDIV_A_POS: u32 = 16;
+pub const PRER_PREDIV_A_WIDTH: u32 = 7;
+pub const PRER_PREDIV_A_MASK: u32 = 0x7f << 16;
+
+pub const PRER_PREDIV_S_POS: u32 = 0;
+pub const PRER_PREDIV_S_WIDTH: u32 = 15;
+pub const PRER_PREDIV_S_MASK: u32 = 0x7fff << 0;
+
+// SR register fields
+pub const SR_WUTF_POS: u32 = 2;
+pub const SR_WUTF_WIDTH: u32 = 1;
+pub const SR_WUTF_MASK: u32 = 0x1 << 2;
+
+// SCR register fields
+pub const SCR_CWUTF_POS: u32 = 2;
+pub const SCR_CWUTF_WIDTH: u32 = 1;
+pub const SCR_CWUTF_MASK: u32 = 0x1 << 2;
@@ -0,0 +1,201 @@
+// SPI peripheral definitions
+// Generated from STM32G030 SVD file
+
+use super::{PeripheralAccess, SPI1_BASEADDR, SPI2_BASEADDR};
+
+// SPI Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr1: u32,    // RW: Control register 1
+    pub cr2: u32,    // RW: Control register 2
+    pub sr: u32,     // RW: Status register
+    pub dr: u32,     // RW: Data register
+    pub crcpr: u32,  // RW: CRC polynomial register
+    pub rxcrcr: u32, // RO: RX CRC register
+    pub txcrcr: u32, // RO: TX CRC register
+}
+
+// SPI Register Field Definitions
+
+// CR1 register fields
+pub const CR1_BIDIMODE_POS: u32 = 15;
+pub const CR1_BIDIMODE_WIDTH: u32 = 1;
+pub const CR1_BIDIMODE_MASK: u32 = 0x1 << 15;
+
+pub const CR1_BIDIOE_POS: u32 = 14;
+pub const CR1_BIDIOE_WIDTH: u32 = 1;
+pub const CR1_BIDIOE_MASK: u32 = 0x1 << 14;
+
+pub const CR1_CRCEN_POS: u32 = 13;
+pub const CR1_CRCEN_WIDTH: u32 = 1;
+pub const CR1_CRCEN_MASK: u32 = 0x1 << 13;
+
+pub const CR1_CRCNEXT_POS: u32 = 12;
+pub const CR1_CRCNEXT_WIDTH: u32 = 1;
+pub const CR1_CRCNEXT_MASK: u32 = 0x1 << 12;
+
+pub const CR1_CRCL_POS: u32 = 11;
+pub const CR1_CRCL_WIDTH: u32 = 1;
+pub const CR1_CRCL_MASK: u32 = 0x1 << 11;
+
+pub const CR1_RXONLY_POS: u32 = 10;
+pub const CR1_RXONLY_WIDTH: u32 = 1;
+pub const CR1_RXONLY_MASK: u32 = 0x1 << 10;
+
+pub const CR1_SSM_POS: u32 = 9;
+pub const CR1_SSM_WIDTH: u32 = 1;
+pub const CR1_SSM_MASK: u32 = 0x1 << 9;
+
+pub const CR1_SSI_POS: u32 = 8;
+pub const CR1_SSI_WIDTH: u32 = 1;
+pub const CR1_SSI_MASK: u32 = 0x1 << 8;
+
+pub const CR1_LSBFIRST_POS: u32 = 7;
+pub const CR1_LSBFIRST_WIDTH: u32 = 1;
+pub const CR1_LSBFIRST_MASK: u32 = 0x1 << 7;
+
+pub const CR1_SPE_POS: u32 = 6;
+pub const CR1_SPE_WIDTH: u32 = 1;
+pub const CR1_SPE_MASK: u32 = 0x1 << 6;
+
+pub const CR1_BR_POS: u32 = 3;
+pub const CR1_BR_WIDTH: u32 = 3;
+pub const CR1_BR_MASK: u32 = 0x7 << 3;
+// BR enumerated values
+pub const CR1_BR_DIV2: u32 = 0 << 3;
+pub const CR1_BR_DIV4: u32 = 1 << 3;
+pub const CR1_BR_DIV8: u32 = 2 << 3;
+pub const CR1_BR_DIV16: u32 = 3 << 3;
+pub const CR1_BR_DIV32: u32 = 4 << 3;
+pub const CR1_BR_DIV64: u32 = 5 << 3;
+pub const CR1_BR_DIV128: u32 = 6 << 3;
+pub const CR1_BR_DIV256: u32 = 7 << 3;
+
+pub const CR1_MSTR_POS: u32 = 2;
+pub const CR1_MSTR_WIDTH: u32 = 1;
+pub const CR1_MSTR_MASK: u32 = 0x1 << 2;
+
+pub const CR1_CPOL_POS: u32 = 1;
+pub const CR1_CPOL_WIDTH: u32 = 1;
+pub const CR1_CPOL_MASK: u32 = 0x1 << 1;
+
+pub const CR1_CPHA_POS: u32 = 0;
+pub const CR1_CPHA_WIDTH: u32 = 1;
+pub const CR1_CPHA_MASK: u32 = 0x1 << 0;
+
+// CR2 register fields
+pub const CR2_LDMA_TX_POS: u32 = 14;
+pub const CR2_LDMA_TX_WIDTH: u32 = 1;
+pub const CR2_LDMA_TX_MASK: u32 = 0x1 << 14;
+
+pub const CR2_LDMA_RX_POS: u32 = 13;
+pub const CR2_LDMA_RX_WIDTH: u32 = 1;
+pub const CR2_LDMA_RX_MASK: u32 = 0x1 << 13;
+
+pub const CR2_FRXTH_POS: u32 = 12;
+pub const CR2_FRXTH_WIDTH: u32 = 1;
+pub const CR2_FRXTH_MASK: u32 = 0x1 << 12;
+
+pub const CR2_DS_POS: u32 = 8;
+pub const CR2_DS_WIDTH: u32 = 4;
+pub const CR2_DS_MASK: u32 = 0xF << 8;
+// DS enumerated values
+pub const CR2_DS_BITS4: u32 = 3 << 8;
+pub const CR2_DS_BITS8: u32 = 7 << 8;
+pub const CR2_DS_BITS16: u32 = 15 << 8;
+
+pub const CR2_TXEIE_POS: u32 = 7;
+pub const CR2_TXEIE_WIDTH: u32 = 1;
+pub const CR2_TXEIE_MASK: u32 = 0x1 << 7;
+
+pub const CR2_RXNEIE_POS: u32 = 6;
+pub const CR2_RXNEIE_WIDTH: u32 = 1;
+pub const CR2_RXNEIE_MASK: u32 = 0x1 << 6;
+
+pub const CR2_ERRIE_POS: u32 = 5;
+pub const CR2_ERRIE_WIDTH: u32 = 1;
+pub const CR2_ERRIE_MASK: u32 = 0x1 << 5;
+
+pub const CR2_FRF_POS: u32 = 4;
+pub const CR2_FRF_WIDTH: u32 = 1;
+pub const CR2_FRF_MASK: u32 = 0x1 << 4;
+// FRF enumerated values
+pub const CR2_FRF_MOTOROLA: u32 = 0 << 4;
+pub const CR2_FRF_TI: u32 = 1 << 4;
+
+pub const CR2_NSSP_POS: u32 = 3;
+pub const CR2_NSSP_WIDTH: u32 = 1;
+pub const CR2_NSSP_MASK: u32 = 0x1 << 3;
+
+pub const CR2_SSOE_POS: u32 = 2;
+pub const CR2_SSOE_WIDTH: u32 = 1;
+pub const CR2_SSOE_MASK: u32 = 0x1 << 2;
+
+pub const CR2_TXDMAEN_POS: u32 = 1;
+pub const CR2_TXDMAEN_WIDTH: u32 = 1;
+pub const CR2_TXDMAEN_MASK: u32 = 0x1 << 1;
+
+pub const CR2_RXDMAEN_POS: u32 = 0;
+pub const CR2_RXDMAEN_WIDTH: u32 = 1;
+pub const CR2_RXDMAEN_MASK: u32 = 0x1 << 0;
+
+// SR register fields
+pub const SR_FTLVL_POS: u32 = 11;
+pub const SR_FTLVL_WIDTH: u32 = 2;
+pub const SR_FTLVL_MASK: u32 = 0x3 << 11;
+
+pub const SR_FRLVL_POS: u32 = 9;
+pub const SR_FRLVL_WIDTH: u32 = 2;
+pub const SR_FRLVL_MASK: u32 = 0x3 << 9;
+
+pub const SR_FRE_POS: u32 = 8;
+pub const SR_FRE_WIDTH: u32 = 1;
+pub const SR_FRE_MASK: u32 = 0x1 << 8;
+
+pub const SR_BSY_POS: u32 = 7;
+pub const SR_BSY_WIDTH: u32 = 1;
+pub const SR_BSY_MASK: u32 = 0x1 << 7;
+
+pub const SR_OVR_POS: u32 = 6;
+pub const SR_OVR_WIDTH: u32 = 1;
+pub const SR_OVR_MASK: u32 = 0x1 << 6;
+
+pub const SR_MODF_POS: u32 = 5;
+pub const SR_MODF_WIDTH: u32 = 1;
+pub const SR_MODF_MASK: u32 = 0x1 << 5;
+
+pub const SR_CRCERR_POS: u32 = 4;
+pub const SR_CRCERR_WIDTH: u32 = 1;
+pub const SR_CRCERR_MASK: u32 = 0x1 << 4;
+
+pub const SR_TXE_POS: u32 = 1;
+pub const SR_TXE_WIDTH: u32 = 1;
+pub const SR_TXE_MASK: u32 = 0x1 << 1;
+
+pub const SR_RXNE_POS: u32 = 0;
+pub const SR_RXNE_WIDTH: u32 = 1;
+pub const SR_RXNE_MASK: u32 = 0x1 << 0;
+
+// DR register fields
+pub const DR_DR_POS: u32 = 0;
+pub const DR_DR_WIDTH: u32 = 16;
+pub const DR_DR_MASK: u32 = 0xFFFF << 0;
+
+// CRCPR register fields
+pub const CRCPR_CRCPOLY_POS: u32 = 0;
+pub const CRCPR_CRCPOLY_WIDTH: u32 = 16;
+pub const CRCPR_CRCPOLY_MASK: u32 = 0xFFFF << 0;
+
+// SPI peripheral instances
+pub struct SPI1;
+pub struct SPI2;
+
+impl PeripheralAccess for SPI1 {
+    const BASE_ADDRESS: u32 = SPI1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for SPI2 {
+    const BASE_ADDRESS: u32 = SPI2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,556 @@
+// USART peripheral definitions
+// Generated from STM32G030 SVD file
+
+use super::{PeripheralAccess, USART1_BASEADDR, USART2_BASEADDR};
+
+// USART Register Block
+#[repr(C)]
+pub struct RegisterBlock {
+    pub cr1: u32,   // RW: Control register 1
+    pub cr2: u32,   // RW: Control register 2
+    pub cr3: u32,   // RW: Control register 3
+    pub brr: u32,   // RW: Baud rate register
+    pub gtpr: u32,  // RW: Guard time and prescaler register
+    pub rtor: u32,  // RW: Receiver timeout register
+    pub rqr: u32,   // WO: Request register
+    pub isr: u32,   // RO: Interrupt and status register
+    pub icr: u32,   // WO: Interrupt flag clear register
+    pub rdr: u32,   // RO: Receive data register
+    pub tdr: u32,   // RW: Transmit data register
+    pub presc: u32, // RW: Prescaler register
+}
+
+// USART Register Field Definitions
+
+// CR1 register fields
+pub const CR1_RXFFIE_POS: u32 = 31;
+pub const CR1_RXFFIE_WIDTH: u32 = 1;
+pub const CR1_RXFFIE_MASK: u32 = 0x1 << 31;
+
+pub const CR1_TXFEIE_POS: u32 = 30;
+pub const CR1_TXFEIE_WIDTH: u32 = 1;
+pub const CR1_TXFEIE_MASK: u32 = 0x1 << 30;
+
+pub const CR1_FIFOEN_POS: u32 = 29;
+pub const CR1_FIFOEN_WIDTH: u32 = 1;
+pub const CR1_FIFOEN_MASK: u32 = 0x1 << 29;
+
+pub const CR1_M1_POS: u32 = 28;
+pub const CR1_M1_WIDTH: u32 = 1;
+pub const CR1_M1_MASK: u32 = 0x1 << 28;
+
+pub const CR1_EOBIE_POS: u32 = 27;
+pub const CR1_EOBIE_WIDTH: u32 = 1;
+pub const CR1_EOBIE_MASK: u32 = 0x1 << 27;
+
+pub const CR1_RTOIE_POS: u32 = 26;
+pub const CR1_RTOIE_WIDTH: u32 = 1;
+pub const CR1_RTOIE_MASK: u32 = 0x1 << 26;
+
+pub const CR1_DEAT_POS: u32 = 21;
+pub const CR1_DEAT_WIDTH: u32 = 5;
+pub const CR1_DEAT_MASK: u32 = 0x1F << 21;
+
+pub const CR1_DEDT_POS: u32 = 16;
+pub const CR1_DEDT_WIDTH: u32 = 5;
+pub const CR1_DEDT_MASK: u32 = 0x1F << 16;
+
+pub const CR1_OVER8_POS: u32 = 15;
+pub const CR1_OVER8_WIDTH: u32 = 1;
+pub const CR1_OVER8_MASK: u32 = 0x1 << 15;
+// OVER8 enumerated values
+pub const CR1_OVER8_OVERSAMPLE16: u32 = 0 << 15;
+pub const CR1_OVER8_OVERSAMPLE8: u32 = 1 << 15;
+
+pub const CR1_CMIE_POS: u32 = 14;
+pub const CR1_CMIE_WIDTH: u32 = 1;
+pub const CR1_CMIE_MASK: u32 = 0x1 << 14;
+
+pub const CR1_MME_POS: u32 = 13;
+pub const CR1_MME_WIDTH: u32 = 1;
+pub const CR1_MME_MASK: u32 = 0x1 << 13;
+
+pub const CR1_M0_POS: u32 = 12;
+pub const CR1_M0_WIDTH: u32 = 1;
+pub const CR1_M0_MASK: u32 = 0x1 << 12;
+
+pub const CR1_WAKE_POS: u32 = 11;
+pub const CR1_WAKE_WIDTH: u32 = 1;
+pub const CR1_WAKE_MASK: u32 = 0x1 << 11;
+
+pub const CR1_PCE_POS: u32 = 10;
+pub const CR1_PCE_WIDTH: u32 = 1;
+pub const CR1_PCE_MASK: u32 = 0x1 << 10;
+
+pub const CR1_PS_POS: u32 = 9;
+pub const CR1_PS_WIDTH: u32 = 1;
+pub const CR1_PS_MASK: u32 = 0x1 << 9;
+// PS enumerated values
+pub const CR1_PS_EVEN: u32 = 0 << 9;
+pub const CR1_PS_ODD: u32 = 1 << 9;
+
+pub const CR1_PEIE_POS: u32 = 8;
+pub const CR1_PEIE_WIDTH: u32 = 1;
+pub const CR1_PEIE_MASK: u32 = 0x1 << 8;
+
+pub const CR1_TXEIE_POS: u32 = 7;
+pub const CR1_TXEIE_WIDTH: u32 = 1;
+pub const CR1_TXEIE_MASK: u32 = 0x1 << 7;
+
+pub const CR1_TCIE_POS: u32 = 6;
+pub const CR1_TCIE_WIDTH: u32 = 1;
+pub const CR1_TCIE_MASK: u32 = 0x1 << 6;
+
+pub const CR1_RXNEIE_POS: u32 = 5;
+pub const CR1_RXNEIE_WIDTH: u32 = 1;
+pub const CR1_RXNEIE_MASK: u32 = 0x1 << 5;
+
+pub const CR1_IDLEIE_POS: u32 = 4;
+pub const CR1_IDLEIE_WIDTH: u32 = 1;
+pub const CR1_IDLEIE_MASK: u32 = 0x1 << 4;
+
+pub const CR1_TE_POS: u32 = 3;
+pub const CR1_TE_WIDTH: u32 = 1;
+pub const CR1_TE_MASK: u32 = 0x1 << 3;
+
+pub const CR1_RE_POS: u32 = 2;
+pub const CR1_RE_WIDTH: u32 = 1;
+pub const CR1_RE_MASK: u32 = 0x1 << 2;
+
+pub const CR1_UESM_POS: u32 = 1;
+pub const CR1_UESM_WIDTH: u32 = 1;
+pub const CR1_UESM_MASK: u32 = 0x1 << 1;
+
+pub const CR1_UE_POS: u32 = 0;
+pub const CR1_UE_WIDTH: u32 = 1;
+pub const CR1_UE_MASK: u32 = 0x1 << 0;
+
+// CR2 register fields
+pub const CR2_ADD_POS: u32 = 24;
+pub const CR2_ADD_WIDTH: u32 = 8;
+pub const CR2_ADD_MASK: u32 = 0xFF << 24;
+
+pub const CR2_RTOEN_POS: u32 = 23;
+pub const CR2_RTOEN_WIDTH: u32 = 1;
+pub const CR2_RTOEN_MASK: u32 = 0x1 << 23;
+
+pub const CR2_ABRMOD_POS: u32 = 21;
+pub const CR2_ABRMOD_WIDTH: u32 = 2;
+pub const CR2_ABRMOD_MASK: u32 = 0x3 << 21;
+
+pub const CR2_ABREN_POS: u32 = 20;
+pub const CR2_ABREN_WIDTH: u32 = 1;
+pub const CR2_ABREN_MASK: u32 = 0x1 << 20;
+
+pub const CR2_MSBFIRST_POS: u32 = 19;
+pub const CR2_MSBFIRST_WIDTH: u32 = 1;
+pub const CR2_MSBFIRST_MASK: u32 = 0x1 << 19;
+
+pub const CR2_DATAINV_POS: u32 = 18;
+pub const CR2_DATAINV_WIDTH: u32 = 1;
+pub const CR2_DATAINV_MASK: u32 = 0x1 << 18;
+
+pub const CR2_TXINV_POS: u32 = 17;
+pub const CR2_TXINV_WIDTH: u32 = 1;
+pub const CR2_TXINV_MASK: u32 = 0x1 << 17;
+
+pub const CR2_RXINV_POS: u32 = 16;
+pub const CR2_RXINV_WIDTH: u32 = 1;
+pub const CR2_RXINV_MASK: u32 = 0x1 << 16;
+
+pub const CR2_SWAP_POS: u32 = 15;
+pub const CR2_SWAP_WIDTH: u32 = 1;
+pub const CR2_SWAP_MASK: u32 = 0x1 << 15;
+
+pub const CR2_LINEN_POS: u32 = 14;
+pub const CR2_LINEN_WIDTH: u32 = 1;
+pub const CR2_LINEN_MASK: u32 = 0x1 << 14;
+
+pub const CR2_STOP_POS: u32 = 12;
+pub const CR2_STOP_WIDTH: u32 = 2;
+pub const CR2_STOP_MASK: u32 = 0x3 << 12;
+// STOP enumerated values
+pub const CR2_STOP_STOP1: u32 = 0 << 12;
+pub const CR2_STOP_STOP0P5: u32 = 1 << 12;
+pub const CR2_STOP_STOP2: u32 = 2 << 12;
+pub const CR2_STOP_STOP1P5: u32 = 3 << 12;
+
+pub const CR2_CLKEN_POS: u32 = 11;
+pub const CR2_CLKEN_WIDTH: u32 = 1;
+pub const CR2_CLKEN_MASK: u32 = 0x1 << 11;
+
+pub const CR2_CPOL_POS: u32 = 10;
+pub const CR2_CPOL_WIDTH: u32 = 1;
+pub const CR2_CPOL_MASK: u32 = 0x1 << 10;
+
+pub const CR2_CPHA_POS: u32 = 9;
+pub const CR2_CPHA_WIDTH: u32 = 1;
+pub const CR2_CPHA_MASK: u32 = 0x1 << 9;
+
+pub const CR2_LBCL_POS: u32 = 8;
+pub const CR2_LBCL_WIDTH: u32 = 1;
+pub const CR2_LBCL_MASK: u32 = 0x1 << 8;
+
+pub const CR2_LBDIE_POS: u32 = 6;
+pub const CR2_LBDIE_WIDTH: u32 = 1;
+pub const CR2_LBDIE_MASK: u32 = 0x1 << 6;
+
+pub const CR2_LBDL_POS: u32 = 5;
+pub const CR2_LBDL_WIDTH: u32 = 1;
+pub const CR2_LBDL_MASK: u32 = 0x1 << 5;
+
+pub const CR2_ADDM7_POS: u32 = 4;
+pub const CR2_ADDM7_WIDTH: u32 = 1;
+pub const CR2_ADDM7_MASK: u32 = 0x1 << 4;
+
+pub const CR2_DIS_NSS_POS: u32 = 3;
+pub const CR2_DIS_NSS_WIDTH: u32 = 1;
+pub const CR2_DIS_NSS_MASK: u32 = 0x1 << 3;
+
+pub const CR2_SLVEN_POS: u32 = 0;
+pub const CR2_SLVEN_WIDTH: u32 = 1;
+pub const CR2_SLVEN_MASK: u32 = 0x1 << 0;
+
+// CR3 register fields
+pub const CR3_TXFTCFG_POS: u32 = 29;
+pub const CR3_TXFTCFG_WIDTH: u32 = 3;
+pub const CR3_TXFTCFG_MASK: u32 = 0x7 << 29;
+
+pub const CR3_RXFTIE_POS: u32 = 28;
+pub const CR3_RXFTIE_WIDTH: u32 = 1;
+pub const CR3_RXFTIE_MASK: u32 = 0x1 << 28;
+
+pub const CR3_RXFTCFG_POS: u32 = 25;
+pub const CR3_RXFTCFG_WIDTH: u32 = 3;
+pub const CR3_RXFTCFG_MASK: u32 = 0x7 << 25;
+
+pub const CR3_TCBGTIE_POS: u32 = 24;
+pub const CR3_TCBGTIE_WIDTH: u32 = 1;
+pub const CR3_TCBGTIE_MASK: u32 = 0x1 << 24;
+
+pub const CR3_TXFTIE_POS: u32 = 23;
+pub const CR3_TXFTIE_WIDTH: u32 = 1;
+pub const CR3_TXFTIE_MASK: u32 = 0x1 << 23;
+
+pub const CR3_WUFIE_POS: u32 = 22;
+pub const CR3_WUFIE_WIDTH: u32 = 1;
+pub const CR3_WUFIE_MASK: u32 = 0x1 << 22;
+
+pub const CR3_WUS_POS: u32 = 20;
+pub const CR3_WUS_WIDTH: u32 = 2;
+pub const CR3_WUS_MASK: u32 = 0x3 << 20;
+
+pub const CR3_SCARCNT_POS: u32 = 17;
+pub const CR3_SCARCNT_WIDTH: u32 = 3;
+pub const CR3_SCARCNT_MASK: u32 = 0x7 << 17;
+
+pub const CR3_DEP_POS: u32 = 15;
+pub const CR3_DEP_WIDTH: u32 = 1;
+pub const CR3_DEP_MASK: u32 = 0x1 << 15;
+
+pub const CR3_DEM_POS: u32 = 14;
+pub const CR3_DEM_WIDTH: u32 = 1;
+pub const CR3_DEM_MASK: u32 = 0x1 << 14;
+
+pub const CR3_DDRE_POS: u32 = 13;
+pub const CR3_DDRE_WIDTH: u32 = 1;
+pub const CR3_DDRE_MASK: u32 = 0x1 << 13;
+
+pub const CR3_OVRDIS_POS: u32 = 12;
+pub const CR3_OVRDIS_WIDTH: u32 = 1;
+pub const CR3_OVRDIS_MASK: u32 = 0x1 << 12;
+
+pub const CR3_ONEBIT_POS: u32 = 11;
+pub const CR3_ONEBIT_WIDTH: u32 = 1;
+pub const CR3_ONEBIT_MASK: u32 = 0x1 << 11;
+
+pub const CR3_CTSIE_POS: u32 = 10;
+pub const CR3_CTSIE_WIDTH: u32 = 1;
+pub const CR3_CTSIE_MASK: u32 = 0x1 << 10;
+
+pub const CR3_CTSE_POS: u32 = 9;
+pub const CR3_CTSE_WIDTH: u32 = 1;
+pub const CR3_CTSE_MASK: u32 = 0x1 << 9;
+
+pub const CR3_RTSE_POS: u32 = 8;
+pub const CR3_RTSE_WIDTH: u32 = 1;
+pub const CR3_RTSE_MASK: u32 = 0x1 << 8;
+
+pub const CR3_DMAT_POS: u32 = 7;
+pub const CR3_DMAT_WIDTH: u32 = 1;
+pub const CR3_DMAT_MASK: u32 = 0x1 << 7;
+
+pub const CR3_DMAR_POS: u32 = 6;
+pub const CR3_DMAR_WIDTH: u32 = 1;
+pub const CR3_DMAR_MASK: u32 = 0x1 << 6;
+
+pub const CR3_SCEN_POS: u32 = 5;
+pub const CR3_SCEN_WIDTH: u32 = 1;
+pub const CR3_SCEN_MASK: u32 = 0x1 << 5;
+
+pub const CR3_NACK_POS: u32 = 4;
+pub const CR3_NACK_WIDTH: u32 = 1;
+pub const CR3_NACK_MASK: u32 = 0x1 << 4;
+
+pub const CR3_HDSEL_POS: u32 = 3;
+pub const CR3_HDSEL_WIDTH: u32 = 1;
+pub const CR3_HDSEL_MASK: u32 = 0x1 << 3;
+
+pub const CR3_IRLP_POS: u32 = 2;
+pub const CR3_IRLP_WIDTH: u32 = 1;
+pub const CR3_IRLP_MASK: u32 = 0x1 << 2;
+
+pub const CR3_IREN_POS: u32 = 1;
+pub const CR3_IREN_WIDTH: u32 = 1;
+pub const CR3_IREN_MASK: u32 = 0x1 << 1;
+
+pub const CR3_EIE_POS: u32 = 0;
+pub const CR3_EIE_WIDTH: u32 = 1;
+pub const CR3_EIE_MASK: u32 = 0x1 << 0;
+
+// BRR register fields
+pub const BRR_BRR_POS: u32 = 0;
+pub const BRR_BRR_WIDTH: u32 = 16;
+pub const BRR_BRR_MASK: u32 = 0xFFFF << 0;
+
+// GTPR register fields
+pub const GTPR_GT_POS: u32 = 8;
+pub const GTPR_GT_WIDTH: u32 = 8;
+pub const GTPR_GT_MASK: u32 = 0xFF << 8;
+
+pub const GTPR_PSC_POS: u32 = 0;
+pub const GTPR_PSC_WIDTH: u32 = 8;
+pub const GTPR_PSC_MASK: u32 = 0xFF << 0;
+
+// RTOR register fields
+pub const RTOR_BLEN_POS: u32 = 24;
+pub const RTOR_BLEN_WIDTH: u32 = 8;
+pub const RTOR_BLEN_MASK: u32 = 0xFF << 24;
+
+pub const RTOR_RTO_POS: u32 = 0;
+pub const RTOR_RTO_WIDTH: u32 = 24;
+pub const RTOR_RTO_MASK: u32 = 0xFFFFFF << 0;
+
+// RQR register fields
+pub const RQR_TXFRQ_POS: u32 = 4;
+pub const RQR_TXFRQ_WIDTH: u32 = 1;
+pub const RQR_TXFRQ_MASK: u32 = 0x1 << 4;
+
+pub const RQR_RXFRQ_POS: u32 = 3;
+pub const RQR_RXFRQ_WIDTH: u32 = 1;
+pub const RQR_RXFRQ_MASK: u32 = 0x1 << 3;
+
+pub const RQR_MMRQ_POS: u32 = 2;
+pub const RQR_MMRQ_WIDTH: u32 = 1;
+pub const RQR_MMRQ_MASK: u32 = 0x1 << 2;
+
+pub const RQR_SBKRQ_POS: u32 = 1;
+pub const RQR_SBKRQ_WIDTH: u32 = 1;
+pub const RQR_SBKRQ_MASK: u32 = 0x1 << 1;
+
+pub const RQR_ABRRQ_POS: u32 = 0;
+pub const RQR_ABRRQ_WIDTH: u32 = 1;
+pub const RQR_ABRRQ_MASK: u32 = 0x1 << 0;
+
+// ISR register fields
+pub const ISR_TXFT_POS: u32 = 27;
+pub const ISR_TXFT_WIDTH: u32 = 1;
+pub const ISR_TXFT_MASK: u32 = 0x1 << 27;
+
+pub const ISR_RXFT_POS: u32 = 26;
+pub const ISR_RXFT_WIDTH: u32 = 1;
+pub const ISR_RXFT_MASK: u32 = 0x1 << 26;
+
+pub const ISR_TCBGT_POS: u32 = 25;
+pub const ISR_TCBGT_WIDTH: u32 = 1;
+pub const ISR_TCBGT_MASK: u32 = 0x1 << 25;
+
+pub const ISR_RXFF_POS: u32 = 24;
+pub const ISR_RXFF_WIDTH: u32 = 1;
+pub const ISR_RXFF_MASK: u32 = 0x1 << 24;
+
+pub const ISR_TXFE_POS: u32 = 23;
+pub const ISR_TXFE_WIDTH: u32 = 1;
+pub const ISR_TXFE_MASK: u32 = 0x1 << 23;
+
+pub const ISR_REACK_POS: u32 = 22;
+pub const ISR_REACK_WIDTH: u32 = 1;
+pub const ISR_REACK_MASK: u32 = 0x1 << 22;
+
+pub const ISR_TEACK_POS: u32 = 21;
+pub const ISR_TEACK_WIDTH: u32 = 1;
+pub const ISR_TEACK_MASK: u32 = 0x1 << 21;
+
+pub const ISR_WUF_POS: u32 = 20;
+pub const ISR_WUF_WIDTH: u32 = 1;
+pub const ISR_WUF_MASK: u32 = 0x1 << 20;
+
+pub const ISR_RWU_POS: u32 = 19;
+pub const ISR_RWU_WIDTH: u32 = 1;
+pub const ISR_RWU_MASK: u32 = 0x1 << 19;
+
+pub const ISR_SBKF_POS: u32 = 18;
+pub const ISR_SBKF_WIDTH: u32 = 1;
+pub const ISR_SBKF_MASK: u32 = 0x1 << 18;
+
+pub const ISR_CMF_POS: u32 = 17;
+pub const ISR_CMF_WIDTH: u32 = 1;
+pub const ISR_CMF_MASK: u32 = 0x1 << 17;
+
+pub const ISR_BUSY_POS: u32 = 16;
+pub const ISR_BUSY_WIDTH: u32 = 1;
+pub const ISR_BUSY_MASK: u32 = 0x1 << 16;
+
+pub const ISR_ABRF_POS: u32 = 15;
+pub const ISR_ABRF_WIDTH: u32 = 1;
+pub const ISR_ABRF_MASK: u32 = 0x1 << 15;
+
+pub const ISR_ABRE_POS: u32 = 14;
+pub const ISR_ABRE_WIDTH: u32 = 1;
+pub const ISR_ABRE_MASK: u32 = 0x1 << 14;
+
+pub const ISR_UDR_POS: u32 = 13;
+pub const ISR_UDR_WIDTH: u32 = 1;
+pub const ISR_UDR_MASK: u32 = 0x1 << 13;
+
+pub const ISR_EOBF_POS: u32 = 12;
+pub const ISR_EOBF_WIDTH: u32 = 1;
+pub const ISR_EOBF_MASK: u32 = 0x1 << 12;
+
+pub const ISR_RTOF_POS: u32 = 11;
+pub const ISR_RTOF_WIDTH: u32 = 1;
+pub const ISR_RTOF_MASK: u32 = 0x1 << 11;
+
+pub const ISR_CTS_POS: u32 = 10;
+pub const ISR_CTS_WIDTH: u32 = 1;
+pub const ISR_CTS_MASK: u32 = 0x1 << 10;
+
+pub const ISR_CTSIF_POS: u32 = 9;
+pub const ISR_CTSIF_WIDTH: u32 = 1;
+pub const ISR_CTSIF_MASK: u32 = 0x1 << 9;
+
+pub const ISR_LBDF_POS: u32 = 8;
+pub const ISR_LBDF_WIDTH: u32 = 1;
+pub const ISR_LBDF_MASK: u32 = 0x1 << 8;
+
+pub const ISR_TXE_POS: u32 = 7;
+pub const ISR_TXE_WIDTH: u32 = 1;
+pub const ISR_TXE_MASK: u32 = 0x1 << 7;
+
+pub const ISR_TC_POS: u32 = 6;
+pub const ISR_TC_WIDTH: u32 = 1;
+pub const ISR_TC_MASK: u32 = 0x1 << 6;
+
+pub const ISR_RXNE_POS: u32 = 5;
+pub const ISR_RXNE_WIDTH: u32 = 1;
+pub const ISR_RXNE_MASK: u32 = 0x1 << 5;
+
+pub const ISR_IDLE_POS: u32 = 4;
+pub const ISR_IDLE_WIDTH: u32 = 1;
+pub const ISR_IDLE_MASK: u32 = 0x1 << 4;
+
+pub const ISR_ORE_POS: u32 = 3;
+pub const ISR_ORE_WIDTH: u32 = 1;
+pub const ISR_ORE_MASK: u32 = 0x1 << 3;
+
+pub const ISR_NE_POS: u32 = 2;
+pub const ISR_NE_WIDTH: u32 = 1;
+pub const ISR_NE_MASK: u32 = 0x1 << 2;
+
+pub const ISR_FE_POS: u32 = 1;
+pub const ISR_FE_WIDTH: u32 = 1;
+pub const ISR_FE_MASK: u32 = 0x1 << 1;
+
+pub const ISR_PE_POS: u32 = 0;
+pub const ISR_PE_WIDTH: u32 = 1;
+pub const ISR_PE_MASK: u32 = 0x1 << 0;
+
+// ICR register fields
+pub const ICR_WUCF_POS: u32 = 20;
+pub const ICR_WUCF_WIDTH: u32 = 1;
+pub const ICR_WUCF_MASK: u32 = 0x1 << 20;
+
+pub const ICR_CMCF_POS: u32 = 17;
+pub const ICR_CMCF_WIDTH: u32 = 1;
+pub const ICR_CMCF_MASK: u32 = 0x1 << 17;
+
+pub const ICR_UDRCF_POS: u32 = 13;
+pub const ICR_UDRCF_WIDTH: u32 = 1;
+pub const ICR_UDRCF_MASK: u32 = 0x1 << 13;
+
+pub const ICR_EOBCF_POS: u32 = 12;
+pub const ICR_EOBCF_WIDTH: u32 = 1;
+pub const ICR_EOBCF_MASK: u32 = 0x1 << 12;
+
+pub const ICR_RTOCF_POS: u32 = 11;
+pub const ICR_RTOCF_WIDTH: u32 = 1;
+pub const ICR_RTOCF_MASK: u32 = 0x1 << 11;
+
+pub const ICR_CTSCF_POS: u32 = 9;
+pub const ICR_CTSCF_WIDTH: u32 = 1;
+pub const ICR_CTSCF_MASK: u32 = 0x1 << 9;
+
+pub const ICR_LBDCF_POS: u32 = 8;
+pub const ICR_LBDCF_WIDTH: u32 = 1;
+pub const ICR_LBDCF_MASK: u32 = 0x1 << 8;
+
+pub const ICR_TCBGTCF_POS: u32 = 7;
+pub const ICR_TCBGTCF_WIDTH: u32 = 1;
+pub const ICR_TCBGTCF_MASK: u32 = 0x1 << 7;
+
+pub const ICR_TCCF_POS: u32 = 6;
+pub const ICR_TCCF_WIDTH: u32 = 1;
+pub const ICR_TCCF_MASK: u32 = 0x1 << 6;
+
+pub const ICR_TXFECF_POS: u32 = 5;
+pub const ICR_TXFECF_WIDTH: u32 = 1;
+pub const ICR_TXFECF_MASK: u32 = 0x1 << 5;
+
+pub const ICR_IDLECF_POS: u32 = 4;
+pub const ICR_IDLECF_WIDTH: u32 = 1;
+pub const ICR_IDLECF_MASK: u32 = 0x1 << 4;
+
+pub const ICR_ORECF_POS: u32 = 3;
+pub const ICR_ORECF_WIDTH: u32 = 1;
+pub const ICR_ORECF_MASK: u32 = 0x1 << 3;
+
+pub const ICR_NECF_POS: u32 = 2;
+pub const ICR_NECF_WIDTH: u32 = 1;
+pub const ICR_NECF_MASK: u32 = 0x1 << 2;
+
+pub const ICR_FECF_POS: u32 = 1;
+pub const ICR_FECF_WIDTH: u32 = 1;
+pub const ICR_FECF_MASK: u32 = 0x1 << 1;
+
+pub const ICR_PECF_POS: u32 = 0;
+pub const ICR_PECF_WIDTH: u32 = 1;
+pub const ICR_PECF_MASK: u32 = 0x1 << 0;
+
+// RDR register fields
+pub const RDR_RDR_POS: u32 = 0;
+pub const RDR_RDR_WIDTH: u32 = 9;
+pub const RDR_RDR_MASK: u32 = 0x1FF << 0;
+
+// TDR register fields
+pub const TDR_TDR_POS: u32 = 0;
+pub const TDR_TDR_WIDTH: u32 = 9;
+pub const TDR_TDR_MASK: u32 = 0x1FF << 0;
+
+// PRESC register fields
+pub const PRESC_PRESCALER_POS: u32 = 0;
+pub const PRESC_PRESCALER_WIDTH: u32 = 4;
+pub const PRESC_PRESCALER_MASK: u32 = 0xF << 0;
+
+// USART peripheral instances
+pub struct USART1;
+pub struct USART2;
+
+impl PeripheralAccess for USART1 {
+    const BASE_ADDRESS: u32 = USART1_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
+
+impl PeripheralAccess for USART2 {
+    const BASE_ADDRESS: u32 = USART2_BASEADDR;
+    type RegisterBlock = RegisterBlock;
+}
@@ -0,0 +1,65 @@
+//! # Panic Handler
+//!
+//! Records the panic message, source location and SysTick count in RAM
+//! that is not initialized at startup, protected by a CRC, then resets the
+//! MCU with SYSRESETREQ. On the next boot `init()` recovers the record and
+//! `last_panic()` hands it to the application.
+use crate::arch::cpu::{nvic::nvic_system_reset, systick};
+use core::fmt::Write;
+use core::mem::MaybeUninit;
+use core::panic::PanicInfo;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+use fault::panic::{PanicRecord, PanicReport};
+
+/// Survives a reset: placed in `.uninit`, which the runtime does not zero
+#[unsafe(link_section = ".uninit.PANIC_RECORD")]
+static mut PANIC_RECORD: MaybeUninit<PanicRecord> = MaybeUninit::uninit();
+
+/// Record recovered from the previous run by `init()`
+static mut LAST_PANIC: PanicRecord = PanicRecord::empty();
+
+/// Set while the handler runs, a panic while formatting resets at once
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
+/// Recover the record of a panic from the previous run. Call once, early
+/// in `main`.
+pub fn init() {
+    unsafe {
+        let record = ptr::read_volatile(ptr::addr_of!(PANIC_RECORD)).assume_init();
+        if record.report().is_some() {
+            LAST_PANIC = record;
+        }
+        ptr::write_volatile(
+            ptr::addr_of_mut!(PANIC_RECORD),
+            MaybeUninit::new(PanicRecord::empty()),
+        );
+    }
+}
+
+/// The panic that caused the last reset, if any
+pub fn last_panic() -> Option<PanicReport<'static>> {
+    unsafe { (*ptr::addr_of!(LAST_PANIC)).report() }
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    cortex_m::interrupt::disable();
+
+    // ARMv6-M has no atomic read-modify-write; interrupts are masked, so a
+    // plain load/store pair cannot race.
+    if !PANICKING.load(Ordering::Relaxed) {
+        PANICKING.store(true, Ordering::Relaxed);
+        let (file, line, column) = info
+            .location()
+            .map_or(("", 0, 0), |l| (l.file(), l.line(), l.column()));
+        let mut record = PanicRecord::new(file, line, column, systick::get_ticks());
+        let _ = write!(record, "{}", info.message());
+        record.seal();
+        unsafe {
+            ptr::write_volatile(ptr::addr_of_mut!(PANIC_RECORD), MaybeUninit::new(record));
+        }
+    }
+
+    nvic_system_reset()
+}
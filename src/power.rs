@@ -0,0 +1,190 @@
+//! # Power
+//!
+//! Low-power idle for the app scheduler. While no app is due, the core
+//! enters the deepest mode the policy allows for the time until the next
+//! scheduled work: the next app release, software timer or async timer.
+//!
+//! Sleep is the default. An app that can live with its peripherals stopped
+//! allows Stop, and takes a lock while it needs them running:
+//!
+//! ```ignore
+//! power::enable_rtc(ClockSource::Lse)?;
+//! power::wake_on_pin(button, EventTrigger::FallingEdge, BUTTON_EVENT)?;
+//! power::set_deepest(PowerMode::Stop);
+//!
+//! power::lock(PowerMode::Stop)?; // USART transfer in progress
+//! power::unlock(PowerMode::Stop)?;
+//! ```
+//!
+//! Stop with scheduled work needs the RTC, which wakes the core in time and
+//! measures how long it slept to bring the tick count forward; without it
+//! Stop is only entered with nothing scheduled and the tick count stands
+//! still meanwhile. Standby is only entered with nothing scheduled and
+//! restarts the firmware, woken by the RTC or a WKUP pin; see
+//! `woke_from_standby()`.
+
+use crate::apps;
+use crate::arch::cpu::{nvic, systick};
+use crate::driver::gpio::{EventTrigger, Pin, decode_pin};
+use crate::driver::{exti, power, rtc};
+use crate::logging::{info, trace};
+use core::cell::RefCell;
+use cortex_m::interrupt::{self, Mutex};
+use sched::power::PowerPolicy;
+
+pub use crate::driver::power::woke_from_standby;
+pub use crate::driver::rtc::ClockSource;
+pub use sched::power::PowerMode;
+
+/// Maximum number of pins waking the core from Stop
+pub const MAX_WAKEUP_PINS: usize = 4;
+
+static POLICY: Mutex<RefCell<PowerPolicy>> = Mutex::new(RefCell::new(PowerPolicy::new()));
+
+/// EXTI lines of the wakeup pins, with the app events they signal
+static WAKEUP_PINS: Mutex<RefCell<[Option<(u8, u32)>; MAX_WAKEUP_PINS]>> =
+    Mutex::new(RefCell::new([None; MAX_WAKEUP_PINS]));
+
+fn with_policy<R>(f: impl FnOnce(&mut PowerPolicy) -> R) -> R {
+    interrupt::free(|cs| f(&mut POLICY.borrow(cs).borrow_mut()))
+}
+
+/// Allow low-power modes down to `mode` while idle
+pub fn set_deepest(mode: PowerMode) {
+    with_policy(|policy| policy.set_deepest(mode));
+}
+
+/// Keep the core out of `mode` and deeper modes until `unlock(mode)`.
+/// Locks nest. Returns Err(-1) for Sleep.
+pub fn lock(mode: PowerMode) -> Result<(), i32> {
+    with_policy(|policy| policy.lock(mode))
+}
+
+/// Release a `lock(mode)`. Returns Err(-1) if `mode` is not locked.
+pub fn unlock(mode: PowerMode) -> Result<(), i32> {
+    with_policy(|policy| policy.unlock(mode))
+}
+
+/// Shortest idle time, in ticks, worth entering Stop for
+pub fn set_min_stop_ticks(ticks: u32) {
+    with_policy(|policy| policy.set_min_stop_ticks(ticks));
+}
+
+/// Start the RTC on `source` to wake the core from Stop in time for
+/// scheduled work. Returns Err(-2) if the LSE crystal does not start.
+pub fn enable_rtc(source: ClockSource) -> Result<(), i32> {
+    rtc::init(source)?;
+    with_policy(|policy| policy.set_timed_wakeup(true));
+    Ok(())
+}
+
+/// Wake the core from Sleep and Stop on `trigger` of `pin` and signal
+/// `event` to the apps. The pin must be configured as an input. Returns
+/// Err(-1) for an invalid pin, a trigger of `None` or once
+/// `MAX_WAKEUP_PINS` pins are registered.
+pub fn wake_on_pin(pin: Pin, trigger: EventTrigger, event: u32) -> Result<(), i32> {
+    if trigger == EventTrigger::None {
+        return Err(-1);
+    }
+    let (_, line) = decode_pin(pin)?;
+    interrupt::free(|cs| {
+        let mut pins = WAKEUP_PINS.borrow(cs).borrow_mut();
+        let slot = pins
+            .iter()
+            .position(|p| p.is_some_and(|(l, _)| l == line))
+            .or_else(|| pins.iter().position(Option::is_none))
+            .ok_or(-1)?;
+        exti::configure_pin(pin, trigger)?;
+        pins[slot] = Some((line, event));
+        Ok(())
+    })
+}
+
+/// Let a rising edge on WKUP pin `pin` wake the MCU from Standby (the
+/// numbering of the datasheet, WKUP1 is PA0). Returns Err(-1) for a pin the
+/// MCU does not have.
+pub fn enable_wakeup_pin(pin: u8, enable: bool) -> Result<(), i32> {
+    power::enable_wakeup_pin(pin, enable)
+}
+
+/// Signal the events of the wakeup pins that fired and clear them.
+/// Returns true if any did.
+fn take_pin_events() -> bool {
+    interrupt::free(|cs| {
+        let mut fired = false;
+        for (line, event) in WAKEUP_PINS.borrow(cs).borrow().iter().flatten() {
+            if exti::is_pending(*line) {
+                exti::clear_pending(*line);
+                apps::signal_event(*event);
+                fired = true;
+            }
+        }
+        fired
+    })
+}
+
+/// Unmask the interrupts of the wakeup pins, which `DefaultHandler` masks
+/// again once they fire, so they wake the core.
+fn arm_pins() {
+    interrupt::free(|cs| {
+        for (line, _) in WAKEUP_PINS.borrow(cs).borrow().iter().flatten() {
+            if let Some(irqn) = exti::irqn(*line) {
+                nvic::nvic_enable_irq(irqn);
+            }
+        }
+    });
+}
+
+/// Ticks until the next scheduled work, `None` if nothing is scheduled
+fn next_work(apps_idle: Option<u32>) -> Option<u32> {
+    [
+        apps_idle,
+        crate::clock::idle_ticks(),
+        crate::runtime::timer::idle_ticks(),
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+}
+
+/// Stop until the next interrupt, at most until `idle` ticks from now when
+/// the RTC runs, and bring the tick count forward by the time spent.
+fn stop(idle: Option<u32>, timed: bool) {
+    if !timed {
+        return power::enter_stop();
+    }
+    if let Some(ticks) = idle {
+        // 1 tick = 1 ms
+        if rtc::start_wakeup(ticks).is_err() {
+            return power::enter_sleep();
+        }
+    }
+    let before = rtc::timestamp();
+    power::enter_stop();
+    let elapsed = rtc::elapsed_ms(before, rtc::timestamp());
+    rtc::stop_wakeup();
+    systick::advance_ticks(elapsed);
+    trace!("woke from stop after {} ms", elapsed);
+}
+
+/// Idle in the deepest allowed mode until the next interrupt or scheduled
+/// work. `apps_idle` are the ticks until the next app is due, `None` if
+/// none is scheduled. Called by the scheduler with interrupts masked.
+pub fn idle(apps_idle: Option<u32>) {
+    // A pin that fired while the core was awake has work for the apps
+    if take_pin_events() {
+        return;
+    }
+    let idle = next_work(apps_idle);
+    let (mode, timed) = with_policy(|policy| (policy.choose(idle), policy.timed_wakeup()));
+    arm_pins();
+    match mode {
+        PowerMode::Sleep => power::enter_sleep(),
+        PowerMode::Stop => stop(idle, timed),
+        PowerMode::Standby => {
+            info!("entering standby");
+            power::enter_standby()
+        }
+    }
+    take_pin_events();
+}
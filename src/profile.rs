@@ -0,0 +1,222 @@
+//! # Profile
+//!
+//! Lightweight profiling with named spans timed on the cycle counter. A
+//! span is entered when created and left when dropped; both are emitted as
+//! small binary records (see `trace::record`) to ITM stimulus port
+//! `ITM_PORT` over SWO, or to the RTT "Profile" channel:
+//!
+//! ```ignore
+//! profile::init(Sink::Itm { swo_hz: 2_000_000 }, cpu_hz)?;
+//!
+//! let _span = profile::span("usart.write");
+//! usart.write(&buf)?;
+//! // the span ends when `_span` is dropped
+//! ```
+//!
+//! The scheduler wraps every `App::loop_step` in a span named after the
+//! app. On the host, `trace-decode` of `src/components/trace` turns a
+//! capture into span durations and statistics per name. Until `init()`
+//! spans cost one flag check.
+//!
+//! The Cortex-M0+ of the STM32G0 has neither DWT nor ITM: timestamps are
+//! SysTick clock cycles and only RTT is available.
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use cortex_m::interrupt::{self, Mutex};
+use trace::names::Names;
+use trace::record::{MAX_RECORD_LEN, Record};
+
+/// Stimulus port of the records; port 0 is usually text output
+pub const ITM_PORT: usize = 1;
+
+/// Maximum number of span names
+pub const MAX_NAMES: usize = 32;
+
+/// Where the records go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sink {
+    /// ITM over the SWO pin, as NRZ at `swo_hz`
+    Itm { swo_hz: u32 },
+    /// RTT up channel 1, "Profile", read by the debug probe (see `rtt`)
+    Rtt,
+}
+
+enum Output {
+    Itm,
+    #[cfg(any(feature = "log", feature = "defmt"))]
+    Rtt(rtt_target::UpChannel),
+}
+
+struct Profiler {
+    names: Names<MAX_NAMES>,
+    output: Option<Output>,
+    hz: u32,
+}
+
+impl Profiler {
+    fn emit(&mut self, record: Record) {
+        let mut buf = [0; MAX_RECORD_LEN];
+        let bytes = record.encode(&mut buf);
+        match &mut self.output {
+            Some(Output::Itm) => write_itm(bytes),
+            #[cfg(any(feature = "log", feature = "defmt"))]
+            Some(Output::Rtt(channel)) => {
+                // Records that do not fit are dropped rather than waited on
+                channel.write(bytes);
+            }
+            _ => {}
+        }
+    }
+}
+
+static PROFILER: Mutex<RefCell<Profiler>> = Mutex::new(RefCell::new(Profiler {
+    names: Names::new(),
+    output: None,
+    hz: 0,
+}));
+
+/// Set once a sink is ready, checked before taking the critical section
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn with_profiler<R>(f: impl FnOnce(&mut Profiler) -> R) -> R {
+    interrupt::free(|cs| f(&mut PROFILER.borrow(cs).borrow_mut()))
+}
+
+/// Core clock cycles, wrapping at 2^32
+#[cfg(not(feature = "stm32g030"))]
+pub fn cycles() -> u32 {
+    crate::arch::cpu::dwt::cycles()
+}
+
+/// SysTick clock cycles since boot, wrapping at 2^32
+#[cfg(feature = "stm32g030")]
+pub fn cycles() -> u32 {
+    let sample = interrupt::free(|_| crate::arch::cpu::systick::sample());
+    time::systick::cycles(sample) as u32
+}
+
+#[cfg(not(feature = "stm32g030"))]
+fn open_itm(cpu_hz: u32, swo_hz: u32) -> Result<Output, i32> {
+    use crate::mcu::device;
+    use core::ptr;
+
+    // DBGMCU_CR: TRACE_IOEN drives the trace pins, TRACE_MODE 0 is
+    // asynchronous (SWO only)
+    const DBGMCU_CR: u32 = device::DBGMCU_BASEADDR + 0x04;
+    const DBGMCU_CR_TRACE_IOEN: u32 = 1 << 5;
+    const DBGMCU_CR_TRACE_MODE_MASK: u32 = 0x3 << 6;
+    unsafe {
+        let cr = DBGMCU_CR as *mut u32;
+        let value = ptr::read_volatile(cr) & !DBGMCU_CR_TRACE_MODE_MASK;
+        ptr::write_volatile(cr, value | DBGMCU_CR_TRACE_IOEN);
+    }
+    crate::arch::cpu::itm::init(cpu_hz, swo_hz, 1 << ITM_PORT)?;
+    Ok(Output::Itm)
+}
+
+#[cfg(not(feature = "stm32g030"))]
+fn write_itm(bytes: &[u8]) {
+    crate::arch::cpu::itm::write_bytes(ITM_PORT, bytes);
+}
+
+#[cfg(feature = "stm32g030")]
+fn open_itm(_cpu_hz: u32, _swo_hz: u32) -> Result<Output, i32> {
+    Err(-2)
+}
+
+#[cfg(feature = "stm32g030")]
+fn write_itm(_bytes: &[u8]) {}
+
+#[cfg(any(feature = "log", feature = "defmt"))]
+fn open_rtt() -> Result<Output, i32> {
+    crate::rtt::take_profile().map(Output::Rtt).ok_or(-2)
+}
+
+#[cfg(not(any(feature = "log", feature = "defmt")))]
+fn open_rtt() -> Result<Output, i32> {
+    Err(-2)
+}
+
+/// Start the cycle counter and emit records to `sink`, with a core clock
+/// of `cpu_hz`. Returns Err(-2) if the sink is not available on this MCU
+/// or build (RTT needs the `log` or `defmt` feature) or the RTT channel
+/// was taken by an earlier call, Err(-1) for an SWO rate the core clock
+/// cannot produce.
+pub fn init(sink: Sink, cpu_hz: u32) -> Result<(), i32> {
+    #[cfg(not(feature = "stm32g030"))]
+    crate::arch::cpu::dwt::enable_cycle_counter()?;
+
+    ENABLED.store(false, Ordering::Relaxed);
+    let output = match sink {
+        Sink::Itm { swo_hz } => open_itm(cpu_hz, swo_hz)?,
+        Sink::Rtt => open_rtt()?,
+    };
+    with_profiler(|profiler| {
+        profiler.output = Some(output);
+        profiler.hz = cpu_hz;
+        profiler.emit(Record::Clock { hz: cpu_hz });
+    });
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Stop emitting records; spans then cost one flag check again
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Emit the clock and the known span names again, for a reader that
+/// attached after they were first sent
+pub fn announce() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    with_profiler(|profiler| {
+        profiler.emit(Record::Clock { hz: profiler.hz });
+        let names = profiler.names.clone();
+        for (id, name) in names.iter() {
+            profiler.emit(Record::Name { id, name });
+        }
+    });
+}
+
+/// A span being timed, left when dropped
+#[must_use = "the span ends when dropped"]
+pub struct Span {
+    id: Option<u8>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            with_profiler(|profiler| {
+                profiler.emit(Record::Exit {
+                    id,
+                    cycles: cycles(),
+                })
+            });
+        }
+    }
+}
+
+/// Enter a span named `name`. Spans of different names may nest and
+/// interleave, e.g. with spans in interrupt handlers. Beyond `MAX_NAMES`
+/// names new spans are not recorded.
+pub fn span(name: &'static str) -> Span {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Span { id: None };
+    }
+    let id = with_profiler(|profiler| {
+        let (id, new) = profiler.names.id(name)?;
+        if new {
+            profiler.emit(Record::Name { id, name });
+        }
+        profiler.emit(Record::Enter {
+            id,
+            cycles: cycles(),
+        });
+        Some(id)
+    });
+    Span { id }
+}
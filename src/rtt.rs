@@ -0,0 +1,46 @@
+//! # RTT
+//!
+//! The RTT control block the debug probe reads, set up once on first use
+//! and shared: up channel 0 "Terminal" carries the log output of
+//! `logging`, channel 1 "Profile" the records of `profile`. Each channel is
+//! handed out once.
+
+use core::cell::RefCell;
+use cortex_m::interrupt::{self, Mutex};
+use rtt_target::UpChannel;
+
+struct Channels {
+    terminal: Option<UpChannel>,
+    profile: Option<UpChannel>,
+}
+
+static CHANNELS: Mutex<RefCell<Option<Channels>>> = Mutex::new(RefCell::new(None));
+
+fn take(f: impl FnOnce(&mut Channels) -> Option<UpChannel>) -> Option<UpChannel> {
+    interrupt::free(|cs| {
+        let mut channels = CHANNELS.borrow(cs).borrow_mut();
+        let channels = channels.get_or_insert_with(|| {
+            let channels = rtt_target::rtt_init! {
+                up: {
+                    0: { size: 1024, name: "Terminal" }
+                    1: { size: 1024, name: "Profile" }
+                }
+            };
+            Channels {
+                terminal: Some(channels.up.0),
+                profile: Some(channels.up.1),
+            }
+        });
+        f(channels)
+    })
+}
+
+/// Up channel 0, "Terminal", or None if already taken
+pub fn take_terminal() -> Option<UpChannel> {
+    take(|channels| channels.terminal.take())
+}
+
+/// Up channel 1, "Profile", or None if already taken
+pub fn take_profile() -> Option<UpChannel> {
+    take(|channels| channels.profile.take())
+}
@@ -0,0 +1,69 @@
+//! Wakes tasks waiting on peripheral interrupts.
+//!
+//! There is no per-peripheral vector table; every device interrupt lands in
+//! `DefaultHandler`, which masks the line in the NVIC and wakes the task
+//! waiting on it. The waiting task unmasks it again on its next wait.
+
+use crate::arch::cpu::nvic;
+use core::cell::RefCell;
+use core::future::poll_fn;
+use core::task::Poll;
+use cortex_m::interrupt::{self, Mutex};
+use cortex_m_rt::exception;
+use executor::waker::WakerTable;
+
+/// Maximum number of interrupt lines waited on at the same time
+const MAX_WAITERS: usize = 8;
+
+static WAITERS: Mutex<RefCell<WakerTable<MAX_WAITERS>>> =
+    Mutex::new(RefCell::new(WakerTable::new()));
+
+#[exception]
+unsafe fn DefaultHandler(irqn: i16) {
+    if irqn < 0 {
+        panic!("unhandled exception {}", irqn);
+    }
+    // The peripheral flag stays set until the task handles it
+    nvic::nvic_disable_irq(irqn as u32);
+    interrupt::free(|cs| WAITERS.borrow(cs).borrow_mut().wake(irqn as u32));
+}
+
+/// Wait until `ready()` holds, sleeping on interrupt `irqn` in between.
+/// `enable(true)` must enable the peripheral interrupt that signals
+/// readiness; `enable(false)` is called once ready.
+pub async fn wait_for(irqn: u32, ready: impl FnMut() -> bool, enable: impl FnMut(bool)) {
+    wait_for_any(&[irqn], ready, enable).await
+}
+
+/// Like `wait_for`, for a peripheral that signals on several interrupt
+/// lines, such as the separate event and error lines of the I2C v1 IP.
+pub async fn wait_for_any(
+    irqns: &[u32],
+    mut ready: impl FnMut() -> bool,
+    mut enable: impl FnMut(bool),
+) {
+    poll_fn(|cx| {
+        if ready() {
+            enable(false);
+            return Poll::Ready(());
+        }
+        let registered = interrupt::free(|cs| {
+            let mut waiters = WAITERS.borrow(cs).borrow_mut();
+            irqns
+                .iter()
+                .all(|&irqn| waiters.register(irqn, cx.waker()).is_ok())
+        });
+        if registered {
+            // Readiness arriving after the check fires right after this
+            enable(true);
+            for &irqn in irqns {
+                nvic::nvic_enable_irq(irqn);
+            }
+        } else {
+            // No free slot, poll again instead
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
+    })
+    .await
+}
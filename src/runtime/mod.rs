@@ -0,0 +1,56 @@
+//! # Async Runtime
+//!
+//! Runs `async` tasks next to the registered apps: the executor is one app
+//! of the cooperative scheduler, woken through an app event whenever a task
+//! is. Tasks wait on peripheral interrupts (`irq::wait_for`, used by the
+//! async driver traits) or on SysTick (`Timer`). Futures are kept in a
+//! static arena, no heap is used.
+//!
+//! ```ignore
+//! async fn heartbeat() {
+//!     loop {
+//!         timer::Timer::after(500).await;
+//!     }
+//! }
+//!
+//! runtime::spawn(heartbeat()).unwrap();
+//! ```
+
+pub mod irq;
+pub mod timer;
+
+use crate::apps;
+use core::future::Future;
+use core::pin::Pin;
+use executor::app::ExecutorApp;
+use executor::arena::Arena;
+use executor::executor::{Executor, TaskId};
+use sched::scheduler::TaskConfig;
+
+/// Maximum number of async tasks
+pub const MAX_TASKS: usize = 8;
+
+/// Bytes of static storage for task futures
+pub const ARENA_SIZE: usize = 1024;
+
+/// App event the executor app waits on
+pub const ASYNC_EVENT: u32 = 1 << 31;
+
+fn notify() {
+    apps::signal_event(ASYNC_EVENT);
+}
+
+static EXECUTOR: Executor<MAX_TASKS> = Executor::new(notify);
+static ARENA: Arena<ARENA_SIZE> = Arena::new();
+
+/// Spawn an async task. Call from thread mode only. Returns Err(-1) if all
+/// task slots are in use or the arena has no room for the future.
+pub fn spawn<F: Future<Output = ()> + 'static>(future: F) -> Result<TaskId, i32> {
+    let future = ARENA.alloc(future).map_err(|_| -1)?;
+    EXECUTOR.spawn(Pin::static_mut(future))
+}
+
+/// The app running the async tasks, to register with the app registry
+pub fn app() -> ExecutorApp<MAX_TASKS> {
+    ExecutorApp::new(&EXECUTOR, ASYNC_EVENT, TaskConfig::default())
+}
@@ -0,0 +1,75 @@
+//! Async delays on the SysTick tick.
+
+use crate::arch::cpu::systick;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use cortex_m::interrupt::{self, Mutex};
+use executor::timer::TimerQueue;
+use sched::tick::{reached, ticks_until};
+
+/// Maximum number of timers pending at the same time
+const MAX_TIMERS: usize = 16;
+
+static TIMERS: Mutex<RefCell<TimerQueue<MAX_TIMERS>>> = Mutex::new(RefCell::new(TimerQueue::new()));
+
+/// Future completing once a tick is reached
+pub struct Timer {
+    deadline: u32,
+}
+
+impl Timer {
+    /// Complete `ticks` ticks (ms) from now
+    pub fn after(ticks: u32) -> Self {
+        Self::at(systick::get_ticks().wrapping_add(ticks))
+    }
+
+    /// Complete at tick `deadline`
+    pub fn at(deadline: u32) -> Self {
+        Self { deadline }
+    }
+}
+
+impl Future for Timer {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // Checked and queued in one critical section, so the tick reaching
+        // the deadline in between cannot be missed
+        let queued = interrupt::free(|cs| {
+            if reached(systick::get_ticks(), self.deadline) {
+                return None;
+            }
+            Some(
+                TIMERS
+                    .borrow(cs)
+                    .borrow_mut()
+                    .schedule(self.deadline, cx.waker()),
+            )
+        });
+        match queued {
+            None => Poll::Ready(()),
+            Some(result) => {
+                if result.is_err() {
+                    // Queue full, poll again instead
+                    cx.waker().wake_by_ref();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Ticks until the next timer completes, `None` if none is pending
+pub fn idle_ticks() -> Option<u32> {
+    let now = systick::get_ticks();
+    interrupt::free(|cs| TIMERS.borrow(cs).borrow().next_deadline(now))
+        .map(|deadline| ticks_until(now, deadline))
+}
+
+/// Wake the timers that expired. Called from the SysTick handler.
+pub fn on_tick() {
+    let now = systick::get_ticks();
+    interrupt::free(|cs| TIMERS.borrow(cs).borrow_mut().expire(now));
+}
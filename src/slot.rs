@@ -0,0 +1,51 @@
+//! # Boot Slot
+//!
+//! The application side of the bootloader in `bootloader/`, for firmware
+//! built with the `slot-a` or `slot-b` feature and packed with `imgtool`.
+//!
+//! An image the bootloader has just installed runs on trial: each reset
+//! before `confirm()` counts as a failed boot, and after
+//! `boot::state::MAX_ATTEMPTS` of them the bootloader goes back to the
+//! previous image. Confirm once the image has shown it works, e.g. after its self-test or
+//! once it has reached the host. There is no watchdog in the loop, so an
+//! image that hangs on trial is only rolled back when the board is reset.
+
+use crate::arch::cpu::mpu;
+use crate::driver::flash::Flash;
+use crate::driver::flash::boot::{Adapter, Journal, LAYOUT, Record, Slot};
+use crate::driver::flash::v1::InternalFlash;
+
+/// Slot the firmware is linked for
+#[cfg(feature = "slot-a")]
+pub const SLOT: Slot = Slot::A;
+#[cfg(all(feature = "slot-b", not(feature = "slot-a")))]
+pub const SLOT: Slot = Slot::B;
+
+fn journal(flash: &mut InternalFlash) -> Result<Journal, i32> {
+    Journal::open(&mut Adapter(flash), LAYOUT.state)
+}
+
+/// Whether the running image is on trial, rolled back if the board resets
+/// before `confirm()`
+pub fn is_trial() -> Result<bool, i32> {
+    let mut flash = InternalFlash::new();
+    Ok(journal(&mut flash)?.state().trial == Some(SLOT))
+}
+
+/// Record that the running image works, so the bootloader keeps booting
+/// it; does nothing if it already is the confirmed image. Returns the
+/// error of the flash driver if the record cannot be read or written.
+pub fn confirm() -> Result<(), i32> {
+    let mut flash = InternalFlash::new();
+    let mut journal = journal(&mut flash)?;
+    let state = journal.state();
+    if state.active == Some(SLOT) && state.trial != Some(SLOT) {
+        return Ok(());
+    }
+    // The MPU keeps flash read-only
+    let result = mpu::with_region_lifted(::mpu::layout::FLASH_REGION, || {
+        journal.append(&mut Adapter(&mut flash), Record::Confirm(SLOT))
+    });
+    let _ = flash.uninitialize();
+    result
+}